@@ -0,0 +1,74 @@
+//! Detection and refresh latency benchmarks.
+//!
+//! The `GpuManager` benchmarks use mock providers through
+//! [`GpuManager::with_providers()`] instead of the platform providers, so
+//! they are deterministic and run without a GPU.
+//!
+//! Run with `cargo bench -p gpu_info`.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use gpu_info::gpu_info::{GpuInfo, GpuProvider, Result};
+use gpu_info::{GpuIndex, GpuManager, GpuProviderManager, Vendor};
+
+/// Provider returning fixed GPUs without touching the system
+struct MockProvider {
+    vendor: Vendor,
+    gpu: fn() -> GpuInfo,
+    count: usize,
+}
+
+impl GpuProvider for MockProvider {
+    fn detect_gpus(&self) -> Result<Vec<GpuInfo>> {
+        Ok((0..self.count).map(|_| (self.gpu)()).collect())
+    }
+
+    fn update_gpu(&self, gpu: &mut GpuInfo) -> Result<()> {
+        gpu.temperature = gpu.temperature.map(|celsius| celsius + 0.5);
+        gpu.utilization = gpu.utilization.map(|percent| (percent + 1.0) % 100.0);
+        Ok(())
+    }
+
+    fn get_vendor(&self) -> Vendor {
+        self.vendor
+    }
+}
+
+/// Two NVIDIA GPUs and one AMD GPU
+fn mock_providers() -> GpuProviderManager {
+    let mut providers = GpuProviderManager::new();
+    providers.register_provider(
+        Vendor::Nvidia,
+        MockProvider {
+            vendor: Vendor::Nvidia,
+            gpu: GpuInfo::mock_nvidia,
+            count: 2,
+        },
+    );
+    providers.register_provider(
+        Vendor::Amd,
+        MockProvider {
+            vendor: Vendor::Amd,
+            gpu: GpuInfo::mock_amd,
+            count: 1,
+        },
+    );
+    providers
+}
+
+fn mock_manager(c: &mut Criterion) {
+    let mut group = c.benchmark_group("mock_manager");
+    group.bench_function("detect", |b| {
+        b.iter(|| GpuManager::with_providers(black_box(mock_providers())))
+    });
+    let mut manager = GpuManager::with_providers(mock_providers());
+    group.bench_function("refresh_all_gpus", |b| {
+        b.iter(|| manager.refresh_all_gpus().unwrap())
+    });
+    group.bench_function("refresh_gpu", |b| {
+        b.iter(|| manager.refresh_gpu_at(black_box(GpuIndex(1))).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, mock_manager);
+criterion_main!(benches);
@@ -15,6 +15,8 @@
 
 #[cfg(windows)]
 use crate::ffi_utils::{AdlResult, ApiResult, ApiTable, DynamicLibrary};
+#[cfg(windows)]
+use crate::gpu_info::Confidence;
 use crate::gpu_info::GpuInfo;
 #[cfg(windows)]
 use crate::vendor::Vendor;
@@ -315,6 +317,7 @@ impl AdlClient {
             name_gpu: Some(name),
             vendor: Vendor::Amd,
             temperature,
+            temperatures: None,
             utilization,
             core_clock,
             memory_clock,
@@ -330,6 +333,22 @@ impl AdlClient {
             power_limit: None,
             driver_version: None,
             max_clock_speed: None,
+            effective_core_clock: None,
+            fan_speed: None,
+            fan_speed_rpm: None,
+            encoder_util: None,
+            decoder_util: None,
+            throttle_reasons: None,
+            uuid: None,
+            bus_id: Some(format!(
+                "0000:{:02x}:{:02x}.{:x}",
+                adapter.iBusNumber, adapter.iDeviceNumber, adapter.iFunctionNumber
+            )),
+            processes: None,
+            ecc_errors: None,
+            metric_sources: Default::default(),
+            confidence: Confidence::High,
+            adapter_luid: None,
         })
     }
 }
@@ -4,9 +4,27 @@
 //! allowing non-blocking operations for better performance in async contexts.
 //!
 //! Uses `Arc<GpuInfo>` for efficient sharing without cloning.
+//!
+//! [`subscribe()`] and [`subscribe_all()`] poll the GPUs on a background
+//! task and stream the snapshots, for dashboards that would otherwise run
+//! their own interval loop. [`stream_gpu_metrics()`] and
+//! [`stream_all_gpus_metrics()`] send every poll over a plain
+//! [`mpsc::Receiver`] and return the task's [`JoinHandle`].
+use crate::cancel::CancelToken;
+use crate::gpu_index::GpuIndex;
 use crate::gpu_info::{GpuError, Result};
+use crate::gpu_manager::{global_gpu_manager, GpuManager};
 use crate::GpuInfo;
-use std::sync::Arc;
+use futures_core::Stream;
+use log::{debug, warn};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
 /// Asynchronously gets the primary GPU information (zero-copy)
 ///
 /// This function runs the GPU detection in a blocking task to avoid blocking
@@ -41,13 +59,91 @@ use std::sync::Arc;
 /// }
 /// ```
 pub async fn get_async() -> Result<Arc<GpuInfo>> {
-    tokio::task::spawn_blocking(|| {
-        crate::gpu_manager::get_primary_gpu_arc().ok_or(GpuError::GpuNotFound)
+    primary_cached_async(global_gpu_manager).await
+}
+
+/// Reads the primary GPU through the cache of the manager returned by `manager`
+///
+/// `manager` runs on the blocking task, as creating the global manager
+/// performs detection.
+pub(crate) async fn primary_cached_async<F>(manager: F) -> Result<Arc<GpuInfo>>
+where
+    F: FnOnce() -> Arc<Mutex<GpuManager>> + Send + 'static,
+{
+    tokio::task::spawn_blocking(move || {
+        let manager = manager();
+        let manager = manager.lock().map_err(|_| GpuError::GpuNotActive)?;
+        manager
+            .get_primary_gpu_cached()
+            .ok_or(GpuError::GpuNotFound)
     })
     .await
     .map_err(|_| GpuError::GpuNotActive)?
 }
 
+/// Asynchronously runs GPU detection and fills the global cache
+///
+/// Awaiting this during startup moves detection out of the first
+/// [`get_async()`], which then resolves from the cache. Nothing is returned:
+/// errors are logged. Concurrent calls share one detection.
+///
+/// See [`prefetch_blocking()`](crate::prefetch_blocking) for the sync version.
+///
+/// # Example
+/// ```rust
+/// #[tokio::main]
+/// async fn main() {
+///     gpu_info::prefetch().await;
+///     // start serving requests; the first get_async() is served from the cache
+///     let _gpu = gpu_info::get_async().await;
+/// }
+/// ```
+pub async fn prefetch() {
+    warm_async(global_gpu_manager).await;
+}
+
+/// Warms the manager returned by `manager` on a blocking task
+pub(crate) async fn warm_async<F>(manager: F)
+where
+    F: FnOnce() -> Arc<Mutex<GpuManager>> + Send + 'static,
+{
+    let result =
+        tokio::task::spawn_blocking(move || crate::gpu_manager::warm_shared(&manager())).await;
+    if let Err(e) = result {
+        warn!("GPU prefetch task failed: {}", e);
+    }
+}
+
+/// Asynchronously detects GPUs, stopping early when `token` is cancelled
+///
+/// Runs [`GpuManager::detect_with_cancel()`] on a blocking task. After
+/// cancellation the returned manager holds the GPUs found so far and its
+/// [`detection_report()`](GpuManager::detection_report) is marked cancelled.
+///
+/// # Errors
+///
+/// Returns [`GpuError::GpuNotActive`] if the blocking task panicked.
+///
+/// # Example
+/// ```rust
+/// use gpu_info::{detect_async_with_cancel, CancelToken};
+/// #[tokio::main]
+/// async fn main() -> gpu_info::Result<()> {
+///     let token = CancelToken::new();
+///     let detection = tokio::spawn(detect_async_with_cancel(token.clone()));
+///     // The window was closed: stop detection
+///     token.cancel();
+///     let manager = detection.await.expect("detection task failed")?;
+///     println!("{} GPU(s)", manager.gpu_count());
+///     Ok(())
+/// }
+/// ```
+pub async fn detect_async_with_cancel(token: CancelToken) -> Result<GpuManager> {
+    tokio::task::spawn_blocking(move || GpuManager::detect_with_cancel(token))
+        .await
+        .map_err(|_| GpuError::GpuNotActive)
+}
+
 /// Asynchronously gets the primary GPU information (owned copy)
 ///
 /// Returns a cloned copy of GPU information. Use this when you need to mutate
@@ -124,7 +220,7 @@ pub async fn get_all_async() -> Result<Vec<Arc<GpuInfo>>> {
         }
         let mut gpus = Vec::with_capacity(gpu_count);
         for i in 0..gpu_count {
-            if let Some(gpu) = manager.get_gpu_cached(i) {
+            if let Some(gpu) = manager.get_cached_gpu_at(GpuIndex(i)) {
                 gpus.push(gpu);
             }
         }
@@ -216,7 +312,7 @@ pub async fn update_gpu_async(gpu: &mut GpuInfo) -> Result<()> {
         let _ = manager_lock.refresh_all_gpus();
         let gpu_count = manager_lock.gpu_count();
         for i in 0..gpu_count {
-            if let Some(gpu) = manager_lock.get_gpu_by_index_owned(i) {
+            if let Some(gpu) = manager_lock.get_gpu_at_owned(GpuIndex(i)) {
                 if gpu.vendor == vendor && gpu.name_gpu == name_gpu {
                     return Ok(gpu);
                 }
@@ -235,3 +331,270 @@ pub async fn update_gpu_async(gpu: &mut GpuInfo) -> Result<()> {
         Err(_) => Err(GpuError::GpuNotActive),
     }
 }
+
+/// Options of a GPU subscription, see [`subscribe_with()`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SubscribeOptions {
+    /// Time between two polls of the provider
+    pub interval: Duration,
+    /// Whether a snapshot equal to the previous one is yielded too
+    ///
+    /// Off by default, so the stream only yields when a value changed.
+    pub emit_unchanged: bool,
+}
+
+impl SubscribeOptions {
+    /// Options polling every `interval` and yielding changed snapshots only
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            emit_unchanged: false,
+        }
+    }
+
+    /// Sets whether unchanged snapshots are yielded
+    pub fn with_emit_unchanged(mut self, emit_unchanged: bool) -> Self {
+        self.emit_unchanged = emit_unchanged;
+        self
+    }
+}
+
+/// Stream of GPU snapshots returned by [`subscribe()`] and [`subscribe_all()`]
+///
+/// Implements [`Stream`]; [`recv()`](Self::recv) reads the next snapshot
+/// without a `StreamExt` import. Dropping the stream stops the background
+/// task that polls the provider.
+#[derive(Debug)]
+pub struct GpuStream<T> {
+    receiver: mpsc::Receiver<T>,
+}
+
+impl<T> GpuStream<T> {
+    /// Waits for the next snapshot
+    ///
+    /// Returns `None` once the background task has stopped.
+    pub async fn recv(&mut self) -> Option<T> {
+        self.receiver.recv().await
+    }
+}
+
+impl<T> Stream for GpuStream<T> {
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        self.receiver.poll_recv(cx)
+    }
+}
+
+/// Streams the primary GPU, polled every `interval`
+///
+/// A snapshot is yielded on the first poll and then whenever a value
+/// changed; see [`subscribe_with()`] to yield every poll. Polls run on a
+/// blocking task through the global [`GpuManager`], and failed polls are
+/// logged and skipped. The poll waits while the consumer has not read the
+/// previous snapshot, and stops when the stream is dropped.
+///
+/// # Panics
+///
+/// Panics if `interval` is zero or if called outside a Tokio runtime.
+///
+/// # Example
+/// ```no_run
+/// use gpu_info::async_api::subscribe;
+/// use std::time::Duration;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let mut updates = subscribe(Duration::from_secs(1));
+///     while let Some(gpu) = updates.recv().await {
+///         println!("{}: {}°C", gpu.format_name_gpu(), gpu.format_temperature());
+///     }
+/// }
+/// ```
+pub fn subscribe(interval: Duration) -> GpuStream<Arc<GpuInfo>> {
+    subscribe_with(SubscribeOptions::new(interval))
+}
+
+/// Streams the primary GPU with the given options
+///
+/// See [`subscribe()`].
+///
+/// # Panics
+///
+/// Panics if `options.interval` is zero or if called outside a Tokio runtime.
+pub fn subscribe_with(options: SubscribeOptions) -> GpuStream<Arc<GpuInfo>> {
+    spawn_subscription(options, || {
+        poll_global(|manager| {
+            manager
+                .get_primary_gpu_owned()
+                .map(Arc::new)
+                .ok_or(GpuError::GpuNotFound)
+        })
+    })
+}
+
+/// Streams every GPU, polled every `interval`
+///
+/// Yields all GPUs in detection order whenever a value of any of them
+/// changed. Otherwise behaves like [`subscribe()`].
+///
+/// # Panics
+///
+/// Panics if `interval` is zero or if called outside a Tokio runtime.
+pub fn subscribe_all(interval: Duration) -> GpuStream<Vec<Arc<GpuInfo>>> {
+    subscribe_all_with(SubscribeOptions::new(interval))
+}
+
+/// Streams every GPU with the given options
+///
+/// See [`subscribe_all()`].
+///
+/// # Panics
+///
+/// Panics if `options.interval` is zero or if called outside a Tokio runtime.
+pub fn subscribe_all_with(options: SubscribeOptions) -> GpuStream<Vec<Arc<GpuInfo>>> {
+    spawn_subscription(options, || {
+        poll_global(|manager| {
+            Ok(manager
+                .get_all_gpus_owned()
+                .into_iter()
+                .map(Arc::new)
+                .collect())
+        })
+    })
+}
+
+/// Sends a fresh snapshot of the primary GPU every `interval`
+///
+/// Unlike [`subscribe()`], every poll is sent, including unchanged ones, as
+/// an owned [`GpuInfo`]. Failed polls are logged and skipped. The returned
+/// task stops when the receiver is dropped; awaiting its [`JoinHandle`]
+/// waits for that.
+///
+/// # Panics
+///
+/// Panics if `interval` is zero or if called outside a Tokio runtime.
+///
+/// # Example
+/// ```no_run
+/// use gpu_info::async_api::stream_gpu_metrics;
+/// use std::time::Duration;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let (mut metrics, task) = stream_gpu_metrics(Duration::from_secs(1));
+///     for _ in 0..10 {
+///         if let Some(gpu) = metrics.recv().await {
+///             println!("{}: {}", gpu.format_name_gpu(), gpu.format_utilization());
+///         }
+///     }
+///     drop(metrics);
+///     task.await.unwrap();
+/// }
+/// ```
+pub fn stream_gpu_metrics(interval: Duration) -> (mpsc::Receiver<GpuInfo>, JoinHandle<()>) {
+    spawn_polling(
+        SubscribeOptions::new(interval).with_emit_unchanged(true),
+        || poll_global(|manager| manager.get_primary_gpu_owned().ok_or(GpuError::GpuNotFound)),
+    )
+}
+
+/// Sends fresh snapshots of every GPU every `interval`
+///
+/// GPUs are in detection order. Otherwise behaves like
+/// [`stream_gpu_metrics()`].
+///
+/// # Panics
+///
+/// Panics if `interval` is zero or if called outside a Tokio runtime.
+pub fn stream_all_gpus_metrics(
+    interval: Duration,
+) -> (mpsc::Receiver<Vec<GpuInfo>>, JoinHandle<()>) {
+    spawn_polling(
+        SubscribeOptions::new(interval).with_emit_unchanged(true),
+        || poll_global(|manager| Ok(manager.get_all_gpus_owned())),
+    )
+}
+
+/// Refreshes the global manager on a blocking task and reads it with `read`
+async fn poll_global<T, F>(read: F) -> Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce(&GpuManager) -> Result<T> + Send + 'static,
+{
+    tokio::task::spawn_blocking(move || {
+        let manager = global_gpu_manager();
+        let mut manager = manager.lock().map_err(|_| GpuError::GpuNotActive)?;
+        if let Err(e) = manager.refresh_all_gpus() {
+            warn!("Failed to refresh GPUs for a subscription: {}", e);
+        }
+        read(&manager)
+    })
+    .await
+    .map_err(|_| GpuError::GpuNotActive)?
+}
+
+/// Spawns the task polling `source` for a subscription
+///
+/// The task exits when the returned stream is dropped.
+pub(crate) fn spawn_subscription<T, F, Fut>(options: SubscribeOptions, source: F) -> GpuStream<T>
+where
+    T: Clone + PartialEq + Send + 'static,
+    F: FnMut() -> Fut + Send + 'static,
+    Fut: Future<Output = Result<T>> + Send + 'static,
+{
+    let (receiver, _task) = spawn_polling(options, source);
+    GpuStream { receiver }
+}
+
+/// Spawns the task polling `source`, returning its channel and handle
+///
+/// The task exits when the receiver is dropped.
+pub(crate) fn spawn_polling<T, F, Fut>(
+    options: SubscribeOptions,
+    source: F,
+) -> (mpsc::Receiver<T>, JoinHandle<()>)
+where
+    T: Clone + PartialEq + Send + 'static,
+    F: FnMut() -> Fut + Send + 'static,
+    Fut: Future<Output = Result<T>> + Send + 'static,
+{
+    // One slot: a slow consumer holds back polling instead of queueing
+    let (sender, receiver) = mpsc::channel(1);
+    let task = tokio::spawn(poll_loop(options, source, sender));
+    (receiver, task)
+}
+
+/// Polls `source` every tick and sends the snapshots to `sender`
+async fn poll_loop<T, F, Fut>(options: SubscribeOptions, mut source: F, sender: mpsc::Sender<T>)
+where
+    T: Clone + PartialEq,
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let mut ticks = tokio::time::interval(options.interval);
+    ticks.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    let mut last: Option<T> = None;
+    loop {
+        tokio::select! {
+            biased;
+            _ = sender.closed() => break,
+            _ = ticks.tick() => {}
+        }
+        let snapshot = match source().await {
+            Ok(snapshot) => snapshot,
+            Err(e) => {
+                warn!("GPU subscription poll failed: {}", e);
+                continue;
+            }
+        };
+        if !options.emit_unchanged && last.as_ref() == Some(&snapshot) {
+            continue;
+        }
+        last = Some(snapshot.clone());
+        if sender.send(snapshot).await.is_err() {
+            break;
+        }
+    }
+    debug!("GPU subscription stopped");
+}
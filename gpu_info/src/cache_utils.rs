@@ -5,11 +5,65 @@
 //!
 //! Uses `Arc<GpuInfo>` internally to avoid cloning on cache hits.
 use crate::gpu_info::GpuInfo;
+use crate::metric_source::Metric;
 use log::debug;
 use std::collections::HashMap;
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::mem::size_of;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex, RwLock};
 use std::time::{Duration, Instant};
+/// A group of [`GpuInfo`] fields whose freshness a [`CacheEntry`] tracks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum MetricKind {
+    /// [`GpuInfo::name_gpu`] and [`GpuInfo::driver_version`]
+    Name,
+    /// A numeric metric
+    Metric(Metric),
+}
+impl From<Metric> for MetricKind {
+    fn from(metric: Metric) -> Self {
+        Self::Metric(metric)
+    }
+}
+impl MetricKind {
+    /// Copies the fields of this kind from `from` into `to`
+    pub(crate) fn copy(self, from: &GpuInfo, to: &mut GpuInfo) {
+        match self {
+            Self::Name => {
+                to.name_gpu.clone_from(&from.name_gpu);
+                to.driver_version.clone_from(&from.driver_version);
+            }
+            Self::Metric(metric) => to.copy_metric(from, metric),
+        }
+    }
+}
+/// Source of the current time for cache ages
+///
+/// The system's monotonic clock unless replaced with `with_clock()`.
+#[derive(Clone)]
+struct Clock(Arc<dyn Fn() -> Instant + Send + Sync>);
+impl Clock {
+    fn now(&self) -> Instant {
+        (self.0)()
+    }
+}
+impl Default for Clock {
+    fn default() -> Self {
+        Self(Arc::new(Instant::now))
+    }
+}
+impl Debug for Clock {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Clock")
+    }
+}
 /// Generic cache entry with TTL support and access tracking
+///
+/// Besides the entry as a whole, each [`MetricKind`] has its own refresh
+/// time, so a metric can be refreshed without replacing the entry.
 #[derive(Debug, Clone)]
 pub struct CacheEntry<T> {
     /// Cached value
@@ -20,31 +74,85 @@ pub struct CacheEntry<T> {
     pub last_accessed: Instant,
     /// Access count for LRU implementation
     pub access_count: usize,
+    /// Metrics refreshed after the entry was created
+    metric_timestamps: HashMap<MetricKind, Instant>,
 }
 impl<T> CacheEntry<T> {
     /// Create a new cache entry
     pub fn new(value: T) -> Self {
-        let now = Instant::now();
+        Self::created_at(value, Instant::now())
+    }
+    /// Create a cache entry as of `now`
+    pub fn created_at(value: T, now: Instant) -> Self {
         Self {
             value,
             timestamp: now,
             last_accessed: now,
             access_count: 0,
+            metric_timestamps: HashMap::new(),
         }
     }
     /// Check if the entry is still valid based on TTL
     pub fn is_valid(&self, ttl: Duration) -> bool {
-        self.timestamp.elapsed() < ttl
+        self.is_valid_at(ttl, Instant::now())
+    }
+    /// Check if the entry is still valid at `now`
+    pub fn is_valid_at(&self, ttl: Duration, now: Instant) -> bool {
+        self.age_at(now) < ttl
     }
     /// Get the age of the entry
     pub fn age(&self) -> Duration {
-        self.timestamp.elapsed()
+        self.age_at(Instant::now())
+    }
+    /// Get the age of the entry at `now`
+    pub fn age_at(&self, now: Instant) -> Duration {
+        now.saturating_duration_since(self.timestamp)
     }
     /// Update access statistics
     pub fn record_access(&mut self) {
-        self.last_accessed = Instant::now();
+        self.record_access_at(Instant::now());
+    }
+    /// Update access statistics for an access at `now`
+    pub fn record_access_at(&mut self, now: Instant) {
+        self.last_accessed = now;
         self.access_count += 1;
     }
+    /// Records that `metric` was refreshed now
+    pub fn mark_metric_refreshed(&mut self, metric: MetricKind) {
+        self.mark_metric_refreshed_at(metric, Instant::now());
+    }
+    /// Records that `metric` was refreshed at `now`
+    pub fn mark_metric_refreshed_at(&mut self, metric: MetricKind, now: Instant) {
+        self.metric_timestamps.insert(metric, now);
+    }
+    /// Returns when `metric` was last refreshed
+    ///
+    /// Metrics never refreshed on their own date from the entry's creation.
+    pub fn metric_refreshed_at(&self, metric: MetricKind) -> Instant {
+        self.metric_timestamps
+            .get(&metric)
+            .copied()
+            .unwrap_or(self.timestamp)
+    }
+    /// Check if `metric` was last refreshed `ttl` or longer ago
+    ///
+    /// # Examples
+    /// ```
+    /// use gpu_info::cache_utils::{CacheEntry, MetricKind};
+    /// use gpu_info::metric_source::Metric;
+    /// use std::time::Duration;
+    ///
+    /// let entry = CacheEntry::new(());
+    /// assert!(entry.is_metric_stale(Metric::Utilization.into(), Duration::ZERO));
+    /// assert!(!entry.is_metric_stale(MetricKind::Name, Duration::from_secs(60)));
+    /// ```
+    pub fn is_metric_stale(&self, metric: MetricKind, ttl: Duration) -> bool {
+        self.is_metric_stale_at(metric, ttl, Instant::now())
+    }
+    /// Check if `metric` was last refreshed `ttl` or longer before `now`
+    pub fn is_metric_stale_at(&self, metric: MetricKind, ttl: Duration, now: Instant) -> bool {
+        now.saturating_duration_since(self.metric_refreshed_at(metric)) >= ttl
+    }
 }
 /// Single-item cache for GPU information
 ///
@@ -67,6 +175,7 @@ pub struct GpuInfoCache {
     info: RwLock<Option<CacheEntry<Arc<GpuInfo>>>>,
     /// Time-to-live for cached entries
     ttl: Duration,
+    clock: Clock,
 }
 impl GpuInfoCache {
     /// Create a new GPU info cache with the specified TTL
@@ -74,20 +183,41 @@ impl GpuInfoCache {
         Self {
             info: RwLock::new(None),
             ttl,
+            clock: Clock::default(),
         }
     }
+    /// Uses `clock` instead of the system's monotonic clock for entry ages
+    ///
+    /// # Examples
+    /// ```
+    /// use gpu_info::cache_utils::GpuInfoCache;
+    /// use gpu_info::gpu_info::GpuInfo;
+    /// use std::time::{Duration, Instant};
+    /// let start = Instant::now();
+    /// let cache = GpuInfoCache::new(Duration::from_secs(1)).with_clock(move || start);
+    /// cache.set(GpuInfo::unknown());
+    /// assert_eq!(cache.age(), Some(Duration::ZERO));
+    /// ```
+    pub fn with_clock<F>(mut self, clock: F) -> Self
+    where
+        F: Fn() -> Instant + Send + Sync + 'static,
+    {
+        self.clock = Clock(Arc::new(clock));
+        self
+    }
     /// Get cached GPU information if it's still valid
     ///
     /// Returns `Arc<GpuInfo>` for cheap cloning (no data duplication).
     pub fn get(&self) -> Option<Arc<GpuInfo>> {
         let mut guard = self.info.write().ok()?;
+        let now = self.clock.now();
         if let Some(entry) = guard.as_mut() {
-            if entry.is_valid(self.ttl) {
-                entry.record_access();
-                debug!("Returning cached GPU info (age: {:?})", entry.age());
+            if entry.is_valid_at(self.ttl, now) {
+                entry.record_access_at(now);
+                debug!("Returning cached GPU info (age: {:?})", entry.age_at(now));
                 Some(Arc::clone(&entry.value))
             } else {
-                debug!("Cached GPU info expired (age: {:?})", entry.age());
+                debug!("Cached GPU info expired (age: {:?})", entry.age_at(now));
                 None
             }
         } else {
@@ -106,7 +236,7 @@ impl GpuInfoCache {
     /// Set GPU information in the cache
     pub fn set(&self, info: GpuInfo) {
         if let Ok(mut guard) = self.info.write() {
-            *guard = Some(CacheEntry::new(Arc::new(info)));
+            *guard = Some(CacheEntry::created_at(Arc::new(info), self.clock.now()));
             debug!("Updated GPU info cache");
         }
     }
@@ -128,7 +258,7 @@ impl GpuInfoCache {
     /// Get the age of the cached entry if it exists
     pub fn age(&self) -> Option<Duration> {
         if let Ok(guard) = self.info.read() {
-            guard.as_ref().map(|entry| entry.age())
+            guard.as_ref().map(|entry| entry.age_at(self.clock.now()))
         } else {
             None
         }
@@ -145,6 +275,13 @@ impl Default for GpuInfoCache {
 /// for multiple GPU information items indexed by key. Uses `Arc<GpuInfo>` internally
 /// to avoid cloning on cache hits.
 ///
+/// Clones share entries, statistics and capacity.
+///
+/// Entries are keyed by `usize` unless another key type is chosen;
+/// [`GpuManager`](crate::GpuManager) keys its cache by
+/// [`GpuIdentity`](crate::GpuIdentity) so entries follow a GPU rather than
+/// its position in the list.
+///
 /// # Examples
 /// ```rust
 /// use gpu_info::cache_utils::{CacheEntry, MultiGpuInfoCache};
@@ -156,105 +293,222 @@ impl Default for GpuInfoCache {
 /// assert!(cache.get(&0).is_some());
 /// ```
 #[derive(Debug, Clone)]
-pub struct MultiGpuInfoCache {
+pub struct MultiGpuInfoCache<K = usize> {
     /// Cached GPU information entries indexed by key (Arc for cheap cloning)
-    entries: Arc<Mutex<HashMap<usize, CacheEntry<Arc<GpuInfo>>>>>,
+    entries: Arc<Mutex<HashMap<K, Slot>>>,
     /// Time-to-live for cached entries
     ttl: Duration,
     /// Maximum number of entries to keep in cache (0 = unlimited)
-    max_entries: usize,
+    max_entries: Arc<AtomicUsize>,
+    /// Hit, miss and eviction counters
+    counters: Arc<Counters>,
+    clock: Clock,
+}
+/// A cache entry with its position in the LRU order
+#[derive(Debug)]
+struct Slot {
+    entry: CacheEntry<Arc<GpuInfo>>,
+    /// Value of [`Counters::clock`] at the last insert or hit
+    last_used: u64,
+}
+/// Counters shared by all clones of a [`MultiGpuInfoCache`]
+#[derive(Debug, Default)]
+struct Counters {
+    hits: AtomicU64,
+    misses: AtomicU64,
+    evictions: AtomicU64,
+    /// Logical clock ordering entries by recency; unlike `Instant` it
+    /// never ties, so eviction order is deterministic
+    clock: AtomicU64,
+}
+impl Counters {
+    fn tick(&self) -> u64 {
+        self.clock.fetch_add(1, Ordering::Relaxed)
+    }
 }
 impl MultiGpuInfoCache {
     /// Create a new multi-GPU info cache with the specified TTL
     pub fn new(ttl: Duration) -> Self {
-        Self {
-            entries: Arc::new(Mutex::new(HashMap::new())),
-            ttl,
-            max_entries: 0, // Unlimited by default
-        }
+        Self::with_max_entries(ttl, 0) // Unlimited by default
     }
     /// Create a new multi-GPU info cache with the specified TTL and maximum entries
     pub fn with_max_entries(ttl: Duration, max_entries: usize) -> Self {
+        Self::keyed(ttl, max_entries)
+    }
+}
+impl<K: Eq + Hash + Clone + Debug> MultiGpuInfoCache<K> {
+    /// Create a cache with keys of type `K` instead of `usize`
+    ///
+    /// `max_entries` of 0 means unlimited.
+    pub fn keyed(ttl: Duration, max_entries: usize) -> Self {
         Self {
             entries: Arc::new(Mutex::new(HashMap::new())),
             ttl,
-            max_entries,
+            max_entries: Arc::new(AtomicUsize::new(max_entries)),
+            counters: Arc::new(Counters::default()),
+            clock: Clock::default(),
         }
     }
+    /// Uses `clock` instead of the system's monotonic clock for entry ages
+    /// and metric staleness
+    pub fn with_clock<F>(mut self, clock: F) -> Self
+    where
+        F: Fn() -> Instant + Send + Sync + 'static,
+    {
+        self.clock = Clock(Arc::new(clock));
+        self
+    }
     /// Get cached GPU information by key if it's still valid
     ///
     /// Returns `Arc<GpuInfo>` for cheap cloning (no data duplication).
-    pub fn get(&self, key: &usize) -> Option<Arc<GpuInfo>> {
+    /// Counts as a hit if a valid entry is found and as a miss otherwise;
+    /// an expired entry is removed and counted as an eviction.
+    pub fn get(&self, key: &K) -> Option<Arc<GpuInfo>> {
         let mut guard = self.entries.lock().ok()?;
-        if let Some(entry) = guard.get_mut(key) {
-            if entry.is_valid(self.ttl) {
-                entry.record_access();
+        let now = self.clock.now();
+        let result = if let Some(slot) = guard.get_mut(key) {
+            if slot.entry.is_valid_at(self.ttl, now) {
+                slot.entry.record_access_at(now);
+                slot.last_used = self.counters.tick();
                 debug!(
-                    "Returning cached GPU info for key {} (age: {:?})",
+                    "Returning cached GPU info for key {:?} (age: {:?})",
                     key,
-                    entry.age()
+                    slot.entry.age_at(now)
                 );
-                Some(Arc::clone(&entry.value))
+                Some(Arc::clone(&slot.entry.value))
             } else {
                 debug!(
-                    "Cached GPU info for key {} expired (age: {:?})",
+                    "Cached GPU info for key {:?} expired (age: {:?})",
                     key,
-                    entry.age()
+                    slot.entry.age_at(now)
                 );
                 guard.remove(key);
+                self.counters.evictions.fetch_add(1, Ordering::Relaxed);
                 None
             }
         } else {
             None
-        }
+        };
+        let counter = match result {
+            Some(_) => &self.counters.hits,
+            None => &self.counters.misses,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        result
     }
 
     /// Get cached GPU information as owned value (clones the data)
     ///
     /// Use this when you need to mutate the GPU info.
     /// For read-only access, prefer `get()` which returns `Arc<GpuInfo>`.
-    pub fn get_owned(&self, key: &usize) -> Option<GpuInfo> {
+    pub fn get_owned(&self, key: &K) -> Option<GpuInfo> {
         self.get(key).map(|arc| (*arc).clone())
     }
 
     /// Set GPU information in the cache by key
-    pub fn set(&self, key: usize, info: GpuInfo) {
+    pub fn set(&self, key: K, info: GpuInfo) {
         if let Ok(mut guard) = self.entries.lock() {
-            guard.insert(key, CacheEntry::new(Arc::new(info)));
+            let slot = Slot {
+                entry: CacheEntry::created_at(Arc::new(info), self.clock.now()),
+                last_used: self.counters.tick(),
+            };
+            debug!("Updated GPU info cache for key {:?}", key);
+            guard.insert(key, slot);
             // Apply LRU eviction if we have a limit
-            if self.max_entries > 0 && guard.len() > self.max_entries {
-                self.evict_lru_entries(&mut guard);
-            }
-            debug!("Updated GPU info cache for key {}", key);
+            self.evict_lru_entries(&mut guard);
+        }
+    }
+    /// Get the metrics of the entry for `key` that are older than their TTL
+    ///
+    /// Returns an empty list if there is no entry. Does not count as a hit
+    /// or miss.
+    pub fn stale_metrics(&self, key: &K, ttls: &[(MetricKind, Duration)]) -> Vec<MetricKind> {
+        let Ok(guard) = self.entries.lock() else {
+            return Vec::new();
+        };
+        let Some(slot) = guard.get(key) else {
+            return Vec::new();
+        };
+        let now = self.clock.now();
+        ttls.iter()
+            .filter(|&&(metric, ttl)| slot.entry.is_metric_stale_at(metric, ttl, now))
+            .map(|&(metric, _)| metric)
+            .collect()
+    }
+    /// Replace `metrics` of the entry for `key` with their values in `fresh`
+    ///
+    /// Other fields keep their cached values, and the entry keeps its age.
+    /// Returns the updated entry, or `None` if there is no entry for `key`.
+    pub fn refresh_metrics(
+        &self,
+        key: &K,
+        fresh: &GpuInfo,
+        metrics: &[MetricKind],
+    ) -> Option<Arc<GpuInfo>> {
+        let mut guard = self.entries.lock().ok()?;
+        let slot = guard.get_mut(key)?;
+        let mut info = (*slot.entry.value).clone();
+        let now = self.clock.now();
+        for &metric in metrics {
+            metric.copy(fresh, &mut info);
+            slot.entry.mark_metric_refreshed_at(metric, now);
         }
+        slot.entry.value = Arc::new(info);
+        debug!(
+            "Refreshed {:?} in GPU info cache for key {:?}",
+            metrics, key
+        );
+        Some(Arc::clone(&slot.entry.value))
     }
     /// Evict least recently used entries to maintain size limit
-    fn evict_lru_entries(&self, guard: &mut HashMap<usize, CacheEntry<Arc<GpuInfo>>>) {
-        if guard.len() <= self.max_entries {
+    fn evict_lru_entries(&self, guard: &mut HashMap<K, Slot>) {
+        let max_entries = self.capacity();
+        if max_entries == 0 || guard.len() <= max_entries {
             return;
         }
         // Find the least recently used entries to remove
         let mut entries: Vec<_> = guard.iter().collect();
-        entries.sort_by_key(|(_, entry)| entry.last_accessed);
+        entries.sort_by_key(|(_, slot)| slot.last_used);
         // Remove excess entries
-        let excess = guard.len() - self.max_entries;
+        let excess = guard.len() - max_entries;
         let keys_to_remove: Vec<_> = entries
             .into_iter()
             .take(excess)
-            .map(|(key, _)| *key)
+            .map(|(key, _)| key.clone())
             .collect();
         for key in keys_to_remove {
             guard.remove(&key);
+            debug!("Evicted GPU info cache entry for key {:?}", key);
         }
+        self.counters
+            .evictions
+            .fetch_add(excess as u64, Ordering::Relaxed);
+    }
+    /// Get the maximum number of entries (0 = unlimited)
+    pub fn capacity(&self) -> usize {
+        self.max_entries.load(Ordering::Relaxed)
+    }
+    /// Change the maximum number of entries (0 = unlimited)
+    ///
+    /// If the cache holds more entries than the new capacity, the least
+    /// recently used ones are evicted immediately.
+    pub fn set_capacity(&self, max_entries: usize) {
+        self.max_entries.store(max_entries, Ordering::Relaxed);
+        if let Ok(mut guard) = self.entries.lock() {
+            self.evict_lru_entries(&mut guard);
+        }
+        debug!("Set GPU info cache capacity to {}", max_entries);
     }
     /// Clear the cache entry for a specific key
-    pub fn clear_key(&self, key: &usize) {
+    pub fn clear_key(&self, key: &K) {
         if let Ok(mut guard) = self.entries.lock() {
             guard.remove(key);
-            debug!("Cleared GPU info cache for key {}", key);
+            debug!("Cleared GPU info cache for key {:?}", key);
         }
     }
     /// Clear all cache entries
+    ///
+    /// Statistics are kept; cleared entries are not counted as evictions.
     pub fn clear_all(&self) {
         if let Ok(mut guard) = self.entries.lock() {
             guard.clear();
@@ -262,7 +516,7 @@ impl MultiGpuInfoCache {
         }
     }
     /// Check if there's a cached entry for a specific key (regardless of validity)
-    pub fn has_entry(&self, key: &usize) -> bool {
+    pub fn has_entry(&self, key: &K) -> bool {
         if let Ok(guard) = self.entries.lock() {
             guard.contains_key(key)
         } else {
@@ -286,9 +540,12 @@ impl MultiGpuInfoCache {
         if let Ok(guard) = self.entries.lock() {
             let total_entries = guard.len();
             let mut total_accesses = 0;
+            let mut approx_bytes = 0;
             let mut oldest_entry = None;
-            for entry in guard.values() {
+            for slot in guard.values() {
+                let entry = &slot.entry;
                 total_accesses += entry.access_count;
+                approx_bytes += approx_entry_size::<K>(&entry.value);
                 match oldest_entry {
                     None => oldest_entry = Some(entry.timestamp),
                     Some(oldest) if entry.timestamp < oldest => {
@@ -301,16 +558,33 @@ impl MultiGpuInfoCache {
                 total_entries,
                 total_accesses,
                 oldest_entry_age: oldest_entry
-                    .map(|ts| ts.elapsed())
+                    .map(|ts| self.clock.now().saturating_duration_since(ts))
                     .unwrap_or(Duration::from_secs(0)),
+                hits: self.counters.hits.load(Ordering::Relaxed),
+                misses: self.counters.misses.load(Ordering::Relaxed),
+                evictions: self.counters.evictions.load(Ordering::Relaxed),
+                approx_bytes,
             })
         } else {
             None
         }
     }
 }
+/// Approximate memory held by one cache entry: the map slot, the shared
+/// `GpuInfo` allocation and its strings
+fn approx_entry_size<K>(gpu: &GpuInfo) -> usize {
+    let strings = [&gpu.name_gpu, &gpu.driver_version, &gpu.uuid, &gpu.bus_id]
+        .iter()
+        .filter_map(|string| string.as_ref())
+        .map(String::capacity)
+        .sum::<usize>();
+    // Map key and slot, then the `Arc` allocation: two reference counts and the `GpuInfo`
+    let entry = size_of::<K>() + size_of::<Slot>();
+    let shared = 2 * size_of::<usize>() + size_of::<GpuInfo>();
+    entry + shared + strings
+}
 /// Statistics about cache performance
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct CacheStats {
     /// Total number of entries in the cache
     pub total_entries: usize,
@@ -318,6 +592,14 @@ pub struct CacheStats {
     pub total_accesses: usize,
     /// Age of the oldest entry
     pub oldest_entry_age: Duration,
+    /// Lookups that returned a valid entry
+    pub hits: u64,
+    /// Lookups that found no entry or an expired one
+    pub misses: u64,
+    /// Entries removed because they expired or exceeded the capacity
+    pub evictions: u64,
+    /// Approximate memory used by the cached entries, in bytes
+    pub approx_bytes: usize,
 }
 impl Default for MultiGpuInfoCache {
     fn default() -> Self {
@@ -0,0 +1,146 @@
+//! Cooperative cancellation of GPU detection.
+//!
+//! A [`CancelToken`] is shared between the thread running detection and the
+//! thread that may want to stop it, for example a GUI closing its window.
+//! The same token works with [`GpuManager::detect_with_cancel()`] and its
+//! async variant [`detect_async_with_cancel()`]. Providers check the token
+//! between devices and backends, and external commands they run are killed
+//! as soon as the token is cancelled.
+//!
+//! [`GpuManager::detect_with_cancel()`]: crate::GpuManager::detect_with_cancel
+//! [`detect_async_with_cancel()`]: crate::detect_async_with_cancel
+use std::io::{self, Read};
+use std::process::{Command, Output, Stdio};
+use std::sync::{Arc, Condvar, Mutex, PoisonError};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// How often a running command is checked for exit, timeout and cancellation
+#[cfg_attr(not(feature = "vendor-intel"), allow(dead_code))]
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Shared flag requesting that detection stop early.
+///
+/// Cloning the token yields a handle to the same flag. Cancellation cannot
+/// be undone.
+///
+/// # Examples
+///
+/// ```no_run
+/// use gpu_info::{CancelToken, GpuManager};
+///
+/// let token = CancelToken::new();
+/// let worker_token = token.clone();
+/// let worker = std::thread::spawn(move || GpuManager::detect_with_cancel(worker_token));
+///
+/// // The window was closed: stop detection
+/// token.cancel();
+/// let manager = worker.join().unwrap();
+/// assert!(manager.detection_report().is_cancelled());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken {
+    inner: Arc<(Mutex<bool>, Condvar)>,
+}
+
+impl CancelToken {
+    /// Creates a token that is not cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancels the token, waking every thread waiting on it.
+    pub fn cancel(&self) {
+        let (cancelled, wakeup) = &*self.inner;
+        *cancelled.lock().unwrap_or_else(PoisonError::into_inner) = true;
+        wakeup.notify_all();
+    }
+
+    /// Returns `true` if the token has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        *self.inner.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Sleeps for `duration` or until the token is cancelled, whichever
+    /// comes first.
+    ///
+    /// Returns `true` if the token is cancelled.
+    pub fn wait_timeout(&self, duration: Duration) -> bool {
+        let (cancelled, wakeup) = &*self.inner;
+        let guard = cancelled.lock().unwrap_or_else(PoisonError::into_inner);
+        let (guard, _) = wakeup
+            .wait_timeout_while(guard, duration, |cancelled| !*cancelled)
+            .unwrap_or_else(PoisonError::into_inner);
+        *guard
+    }
+}
+
+/// Runs `command` and collects its output, like [`Command::output()`].
+///
+/// The command is killed when `token` is cancelled or `timeout` elapses.
+///
+/// # Errors
+///
+/// Returns an error if the command cannot be spawned, with kind
+/// [`io::ErrorKind::Interrupted`] if it was cancelled and
+/// [`io::ErrorKind::TimedOut`] if it timed out.
+#[cfg_attr(not(feature = "vendor-intel"), allow(dead_code))]
+pub(crate) fn output_with_cancel(
+    command: &mut Command,
+    timeout: Option<Duration>,
+    token: &CancelToken,
+) -> io::Result<Output> {
+    if token.is_cancelled() {
+        return Err(cancelled_error());
+    }
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    // Drain both pipes while waiting, so a chatty command cannot block on a full pipe
+    let stdout = child.stdout.take().map(read_to_end);
+    let stderr = child.stderr.take().map(read_to_end);
+    let deadline = timeout.map(|timeout| Instant::now() + timeout);
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        let timed_out = deadline.is_some_and(|deadline| Instant::now() >= deadline);
+        if timed_out || token.wait_timeout(POLL_INTERVAL) {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(if timed_out {
+                io::Error::new(io::ErrorKind::TimedOut, "command timed out")
+            } else {
+                cancelled_error()
+            });
+        }
+    };
+    Ok(Output {
+        status,
+        stdout: join_reader(stdout),
+        stderr: join_reader(stderr),
+    })
+}
+
+#[cfg_attr(not(feature = "vendor-intel"), allow(dead_code))]
+fn cancelled_error() -> io::Error {
+    io::Error::new(io::ErrorKind::Interrupted, "detection cancelled")
+}
+
+#[cfg_attr(not(feature = "vendor-intel"), allow(dead_code))]
+fn read_to_end(mut pipe: impl Read + Send + 'static) -> JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut buffer = Vec::new();
+        let _ = pipe.read_to_end(&mut buffer);
+        buffer
+    })
+}
+
+#[cfg_attr(not(feature = "vendor-intel"), allow(dead_code))]
+fn join_reader(reader: Option<JoinHandle<Vec<u8>>>) -> Vec<u8> {
+    reader
+        .and_then(|reader| reader.join().ok())
+        .unwrap_or_default()
+}
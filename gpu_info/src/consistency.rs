@@ -0,0 +1,182 @@
+//! Cross-field consistency checks for provider output.
+//!
+//! [`GpuInfo::validate()`] checks each field against its own range. This
+//! module adds rules that relate fields to each other, catching impossible
+//! combinations such as more memory used than installed or an inactive GPU
+//! reporting load.
+//!
+//! The checker itself is a pure function ([`check`]). [`enforce`] is used by
+//! [`GpuManager`] in strict mode: violations panic in debug builds, naming
+//! the provider, and in release builds they are logged with the provider
+//! name and the offending values are clamped or dropped.
+//!
+//! [`GpuInfo::validate()`]: crate::GpuInfo::validate
+//! [`GpuManager`]: crate::GpuManager
+use crate::gpu_info::GpuInfo;
+use log::error;
+use std::fmt::{Display, Formatter};
+
+/// A single cross-field consistency rule violation.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum ConsistencyViolation {
+    /// `memory_used` is greater than `memory_total`.
+    MemoryUsedExceedsTotal {
+        /// Reported used memory in MB.
+        used: u32,
+        /// Reported total memory in MB.
+        total: u32,
+    },
+    /// `utilization` is outside 0-100% (or NaN).
+    UtilizationOutOfRange(f32),
+    /// `memory_util` is outside 0-100% (or NaN).
+    MemoryUtilOutOfRange(f32),
+    /// `core_clock` is greater than `max_clock_speed`.
+    CoreClockExceedsMax {
+        /// Reported core clock in MHz.
+        clock: u32,
+        /// Reported maximum clock in MHz.
+        max: u32,
+    },
+    /// The GPU is reported inactive but has non-zero utilization.
+    InactiveWithUtilization(f32),
+}
+
+impl Display for ConsistencyViolation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MemoryUsedExceedsTotal { used, total } => {
+                write!(
+                    f,
+                    "memory_used ({} MB) exceeds memory_total ({} MB)",
+                    used, total
+                )
+            }
+            Self::UtilizationOutOfRange(util) => {
+                write!(f, "utilization {}% is outside 0-100%", util)
+            }
+            Self::MemoryUtilOutOfRange(util) => {
+                write!(f, "memory_util {}% is outside 0-100%", util)
+            }
+            Self::CoreClockExceedsMax { clock, max } => {
+                write!(
+                    f,
+                    "core_clock ({} MHz) exceeds max_clock_speed ({} MHz)",
+                    clock, max
+                )
+            }
+            Self::InactiveWithUtilization(util) => {
+                write!(f, "GPU is inactive but reports {}% utilization", util)
+            }
+        }
+    }
+}
+
+fn percent_in_range(value: f32) -> bool {
+    (0.0..=100.0).contains(&value)
+}
+
+/// Checks `gpu` against all cross-field rules.
+///
+/// Returns every violation found, in a stable order. An empty vector means
+/// the data is consistent. Missing (`None`) fields never cause a violation.
+pub fn check(gpu: &GpuInfo) -> Vec<ConsistencyViolation> {
+    let mut violations = Vec::new();
+    if let (Some(used), Some(total)) = (gpu.memory_used, gpu.memory_total) {
+        if used > total {
+            violations.push(ConsistencyViolation::MemoryUsedExceedsTotal { used, total });
+        }
+    }
+    if let Some(util) = gpu.utilization {
+        if !percent_in_range(util) {
+            violations.push(ConsistencyViolation::UtilizationOutOfRange(util));
+        }
+    }
+    if let Some(util) = gpu.memory_util {
+        if !percent_in_range(util) {
+            violations.push(ConsistencyViolation::MemoryUtilOutOfRange(util));
+        }
+    }
+    if let (Some(clock), Some(max)) = (gpu.core_clock, gpu.max_clock_speed) {
+        if clock > max {
+            violations.push(ConsistencyViolation::CoreClockExceedsMax { clock, max });
+        }
+    }
+    if let (Some(false), Some(util)) = (gpu.active, gpu.utilization) {
+        if util > 0.0 {
+            violations.push(ConsistencyViolation::InactiveWithUtilization(util));
+        }
+    }
+    violations
+}
+
+/// Repairs the fields involved in `violations`.
+///
+/// Percentages are clamped to 0-100 (NaN is dropped), `memory_used` is
+/// clamped to `memory_total`, and values that cannot be trusted on their own
+/// (a core clock above the maximum, utilization on an inactive GPU) are
+/// dropped.
+pub fn repair(gpu: &mut GpuInfo, violations: &[ConsistencyViolation]) {
+    for violation in violations {
+        match violation {
+            ConsistencyViolation::MemoryUsedExceedsTotal { total, .. } => {
+                gpu.memory_used = Some(*total);
+            }
+            ConsistencyViolation::UtilizationOutOfRange(util) => {
+                gpu.utilization = clamp_percent(*util);
+            }
+            ConsistencyViolation::MemoryUtilOutOfRange(util) => {
+                gpu.memory_util = clamp_percent(*util);
+            }
+            ConsistencyViolation::CoreClockExceedsMax { .. } => {
+                gpu.core_clock = None;
+            }
+            ConsistencyViolation::InactiveWithUtilization(_) => {
+                gpu.utilization = None;
+            }
+        }
+    }
+}
+
+fn clamp_percent(value: f32) -> Option<f32> {
+    if value.is_nan() {
+        None
+    } else {
+        Some(value.clamp(0.0, 100.0))
+    }
+}
+
+/// Checks `gpu` and handles violations according to the build profile.
+///
+/// In debug builds any violation panics, naming `provider`. In release
+/// builds each violation is logged at error level, naming `provider`, and
+/// the data is repaired with [`repair`].
+///
+/// Returns the violations that were found.
+///
+/// # Panics
+///
+/// Panics in debug builds if `gpu` violates any consistency rule.
+pub fn enforce(gpu: &mut GpuInfo, provider: &str) -> Vec<ConsistencyViolation> {
+    let violations = check(gpu);
+    #[cfg(debug_assertions)]
+    if !violations.is_empty() {
+        let details: Vec<String> = violations.iter().map(|v| v.to_string()).collect();
+        panic!(
+            "Provider {} returned inconsistent GPU data for {}: {}",
+            provider,
+            gpu.name_or_default(),
+            details.join("; ")
+        );
+    }
+    for violation in &violations {
+        error!(
+            "Provider {} returned inconsistent GPU data for {}: {}",
+            provider,
+            gpu.name_or_default(),
+            violation
+        );
+    }
+    repair(gpu, &violations);
+    violations
+}
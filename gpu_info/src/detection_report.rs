@@ -0,0 +1,118 @@
+//! Diagnostics collected while detecting GPUs.
+//!
+//! A [`DetectionReport`] records how long each detection stage took and any
+//! actionable hints produced along the way (for example, advice to enable
+//! NVIDIA persistence mode when NVML initialization is slow).
+//!
+//! The report is filled by [`GpuManager`] during detection and can be read
+//! back with [`GpuManager::detection_report()`].
+//!
+//! [`GpuManager`]: crate::GpuManager
+//! [`GpuManager::detection_report()`]: crate::GpuManager::detection_report
+use std::time::Duration;
+
+/// Diagnostics produced by a single GPU detection pass.
+///
+/// # Examples
+///
+/// ```
+/// use gpu_info::DetectionReport;
+/// use std::time::Duration;
+///
+/// let mut report = DetectionReport::new();
+/// report.record_latency("nvml_init", Duration::from_millis(1200));
+/// report.add_hint("enable persistence mode to speed up GPU queries");
+///
+/// assert_eq!(report.latency("nvml_init"), Some(Duration::from_millis(1200)));
+/// assert_eq!(report.hints().len(), 1);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DetectionReport {
+    /// Measured latency per detection stage, in recording order
+    latencies: Vec<(String, Duration)>,
+    /// Actionable advice for the user
+    hints: Vec<String>,
+    /// Whether detection was cancelled before it finished
+    cancelled: bool,
+}
+
+impl DetectionReport {
+    /// Creates an empty report.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the latency of a detection stage.
+    ///
+    /// Recording the same stage twice replaces the previous value.
+    pub fn record_latency(&mut self, stage: impl Into<String>, latency: Duration) {
+        let stage = stage.into();
+        if let Some(entry) = self.latencies.iter_mut().find(|(name, _)| *name == stage) {
+            entry.1 = latency;
+        } else {
+            self.latencies.push((stage, latency));
+        }
+    }
+
+    /// Returns the recorded latency of a stage, if any.
+    pub fn latency(&self, stage: &str) -> Option<Duration> {
+        self.latencies
+            .iter()
+            .find(|(name, _)| name == stage)
+            .map(|(_, latency)| *latency)
+    }
+
+    /// Returns all recorded stage latencies in recording order.
+    pub fn latencies(&self) -> &[(String, Duration)] {
+        &self.latencies
+    }
+
+    /// Adds a hint. Duplicate hints are ignored.
+    pub fn add_hint(&mut self, hint: impl Into<String>) {
+        let hint = hint.into();
+        if !self.hints.contains(&hint) {
+            self.hints.push(hint);
+        }
+    }
+
+    /// Returns all hints collected during detection.
+    pub fn hints(&self) -> &[String] {
+        &self.hints
+    }
+
+    /// Marks detection as cancelled.
+    ///
+    /// The GPUs found so far are kept; the report records that the list
+    /// may be incomplete.
+    pub fn mark_cancelled(&mut self) {
+        self.cancelled = true;
+    }
+
+    /// Returns `true` if detection was cancelled and its results are partial.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled
+    }
+
+    /// Merges another report into this one.
+    pub fn merge(&mut self, other: DetectionReport) {
+        self.cancelled |= other.cancelled;
+        for (stage, latency) in other.latencies {
+            self.record_latency(stage, latency);
+        }
+        for hint in other.hints {
+            self.add_hint(hint);
+        }
+    }
+
+    /// Returns `true` if nothing has been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.latencies.is_empty() && self.hints.is_empty() && !self.cancelled
+    }
+
+    /// Clears all recorded data.
+    pub fn clear(&mut self) {
+        self.latencies.clear();
+        self.hints.clear();
+        self.cancelled = false;
+    }
+}
@@ -26,6 +26,18 @@ pub struct ExtendedGpuInfo {
 
     /// Performance information
     pub performance_info: PerformanceInfo,
+
+    /// Whether driver persistence mode is enabled (NVIDIA on Linux)
+    pub persistence_mode: Option<bool>,
+
+    /// CUDA compute capability as `(major, minor)` (NVIDIA only)
+    pub compute_capability: Option<(u32, u32)>,
+
+    /// Metal device capabilities (macOS with the `macos-metal` feature)
+    pub metal_info: MetalInfo,
+
+    /// SmartShift power split between APU and dGPU (AMD laptops on Linux)
+    pub smartshift: Option<SmartShiftInfo>,
 }
 /// Cooling system information
 #[derive(Debug, Clone, PartialEq, Default)]
@@ -115,19 +127,63 @@ pub struct MemoryInfo {
     /// ECC status (if supported)
     pub ecc_enabled: Option<bool>,
 
-    /// Number of ECC errors
-    pub ecc_errors: Option<u64>,
+    /// Retired pages and remapped rows
+    pub memory_errors: MemoryErrorCounts,
+}
+/// GPU memory errors the driver contained by retiring or remapping memory
+///
+/// Reported by NVML on NVIDIA GPUs, and on Windows for GPUs of any vendor
+/// through the WHEA hardware error events logged for the GPU; other vendor
+/// APIs plug in through [`MemoryErrorSource`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MemoryErrorCounts {
+    /// Pages retired after single- or double-bit ECC errors
+    pub retired_pages: Option<u64>,
+
+    /// DRAM rows remapped after errors (NVIDIA Ampere and newer)
+    pub remapped_rows: Option<u64>,
+
+    /// Whether a row remapping waits for the next GPU reset
+    pub pending_remapping: Option<bool>,
+
+    /// Hardware error events the Windows WHEA logger recorded for the GPU's
+    /// PCI device
+    pub whea_errors: Option<u64>,
+}
+impl MemoryErrorCounts {
+    /// Returns retired pages plus remapped rows plus WHEA errors, or `None`
+    /// if none of them is known
+    pub fn total(&self) -> Option<u64> {
+        [self.retired_pages, self.remapped_rows, self.whea_errors]
+            .into_iter()
+            .flatten()
+            .reduce(|total, count| total + count)
+    }
+}
+/// Vendor API that reports [`MemoryErrorCounts`]
+pub trait MemoryErrorSource {
+    /// Reads the memory error counts of the GPU at `gpu_index`
+    ///
+    /// Returns `None` when the GPU or its driver does not report them.
+    fn memory_errors(&self, gpu_index: u32) -> Option<MemoryErrorCounts>;
 }
 /// Connection information
 #[derive(Debug, Clone, PartialEq, Default)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ConnectionInfo {
-    /// PCIe generation (3, 4, 5, etc.)
+    /// Negotiated PCIe generation (3, 4, 5, etc.)
     pub pcie_generation: Option<u8>,
 
-    /// PCIe lane width (x1, x4, x8, x16)
+    /// Negotiated PCIe lane width (x1, x4, x8, x16)
     pub pcie_width: Option<u8>,
 
+    /// Highest PCIe generation the device and slot support
+    pub pcie_generation_max: Option<u8>,
+
+    /// Widest PCIe link the device and slot support
+    pub pcie_width_max: Option<u8>,
+
     /// Current PCIe throughput in GB/s
     pub pcie_throughput_gb_s: Option<f32>,
 
@@ -148,6 +204,78 @@ pub struct ConnectionInfo {
 
     /// Subsystem ID
     pub subsystem_id: Option<String>,
+
+    /// PCIe link replays since boot (NVIDIA)
+    pub pcie_replay_count: Option<u64>,
+
+    /// Correctable PCIe AER errors of the device at `bus_id` (Linux)
+    pub pcie_aer_correctable: Option<PcieAerCounters>,
+}
+/// PCIe Advanced Error Reporting counters
+///
+/// Parsed from `/sys/bus/pci/devices/<bus_id>/aer_dev_correctable` on Linux.
+/// A steadily rising total points to a marginal link, such as a flaky riser.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PcieAerCounters {
+    /// Total errors since boot
+    pub total: u64,
+
+    /// Errors per type, e.g. `("BadTLP", 3)`, in the order the kernel lists them
+    pub by_type: Vec<(String, u64)>,
+}
+impl ConnectionInfo {
+    /// Returns the number of PCIe errors seen on the link
+    ///
+    /// Uses the AER correctable total when available, which already counts
+    /// replays, and falls back to [`pcie_replay_count`](Self::pcie_replay_count).
+    pub fn pcie_error_count(&self) -> Option<u64> {
+        self.pcie_aer_correctable
+            .as_ref()
+            .map(|aer| aer.total)
+            .or(self.pcie_replay_count)
+    }
+    /// Returns whether the link runs narrower than its maximum width
+    ///
+    /// `None` if the width or its maximum is unknown. A narrower link, such
+    /// as x8 where the GPU and slot support x16, points to a bad seat or a
+    /// lane-sharing slot. The generation is not compared: GPUs drop to a
+    /// lower generation, often Gen1, when idle to save power, so a slower
+    /// generation is only meaningful under load and is left to the caller,
+    /// e.g. through [`format_pcie_link_max()`](Self::format_pcie_link_max).
+    ///
+    /// # Example
+    /// ```
+    /// use gpu_info::extended_info::ConnectionInfo;
+    /// let link = ConnectionInfo {
+    ///     pcie_generation: Some(1),
+    ///     pcie_width: Some(16),
+    ///     pcie_generation_max: Some(4),
+    ///     pcie_width_max: Some(16),
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(link.pcie_link_degraded(), Some(false));
+    /// let link = ConnectionInfo {
+    ///     pcie_width: Some(8),
+    ///     ..link
+    /// };
+    /// assert_eq!(link.pcie_link_degraded(), Some(true));
+    /// ```
+    pub fn pcie_link_degraded(&self) -> Option<bool> {
+        Some(self.pcie_width? < self.pcie_width_max?)
+    }
+    /// Returns the negotiated link, e.g. "PCIe 4.0 x16"
+    ///
+    /// Either part is left out if unknown; "N/A" if both are.
+    pub fn format_pcie_link(&self) -> String {
+        format_pcie_link(self.pcie_generation, self.pcie_width)
+    }
+    /// Returns the maximum link, e.g. "PCIe 4.0 x16"
+    ///
+    /// Either part is left out if unknown; "N/A" if both are.
+    pub fn format_pcie_link_max(&self) -> String {
+        format_pcie_link(self.pcie_generation_max, self.pcie_width_max)
+    }
 }
 /// Extended thermal information
 #[derive(Debug, Clone, PartialEq, Default)]
@@ -268,6 +396,48 @@ pub struct OverclockingInfo {
     /// Maximum voltage limit
     pub max_voltage_limit: Option<u32>,
 }
+/// AMD SmartShift power split
+///
+/// On AMD Advantage laptops SmartShift moves the shared power budget between
+/// the APU and the discrete GPU. Read from the amdgpu `smartshift_*` sysfs
+/// files on Linux; desktop cards do not have them.
+///
+/// Displayed as `APU 130%, dGPU 0%, bias 0`, with `N/A` for unknown values.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SmartShiftInfo {
+    /// APU power as a percentage of its STAPM limit; over 100 when the APU
+    /// uses headroom of the discrete GPU
+    pub apu_power_percent: Option<f32>,
+
+    /// Discrete GPU power as a percentage of its STAPM limit
+    pub dgpu_power_percent: Option<f32>,
+
+    /// Bias towards the APU (negative) or the dGPU (positive), -100 to 100
+    pub bias: Option<i32>,
+}
+/// Metal device capabilities
+///
+/// Only filled by the Metal backend on macOS when the `macos-metal` feature
+/// is enabled; all fields are `None` elsewhere.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MetalInfo {
+    /// Highest supported `MTLGPUFamily`, e.g. "Apple8" or "Mac2"
+    pub metal_gpu_family: Option<String>,
+
+    /// Hardware ray tracing support
+    pub supports_raytracing: Option<bool>,
+
+    /// Maximum length of a single buffer in bytes
+    pub max_buffer_length: Option<u64>,
+
+    /// Recommended maximum working set size in bytes
+    pub recommended_max_working_set: Option<u64>,
+
+    /// Whether the GPU shares memory with the CPU
+    pub has_unified_memory: Option<bool>,
+}
 impl ExtendedGpuInfo {
     /// Creates ExtendedGpuInfo from basic GpuInfo
     pub fn from_basic(gpu_info: GpuInfo) -> Self {
@@ -279,6 +449,10 @@ impl ExtendedGpuInfo {
             connection_info: ConnectionInfo::default(),
             thermal_info: ThermalInfo::default(),
             performance_info: PerformanceInfo::default(),
+            persistence_mode: None,
+            compute_capability: None,
+            metal_info: MetalInfo::default(),
+            smartshift: None,
         }
     }
     /// Creates an unknown ExtendedGpuInfo
@@ -291,12 +465,33 @@ impl ExtendedGpuInfo {
             connection_info: ConnectionInfo::default(),
             thermal_info: ThermalInfo::default(),
             performance_info: PerformanceInfo::default(),
+            persistence_mode: None,
+            compute_capability: None,
+            metal_info: MetalInfo::default(),
+            smartshift: None,
         }
     }
     /// Returns basic information
     pub fn base(&self) -> &GpuInfo {
         &self.base_info
     }
+    /// Returns the compute capability formatted as `major.minor`.
+    ///
+    /// If unknown, returns "N/A".
+    ///
+    /// # Example
+    /// ```
+    /// use gpu_info::ExtendedGpuInfo;
+    /// let mut info = ExtendedGpuInfo::unknown();
+    /// info.compute_capability = Some((8, 6));
+    /// assert_eq!(info.format_compute_capability(), "8.6");
+    /// ```
+    pub fn format_compute_capability(&self) -> String {
+        match self.compute_capability {
+            Some((major, minor)) => format!("{}.{}", major, minor),
+            None => "N/A".to_string(),
+        }
+    }
     /// Returns overall cooling efficiency
     pub fn cooling_efficiency(&self) -> Option<f32> {
         if let (Some(temp), Some(fan_speed)) = (
@@ -338,9 +533,9 @@ impl ExtendedGpuInfo {
                 score -= 10.0;
             }
         }
-        if let Some(ecc_errors) = self.memory_info.ecc_errors {
+        if let Some(ecc) = self.base_info.ecc_errors {
             factors += 1;
-            if ecc_errors > 0 {
+            if ecc.double_bit_uncorrected > 0 {
                 score -= 20.0;
             }
         }
@@ -355,6 +550,87 @@ impl ExtendedGpuInfo {
         self.health_score() < 70.0
     }
 }
+impl MetalInfo {
+    /// Returns the Metal GPU family, or "N/A" if unknown.
+    pub fn format_gpu_family(&self) -> String {
+        self.metal_gpu_family
+            .clone()
+            .unwrap_or_else(|| "N/A".to_string())
+    }
+    /// Returns "Yes" or "No" for ray tracing support, or "N/A" if unknown.
+    pub fn format_raytracing(&self) -> String {
+        format_flag(self.supports_raytracing)
+    }
+    /// Returns the maximum buffer length in gigabytes.
+    ///
+    /// If unknown, returns "N/A".
+    ///
+    /// # Example
+    /// ```
+    /// use gpu_info::extended_info::MetalInfo;
+    /// let info = MetalInfo {
+    ///     max_buffer_length: Some(8 * 1024 * 1024 * 1024),
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(info.format_max_buffer_length(), "8.00 GB");
+    /// ```
+    pub fn format_max_buffer_length(&self) -> String {
+        format_bytes_gb(self.max_buffer_length)
+    }
+    /// Returns the recommended maximum working set in gigabytes.
+    ///
+    /// If unknown, returns "N/A".
+    pub fn format_recommended_max_working_set(&self) -> String {
+        format_bytes_gb(self.recommended_max_working_set)
+    }
+    /// Returns "Yes" or "No" for unified memory, or "N/A" if unknown.
+    pub fn format_unified_memory(&self) -> String {
+        format_flag(self.has_unified_memory)
+    }
+    /// Returns `true` if no capability is known
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+fn format_flag(flag: Option<bool>) -> String {
+    match flag {
+        Some(true) => "Yes".to_string(),
+        Some(false) => "No".to_string(),
+        None => "N/A".to_string(),
+    }
+}
+fn format_pcie_link(generation: Option<u8>, width: Option<u8>) -> String {
+    match (generation, width) {
+        (Some(generation), Some(width)) => format!("PCIe {}.0 x{}", generation, width),
+        (Some(generation), None) => format!("PCIe {}.0", generation),
+        (None, Some(width)) => format!("PCIe x{}", width),
+        (None, None) => "N/A".to_string(),
+    }
+}
+fn format_bytes_gb(bytes: Option<u64>) -> String {
+    match bytes {
+        Some(bytes) => format!("{:.2} GB", bytes as f64 / (1024.0 * 1024.0 * 1024.0)),
+        None => "N/A".to_string(),
+    }
+}
+impl Display for SmartShiftInfo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let percent = |power: Option<f32>| match power {
+            Some(power) => format!("{}%", power),
+            None => "N/A".to_string(),
+        };
+        let bias = self
+            .bias
+            .map_or_else(|| "N/A".to_string(), |bias| bias.to_string());
+        write!(
+            f,
+            "APU {}, dGPU {}, bias {}",
+            percent(self.apu_power_percent),
+            percent(self.dgpu_power_percent),
+            bias
+        )
+    }
+}
 impl Display for ExtendedGpuInfo {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         writeln!(f, "Extended GPU Information")?;
@@ -373,6 +649,37 @@ impl Display for ExtendedGpuInfo {
         if let Some(fan_speed) = self.fan_info.fan_speed_percent {
             writeln!(f, "Fan Speed: {:.1}%", fan_speed)?;
         }
+        if self.connection_info.pcie_generation.is_some()
+            || self.connection_info.pcie_width.is_some()
+        {
+            write!(f, "PCIe Link: {}", self.connection_info.format_pcie_link())?;
+            if self.connection_info.pcie_link_degraded() == Some(true) {
+                write!(f, " (max {})", self.connection_info.format_pcie_link_max())?;
+            }
+            writeln!(f)?;
+        }
+        if let Some(smartshift) = &self.smartshift {
+            writeln!(f, "SmartShift: {}", smartshift)?;
+        }
+        if !self.metal_info.is_empty() {
+            writeln!(f, "Metal Family: {}", self.metal_info.format_gpu_family())?;
+            writeln!(f, "Ray Tracing: {}", self.metal_info.format_raytracing())?;
+            writeln!(
+                f,
+                "Max Buffer Length: {}",
+                self.metal_info.format_max_buffer_length()
+            )?;
+            writeln!(
+                f,
+                "Recommended Working Set: {}",
+                self.metal_info.format_recommended_max_working_set()
+            )?;
+            writeln!(
+                f,
+                "Unified Memory: {}",
+                self.metal_info.format_unified_memory()
+            )?;
+        }
         Ok(())
     }
 }
@@ -391,7 +698,13 @@ pub trait GpuInfoExtensions {
     /// Converts this GPU info into an extended GPU info struct.
     ///
     /// This method consumes the original `GpuInfo` and returns an
-    /// `ExtendedGpuInfo` with the base information populated.
+    /// `ExtendedGpuInfo` with the base information populated. On Linux, AMD
+    /// GPUs get their [`SmartShiftInfo`] from sysfs, and GPUs of any vendor
+    /// with a known bus id their PCIe link state and AER counters. On macOS with the `macos-metal` feature,
+    /// [`MetalInfo`] is filled from the Metal device with the same name. On
+    /// Windows, NVIDIA GPUs get their PCIe link state from the NVML device at
+    /// their bus id. [`MemoryErrorCounts`] are read as by
+    /// [`memory_error_counts()`](Self::memory_error_counts).
     fn to_extended(self) -> ExtendedGpuInfo;
 
     /// Enhances this GPU info with extended information.
@@ -403,10 +716,65 @@ pub trait GpuInfoExtensions {
     ///
     /// Returns an error if the extended information cannot be retrieved.
     fn enhance(&mut self) -> Result<()>;
+
+    /// Returns whether the PCIe link runs narrower than its maximum
+    ///
+    /// Reads only the link state, from the same source as
+    /// [`to_extended()`](Self::to_extended), and compares it with
+    /// [`ConnectionInfo::pcie_link_degraded()`]. `None` if the link is
+    /// unknown, which is the default for other implementors.
+    fn pcie_link_degraded(&self) -> Option<bool> {
+        None
+    }
+
+    /// Returns the number of PCIe errors seen on the link
+    ///
+    /// Reads only the error counters, from the same source as
+    /// [`to_extended()`](Self::to_extended), and returns
+    /// [`ConnectionInfo::pcie_error_count()`]. `None` if no counter is
+    /// readable, which is the default for other implementors.
+    fn pcie_error_count(&self) -> Option<u64> {
+        None
+    }
+
+    /// Returns the memory errors contained on the GPU
+    ///
+    /// NVIDIA GPUs with a known bus id are read from the NVML device at that
+    /// bus id, through a client shared with later calls. On Windows, the
+    /// WHEA hardware error events logged for the GPU's bus id are counted
+    /// too. `None` if no count is known, which is the default for other
+    /// implementors.
+    fn memory_error_counts(&self) -> Option<MemoryErrorCounts> {
+        None
+    }
 }
 impl GpuInfoExtensions for GpuInfo {
     fn to_extended(self) -> ExtendedGpuInfo {
-        ExtendedGpuInfo::from_basic(self)
+        #[cfg(all(target_os = "macos", feature = "vendor-apple", feature = "macos-metal"))]
+        let metal_info = crate::providers::macos::backends::MetalBackend::new()
+            .ok()
+            .and_then(|backend| backend.metal_info(self.name_gpu.as_deref()));
+        #[allow(unused_mut)]
+        let mut extended = ExtendedGpuInfo::from_basic(self);
+        #[cfg(all(target_os = "macos", feature = "vendor-apple", feature = "macos-metal"))]
+        if let Some(metal_info) = metal_info {
+            extended.metal_info = metal_info;
+        }
+        #[cfg(all(target_os = "linux", feature = "vendor-amd"))]
+        if extended.base_info.vendor == crate::vendor::Vendor::Amd {
+            if let Some(bus_id) = extended.base_info.bus_id.as_deref() {
+                extended.smartshift = crate::providers::linux::amd::smartshift(
+                    &std::path::Path::new(crate::providers::linux::pcie::PCI_DEVICES_PATH)
+                        .join(bus_id),
+                );
+            }
+        }
+        fill_pcie_link(&extended.base_info, &mut extended.connection_info);
+        fill_pcie_errors(&extended.base_info, &mut extended.connection_info);
+        if let Some(errors) = extended.base_info.memory_error_counts() {
+            extended.memory_info.memory_errors = errors;
+        }
+        extended
     }
     fn enhance(&mut self) -> Result<()> {
         match self.vendor {
@@ -417,4 +785,76 @@ impl GpuInfoExtensions for GpuInfo {
             _ => Ok(()),
         }
     }
+    fn pcie_link_degraded(&self) -> Option<bool> {
+        let mut connection = ConnectionInfo::default();
+        fill_pcie_link(self, &mut connection);
+        connection.pcie_link_degraded()
+    }
+    fn pcie_error_count(&self) -> Option<u64> {
+        let mut connection = ConnectionInfo::default();
+        fill_pcie_errors(self, &mut connection);
+        connection.pcie_error_count()
+    }
+    fn memory_error_counts(&self) -> Option<MemoryErrorCounts> {
+        let bus_id = self.bus_id.as_deref()?;
+        #[allow(unused_mut)]
+        let mut counts = MemoryErrorCounts::default();
+        #[cfg(feature = "vendor-nvidia")]
+        if self.vendor == crate::vendor::Vendor::Nvidia {
+            if let Some(nvml) = crate::nvml_api::memory_errors_by_bus_id(bus_id) {
+                counts = nvml;
+            }
+        }
+        #[cfg(target_os = "windows")]
+        {
+            counts.whea_errors = crate::providers::wmi::whea_error_count(bus_id);
+        }
+        #[cfg(not(any(feature = "vendor-nvidia", target_os = "windows")))]
+        let _ = bus_id;
+        (counts != MemoryErrorCounts::default()).then_some(counts)
+    }
+}
+/// Reads the PCIe AER counters of `gpu`, found by its PCI bus ID, into `connection`
+///
+/// Uses sysfs on Linux for GPUs of any vendor. Values that cannot be read
+/// are left unchanged.
+#[allow(unused_variables)]
+fn fill_pcie_errors(gpu: &GpuInfo, connection: &mut ConnectionInfo) {
+    #[cfg(target_os = "linux")]
+    if let Some(bus_id) = gpu.bus_id.as_deref() {
+        crate::providers::linux::pcie::fill_errors(
+            connection,
+            std::path::Path::new(crate::providers::linux::pcie::PCI_DEVICES_PATH),
+            bus_id,
+        );
+    }
+}
+/// Reads the PCIe link of `gpu`, found by its PCI bus ID, into `connection`
+///
+/// Uses sysfs on Linux and NVML for NVIDIA GPUs on Windows. Values that
+/// cannot be read are left unchanged.
+#[allow(unused_variables)]
+fn fill_pcie_link(gpu: &GpuInfo, connection: &mut ConnectionInfo) {
+    #[cfg(target_os = "linux")]
+    if let Some(bus_id) = gpu.bus_id.as_deref() {
+        crate::providers::linux::pcie::fill_link(
+            connection,
+            std::path::Path::new(crate::providers::linux::pcie::PCI_DEVICES_PATH),
+            bus_id,
+        );
+    }
+    #[cfg(all(target_os = "windows", feature = "vendor-nvidia"))]
+    if gpu.vendor == Vendor::Nvidia {
+        if let Some(link) = gpu
+            .bus_id
+            .as_deref()
+            .and_then(crate::nvml_api::pcie_link_by_bus_id)
+        {
+            connection.pcie_generation = link.pcie_generation.or(connection.pcie_generation);
+            connection.pcie_width = link.pcie_width.or(connection.pcie_width);
+            connection.pcie_generation_max =
+                link.pcie_generation_max.or(connection.pcie_generation_max);
+            connection.pcie_width_max = link.pcie_width_max.or(connection.pcie_width_max);
+        }
+    }
 }
@@ -0,0 +1,212 @@
+//! Metric-independent identity of a GPU.
+//!
+//! `GpuInfo` mixes what a GPU *is* (vendor, name, bus address) with what it
+//! is *doing* (temperature, clocks). Its `PartialEq` compares everything, so
+//! the same card read twice is usually not equal to itself, and a
+//! `HashSet<GpuInfo>` or `HashMap<GpuInfo, T>` lookup misses as soon as a
+//! metric changes. [`GpuIdentity`] holds only the identifying fields, with
+//! `Eq`, `Hash` and `Ord` that agree with each other, and is the key to use
+//! for per-GPU state.
+//!
+//! # Examples
+//!
+//! ```
+//! use gpu_info::{GpuIdentity, GpuInfo, Vendor};
+//! use std::collections::HashMap;
+//!
+//! let mut labels: HashMap<GpuIdentity, &str> = HashMap::new();
+//! let gpu = GpuInfo::builder()
+//!     .vendor(Vendor::Nvidia)
+//!     .name("NVIDIA GeForce RTX 3080")
+//!     .bus_id("0000:01:00.0")
+//!     .temperature(65.0)
+//!     .build();
+//! labels.insert(gpu.identity(), "render node");
+//!
+//! // The next sample of the same card has other metrics, but the same identity
+//! let mut later = gpu.clone();
+//! later.temperature = Some(71.0);
+//! assert_eq!(labels.get(&later.identity()), Some(&"render node"));
+//! ```
+//!
+//! # Migrating from `GpuInfo` keys
+//!
+//! `GpuInfo` still implements `Hash`, now by hashing its UUID or, without
+//! one, its vendor and name, but its `PartialEq` keeps
+//! comparing metrics, so it is not a usable map key for a live GPU. Replace
+//! `HashMap<GpuInfo, T>` with `HashMap<GpuIdentity, T>` and key with
+//! `gpu.identity()`; to find a GPU again, compare identities instead of
+//! whole `GpuInfo` values.
+use crate::gpu_info::GpuInfo;
+use crate::vendor::Vendor;
+use std::fmt::{self, Display, Formatter};
+
+/// What identifies a GPU across samples: vendor, name, UUID and bus ID.
+///
+/// Built with [`GpuInfo::identity()`]. The name is normalized (whitespace
+/// collapsed, lowercase) and so is the PCI bus ID (lowercase, 4-digit
+/// domain), so readings of the same card from different backends agree.
+/// Two cards of the same model are told apart by their UUID or bus ID; when
+/// a backend reports neither, they share an identity.
+///
+/// Ordering follows vendor, then name, UUID and bus ID, with unknown values
+/// first.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GpuIdentity {
+    vendor: Vendor,
+    name: Option<String>,
+    uuid: Option<String>,
+    bus_id: Option<String>,
+}
+
+impl GpuIdentity {
+    /// Creates an identity from a vendor and a GPU name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gpu_info::{GpuIdentity, Vendor};
+    ///
+    /// let a = GpuIdentity::new(Vendor::Amd, Some("AMD  Radeon RX 7900 XTX"));
+    /// let b = GpuIdentity::new(Vendor::Amd, Some("amd radeon rx 7900 xtx"));
+    /// assert_eq!(a, b);
+    /// assert_eq!(a.name(), Some("amd radeon rx 7900 xtx"));
+    /// ```
+    pub fn new(vendor: Vendor, name: Option<&str>) -> Self {
+        Self {
+            vendor,
+            name: name.and_then(normalize_name),
+            uuid: None,
+            bus_id: None,
+        }
+    }
+
+    /// Sets the device UUID, e.g. `GPU-5a8f...` from NVML.
+    pub fn with_uuid(mut self, uuid: &str) -> Self {
+        self.uuid = normalize_uuid(uuid);
+        self
+    }
+
+    /// Sets the PCI bus ID, e.g. `0000:01:00.0`.
+    pub fn with_bus_id(mut self, bus_id: &str) -> Self {
+        self.bus_id = normalize_bus_id(bus_id);
+        self
+    }
+
+    /// Returns the GPU vendor.
+    pub fn vendor(&self) -> Vendor {
+        self.vendor
+    }
+
+    /// Returns the normalized GPU name.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Returns the normalized device UUID.
+    pub fn uuid(&self) -> Option<&str> {
+        self.uuid.as_deref()
+    }
+
+    /// Returns the normalized PCI bus ID.
+    pub fn bus_id(&self) -> Option<&str> {
+        self.bus_id.as_deref()
+    }
+}
+
+impl From<&GpuInfo> for GpuIdentity {
+    fn from(gpu: &GpuInfo) -> Self {
+        gpu.identity()
+    }
+}
+
+impl Display for GpuIdentity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.vendor)?;
+        if let Some(name) = &self.name {
+            write!(f, " {}", name)?;
+        }
+        match (&self.bus_id, &self.uuid) {
+            (Some(bus_id), _) => write!(f, " @ {}", bus_id),
+            (None, Some(uuid)) => write!(f, " ({})", uuid),
+            (None, None) => Ok(()),
+        }
+    }
+}
+
+/// Collapses whitespace and lowercases a GPU name.
+pub(crate) fn normalize_name(name: &str) -> Option<String> {
+    let name = name.split_whitespace().collect::<Vec<_>>().join(" ");
+    (!name.is_empty()).then(|| name.to_lowercase())
+}
+
+fn normalize_uuid(uuid: &str) -> Option<String> {
+    let uuid = uuid.trim();
+    (!uuid.is_empty()).then(|| uuid.to_ascii_lowercase())
+}
+
+/// Returns `true` if `bus_id` has the `domain:bus:device.function` form.
+pub(crate) fn is_bus_id(bus_id: &str) -> bool {
+    let parts: Vec<&str> = bus_id.split([':', '.']).collect();
+    parts.len() == 4
+        && parts
+            .iter()
+            .all(|part| !part.is_empty() && part.bytes().all(|b| b.is_ascii_hexdigit()))
+}
+
+/// Lowercases a PCI address and shortens NVML's 8-digit domain to 4 digits.
+pub(crate) fn normalize_bus_id(bus_id: &str) -> Option<String> {
+    let bus_id = bus_id.trim().to_ascii_lowercase();
+    if bus_id.is_empty() {
+        return None;
+    }
+    match bus_id.split_once(':') {
+        Some((domain, rest)) if domain.len() > 4 && domain.starts_with("0000") => {
+            match domain.get(domain.len() - 4..) {
+                Some(short) => Some(format!("{}:{}", short, rest)),
+                None => Some(bus_id.clone()),
+            }
+        }
+        _ => Some(bus_id),
+    }
+}
+
+/// Reorders per-GPU `state` to follow the identities of `gpus`.
+///
+/// `identities` holds the identity each entry of `state` belongs to. After
+/// the call, entry `i` of both belongs to `gpus[i]`: state is carried over
+/// from the entry with the same identity, with duplicates matched in order,
+/// and GPUs seen for the first time get `new()`. State of GPUs that are no
+/// longer listed is dropped.
+pub(crate) fn realign<T>(
+    identities: &mut Vec<Option<GpuIdentity>>,
+    state: &mut Vec<T>,
+    gpus: &[GpuInfo],
+    mut new: impl FnMut() -> T,
+) {
+    let current: Vec<GpuIdentity> = gpus.iter().map(GpuInfo::identity).collect();
+    let unchanged = state.len() == current.len()
+        && identities.len() == current.len()
+        && identities
+            .iter()
+            .zip(&current)
+            .all(|(old, identity)| old.as_ref() == Some(identity));
+    if unchanged {
+        return;
+    }
+    let mut previous: Vec<Option<(Option<GpuIdentity>, T)>> = identities
+        .drain(..)
+        .zip(state.drain(..))
+        .map(Some)
+        .collect();
+    for identity in current {
+        let carried = previous
+            .iter_mut()
+            .find(|slot| matches!(slot, Some((Some(old), _)) if *old == identity))
+            .and_then(Option::take)
+            .map(|(_, value)| value);
+        state.push(carried.unwrap_or_else(&mut new));
+        identities.push(Some(identity));
+    }
+}
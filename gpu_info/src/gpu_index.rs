@@ -0,0 +1,122 @@
+//! Typed GPU indices.
+//!
+//! A GPU has different indices depending on who counts: [`GpuManager`]
+//! numbers every detected GPU, while CUDA only counts the NVIDIA GPUs it can
+//! see. Both used to be plain `usize`, so a CUDA device ordinal could be
+//! passed to the manager and silently select the wrong card. [`GpuIndex`]
+//! and [`CudaIndex`] keep the two apart; converting between them goes
+//! through [`GpuManager::cuda_to_gpu_index()`].
+//!
+//! [`GpuManager`]: crate::GpuManager
+//! [`GpuManager::cuda_to_gpu_index()`]: crate::GpuManager::cuda_to_gpu_index
+use std::collections::BTreeMap;
+use std::fmt::{self, Display, Formatter};
+
+/// Position of a GPU in the [`GpuManager`](crate::GpuManager) list.
+///
+/// Neither a plain `usize` nor a [`CudaIndex`] converts into a `GpuIndex`;
+/// the index must be spelled out, e.g. `GpuIndex(1)`.
+///
+/// # Examples
+///
+/// ```
+/// use gpu_info::GpuIndex;
+///
+/// let index = GpuIndex(1);
+/// assert_eq!(index.get(), 1);
+/// assert_eq!(index.to_string(), "#1");
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct GpuIndex(pub usize);
+
+impl GpuIndex {
+    /// Returns the raw index
+    pub fn get(self) -> usize {
+        self.0
+    }
+}
+
+impl From<GpuIndex> for usize {
+    fn from(index: GpuIndex) -> Self {
+        index.0
+    }
+}
+
+impl Display for GpuIndex {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "#{}", self.0)
+    }
+}
+
+/// CUDA device ordinal, as used by `cudaSetDevice()` and most ML frameworks.
+///
+/// Only counts the NVIDIA GPUs visible to CUDA, so it generally differs from
+/// the [`GpuIndex`] of the same card.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct CudaIndex(pub usize);
+
+impl CudaIndex {
+    /// Returns the raw device ordinal
+    pub fn get(self) -> usize {
+        self.0
+    }
+}
+
+impl From<usize> for CudaIndex {
+    fn from(ordinal: usize) -> Self {
+        Self(ordinal)
+    }
+}
+
+impl From<CudaIndex> for usize {
+    fn from(ordinal: CudaIndex) -> Self {
+        ordinal.0
+    }
+}
+
+impl Display for CudaIndex {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "cuda:{}", self.0)
+    }
+}
+
+/// Maps CUDA device ordinals to manager indices
+///
+/// `nvidia` lists the manager indices of the NVIDIA GPUs in NVML order,
+/// which is PCI bus order. CUDA uses the same order with
+/// `CUDA_DEVICE_ORDER=PCI_BUS_ID`. `visible_devices` is the value of
+/// `CUDA_VISIBLE_DEVICES`; like CUDA, the list is cut at the first entry
+/// that is not a valid, unused ordinal. Entries given as GPU UUIDs are not
+/// supported and end the list as well.
+pub(crate) fn cuda_index_map(
+    nvidia: &[GpuIndex],
+    visible_devices: Option<&str>,
+) -> BTreeMap<CudaIndex, GpuIndex> {
+    let Some(visible_devices) = visible_devices else {
+        return nvidia
+            .iter()
+            .enumerate()
+            .map(|(ordinal, index)| (CudaIndex(ordinal), *index))
+            .collect();
+    };
+    let mut map = BTreeMap::new();
+    let mut used = Vec::new();
+    for entry in visible_devices.split(',') {
+        let Some(physical) = entry.trim().parse::<usize>().ok() else {
+            break;
+        };
+        let Some(index) = nvidia.get(physical) else {
+            break;
+        };
+        if used.contains(&physical) {
+            break;
+        }
+        used.push(physical);
+        map.insert(CudaIndex(map.len()), *index);
+    }
+    map
+}
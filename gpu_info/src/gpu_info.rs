@@ -1,6 +1,62 @@
+use crate::cancel::CancelToken;
+use crate::detection_report::DetectionReport;
+use crate::gpu_identity::{normalize_name, GpuIdentity};
+use crate::metric_source::{Metric, MetricSources};
+use crate::process_usage::GpuProcessInfo;
 use crate::vendor::Vendor;
-use std::fmt::{Debug, Display, Formatter};
+use std::collections::BTreeMap;
+use std::fmt::{Debug, Display, Formatter, Write};
 use std::hash::{Hash, Hasher};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Sorts `gpus` by [`GpuInfo::sort_key()`].
+///
+/// The resulting order depends only on identity fields, not on metrics or on
+/// the order in which providers reported the GPUs, which makes it suitable
+/// for snapshot comparisons. The sort is stable, so GPUs with equal keys keep
+/// their relative order.
+///
+/// # Examples
+///
+/// ```
+/// use gpu_info::{sort_stable, GpuInfo};
+///
+/// let mut gpus = vec![GpuInfo::unknown(), GpuInfo::mock_nvidia(), GpuInfo::mock_amd()];
+/// sort_stable(&mut gpus);
+/// assert_eq!(gpus[0], GpuInfo::mock_amd());
+/// assert_eq!(gpus[2], GpuInfo::unknown());
+/// ```
+pub fn sort_stable(gpus: &mut [GpuInfo]) {
+    gpus.sort_by_cached_key(|gpu| gpu.sort_key());
+}
+
+/// Appends `value` to `out`, backslash-escaping `special` characters as
+/// required by the InfluxDB line protocol.
+fn push_influx_escaped(out: &mut String, value: &str, special: &[char]) {
+    for c in value.chars() {
+        if special.contains(&c) {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+}
+
+/// Vendor name used by [`GpuInfo::sort_key()`]; kept stable across releases.
+fn vendor_sort_name(vendor: Vendor) -> &'static str {
+    use crate::vendor::{IntelGpuType, VirtualKind};
+    match vendor {
+        Vendor::Amd => "amd",
+        Vendor::Apple => "apple",
+        Vendor::Intel(IntelGpuType::Discrete) => "intel-discrete",
+        Vendor::Intel(IntelGpuType::Integrated) => "intel-integrated",
+        Vendor::Intel(IntelGpuType::Unknown) => "intel",
+        Vendor::Nvidia => "nvidia",
+        Vendor::Virtual(VirtualKind::Qxl) => "virtual-qxl",
+        Vendor::Virtual(VirtualKind::Virtio) => "virtual-virtio",
+        Vendor::Virtual(VirtualKind::VmwareSvga) => "virtual-vmware-svga",
+        Vendor::Unknown => "unknown",
+    }
+}
 
 /// Errors that can occur when working with GPU information.
 ///
@@ -24,6 +80,12 @@ pub enum GpuError {
     /// Invalid memory value (expected 0-131072 MB).
     #[error("Invalid memory value: {0}")]
     InvalidMemory(u32),
+    /// Invalid fan speed value (expected 0-100%).
+    #[error("Invalid fan speed value: {0}")]
+    InvalidFanSpeed(u32),
+    /// Invalid PCI bus ID (expected `domain:bus:device.function` in hex).
+    #[error("Invalid PCI bus ID: {0}")]
+    InvalidBusId(String),
     /// No GPU was found in the system.
     #[error("GPU not found")]
     GpuNotFound,
@@ -42,6 +104,15 @@ pub enum GpuError {
     /// FFI operation failed.
     #[error("FFI error: {0}")]
     Ffi(String),
+    /// GPU detection is not supported, e.g. on an unsupported platform.
+    #[error("Unsupported: {0}")]
+    Unsupported(String),
+    /// Serializing GPU information failed.
+    #[error("Serialization error: {0}")]
+    Serialization(String),
+    /// Uncorrected (double-bit) ECC errors exceed the allowed count.
+    #[error("Uncorrected ECC errors: {0}")]
+    EccError(u64),
 }
 
 /// A specialized `Result` type for GPU operations.
@@ -67,10 +138,54 @@ pub type Result<T> = std::result::Result<T, GpuError>;
 pub trait GpuProvider: Send + Sync {
     /// Detect all GPUs provided by this provider
     fn detect_gpus(&self) -> Result<Vec<GpuInfo>>;
+    /// Detect GPUs, stopping early when `token` is cancelled
+    ///
+    /// Providers that probe several devices or backends should check the
+    /// token between them and return the GPUs found so far. Diagnostics go
+    /// into the report. The default implementation skips detection if the
+    /// token is already cancelled and otherwise calls
+    /// [`detect_gpus()`](Self::detect_gpus).
+    fn detect_gpus_with_cancel(
+        &self,
+        token: &CancelToken,
+        _report: &mut DetectionReport,
+    ) -> Result<Vec<GpuInfo>> {
+        if token.is_cancelled() {
+            return Ok(Vec::new());
+        }
+        self.detect_gpus()
+    }
     /// Update the information for a specific GPU
     fn update_gpu(&self, gpu: &mut GpuInfo) -> Result<()>;
+    /// Update only `metrics` of a specific GPU
+    ///
+    /// Used by [`GpuManager`](crate::GpuManager) to refresh metrics whose
+    /// per-metric TTL expired. Providers that read each metric separately,
+    /// e.g. from its own sysfs file, override it to skip the other reads.
+    /// Other fields may be left unchanged or refreshed as well. The default
+    /// implementation calls [`update_gpu()`](Self::update_gpu).
+    fn update_metrics(&self, gpu: &mut GpuInfo, _metrics: &[Metric]) -> Result<()> {
+        self.update_gpu(gpu)
+    }
     /// Get the vendor associated with this provider
     fn get_vendor(&self) -> Vendor;
+    /// Whether this provider can report `metric`, without running detection
+    ///
+    /// Lets callers skip providers that would never fill a field. The answer
+    /// reflects the provider's configuration, not the current hardware: a
+    /// supported metric may still be `None` on a given GPU. The default
+    /// implementation returns `true`.
+    fn supports_metric(&self, _metric: Metric) -> bool {
+        true
+    }
+    /// Name of the provider, used in diagnostics
+    ///
+    /// The default implementation returns the type name without its module
+    /// path, e.g. `NvidiaLinuxProvider`.
+    fn name(&self) -> &str {
+        let name = std::any::type_name::<Self>();
+        name.rsplit("::").next().unwrap_or(name)
+    }
 }
 /// Handle empty vector result by converting to Result
 ///
@@ -105,6 +220,7 @@ pub fn handle_empty_result<T>(items: Vec<T>) -> Result<Vec<T>> {
 ///
 /// This function eliminates duplication in provider implementations where
 /// they need to update a single GPU from a list of GPUs obtained from API.
+/// The reading for `gpu` is picked by [`matching_gpu()`].
 ///
 /// # Arguments
 /// * `gpu` - Mutable reference to GPU to update
@@ -126,12 +242,46 @@ where
     F: FnOnce() -> Vec<GpuInfo>,
 {
     let gpus = api_gpus_fn();
-    if let Some(updated_gpu) = gpus.first() {
-        *gpu = updated_gpu.clone();
-        Ok(())
-    } else {
-        Err(GpuError::GpuNotActive)
+    if gpus.is_empty() {
+        return Err(GpuError::GpuNotActive);
+    }
+    let updated_gpu = matching_gpu(gpu, &gpus).ok_or(GpuError::GpuNotFound)?;
+    *gpu = updated_gpu.clone();
+    Ok(())
+}
+/// Picks the reading in `candidates` that belongs to `gpu`
+///
+/// GPUs are matched by PCI bus ID, then by UUID, so identical cards are told
+/// apart. Only if neither is known on both sides does the name decide,
+/// case-insensitively, and then the first candidate.
+///
+/// Returns `None` if `gpu` has a bus ID or UUID that the candidates report
+/// for other GPUs only, e.g. after the card was removed.
+pub fn matching_gpu<'a>(gpu: &GpuInfo, candidates: &'a [GpuInfo]) -> Option<&'a GpuInfo> {
+    let identity = gpu.identity();
+    let identities: Vec<GpuIdentity> = candidates.iter().map(GpuInfo::identity).collect();
+    let keys: [fn(&GpuIdentity) -> Option<&str>; 2] = [GpuIdentity::bus_id, GpuIdentity::uuid];
+    for key in keys {
+        let Some(wanted) = key(&identity) else {
+            continue;
+        };
+        if identities.iter().any(|candidate| key(candidate).is_some()) {
+            return identities
+                .iter()
+                .position(|candidate| key(candidate) == Some(wanted))
+                .map(|index| &candidates[index]);
+        }
     }
+    let name = gpu.name_gpu.as_deref();
+    candidates
+        .iter()
+        .find(|candidate| {
+            matches!(
+                (name, candidate.name_gpu.as_deref()),
+                (Some(a), Some(b)) if a.eq_ignore_ascii_case(b)
+            )
+        })
+        .or_else(|| candidates.first())
 }
 /// Trait for formatting values into string representation.
 ///
@@ -153,18 +303,27 @@ pub trait Formattable: Debug {
 /// are either primitive types or `Option<T>` where `T` is `Send + Sync`.
 ///
 /// For concurrent access patterns, consider using `Arc<GpuInfo>` which is returned
-/// by `GpuManager::get_gpu_cached()` for zero-copy sharing.
+/// by `GpuManager::get_cached_gpu_at()` for zero-copy sharing.
 ///
 /// # Cloning
 ///
 /// Cloning a `GpuInfo` allocates memory for the `String` fields (`name_gpu` and
 /// `driver_version`). The total allocation is typically 50-200 bytes depending on
 /// string lengths. For read-only access, prefer using `Arc<GpuInfo>` from
-/// `GpuManager::get_gpu_cached()` which provides zero-copy sharing.
+/// `GpuManager::get_cached_gpu_at()` which provides zero-copy sharing.
 ///
 /// The [`clone_from()`](Clone::clone_from) method is optimized to reuse existing
 /// string allocations when possible, reducing memory churn in hot paths.
 ///
+/// # Panics
+///
+/// Accessors, `format_*` methods, [`Display`], [`validate()`](Self::validate),
+/// [`check_consistency()`](Self::check_consistency), [`identity()`](Self::identity)
+/// and the exporters never panic, whatever the field values: NaN or infinite
+/// floats, `u32::MAX` amounts and arbitrary strings are all handled. The same
+/// holds for the statistics and history aggregates built from `GpuInfo`
+/// values. Property tests exercise every such method with arbitrary values.
+///
 /// # Example
 /// ```
 /// use gpu_info::{GpuInfo, vendor::Vendor};
@@ -187,6 +346,10 @@ pub struct GpuInfo {
     pub name_gpu: Option<String>, // full GPU name
     /// The current temperature of the GPU in degrees Celsius.
     pub temperature: Option<f32>, // current GPU temperature
+    /// Readings of the individual thermal sensors in degrees Celsius, by
+    /// sensor name; see [`temperatures()`](Self::temperatures).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub temperatures: Option<BTreeMap<String, f32>>, // per-sensor temperatures
     /// The current utilization of the GPU as a percentage.
     pub utilization: Option<f32>, // current GPU utilization (%)
     /// The current power usage of the GPU in watts.
@@ -209,6 +372,404 @@ pub struct GpuInfo {
     pub driver_version: Option<String>, // driver version
     /// The maximum clock speed of the GPU in MHz.
     pub max_clock_speed: Option<u32>, // maximum GPU clock speed (MHz)
+    /// The effective core clock speed of the GPU in MHz, as actually delivered.
+    ///
+    /// Under power or thermal throttling this falls below the requested
+    /// [`core_clock`](Self::core_clock); see [`clock_deficit_percent()`](Self::clock_deficit_percent).
+    ///
+    /// On NVIDIA GPUs the requested clock is the application clock target,
+    /// and this is the current graphics clock; without a target, `core_clock`
+    /// is the current clock and this is `None`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub effective_core_clock: Option<u32>, // effective GPU core clock (MHz)
+    /// The fan speed as a percentage of the maximum (0-100).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub fan_speed: Option<u32>, // fan speed (%)
+    /// The fan speed in revolutions per minute, e.g. from hwmon `fan1_input`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub fan_speed_rpm: Option<u32>, // fan speed (RPM)
+    /// The video encoder (NVENC) utilization as a percentage.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub encoder_util: Option<f32>, // encoder utilization (%)
+    /// The video decoder (NVDEC) utilization as a percentage.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub decoder_util: Option<f32>, // decoder utilization (%)
+    /// Why the clocks are held below their maximum, e.g. from NVML.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub throttle_reasons: Option<ThrottleReasons>, // clock throttle reasons
+    /// The device UUID, e.g. `GPU-5a8f...` from NVML.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub uuid: Option<String>, // device UUID
+    /// The PCI bus ID, e.g. `0000:01:00.0`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub bus_id: Option<String>, // PCI bus address
+    /// The DXGI adapter LUID on Windows, e.g. `LUID-00000000-0000d1a3`.
+    ///
+    /// Changes on every reboot, so it is not part of the GPU's
+    /// [`identity()`](Self::identity) and is not persisted.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub adapter_luid: Option<String>, // DXGI adapter LUID (Windows, per boot)
+    /// The processes using the GPU, e.g. from NVML.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub processes: Option<Vec<GpuProcessInfo>>, // processes with work on the GPU
+    /// The ECC memory error counts, e.g. from NVML.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub ecc_errors: Option<EccStats>, // ECC error counters
+    /// The source each metric was resolved from, for diagnostics.
+    ///
+    /// Only recorded with the `debug-capture` feature; see
+    /// [`metric_source`](crate::metric_source).
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "MetricSources::is_empty")
+    )]
+    pub metric_sources: MetricSources, // winning source per metric (debug-capture)
+    /// How trustworthy the detected identity is; see [`Confidence`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub confidence: Confidence, // how the GPU was identified
+}
+
+/// How trustworthy the identity of a [`GpuInfo`] is.
+///
+/// Set by the detection path that produced the GPU, so a UI can flag data
+/// that rests on guesswork. Ordered from least to most trustworthy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Confidence {
+    /// Guessed from the GPU name, e.g. the vendor or VRAM size of a
+    /// `system_profiler` model string.
+    #[default]
+    Low,
+    /// Reported by the OS, e.g. a WMI `Win32_VideoController` entry.
+    Medium,
+    /// Read from a vendor API (NVML, ADL) or a PCI vendor ID.
+    High,
+}
+
+impl Display for Confidence {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Confidence::Low => write!(f, "low"),
+            Confidence::Medium => write!(f, "medium"),
+            Confidence::High => write!(f, "high"),
+        }
+    }
+}
+
+/// A reason the driver holds the GPU clocks below their maximum.
+///
+/// The discriminants are the bits of NVML's `nvmlClocksThrottleReason*`
+/// constants; a set of reasons is a [`ThrottleReasons`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ClockThrottleReason {
+    /// Nothing is running on the GPU, so the clocks are lowered.
+    GpuIdle = 0x1,
+    /// The clocks are limited by the application clock setting.
+    ApplicationClockSetting = 0x2,
+    /// The software power limit lowers the clocks.
+    SwPowerCap = 0x4,
+    /// A hardware signal, e.g. over-temperature or an external power brake,
+    /// slows the GPU down.
+    HwSlowdown = 0x8,
+    /// The GPU is held to the clocks of the other GPUs in its sync boost group.
+    SyncBoost = 0x10,
+    /// Software thermal slowdown keeps the GPU or memory below its maximum
+    /// operating temperature.
+    SwThermalSlowdown = 0x20,
+    /// Hardware thermal slowdown, reducing the clocks by a factor of 2 or more.
+    HwThermalSlowdown = 0x40,
+    /// The external power brake assertion slows the GPU down.
+    HwPowerBrakeSlowdown = 0x80,
+    /// The clocks are limited by the display clock setting.
+    DisplayClockSetting = 0x100,
+}
+
+impl ClockThrottleReason {
+    /// Every reason, in bit order.
+    pub const ALL: [ClockThrottleReason; 9] = [
+        ClockThrottleReason::GpuIdle,
+        ClockThrottleReason::ApplicationClockSetting,
+        ClockThrottleReason::SwPowerCap,
+        ClockThrottleReason::HwSlowdown,
+        ClockThrottleReason::SyncBoost,
+        ClockThrottleReason::SwThermalSlowdown,
+        ClockThrottleReason::HwThermalSlowdown,
+        ClockThrottleReason::HwPowerBrakeSlowdown,
+        ClockThrottleReason::DisplayClockSetting,
+    ];
+
+    /// Returns the NVML bit of the reason.
+    pub fn bit(self) -> u64 {
+        self as u64
+    }
+}
+
+impl Display for ClockThrottleReason {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(self, f)
+    }
+}
+
+/// A set of [`ClockThrottleReason`]s, stored as the NVML bitmask.
+///
+/// # Examples
+///
+/// ```
+/// use gpu_info::{ClockThrottleReason, ThrottleReasons};
+///
+/// let reasons = ThrottleReasons::from_bits(0x24);
+/// assert!(reasons.contains(ClockThrottleReason::SwPowerCap));
+/// assert!(reasons.is_throttling());
+/// assert_eq!(reasons.to_string(), "SwPowerCap, SwThermalSlowdown");
+///
+/// let idle = ThrottleReasons::from(ClockThrottleReason::GpuIdle);
+/// assert!(!idle.is_throttling());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct ThrottleReasons(u64);
+
+impl ThrottleReasons {
+    /// Creates a set from an NVML bitmask; bits of unknown reasons are kept.
+    pub fn from_bits(bits: u64) -> Self {
+        Self(bits)
+    }
+
+    /// Returns the NVML bitmask.
+    pub fn bits(self) -> u64 {
+        self.0
+    }
+
+    /// Returns `true` if no reason is set.
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns `true` if `reason` is set.
+    pub fn contains(self, reason: ClockThrottleReason) -> bool {
+        self.0 & reason.bit() != 0
+    }
+
+    /// Adds `reason` to the set.
+    pub fn insert(&mut self, reason: ClockThrottleReason) {
+        self.0 |= reason.bit();
+    }
+
+    /// Iterates over the known reasons that are set, in bit order.
+    pub fn iter(self) -> impl Iterator<Item = ClockThrottleReason> {
+        ClockThrottleReason::ALL
+            .into_iter()
+            .filter(move |reason| self.contains(*reason))
+    }
+
+    /// Returns `true` if the clocks are held back while the GPU has work.
+    ///
+    /// [`ClockThrottleReason::GpuIdle`] and the configured limits,
+    /// [`ClockThrottleReason::ApplicationClockSetting`] and
+    /// [`ClockThrottleReason::DisplayClockSetting`], do not count: they are
+    /// reported whenever the GPU idles or runs at the clocks it was set to.
+    pub fn is_throttling(self) -> bool {
+        let not_throttling = ClockThrottleReason::GpuIdle.bit()
+            | ClockThrottleReason::ApplicationClockSetting.bit()
+            | ClockThrottleReason::DisplayClockSetting.bit();
+        self.0 & !not_throttling != 0
+    }
+}
+
+impl From<ClockThrottleReason> for ThrottleReasons {
+    fn from(reason: ClockThrottleReason) -> Self {
+        Self(reason.bit())
+    }
+}
+
+impl FromIterator<ClockThrottleReason> for ThrottleReasons {
+    fn from_iter<I: IntoIterator<Item = ClockThrottleReason>>(reasons: I) -> Self {
+        let mut set = Self::default();
+        for reason in reasons {
+            set.insert(reason);
+        }
+        set
+    }
+}
+
+impl Display for ThrottleReasons {
+    /// Lists the reasons separated by commas, or `None` for an empty set.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.is_empty() {
+            return write!(f, "None");
+        }
+        let mut first = true;
+        for reason in self.iter() {
+            if !first {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", reason)?;
+            first = false;
+        }
+        let unknown = self.0
+            & !ClockThrottleReason::ALL
+                .iter()
+                .fold(0, |bits, r| bits | r.bit());
+        if unknown != 0 {
+            if !first {
+                write!(f, ", ")?;
+            }
+            write!(f, "{:#x}", unknown)?;
+        }
+        Ok(())
+    }
+}
+
+/// ECC memory error counts of a GPU, e.g. from NVML.
+///
+/// Only GPUs with ECC memory enabled report them, mostly data-center cards
+/// such as Tesla, A100 or H100.
+///
+/// # Example
+///
+/// ```
+/// use gpu_info::{EccStats, GpuError};
+///
+/// let ecc = EccStats {
+///     single_bit_corrected: 12,
+///     double_bit_uncorrected: 2,
+///     aggregate: true,
+/// };
+/// assert!(ecc.check_uncorrected(2).is_ok());
+/// assert!(matches!(ecc.check_uncorrected(1), Err(GpuError::EccError(2))));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EccStats {
+    /// Single-bit errors the memory corrected.
+    pub single_bit_corrected: u64,
+    /// Double-bit errors the memory could not correct.
+    pub double_bit_uncorrected: u64,
+    /// `true` for lifetime counts, `false` for counts since the last driver
+    /// reload.
+    pub aggregate: bool,
+}
+
+impl EccStats {
+    /// Fails with [`GpuError::EccError`] if there are more than `max`
+    /// uncorrected errors.
+    pub fn check_uncorrected(&self, max: u64) -> Result<()> {
+        if self.double_bit_uncorrected > max {
+            Err(GpuError::EccError(self.double_bit_uncorrected))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl Display for EccStats {
+    /// Formats as `<corrected> corrected, <uncorrected> uncorrected`, with
+    /// `(volatile)` appended for counts since the last driver reload.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} corrected, {} uncorrected",
+            self.single_bit_corrected, self.double_bit_uncorrected
+        )?;
+        if !self.aggregate {
+            write!(f, " (volatile)")?;
+        }
+        Ok(())
+    }
+}
+
+/// Which optional fields of a [`GpuInfo`] are populated.
+///
+/// Returned by [`GpuInfo::availability()`]; each flag is `true` if the
+/// corresponding field is `Some`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FieldAvailability {
+    /// GPU name is known
+    pub name: bool,
+    /// Temperature is reported
+    pub temperature: bool,
+    /// Utilization is reported
+    pub utilization: bool,
+    /// Power usage is reported
+    pub power_usage: bool,
+    /// Power limit is reported
+    pub power_limit: bool,
+    /// Core clock is reported
+    pub core_clock: bool,
+    /// Memory clock is reported
+    pub memory_clock: bool,
+    /// Maximum clock speed is reported
+    pub max_clock_speed: bool,
+    /// Effective core clock is reported
+    pub effective_core_clock: bool,
+    /// Fan speed is reported
+    pub fan_speed: bool,
+    /// Fan speed in RPM is reported
+    pub fan_speed_rpm: bool,
+    /// Per-sensor temperatures are reported
+    pub temperatures: bool,
+    /// Encoder utilization is reported
+    pub encoder_util: bool,
+    /// Decoder utilization is reported
+    pub decoder_util: bool,
+    /// Clock throttle reasons are reported
+    pub throttle_reasons: bool,
+    /// ECC error counters are reported
+    pub ecc_errors: bool,
+    /// Processes using the GPU are reported
+    pub processes: bool,
+    /// Device UUID is known
+    pub uuid: bool,
+    /// PCI bus id is known
+    pub bus_id: bool,
+    /// DXGI adapter LUID is known
+    pub adapter_luid: bool,
+    /// Memory utilization is reported
+    pub memory_util: bool,
+    /// Total memory is known
+    pub memory_total: bool,
+    /// Used memory is reported
+    pub memory_used: bool,
+    /// Driver version is known
+    pub driver_version: bool,
+    /// Active state is reported
+    pub active: bool,
+}
+
+/// Normalized 0-100 load figures of a [`GpuInfo`].
+///
+/// Returned by [`GpuInfo::usage_summary()`] as a compact, vendor-neutral
+/// payload for dashboards. Each value is `None` when the GPU does not report
+/// its inputs or they give NaN, and is clamped to 0-100.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UsageSummary {
+    /// GPU utilization
+    pub gpu: Option<f32>,
+    /// Used memory as a percentage of total memory, or the reported memory
+    /// utilization if the amounts are unknown
+    pub memory: Option<f32>,
+    /// Power usage as a percentage of the power limit
+    pub power: Option<f32>,
+    /// Temperature as a percentage of the shutdown temperature
+    pub thermal: Option<f32>,
+}
+
+impl UsageSummary {
+    /// Shutdown temperature (°C) assumed by [`GpuInfo::usage_summary()`].
+    ///
+    /// Discrete GPUs from all vendors shut down at around 100 °C; use
+    /// [`GpuInfo::usage_summary_with_shutdown()`] when the exact value is known.
+    pub const DEFAULT_SHUTDOWN_TEMPERATURE: f32 = 100.0;
 }
 
 /// Manual Clone implementation with optimized `clone_from()`.
@@ -221,6 +782,7 @@ impl Clone for GpuInfo {
             vendor: self.vendor,
             name_gpu: self.name_gpu.clone(),
             temperature: self.temperature,
+            temperatures: self.temperatures.clone(),
             utilization: self.utilization,
             power_usage: self.power_usage,
             core_clock: self.core_clock,
@@ -232,6 +794,19 @@ impl Clone for GpuInfo {
             memory_used: self.memory_used,
             driver_version: self.driver_version.clone(),
             max_clock_speed: self.max_clock_speed,
+            effective_core_clock: self.effective_core_clock,
+            fan_speed: self.fan_speed,
+            fan_speed_rpm: self.fan_speed_rpm,
+            encoder_util: self.encoder_util,
+            decoder_util: self.decoder_util,
+            throttle_reasons: self.throttle_reasons,
+            uuid: self.uuid.clone(),
+            bus_id: self.bus_id.clone(),
+            adapter_luid: self.adapter_luid.clone(),
+            processes: self.processes.clone(),
+            ecc_errors: self.ecc_errors,
+            metric_sources: self.metric_sources.clone(),
+            confidence: self.confidence,
         }
     }
 
@@ -244,6 +819,7 @@ impl Clone for GpuInfo {
         // Reuse string allocation if possible
         clone_option_string(&mut self.name_gpu, &source.name_gpu);
         self.temperature = source.temperature;
+        self.temperatures.clone_from(&source.temperatures);
         self.utilization = source.utilization;
         self.power_usage = source.power_usage;
         self.core_clock = source.core_clock;
@@ -256,6 +832,19 @@ impl Clone for GpuInfo {
         // Reuse string allocation if possible
         clone_option_string(&mut self.driver_version, &source.driver_version);
         self.max_clock_speed = source.max_clock_speed;
+        self.effective_core_clock = source.effective_core_clock;
+        self.fan_speed = source.fan_speed;
+        self.fan_speed_rpm = source.fan_speed_rpm;
+        self.encoder_util = source.encoder_util;
+        self.decoder_util = source.decoder_util;
+        self.throttle_reasons = source.throttle_reasons;
+        clone_option_string(&mut self.uuid, &source.uuid);
+        clone_option_string(&mut self.bus_id, &source.bus_id);
+        clone_option_string(&mut self.adapter_luid, &source.adapter_luid);
+        self.processes.clone_from(&source.processes);
+        self.ecc_errors = source.ecc_errors;
+        self.metric_sources.clone_from(&source.metric_sources);
+        self.confidence = source.confidence;
     }
 }
 
@@ -285,6 +874,8 @@ impl Formattable for Option<f32> {
 impl_formattable_for_option!(u32);
 impl_formattable_for_option!(bool);
 impl_formattable_for_option!(String);
+impl_formattable_for_option!(ThrottleReasons);
+impl_formattable_for_option!(EccStats);
 impl Formattable for Option<&str> {
     fn fmt_string(&self) -> String {
         match self {
@@ -322,6 +913,7 @@ impl GpuInfo {
             vendor: Vendor::Unknown,
             name_gpu: None,
             temperature: None,
+            temperatures: None,
             utilization: None,
             power_usage: None,
             core_clock: None,
@@ -333,9 +925,52 @@ impl GpuInfo {
             memory_used: None,
             driver_version: None,
             max_clock_speed: None,
+            effective_core_clock: None,
+            fan_speed: None,
+            fan_speed_rpm: None,
+            encoder_util: None,
+            decoder_util: None,
+            throttle_reasons: None,
+            uuid: None,
+            bus_id: None,
+            adapter_luid: None,
+            processes: None,
+            ecc_errors: None,
+            metric_sources: MetricSources::default(),
+            confidence: Confidence::Low,
+        }
+    }
+
+    /// Creates an unknown `GpuInfo` recording why detection failed.
+    ///
+    /// Used by the platform fallbacks of [`get()`](crate::get), which stays
+    /// infallible; callers can inspect the reason with
+    /// [`detection_error()`](Self::detection_error). The reason is not
+    /// serialized and does not affect equality.
+    ///
+    /// # Example
+    /// ```
+    /// use gpu_info::{GpuError, GpuInfo};
+    ///
+    /// let gpu = GpuInfo::unknown_with_reason(GpuError::DriverNotInstalled);
+    /// assert!(matches!(gpu.detection_error(), Some(GpuError::DriverNotInstalled)));
+    /// assert_eq!(gpu, GpuInfo::unknown());
+    /// ```
+    pub fn unknown_with_reason(reason: GpuError) -> Self {
+        Self {
+            metric_sources: MetricSources::with_detection_error(reason),
+            ..Self::unknown()
         }
     }
 
+    /// Returns why detection fell back to an unknown GPU, if recorded.
+    ///
+    /// `None` for detected GPUs and for unknown GPUs created without a
+    /// reason.
+    pub fn detection_error(&self) -> Option<&GpuError> {
+        self.metric_sources.detection_error()
+    }
+
     /// Creates a mock `GpuInfo` with typical NVIDIA GPU values.
     ///
     /// Useful for unit tests that need realistic GPU data without
@@ -593,11 +1228,39 @@ impl GpuInfo {
     /// - Some GPUs may throttle performance at certain temperature thresholds
     /// - The temperature reading may have a small delay from real-time values
     ///
+    /// With per-sensor [`temperatures()`](Self::temperatures), the `"core"`
+    /// sensor is returned, or the `"edge"` sensor if there is no core sensor.
+    ///
     /// # Performance
     /// This is a lightweight accessor method that simply returns stored data.
     /// It performs no I/O operations or complex calculations.
     pub fn temperature(&self) -> Option<f32> {
-        self.temperature
+        self.temperatures
+            .as_ref()
+            .and_then(|sensors| sensors.get("core").or_else(|| sensors.get("edge")))
+            .copied()
+            .or(self.temperature)
+    }
+
+    /// Returns the readings of the individual thermal sensors in degrees Celsius.
+    ///
+    /// Keys are sensor names: `"core"` and `"memory"` from NVML, and the
+    /// hwmon labels of amdgpu (`"edge"`, `"junction"`, with `"mem"` reported
+    /// as `"memory"`).
+    ///
+    /// # Example
+    /// ```
+    /// use gpu_info::GpuInfo;
+    ///
+    /// let gpu = GpuInfo::builder()
+    ///     .temperature_sensor("core", 62.0)
+    ///     .temperature_sensor("memory", 78.0)
+    ///     .build();
+    /// assert_eq!(gpu.temperatures().unwrap()["memory"], 78.0);
+    /// assert_eq!(gpu.temperature(), Some(62.0));
+    /// ```
+    pub fn temperatures(&self) -> Option<&BTreeMap<String, f32>> {
+        self.temperatures.as_ref()
     }
     /// Returns the current utilization of the GPU as a percentage.
     ///
@@ -728,6 +1391,29 @@ impl GpuInfo {
         self.memory_used
     }
 
+    /// Returns used memory as a percentage of total memory.
+    ///
+    /// Unlike [`memory_util()`](Self::memory_util), which is reported by the
+    /// provider, this is computed from `memory_used` and `memory_total`.
+    ///
+    /// # Returns
+    /// * `Some(f32)` - The percentage (0-100 for consistent data).
+    /// * `None` - If either value is unknown or `memory_total` is zero.
+    ///
+    /// # Example
+    /// ```rust
+    /// use gpu_info::GpuInfo;
+    ///
+    /// let gpu = GpuInfo::builder().memory_total(8192).memory_used(2048).build();
+    /// assert_eq!(gpu.memory_used_percent(), Some(25.0));
+    /// ```
+    pub fn memory_used_percent(&self) -> Option<f32> {
+        match (self.memory_used, self.memory_total) {
+            (Some(used), Some(total)) if total > 0 => Some(used as f32 / total as f32 * 100.0),
+            _ => None,
+        }
+    }
+
     /// Returns the driver version of the GPU.
     ///
     /// # Returns
@@ -742,6 +1428,42 @@ impl GpuInfo {
     pub fn driver_version(&self) -> Option<&str> {
         self.driver_version.as_deref()
     }
+
+    /// Returns `true` if the driver version is older than `version`.
+    ///
+    /// Both versions are compared numerically, component by component
+    /// (`"535.154.05"` style). Missing trailing components count as zero,
+    /// so `"535.154"` equals `"535.154.0"`.
+    ///
+    /// # Returns
+    /// * `Some(bool)` - Whether the driver is older than `version`.
+    /// * `None` - If the driver version is unknown, or either version is not
+    ///   a dotted list of numbers.
+    ///
+    /// # Example
+    /// ```
+    /// use gpu_info::GpuInfo;
+    ///
+    /// let gpu = GpuInfo::builder().driver_version("535.154.05").build();
+    /// assert_eq!(gpu.driver_is_older_than("550.54.14"), Some(true));
+    /// assert_eq!(gpu.driver_is_older_than("535.104"), Some(false));
+    /// assert_eq!(GpuInfo::unknown().driver_is_older_than("550"), None);
+    /// ```
+    pub fn driver_is_older_than(&self, version: &str) -> Option<bool> {
+        fn parse(version: &str) -> Option<Vec<u64>> {
+            version
+                .trim()
+                .split('.')
+                .map(|component| component.parse().ok())
+                .collect()
+        }
+        let mut current = parse(self.driver_version.as_deref()?)?;
+        let mut other = parse(version)?;
+        let len = current.len().max(other.len());
+        current.resize(len, 0);
+        other.resize(len, 0);
+        Some(current < other)
+    }
     /// Returns the maximum clock speed of the GPU in MHz.
     ///
     /// # Returns
@@ -757,103 +1479,322 @@ impl GpuInfo {
         self.max_clock_speed
     }
 
-    /// Returns formatted name of the GPU.
+    /// Returns the effective core clock speed of the GPU in MHz.
     ///
-    /// If the GPU name is unknown, returns "Unknown GPU".
+    /// # Returns
+    /// * `Some(u32)` - The clock the GPU actually runs at.
+    /// * `None` - If the provider cannot measure the effective clock.
     ///
     /// # Example
+    /// ```rust
+    /// let gpu = gpu_info::get();
+    /// println!("Effective Core Clock: {:?}", gpu.effective_core_clock());
     /// ```
-    /// use gpu_info::GpuInfo;
-    /// let gpu = GpuInfo::builder().name("RTX 3080").build();
-    /// assert_eq!(gpu.format_name_gpu(), "RTX 3080");
-    /// ```
-    pub fn format_name_gpu(&self) -> String {
-        self.name_gpu
-            .as_ref()
-            .map_or_else(|| "Unknown GPU".to_string(), |s| s.clone())
+    pub fn effective_core_clock(&self) -> Option<u32> {
+        self.effective_core_clock
     }
 
-    /// Returns formatted GPU utilization percentage.
+    /// Returns the fan speed of the GPU as a percentage of the maximum.
     ///
-    /// Rounds to 2 decimal places for readability.
-    /// If unknown, returns "N/A".
+    /// # Returns
+    /// * `Some(u32)` - The fan speed (0-100%).
+    /// * `None` - If the GPU has no fan or the provider cannot read it.
     ///
     /// # Example
+    /// ```rust
+    /// let gpu = gpu_info::get();
+    /// println!("Fan Speed: {:?}", gpu.fan_speed());
     /// ```
-    /// use gpu_info::GpuInfo;
-    /// let gpu = GpuInfo::builder().utilization(75.5).build();
-    /// assert_eq!(gpu.format_utilization(), "75.50%");
-    /// ```
-    pub fn format_utilization(&self) -> String {
-        match self.utilization {
-            Some(util) => format!("{:.2}%", (util * 100.0).round() / 100.0),
-            None => "N/A".to_string(),
-        }
+    pub fn fan_speed(&self) -> Option<u32> {
+        self.fan_speed
     }
 
-    /// Returns formatted temperature in Celsius.
+    /// Returns the fan speed of the GPU in revolutions per minute.
     ///
-    /// Rounds to 2 decimal places for readability.
-    /// If not supported by driver, returns "Not supported".
+    /// # Returns
+    /// * `Some(u32)` - The fan speed in RPM.
+    /// * `None` - If the GPU has no fan tachometer or the provider cannot read it.
     ///
     /// # Example
+    /// ```rust
+    /// let gpu = gpu_info::get();
+    /// println!("Fan Speed: {:?} RPM", gpu.fan_speed_rpm());
     /// ```
-    /// use gpu_info::GpuInfo;
-    /// let gpu = GpuInfo::builder().temperature(65.5).build();
-    /// assert_eq!(gpu.format_temperature(), "65.50°C");
-    /// ```
-    pub fn format_temperature(&self) -> String {
-        match self.temperature {
-            Some(temp) => format!("{:.2}°C", (temp * 100.0).round() / 100.0),
-            None => "Not supported".to_string(),
-        }
+    pub fn fan_speed_rpm(&self) -> Option<u32> {
+        self.fan_speed_rpm
     }
 
-    /// Returns formatted power usage in watts.
+    /// Returns the utilization of the video encoder (NVENC) as a percentage.
     ///
-    /// Rounds to 2 decimal places for readability.
-    /// If not supported by driver, returns "Not supported".
+    /// # Returns
+    /// * `Some(f32)` - The encoder utilization (0.0-100.0).
+    /// * `None` - If the GPU has no encoder or the provider cannot read it.
     ///
     /// # Example
+    /// ```rust
+    /// let gpu = gpu_info::get();
+    /// println!("Encoder: {:?}%", gpu.encoder_util());
     /// ```
-    /// use gpu_info::GpuInfo;
-    /// let gpu = GpuInfo::builder().power_usage(250.0).build();
-    /// assert_eq!(gpu.format_power_usage(), "250.00W");
-    /// ```
-    pub fn format_power_usage(&self) -> String {
-        match self.power_usage {
-            Some(power) => format!("{:.2}W", (power * 100.0).round() / 100.0),
-            None => "Not supported".to_string(),
-        }
+    pub fn encoder_util(&self) -> Option<f32> {
+        self.encoder_util
     }
 
-    /// Returns formatted power limit in watts.
+    /// Returns the utilization of the video decoder (NVDEC) as a percentage.
     ///
-    /// Rounds to 2 decimal places for readability.
-    /// If not supported by driver, returns "Not supported".
+    /// # Returns
+    /// * `Some(f32)` - The decoder utilization (0.0-100.0).
+    /// * `None` - If the GPU has no decoder or the provider cannot read it.
     ///
     /// # Example
+    /// ```rust
+    /// let gpu = gpu_info::get();
+    /// println!("Decoder: {:?}%", gpu.decoder_util());
     /// ```
-    /// use gpu_info::GpuInfo;
-    /// let gpu = GpuInfo::builder().power_limit(350.0).build();
-    /// assert_eq!(gpu.format_power_limit(), "350.00W");
-    /// ```
-    pub fn format_power_limit(&self) -> String {
-        match self.power_limit {
-            Some(limit) => format!("{:.2}W", (limit * 100.0).round() / 100.0),
-            None => "Not supported".to_string(),
-        }
+    pub fn decoder_util(&self) -> Option<f32> {
+        self.decoder_util
     }
 
-    /// Returns formatted memory utilization percentage.
+    /// Returns the reasons the clocks are held below their maximum.
     ///
-    /// Rounds to 2 decimal places for readability.
-    /// If unknown, returns "N/A".
+    /// # Returns
+    /// * `Some(ThrottleReasons)` - The reasons reported by the driver; an
+    ///   empty set means the GPU runs at full clocks.
+    /// * `None` - If the provider cannot read them.
     ///
     /// # Example
+    /// ```rust
+    /// let gpu = gpu_info::get();
+    /// if let Some(reasons) = gpu.throttle_reasons().filter(|r| r.is_throttling()) {
+    ///     println!("Throttled: {}", reasons);
+    /// }
     /// ```
-    /// use gpu_info::GpuInfo;
-    /// let gpu = GpuInfo::builder().memory_util(45.5).build();
+    pub fn throttle_reasons(&self) -> Option<ThrottleReasons> {
+        self.throttle_reasons
+    }
+
+    /// Returns the device UUID reported by the driver.
+    ///
+    /// # Returns
+    /// * `Some(&str)` - The UUID, e.g. `GPU-5a8f...` from NVML.
+    /// * `None` - If the provider cannot read it.
+    ///
+    /// # Example
+    /// ```rust
+    /// let gpu = gpu_info::get();
+    /// println!("UUID: {:?}", gpu.uuid());
+    /// ```
+    pub fn uuid(&self) -> Option<&str> {
+        self.uuid.as_deref()
+    }
+
+    /// Returns the PCI bus ID of the GPU.
+    ///
+    /// # Returns
+    /// * `Some(&str)` - The PCI address, e.g. `0000:01:00.0`.
+    /// * `None` - If the provider cannot read it.
+    ///
+    /// # Example
+    /// ```rust
+    /// let gpu = gpu_info::get();
+    /// println!("PCI Bus ID: {:?}", gpu.pci_bus_id());
+    /// ```
+    pub fn pci_bus_id(&self) -> Option<&str> {
+        self.bus_id.as_deref()
+    }
+
+    /// Returns the DXGI adapter LUID on Windows.
+    ///
+    /// The LUID names the adapter in Windows APIs such as the `GPU Engine`
+    /// performance counters, but is assigned anew on every boot. Use
+    /// [`identity()`](Self::identity) to recognise a GPU across reboots.
+    ///
+    /// # Returns
+    /// * `Some(&str)` - The LUID, e.g. `LUID-00000000-0000d1a3`.
+    /// * `None` - On other platforms, or if DXGI lists no matching adapter.
+    pub fn adapter_luid(&self) -> Option<&str> {
+        self.adapter_luid.as_deref()
+    }
+
+    /// Returns the processes currently using the GPU.
+    ///
+    /// Reported through NVML for NVIDIA GPUs, through the DRM clients list
+    /// in debugfs for AMD GPUs on Linux (requires root) and through
+    /// `powermetrics` on Apple GPUs (requires root). Empty if no process uses
+    /// the GPU or the provider cannot list them; the `processes` field tells
+    /// the two apart.
+    ///
+    /// # Example
+    /// ```rust
+    /// let gpu = gpu_info::get();
+    /// for process in gpu.running_processes() {
+    ///     println!("{} ({}): {} MB", process.pid, process.process_type, process.memory_used_mb);
+    /// }
+    /// ```
+    pub fn running_processes(&self) -> Vec<GpuProcessInfo> {
+        self.processes.clone().unwrap_or_default()
+    }
+
+    /// Returns the ECC memory error counts.
+    ///
+    /// # Returns
+    /// * `Some(EccStats)` - The counts reported by the driver.
+    /// * `None` - If the GPU has no ECC memory, ECC is disabled, or the
+    ///   provider cannot read the counters.
+    ///
+    /// # Example
+    /// ```rust
+    /// let gpu = gpu_info::get();
+    /// if let Some(ecc) = gpu.ecc_errors() {
+    ///     println!("ECC errors: {}", ecc);
+    /// }
+    /// ```
+    pub fn ecc_errors(&self) -> Option<EccStats> {
+        self.ecc_errors
+    }
+
+    /// Returns how trustworthy the detected identity of the GPU is.
+    ///
+    /// # Example
+    /// ```rust
+    /// use gpu_info::Confidence;
+    ///
+    /// let gpu = gpu_info::get();
+    /// if gpu.confidence() == Confidence::Low {
+    ///     println!("GPU details are estimated");
+    /// }
+    /// ```
+    pub fn confidence(&self) -> Confidence {
+        self.confidence
+    }
+
+    /// Returns how far the effective core clock falls short of the requested
+    /// core clock, as a percentage of the requested clock.
+    ///
+    /// A persistently high deficit indicates power or thermal throttling.
+    /// An effective clock above the requested one (boost) yields `0.0`.
+    ///
+    /// # Returns
+    /// * `Some(f32)` - The deficit in percent (0-100).
+    /// * `None` - If either clock is unknown, or the requested clock is zero.
+    ///
+    /// # Example
+    /// ```
+    /// use gpu_info::GpuInfo;
+    ///
+    /// let gpu = GpuInfo::builder()
+    ///     .core_clock(2500)
+    ///     .effective_core_clock(2000)
+    ///     .build();
+    /// assert_eq!(gpu.clock_deficit_percent(), Some(20.0));
+    /// assert_eq!(GpuInfo::builder().core_clock(2500).build().clock_deficit_percent(), None);
+    /// ```
+    pub fn clock_deficit_percent(&self) -> Option<f32> {
+        match (self.core_clock, self.effective_core_clock) {
+            (Some(requested), Some(effective)) if requested > 0 => {
+                let deficit = requested.saturating_sub(effective);
+                Some(deficit as f32 / requested as f32 * 100.0)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns formatted name of the GPU.
+    ///
+    /// If the GPU name is unknown, returns "Unknown GPU".
+    ///
+    /// # Example
+    /// ```
+    /// use gpu_info::GpuInfo;
+    /// let gpu = GpuInfo::builder().name("RTX 3080").build();
+    /// assert_eq!(gpu.format_name_gpu(), "RTX 3080");
+    /// ```
+    pub fn format_name_gpu(&self) -> String {
+        self.name_gpu
+            .as_ref()
+            .map_or_else(|| "Unknown GPU".to_string(), |s| s.clone())
+    }
+
+    /// Returns formatted GPU utilization percentage.
+    ///
+    /// Rounds to 2 decimal places for readability.
+    /// If unknown, returns "N/A".
+    ///
+    /// # Example
+    /// ```
+    /// use gpu_info::GpuInfo;
+    /// let gpu = GpuInfo::builder().utilization(75.5).build();
+    /// assert_eq!(gpu.format_utilization(), "75.50%");
+    /// ```
+    pub fn format_utilization(&self) -> String {
+        match self.utilization {
+            Some(util) => format!("{:.2}%", (util * 100.0).round() / 100.0),
+            None => "N/A".to_string(),
+        }
+    }
+
+    /// Returns formatted temperature in Celsius.
+    ///
+    /// Rounds to 2 decimal places for readability.
+    /// If not supported by driver, returns "Not supported".
+    ///
+    /// # Example
+    /// ```
+    /// use gpu_info::GpuInfo;
+    /// let gpu = GpuInfo::builder().temperature(65.5).build();
+    /// assert_eq!(gpu.format_temperature(), "65.50°C");
+    /// ```
+    pub fn format_temperature(&self) -> String {
+        match self.temperature() {
+            Some(temp) => format!("{:.2}°C", (temp * 100.0).round() / 100.0),
+            None => "Not supported".to_string(),
+        }
+    }
+
+    /// Returns formatted power usage in watts.
+    ///
+    /// Rounds to 2 decimal places for readability.
+    /// If not supported by driver, returns "Not supported".
+    ///
+    /// # Example
+    /// ```
+    /// use gpu_info::GpuInfo;
+    /// let gpu = GpuInfo::builder().power_usage(250.0).build();
+    /// assert_eq!(gpu.format_power_usage(), "250.00W");
+    /// ```
+    pub fn format_power_usage(&self) -> String {
+        match self.power_usage {
+            Some(power) => format!("{:.2}W", (power * 100.0).round() / 100.0),
+            None => "Not supported".to_string(),
+        }
+    }
+
+    /// Returns formatted power limit in watts.
+    ///
+    /// Rounds to 2 decimal places for readability.
+    /// If not supported by driver, returns "Not supported".
+    ///
+    /// # Example
+    /// ```
+    /// use gpu_info::GpuInfo;
+    /// let gpu = GpuInfo::builder().power_limit(350.0).build();
+    /// assert_eq!(gpu.format_power_limit(), "350.00W");
+    /// ```
+    pub fn format_power_limit(&self) -> String {
+        match self.power_limit {
+            Some(limit) => format!("{:.2}W", (limit * 100.0).round() / 100.0),
+            None => "Not supported".to_string(),
+        }
+    }
+
+    /// Returns formatted memory utilization percentage.
+    ///
+    /// Rounds to 2 decimal places for readability.
+    /// If unknown, returns "N/A".
+    ///
+    /// # Example
+    /// ```
+    /// use gpu_info::GpuInfo;
+    /// let gpu = GpuInfo::builder().memory_util(45.5).build();
     /// assert_eq!(gpu.format_memory_util(), "45.50%");
     /// ```
     pub fn format_memory_util(&self) -> String {
@@ -914,28 +1855,86 @@ impl GpuInfo {
         }
     }
 
-    /// Returns formatted total memory in gigabytes.
+    /// Returns formatted fan speed in percent, with the RPM if known.
+    ///
+    /// If unknown, returns "N/A".
+    ///
+    /// # Example
+    /// ```
+    /// use gpu_info::GpuInfo;
+    /// let gpu = GpuInfo::builder().fan_speed(45).build();
+    /// assert_eq!(gpu.format_fan_speed(), "45%");
+    /// let gpu = GpuInfo::builder().fan_speed(45).fan_speed_rpm(1350).build();
+    /// assert_eq!(gpu.format_fan_speed(), "45% (1350 RPM)");
+    /// assert_eq!(GpuInfo::unknown().format_fan_speed(), "N/A");
+    /// ```
+    pub fn format_fan_speed(&self) -> String {
+        match (self.fan_speed, self.fan_speed_rpm) {
+            (Some(speed), Some(rpm)) => format!("{}% ({} RPM)", speed, rpm),
+            (Some(speed), None) => format!("{}%", speed),
+            (None, Some(rpm)) => format!("{} RPM", rpm),
+            (None, None) => "N/A".to_string(),
+        }
+    }
+
+    /// Returns formatted video encoder utilization percentage.
     ///
-    /// Converts internal MB storage to GB for display.
     /// If unknown, returns "N/A".
     ///
     /// # Example
     /// ```
     /// use gpu_info::GpuInfo;
+    /// let gpu = GpuInfo::builder().encoder_util(12.0).build();
+    /// assert_eq!(gpu.format_encoder_util(), "12.00%");
+    /// assert_eq!(GpuInfo::unknown().format_encoder_util(), "N/A");
+    /// ```
+    pub fn format_encoder_util(&self) -> String {
+        match self.encoder_util {
+            Some(util) => format!("{:.2}%", (util * 100.0).round() / 100.0),
+            None => "N/A".to_string(),
+        }
+    }
+
+    /// Returns formatted video decoder utilization percentage.
+    ///
+    /// If unknown, returns "N/A".
+    ///
+    /// # Example
+    /// ```
+    /// use gpu_info::GpuInfo;
+    /// let gpu = GpuInfo::builder().decoder_util(3.5).build();
+    /// assert_eq!(gpu.format_decoder_util(), "3.50%");
+    /// assert_eq!(GpuInfo::unknown().format_decoder_util(), "N/A");
+    /// ```
+    pub fn format_decoder_util(&self) -> String {
+        match self.decoder_util {
+            Some(util) => format!("{:.2}%", (util * 100.0).round() / 100.0),
+            None => "N/A".to_string(),
+        }
+    }
+
+    /// Returns formatted total memory in gigabytes.
+    ///
+    /// Converts internal MB storage to GB for display, exactly for any
+    /// amount up to `u32::MAX`. If unknown, returns "N/A".
+    ///
+    /// # Example
+    /// ```
+    /// use gpu_info::GpuInfo;
     /// let gpu = GpuInfo::builder().memory_total(8192).build(); // 8 GB in MB
     /// assert_eq!(gpu.format_memory_total(), "8.00 GB");
     /// ```
     pub fn format_memory_total(&self) -> String {
         match self.memory_total {
-            Some(mb) => format!("{:.2} GB", (mb as f32) / 1024.0),
+            Some(mb) => format!("{:.2} GB", f64::from(mb) / 1024.0),
             None => "N/A".to_string(),
         }
     }
 
     /// Returns formatted used memory in gigabytes.
     ///
-    /// Converts internal MB storage to GB for display.
-    /// If unknown, returns "N/A".
+    /// Converts internal MB storage to GB for display, exactly for any
+    /// amount up to `u32::MAX`. If unknown, returns "N/A".
     ///
     /// # Example
     /// ```
@@ -945,7 +1944,7 @@ impl GpuInfo {
     /// ```
     pub fn format_memory_used(&self) -> String {
         match self.memory_used {
-            Some(mb) => format!("{:.2} GB", (mb as f32) / 1024.0),
+            Some(mb) => format!("{:.2} GB", f64::from(mb) / 1024.0),
             None => "N/A".to_string(),
         }
     }
@@ -996,7 +1995,7 @@ impl GpuInfo {
     /// assert!(!gpu.has_temperature());
     /// ```
     pub fn has_temperature(&self) -> bool {
-        self.temperature.is_some()
+        self.temperature().is_some()
     }
 
     /// Returns `true` if utilization data is available.
@@ -1019,11 +2018,118 @@ impl GpuInfo {
         self.memory_total.is_some() || self.memory_used.is_some()
     }
 
+    /// Returns `true` if the fan speed is reported, as a percentage or in RPM.
+    pub fn has_fan_speed(&self) -> bool {
+        self.fan_speed.is_some() || self.fan_speed_rpm.is_some()
+    }
+
     /// Returns `true` if clock speed information is available.
     pub fn has_clock_info(&self) -> bool {
         self.core_clock.is_some() || self.memory_clock.is_some()
     }
 
+    /// Returns which fields of this GPU are populated.
+    ///
+    /// Intended for UIs that grey out metrics the GPU or driver does not
+    /// report, without calling each `has_*` method separately.
+    ///
+    /// # Example
+    /// ```
+    /// use gpu_info::GpuInfo;
+    ///
+    /// let gpu = GpuInfo::builder().temperature(65.0).build();
+    /// let availability = gpu.availability();
+    /// assert!(availability.temperature);
+    /// assert!(!availability.power_usage);
+    /// ```
+    pub fn availability(&self) -> FieldAvailability {
+        FieldAvailability {
+            name: self.name_gpu.is_some(),
+            temperature: self.has_temperature(),
+            utilization: self.has_utilization(),
+            power_usage: self.has_power_usage(),
+            power_limit: self.power_limit.is_some(),
+            core_clock: self.core_clock.is_some(),
+            memory_clock: self.memory_clock.is_some(),
+            max_clock_speed: self.max_clock_speed.is_some(),
+            effective_core_clock: self.effective_core_clock.is_some(),
+            fan_speed: self.fan_speed.is_some(),
+            fan_speed_rpm: self.fan_speed_rpm.is_some(),
+            temperatures: self.temperatures.is_some(),
+            encoder_util: self.encoder_util.is_some(),
+            decoder_util: self.decoder_util.is_some(),
+            throttle_reasons: self.throttle_reasons.is_some(),
+            ecc_errors: self.ecc_errors.is_some(),
+            processes: self.processes.is_some(),
+            uuid: self.uuid.is_some(),
+            bus_id: self.bus_id.is_some(),
+            adapter_luid: self.adapter_luid.is_some(),
+            memory_util: self.memory_util.is_some(),
+            memory_total: self.memory_total.is_some(),
+            memory_used: self.memory_used.is_some(),
+            driver_version: self.has_driver(),
+            active: self.active.is_some(),
+        }
+    }
+
+    /// Returns the load of this GPU as normalized 0-100 values.
+    ///
+    /// The temperature is related to
+    /// [`UsageSummary::DEFAULT_SHUTDOWN_TEMPERATURE`].
+    ///
+    /// # Example
+    /// ```
+    /// use gpu_info::GpuInfo;
+    ///
+    /// let gpu = GpuInfo::builder()
+    ///     .utilization(40.0)
+    ///     .power_usage(150.0)
+    ///     .power_limit(300.0)
+    ///     .build();
+    /// let summary = gpu.usage_summary();
+    /// assert_eq!(summary.gpu, Some(40.0));
+    /// assert_eq!(summary.power, Some(50.0));
+    /// assert_eq!(summary.memory, None);
+    /// ```
+    pub fn usage_summary(&self) -> UsageSummary {
+        self.usage_summary_with_shutdown(UsageSummary::DEFAULT_SHUTDOWN_TEMPERATURE)
+    }
+
+    /// Returns the load of this GPU as normalized 0-100 values, relating the
+    /// temperature to `shutdown_temperature` (°C).
+    ///
+    /// `thermal` is `None` if `shutdown_temperature` is not positive.
+    ///
+    /// # Example
+    /// ```
+    /// use gpu_info::GpuInfo;
+    ///
+    /// let gpu = GpuInfo::builder().temperature(72.0).build();
+    /// assert_eq!(gpu.usage_summary_with_shutdown(90.0).thermal, Some(80.0));
+    /// ```
+    pub fn usage_summary_with_shutdown(&self, shutdown_temperature: f32) -> UsageSummary {
+        let percent = |value: f32| (!value.is_nan()).then(|| value.clamp(0.0, 100.0));
+        let power = match (self.power_usage, self.power_limit) {
+            (Some(usage), Some(limit)) if limit > 0.0 => Some(usage / limit * 100.0),
+            _ => None,
+        };
+        let thermal = match self.temperature {
+            Some(temperature) if shutdown_temperature > 0.0 => {
+                Some(temperature / shutdown_temperature * 100.0)
+            }
+            _ => None,
+        };
+        UsageSummary {
+            gpu: self.utilization.and_then(percent),
+            memory: self
+                .memory_used_percent()
+                .or(self.memory_util)
+                .and_then(percent),
+            power: power.and_then(percent),
+            thermal: thermal.and_then(percent),
+        }
+    }
+
     /// Returns `true` if the GPU can report power metrics.
     ///
     /// This indicates whether the GPU driver supports power monitoring.
@@ -1045,69 +2151,533 @@ impl GpuInfo {
         self.active.unwrap_or(false)
     }
 
-    /// Returns `true` if the GPU vendor is known.
-    pub fn is_vendor_known(&self) -> bool {
-        self.vendor != Vendor::Unknown
+    /// Returns `true` if the GPU vendor is known.
+    pub fn is_vendor_known(&self) -> bool {
+        self.vendor != Vendor::Unknown
+    }
+
+    /// Validates all fields are within expected ranges.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any field is out of valid range:
+    /// - Temperature: 0-1000°C
+    /// - Utilization: 0-100%
+    /// - Power usage: 0-1000W
+    /// - Clock speed: 0-5000 MHz
+    /// - Memory: 0-131072 MB (128 GB)
+    /// - PCI bus ID: `domain:bus:device.function` in hex
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gpu_info::GpuInfo;
+    ///
+    /// let gpu = GpuInfo::builder()
+    ///     .temperature(65.0)
+    ///     .utilization(45.0)
+    ///     .bus_id("0000:01:00.0")
+    ///     .build();
+    ///
+    /// assert!(gpu.validate().is_ok());
+    /// ```
+    pub fn validate(&self) -> Result<()> {
+        if let Some(temp) = self.temperature {
+            if !(0.0..=1000.0).contains(&temp) {
+                return Err(GpuError::InvalidTemperature(temp));
+            }
+        }
+        if let Some(util) = self.utilization {
+            if !(0.0..=100.0).contains(&util) {
+                return Err(GpuError::InvalidUtilization(util));
+            }
+        }
+        if let Some(power) = self.power_usage {
+            if !(0.0..=1000.0).contains(&power) {
+                return Err(GpuError::InvalidPowerUsage(power));
+            }
+        }
+        if let Some(clock) = self.core_clock {
+            if clock > 5000 {
+                return Err(GpuError::InvalidClockSpeed(clock));
+            }
+        }
+        if let Some(mem) = self.memory_total {
+            if mem > 131072 {
+                // 128 GB in MB
+                return Err(GpuError::InvalidMemory(mem));
+            }
+        }
+        if let Some(speed) = self.fan_speed {
+            if speed > 100 {
+                return Err(GpuError::InvalidFanSpeed(speed));
+            }
+        }
+        if let Some(bus_id) = &self.bus_id {
+            if !crate::gpu_identity::is_bus_id(bus_id) {
+                return Err(GpuError::InvalidBusId(bus_id.clone()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns `true` if all fields are within valid ranges.
+    ///
+    /// This is a convenience method equivalent to `self.validate().is_ok()`.
+    pub fn is_valid(&self) -> bool {
+        self.validate().is_ok()
+    }
+
+    /// Checks cross-field consistency rules.
+    ///
+    /// Unlike [`validate()`](Self::validate), which checks each field on its
+    /// own, this checks relations between fields:
+    /// - `memory_used` must not exceed `memory_total`
+    /// - `utilization` and `memory_util` must be within 0-100%
+    /// - `core_clock` must not exceed `max_clock_speed`
+    /// - An inactive GPU must not report non-zero utilization
+    ///
+    /// Returns all violations found; an empty vector means the data is consistent.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gpu_info::GpuInfo;
+    ///
+    /// let gpu = GpuInfo::builder().memory_total(8192).memory_used(9000).build();
+    /// assert_eq!(gpu.check_consistency().len(), 1);
+    ///
+    /// assert!(GpuInfo::mock_nvidia().check_consistency().is_empty());
+    /// ```
+    pub fn check_consistency(&self) -> Vec<crate::consistency::ConsistencyViolation> {
+        crate::consistency::check(self)
+    }
+
+    /// Compares two snapshots, treating floating-point metrics within
+    /// `epsilon` of each other as equal.
+    ///
+    /// Integer, string and boolean fields must match exactly, and a field that
+    /// is `Some` in one snapshot and `None` in the other is a difference. Two
+    /// NaN values compare as equal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gpu_info::GpuInfo;
+    ///
+    /// let a = GpuInfo::builder().temperature(65.0).build();
+    /// let b = GpuInfo::builder().temperature(65.005).build();
+    /// assert!(a.approx_eq(&b, 0.01));
+    /// assert!(!a.approx_eq(&b, 0.001));
+    /// ```
+    pub fn approx_eq(&self, other: &GpuInfo, epsilon: f32) -> bool {
+        fn close(a: Option<f32>, b: Option<f32>, epsilon: f32) -> bool {
+            match (a, b) {
+                (Some(a), Some(b)) => (a.is_nan() && b.is_nan()) || (a - b).abs() <= epsilon,
+                (None, None) => true,
+                _ => false,
+            }
+        }
+        self.vendor == other.vendor
+            && self.name_gpu == other.name_gpu
+            && close(self.temperature, other.temperature, epsilon)
+            && match (&self.temperatures, &other.temperatures) {
+                (Some(a), Some(b)) => {
+                    a.len() == b.len()
+                        && a.iter().all(|(sensor, &value)| {
+                            close(Some(value), b.get(sensor).copied(), epsilon)
+                        })
+                }
+                (None, None) => true,
+                _ => false,
+            }
+            && close(self.utilization, other.utilization, epsilon)
+            && close(self.power_usage, other.power_usage, epsilon)
+            && self.core_clock == other.core_clock
+            && close(self.memory_util, other.memory_util, epsilon)
+            && self.memory_clock == other.memory_clock
+            && self.active == other.active
+            && close(self.power_limit, other.power_limit, epsilon)
+            && self.memory_total == other.memory_total
+            && self.memory_used == other.memory_used
+            && self.driver_version == other.driver_version
+            && self.max_clock_speed == other.max_clock_speed
+            && self.effective_core_clock == other.effective_core_clock
+            && self.fan_speed == other.fan_speed
+            && self.fan_speed_rpm == other.fan_speed_rpm
+            && close(self.encoder_util, other.encoder_util, epsilon)
+            && close(self.decoder_util, other.decoder_util, epsilon)
+            && self.throttle_reasons == other.throttle_reasons
+            && self.processes == other.processes
+            && self.ecc_errors == other.ecc_errors
+    }
+
+    /// Fills every unset field from `fallback`.
+    ///
+    /// Fields that are already set keep their value, so merging readings in
+    /// order of trust gives each field the value of the most trusted source
+    /// that reported it. An unknown vendor is replaced as well, together with
+    /// its [`confidence`](Self::confidence). Recorded
+    /// [`metric_sources`](Self::metric_sources) are left unchanged.
+    ///
+    /// Merge readings in the order
+    /// [`metric_source::source_order()`](crate::metric_source::source_order)
+    /// gives for the GPU's vendor.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gpu_info::{GpuInfo, Vendor};
+    ///
+    /// let mut gpu = GpuInfo::builder().vendor(Vendor::Nvidia).temperature(65.0).build();
+    /// let fallback = GpuInfo::builder()
+    ///     .temperature(70.0)
+    ///     .driver_version("550.54.14")
+    ///     .build();
+    /// gpu.merge(&fallback);
+    /// assert_eq!(gpu.temperature, Some(65.0));
+    /// assert_eq!(gpu.driver_version.as_deref(), Some("550.54.14"));
+    /// ```
+    pub fn merge(&mut self, fallback: &GpuInfo) {
+        fn fill<T: Clone>(field: &mut Option<T>, fallback: &Option<T>) {
+            if field.is_none() {
+                field.clone_from(fallback);
+            }
+        }
+        if self.vendor == Vendor::Unknown {
+            self.vendor = fallback.vendor;
+            self.confidence = fallback.confidence;
+        }
+        fill(&mut self.name_gpu, &fallback.name_gpu);
+        fill(&mut self.temperature, &fallback.temperature);
+        fill(&mut self.temperatures, &fallback.temperatures);
+        fill(&mut self.utilization, &fallback.utilization);
+        fill(&mut self.power_usage, &fallback.power_usage);
+        fill(&mut self.core_clock, &fallback.core_clock);
+        fill(&mut self.memory_util, &fallback.memory_util);
+        fill(&mut self.memory_clock, &fallback.memory_clock);
+        fill(&mut self.active, &fallback.active);
+        fill(&mut self.power_limit, &fallback.power_limit);
+        fill(&mut self.memory_total, &fallback.memory_total);
+        fill(&mut self.memory_used, &fallback.memory_used);
+        fill(&mut self.driver_version, &fallback.driver_version);
+        fill(&mut self.max_clock_speed, &fallback.max_clock_speed);
+        fill(
+            &mut self.effective_core_clock,
+            &fallback.effective_core_clock,
+        );
+        fill(&mut self.fan_speed, &fallback.fan_speed);
+        fill(&mut self.fan_speed_rpm, &fallback.fan_speed_rpm);
+        fill(&mut self.encoder_util, &fallback.encoder_util);
+        fill(&mut self.decoder_util, &fallback.decoder_util);
+        fill(&mut self.throttle_reasons, &fallback.throttle_reasons);
+        fill(&mut self.uuid, &fallback.uuid);
+        fill(&mut self.bus_id, &fallback.bus_id);
+        fill(&mut self.adapter_luid, &fallback.adapter_luid);
+        fill(&mut self.processes, &fallback.processes);
+        fill(&mut self.ecc_errors, &fallback.ecc_errors);
+    }
+
+    /// Returns how long ago `metric` was last read successfully.
+    ///
+    /// `metric` is a [`Metric`] name such as `"temperature"`. Ages are only
+    /// tracked by a [`GpuManager`](crate::GpuManager) with
+    /// [`set_metric_age_tracking()`](crate::GpuManager::set_metric_age_tracking)
+    /// enabled; a metric a refresh did not return then keeps its last value
+    /// and keeps aging.
+    ///
+    /// Returns `None` if ages are not tracked, the name is unknown or the
+    /// metric was never read.
+    pub fn metric_age(&self, metric: &str) -> Option<Duration> {
+        let metric = Metric::ALL.iter().find(|m| m.name() == metric)?;
+        let read_at = self.metric_sources.read_times()?.get(metric)?;
+        Some(read_at.elapsed())
+    }
+
+    /// Returns the age of every tracked metric, keyed by [`Metric`] name.
+    ///
+    /// Returns `None` if ages are not tracked; see [`metric_age()`](Self::metric_age).
+    pub fn metric_ages(&self) -> Option<BTreeMap<&'static str, Duration>> {
+        let times = self.metric_sources.read_times()?;
+        Some(
+            times
+                .iter()
+                .map(|(metric, read_at)| (metric.name(), read_at.elapsed()))
+                .collect(),
+        )
+    }
+
+    /// Clears every metric read more than `max_age` ago.
+    ///
+    /// Metrics without a recorded age are kept, so this does nothing if ages
+    /// are not tracked.
+    pub fn clear_stale_metrics(&mut self, max_age: Duration) {
+        let Some(times) = self.metric_sources.read_times() else {
+            return;
+        };
+        let stale: Vec<Metric> = times
+            .iter()
+            .filter(|(_, read_at)| read_at.elapsed() > max_age)
+            .map(|(metric, _)| *metric)
+            .collect();
+        for metric in stale {
+            self.clear_metric(metric);
+        }
+    }
+
+    /// Records `now` for the metrics this reading returned and keeps the
+    /// value and read time of the others from `previous`
+    pub(crate) fn track_metric_times(&mut self, previous: &GpuInfo, now: Instant) {
+        let mut times = previous
+            .metric_sources
+            .read_times()
+            .cloned()
+            .unwrap_or_default();
+        for metric in Metric::ALL {
+            if self.has_metric(metric) {
+                times.insert(metric, now);
+            }
+        }
+        self.merge(previous);
+        self.metric_sources.set_read_times(times);
+    }
+
+    /// Returns `true` if `metric` has a value
+    fn has_metric(&self, metric: Metric) -> bool {
+        match metric {
+            Metric::Temperature => self.has_temperature(),
+            Metric::Utilization => self.utilization.is_some(),
+            Metric::PowerUsage => self.power_usage.is_some(),
+            Metric::CoreClock => self.core_clock.is_some(),
+            Metric::MemoryUtil => self.memory_util.is_some(),
+            Metric::MemoryClock => self.memory_clock.is_some(),
+            Metric::MemoryTotal => self.memory_total.is_some(),
+            Metric::MemoryUsed => self.memory_used.is_some(),
+            Metric::MaxClockSpeed => self.max_clock_speed.is_some(),
+        }
+    }
+
+    /// Copies the value of `metric` from `other`
+    pub(crate) fn copy_metric(&mut self, other: &GpuInfo, metric: Metric) {
+        match metric {
+            Metric::Temperature => {
+                self.temperature = other.temperature;
+                self.temperatures.clone_from(&other.temperatures);
+            }
+            Metric::Utilization => self.utilization = other.utilization,
+            Metric::PowerUsage => self.power_usage = other.power_usage,
+            Metric::CoreClock => self.core_clock = other.core_clock,
+            Metric::MemoryUtil => self.memory_util = other.memory_util,
+            Metric::MemoryClock => self.memory_clock = other.memory_clock,
+            Metric::MemoryTotal => self.memory_total = other.memory_total,
+            Metric::MemoryUsed => self.memory_used = other.memory_used,
+            Metric::MaxClockSpeed => self.max_clock_speed = other.max_clock_speed,
+        }
+    }
+
+    /// Removes the value of `metric`
+    fn clear_metric(&mut self, metric: Metric) {
+        match metric {
+            Metric::Temperature => {
+                self.temperature = None;
+                self.temperatures = None;
+            }
+            Metric::Utilization => self.utilization = None,
+            Metric::PowerUsage => self.power_usage = None,
+            Metric::CoreClock => self.core_clock = None,
+            Metric::MemoryUtil => self.memory_util = None,
+            Metric::MemoryClock => self.memory_clock = None,
+            Metric::MemoryTotal => self.memory_total = None,
+            Metric::MemoryUsed => self.memory_used = None,
+            Metric::MaxClockSpeed => self.max_clock_speed = None,
+        }
     }
 
-    /// Validates all fields are within expected ranges.
+    /// Returns the metric-independent identity of the GPU.
     ///
-    /// # Errors
+    /// Use it, rather than the `GpuInfo` itself, to key per-GPU state: two
+    /// samples of the same card have equal identities even when their
+    /// metrics differ. See [`gpu_identity`](crate::gpu_identity).
     ///
-    /// Returns an error if any field is out of valid range:
-    /// - Temperature: 0-1000°C
-    /// - Utilization: 0-100%
-    /// - Power usage: 0-1000W
-    /// - Clock speed: 0-5000 MHz
-    /// - Memory: 0-131072 MB (128 GB)
+    /// # Examples
+    /// ```
+    /// use gpu_info::GpuInfo;
+    ///
+    /// let mut first = GpuInfo::mock_nvidia();
+    /// let mut second = GpuInfo::mock_nvidia();
+    /// second.temperature = Some(90.0);
+    /// assert_ne!(first, second);
+    /// assert_eq!(first.identity(), second.identity());
+    ///
+    /// first.bus_id = Some("0000:01:00.0".to_string());
+    /// second.bus_id = Some("0000:02:00.0".to_string());
+    /// assert_ne!(first.identity(), second.identity());
+    /// ```
+    pub fn identity(&self) -> GpuIdentity {
+        let mut identity = GpuIdentity::new(self.vendor, self.name_gpu.as_deref());
+        if let Some(uuid) = &self.uuid {
+            identity = identity.with_uuid(uuid);
+        }
+        if let Some(bus_id) = &self.bus_id {
+            identity = identity.with_bus_id(bus_id);
+        }
+        identity
+    }
+
+    /// Returns a key for ordering GPUs deterministically.
+    ///
+    /// The key is built from identity fields only, in this order: kind
+    /// (hardware, virtual, unknown vendor, then fully unknown GPUs with
+    /// neither vendor nor name), vendor, normalized name (lowercase, with
+    /// whitespace collapsed), total memory, driver version and finally the
+    /// [`identity()`](Self::identity), whose bus ID or UUID tells otherwise
+    /// identical models apart.
+    ///
+    /// Metrics such as temperature, utilization or clocks are ignored, so
+    /// the order of a set of GPUs does not change between polls.
     ///
     /// # Examples
     ///
     /// ```
     /// use gpu_info::GpuInfo;
     ///
+    /// let mut hot = GpuInfo::mock_nvidia();
+    /// hot.temperature = Some(90.0);
+    /// assert!(hot.sort_key() == GpuInfo::mock_nvidia().sort_key());
+    /// assert!(GpuInfo::mock_nvidia().sort_key() < GpuInfo::unknown().sort_key());
+    /// ```
+    pub fn sort_key(&self) -> impl Ord {
+        let kind: u8 = match (self.vendor, &self.name_gpu) {
+            (Vendor::Unknown, None) => 3,
+            (Vendor::Unknown, Some(_)) => 2,
+            (Vendor::Virtual(_), _) => 1,
+            _ => 0,
+        };
+        (
+            kind,
+            vendor_sort_name(self.vendor),
+            self.name_gpu.as_deref().and_then(normalize_name),
+            self.memory_total,
+            self.driver_version.clone(),
+            self.identity(),
+        )
+    }
+
+    /// Formats the metrics as one line of InfluxDB line protocol.
+    ///
+    /// The line is tagged with `vendor`, `name` (if known) and `tags`, and
+    /// carries every present metric as a float field; absent metrics are
+    /// omitted. No timestamp is written, so the server assigns one; use
+    /// [`to_influx_at()`](Self::to_influx_at) to set it explicitly.
+    ///
+    /// Commas and spaces in the measurement, and commas, equals signs and
+    /// spaces in tag keys and values are escaped. Tags with an empty key or
+    /// value are skipped.
+    ///
+    /// # Returns
+    /// The line without a trailing newline, or an empty string if no metric
+    /// is present, since the line protocol requires at least one field.
+    ///
+    /// # Example
+    /// ```
+    /// use gpu_info::{GpuInfo, Vendor};
+    ///
     /// let gpu = GpuInfo::builder()
+    ///     .vendor(Vendor::Nvidia)
+    ///     .name("RTX 3080")
     ///     .temperature(65.0)
     ///     .utilization(45.0)
     ///     .build();
-    ///
-    /// assert!(gpu.validate().is_ok());
-    /// ```
-    pub fn validate(&self) -> Result<()> {
-        if let Some(temp) = self.temperature {
-            if !(0.0..=1000.0).contains(&temp) {
-                return Err(GpuError::InvalidTemperature(temp));
-            }
-        }
-        if let Some(util) = self.utilization {
-            if !(0.0..=100.0).contains(&util) {
-                return Err(GpuError::InvalidUtilization(util));
-            }
-        }
-        if let Some(power) = self.power_usage {
-            if !(0.0..=1000.0).contains(&power) {
-                return Err(GpuError::InvalidPowerUsage(power));
-            }
+    /// assert_eq!(
+    ///     gpu.to_influx("gpu", &[("host", "node1")]),
+    ///     "gpu,vendor=NVIDIA,name=RTX\\ 3080,host=node1 temperature=65,utilization=45"
+    /// );
+    /// ```
+    pub fn to_influx(&self, measurement: &str, tags: &[(&str, &str)]) -> String {
+        let fields = [
+            ("temperature", self.temperature()),
+            ("utilization", self.utilization),
+            ("power_usage", self.power_usage),
+            ("power_limit", self.power_limit),
+            ("core_clock", self.core_clock.map(|v| v as f32)),
+            (
+                "effective_core_clock",
+                self.effective_core_clock.map(|v| v as f32),
+            ),
+            ("max_clock_speed", self.max_clock_speed.map(|v| v as f32)),
+            ("fan_speed", self.fan_speed.map(|v| v as f32)),
+            ("fan_speed_rpm", self.fan_speed_rpm.map(|v| v as f32)),
+            ("encoder_util", self.encoder_util),
+            ("decoder_util", self.decoder_util),
+            ("memory_clock", self.memory_clock.map(|v| v as f32)),
+            ("memory_util", self.memory_util),
+            ("memory_total", self.memory_total.map(|v| v as f32)),
+            ("memory_used", self.memory_used.map(|v| v as f32)),
+        ];
+        let fields: Vec<(&str, f32)> = fields
+            .iter()
+            .filter_map(|&(key, value)| value.filter(|v| v.is_finite()).map(|v| (key, v)))
+            .collect();
+        if fields.is_empty() {
+            return String::new();
         }
-        if let Some(clock) = self.core_clock {
-            if clock > 5000 {
-                return Err(GpuError::InvalidClockSpeed(clock));
+
+        let mut line = String::new();
+        push_influx_escaped(&mut line, measurement, &[',', ' ']);
+        let vendor = self.vendor.to_string();
+        let own_tags = [
+            ("vendor", Some(vendor.as_str())),
+            ("name", self.name_gpu.as_deref()),
+        ];
+        let own_tags = own_tags
+            .iter()
+            .filter_map(|&(key, value)| value.map(|value| (key, value)));
+        for (key, value) in own_tags.chain(tags.iter().copied()) {
+            if key.is_empty() || value.is_empty() {
+                continue;
             }
+            line.push(',');
+            push_influx_escaped(&mut line, key, &[',', '=', ' ']);
+            line.push('=');
+            push_influx_escaped(&mut line, value, &[',', '=', ' ']);
         }
-        if let Some(mem) = self.memory_total {
-            if mem > 131072 {
-                // 128 GB in MB
-                return Err(GpuError::InvalidMemory(mem));
-            }
+        for (index, (key, value)) in fields.iter().enumerate() {
+            line.push(if index == 0 { ' ' } else { ',' });
+            let _ = write!(line, "{}={}", key, value);
         }
-        Ok(())
+        line
     }
 
-    /// Returns `true` if all fields are within valid ranges.
+    /// Formats the metrics like [`to_influx()`](Self::to_influx), with
+    /// `timestamp` appended in nanoseconds since the Unix epoch.
     ///
-    /// This is a convenience method equivalent to `self.validate().is_ok()`.
-    pub fn is_valid(&self) -> bool {
-        self.validate().is_ok()
+    /// Timestamps before the epoch are omitted.
+    ///
+    /// # Example
+    /// ```
+    /// use gpu_info::GpuInfo;
+    /// use std::time::{Duration, UNIX_EPOCH};
+    ///
+    /// let gpu = GpuInfo::builder().temperature(40.0).build();
+    /// let at = UNIX_EPOCH + Duration::from_secs(1);
+    /// assert_eq!(
+    ///     gpu.to_influx_at("gpu", &[], at),
+    ///     "gpu,vendor=UNKNOWN temperature=40 1000000000"
+    /// );
+    /// ```
+    pub fn to_influx_at(
+        &self,
+        measurement: &str,
+        tags: &[(&str, &str)],
+        timestamp: SystemTime,
+    ) -> String {
+        let mut line = self.to_influx(measurement, tags);
+        if !line.is_empty() {
+            if let Ok(since_epoch) = timestamp.duration_since(UNIX_EPOCH) {
+                let _ = write!(line, " {}", since_epoch.as_nanos());
+            }
+        }
+        line
     }
 
     /// Creates a new builder for constructing `GpuInfo` instances.
@@ -1155,10 +2725,15 @@ impl Display for GpuInfo {
         writeln!(f, "GPU Information:")?;
         writeln!(f, "  Vendor: {}", self.vendor)?;
         writeln!(f, "  Name: {}", self.name_gpu.fmt_string())?;
-        writeln!(f, "  Temperature: {}", self.temperature.fmt_string())?;
+        writeln!(f, "  Temperature: {}", self.temperature().fmt_string())?;
         writeln!(f, "  Utilization: {}", self.utilization.fmt_string())?;
         writeln!(f, "  Power Usage: {}", self.power_usage.fmt_string())?;
         writeln!(f, "  Core Clock: {}", self.core_clock.fmt_string())?;
+        writeln!(
+            f,
+            "  Effective Core Clock: {}",
+            self.effective_core_clock.fmt_string()
+        )?;
         writeln!(f, "  Memory Utilization: {}", self.memory_util.fmt_string())?;
         writeln!(f, "  Memory Clock: {}", self.memory_clock.fmt_string())?;
         writeln!(f, "  Active: {}", self.active.fmt_string())?;
@@ -1170,6 +2745,34 @@ impl Display for GpuInfo {
             "  Max Clock Speed: {}",
             self.max_clock_speed.fmt_string()
         )?;
+        writeln!(f, "  Fan Speed: {}", self.fan_speed.fmt_string())?;
+        writeln!(f, "  Fan Speed (RPM): {}", self.fan_speed_rpm.fmt_string())?;
+        writeln!(
+            f,
+            "  Encoder Utilization: {}",
+            self.encoder_util.fmt_string()
+        )?;
+        writeln!(
+            f,
+            "  Decoder Utilization: {}",
+            self.decoder_util.fmt_string()
+        )?;
+        writeln!(
+            f,
+            "  Throttle Reasons: {}",
+            self.throttle_reasons.fmt_string()
+        )?;
+        writeln!(f, "  UUID: {}", self.uuid.fmt_string())?;
+        writeln!(f, "  PCI Bus ID: {}", self.bus_id.fmt_string())?;
+        writeln!(
+            f,
+            "  Processes: {}",
+            self.processes
+                .as_ref()
+                .map(|processes| processes.len() as u32)
+                .fmt_string()
+        )?;
+        writeln!(f, "  ECC Errors: {}", self.ecc_errors.fmt_string())?;
         Ok(())
     }
 }
@@ -1215,6 +2818,19 @@ pub struct GpuInfoBuilder {
     memory_used: Option<u32>,
     driver_version: Option<String>,
     max_clock_speed: Option<u32>,
+    effective_core_clock: Option<u32>,
+    fan_speed: Option<u32>,
+    fan_speed_rpm: Option<u32>,
+    encoder_util: Option<f32>,
+    decoder_util: Option<f32>,
+    throttle_reasons: Option<ThrottleReasons>,
+    uuid: Option<String>,
+    bus_id: Option<String>,
+    adapter_luid: Option<String>,
+    processes: Option<Vec<GpuProcessInfo>>,
+    ecc_errors: Option<EccStats>,
+    temperatures: Option<BTreeMap<String, f32>>,
+    confidence: Confidence,
 }
 
 impl GpuInfoBuilder {
@@ -1269,6 +2885,23 @@ impl GpuInfoBuilder {
         self
     }
 
+    /// Adds the reading of one thermal sensor in degrees Celsius.
+    ///
+    /// # Arguments
+    ///
+    /// * `sensor` - The sensor name, e.g. `"core"`, `"edge"` or `"memory"`.
+    /// * `temperature` - The current temperature of that sensor.
+    ///
+    /// # Returns
+    ///
+    /// The builder instance for method chaining.
+    pub fn temperature_sensor(mut self, sensor: impl Into<String>, temperature: f32) -> Self {
+        self.temperatures
+            .get_or_insert_with(BTreeMap::new)
+            .insert(sensor.into(), temperature);
+        self
+    }
+
     /// Sets the GPU utilization percentage.
     ///
     /// # Arguments
@@ -1423,6 +3056,174 @@ impl GpuInfoBuilder {
         self
     }
 
+    /// Sets the effective GPU core clock speed in MHz.
+    ///
+    /// # Arguments
+    ///
+    /// * `effective_core_clock` - The clock the GPU actually runs at.
+    ///
+    /// # Returns
+    ///
+    /// The builder instance for method chaining.
+    pub fn effective_core_clock(mut self, effective_core_clock: u32) -> Self {
+        self.effective_core_clock = Some(effective_core_clock);
+        self
+    }
+
+    /// Sets the fan speed as a percentage of the maximum.
+    ///
+    /// # Arguments
+    ///
+    /// * `fan_speed` - The fan speed (0-100%).
+    ///
+    /// # Returns
+    ///
+    /// The builder instance for method chaining.
+    pub fn fan_speed(mut self, fan_speed: u32) -> Self {
+        self.fan_speed = Some(fan_speed);
+        self
+    }
+
+    /// Sets the fan speed in revolutions per minute.
+    ///
+    /// # Arguments
+    ///
+    /// * `fan_speed_rpm` - The fan speed (RPM).
+    ///
+    /// # Returns
+    ///
+    /// The builder instance for method chaining.
+    pub fn fan_speed_rpm(mut self, fan_speed_rpm: u32) -> Self {
+        self.fan_speed_rpm = Some(fan_speed_rpm);
+        self
+    }
+
+    /// Sets the video encoder (NVENC) utilization percentage.
+    ///
+    /// # Arguments
+    ///
+    /// * `encoder_util` - The encoder utilization (0.0-100.0).
+    ///
+    /// # Returns
+    ///
+    /// The builder instance for method chaining.
+    pub fn encoder_util(mut self, encoder_util: f32) -> Self {
+        self.encoder_util = Some(encoder_util);
+        self
+    }
+
+    /// Sets the video decoder (NVDEC) utilization percentage.
+    ///
+    /// # Arguments
+    ///
+    /// * `decoder_util` - The decoder utilization (0.0-100.0).
+    ///
+    /// # Returns
+    ///
+    /// The builder instance for method chaining.
+    pub fn decoder_util(mut self, decoder_util: f32) -> Self {
+        self.decoder_util = Some(decoder_util);
+        self
+    }
+
+    /// Sets the reasons the clocks are held below their maximum.
+    ///
+    /// # Arguments
+    ///
+    /// * `reasons` - A [`ThrottleReasons`] set, or a single [`ClockThrottleReason`].
+    ///
+    /// # Returns
+    ///
+    /// The builder instance for method chaining.
+    pub fn throttle_reasons(mut self, reasons: impl Into<ThrottleReasons>) -> Self {
+        self.throttle_reasons = Some(reasons.into());
+        self
+    }
+
+    /// Sets the device UUID.
+    ///
+    /// # Arguments
+    ///
+    /// * `uuid` - The UUID reported by the driver, e.g. `GPU-5a8f...`.
+    ///
+    /// # Returns
+    ///
+    /// The builder instance for method chaining.
+    pub fn uuid(mut self, uuid: impl Into<String>) -> Self {
+        self.uuid = Some(uuid.into());
+        self
+    }
+
+    /// Sets the PCI bus ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `bus_id` - The PCI address of the GPU, e.g. `0000:01:00.0`.
+    ///
+    /// # Returns
+    ///
+    /// The builder instance for method chaining.
+    pub fn bus_id(mut self, bus_id: impl Into<String>) -> Self {
+        self.bus_id = Some(bus_id.into());
+        self
+    }
+
+    /// Sets the DXGI adapter LUID.
+    ///
+    /// # Arguments
+    ///
+    /// * `adapter_luid` - The LUID of the adapter on Windows, e.g. `LUID-00000000-0000d1a3`.
+    ///
+    /// # Returns
+    ///
+    /// The builder instance for method chaining.
+    pub fn adapter_luid(mut self, adapter_luid: impl Into<String>) -> Self {
+        self.adapter_luid = Some(adapter_luid.into());
+        self
+    }
+
+    /// Sets the processes using the GPU.
+    ///
+    /// # Arguments
+    ///
+    /// * `processes` - The processes with work on the GPU.
+    ///
+    /// # Returns
+    ///
+    /// The builder instance for method chaining.
+    pub fn processes(mut self, processes: impl IntoIterator<Item = GpuProcessInfo>) -> Self {
+        self.processes = Some(processes.into_iter().collect());
+        self
+    }
+
+    /// Sets the ECC memory error counts.
+    ///
+    /// # Arguments
+    ///
+    /// * `ecc_errors` - The counts reported by the driver.
+    ///
+    /// # Returns
+    ///
+    /// The builder instance for method chaining.
+    pub fn ecc_errors(mut self, ecc_errors: EccStats) -> Self {
+        self.ecc_errors = Some(ecc_errors);
+        self
+    }
+
+    /// Sets how trustworthy the GPU identity is (defaults to [`Confidence::Low`]).
+    ///
+    /// # Arguments
+    ///
+    /// * `confidence` - How the GPU was identified.
+    ///
+    /// # Returns
+    ///
+    /// The builder instance for method chaining.
+    pub fn confidence(mut self, confidence: Confidence) -> Self {
+        self.confidence = confidence;
+        self
+    }
+
     /// Builds the [`GpuInfo`] instance.
     ///
     /// All unset fields will default to their unknown values:
@@ -1437,6 +3238,7 @@ impl GpuInfoBuilder {
             vendor: self.vendor.unwrap_or(Vendor::Unknown),
             name_gpu: self.name_gpu,
             temperature: self.temperature,
+            temperatures: self.temperatures,
             utilization: self.utilization,
             power_usage: self.power_usage,
             core_clock: self.core_clock,
@@ -1448,6 +3250,19 @@ impl GpuInfoBuilder {
             memory_used: self.memory_used,
             driver_version: self.driver_version,
             max_clock_speed: self.max_clock_speed,
+            effective_core_clock: self.effective_core_clock,
+            fan_speed: self.fan_speed,
+            fan_speed_rpm: self.fan_speed_rpm,
+            encoder_util: self.encoder_util,
+            decoder_util: self.decoder_util,
+            throttle_reasons: self.throttle_reasons,
+            uuid: self.uuid,
+            bus_id: self.bus_id,
+            adapter_luid: self.adapter_luid,
+            processes: self.processes,
+            ecc_errors: self.ecc_errors,
+            metric_sources: MetricSources::default(),
+            confidence: self.confidence,
         }
     }
 
@@ -1516,6 +3331,60 @@ impl From<Vendor> for GpuInfo {
     }
 }
 
+#[cfg(feature = "serde_json")]
+impl GpuInfo {
+    /// Serializes the GPU information as compact JSON.
+    ///
+    /// The output can be read back with `GpuInfo::try_from(json.as_str())`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GpuError::Serialization`] if serialization fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gpu_info::{GpuInfo, Vendor};
+    ///
+    /// let gpu = GpuInfo::builder().vendor(Vendor::Nvidia).temperature(65.0).build();
+    /// let json = gpu.to_json().unwrap();
+    /// assert!(json.contains(r#""temperature":65.0"#));
+    /// assert_eq!(GpuInfo::try_from(json.as_str()).unwrap(), gpu);
+    /// ```
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string(self).map_err(|e| GpuError::Serialization(e.to_string()))
+    }
+
+    /// Serializes the GPU information as indented JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GpuError::Serialization`] if serialization fails.
+    pub fn to_json_pretty(&self) -> Result<String> {
+        serde_json::to_string_pretty(self).map_err(|e| GpuError::Serialization(e.to_string()))
+    }
+
+    /// Parses GPU information from JSON, as written by [`to_json()`](Self::to_json).
+    ///
+    /// Same as `GpuInfo::try_from(json)`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GpuError::Ffi`] if the JSON parsing fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gpu_info::GpuInfo;
+    ///
+    /// let gpu = GpuInfo::mock_nvidia();
+    /// assert_eq!(GpuInfo::from_json(&gpu.to_json().unwrap()).unwrap(), gpu);
+    /// ```
+    pub fn from_json(json: &str) -> Result<Self> {
+        Self::try_from(json)
+    }
+}
+
 /// Parses a `GpuInfo` from a JSON string.
 ///
 /// # Errors
@@ -1599,21 +3468,23 @@ impl Eq for GpuInfo {}
 
 /// `Hash` implementation for `GpuInfo`.
 ///
-/// Hashes by identity fields only: `vendor` and `name_gpu`.
-/// Metric fields (temperature, utilization, etc.) are intentionally excluded
-/// because they change frequently and shouldn't affect GPU identity.
+/// Hashes the UUID when the GPU has one, and otherwise the vendor and the
+/// normalized name, as in [`identity()`](GpuInfo::identity), so metrics do
+/// not affect the hash. This is consistent with `Eq`, but `PartialEq` compares
+/// every field: two samples of the same GPU with different metrics hash
+/// alike yet are not equal, so a `HashMap<GpuInfo, T>` cannot find a GPU
+/// again after its metrics change.
 ///
-/// This allows using `GpuInfo` as a key in `HashMap` or `HashSet` where
-/// the same physical GPU (identified by vendor + name) should hash to
-/// the same value regardless of current metrics.
+/// Relying on this impl to identify GPUs is deprecated; key maps with
+/// [`GpuIdentity`] instead.
 ///
 /// # Examples
 ///
 /// ```
-/// use gpu_info::{GpuInfo, vendor::Vendor};
+/// use gpu_info::{GpuIdentity, GpuInfo, vendor::Vendor};
 /// use std::collections::HashMap;
 ///
-/// let mut gpu_data: HashMap<GpuInfo, String> = HashMap::new();
+/// let mut gpu_data: HashMap<GpuIdentity, String> = HashMap::new();
 ///
 /// let gpu = GpuInfo::builder()
 ///     .vendor(Vendor::Nvidia)
@@ -1621,30 +3492,26 @@ impl Eq for GpuInfo {}
 ///     .temperature(65.0)
 ///     .build();
 ///
-/// gpu_data.insert(gpu.clone(), "Primary GPU".to_string());
+/// gpu_data.insert(gpu.identity(), "Primary GPU".to_string());
 ///
-/// // Same GPU with different temperature still matches
+/// // Same GPU with a different temperature still matches
 /// let gpu_updated = GpuInfo::builder()
 ///     .vendor(Vendor::Nvidia)
 ///     .name("RTX 3080")
 ///     .temperature(70.0)
 ///     .build();
-///
-/// // Note: This will NOT find the entry because PartialEq compares all fields
-/// // Use vendor + name for lookups if you need metric-independent matching
+/// assert!(gpu_data.contains_key(&gpu_updated.identity()));
 /// ```
-///
-/// # Note
-///
-/// The `Hash` implementation only considers identity fields, but `PartialEq`
-/// (derived) compares all fields. This means two `GpuInfo` instances with
-/// the same vendor and name but different metrics will hash to the same
-/// bucket but won't be considered equal. This is intentional for use cases
-/// where you want to group GPUs by identity but distinguish by metrics.
 impl Hash for GpuInfo {
     fn hash<H: Hasher>(&self, state: &mut H) {
-        self.vendor.hash(state);
-        self.name_gpu.hash(state);
+        let identity = self.identity();
+        match identity.uuid() {
+            Some(uuid) => uuid.hash(state),
+            None => {
+                identity.vendor().hash(state);
+                identity.name().hash(state);
+            }
+        }
     }
 }
 
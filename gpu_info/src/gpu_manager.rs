@@ -1,9 +1,31 @@
-use crate::gpu_info::{GpuError, GpuInfo, Result};
+use crate::cache_utils::{CacheStats, MetricKind};
+use crate::cancel::CancelToken;
+use crate::detection_report::DetectionReport;
+use crate::gpu_identity::{normalize_bus_id, GpuIdentity};
+use crate::gpu_index::{self, CudaIndex, GpuIndex};
+use crate::gpu_info::{GpuError, GpuInfo, GpuProvider, Result};
+use crate::metric_source::Metric;
+use crate::provider_manager::GpuProviderManager;
 use crate::query::GpuQuery;
 use crate::vendor::Vendor;
 use log::{debug, error, info, warn};
-use std::sync::{Arc, Mutex};
-use std::time::Duration;
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+/// Tolerance used by [`GpuManager::detect_changes_since`] when comparing
+/// floating-point metrics.
+pub const CHANGE_EPSILON: f32 = 0.01;
+
+/// Refreshes the metrics of a single GPU from its provider.
+type Updater = fn(&mut GpuInfo) -> Result<()>;
+/// Provider call refreshing only some metrics of a single GPU
+type MetricUpdater = fn(&mut GpuInfo, &[Metric]) -> Result<()>;
+
+/// Cache key of a GPU: its identity and how many earlier GPUs share it,
+/// so identical cards without a UUID or bus ID keep separate entries.
+type CacheKey = (GpuIdentity, usize);
 
 /// Manager for working with multiple GPUs in the system.
 ///
@@ -15,7 +37,7 @@ use std::time::Duration;
 /// For multi-threaded applications, wrap `GpuManager` in `Arc` for shared ownership:
 ///
 /// ```
-/// use gpu_info::GpuManager;
+/// use gpu_info::{GpuIndex, GpuManager};
 /// use std::sync::Arc;
 ///
 /// let manager = Arc::new(GpuManager::new());
@@ -23,7 +45,7 @@ use std::time::Duration;
 /// // Clone Arc for each thread
 /// let manager_clone = Arc::clone(&manager);
 /// std::thread::spawn(move || {
-///     let gpu = manager_clone.get_gpu_cached(0);
+///     let gpu = manager_clone.get_cached_gpu_at(GpuIndex(0));
 ///     // ...
 /// });
 /// ```
@@ -32,6 +54,8 @@ use std::time::Duration;
 ///
 /// The manager maintains an internal cache with configurable TTL (default: 500ms).
 /// Cache operations are thread-safe and use `Arc<GpuInfo>` for zero-copy reads.
+/// Entries are keyed by [`GpuIdentity`], so a cached entry belongs to a GPU,
+/// not to a position in the list.
 #[derive(Debug, Clone)]
 pub struct GpuManager {
     /// List of all detected GPUs
@@ -41,7 +65,26 @@ pub struct GpuManager {
     /// GPU information cache with unified caching utilities
     ///
     /// This cache eliminates duplication by using the common caching infrastructure.
-    cache: crate::cache_utils::MultiGpuInfoCache,
+    cache: crate::cache_utils::MultiGpuInfoCache<CacheKey>,
+    /// Diagnostics from the last detection pass
+    report: DetectionReport,
+    /// Whether provider output is checked for consistency
+    strict: bool,
+    /// Whether per-metric read times are recorded, see [`set_metric_age_tracking()`](Self::set_metric_age_tracking)
+    track_metric_ages: bool,
+    /// Per-metric TTLs, see [`set_metric_ttl()`](Self::set_metric_ttl)
+    metric_ttls: Vec<(MetricKind, Duration)>,
+    /// Provider call used to refresh a single GPU
+    updater: Updater,
+    /// Provider call used to refresh some metrics of a single GPU; `updater` if `None`
+    metric_updater: Option<MetricUpdater>,
+    /// Providers used instead of the platform ones, see [`with_providers()`](Self::with_providers)
+    providers: Option<Arc<GpuProviderManager>>,
+    /// Guard making concurrent [`warm()`](Self::warm) calls share one detection
+    warming: Arc<Mutex<()>>,
+    /// Result of a background detection started from the persistent cache
+    #[cfg(feature = "persistent-cache")]
+    background: Arc<Mutex<Option<Vec<GpuInfo>>>>,
 }
 impl Default for GpuManager {
     fn default() -> Self {
@@ -54,7 +97,17 @@ impl GpuManager {
         let mut manager = Self {
             gpus: Vec::new(),
             primary_gpu_index: 0,
-            cache: crate::cache_utils::MultiGpuInfoCache::new(Duration::from_millis(500)),
+            cache: crate::cache_utils::MultiGpuInfoCache::keyed(Duration::from_millis(500), 0),
+            report: DetectionReport::new(),
+            strict: cfg!(feature = "strict-validation"),
+            track_metric_ages: false,
+            metric_ttls: Vec::new(),
+            updater: Self::update_single_gpu_static,
+            metric_updater: Some(Self::update_gpu_metrics_static),
+            providers: None,
+            warming: Arc::new(Mutex::new(())),
+            #[cfg(feature = "persistent-cache")]
+            background: Arc::new(Mutex::new(None)),
         };
         manager.detect_all_gpus();
         manager
@@ -64,7 +117,17 @@ impl GpuManager {
         let mut manager = Self {
             gpus: Vec::new(),
             primary_gpu_index: 0,
-            cache: crate::cache_utils::MultiGpuInfoCache::new(cache_ttl),
+            cache: crate::cache_utils::MultiGpuInfoCache::keyed(cache_ttl, 0),
+            report: DetectionReport::new(),
+            strict: cfg!(feature = "strict-validation"),
+            track_metric_ages: false,
+            metric_ttls: Vec::new(),
+            updater: Self::update_single_gpu_static,
+            metric_updater: Some(Self::update_gpu_metrics_static),
+            providers: None,
+            warming: Arc::new(Mutex::new(())),
+            #[cfg(feature = "persistent-cache")]
+            background: Arc::new(Mutex::new(None)),
         };
         manager.detect_all_gpus();
         manager
@@ -74,26 +137,240 @@ impl GpuManager {
         let mut manager = Self {
             gpus: Vec::new(),
             primary_gpu_index: 0,
-            cache: crate::cache_utils::MultiGpuInfoCache::with_max_entries(cache_ttl, max_entries),
+            cache: crate::cache_utils::MultiGpuInfoCache::keyed(cache_ttl, max_entries),
+            report: DetectionReport::new(),
+            strict: cfg!(feature = "strict-validation"),
+            track_metric_ages: false,
+            metric_ttls: Vec::new(),
+            updater: Self::update_single_gpu_static,
+            metric_updater: Some(Self::update_gpu_metrics_static),
+            providers: None,
+            warming: Arc::new(Mutex::new(())),
+            #[cfg(feature = "persistent-cache")]
+            background: Arc::new(Mutex::new(None)),
         };
         manager.detect_all_gpus();
         manager
     }
+    /// Creates a manager that detects and refreshes GPUs through `providers`
+    ///
+    /// The platform providers are not used at all, which makes detection
+    /// deterministic and independent of the installed hardware, e.g. for
+    /// benchmarks and tests with mock providers. GPUs are refreshed by the
+    /// provider registered for their vendor.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gpu_info::gpu_info::{GpuInfo, GpuProvider, Result};
+    /// use gpu_info::{GpuManager, GpuProviderManager, Vendor};
+    ///
+    /// struct MockProvider;
+    ///
+    /// impl GpuProvider for MockProvider {
+    ///     fn detect_gpus(&self) -> Result<Vec<GpuInfo>> {
+    ///         Ok(vec![GpuInfo::mock_nvidia()])
+    ///     }
+    ///
+    ///     fn update_gpu(&self, _gpu: &mut GpuInfo) -> Result<()> {
+    ///         Ok(())
+    ///     }
+    ///
+    ///     fn get_vendor(&self) -> Vendor {
+    ///         Vendor::Nvidia
+    ///     }
+    /// }
+    ///
+    /// let mut providers = GpuProviderManager::new();
+    /// providers.register_provider(Vendor::Nvidia, MockProvider);
+    /// let mut manager = GpuManager::with_providers(providers);
+    /// assert_eq!(manager.gpu_count(), 1);
+    /// manager.refresh_all_gpus().unwrap();
+    /// ```
+    pub fn with_providers(providers: GpuProviderManager) -> Self {
+        let mut manager: Self = std::iter::empty().collect();
+        manager.providers = Some(Arc::new(providers));
+        manager.detect_all_gpus();
+        manager
+    }
+    /// Creates a manager pre-populated from the persistent identity cache.
+    ///
+    /// If `cache` holds a valid entry, the manager is returned immediately
+    /// with identity-only GPU data (vendor, name, UUID, PCI bus ID, total
+    /// memory, driver version) and full detection runs on a background thread. Its result
+    /// validates the cache and replaces the cached data on the next
+    /// [`refresh_all_gpus()`](Self::refresh_all_gpus) or
+    /// [`apply_background_detection()`](Self::apply_background_detection).
+    ///
+    /// Without a valid entry this behaves like [`new()`](Self::new) and
+    /// writes the detected GPUs to the cache.
+    #[cfg(feature = "persistent-cache")]
+    pub fn with_persistent_cache(cache: crate::persistent_cache::PersistentCache) -> Self {
+        let Some(persisted) = cache.load() else {
+            let manager = Self::new();
+            cache.validate(&manager.gpus);
+            return manager;
+        };
+        debug!(
+            "Loaded {} GPU(s) from persistent cache {}",
+            persisted.gpus.len(),
+            cache.path().display()
+        );
+        let manager = Self {
+            gpus: persisted.to_gpu_infos(),
+            primary_gpu_index: 0,
+            cache: crate::cache_utils::MultiGpuInfoCache::keyed(Duration::from_millis(500), 0),
+            report: DetectionReport::new(),
+            strict: cfg!(feature = "strict-validation"),
+            track_metric_ages: false,
+            metric_ttls: Vec::new(),
+            updater: Self::update_single_gpu_static,
+            metric_updater: Some(Self::update_gpu_metrics_static),
+            providers: None,
+            warming: Arc::new(Mutex::new(())),
+            background: Arc::new(Mutex::new(None)),
+        };
+        let slot = Arc::clone(&manager.background);
+        std::thread::spawn(move || {
+            let detected = Self::new();
+            if !cache.validate(&detected.gpus) {
+                info!("Persistent GPU cache was outdated and has been replaced");
+            }
+            if let Ok(mut slot) = slot.lock() {
+                *slot = Some(detected.gpus);
+            }
+        });
+        manager
+    }
+    /// Replaces cached identity data with the result of the background
+    /// detection started by [`with_persistent_cache()`](Self::with_persistent_cache).
+    ///
+    /// Returns `true` if new data was applied, `false` if the background
+    /// detection has not finished or was already applied.
+    #[cfg(feature = "persistent-cache")]
+    pub fn apply_background_detection(&mut self) -> bool {
+        let detected = self.background.lock().ok().and_then(|mut slot| slot.take());
+        let Some(gpus) = detected else {
+            return false;
+        };
+        self.gpus = gpus;
+        if self.primary_gpu_index >= self.gpus.len() {
+            self.primary_gpu_index = 0;
+        }
+        self.cache.clear_all();
+        true
+    }
+    /// Creates a manager, stopping detection early when `token` is cancelled
+    ///
+    /// Providers check the token between devices and backends, and external
+    /// commands are killed as soon as it is cancelled. A cancelled detection
+    /// keeps the GPUs found so far and marks the
+    /// [`detection_report()`](Self::detection_report) as cancelled instead
+    /// of failing.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use gpu_info::{CancelToken, GpuManager};
+    ///
+    /// let token = CancelToken::new();
+    /// let worker_token = token.clone();
+    /// let worker = std::thread::spawn(move || GpuManager::detect_with_cancel(worker_token));
+    ///
+    /// // The window was closed: stop detection
+    /// token.cancel();
+    /// let manager = worker.join().unwrap();
+    /// if manager.detection_report().is_cancelled() {
+    ///     println!("partial results: {} GPU(s)", manager.gpu_count());
+    /// }
+    /// ```
+    pub fn detect_with_cancel(token: CancelToken) -> Self {
+        let mut manager: Self = std::iter::empty().collect();
+        manager.run_detection(&token);
+        manager
+    }
     /// Detects all GPUs in the system
     pub fn detect_all_gpus(&mut self) {
+        self.run_detection(&CancelToken::new());
+    }
+    /// Detects all GPUs, stopping early when `token` is cancelled
+    fn run_detection(&mut self, token: &CancelToken) {
         self.gpus.clear();
+        self.report.clear();
         info!("Starting multi-GPU detection");
+        if let Some(providers) = self.providers.clone() {
+            let names: Vec<(String, &dyn GpuProvider)> = providers
+                .providers()
+                .map(|(vendor, provider)| (vendor.to_string(), provider))
+                .collect();
+            let providers: Vec<(&str, &dyn GpuProvider)> = names
+                .iter()
+                .map(|(name, provider)| (name.as_str(), *provider))
+                .collect();
+            self.detect_from_providers(&providers, token);
+            self.finish_detection(token);
+            return;
+        }
         #[cfg(target_os = "windows")]
         {
-            self.detect_windows_gpus();
+            self.detect_windows_gpus(token);
         }
         #[cfg(target_os = "linux")]
         {
-            self.detect_linux_gpus();
+            let providers: &[(&str, &dyn GpuProvider)] = &[
+                #[cfg(feature = "vendor-nvidia")]
+                (
+                    "NVIDIA",
+                    &crate::providers::linux::NvidiaLinuxProvider::new(),
+                ),
+                #[cfg(feature = "vendor-amd")]
+                ("AMD", &crate::providers::linux::AmdLinuxProvider::new()),
+            ];
+            self.detect_from_providers(providers, token);
         }
-        #[cfg(target_os = "macos")]
+        #[cfg(all(target_os = "macos", feature = "vendor-apple"))]
         {
-            self.detect_macos_gpus();
+            self.detect_macos_gpus(token);
+        }
+        self.finish_detection(token);
+    }
+    /// Runs `providers` in order, checking `token` before each one
+    pub(crate) fn detect_from_providers(
+        &mut self,
+        providers: &[(&str, &dyn GpuProvider)],
+        token: &CancelToken,
+    ) {
+        for (name, provider) in providers {
+            if token.is_cancelled() {
+                debug!("Detection cancelled before the {} provider", name);
+                return;
+            }
+            match provider.detect_gpus_with_cancel(token, &mut self.report) {
+                Ok(gpus) => {
+                    for gpu in gpus {
+                        info!("Found {} GPU: {:?}", name, gpu.name_gpu);
+                        self.gpus.push(gpu);
+                    }
+                }
+                Err(e) => {
+                    warn!("Failed to detect {} GPUs: {}", name, e);
+                }
+            }
+        }
+    }
+    /// Validates detected GPUs, records cancellation and selects the primary GPU
+    pub(crate) fn finish_detection(&mut self, token: &CancelToken) {
+        if token.is_cancelled() {
+            info!(
+                "GPU detection cancelled, keeping {} GPU(s) found so far",
+                self.gpus.len()
+            );
+            self.report.mark_cancelled();
+        }
+        if self.strict {
+            for gpu in &mut self.gpus {
+                Self::enforce_consistency(self.providers.as_deref(), gpu);
+            }
         }
         if self.gpus.is_empty() {
             warn!("No GPUs detected in the system");
@@ -104,65 +381,20 @@ impl GpuManager {
         }
     }
     #[cfg(target_os = "windows")]
-    fn detect_windows_gpus(&mut self) {
-        use crate::providers::{amd, intel, nvidia};
-        // NVIDIA GPUs
-        if let Ok(nvidia_gpus) = nvidia::detect_nvidia_gpus() {
-            for (index, gpu) in nvidia_gpus.into_iter().enumerate() {
-                info!("Found NVIDIA GPU #{}: {:?}", index, gpu.name_gpu);
-                self.gpus.push(gpu);
-            }
-        }
-        // AMD GPUs
-        if let Ok(amd_gpus) = amd::detect_amd_gpus() {
-            for (index, gpu) in amd_gpus.into_iter().enumerate() {
-                info!("Found AMD GPU #{}: {:?}", index, gpu.name_gpu);
-                self.gpus.push(gpu);
-            }
-        }
-        // Intel GPUs
-        let intel_gpus = intel::detect_intel_gpus();
-        for (index, gpu) in intel_gpus.into_iter().enumerate() {
-            info!("Found Intel GPU #{}: {:?}", index, gpu.name_gpu);
+    fn detect_windows_gpus(&mut self, token: &CancelToken) {
+        for gpu in crate::windows::get_all_gpus_with_cancel(token, &mut self.report) {
+            info!("Found {} GPU: {:?}", gpu.vendor, gpu.name_gpu);
             self.gpus.push(gpu);
         }
     }
-    #[cfg(target_os = "linux")]
-    fn detect_linux_gpus(&mut self) {
-        use crate::gpu_info::GpuProvider;
-        use crate::providers::linux::{AmdLinuxProvider, NvidiaLinuxProvider};
-        // NVIDIA GPUs
-        let nvidia_provider = NvidiaLinuxProvider::new();
-        match nvidia_provider.detect_gpus() {
-            Ok(nvidia_gpus) => {
-                for gpu in nvidia_gpus {
-                    info!("Found NVIDIA GPU: {:?}", gpu.name_gpu);
-                    self.gpus.push(gpu);
-                }
-            }
-            Err(e) => {
-                warn!("Failed to detect NVIDIA GPUs: {}", e);
-            }
-        }
-        // AMD GPUs
-        let amd_provider = AmdLinuxProvider::new();
-        match amd_provider.detect_gpus() {
-            Ok(amd_gpus) => {
-                for gpu in amd_gpus {
-                    info!("Found AMD GPU: {:?}", gpu.name_gpu);
-                    self.gpus.push(gpu);
-                }
-            }
-            Err(e) => {
-                warn!("Failed to detect AMD GPUs: {}", e);
-            }
-        }
-    }
-    #[cfg(target_os = "macos")]
-    fn detect_macos_gpus(&mut self) {
+    #[cfg(all(target_os = "macos", feature = "vendor-apple"))]
+    fn detect_macos_gpus(&mut self, token: &CancelToken) {
         use crate::macos;
-        let gpus = macos::get_all_gpus();
-        for gpu in gpus {
+        use crate::providers::macos::sandbox::{self, SystemEnvironment, SANDBOX_HINT};
+        if sandbox::is_sandboxed(&SystemEnvironment) {
+            self.report.add_hint(SANDBOX_HINT);
+        }
+        for gpu in macos::get_all_gpus_with_cancel(token) {
             info!("Found macOS GPU: {:?}", gpu.name_gpu);
             self.gpus.push(gpu);
         }
@@ -193,6 +425,122 @@ impl GpuManager {
             );
         }
     }
+    /// Enables or disables strict consistency checking of provider output
+    ///
+    /// In strict mode every `GpuInfo` produced by a provider is passed through
+    /// [`consistency::enforce`](crate::consistency::enforce): violations panic in
+    /// debug builds and are logged with the provider's name and repaired in
+    /// release builds.
+    ///
+    /// Strict mode is on by default when the `strict-validation` feature is enabled.
+    pub fn set_strict(&mut self, strict: bool) {
+        self.strict = strict;
+    }
+    /// Returns `true` if strict consistency checking is enabled
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+    /// Enables or disables tracking when each metric was last read
+    ///
+    /// With tracking enabled, a refresh that does not return a metric keeps
+    /// its previous value, and [`GpuInfo::metric_age()`] reports how old it
+    /// is. Combine with [`GpuThresholds::max_metric_age`](crate::monitoring::GpuThresholds::max_metric_age)
+    /// so alerts ignore stale values. Tracking is off by default, and then
+    /// refreshes neither copy nor allocate anything for it.
+    pub fn set_metric_age_tracking(&mut self, enabled: bool) {
+        self.track_metric_ages = enabled;
+    }
+    /// Returns `true` if per-metric ages are tracked
+    pub fn is_metric_age_tracking(&self) -> bool {
+        self.track_metric_ages
+    }
+    /// Sets how long a cached `metric` stays fresh, replacing an earlier TTL
+    ///
+    /// When a cached read finds metrics older than their TTL, the GPU is
+    /// queried again but only those metrics are replaced in the cache entry;
+    /// the other fields and their refresh times are kept. Only TTLs shorter
+    /// than the cache TTL have an effect, since an expired entry is refreshed
+    /// as a whole.
+    ///
+    /// Stale metrics are read with
+    /// [`GpuProvider::update_metrics()`](crate::gpu_info::GpuProvider::update_metrics).
+    /// Providers with per-metric reads, such as the Linux AMD provider, then
+    /// read only those; others fall back to their full update.
+    ///
+    /// # Example
+    /// ```
+    /// use gpu_info::cache_utils::MetricKind;
+    /// use gpu_info::metric_source::Metric;
+    /// use gpu_info::GpuManager;
+    /// use std::time::Duration;
+    ///
+    /// let mut manager = GpuManager::new();
+    /// manager.set_metric_ttl(Metric::Utilization.into(), Duration::from_millis(100));
+    /// assert_eq!(
+    ///     manager.metric_ttl(MetricKind::Metric(Metric::Utilization)),
+    ///     Some(Duration::from_millis(100))
+    /// );
+    /// ```
+    pub fn set_metric_ttl(&mut self, metric: MetricKind, ttl: Duration) {
+        match self.metric_ttls.iter_mut().find(|(m, _)| *m == metric) {
+            Some(entry) => entry.1 = ttl,
+            None => self.metric_ttls.push((metric, ttl)),
+        }
+    }
+    /// Returns the TTL set for `metric` with [`set_metric_ttl()`](Self::set_metric_ttl)
+    pub fn metric_ttl(&self, metric: MetricKind) -> Option<Duration> {
+        self.metric_ttls
+            .iter()
+            .find(|(m, _)| *m == metric)
+            .map(|&(_, ttl)| ttl)
+    }
+    /// Returns the cache key of the GPU at `index`
+    fn cache_key(gpus: &[GpuInfo], index: usize) -> Option<CacheKey> {
+        let identity = gpus.get(index)?.identity();
+        let earlier = gpus[..index]
+            .iter()
+            .filter(|gpu| gpu.identity() == identity)
+            .count();
+        Some((identity, earlier))
+    }
+    /// Checks a provider result, logging and repairing violations
+    fn enforce_consistency(providers: Option<&GpuProviderManager>, gpu: &mut GpuInfo) {
+        let provider = Self::provider_name(providers, &gpu.vendor);
+        crate::consistency::enforce(gpu, provider);
+    }
+    /// Name of the provider that detects and refreshes GPUs of `vendor`
+    fn provider_name<'a>(providers: Option<&'a GpuProviderManager>, vendor: &Vendor) -> &'a str {
+        match providers.and_then(|providers| providers.provider_for(vendor)) {
+            Some(provider) => provider.name(),
+            None => Self::platform_provider_name(vendor),
+        }
+    }
+    /// Name of the platform provider used by [`update_single_gpu_static()`](Self::update_single_gpu_static)
+    fn platform_provider_name(vendor: &Vendor) -> &'static str {
+        match vendor {
+            #[cfg(target_os = "windows")]
+            Vendor::Nvidia => "NvidiaProvider",
+            #[cfg(target_os = "windows")]
+            Vendor::Amd => "AmdProvider",
+            #[cfg(target_os = "windows")]
+            Vendor::Intel(_) => "IntelProvider",
+            #[cfg(target_os = "linux")]
+            Vendor::Nvidia => "NvidiaLinuxProvider",
+            #[cfg(target_os = "linux")]
+            Vendor::Amd => "AmdLinuxProvider",
+            #[cfg(target_os = "macos")]
+            _ => "MacosProvider",
+            #[cfg(not(target_os = "macos"))]
+            _ => "unknown",
+        }
+    }
+    /// Returns diagnostics collected during the last detection pass
+    ///
+    /// The report contains per-stage latencies and actionable hints, such as
+    /// advice to enable NVIDIA persistence mode after a slow NVML cold start.
+    pub fn detection_report(&self) -> &DetectionReport {
+        &self.report
+    }
     /// Returns the number of detected GPUs
     pub fn gpu_count(&self) -> usize {
         self.gpus.len()
@@ -205,6 +553,27 @@ impl GpuManager {
     pub fn get_all_gpus_owned(&self) -> Vec<GpuInfo> {
         self.gpus.clone()
     }
+    /// Serializes all GPUs as a JSON array, in detection order
+    ///
+    /// Each element has the format of [`GpuInfo::to_json()`].
+    ///
+    /// # Errors
+    /// Returns [`GpuError::Serialization`] if serialization fails.
+    ///
+    /// # Example
+    /// ```
+    /// use gpu_info::{GpuInfo, GpuManager};
+    ///
+    /// let manager: GpuManager = vec![GpuInfo::mock_nvidia(), GpuInfo::mock_amd()]
+    ///     .into_iter()
+    ///     .collect();
+    /// let json = manager.export_all_json().unwrap();
+    /// assert!(json.starts_with('['));
+    /// ```
+    #[cfg(feature = "serde_json")]
+    pub fn export_all_json(&self) -> Result<String> {
+        serde_json::to_string(&self.gpus).map_err(|e| GpuError::Serialization(e.to_string()))
+    }
     /// Returns the primary GPU
     pub fn get_primary_gpu(&self) -> Option<&GpuInfo> {
         self.gpus.get(self.primary_gpu_index)
@@ -213,13 +582,88 @@ impl GpuManager {
     pub fn get_primary_gpu_owned(&self) -> Option<GpuInfo> {
         self.gpus.get(self.primary_gpu_index).cloned()
     }
+    /// Returns the GPU at `index`
+    ///
+    /// A CUDA device ordinal must be converted with
+    /// [`cuda_to_gpu_index()`](Self::cuda_to_gpu_index) first.
+    pub fn get_gpu_at(&self, index: GpuIndex) -> Option<&GpuInfo> {
+        self.gpus.get(index.get())
+    }
+    /// Returns a copy of the GPU at `index`
+    pub fn get_gpu_at_owned(&self, index: GpuIndex) -> Option<GpuInfo> {
+        self.gpus.get(index.get()).cloned()
+    }
     /// Returns GPU by index
+    #[deprecated(note = "use `get_gpu_at(GpuIndex(index))`")]
     pub fn get_gpu_by_index(&self, index: usize) -> Option<&GpuInfo> {
-        self.gpus.get(index)
+        self.get_gpu_at(GpuIndex(index))
     }
     /// Returns a copy of GPU by index
+    #[deprecated(note = "use `get_gpu_at_owned(GpuIndex(index))`")]
     pub fn get_gpu_by_index_owned(&self, index: usize) -> Option<GpuInfo> {
-        self.gpus.get(index).cloned()
+        self.get_gpu_at_owned(GpuIndex(index))
+    }
+    /// Returns the GPU at PCI bus ID `pci_id`, e.g. `0000:01:00.0`, through the cache
+    ///
+    /// Unlike an index, the bus ID addresses the same card across restarts
+    /// and tells identical cards apart. NVML's 8-digit domain form is
+    /// accepted as well.
+    ///
+    /// # Example
+    /// ```
+    /// use gpu_info::{GpuInfo, GpuManager};
+    ///
+    /// let mut gpu = GpuInfo::mock_nvidia();
+    /// gpu.bus_id = Some("0000:65:00.0".to_string());
+    /// let manager: GpuManager = vec![GpuInfo::mock_nvidia(), gpu].into_iter().collect();
+    /// let found = manager.get_gpu_by_pci_id("00000000:65:00.0").unwrap();
+    /// assert_eq!(found.bus_id.as_deref(), Some("0000:65:00.0"));
+    /// assert!(manager.get_gpu_by_pci_id("0000:01:00.0").is_none());
+    /// ```
+    pub fn get_gpu_by_pci_id(&self, pci_id: &str) -> Option<Arc<GpuInfo>> {
+        let wanted = normalize_bus_id(pci_id)?;
+        let index = self
+            .gpus
+            .iter()
+            .position(|gpu| gpu.identity().bus_id() == Some(wanted.as_str()))?;
+        self.get_cached_gpu_at(GpuIndex(index))
+    }
+    /// Returns the manager indices of the GPUs visible to CUDA, keyed by
+    /// CUDA device ordinal
+    ///
+    /// The NVIDIA GPUs are numbered in detection order, which is PCI bus
+    /// order and matches CUDA with `CUDA_DEVICE_ORDER=PCI_BUS_ID`.
+    /// `CUDA_VISIBLE_DEVICES` is honoured if it lists ordinals; GPU UUIDs
+    /// are not supported.
+    pub fn cuda_indices(&self) -> BTreeMap<CudaIndex, GpuIndex> {
+        let nvidia: Vec<GpuIndex> = self
+            .gpus
+            .iter()
+            .enumerate()
+            .filter(|(_, gpu)| gpu.vendor == Vendor::Nvidia)
+            .map(|(index, _)| GpuIndex(index))
+            .collect();
+        let visible_devices = std::env::var("CUDA_VISIBLE_DEVICES").ok();
+        gpu_index::cuda_index_map(&nvidia, visible_devices.as_deref())
+    }
+    /// Converts a CUDA device ordinal to the manager index of the same GPU
+    ///
+    /// Returns `None` if no GPU has this ordinal. See
+    /// [`cuda_indices()`](Self::cuda_indices) for how ordinals are assigned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gpu_info::{CudaIndex, GpuManager};
+    ///
+    /// let manager = GpuManager::new();
+    /// if let Some(index) = manager.cuda_to_gpu_index(CudaIndex(0)) {
+    ///     let gpu = manager.get_gpu_at(index);
+    ///     println!("cuda:0 is {:?}", gpu.and_then(|gpu| gpu.name_gpu.as_deref()));
+    /// }
+    /// ```
+    pub fn cuda_to_gpu_index(&self, ordinal: CudaIndex) -> Option<GpuIndex> {
+        self.cuda_indices().get(&ordinal).copied()
     }
     /// Returns GPUs by vendor
     pub fn get_gpus_by_vendor(&self, vendor: Vendor) -> Vec<&GpuInfo> {
@@ -236,12 +680,13 @@ impl GpuManager {
             .cloned()
             .collect()
     }
-    /// Sets the primary GPU
+    /// Makes the GPU at `index` the primary GPU
     ///
     /// # Errors
     ///
     /// Returns [`GpuError::GpuNotFound`] if the index is out of bounds.
-    pub fn set_primary_gpu(&mut self, index: usize) -> Result<()> {
+    pub fn set_primary_gpu_at(&mut self, index: GpuIndex) -> Result<()> {
+        let index = index.get();
         if index >= self.gpus.len() {
             return Err(GpuError::GpuNotFound);
         }
@@ -253,6 +698,15 @@ impl GpuManager {
         );
         Ok(())
     }
+    /// Sets the primary GPU
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GpuError::GpuNotFound`] if the index is out of bounds.
+    #[deprecated(note = "use `set_primary_gpu_at(GpuIndex(index))`")]
+    pub fn set_primary_gpu(&mut self, index: usize) -> Result<()> {
+        self.set_primary_gpu_at(GpuIndex(index))
+    }
     /// Updates information about all GPUs
     ///
     /// # Errors
@@ -260,12 +714,19 @@ impl GpuManager {
     /// Returns the first error encountered during GPU updates. All GPUs are
     /// attempted to be updated even if some fail.
     pub fn refresh_all_gpus(&mut self) -> Result<()> {
+        #[cfg(feature = "persistent-cache")]
+        self.apply_background_detection();
         debug!("Refreshing information for all {} GPUs", self.gpus.len());
         let mut errors = Vec::new();
+        let updater = self.updater;
+        let providers = self.providers.clone();
+        let track_ages = self.track_metric_ages;
         for (index, gpu) in self.gpus.iter_mut().enumerate() {
-            if let Err(e) = Self::update_single_gpu_static(gpu) {
+            if let Err(e) = Self::update_with(providers.as_deref(), updater, gpu, track_ages) {
                 error!("Failed to update GPU #{}: {}", index, e);
                 errors.push((index, e));
+            } else if self.strict {
+                Self::enforce_consistency(providers.as_deref(), gpu);
             }
         }
         self.cache.clear_all();
@@ -275,36 +736,265 @@ impl GpuManager {
             Ok(())
         }
     }
-    /// Updates information about a specific GPU
+    /// Updates all GPUs concurrently, one scoped thread per GPU
+    ///
+    /// A slow provider only delays its own GPU, so a full refresh takes about
+    /// as long as the slowest GPU instead of the sum of all of them. GPUs
+    /// keep their order, and each updated GPU replaces its own cache entry.
+    /// A GPU that fails to update keeps its previous metrics and cache entry,
+    /// and does not stop the others from updating.
+    ///
+    /// Returns the index and error of every GPU that failed to update.
+    pub fn refresh_parallel(&mut self) -> Vec<(usize, GpuError)> {
+        #[cfg(feature = "persistent-cache")]
+        self.apply_background_detection();
+        debug!("Refreshing {} GPUs in parallel", self.gpus.len());
+        let updater = self.updater;
+        let providers = self.providers.as_deref();
+        let track_ages = self.track_metric_ages;
+        let results = std::thread::scope(|scope| {
+            let handles: Vec<_> = self
+                .gpus
+                .iter()
+                .map(|gpu| {
+                    let mut gpu = gpu.clone();
+                    scope.spawn(move || {
+                        Self::update_with(providers, updater, &mut gpu, track_ages).map(|()| gpu)
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap_or(Err(GpuError::GpuNotActive)))
+                .collect()
+        });
+        self.store_refreshed(results)
+    }
+    /// Asynchronously updates all GPUs concurrently
+    ///
+    /// Provider calls are blocking FFI, so every GPU is updated on its own
+    /// task of tokio's blocking pool. Otherwise behaves like
+    /// [`refresh_parallel()`](Self::refresh_parallel).
+    ///
+    /// Returns the index and error of every GPU that failed to update.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use gpu_info::GpuManager;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut manager = GpuManager::new();
+    ///     for (index, error) in manager.refresh_async().await {
+    ///         eprintln!("GPU #{} was not updated: {}", index, error);
+    ///     }
+    /// }
+    /// ```
+    pub async fn refresh_async(&mut self) -> Vec<(usize, GpuError)> {
+        #[cfg(feature = "persistent-cache")]
+        self.apply_background_detection();
+        debug!("Refreshing {} GPUs asynchronously", self.gpus.len());
+        let updater = self.updater;
+        let track_ages = self.track_metric_ages;
+        let tasks: Vec<_> = self
+            .gpus
+            .iter()
+            .map(|gpu| {
+                let mut gpu = gpu.clone();
+                let providers = self.providers.clone();
+                tokio::task::spawn_blocking(move || {
+                    Self::update_with(providers.as_deref(), updater, &mut gpu, track_ages)
+                        .map(|()| gpu)
+                })
+            })
+            .collect();
+        let mut results = Vec::with_capacity(tasks.len());
+        for task in tasks {
+            results.push(task.await.unwrap_or(Err(GpuError::GpuNotActive)));
+        }
+        self.store_refreshed(results)
+    }
+    /// Stores the updated GPUs of a concurrent refresh in order
+    ///
+    /// `results` holds one entry per GPU. Returns the failed updates.
+    fn store_refreshed(&mut self, results: Vec<Result<GpuInfo>>) -> Vec<(usize, GpuError)> {
+        let mut errors = Vec::new();
+        for (index, result) in results.into_iter().enumerate() {
+            match result {
+                Ok(mut gpu) => {
+                    if self.strict {
+                        Self::enforce_consistency(self.providers.as_deref(), &mut gpu);
+                    }
+                    self.gpus[index] = gpu;
+                    if let Some(key) = Self::cache_key(&self.gpus, index) {
+                        self.cache.set(key, self.gpus[index].clone());
+                    }
+                }
+                Err(e) => {
+                    error!("Failed to update GPU #{}: {}", index, e);
+                    errors.push((index, e));
+                }
+            }
+        }
+        errors
+    }
+    /// Updates information about the GPU at `index`
     ///
     /// # Errors
     ///
     /// Returns an error in the following cases:
     /// - [`GpuError::GpuNotFound`] - The index is out of bounds
     /// - Provider-specific errors if the GPU update fails
-    pub fn refresh_gpu(&mut self, index: usize) -> Result<()> {
+    pub fn refresh_gpu_at(&mut self, index: GpuIndex) -> Result<()> {
+        let index = index.get();
+        let updater = self.updater;
+        let providers = self.providers.clone();
+        let track_ages = self.track_metric_ages;
         let gpu = self.gpus.get_mut(index).ok_or(GpuError::GpuNotFound)?;
-        Self::update_single_gpu_static(gpu)?;
-        self.cache.set(index, gpu.clone());
+        Self::update_with(providers.as_deref(), updater, gpu, track_ages)?;
+        if self.strict {
+            Self::enforce_consistency(providers.as_deref(), gpu);
+        }
+        let gpu = gpu.clone();
+        if let Some(key) = Self::cache_key(&self.gpus, index) {
+            self.cache.set(key, gpu);
+        }
         Ok(())
     }
+    /// Updates information about a specific GPU
+    ///
+    /// # Errors
+    ///
+    /// Returns an error in the following cases:
+    /// - [`GpuError::GpuNotFound`] - The index is out of bounds
+    /// - Provider-specific errors if the GPU update fails
+    #[deprecated(note = "use `refresh_gpu_at(GpuIndex(index))`")]
+    pub fn refresh_gpu(&mut self, index: usize) -> Result<()> {
+        self.refresh_gpu_at(GpuIndex(index))
+    }
     /// Updates information about the primary GPU
     ///
     /// # Errors
     ///
     /// Returns an error if the primary GPU update fails.
     pub fn refresh_primary_gpu(&mut self) -> Result<()> {
-        self.refresh_gpu(self.primary_gpu_index)
+        self.refresh_gpu_at(GpuIndex(self.primary_gpu_index))
+    }
+    /// Populates the cache with fresh metrics for every detected GPU.
+    ///
+    /// Use this to move the provider calls of the first cached read out of a
+    /// latency-sensitive path, e.g. during startup. GPUs with a fresh cache
+    /// entry are skipped and update failures are logged, not returned.
+    ///
+    /// Concurrent calls are single-flight: while one call updates the GPUs,
+    /// the others wait for it and then find the cache already populated.
+    ///
+    /// # Example
+    /// ```
+    /// use gpu_info::GpuManager;
+    ///
+    /// let manager = GpuManager::new();
+    /// manager.warm();
+    /// let primary = manager.get_primary_gpu_cached(); // served from the cache
+    /// ```
+    pub fn warm(&self) {
+        let _guard = self.warming.lock().unwrap_or_else(PoisonError::into_inner);
+        debug!("Warming cache for {} GPU(s)", self.gpus.len());
+        for index in 0..self.gpus.len() {
+            self.get_cached_gpu_at(GpuIndex(index));
+        }
+    }
+    /// Returns statistics of the GPU info cache
+    ///
+    /// Hits and misses count lookups by the `*_cached` methods since the
+    /// manager was created.
+    pub fn cache_stats(&self) -> CacheStats {
+        self.cache.get_stats().unwrap_or_default()
+    }
+    /// Removes all entries from the GPU info cache
+    ///
+    /// Use this after a GPU was removed, e.g. an eGPU was unplugged, so no
+    /// stale entry is kept. The next cached read queries the provider again.
+    pub fn clear_cache(&self) {
+        self.cache.clear_all();
+    }
+    /// Changes the maximum number of cached GPUs (0 = unlimited)
+    ///
+    /// Entries over the new capacity are evicted immediately, least recently
+    /// used first.
+    pub fn set_cache_capacity(&self, max_entries: usize) {
+        self.cache.set_capacity(max_entries);
+    }
+    /// Creates a manager for `gpus` with an empty cache, refreshed by `updater`
+    #[cfg(test)]
+    pub(crate) fn with_updater(gpus: Vec<GpuInfo>, updater: Updater, cache_ttl: Duration) -> Self {
+        let mut manager: Self = gpus.into_iter().collect();
+        manager.cache = crate::cache_utils::MultiGpuInfoCache::keyed(cache_ttl, 0);
+        manager.updater = updater;
+        manager.metric_updater = None;
+        manager
+    }
+    /// Refreshes `gpu` through `providers` if set, otherwise through `updater`
+    ///
+    /// With `track_ages`, metrics the refresh did not return keep their
+    /// previous value and read time.
+    fn update_with(
+        providers: Option<&GpuProviderManager>,
+        updater: Updater,
+        gpu: &mut GpuInfo,
+        track_ages: bool,
+    ) -> Result<()> {
+        let previous = track_ages.then(|| gpu.clone());
+        match providers {
+            Some(providers) => providers.update_gpu(gpu)?,
+            None => updater(gpu)?,
+        }
+        if let Some(previous) = previous {
+            gpu.track_metric_times(&previous, Instant::now());
+        }
+        Ok(())
+    }
+    /// Refreshes `metrics` of `gpu` through `providers` if set, otherwise
+    /// through `metric_updater`, falling back to a full update by `updater`
+    ///
+    /// With `track_ages`, metrics the refresh did not return keep their
+    /// previous value and read time.
+    fn update_metrics_with(&self, gpu: &mut GpuInfo, metrics: &[Metric]) -> Result<()> {
+        let previous = self.track_metric_ages.then(|| gpu.clone());
+        match (self.providers.as_deref(), self.metric_updater) {
+            (Some(providers), _) => providers.update_metrics(gpu, metrics)?,
+            (None, Some(metric_updater)) => metric_updater(gpu, metrics)?,
+            (None, None) => (self.updater)(gpu)?,
+        }
+        if let Some(previous) = previous {
+            gpu.track_metric_times(&previous, Instant::now());
+        }
+        Ok(())
+    }
+    /// Internal function for updating some metrics of a single GPU
+    ///
+    /// Uses the per-metric reads of the Linux AMD provider and the full
+    /// update of [`update_single_gpu_static()`](Self::update_single_gpu_static)
+    /// for other GPUs.
+    #[allow(unused_variables)]
+    fn update_gpu_metrics_static(gpu: &mut GpuInfo, metrics: &[Metric]) -> Result<()> {
+        #[cfg(all(target_os = "linux", feature = "vendor-amd"))]
+        if gpu.vendor == Vendor::Amd {
+            return crate::providers::linux::AmdLinuxProvider::new().update_metrics(gpu, metrics);
+        }
+        Self::update_single_gpu_static(gpu)
     }
     /// Internal function for updating a single GPU
     fn update_single_gpu_static(gpu: &mut GpuInfo) -> Result<()> {
         #[cfg(target_os = "windows")]
         {
-            use crate::providers::{amd, intel, nvidia};
             match gpu.vendor {
-                Vendor::Nvidia => nvidia::update_nvidia_info(gpu),
-                Vendor::Amd => amd::update_amd_info(gpu),
-                Vendor::Intel(_) => intel::update_intel_info(gpu),
+                #[cfg(feature = "vendor-nvidia")]
+                Vendor::Nvidia => crate::providers::nvidia::update_nvidia_info(gpu),
+                #[cfg(feature = "vendor-amd")]
+                Vendor::Amd => crate::providers::amd::update_amd_info(gpu),
+                #[cfg(feature = "vendor-intel")]
+                Vendor::Intel(_) => crate::providers::intel::update_intel_info(gpu),
                 _ => {
                     warn!("GPU update not implemented for vendor: {:?}", gpu.vendor);
                     Ok(())
@@ -313,15 +1003,15 @@ impl GpuManager {
         }
         #[cfg(target_os = "linux")]
         {
-            use crate::gpu_info::GpuProvider;
-            use crate::providers::linux::{AmdLinuxProvider, NvidiaLinuxProvider};
             match gpu.vendor {
+                #[cfg(feature = "vendor-nvidia")]
                 Vendor::Nvidia => {
-                    let nvidia_provider = NvidiaLinuxProvider::new();
+                    let nvidia_provider = crate::providers::linux::NvidiaLinuxProvider::new();
                     nvidia_provider.update_gpu(gpu)
                 }
+                #[cfg(feature = "vendor-amd")]
                 Vendor::Amd => {
-                    let amd_provider = AmdLinuxProvider::new();
+                    let amd_provider = crate::providers::linux::AmdLinuxProvider::new();
                     amd_provider.update_gpu(gpu)
                 }
                 _ => {
@@ -330,11 +1020,15 @@ impl GpuManager {
                 }
             }
         }
-        #[cfg(target_os = "macos")]
+        #[cfg(all(target_os = "macos", feature = "vendor-apple"))]
         {
             crate::macos::update_gpu_info(gpu)
         }
-        #[cfg(not(any(target_os = "windows", target_os = "linux", target_os = "macos")))]
+        #[cfg(not(any(
+            target_os = "windows",
+            target_os = "linux",
+            all(target_os = "macos", feature = "vendor-apple")
+        )))]
         {
             warn!("GPU update not implemented for vendor: {:?}", gpu.vendor);
             Ok(())
@@ -343,7 +1037,7 @@ impl GpuManager {
     /// Returns GPU with caching (zero-copy)
     ///
     /// Returns `Arc<GpuInfo>` for efficient sharing without cloning.
-    /// Use `get_gpu_cached_owned()` if you need to mutate the data.
+    /// Use `get_cached_gpu_at_owned()` if you need to mutate the data.
     ///
     /// This method automatically updates GPU metrics if cache is expired.
     ///
@@ -355,29 +1049,93 @@ impl GpuManager {
     /// # Memory
     ///
     /// Returns `Arc<GpuInfo>` (8 bytes pointer) - zero-copy for read-only access.
-    pub fn get_gpu_cached(&self, index: usize) -> Option<Arc<GpuInfo>> {
-        if let Some(cached_gpu) = self.cache.get(&index) {
-            debug!("Returning cached GPU #{}", index);
-            return Some(cached_gpu);
+    pub fn get_cached_gpu_at(&self, index: GpuIndex) -> Option<Arc<GpuInfo>> {
+        let index = index.get();
+        let key = Self::cache_key(&self.gpus, index)?;
+        if let Some(cached_gpu) = self.cache.get(&key) {
+            let stale = self.cache.stale_metrics(&key, &self.metric_ttls);
+            if stale.is_empty() {
+                debug!("Returning cached GPU #{}", index);
+                return Some(cached_gpu);
+            }
+            return Some(self.refresh_stale_metrics(index, &key, cached_gpu, &stale));
         }
 
-        if let Some(mut gpu) = self.get_gpu_by_index_owned(index) {
-            if let Err(e) = Self::update_single_gpu_static(&mut gpu) {
+        if let Some(mut gpu) = self.get_gpu_at_owned(GpuIndex(index)) {
+            if let Err(e) = Self::update_with(
+                self.providers.as_deref(),
+                self.updater,
+                &mut gpu,
+                self.track_metric_ages,
+            ) {
                 warn!("Failed to update GPU #{} metrics: {}", index, e);
+            } else if self.strict {
+                Self::enforce_consistency(self.providers.as_deref(), &mut gpu);
             }
-            self.cache.set(index, gpu.clone());
+            self.cache.set(key.clone(), gpu);
             debug!("Populated cache for GPU #{} with updated metrics", index);
-            self.cache.get(&index)
+            self.cache.get(&key)
         } else {
             None
         }
     }
 
+    /// Queries the GPU and replaces the `stale` metrics of its cache entry
+    ///
+    /// Stale numeric metrics are read through
+    /// [`GpuProvider::update_metrics()`](crate::gpu_info::GpuProvider::update_metrics),
+    /// so providers with per-metric reads skip the others. A stale
+    /// [`MetricKind::Name`] needs the full update. Other metrics the update
+    /// returns are discarded so they keep their own refresh times. Returns
+    /// `cached` unchanged if the update fails.
+    fn refresh_stale_metrics(
+        &self,
+        index: usize,
+        key: &CacheKey,
+        cached: Arc<GpuInfo>,
+        stale: &[MetricKind],
+    ) -> Arc<GpuInfo> {
+        let mut fresh = (*cached).clone();
+        let metrics: Option<Vec<Metric>> = stale
+            .iter()
+            .map(|kind| match kind {
+                MetricKind::Metric(metric) => Some(*metric),
+                MetricKind::Name => None,
+            })
+            .collect();
+        let updated = match metrics {
+            Some(metrics) => self.update_metrics_with(&mut fresh, &metrics),
+            None => Self::update_with(
+                self.providers.as_deref(),
+                self.updater,
+                &mut fresh,
+                self.track_metric_ages,
+            ),
+        };
+        if let Err(e) = updated {
+            warn!("Failed to refresh stale metrics of GPU #{}: {}", index, e);
+            return cached;
+        }
+        if self.strict {
+            Self::enforce_consistency(self.providers.as_deref(), &mut fresh);
+        }
+        debug!("Refreshed {:?} of cached GPU #{}", stale, index);
+        self.cache
+            .refresh_metrics(key, &fresh, stale)
+            .unwrap_or(cached)
+    }
+
+    /// Returns GPU with caching (zero-copy)
+    #[deprecated(note = "use `get_cached_gpu_at(GpuIndex(index))`")]
+    pub fn get_gpu_cached(&self, index: usize) -> Option<Arc<GpuInfo>> {
+        self.get_cached_gpu_at(GpuIndex(index))
+    }
+
     /// Returns GPU with caching (owned copy)
     ///
     /// Returns a cloned copy of cached GPU information.
     /// Use this when you need to mutate the GPU info.
-    /// For read-only access, prefer `get_gpu_cached()` which is more efficient.
+    /// For read-only access, prefer `get_cached_gpu_at()` which is more efficient.
     ///
     /// # Time Complexity
     ///
@@ -387,17 +1145,21 @@ impl GpuManager {
     /// # Memory
     ///
     /// Allocates a new `GpuInfo` (~128 bytes) on each call.
-    pub fn get_gpu_cached_owned(&self, index: usize) -> Option<GpuInfo> {
-        self.cache.get_owned(&index).or_else(|| {
-            if let Some(gpu) = self.get_gpu_by_index_owned(index) {
-                self.cache.set(index, gpu.clone());
-                Some(gpu)
-            } else {
-                None
-            }
+    pub fn get_cached_gpu_at_owned(&self, index: GpuIndex) -> Option<GpuInfo> {
+        let key = Self::cache_key(&self.gpus, index.get())?;
+        self.cache.get_owned(&key).or_else(|| {
+            let gpu = self.get_gpu_at_owned(index)?;
+            self.cache.set(key, gpu.clone());
+            Some(gpu)
         })
     }
 
+    /// Returns GPU with caching (owned copy)
+    #[deprecated(note = "use `get_cached_gpu_at_owned(GpuIndex(index))`")]
+    pub fn get_gpu_cached_owned(&self, index: usize) -> Option<GpuInfo> {
+        self.get_cached_gpu_at_owned(GpuIndex(index))
+    }
+
     /// Returns primary GPU with caching (zero-copy)
     ///
     /// Returns `Arc<GpuInfo>` for efficient sharing without cloning.
@@ -408,7 +1170,7 @@ impl GpuManager {
     /// - Cache hit: O(1), ~0.1-0.5ms
     /// - Cache miss: O(1) + FFI call time, ~1-200ms depending on vendor
     pub fn get_primary_gpu_cached(&self) -> Option<Arc<GpuInfo>> {
-        self.get_gpu_cached(self.primary_gpu_index)
+        self.get_cached_gpu_at(GpuIndex(self.primary_gpu_index))
     }
 
     /// Returns primary GPU with caching (owned copy)
@@ -417,7 +1179,7 @@ impl GpuManager {
     /// Use this when you need to mutate the GPU info.
     /// For read-only access, prefer `get_primary_gpu_cached()` which is more efficient.
     pub fn get_primary_gpu_cached_owned(&self) -> Option<GpuInfo> {
-        self.get_gpu_cached_owned(self.primary_gpu_index)
+        self.get_cached_gpu_at_owned(GpuIndex(self.primary_gpu_index))
     }
     /// Returns GPU statistics
     pub fn get_gpu_statistics(&self) -> GpuStatistics {
@@ -436,6 +1198,9 @@ impl GpuManager {
                 Vendor::Apple => {
                     stats.apple_count += 1;
                 }
+                Vendor::Virtual(_) => {
+                    stats.virtual_count += 1;
+                }
                 Vendor::Unknown => {
                     stats.unknown_count += 1;
                 }
@@ -465,6 +1230,67 @@ impl GpuManager {
             .map(|(index, _)| index)
             .collect()
     }
+    /// Returns the GPUs ordered by ascending utilization.
+    ///
+    /// GPUs with unknown utilization come last. The sort is stable, so GPUs
+    /// with equal utilization keep their detection order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gpu_info::{GpuInfo, GpuManager};
+    ///
+    /// let manager: GpuManager = vec![
+    ///     GpuInfo::builder().name("busy").utilization(90.0).build(),
+    ///     GpuInfo::builder().name("unknown").build(),
+    ///     GpuInfo::builder().name("idle").utilization(5.0).build(),
+    /// ]
+    /// .into_iter()
+    /// .collect();
+    /// let names: Vec<_> = manager
+    ///     .sorted_by_utilization()
+    ///     .iter()
+    ///     .map(|gpu| gpu.format_name_gpu())
+    ///     .collect();
+    /// assert_eq!(names, ["idle", "busy", "unknown"]);
+    /// ```
+    pub fn sorted_by_utilization(&self) -> Vec<&GpuInfo> {
+        let mut gpus: Vec<&GpuInfo> = self.gpus.iter().collect();
+        // NaN readings count as unknown
+        let utilization = |gpu: &GpuInfo| gpu.utilization.filter(|u| u.is_finite());
+        gpus.sort_by(|a, b| match (utilization(a), utilization(b)) {
+            (Some(a), Some(b)) => a.total_cmp(&b),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        });
+        gpus
+    }
+    /// Returns the least busy GPU, e.g. to place a new workload.
+    ///
+    /// A GPU with unknown utilization is only returned if no GPU reports
+    /// its utilization; the first such GPU is returned then.
+    pub fn least_utilized(&self) -> Option<&GpuInfo> {
+        self.sorted_by_utilization().first().copied()
+    }
+    /// Returns the indices of GPUs whose metrics differ from `prev`.
+    ///
+    /// `prev` is typically the result of an earlier
+    /// [`get_all_gpus_owned()`](Self::get_all_gpus_owned). GPUs are matched by
+    /// index and compared with [`GpuInfo::approx_eq`] using
+    /// [`CHANGE_EPSILON`], so float jitter below that threshold is ignored.
+    /// GPUs with no counterpart in `prev` are always reported as changed.
+    pub fn detect_changes_since(&self, prev: &[GpuInfo]) -> Vec<usize> {
+        self.gpus
+            .iter()
+            .enumerate()
+            .filter(|(index, gpu)| {
+                prev.get(*index)
+                    .map_or(true, |old| !gpu.approx_eq(old, CHANGE_EPSILON))
+            })
+            .map(|(index, _)| index)
+            .collect()
+    }
     /// Gets cache statistics
     pub fn get_cache_stats(&self) -> Option<crate::cache_utils::CacheStats> {
         self.cache.get_stats()
@@ -602,12 +1428,24 @@ impl FromIterator<GpuInfo> for GpuManager {
         let manager = Self {
             gpus,
             primary_gpu_index: 0,
-            cache: crate::cache_utils::MultiGpuInfoCache::new(Duration::from_millis(500)),
+            cache: crate::cache_utils::MultiGpuInfoCache::keyed(Duration::from_millis(500), 0),
+            report: DetectionReport::new(),
+            strict: cfg!(feature = "strict-validation"),
+            track_metric_ages: false,
+            metric_ttls: Vec::new(),
+            updater: Self::update_single_gpu_static,
+            metric_updater: Some(Self::update_gpu_metrics_static),
+            providers: None,
+            warming: Arc::new(Mutex::new(())),
+            #[cfg(feature = "persistent-cache")]
+            background: Arc::new(Mutex::new(None)),
         };
 
         // Pre-populate cache with the provided GPUs
         for (i, gpu) in manager.gpus.iter().enumerate() {
-            manager.cache.set(i, gpu.clone());
+            if let Some(key) = Self::cache_key(&manager.gpus, i) {
+                manager.cache.set(key, gpu.clone());
+            }
         }
 
         debug!("Created GpuManager from iterator with {} GPU(s)", gpu_count);
@@ -648,12 +1486,11 @@ impl FromIterator<GpuInfo> for GpuManager {
 /// ```
 impl Extend<GpuInfo> for GpuManager {
     fn extend<I: IntoIterator<Item = GpuInfo>>(&mut self, iter: I) {
-        let start_index = self.gpus.len();
-
-        for (i, gpu) in iter.into_iter().enumerate() {
-            let cache_index = start_index + i;
-            self.cache.set(cache_index, gpu.clone());
-            self.gpus.push(gpu);
+        for gpu in iter {
+            self.gpus.push(gpu.clone());
+            if let Some(key) = Self::cache_key(&self.gpus, self.gpus.len() - 1) {
+                self.cache.set(key, gpu);
+            }
         }
 
         debug!("Extended GpuManager: now has {} GPU(s)", self.gpus.len());
@@ -677,6 +1514,8 @@ pub struct GpuStatistics {
     pub intel_count: usize,
     /// Number of Apple GPUs detected.
     pub apple_count: usize,
+    /// Number of virtual display adapters detected.
+    pub virtual_count: usize,
     /// Number of GPUs with unknown vendor.
     pub unknown_count: usize,
     /// Sum of all GPU temperatures for averaging.
@@ -715,6 +1554,31 @@ pub fn global_gpu_manager() -> Arc<Mutex<GpuManager>> {
         .get_or_init(|| Arc::new(Mutex::new(GpuManager::new())))
         .clone()
 }
+/// Runs GPU detection and fills the cache of the global manager
+///
+/// Call this during startup, before serving traffic, so that the first
+/// [`get_primary_gpu_arc()`] or [`get_async()`](crate::get_async) does not
+/// pay for detection. Errors are logged, not returned. Safe to call from
+/// several threads at once: detection runs only once.
+///
+/// See [`prefetch()`](crate::prefetch) for the async version.
+///
+/// # Example
+/// ```
+/// // In main(), before serving requests
+/// gpu_info::prefetch_blocking();
+/// let primary = gpu_info::gpu_manager::get_primary_gpu_arc(); // served from the cache
+/// ```
+pub fn prefetch_blocking() {
+    warm_shared(&global_gpu_manager());
+}
+/// Warms a shared manager, see [`GpuManager::warm()`]
+pub(crate) fn warm_shared(manager: &Mutex<GpuManager>) {
+    match manager.lock() {
+        Ok(manager) => manager.warm(),
+        Err(_) => warn!("GPU manager lock is poisoned, skipping cache warm-up"),
+    }
+}
 /// Convenience function for getting the primary GPU (owned copy)
 ///
 /// Returns owned `GpuInfo` for backward compatibility.
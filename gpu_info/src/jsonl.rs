@@ -0,0 +1,311 @@
+//! Line-delimited JSON (JSONL) persistence for monitoring samples.
+//!
+//! [`SampleWriter`] appends one JSON object per line to any [`Write`], so a
+//! long-running monitor can persist samples in constant time per sample
+//! instead of re-serializing its whole history. [`SampleReader`] reads them
+//! back and tolerates a truncated final line, which is what a file looks like
+//! after the writing process was killed mid-write.
+//!
+//! Each line is a [`SampleRecord`]:
+//!
+//! ```text
+//! {"timestamp_ms":1700000000000,"sample":{"kind":"gpu","data":{...}}}
+//! {"timestamp_ms":1700000001000,"sample":{"kind":"snapshot","data":{"gpus":[...]}}}
+//! ```
+//!
+//! # Examples
+//!
+//! ```
+//! use gpu_info::jsonl::{Sample, SampleReader, SampleWriter};
+//! use gpu_info::GpuInfo;
+//!
+//! let mut writer = SampleWriter::new(Vec::new()).with_clock(|| 1_000);
+//! writer.write_sample(&GpuInfo::mock_nvidia()).unwrap();
+//! let bytes = writer.into_inner().unwrap();
+//!
+//! let records: Vec<_> = SampleReader::new(bytes.as_slice())
+//!     .collect::<Result<_, _>>()
+//!     .unwrap();
+//! assert_eq!(records.len(), 1);
+//! assert_eq!(records[0].timestamp_ms, 1_000);
+//! assert!(matches!(records[0].sample, Sample::Gpu(_)));
+//! ```
+use crate::gpu_info::{GpuError, GpuInfo, Result};
+use crate::monitoring::GpuMetricsSnapshot;
+use crate::redaction::{Redact, Redaction};
+use std::fmt::{Debug, Formatter};
+use std::io::{self, BufRead, Write};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Payload of a single JSONL line.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "kind", content = "data", rename_all = "snake_case")]
+#[allow(clippy::large_enum_variant)] // single-GPU samples are the common case, keep them unboxed
+pub enum Sample {
+    /// Metrics of a single GPU.
+    Gpu(GpuInfo),
+    /// Metrics of all GPUs from one monitoring iteration.
+    Snapshot(GpuMetricsSnapshot),
+}
+
+/// A timestamped sample as stored on one JSONL line.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SampleRecord {
+    /// Time the sample was written, in milliseconds since the Unix epoch.
+    pub timestamp_ms: u64,
+    /// The sample itself.
+    pub sample: Sample,
+}
+
+/// Callback invoked when the rotation threshold is crossed.
+///
+/// Receives the current (already flushed) writer and returns the writer to
+/// continue with, e.g. after renaming the old file and opening a new one.
+pub type RotateFn<W> = Box<dyn FnMut(W) -> io::Result<W> + Send>;
+
+/// Source of sample timestamps in milliseconds since the Unix epoch.
+pub type ClockFn = Box<dyn Fn() -> u64 + Send>;
+
+/// Returns the current system time in milliseconds since the Unix epoch.
+pub fn system_clock_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Appends timestamped samples to a writer as line-delimited JSON.
+///
+/// By default every line is flushed immediately, timestamps come from the
+/// system clock, no rotation happens and identifying fields are written
+/// unchanged. See [`with_flush_every`], [`with_clock`], [`with_rotation`]
+/// and [`with_redaction`].
+///
+/// [`with_flush_every`]: SampleWriter::with_flush_every
+/// [`with_clock`]: SampleWriter::with_clock
+/// [`with_rotation`]: SampleWriter::with_rotation
+/// [`with_redaction`]: SampleWriter::with_redaction
+pub struct SampleWriter<W: Write> {
+    /// Current output; `None` only if a rotation callback failed
+    writer: Option<W>,
+    /// Timestamp source
+    clock: ClockFn,
+    /// Flush after this many lines
+    flush_every: usize,
+    /// Lines written since the last flush
+    unflushed: usize,
+    /// Bytes written since creation or the last rotation
+    bytes_written: u64,
+    /// Size threshold and callback for rotation
+    rotation: Option<(u64, RotateFn<W>)>,
+    /// Policy for identifying fields
+    redaction: Redaction,
+}
+
+impl<W: Write> Debug for SampleWriter<W> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SampleWriter")
+            .field("flush_every", &self.flush_every)
+            .field("unflushed", &self.unflushed)
+            .field("bytes_written", &self.bytes_written)
+            .field("rotate_at", &self.rotation.as_ref().map(|(max, _)| *max))
+            .field("redaction", &self.redaction)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<W: Write> SampleWriter<W> {
+    /// Creates a writer that flushes after every line.
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer: Some(writer),
+            clock: Box::new(system_clock_ms),
+            flush_every: 1,
+            unflushed: 0,
+            bytes_written: 0,
+            rotation: None,
+            redaction: Redaction::None,
+        }
+    }
+
+    /// Flushes after every `lines` lines instead of after each one.
+    ///
+    /// A value of `0` is treated as `1`.
+    pub fn with_flush_every(mut self, lines: usize) -> Self {
+        self.flush_every = lines.max(1);
+        self
+    }
+
+    /// Uses `clock` instead of the system clock for sample timestamps.
+    pub fn with_clock<F>(mut self, clock: F) -> Self
+    where
+        F: Fn() -> u64 + Send + 'static,
+    {
+        self.clock = Box::new(clock);
+        self
+    }
+
+    /// Calls `rotate` once at least `max_bytes` have been written since the
+    /// last rotation.
+    ///
+    /// The check runs after each line, so a file may exceed `max_bytes` by at
+    /// most one line.
+    pub fn with_rotation<F>(mut self, max_bytes: u64, rotate: F) -> Self
+    where
+        F: FnMut(W) -> io::Result<W> + Send + 'static,
+    {
+        self.rotation = Some((max_bytes, Box::new(rotate)));
+        self
+    }
+
+    /// Applies `redaction` to the identifying fields of every written sample.
+    pub fn with_redaction(mut self, redaction: Redaction) -> Self {
+        self.redaction = redaction;
+        self
+    }
+
+    /// Writes one GPU sample.
+    pub fn write_sample(&mut self, gpu: &GpuInfo) -> Result<()> {
+        self.write_record(&SampleRecord {
+            timestamp_ms: (self.clock)(),
+            sample: Sample::Gpu(gpu.clone()),
+        })
+    }
+
+    /// Writes one multi-GPU snapshot.
+    pub fn write_snapshot(&mut self, snapshot: &GpuMetricsSnapshot) -> Result<()> {
+        self.write_record(&SampleRecord {
+            timestamp_ms: (self.clock)(),
+            sample: Sample::Snapshot(snapshot.clone()),
+        })
+    }
+
+    /// Writes a pre-built record, keeping its timestamp.
+    pub fn write_record(&mut self, record: &SampleRecord) -> Result<()> {
+        let mut line = if self.redaction == Redaction::None {
+            serde_json::to_vec(record)
+        } else {
+            let mut record = record.clone();
+            match &mut record.sample {
+                Sample::Gpu(gpu) => gpu.redact(&self.redaction),
+                Sample::Snapshot(snapshot) => snapshot.redact(&self.redaction),
+            }
+            serde_json::to_vec(&record)
+        }
+        .map_err(io::Error::from)?;
+        line.push(b'\n');
+        self.inner_mut()?.write_all(&line)?;
+        self.bytes_written += line.len() as u64;
+        self.unflushed += 1;
+        if self.unflushed >= self.flush_every {
+            self.flush()?;
+        }
+        self.rotate_if_needed()
+    }
+
+    /// Flushes buffered lines to the underlying writer.
+    pub fn flush(&mut self) -> Result<()> {
+        self.inner_mut()?.flush()?;
+        self.unflushed = 0;
+        Ok(())
+    }
+
+    /// Returns the number of bytes written since creation or the last rotation.
+    pub fn bytes_written(&self) -> u64 {
+        self.bytes_written
+    }
+
+    /// Flushes and returns the underlying writer.
+    pub fn into_inner(mut self) -> Result<W> {
+        self.flush()?;
+        self.writer.take().ok_or_else(rotation_failed)
+    }
+
+    fn inner_mut(&mut self) -> Result<&mut W> {
+        self.writer.as_mut().ok_or_else(rotation_failed)
+    }
+
+    fn rotate_if_needed(&mut self) -> Result<()> {
+        let Some((max_bytes, rotate)) = self.rotation.as_mut() else {
+            return Ok(());
+        };
+        if self.bytes_written < *max_bytes {
+            return Ok(());
+        }
+        if self.unflushed > 0 {
+            if let Some(writer) = self.writer.as_mut() {
+                writer.flush()?;
+            }
+            self.unflushed = 0;
+        }
+        let current = self.writer.take().ok_or_else(rotation_failed)?;
+        self.writer = Some(rotate(current)?);
+        self.bytes_written = 0;
+        Ok(())
+    }
+}
+
+fn rotation_failed() -> GpuError {
+    GpuError::Io(io::Error::new(
+        io::ErrorKind::Other,
+        "sample writer lost its output after a failed rotation",
+    ))
+}
+
+/// Iterator over the records of a JSONL stream written by [`SampleWriter`].
+///
+/// Blank lines are skipped. A final line without a trailing newline that
+/// fails to parse is treated as truncated and silently ends the iteration;
+/// a malformed line anywhere else yields an error.
+#[derive(Debug)]
+pub struct SampleReader<R: BufRead> {
+    reader: R,
+    line: String,
+    done: bool,
+}
+
+impl<R: BufRead> SampleReader<R> {
+    /// Creates a reader over `reader`.
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            line: String::new(),
+            done: false,
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for SampleReader<R> {
+    type Item = Result<SampleRecord>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            self.line.clear();
+            match self.reader.read_line(&mut self.line) {
+                Ok(0) => self.done = true,
+                Ok(_) => {
+                    let complete = self.line.ends_with('\n');
+                    let text = self.line.trim();
+                    if text.is_empty() {
+                        continue;
+                    }
+                    match serde_json::from_str(text) {
+                        Ok(record) => return Some(Ok(record)),
+                        Err(_) if !complete => self.done = true,
+                        Err(e) => {
+                            return Some(Err(GpuError::Io(io::Error::new(
+                                io::ErrorKind::InvalidData,
+                                e,
+                            ))))
+                        }
+                    }
+                }
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(GpuError::Io(e)));
+                }
+            }
+        }
+        None
+    }
+}
@@ -23,8 +23,11 @@
 
 #![deny(missing_docs)]
 
-pub use crate::gpu_info::{GpuError, GpuInfo, GpuInfoBuilder, Result};
-pub use crate::metric_value::MetricValue;
+pub use crate::gpu_info::{
+    sort_stable, ClockThrottleReason, Confidence, EccStats, FieldAvailability, GpuError, GpuInfo,
+    GpuInfoBuilder, Result, ThrottleReasons, UsageSummary,
+};
+pub use crate::metric_value::{MetricValue, TypedMetricValue};
 
 /// Macros for GPU information formatting and display.
 ///
@@ -43,6 +46,9 @@ pub mod macros;
 ///
 /// This module contains unsafe FFI code. All unsafe operations are isolated
 /// here and wrapped in safe abstractions by the provider layer.
+///
+/// Requires the `vendor-amd` feature.
+#[cfg(feature = "vendor-amd")]
 pub mod adl_api;
 
 /// Asynchronous GPU information API.
@@ -64,6 +70,15 @@ pub mod adl_api;
 /// ```
 pub mod async_api;
 
+/// Cooperative cancellation of GPU detection.
+///
+/// This module provides [`CancelToken`], which stops a running
+/// [`GpuManager::detect_with_cancel()`] early.
+///
+/// [`CancelToken`]: crate::CancelToken
+/// [`GpuManager::detect_with_cancel()`]: crate::GpuManager::detect_with_cancel
+pub mod cancel;
+
 /// GPU information caching utilities.
 ///
 /// This module provides caching infrastructure for GPU metrics with
@@ -71,6 +86,23 @@ pub mod async_api;
 /// Caching significantly improves performance for frequent GPU queries.
 pub mod cache_utils;
 
+/// Cross-field consistency checks for GPU information.
+///
+/// This module provides the checker behind [`GpuInfo::check_consistency()`]
+/// and the strict mode of [`GpuManager`].
+///
+/// [`GpuInfo::check_consistency()`]: crate::GpuInfo::check_consistency
+/// [`GpuManager`]: crate::GpuManager
+pub mod consistency;
+
+/// Detection diagnostics.
+///
+/// This module provides [`DetectionReport`], which records per-stage
+/// detection latency and actionable hints gathered while detecting GPUs.
+///
+/// [`DetectionReport`]: crate::DetectionReport
+pub mod detection_report;
+
 /// Extended GPU information and capabilities.
 ///
 /// This module provides additional GPU information beyond basic metrics,
@@ -88,6 +120,25 @@ pub mod extended_info;
 /// are documented with safety invariants.
 pub mod ffi_utils;
 
+/// Metric-independent GPU identity.
+///
+/// This module provides [`GpuIdentity`], the key for per-GPU state that
+/// must survive metric changes.
+///
+/// [`GpuIdentity`]: crate::GpuIdentity
+pub mod gpu_identity;
+
+/// Typed GPU indices.
+///
+/// This module provides [`GpuIndex`] for positions in the [`GpuManager`]
+/// list and [`CudaIndex`] for CUDA device ordinals, so the two cannot be
+/// mixed up.
+///
+/// [`GpuIndex`]: crate::GpuIndex
+/// [`CudaIndex`]: crate::CudaIndex
+/// [`GpuManager`]: crate::GpuManager
+pub mod gpu_index;
+
 /// Core GPU information types and traits.
 ///
 /// This module contains the primary [`GpuInfo`] struct, [`GpuError`] enum,
@@ -107,6 +158,26 @@ pub mod gpu_info;
 /// [`GpuManager`]: crate::GpuManager
 pub mod gpu_manager;
 
+/// Line-delimited JSON persistence for monitoring samples.
+///
+/// This module provides [`SampleWriter`], which appends one JSON object per
+/// sample to any writer, and [`SampleReader`] for reading them back.
+///
+/// [`SampleWriter`]: crate::jsonl::SampleWriter
+/// [`SampleReader`]: crate::jsonl::SampleReader
+#[cfg(feature = "serde_json")]
+pub mod jsonl;
+
+/// Per-field metric source priority.
+///
+/// This module provides [`MetricSource`] and [`resolve()`], which providers
+/// combining several backends use to pick each metric from the
+/// highest-priority source that produced it.
+///
+/// [`MetricSource`]: crate::MetricSource
+/// [`resolve()`]: crate::metric_source::resolve
+pub mod metric_source;
+
 /// Metric value types for GPU measurements.
 ///
 /// This module provides the [`MetricValue`] enum for representing
@@ -135,8 +206,30 @@ pub mod monitoring;
 ///
 /// This module contains unsafe FFI code. All unsafe operations are isolated
 /// here and wrapped in safe abstractions by the provider layer.
+///
+/// Requires the `vendor-nvidia` feature.
+#[cfg(feature = "vendor-nvidia")]
 pub mod nvml_api;
 
+/// Persistent on-disk cache of GPU identity.
+///
+/// This module provides [`PersistentCache`], used by
+/// [`GpuManager::with_persistent_cache()`] to return GPU identity data
+/// immediately on startup.
+///
+/// [`PersistentCache`]: crate::persistent_cache::PersistentCache
+/// [`GpuManager::with_persistent_cache()`]: crate::GpuManager::with_persistent_cache
+#[cfg(feature = "persistent-cache")]
+pub mod persistent_cache;
+
+/// GPU usage attribution for the calling process.
+///
+/// This module provides [`self_usage()`], which reports the GPU memory and
+/// utilization share of the current process rather than of the whole system.
+///
+/// [`self_usage()`]: crate::self_usage
+pub mod process_usage;
+
 /// GPU provider management.
 ///
 /// This module provides [`GpuProviderManager`] for managing vendor-specific
@@ -160,6 +253,33 @@ pub mod providers;
 /// [`GpuQuery`]: crate::GpuQuery
 pub mod query;
 
+/// Redaction of machine-identifying fields in exported data.
+///
+/// This module provides the [`Redaction`] policy, the [`Redacted`] serde
+/// wrapper and the list of identifying fields it applies to.
+///
+/// [`Redaction`]: crate::redaction::Redaction
+/// [`Redacted`]: crate::redaction::Redacted
+pub mod redaction;
+
+/// Shared GPU sampling for multiple monitors.
+///
+/// This module provides [`Sampler`], which owns the polling thread and
+/// delivers every sample to several [`GpuMonitor`]s, each with its own
+/// thresholds, history and alert handlers.
+///
+/// [`Sampler`]: crate::Sampler
+/// [`GpuMonitor`]: crate::GpuMonitor
+pub mod sampler;
+
+/// Test helpers for code that works with [`GpuInfo`].
+///
+/// Requires the `testing` feature. Provides
+/// [`assert_gpus_eq_unordered()`](crate::testing::assert_gpus_eq_unordered)
+/// for comparing GPU lists regardless of detection order.
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+
 /// Fallback implementation for unknown platforms.
 ///
 /// This module provides a fallback implementation that returns
@@ -175,13 +295,28 @@ pub mod unknown;
 /// [`IntelGpuType`]: crate::vendor::IntelGpuType
 pub mod vendor;
 pub use async_api::{
-    get_all_async, get_all_async_owned, get_async, get_async_owned, update_gpu_async,
+    detect_async_with_cancel, get_all_async, get_all_async_owned, get_async, get_async_owned,
+    prefetch, stream_all_gpus_metrics, stream_gpu_metrics, subscribe, subscribe_all,
+    update_gpu_async, GpuStream, SubscribeOptions,
 };
+pub use cancel::CancelToken;
+pub use detection_report::DetectionReport;
 pub use extended_info::{ExtendedGpuInfo, GpuInfoExtensions};
-pub use gpu_manager::{GpuManager, GpuStatistics};
-pub use monitoring::{AlertType, GpuMonitor, GpuThresholds, MonitorConfig};
+pub use gpu_identity::GpuIdentity;
+pub use gpu_index::{CudaIndex, GpuIndex};
+pub use gpu_manager::{prefetch_blocking, GpuManager, GpuStatistics};
+pub use metric_source::{Metric, MetricSource, MetricSources};
+pub use monitoring::{
+    AdaptiveInterval, AlertHysteresis, AlertType, EccTracker, GpuMetricsSnapshot, GpuMonitor,
+    GpuThresholds, MemoryErrorTracker, MonitorConfig, PcieErrorTracker, ThreadPriority,
+    ThrottleTracker,
+};
+pub use process_usage::{
+    self_usage, GpuProcessInfo, GpuProcessShare, ProcessGpuUsage, ProcessType,
+};
 pub use provider_manager::GpuProviderManager;
-pub use query::GpuQuery;
+pub use query::{GpuQuery, SortOrder};
+pub use sampler::{SampleSubscriber, Sampler};
 pub use vendor::Vendor;
 
 /// Windows platform implementation.
@@ -198,10 +333,10 @@ pub use imp as windows;
 ///
 /// This module provides GPU detection and metrics collection for macOS
 /// using IOKit, Metal, and system profiler APIs.
-#[cfg(target_os = "macos")]
+#[cfg(all(target_os = "macos", feature = "vendor-apple"))]
 #[path = "macos/mod.rs"]
 pub mod imp;
-#[cfg(target_os = "macos")]
+#[cfg(all(target_os = "macos", feature = "vendor-apple"))]
 pub use imp as macos;
 
 /// Linux platform implementation.
@@ -218,13 +353,16 @@ pub use imp as linux;
 /// Fallback platform implementation for unsupported operating systems.
 ///
 /// This module provides a no-op implementation that returns unknown GPU info
-/// for platforms that are not explicitly supported.
-#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+/// for platforms that are not explicitly supported, and for macOS when the
+/// `vendor-apple` feature is disabled.
+#[cfg(any(
+    not(any(target_os = "linux", target_os = "macos", target_os = "windows")),
+    all(target_os = "macos", not(feature = "vendor-apple"))
+))]
 #[path = "unknown/mod.rs"]
 pub mod imp;
 #[cfg(test)]
 mod test;
-#[cfg(any(target_os = "linux", target_os = "macos", target_os = "windows"))]
 /// Gets information about the primary GPU in the system.
 ///
 /// Returns a `GpuInfo` struct with GPU metrics including vendor, model name,
@@ -245,7 +383,13 @@ mod test;
 /// # Platform Support
 ///
 /// Supported on Windows, Linux, and macOS. On unsupported platforms,
-/// this function is not available.
+/// returns an unknown GPU whose [`GpuInfo::detection_error()`] is
+/// [`GpuError::Unsupported`].
+///
+/// # Diagnostics
+///
+/// When no GPU could be detected, [`GpuInfo::detection_error()`] tells why,
+/// e.g. [`GpuError::DriverNotInstalled`].
 ///
 /// # Performance
 ///
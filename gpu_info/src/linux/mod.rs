@@ -11,9 +11,20 @@
 //!
 //! This legacy API is maintained for backward compatibility but internally delegates
 //! to the provider system. Consider using `GpuManager` or providers directly.
+#[cfg(any(
+    feature = "vendor-nvidia",
+    feature = "vendor-amd",
+    feature = "vendor-intel"
+))]
+use crate::gpu_info::GpuProvider;
+#[cfg(feature = "vendor-amd")]
+use crate::providers::linux::AmdLinuxProvider;
+#[cfg(feature = "vendor-intel")]
+use crate::providers::linux::IntelLinuxProvider;
+#[cfg(feature = "vendor-nvidia")]
+use crate::providers::linux::NvidiaLinuxProvider;
 use crate::{
-    gpu_info::{GpuInfo, GpuProvider},
-    providers::linux::{AmdLinuxProvider, IntelLinuxProvider, NvidiaLinuxProvider},
+    gpu_info::{GpuError, GpuInfo, Result},
     vendor::Vendor,
 };
 use log::{debug, warn};
@@ -24,11 +35,12 @@ pub(crate) fn detect_vendor() -> Vendor {
 
     if let Ok(vendor_id) = fs::read_to_string(vendor_path) {
         let vendor_id = vendor_id.trim();
-        match vendor_id {
-            "0x10de" => return Vendor::Nvidia,
-            "0x1002" => return Vendor::Amd,
-            "0x8086" => return Vendor::Intel(crate::vendor::IntelGpuType::Unknown),
-            _ => debug!("Unknown vendor ID: {}", vendor_id),
+        let vendor = u16::from_str_radix(vendor_id.trim_start_matches("0x"), 16)
+            .map_or(Vendor::Unknown, Vendor::from_pci_id);
+        if vendor == Vendor::Unknown {
+            debug!("Unknown vendor ID: {}", vendor_id);
+        } else {
+            return vendor;
         }
     }
 
@@ -46,22 +58,35 @@ pub fn info_gpu() -> GpuInfo {
     let vendor = detect_vendor();
     debug!("Detected GPU vendor: {:?}", vendor);
 
-    let gpus = match vendor {
+    let gpus: Result<Vec<GpuInfo>> = match vendor {
+        #[cfg(feature = "vendor-nvidia")]
         Vendor::Nvidia => {
             let provider = NvidiaLinuxProvider::new();
             provider.detect_gpus()
         }
+        #[cfg(not(feature = "vendor-nvidia"))]
+        Vendor::Nvidia => Err(pruned("vendor-nvidia")),
+        #[cfg(feature = "vendor-amd")]
         Vendor::Amd => {
             let provider = AmdLinuxProvider::new();
             provider.detect_gpus()
         }
+        #[cfg(not(feature = "vendor-amd"))]
+        Vendor::Amd => Err(pruned("vendor-amd")),
+        #[cfg(feature = "vendor-intel")]
         Vendor::Intel(_) => {
             let provider = IntelLinuxProvider::new();
             provider.detect_gpus()
         }
+        #[cfg(not(feature = "vendor-intel"))]
+        Vendor::Intel(_) => Err(pruned("vendor-intel")),
+        Vendor::Virtual(_) => {
+            debug!("Virtual display adapter detected, no metrics available");
+            return GpuInfo::write_vendor(vendor);
+        }
         _ => {
             warn!("No supported GPU vendor detected, returning default GpuInfo");
-            return GpuInfo::default();
+            return GpuInfo::unknown_with_reason(GpuError::GpuNotFound);
         }
     };
 
@@ -72,11 +97,25 @@ pub fn info_gpu() -> GpuInfo {
         }
         Ok(_) => {
             warn!("Provider detected 0 GPUs");
-            GpuInfo::default()
+            GpuInfo::unknown_with_reason(GpuError::GpuNotFound)
         }
         Err(e) => {
             warn!("Failed to detect GPUs: {:?}", e);
-            GpuInfo::default()
+            GpuInfo::unknown_with_reason(e)
         }
     }
 }
+
+/// Error for a GPU whose vendor backend was pruned at compile time
+#[cfg(not(all(
+    feature = "vendor-nvidia",
+    feature = "vendor-amd",
+    feature = "vendor-intel"
+)))]
+fn pruned(feature: &str) -> GpuError {
+    warn!(
+        "GPU detected, but its backend is disabled by the `{}` feature",
+        feature
+    );
+    GpuError::FeatureNotEnabled(feature.to_string())
+}
@@ -1,6 +1,9 @@
 //gpu_info/src/macos/mod.rs
 use crate::{
-    gpu_info::{GpuInfo, Result},
+    cancel::CancelToken,
+    gpu_info::{Confidence, GpuError, GpuInfo, Result},
+    process_usage::{parse_powermetrics_tasks, GpuProcessInfo},
+    providers::macos::sandbox::{self, SystemEnvironment},
     vendor::{IntelGpuType, Vendor},
 };
 use log::{debug, info, warn};
@@ -10,16 +13,57 @@ struct MacOSGpuProvider;
 impl MacOSGpuProvider {
     /// Gets list of all GPUs in macOS system
     pub fn detect_all_gpus() -> Vec<GpuInfo> {
+        Self::detect_all_gpus_with_cancel(&CancelToken::new())
+    }
+    /// [`detect_all_gpus()`](Self::detect_all_gpus), checking `token` before each backend
+    ///
+    /// GPUs found before the cancellation are returned.
+    pub fn detect_all_gpus_with_cancel(token: &CancelToken) -> Vec<GpuInfo> {
+        if sandbox::is_sandboxed(&SystemEnvironment) {
+            return Self::detect_sandboxed(token);
+        }
         let mut gpus = Vec::new();
+        if token.is_cancelled() {
+            return gpus;
+        }
         gpus.extend(Self::get_basic_gpu_info());
+        if token.is_cancelled() {
+            return gpus;
+        }
         Self::enhance_with_iokit(&mut gpus);
-        if gpus.is_empty() {
+        if gpus.is_empty() && !token.is_cancelled() {
             if let Some(apple_gpu) = Self::detect_apple_silicon_gpu() {
                 gpus.push(apple_gpu);
             }
         }
         gpus
     }
+    /// Detection inside the App Sandbox, where spawning processes is denied
+    ///
+    /// Only the in-process Metal and IOKit backends are used.
+    #[allow(unused_variables)]
+    fn detect_sandboxed(token: &CancelToken) -> Vec<GpuInfo> {
+        debug!("App Sandbox detected, skipping subprocess-based detection");
+        #[cfg(feature = "macos-metal")]
+        if !token.is_cancelled() {
+            use crate::providers::macos::backends::MetalBackend;
+            match MetalBackend::new().and_then(|backend| backend.detect_gpus()) {
+                Ok(gpus) if !gpus.is_empty() => return gpus,
+                Ok(_) => debug!("Metal found no GPUs"),
+                Err(e) => debug!("Metal detection failed: {}", e),
+            }
+        }
+        #[cfg(feature = "macos-iokit")]
+        if !token.is_cancelled() {
+            use crate::providers::macos::backends::IOKitBackend;
+            match IOKitBackend::new().and_then(|backend| backend.detect_gpus()) {
+                Ok(gpus) if !gpus.is_empty() => return gpus,
+                Ok(_) => debug!("IOKit found no GPUs"),
+                Err(e) => debug!("IOKit detection failed: {}", e),
+            }
+        }
+        Vec::new()
+    }
     /// Basic information via system_profiler
     fn get_basic_gpu_info() -> Vec<GpuInfo> {
         let mut gpus = Vec::new();
@@ -237,6 +281,7 @@ impl MacOSGpuProvider {
                 core_clock: Self::estimate_apple_gpu_clock(&cpu_info),
                 utilization: Self::get_apple_gpu_utilization(),
                 temperature: Self::get_apple_gpu_temperature(),
+                confidence: Confidence::Medium,
                 ..Default::default()
             });
         }
@@ -299,6 +344,25 @@ impl MacOSGpuProvider {
         }
         None
     }
+    /// Get the processes with GPU time, via powermetrics (requires root)
+    fn get_apple_gpu_processes() -> Option<Vec<GpuProcessInfo>> {
+        let output = Command::new("powermetrics")
+            .args([
+                "-n",
+                "1",
+                "-i",
+                "100",
+                "--samplers",
+                "tasks",
+                "--show-process-gpu",
+            ])
+            .output()
+            .ok()
+            .filter(|out| out.status.success())?;
+        Some(parse_powermetrics_tasks(&String::from_utf8_lossy(
+            &output.stdout,
+        )))
+    }
     /// Get Apple GPU temperature
     fn get_apple_gpu_temperature() -> Option<f32> {
         let output = Command::new("sysctl").args(["-a"]).output();
@@ -383,6 +447,16 @@ impl MacOSGpuProvider {
     pub fn update_gpu_info(gpu: &mut GpuInfo) -> Result<()> {
         debug!("Updating macOS GPU information for {:?}", gpu.name_gpu);
         gpu.active = Some(true);
+        if sandbox::is_sandboxed(&SystemEnvironment) {
+            #[cfg(feature = "macos-metal")]
+            {
+                use crate::providers::macos::backends::MetalBackend;
+                if let Err(e) = MetalBackend::new().and_then(|backend| backend.update_gpu(gpu)) {
+                    debug!("Metal update failed: {}", e);
+                }
+            }
+            return Ok(());
+        }
         if matches!(gpu.vendor, Vendor::Apple) {
             if gpu.utilization.is_none() {
                 gpu.utilization = Self::get_apple_gpu_utilization();
@@ -390,6 +464,7 @@ impl MacOSGpuProvider {
             if gpu.temperature.is_none() {
                 gpu.temperature = Self::get_apple_gpu_temperature();
             }
+            gpu.processes = Self::get_apple_gpu_processes();
         } else if gpu.utilization.is_none() {
             gpu.utilization = Self::get_gpu_utilization_estimate();
         }
@@ -402,14 +477,22 @@ pub fn info_gpu() -> GpuInfo {
     if let Some(primary_gpu) = gpus.first() {
         primary_gpu.clone()
     } else {
-        warn!("No GPU detected on macOS, returning unknown GPU");
-        GpuInfo::unknown()
+        if sandbox::is_sandboxed(&SystemEnvironment) {
+            debug!("No GPU detected inside the App Sandbox, returning unknown GPU");
+        } else {
+            warn!("No GPU detected on macOS, returning unknown GPU");
+        }
+        GpuInfo::unknown_with_reason(GpuError::GpuNotFound)
     }
 }
 /// Gets all available GPUs on macOS
 pub fn get_all_gpus() -> Vec<GpuInfo> {
     MacOSGpuProvider::detect_all_gpus()
 }
+/// [`get_all_gpus()`], stopping between backends if `token` is cancelled
+pub(crate) fn get_all_gpus_with_cancel(token: &CancelToken) -> Vec<GpuInfo> {
+    MacOSGpuProvider::detect_all_gpus_with_cancel(token)
+}
 /// Update detailed GPU information on macOS
 pub fn update_gpu_info(gpu: &mut GpuInfo) -> Result<()> {
     MacOSGpuProvider::update_gpu_info(gpu)
@@ -0,0 +1,418 @@
+//! Per-field metric source priority.
+//!
+//! Providers that combine several backends (for example WMI, the Intel
+//! Metrics Discovery API and PDH on Windows) can get the same metric from
+//! more than one source. Each such provider declares a [`PriorityTable`]
+//! listing, for every metric, the sources it trusts in order. [`resolve()`]
+//! then picks the value of the highest-priority source that produced one.
+//!
+//! With the `debug-capture` feature, the winning source of every resolved
+//! metric is recorded in [`GpuInfo::metric_sources`].
+//!
+//! # Examples
+//!
+//! ```
+//! use gpu_info::metric_source::{resolve, Metric, MetricSource};
+//!
+//! let priority = [MetricSource::Pdh, MetricSource::IntelMdApi];
+//! let winner = resolve(
+//!     Metric::Utilization,
+//!     &priority,
+//!     [(MetricSource::IntelMdApi, Some(40.0)), (MetricSource::Pdh, Some(35.0))],
+//! );
+//! assert_eq!(winner, Some((35.0, MetricSource::Pdh)));
+//! ```
+use crate::gpu_info::{GpuError, GpuInfo};
+use crate::vendor::Vendor;
+use log::debug;
+use std::collections::BTreeMap;
+use std::fmt::{self, Debug, Display, Formatter};
+use std::sync::Arc;
+use std::time::Instant;
+
+/// A backend a metric value was read from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum MetricSource {
+    /// Windows Management Instrumentation
+    Wmi,
+    /// Intel Metrics Discovery API
+    IntelMdApi,
+    /// Windows Performance Data Helper counters
+    Pdh,
+    /// macOS IOKit registry
+    IoKit,
+    /// macOS Metal framework
+    Metal,
+    /// macOS `powermetrics` tool
+    PowerMetrics,
+    /// macOS `system_profiler` tool
+    SystemProfiler,
+    /// NVIDIA Management Library
+    Nvml,
+    /// AMD Display Library
+    Adl,
+    /// Linux sysfs, including hwmon sensors
+    Sysfs,
+}
+
+impl MetricSource {
+    /// Returns a short human-readable name of the source
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Wmi => "WMI",
+            Self::IntelMdApi => "Intel MD API",
+            Self::Pdh => "PDH",
+            Self::IoKit => "IOKit",
+            Self::Metal => "Metal",
+            Self::PowerMetrics => "powermetrics",
+            Self::SystemProfiler => "system_profiler",
+            Self::Nvml => "NVML",
+            Self::Adl => "ADL",
+            Self::Sysfs => "sysfs",
+        }
+    }
+}
+
+impl Display for MetricSource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A numeric [`GpuInfo`] metric.
+///
+/// Identifies a field for source priority, and the kind of a
+/// [`TypedMetricValue`](crate::metric_value::TypedMetricValue).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[non_exhaustive]
+pub enum Metric {
+    /// [`GpuInfo::temperature`]
+    Temperature,
+    /// [`GpuInfo::utilization`]
+    Utilization,
+    /// [`GpuInfo::power_usage`]
+    PowerUsage,
+    /// [`GpuInfo::core_clock`]
+    CoreClock,
+    /// [`GpuInfo::memory_util`]
+    MemoryUtil,
+    /// [`GpuInfo::memory_clock`]
+    MemoryClock,
+    /// [`GpuInfo::memory_total`]
+    MemoryTotal,
+    /// [`GpuInfo::memory_used`]
+    MemoryUsed,
+    /// [`GpuInfo::max_clock_speed`]
+    MaxClockSpeed,
+}
+
+impl Metric {
+    /// Every metric, in declaration order
+    pub const ALL: [Metric; 9] = [
+        Self::Temperature,
+        Self::Utilization,
+        Self::PowerUsage,
+        Self::CoreClock,
+        Self::MemoryUtil,
+        Self::MemoryClock,
+        Self::MemoryTotal,
+        Self::MemoryUsed,
+        Self::MaxClockSpeed,
+    ];
+
+    /// Returns the snake_case name of the metric, e.g. `"power_usage"`
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Temperature => "temperature",
+            Self::Utilization => "utilization",
+            Self::PowerUsage => "power_usage",
+            Self::CoreClock => "core_clock",
+            Self::MemoryUtil => "memory_util",
+            Self::MemoryClock => "memory_clock",
+            Self::MemoryTotal => "memory_total",
+            Self::MemoryUsed => "memory_used",
+            Self::MaxClockSpeed => "max_clock_speed",
+        }
+    }
+
+    /// Returns the unit the metric is measured in, e.g. `"celsius"`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gpu_info::Metric;
+    ///
+    /// assert_eq!(Metric::Temperature.unit(), "celsius");
+    /// assert_eq!(Metric::MemoryUsed.unit(), "megabytes");
+    /// ```
+    pub fn unit(self) -> &'static str {
+        match self {
+            Self::Temperature => "celsius",
+            Self::Utilization | Self::MemoryUtil => "percent",
+            Self::PowerUsage => "watts",
+            Self::CoreClock | Self::MemoryClock | Self::MaxClockSpeed => "megahertz",
+            Self::MemoryTotal | Self::MemoryUsed => "megabytes",
+        }
+    }
+}
+
+impl Display for Metric {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Source priority per metric, highest priority first.
+///
+/// Metrics missing from the table are left untouched by [`apply()`].
+pub type PriorityTable = [(Metric, &'static [MetricSource])];
+
+/// Returns the source priority for `metric` in `table`
+///
+/// Returns an empty slice if the table has no entry for `metric`.
+pub fn priority(table: &PriorityTable, metric: Metric) -> &'static [MetricSource] {
+    table
+        .iter()
+        .find(|(entry, _)| *entry == metric)
+        .map_or(&[], |(_, sources)| sources)
+}
+
+/// Picks the value of the highest-priority source that produced one
+///
+/// Candidates from sources missing from `priority` are ignored. The winning
+/// source is logged at debug level.
+///
+/// Returns `None` if no source in `priority` produced a value.
+pub fn resolve<T: Debug>(
+    metric: Metric,
+    priority: &[MetricSource],
+    candidates: impl IntoIterator<Item = (MetricSource, Option<T>)>,
+) -> Option<(T, MetricSource)> {
+    let mut best: Option<(usize, T, MetricSource)> = None;
+    for (source, value) in candidates {
+        let Some(value) = value else {
+            continue;
+        };
+        let Some(rank) = priority.iter().position(|s| *s == source) else {
+            debug!(
+                "{:?}: ignoring {:?} from unranked source {}",
+                metric, value, source
+            );
+            continue;
+        };
+        if best
+            .as_ref()
+            .map_or(true, |(best_rank, _, _)| rank < *best_rank)
+        {
+            best = Some((rank, value, source));
+        }
+    }
+    let (_, value, source) = best?;
+    debug!("{:?}: using {:?} from {}", metric, value, source);
+    Some((value, source))
+}
+
+/// Resolves every metric of `table` from partial readings into `gpu`
+///
+/// Each reading is a [`GpuInfo`] holding only the fields one source
+/// produced. For every metric in `table`, the value of the
+/// highest-priority source overwrites the field in `gpu`; if no source
+/// produced a value, the field keeps its current value.
+pub fn apply(gpu: &mut GpuInfo, table: &PriorityTable, readings: &[(MetricSource, &GpuInfo)]) {
+    macro_rules! resolve_field {
+        ($metric:expr, $field:ident) => {{
+            let candidates = readings.iter().map(|(source, info)| (*source, info.$field));
+            if let Some((value, source)) = resolve($metric, priority(table, $metric), candidates) {
+                gpu.$field = Some(value);
+                gpu.metric_sources.record($metric, source);
+            }
+        }};
+    }
+    for (metric, _) in table {
+        match metric {
+            Metric::Temperature => resolve_field!(Metric::Temperature, temperature),
+            Metric::Utilization => resolve_field!(Metric::Utilization, utilization),
+            Metric::PowerUsage => resolve_field!(Metric::PowerUsage, power_usage),
+            Metric::CoreClock => resolve_field!(Metric::CoreClock, core_clock),
+            Metric::MemoryUtil => resolve_field!(Metric::MemoryUtil, memory_util),
+            Metric::MemoryClock => resolve_field!(Metric::MemoryClock, memory_clock),
+            Metric::MemoryTotal => resolve_field!(Metric::MemoryTotal, memory_total),
+            Metric::MemoryUsed => resolve_field!(Metric::MemoryUsed, memory_used),
+            Metric::MaxClockSpeed => resolve_field!(Metric::MaxClockSpeed, max_clock_speed),
+        }
+    }
+}
+
+/// Returns the backends used for `vendor`, most trusted first
+///
+/// This is the single place the per-vendor order is defined. Providers
+/// that need a different order for individual metrics refine it with their
+/// own [`PriorityTable`], such as Intel preferring PDH utilization over the
+/// metrics API.
+///
+/// # Examples
+///
+/// ```
+/// use gpu_info::metric_source::source_order;
+/// use gpu_info::{MetricSource, Vendor};
+///
+/// assert_eq!(source_order(Vendor::Nvidia)[0], MetricSource::Nvml);
+/// ```
+pub fn source_order(vendor: Vendor) -> &'static [MetricSource] {
+    match vendor {
+        Vendor::Nvidia => &[MetricSource::Nvml, MetricSource::Sysfs, MetricSource::Wmi],
+        Vendor::Amd => &[MetricSource::Adl, MetricSource::Sysfs, MetricSource::Wmi],
+        Vendor::Intel(_) => &[
+            MetricSource::IntelMdApi,
+            MetricSource::Pdh,
+            MetricSource::Sysfs,
+            MetricSource::Wmi,
+        ],
+        Vendor::Apple => &[
+            MetricSource::Metal,
+            MetricSource::IoKit,
+            MetricSource::PowerMetrics,
+            MetricSource::SystemProfiler,
+        ],
+        _ => &[MetricSource::Sysfs, MetricSource::Wmi],
+    }
+}
+
+/// Merges the readings of every backend for a `vendor` GPU
+///
+/// Each metric is resolved as by [`apply()`] using [`source_order()`] for
+/// every metric, recording the winning source. The remaining fields, such
+/// as the name and driver version, are then filled with
+/// [`GpuInfo::merge()`] from the readings in the same order. Readings from
+/// sources not in the vendor's order are ignored.
+#[cfg_attr(
+    not(all(target_os = "linux", feature = "vendor-nvidia")),
+    allow(dead_code)
+)]
+pub(crate) fn compose(vendor: Vendor, readings: &[(MetricSource, &GpuInfo)]) -> GpuInfo {
+    let order = source_order(vendor);
+    let table: Vec<(Metric, &'static [MetricSource])> =
+        Metric::ALL.iter().map(|metric| (*metric, order)).collect();
+    let mut gpu = GpuInfo::write_vendor(vendor);
+    apply(&mut gpu, &table, readings);
+    for source in order {
+        for (_, reading) in readings.iter().filter(|(s, _)| s == source) {
+            gpu.merge(reading);
+        }
+    }
+    gpu
+}
+
+/// The source each metric of a [`GpuInfo`] was resolved from.
+///
+/// Only recorded with the `debug-capture` feature; otherwise always empty.
+/// Also carries the reason detection fell back to an unknown GPU, read
+/// with [`GpuInfo::detection_error()`], and when each metric was last read,
+/// read with [`GpuInfo::metric_age()`], so that every field of [`GpuInfo`]
+/// stays public. Neither is serialized or affects equality.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct MetricSources {
+    /// Winning source per metric
+    sources: Vec<(Metric, MetricSource)>,
+    /// Why detection fell back to an unknown GPU
+    #[cfg_attr(feature = "serde", serde(skip))]
+    detection_error: DetectionError,
+    /// Last successful read per metric
+    #[cfg_attr(feature = "serde", serde(skip))]
+    read_times: ReadTimes,
+}
+
+/// Reason a [`GpuInfo`] is unknown, shared between clones.
+///
+/// Diagnostic only: it is ignored by equality, so an unknown GPU with a
+/// reason still equals [`GpuInfo::unknown()`].
+#[derive(Debug, Clone, Default)]
+struct DetectionError(Option<Arc<GpuError>>);
+
+impl PartialEq for DetectionError {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for DetectionError {}
+
+/// When each metric of a [`GpuInfo`] was last read.
+///
+/// `None` unless the [`GpuManager`](crate::GpuManager) tracks metric ages.
+/// Ignored by equality, so two readings with the same values are equal
+/// whenever they were taken.
+#[derive(Debug, Clone, Default)]
+struct ReadTimes(Option<BTreeMap<Metric, Instant>>);
+
+impl PartialEq for ReadTimes {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for ReadTimes {}
+
+impl MetricSources {
+    /// Returns the source `metric` was resolved from, if recorded
+    pub fn get(&self, metric: Metric) -> Option<MetricSource> {
+        self.sources
+            .iter()
+            .find(|(entry, _)| *entry == metric)
+            .map(|(_, source)| *source)
+    }
+
+    /// Returns `true` if no source is recorded
+    pub fn is_empty(&self) -> bool {
+        self.sources.is_empty()
+    }
+
+    /// Iterates over the recorded `(metric, source)` pairs
+    pub fn iter(&self) -> impl Iterator<Item = (Metric, MetricSource)> + '_ {
+        self.sources.iter().copied()
+    }
+
+    /// Records that `metric` was resolved from `source`
+    ///
+    /// Does nothing without the `debug-capture` feature.
+    pub fn record(&mut self, metric: Metric, source: MetricSource) {
+        if cfg!(feature = "debug-capture") {
+            match self.sources.iter_mut().find(|(entry, _)| *entry == metric) {
+                Some(entry) => entry.1 = source,
+                None => self.sources.push((metric, source)),
+            }
+        }
+    }
+
+    /// Creates an empty record carrying why detection failed
+    pub(crate) fn with_detection_error(reason: GpuError) -> Self {
+        Self {
+            detection_error: DetectionError(Some(Arc::new(reason))),
+            ..Self::default()
+        }
+    }
+
+    /// Returns why detection fell back to an unknown GPU, if recorded
+    pub(crate) fn detection_error(&self) -> Option<&GpuError> {
+        self.detection_error.0.as_deref()
+    }
+
+    /// Returns when each metric was last read, if tracked
+    pub(crate) fn read_times(&self) -> Option<&BTreeMap<Metric, Instant>> {
+        self.read_times.0.as_ref()
+    }
+
+    /// Records when each metric was last read
+    pub(crate) fn set_read_times(&mut self, times: BTreeMap<Metric, Instant>) {
+        self.read_times.0 = Some(times);
+    }
+}
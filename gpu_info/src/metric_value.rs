@@ -7,6 +7,7 @@
 //!
 //! `MetricValue<T>` implements `Default`, returning `Unavailable` as the default state.
 
+use crate::metric_source::Metric;
 use std::fmt;
 
 /// Represents a GPU metric value with three possible states
@@ -70,6 +71,21 @@ impl<T> MetricValue<T> {
         }
     }
 
+    /// Tags the value with the metric it measures
+    ///
+    /// The result serializes with the metric's unit, see [`TypedMetricValue`].
+    ///
+    /// # Example
+    /// ```
+    /// use gpu_info::{Metric, MetricValue};
+    ///
+    /// let temp = MetricValue::Available(65.0).with_kind(Metric::Temperature);
+    /// assert_eq!(temp.unit(), "celsius");
+    /// ```
+    pub fn with_kind(self, kind: Metric) -> TypedMetricValue<T> {
+        TypedMetricValue { kind, value: self }
+    }
+
     /// Maps the value if available
     pub fn map<U, F>(self, f: F) -> MetricValue<U>
     where
@@ -137,6 +153,111 @@ impl<T: serde::Serialize> serde::Serialize for MetricValue<T> {
     }
 }
 
+/// A [`MetricValue`] tagged with the [`Metric`] it measures
+///
+/// With the `serde` feature, serializes as an object carrying the metric's
+/// unit and kind:
+///
+/// ```json
+/// {"value": 65.0, "unit": "celsius", "kind": "temperature"}
+/// ```
+///
+/// `Unavailable` and `NotSupported` values serialize `value` as `null` and
+/// add a `status` of `"unavailable"` or `"not_supported"`, so that every
+/// variant round-trips.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TypedMetricValue<T> {
+    /// The metric measured
+    pub kind: Metric,
+    /// The measured value
+    pub value: MetricValue<T>,
+}
+
+impl<T> TypedMetricValue<T> {
+    /// Returns the unit of the value, e.g. `"celsius"`
+    pub fn unit(&self) -> &'static str {
+        self.kind.unit()
+    }
+}
+
+/// Status of a value that is not [`MetricValue::Available`]
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Status {
+    Unavailable,
+    NotSupported,
+}
+
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize> serde::Serialize for TypedMetricValue<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+
+        let status = match self.value {
+            MetricValue::Available(_) => None,
+            MetricValue::Unavailable => Some(Status::Unavailable),
+            MetricValue::NotSupported => Some(Status::NotSupported),
+        };
+        let len = if status.is_some() { 4 } else { 3 };
+        let mut state = serializer.serialize_struct("TypedMetricValue", len)?;
+        state.serialize_field("value", &self.value.value())?;
+        state.serialize_field("unit", self.kind.unit())?;
+        state.serialize_field("kind", &self.kind)?;
+        if let Some(status) = status {
+            state.serialize_field("status", &status)?;
+        }
+        state.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for TypedMetricValue<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::Error;
+
+        #[derive(serde::Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct Repr<T> {
+            value: Option<T>,
+            unit: String,
+            kind: Metric,
+            #[serde(default)]
+            status: Option<Status>,
+        }
+
+        let repr = Repr::<T>::deserialize(deserializer)?;
+        if repr.unit != repr.kind.unit() {
+            return Err(D::Error::custom(format!(
+                "unit `{}` does not match {}, expected `{}`",
+                repr.unit,
+                repr.kind,
+                repr.kind.unit()
+            )));
+        }
+        let value = match (repr.value, repr.status) {
+            (Some(value), None) => MetricValue::Available(value),
+            (None, None) | (None, Some(Status::Unavailable)) => MetricValue::Unavailable,
+            (None, Some(Status::NotSupported)) => MetricValue::NotSupported,
+            (Some(_), Some(_)) => {
+                return Err(D::Error::custom(
+                    "`status` is only allowed when `value` is null",
+                ))
+            }
+        };
+        Ok(TypedMetricValue {
+            kind: repr.kind,
+            value,
+        })
+    }
+}
+
 // TODO: there should be no tests here. Transfer them to gpu_info\src\test
 #[cfg(test)]
 mod tests {
@@ -1,15 +1,32 @@
-use crate::gpu_info::{GpuError, GpuInfo, Result};
+use crate::extended_info::{GpuInfoExtensions, MemoryErrorCounts, MemoryErrorSource};
+use crate::gpu_identity::{self, GpuIdentity};
+use crate::gpu_info::{GpuError, GpuInfo, Result, ThrottleReasons};
 use crate::gpu_manager::GpuManager;
+use crate::metric_source::Metric;
+use crate::sampler::{SampleSubscriber, Sampler, SubscriberId};
 use log::{debug, error, info, warn};
-use std::collections::VecDeque;
+use std::borrow::Cow;
+use std::collections::{BTreeSet, VecDeque};
+#[cfg(feature = "serde_json")]
+use std::io::Write;
 use std::sync::{Arc, Mutex};
-use std::thread::{self, JoinHandle};
 use std::time::{Duration, Instant};
+/// Alert handlers shared between a monitor and its sampler subscription
+type AlertHandlers = Arc<Mutex<Vec<Box<dyn AlertHandler + Send + Sync>>>>;
+/// Sample writer shared between a monitor and its sampler subscription
+#[cfg(feature = "serde_json")]
+type SharedSampleWriter = Arc<Mutex<Option<crate::jsonl::SampleWriter<Box<dyn Write + Send>>>>>;
 /// GPU monitoring system with alert and history support
+///
+/// Each monitor has its own configuration, history, statistics and alert
+/// handlers, and receives samples from a [`Sampler`]. Monitors created with
+/// [`new()`](Self::new) get a private sampler; use
+/// [`with_sampler()`](Self::with_sampler) to share one sampler, and therefore
+/// one provider update per tick, between several monitors.
 #[derive(Debug)]
 pub struct GpuMonitor {
-    /// GPU manager
-    gpu_manager: Arc<Mutex<GpuManager>>,
+    /// Sampler delivering GPU metrics to this monitor
+    sampler: Arc<Sampler>,
 
     /// Monitoring configuration
     config: MonitorConfig,
@@ -18,16 +35,20 @@ pub struct GpuMonitor {
     history: Arc<Mutex<GpuHistory>>,
 
     /// Alert handlers
-    alert_handlers: Arc<Mutex<Vec<Box<dyn AlertHandler + Send + Sync>>>>,
+    alert_handlers: AlertHandlers,
 
     /// Monitoring state
-    is_running: Arc<Mutex<bool>>,
+    is_running: Arc<Mutex<RunState>>,
 
     /// Monitoring statistics
     stats: Arc<Mutex<MonitorStats>>,
 
-    /// Monitoring thread handle
-    thread_handle: Arc<Mutex<Option<JoinHandle<()>>>>,
+    /// Registration with the sampler while monitoring
+    subscription: Mutex<Option<SubscriberId>>,
+
+    /// Optional sink that persists every collected snapshot
+    #[cfg(feature = "serde_json")]
+    sample_writer: SharedSampleWriter,
 }
 /// Monitoring configuration
 #[derive(Debug, Clone)]
@@ -49,6 +70,167 @@ pub struct MonitorConfig {
 
     /// Save metrics to file
     pub save_to_file: Option<String>,
+
+    /// Scheduling priority of the monitoring thread
+    pub thread_priority: ThreadPriority,
+
+    /// Lengthen the polling interval while metrics are stable
+    pub adaptive_interval: Option<AdaptiveInterval>,
+}
+/// Scheduling priority of the monitoring thread
+///
+/// `Low` maps to `THREAD_PRIORITY_BELOW_NORMAL` on Windows and to a nice
+/// value of 10 for the monitoring thread on Linux and Android. On macOS and
+/// the BSDs the thread gets the lowest priority of its scheduling policy.
+/// Other platforms keep the default priority.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ThreadPriority {
+    /// Yield to foreground work such as games or rendering threads
+    Low,
+    /// Inherit the priority of the spawning thread
+    #[default]
+    Normal,
+}
+/// Adaptive polling interval settings
+///
+/// When all metrics stay within `epsilon` of the previous sample for
+/// `stable_samples` consecutive samples, the polling interval is doubled, up
+/// to `max_interval`. Any change snaps it back to the configured
+/// `polling_interval`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AdaptiveInterval {
+    /// Maximum difference between floating-point metrics still considered stable
+    pub epsilon: f32,
+    /// Number of consecutive stable samples before the interval is lengthened
+    pub stable_samples: usize,
+    /// Upper bound for the polling interval
+    pub max_interval: Duration,
+}
+/// State machine driving [`AdaptiveInterval`]
+#[derive(Debug, Clone)]
+pub struct AdaptiveIntervalState {
+    /// Adaptive interval settings
+    config: AdaptiveInterval,
+    /// Configured polling interval
+    base: Duration,
+    /// Interval to wait before the next sample
+    current: Duration,
+    /// Consecutive stable samples at the current interval
+    stable_count: usize,
+}
+/// Detector for sustained clock throttling
+///
+/// Counts, per GPU, consecutive samples whose
+/// [`clock_deficit_percent()`](GpuInfo::clock_deficit_percent) exceeds
+/// [`GpuThresholds::clock_deficit_warning`]. An
+/// [`AlertType::SustainedThrottling`] alert is raised once per episode, on the
+/// sample that completes [`GpuThresholds::clock_deficit_samples`] consecutive
+/// samples. A sample at or below the threshold, or without both clocks,
+/// ends the episode.
+///
+/// It also raises [`AlertType::ClockThrottle`] on the sample where the
+/// driver starts reporting [`ThrottleReasons::is_throttling()`] reasons, and
+/// again only after a sample without them. State follows each GPU's
+/// [`GpuIdentity`], so a sample listing the GPUs in another order continues
+/// it.
+#[derive(Debug, Clone, Default)]
+pub struct ThrottleTracker {
+    /// Consecutive samples over the threshold and whether the driver
+    /// reported throttling in the previous sample, per GPU index
+    states: Vec<(usize, bool)>,
+    /// Identity of the GPU each state belongs to
+    identities: Vec<Option<GpuIdentity>>,
+}
+/// Tracks breached thresholds to suppress repeats and report recoveries
+///
+/// A threshold alert such as [`AlertType::HighTemperature`] marks its
+/// threshold as breached, and reaching a critical threshold also breaches
+/// the matching warning. A threshold recovers once the metric drops below
+/// it minus the [`GpuThresholds::hysteresis`] margin. When the last breached
+/// threshold of a metric recovers, one [`AlertType::Recovered`] is raised,
+/// so handlers see matched breach and recovery pairs.
+///
+/// With a margin above 0, alerts of a breached threshold are suppressed
+/// until it recovers, so a value hovering around the limit alerts only
+/// once. With a margin of 0 every alert passes. State is kept per GPU and
+/// follows each GPU's [`GpuIdentity`].
+#[derive(Debug, Clone, Default)]
+pub struct AlertHysteresis {
+    /// Thresholds that were breached and have not recovered yet, per GPU index
+    disarmed: Vec<BTreeSet<AlertLevel>>,
+    /// Identity of the GPU each entry belongs to
+    identities: Vec<Option<GpuIdentity>>,
+}
+/// Threshold an alert of [`collect_alerts`](GpuMonitor::collect_alerts) is raised for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum AlertLevel {
+    TemperatureWarning,
+    TemperatureCritical,
+    MemoryWarning,
+    MemoryCritical,
+    MemoryHigh,
+    PowerWarning,
+    PowerCritical,
+    Utilization,
+}
+/// Detector for rising PCIe error counters
+///
+/// Compares, per GPU, the error count of each sample with the previous one
+/// and raises [`AlertType::PcieErrorsIncreasing`] when the rate exceeds
+/// [`GpuThresholds::pcie_errors_per_minute`]. Counts come from
+/// [`ConnectionInfo::pcie_error_count()`](crate::extended_info::ConnectionInfo::pcie_error_count),
+/// which the basic metrics do not include; [`GpuMonitor`] reads them for
+/// each sample through
+/// [`GpuInfoExtensions::pcie_error_count()`](crate::GpuInfoExtensions::pcie_error_count).
+/// A counter that goes down, e.g. after a reboot, restarts the comparison.
+#[derive(Debug, Clone, Default)]
+pub struct PcieErrorTracker {
+    /// Previous error count and when it was read, per GPU index
+    previous: Vec<Option<(u64, Instant)>>,
+    /// Identity of the GPU each entry belongs to
+    identities: Vec<Option<GpuIdentity>>,
+}
+/// Detector for new uncorrected ECC errors
+///
+/// Drivers report lifetime or since-reload counts, so a GPU over
+/// [`GpuThresholds::ecc_uncorrected_max`] stays over it. The tracker raises
+/// [`AlertType::EccUncorrected`] on the first sample over the maximum and
+/// afterwards only when the count rises again. A count that goes down, e.g.
+/// when volatile counters reset on a driver reload, restarts the
+/// comparison. Counts follow each GPU's [`GpuIdentity`].
+#[derive(Debug, Clone, Default)]
+pub struct EccTracker {
+    /// Uncorrected errors of the previous sample, per GPU index
+    previous: Vec<Option<u64>>,
+    /// Identity of the GPU each entry belongs to
+    identities: Vec<Option<GpuIdentity>>,
+}
+/// Raises an alert when a GPU retires or remaps more memory
+///
+/// Tracks, per GPU, the highest [`MemoryErrorCounts::total()`] seen and
+/// raises [`AlertType::MemoryPageRetired`] once for each sample that goes
+/// above it. The first sample only sets the baseline, and a count that
+/// drops, e.g. after the driver clears pending remappings, alerts again
+/// only once it passes the previous high. [`GpuMonitor`] reads the counts
+/// for each sample through
+/// [`GpuInfoExtensions::memory_error_counts()`](crate::GpuInfoExtensions::memory_error_counts)
+/// while [`GpuThresholds::memory_error_alerts`] is set; other callers feed it
+/// counts from
+/// [`MemoryInfo::memory_errors`](crate::extended_info::MemoryInfo::memory_errors)
+/// or straight from a [`MemoryErrorSource`].
+#[derive(Debug, Clone, Default)]
+pub struct MemoryErrorTracker {
+    /// Highest count seen, per GPU index
+    high_water: Vec<Option<u64>>,
+    /// Identity of the GPU each entry belongs to
+    identities: Vec<Option<GpuIdentity>>,
+}
+/// Lifecycle state of a monitor
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RunState {
+    Stopped,
+    Running,
+    Paused,
 }
 /// Threshold values for alerts
 #[derive(Debug, Clone)]
@@ -65,6 +247,9 @@ pub struct GpuThresholds {
     /// Critical memory usage threshold (%)
     pub memory_critical: f32,
 
+    /// Used/total memory threshold for [`AlertType::MemoryHigh`] (%)
+    pub memory_high: f32,
+
     /// Power usage warning threshold (W)
     pub power_warning: f32,
 
@@ -76,8 +261,41 @@ pub struct GpuThresholds {
 
     /// Minimum fan speed for warning (%)
     pub fan_speed_min: f32,
+
+    /// Effective core clock deficit for [`AlertType::SustainedThrottling`] (%)
+    pub clock_deficit_warning: f32,
+
+    /// Consecutive samples over `clock_deficit_warning` before alerting (0 disables)
+    pub clock_deficit_samples: usize,
+
+    /// PCIe error rate for [`AlertType::PcieErrorsIncreasing`] (errors/min, 0 disables)
+    pub pcie_errors_per_minute: f32,
+
+    /// Uncorrected ECC errors allowed before [`AlertType::EccUncorrected`]
+    /// (`None`, the default, disables); see [`EccTracker`]
+    pub ecc_uncorrected_max: Option<u64>,
+
+    /// Raise [`AlertType::MemoryPageRetired`] when a GPU retires or remaps
+    /// more memory (off by default); see [`MemoryErrorTracker`]
+    pub memory_error_alerts: bool,
+
+    /// Margin a metric must drop below its threshold before the alert fires
+    /// again, in the metric's unit (0 disables); see [`AlertHysteresis`]
+    pub hysteresis: f32,
+
+    /// Metrics read longer ago are ignored by alerts (`None` disables)
+    ///
+    /// Needs [`GpuManager::set_metric_age_tracking()`], which
+    /// [`GpuMonitor::new()`] enables when this is set; for a monitor built
+    /// with [`GpuMonitor::with_sampler()`], enable it on the sampled manager.
+    /// See [`GpuInfo::metric_age()`].
+    pub max_metric_age: Option<Duration>,
 }
 /// GPU metrics history
+///
+/// Entries follow each GPU's [`GpuIdentity`]: after every sample,
+/// `gpu_histories[i]` is the history of the sample's GPU `i`, even if the
+/// GPUs were listed in another order before.
 #[derive(Debug)]
 pub struct GpuHistory {
     /// History entries for each GPU
@@ -85,6 +303,9 @@ pub struct GpuHistory {
 
     /// Maximum history size
     pub max_size: usize,
+
+    /// Identity of the GPU each entry belongs to
+    identities: Vec<Option<GpuIdentity>>,
 }
 /// Metrics history for a single GPU
 #[derive(Debug)]
@@ -110,6 +331,13 @@ pub struct SingleGpuHistory {
     /// Fan speeds
     pub fan_speeds: VecDeque<f32>,
 }
+/// Metrics of all GPUs collected in one monitoring iteration
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GpuMetricsSnapshot {
+    /// GPU metrics, indexed like [`GpuManager::get_all_gpus`]
+    pub gpus: Vec<GpuInfo>,
+}
 /// Monitoring statistics
 #[derive(Debug, Default, Clone)]
 pub struct MonitorStats {
@@ -170,6 +398,20 @@ pub enum AlertType {
         usage: f32,
     },
 
+    /// High memory allocation alert - triggered when `memory_used` exceeds the
+    /// `memory_high` percentage of `memory_total`, unless a memory usage alert
+    /// for the same GPU was already derived from that ratio.
+    MemoryHigh {
+        /// Index of the GPU that triggered the alert.
+        gpu_index: usize,
+        /// Used memory in MB.
+        used_mb: u32,
+        /// Total memory in MB.
+        total_mb: u32,
+        /// Used memory as a percentage of total memory (0-100).
+        usage: f32,
+    },
+
     /// High power usage alert - triggered when GPU power consumption exceeds warning threshold.
     HighPowerUsage {
         /// Index of the GPU that triggered the alert.
@@ -202,6 +444,74 @@ pub enum AlertType {
         fan_speed: f32,
     },
 
+    /// Sustained throttling alert - triggered when the effective core clock falls
+    /// short of the requested clock by more than the threshold for several
+    /// consecutive samples.
+    SustainedThrottling {
+        /// Index of the GPU that triggered the alert.
+        gpu_index: usize,
+        /// Current clock deficit as a percentage of the requested clock (0-100).
+        deficit_percent: f32,
+        /// Number of consecutive samples over the threshold.
+        samples: usize,
+    },
+
+    /// Clock throttle alert - triggered when the driver starts reporting
+    /// [`ThrottleReasons::is_throttling()`] reasons for holding the clocks
+    /// below their maximum; see [`ThrottleTracker`].
+    ClockThrottle {
+        /// Index of the GPU that triggered the alert.
+        gpu_index: usize,
+        /// The reasons reported by the driver.
+        reasons: ThrottleReasons,
+    },
+
+    /// ECC alert - triggered when the uncorrected (double-bit) ECC errors of
+    /// a GPU exceed [`GpuThresholds::ecc_uncorrected_max`], and again each
+    /// time they rise further.
+    EccUncorrected {
+        /// Index of the GPU that triggered the alert.
+        gpu_index: usize,
+        /// Uncorrected ECC errors reported by the driver.
+        errors: u64,
+        /// Maximum allowed uncorrected errors.
+        max: u64,
+    },
+
+    /// PCIe errors alert - triggered when the PCIe error counters of a GPU rise
+    /// faster than the threshold between two samples.
+    PcieErrorsIncreasing {
+        /// Index of the GPU that triggered the alert.
+        gpu_index: usize,
+        /// Errors per minute since the previous sample.
+        errors_per_minute: f32,
+        /// Total errors since boot.
+        total: u64,
+    },
+
+    /// Memory page retired alert - triggered when the memory errors of a
+    /// GPU exceed their previous highest count.
+    MemoryPageRetired {
+        /// Index of the GPU that triggered the alert.
+        gpu_index: usize,
+        /// [`MemoryErrorCounts::total()`] now.
+        total: u64,
+        /// Highest count seen before this increase.
+        previous: u64,
+    },
+
+    /// Recovery event - triggered once when a metric that raised a threshold
+    /// alert drops back below its thresholds; see [`AlertHysteresis`].
+    Recovered {
+        /// Index of the GPU that recovered.
+        gpu_index: usize,
+        /// The metric that returned to normal; memory usage alerts recover as
+        /// [`Metric::MemoryUtil`], [`AlertType::MemoryHigh`] as [`Metric::MemoryUsed`].
+        metric: Metric,
+        /// Current value of the metric, in the unit of its thresholds.
+        value: f32,
+    },
+
     /// GPU inactive alert - triggered when a GPU becomes inactive or unresponsive.
     GpuInactive {
         /// Index of the GPU that triggered the alert.
@@ -250,6 +560,17 @@ impl AlertHandler for LogAlertHandler {
             AlertType::CriticalMemoryUsage { gpu_index, usage } => {
                 error!("GPU #{} CRITICAL memory usage: {:.1}%", gpu_index, usage);
             }
+            AlertType::MemoryHigh {
+                gpu_index,
+                used_mb,
+                total_mb,
+                usage,
+            } => {
+                warn!(
+                    "GPU #{} high memory allocation: {} / {} MB ({:.1}%)",
+                    gpu_index, used_mb, total_mb, usage
+                );
+            }
             AlertType::HighPowerUsage { gpu_index, power } => {
                 warn!("GPU #{} high power usage: {:.1}W", gpu_index, power);
             }
@@ -268,6 +589,56 @@ impl AlertHandler for LogAlertHandler {
             } => {
                 warn!("GPU #{} low fan speed: {:.1}%", gpu_index, fan_speed);
             }
+            AlertType::SustainedThrottling {
+                gpu_index,
+                deficit_percent,
+                samples,
+            } => {
+                warn!(
+                    "GPU #{} throttled: effective clock {:.1}% below requested for {} samples",
+                    gpu_index, deficit_percent, samples
+                );
+            }
+            AlertType::ClockThrottle { gpu_index, reasons } => {
+                warn!("GPU #{} clocks throttled: {}", gpu_index, reasons);
+            }
+            AlertType::EccUncorrected {
+                gpu_index,
+                errors,
+                max,
+            } => {
+                error!(
+                    "GPU #{} uncorrected ECC errors: {} (max {})",
+                    gpu_index, errors, max
+                );
+            }
+            AlertType::PcieErrorsIncreasing {
+                gpu_index,
+                errors_per_minute,
+                total,
+            } => {
+                warn!(
+                    "GPU #{} PCIe errors increasing: {:.1}/min ({} total)",
+                    gpu_index, errors_per_minute, total
+                );
+            }
+            AlertType::MemoryPageRetired {
+                gpu_index,
+                total,
+                previous,
+            } => {
+                error!(
+                    "GPU #{} memory pages retired: {} (was {})",
+                    gpu_index, total, previous
+                );
+            }
+            AlertType::Recovered {
+                gpu_index,
+                metric,
+                value,
+            } => {
+                info!("GPU #{} {} back to normal: {:.1}", gpu_index, metric, value);
+            }
             AlertType::GpuInactive { gpu_index } => {
                 error!("GPU #{} became inactive", gpu_index);
             }
@@ -290,6 +661,17 @@ impl Default for MonitorConfig {
             enable_alerts: true,
             log_metrics: false,
             save_to_file: None,
+            thread_priority: ThreadPriority::Normal,
+            adaptive_interval: None,
+        }
+    }
+}
+impl Default for AdaptiveInterval {
+    fn default() -> Self {
+        Self {
+            epsilon: 0.5,
+            stable_samples: 5,
+            max_interval: Duration::from_secs(10),
         }
     }
 }
@@ -305,6 +687,8 @@ impl MonitorConfig {
     /// - `enable_alerts`: true
     /// - `log_metrics`: false
     /// - `save_to_file`: None
+    /// - `thread_priority`: Normal
+    /// - `adaptive_interval`: None
     pub fn new() -> Self {
         Self::default()
     }
@@ -403,6 +787,35 @@ impl MonitorConfig {
         self
     }
 
+    /// Sets the scheduling priority of the monitoring thread.
+    ///
+    /// # Arguments
+    ///
+    /// * `priority` - The thread priority.
+    ///
+    /// # Returns
+    ///
+    /// The modified configuration for method chaining.
+    pub fn with_thread_priority(mut self, priority: ThreadPriority) -> Self {
+        self.thread_priority = priority;
+        self
+    }
+
+    /// Enables or disables the adaptive polling interval.
+    ///
+    /// # Arguments
+    ///
+    /// * `adaptive` - The adaptive interval settings, or `None` to always
+    ///   poll at `polling_interval`.
+    ///
+    /// # Returns
+    ///
+    /// The modified configuration for method chaining.
+    pub fn with_adaptive_interval(mut self, adaptive: Option<AdaptiveInterval>) -> Self {
+        self.adaptive_interval = adaptive;
+        self
+    }
+
     // BORROWING CHAIN PATTERN: &mut self -> &mut Self
     // Use for in-place modification of existing config
 
@@ -501,6 +914,35 @@ impl MonitorConfig {
         self.save_to_file = path;
         self
     }
+
+    /// Sets the scheduling priority of the monitoring thread (borrowing pattern).
+    ///
+    /// # Arguments
+    ///
+    /// * `priority` - The thread priority.
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to self for method chaining.
+    pub fn thread_priority(&mut self, priority: ThreadPriority) -> &mut Self {
+        self.thread_priority = priority;
+        self
+    }
+
+    /// Enables or disables the adaptive polling interval (borrowing pattern).
+    ///
+    /// # Arguments
+    ///
+    /// * `adaptive` - The adaptive interval settings, or `None` to always
+    ///   poll at `polling_interval`.
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to self for method chaining.
+    pub fn adaptive_interval(&mut self, adaptive: Option<AdaptiveInterval>) -> &mut Self {
+        self.adaptive_interval = adaptive;
+        self
+    }
 }
 impl Default for GpuThresholds {
     fn default() -> Self {
@@ -509,37 +951,71 @@ impl Default for GpuThresholds {
             temperature_critical: 85.0,
             memory_warning: 80.0,
             memory_critical: 95.0,
+            memory_high: 90.0,
             power_warning: 250.0,
             power_critical: 300.0,
             utilization_warning: 95.0,
             fan_speed_min: 10.0,
+            clock_deficit_warning: 15.0,
+            clock_deficit_samples: 5,
+            pcie_errors_per_minute: 10.0,
+            ecc_uncorrected_max: None,
+            memory_error_alerts: false,
+            hysteresis: 0.0,
+            max_metric_age: None,
         }
     }
 }
 impl GpuMonitor {
-    /// Creates a new GPU monitor
+    /// Creates a new GPU monitor with its own sampler
+    ///
+    /// The sampled manager tracks metric ages if
+    /// [`GpuThresholds::max_metric_age`] is set.
     pub fn new(config: MonitorConfig) -> Self {
-        let gpu_manager = Arc::new(Mutex::new(GpuManager::new()));
+        let mut manager = GpuManager::new();
+        manager.set_metric_age_tracking(config.thresholds.max_metric_age.is_some());
+        let gpu_manager = Arc::new(Mutex::new(manager));
         let gpu_count = if let Ok(mgr) = gpu_manager.lock() {
             mgr.gpu_count()
         } else {
             0
         };
-        let history = Arc::new(Mutex::new(GpuHistory::new(gpu_count, config.history_size)));
+        let mut monitor = Self::with_sampler(config, Arc::new(Sampler::new(gpu_manager)));
+        monitor.history = Arc::new(Mutex::new(GpuHistory::new(
+            gpu_count,
+            monitor.config.history_size,
+        )));
+        monitor
+    }
+    /// Creates a monitor that receives samples from a shared `sampler`
+    ///
+    /// The sampler polls at the shortest interval of its running monitors,
+    /// and every monitor evaluates each sample against its own thresholds.
+    pub fn with_sampler(config: MonitorConfig, sampler: Arc<Sampler>) -> Self {
+        let history = Arc::new(Mutex::new(GpuHistory::new(0, config.history_size)));
         Self {
-            gpu_manager,
+            sampler,
             config,
             history,
             alert_handlers: Arc::new(Mutex::new(Vec::new())),
-            is_running: Arc::new(Mutex::new(false)),
+            is_running: Arc::new(Mutex::new(RunState::Stopped)),
             stats: Arc::new(Mutex::new(MonitorStats::default())),
-            thread_handle: Arc::new(Mutex::new(None)),
+            subscription: Mutex::new(None),
+            #[cfg(feature = "serde_json")]
+            sample_writer: Arc::new(Mutex::new(None)),
         }
     }
     /// Creates a monitor with default settings
     pub fn with_defaults() -> Self {
         Self::new(MonitorConfig::default())
     }
+    /// Returns the sampler this monitor receives samples from
+    ///
+    /// Pass it to [`with_sampler()`](Self::with_sampler) to attach another
+    /// monitor without adding provider calls.
+    pub fn sampler(&self) -> &Arc<Sampler> {
+        &self.sampler
+    }
     /// Adds an alert handler
     pub fn add_alert_handler(&self, handler: Box<dyn AlertHandler + Send + Sync>) -> Result<()> {
         if let Ok(mut handlers) = self.alert_handlers.lock() {
@@ -550,15 +1026,36 @@ impl GpuMonitor {
             Err(GpuError::GpuNotActive)
         }
     }
-    /// Starts monitoring in a separate thread
+    /// Sets a writer that persists a [`GpuMetricsSnapshot`] after every
+    /// successful collection, replacing any previous writer.
+    ///
+    /// Write errors are logged and do not stop monitoring.
+    #[cfg(feature = "serde_json")]
+    pub fn set_sample_writer(
+        &self,
+        writer: crate::jsonl::SampleWriter<Box<dyn Write + Send>>,
+    ) -> Result<()> {
+        if let Ok(mut sample_writer) = self.sample_writer.lock() {
+            *sample_writer = Some(writer);
+            Ok(())
+        } else {
+            Err(GpuError::GpuNotActive)
+        }
+    }
+    /// Removes and returns the sample writer, if one was set
+    #[cfg(feature = "serde_json")]
+    pub fn take_sample_writer(&self) -> Option<crate::jsonl::SampleWriter<Box<dyn Write + Send>>> {
+        self.sample_writer.lock().ok().and_then(|mut w| w.take())
+    }
+    /// Starts monitoring by subscribing to the sampler
     pub fn start_monitoring(&self) -> Result<()> {
-        // Check if already running
-        if let Ok(mut is_running) = self.is_running.lock() {
-            if *is_running {
-                warn!("Monitoring is already running");
-                return Ok(());
-            }
-            *is_running = true;
+        let mut subscription = self
+            .subscription
+            .lock()
+            .map_err(|_| GpuError::GpuNotActive)?;
+        if subscription.is_some() {
+            warn!("Monitoring is already running");
+            return Ok(());
         }
 
         // Ensure we have at least one alert handler
@@ -578,72 +1075,110 @@ impl GpuMonitor {
         if let Ok(mut stats) = self.stats.lock() {
             stats.start_time = Some(Instant::now());
         }
-
-        // Clone all necessary data for the thread
-        let gpu_manager = Arc::clone(&self.gpu_manager);
-        let history = Arc::clone(&self.history);
-        let alert_handlers = Arc::clone(&self.alert_handlers);
-        let is_running = Arc::clone(&self.is_running);
-        let stats = Arc::clone(&self.stats);
-        let config = self.config.clone();
-
-        // Start the monitoring thread
-        let handle = thread::spawn(move || {
-            Self::monitoring_loop(
-                gpu_manager,
-                history,
-                alert_handlers,
-                is_running,
-                stats,
-                config,
-            );
-        });
-
-        // Store the thread handle
-        if let Ok(mut thread_handle) = self.thread_handle.lock() {
-            *thread_handle = Some(handle);
+        if let Ok(mut is_running) = self.is_running.lock() {
+            *is_running = RunState::Running;
         }
 
-        // Give the thread a moment to start
-        thread::sleep(Duration::from_millis(10));
-
-        Ok(())
+        let adaptive = self
+            .config
+            .adaptive_interval
+            .clone()
+            .map(|adaptive| AdaptiveIntervalState::new(self.config.polling_interval, adaptive));
+        let subscriber = MonitorSubscriber {
+            config: self.config.clone(),
+            history: Arc::clone(&self.history),
+            alert_handlers: Arc::clone(&self.alert_handlers),
+            is_running: Arc::clone(&self.is_running),
+            stats: Arc::clone(&self.stats),
+            adaptive: Mutex::new((adaptive, Vec::new())),
+            throttle: Mutex::new(ThrottleTracker::new()),
+            ecc: Mutex::new(EccTracker::new()),
+            pcie: Mutex::new(PcieErrorTracker::new()),
+            memory_errors: Mutex::new(MemoryErrorTracker::new()),
+            hysteresis: Mutex::new(AlertHysteresis::new()),
+            #[cfg(feature = "serde_json")]
+            sample_writer: Arc::clone(&self.sample_writer),
+        };
+        match self.sampler.subscribe(Arc::new(subscriber)) {
+            Ok(id) => {
+                *subscription = Some(id);
+                Ok(())
+            }
+            Err(e) => {
+                if let Ok(mut is_running) = self.is_running.lock() {
+                    *is_running = RunState::Stopped;
+                }
+                Err(e)
+            }
+        }
     }
     /// Stops monitoring
+    ///
+    /// No samples are delivered to this monitor after this returns. The
+    /// sampler keeps running for any other monitors subscribed to it.
     pub fn stop_monitoring(&self) -> Result<()> {
-        // Set the running flag to false
+        let mut subscription = self
+            .subscription
+            .lock()
+            .map_err(|_| GpuError::GpuNotActive)?;
+        let Some(id) = subscription.take() else {
+            warn!("Monitoring is not running");
+            return Ok(());
+        };
+        info!("Stopping GPU monitoring");
+        if !self.sampler.unsubscribe(id) {
+            debug!("Monitor was already unsubscribed from the sampler");
+        }
         if let Ok(mut is_running) = self.is_running.lock() {
-            if !*is_running {
-                warn!("Monitoring is not running");
-                return Ok(());
+            *is_running = RunState::Stopped;
+        }
+        Ok(())
+    }
+    /// Checks if monitoring is running (including while paused)
+    pub fn is_monitoring(&self) -> bool {
+        self.is_running
+            .lock()
+            .map(|r| *r != RunState::Stopped)
+            .unwrap_or(false)
+    }
+    /// Pauses metric collection without unsubscribing from the sampler
+    ///
+    /// A paused monitor receives no samples and does not count towards the
+    /// sampler interval. The sampler stops collecting if all of its monitors
+    /// are paused.
+    pub fn pause(&self) {
+        if let Ok(mut state) = self.is_running.lock() {
+            match *state {
+                RunState::Running => {
+                    *state = RunState::Paused;
+                    info!("Pausing GPU monitoring");
+                }
+                RunState::Paused => debug!("Monitoring is already paused"),
+                RunState::Stopped => warn!("Monitoring is not running"),
             }
-            *is_running = false;
-            info!("Stopping GPU monitoring");
         }
-
-        // Wait for the thread to finish
-        if let Ok(mut thread_handle) = self.thread_handle.lock() {
-            if let Some(handle) = thread_handle.take() {
-                // Give the thread time to notice the stop signal
-                thread::sleep(Duration::from_millis(50));
-
-                // Try to join the thread with a timeout
-                match handle.join() {
-                    Ok(()) => {
-                        debug!("Monitoring thread joined successfully");
-                    }
-                    Err(_) => {
-                        warn!("Failed to join monitoring thread cleanly");
-                    }
+        self.sampler.wake();
+    }
+    /// Resumes metric collection after [`pause()`](Self::pause)
+    pub fn resume(&self) {
+        if let Ok(mut state) = self.is_running.lock() {
+            match *state {
+                RunState::Paused => {
+                    *state = RunState::Running;
+                    info!("Resuming GPU monitoring");
                 }
+                RunState::Running => debug!("Monitoring is not paused"),
+                RunState::Stopped => warn!("Monitoring is not running"),
             }
         }
-
-        Ok(())
+        self.sampler.wake();
     }
-    /// Checks if monitoring is running
-    pub fn is_monitoring(&self) -> bool {
-        self.is_running.lock().map(|r| *r).unwrap_or(false)
+    /// Checks if monitoring is paused
+    pub fn is_paused(&self) -> bool {
+        self.is_running
+            .lock()
+            .map(|r| *r == RunState::Paused)
+            .unwrap_or(false)
     }
     /// Returns monitoring statistics
     pub fn get_stats(&self) -> MonitorStats {
@@ -657,102 +1192,78 @@ impl GpuMonitor {
             None
         }
     }
-    /// Main monitoring loop
-    fn monitoring_loop(
-        gpu_manager: Arc<Mutex<GpuManager>>,
-        history: Arc<Mutex<GpuHistory>>,
-        alert_handlers: Arc<Mutex<Vec<Box<dyn AlertHandler + Send + Sync>>>>,
-        is_running: Arc<Mutex<bool>>,
-        stats: Arc<Mutex<MonitorStats>>,
-        config: MonitorConfig,
-    ) {
-        info!(
-            "GPU monitoring loop started with interval: {:?}",
-            config.polling_interval
-        );
-        let mut consecutive_errors = 0;
-        const MAX_CONSECUTIVE_ERRORS: u32 = 10;
-        let mut iteration_count = 0;
-
-        while Self::should_continue_monitoring(&is_running) {
-            iteration_count += 1;
-            debug!("Monitoring iteration #{}", iteration_count);
-
-            let collection_start = Instant::now();
-            let collection_result = if let Ok(mut manager) = gpu_manager.lock() {
-                let refresh_result = manager.refresh_all_gpus();
-                if refresh_result.is_err() && manager.gpu_count() == 0 {
-                    debug!("No GPUs found, attempting detection...");
-                }
-                refresh_result
-            } else {
-                Err(GpuError::GpuNotActive)
-            };
-
-            match collection_result {
-                Ok(()) => {
-                    consecutive_errors = 0;
-                    if let Ok(manager) = gpu_manager.lock() {
-                        let gpus = manager.get_all_gpus();
-                        debug!("Successfully collected data for {} GPUs", gpus.len());
-                        Self::update_history(&history, gpus, collection_start);
-                        if config.enable_alerts {
-                            Self::check_alerts(gpus, &config.thresholds, &alert_handlers);
-                        }
-                        if config.log_metrics {
-                            Self::log_metrics(gpus);
-                        }
-                        Self::update_stats(&stats, collection_start);
-                    }
-                }
-                Err(e) => {
-                    consecutive_errors += 1;
-                    debug!(
-                        "GPU data collection failed (attempt {}): {}",
-                        consecutive_errors, e
-                    );
-                    if let Ok(mut s) = stats.lock() {
-                        s.total_errors += 1;
-                    }
-                    if consecutive_errors >= MAX_CONSECUTIVE_ERRORS {
-                        warn!(
-                            "Too many consecutive errors ({}), taking a longer break",
-                            consecutive_errors
-                        );
-                        thread::sleep(Duration::from_secs(1));
-                        consecutive_errors = 0;
-                    }
-                }
-            }
-            thread::sleep(config.polling_interval);
-        }
-        info!(
-            "GPU monitoring loop ended after {} iterations",
-            iteration_count
-        );
-    }
-    /// Checks if monitoring should continue
-    fn should_continue_monitoring(is_running: &Arc<Mutex<bool>>) -> bool {
-        is_running.lock().map(|r| *r).unwrap_or(false)
-    }
-    /// Updates metrics history
+    /// Updates metrics history, adding entries for newly seen GPUs
     fn update_history(history: &Arc<Mutex<GpuHistory>>, gpus: &[GpuInfo], timestamp: Instant) {
         if let Ok(mut hist) = history.lock() {
-            for (gpu_index, gpu) in gpus.iter().enumerate() {
-                if let Some(gpu_history) = hist.gpu_histories.get_mut(gpu_index) {
-                    gpu_history.add_measurement(gpu, timestamp);
+            hist.record(gpus, timestamp);
+        }
+    }
+    /// Writes the collected metrics to the sample writer, if any
+    #[cfg(feature = "serde_json")]
+    fn persist_snapshot(sample_writer: &SharedSampleWriter, gpus: &[GpuInfo]) {
+        if let Ok(mut guard) = sample_writer.lock() {
+            if let Some(writer) = guard.as_mut() {
+                let snapshot = GpuMetricsSnapshot {
+                    gpus: gpus.to_vec(),
+                };
+                if let Err(e) = writer.write_snapshot(&snapshot) {
+                    warn!("Failed to persist GPU sample: {}", e);
                 }
             }
         }
     }
-    /// Checks alerts
+    /// Checks alerts, including sustained throttling tracked across samples
+    #[allow(clippy::too_many_arguments)]
     fn check_alerts(
         gpus: &[GpuInfo],
+        collected_at: Instant,
         thresholds: &GpuThresholds,
-        alert_handlers: &Arc<Mutex<Vec<Box<dyn AlertHandler + Send + Sync>>>>,
+        throttle: &Mutex<ThrottleTracker>,
+        ecc: &Mutex<EccTracker>,
+        pcie: &Mutex<PcieErrorTracker>,
+        memory_errors: &Mutex<MemoryErrorTracker>,
+        hysteresis: &Mutex<AlertHysteresis>,
+        alert_handlers: &AlertHandlers,
     ) {
+        let mut alerts = Self::collect_alerts(gpus, thresholds);
+        if let Ok(mut hysteresis) = hysteresis.lock() {
+            alerts = hysteresis.observe(gpus, thresholds, alerts);
+        }
+        if let Ok(mut throttle) = throttle.lock() {
+            alerts.extend(throttle.observe(gpus, thresholds));
+        }
+        if let Ok(mut ecc) = ecc.lock() {
+            alerts.extend(ecc.observe(gpus, thresholds));
+        }
+        if let Ok(mut pcie) = pcie.lock() {
+            alerts.extend(pcie.observe_gpus(
+                gpus,
+                GpuInfoExtensions::pcie_error_count,
+                collected_at,
+                thresholds,
+            ));
+        }
+        if let Ok(mut memory_errors) = memory_errors.lock() {
+            alerts.extend(memory_errors.observe_gpus(
+                gpus,
+                GpuInfoExtensions::memory_error_counts,
+                thresholds,
+            ));
+        }
+        Self::dispatch_alerts(&alerts, alert_handlers);
+    }
+    /// Evaluates `thresholds` against `gpus` and returns the resulting alerts
+    ///
+    /// Memory usage alerts use the provider's `memory_util` when available
+    /// and fall back to [`GpuInfo::memory_used_percent`], so they work for
+    /// providers that only report used and total memory. In that case
+    /// [`AlertType::MemoryHigh`] is not raised alongside them, since it would
+    /// report the same ratio. Metrics older than
+    /// [`GpuThresholds::max_metric_age`] are treated as missing.
+    pub(crate) fn collect_alerts(gpus: &[GpuInfo], thresholds: &GpuThresholds) -> Vec<AlertType> {
         let mut alerts = Vec::new();
         for (gpu_index, gpu) in gpus.iter().enumerate() {
+            let gpu = &*fresh_metrics(gpu, thresholds);
             if let Some(temp) = gpu.temperature {
                 if temp >= thresholds.temperature_critical {
                     alerts.push(AlertType::CriticalTemperature {
@@ -766,17 +1277,35 @@ impl GpuMonitor {
                     });
                 }
             }
-            if let Some(mem_util) = gpu.memory_util {
+            let mut usage_alerted = false;
+            if let Some(mem_util) = gpu.memory_util.or_else(|| gpu.memory_used_percent()) {
                 if mem_util >= thresholds.memory_critical {
                     alerts.push(AlertType::CriticalMemoryUsage {
                         gpu_index,
                         usage: mem_util,
                     });
+                    usage_alerted = true;
                 } else if mem_util >= thresholds.memory_warning {
                     alerts.push(AlertType::HighMemoryUsage {
                         gpu_index,
                         usage: mem_util,
                     });
+                    usage_alerted = true;
+                }
+            }
+            // Without `memory_util` the usage alerts above already report the
+            // used/total ratio, so `MemoryHigh` would repeat them.
+            let ratio_alerted = usage_alerted && gpu.memory_util.is_none();
+            if let (Some(used_mb), Some(total_mb), Some(usage)) =
+                (gpu.memory_used, gpu.memory_total, gpu.memory_used_percent())
+            {
+                if usage >= thresholds.memory_high && !ratio_alerted {
+                    alerts.push(AlertType::MemoryHigh {
+                        gpu_index,
+                        used_mb,
+                        total_mb,
+                        usage,
+                    });
                 }
             }
             if let Some(power) = gpu.power_usage {
@@ -798,9 +1327,13 @@ impl GpuMonitor {
                 alerts.push(AlertType::GpuInactive { gpu_index });
             }
         }
+        alerts
+    }
+    /// Dispatches alerts to the registered handlers
+    fn dispatch_alerts(alerts: &[AlertType], alert_handlers: &AlertHandlers) {
         if !alerts.is_empty() {
             if let Ok(handlers) = alert_handlers.lock() {
-                for alert in &alerts {
+                for alert in alerts {
                     for handler in handlers.iter() {
                         if let Err(e) = handler.handle_alert(alert) {
                             error!("Alert handler '{}' failed: {}", handler.name(), e);
@@ -842,6 +1375,569 @@ impl GpuMonitor {
         }
     }
 }
+/// `gpu` without the metrics older than [`GpuThresholds::max_metric_age`]
+fn fresh_metrics<'a>(gpu: &'a GpuInfo, thresholds: &GpuThresholds) -> Cow<'a, GpuInfo> {
+    match thresholds.max_metric_age {
+        Some(max_age) if gpu.metric_sources.read_times().is_some() => {
+            let mut copy = gpu.clone();
+            copy.clear_stale_metrics(max_age);
+            Cow::Owned(copy)
+        }
+        _ => Cow::Borrowed(gpu),
+    }
+}
+/// Sampler subscription of a running [`GpuMonitor`]
+struct MonitorSubscriber {
+    /// Monitoring configuration
+    config: MonitorConfig,
+    /// Metrics history
+    history: Arc<Mutex<GpuHistory>>,
+    /// Alert handlers
+    alert_handlers: AlertHandlers,
+    /// Monitoring state
+    is_running: Arc<Mutex<RunState>>,
+    /// Monitoring statistics
+    stats: Arc<Mutex<MonitorStats>>,
+    /// Adaptive interval state and the previous sample it compares against
+    adaptive: Mutex<(Option<AdaptiveIntervalState>, Vec<GpuInfo>)>,
+    /// Consecutive throttled samples per GPU
+    throttle: Mutex<ThrottleTracker>,
+    /// Uncorrected ECC errors of the previous sample per GPU
+    ecc: Mutex<EccTracker>,
+    /// PCIe error count of the previous sample per GPU
+    pcie: Mutex<PcieErrorTracker>,
+    /// Highest memory error count seen per GPU
+    memory_errors: Mutex<MemoryErrorTracker>,
+    /// Threshold alerts waiting for their metric to recover
+    hysteresis: Mutex<AlertHysteresis>,
+    /// Optional sink that persists every collected snapshot
+    #[cfg(feature = "serde_json")]
+    sample_writer: SharedSampleWriter,
+}
+impl SampleSubscriber for MonitorSubscriber {
+    fn requested_interval(&self) -> Option<Duration> {
+        if self
+            .is_running
+            .lock()
+            .map_or(true, |r| *r != RunState::Running)
+        {
+            return None;
+        }
+        self.adaptive
+            .lock()
+            .ok()
+            .and_then(|state| state.0.as_ref().map(AdaptiveIntervalState::current))
+            .or(Some(self.config.polling_interval))
+    }
+    fn thread_priority(&self) -> ThreadPriority {
+        self.config.thread_priority
+    }
+    fn on_sample(&self, gpus: &[GpuInfo], collected_at: Instant) {
+        debug!("Successfully collected data for {} GPUs", gpus.len());
+        GpuMonitor::update_history(&self.history, gpus, collected_at);
+        #[cfg(feature = "serde_json")]
+        GpuMonitor::persist_snapshot(&self.sample_writer, gpus);
+        if self.config.enable_alerts {
+            GpuMonitor::check_alerts(
+                gpus,
+                collected_at,
+                &self.config.thresholds,
+                &self.throttle,
+                &self.ecc,
+                &self.pcie,
+                &self.memory_errors,
+                &self.hysteresis,
+                &self.alert_handlers,
+            );
+        }
+        if self.config.log_metrics {
+            GpuMonitor::log_metrics(gpus);
+        }
+        if let Ok(mut adaptive) = self.adaptive.lock() {
+            let (state, previous) = &mut *adaptive;
+            if let Some(state) = state.as_mut() {
+                state.observe(previous, gpus);
+                *previous = gpus.to_vec();
+            }
+        }
+        GpuMonitor::update_stats(&self.stats, collected_at);
+    }
+    fn on_error(&self, error: &GpuError) {
+        debug!("GPU data collection failed: {}", error);
+        if let Ok(mut s) = self.stats.lock() {
+            s.total_errors += 1;
+        }
+    }
+}
+impl ThrottleTracker {
+    /// Creates a tracker with no throttled samples recorded
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Returns the number of consecutive throttled samples of a GPU
+    pub fn streak(&self, gpu_index: usize) -> usize {
+        self.states.get(gpu_index).map_or(0, |(streak, _)| *streak)
+    }
+    /// Records a sample and returns the alerts it raises
+    pub fn observe(&mut self, gpus: &[GpuInfo], thresholds: &GpuThresholds) -> Vec<AlertType> {
+        gpu_identity::realign(&mut self.identities, &mut self.states, gpus, || (0, false));
+        let mut alerts = Vec::new();
+        for (gpu_index, (gpu, (streak, throttled))) in gpus.iter().zip(&mut self.states).enumerate()
+        {
+            let reasons = gpu.throttle_reasons.filter(|r| r.is_throttling());
+            if let (Some(reasons), false) = (reasons, *throttled) {
+                alerts.push(AlertType::ClockThrottle { gpu_index, reasons });
+            }
+            *throttled = reasons.is_some();
+            if thresholds.clock_deficit_samples == 0 {
+                continue;
+            }
+            match gpu.clock_deficit_percent() {
+                Some(deficit_percent) if deficit_percent > thresholds.clock_deficit_warning => {
+                    *streak += 1;
+                    if *streak == thresholds.clock_deficit_samples {
+                        alerts.push(AlertType::SustainedThrottling {
+                            gpu_index,
+                            deficit_percent,
+                            samples: *streak,
+                        });
+                    }
+                }
+                _ => *streak = 0,
+            }
+        }
+        alerts
+    }
+}
+impl EccTracker {
+    /// Creates a tracker with no samples recorded
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Records a sample and returns the alerts it raises
+    pub fn observe(&mut self, gpus: &[GpuInfo], thresholds: &GpuThresholds) -> Vec<AlertType> {
+        gpu_identity::realign(&mut self.identities, &mut self.previous, gpus, || None);
+        let mut alerts = Vec::new();
+        for (gpu_index, (gpu, previous)) in gpus.iter().zip(&mut self.previous).enumerate() {
+            let Some(ecc) = gpu.ecc_errors else {
+                continue;
+            };
+            let errors = ecc.double_bit_uncorrected;
+            let rose = previous.map_or(true, |previous| errors > previous);
+            *previous = Some(errors);
+            if let Some(max) = thresholds.ecc_uncorrected_max {
+                if rose && ecc.check_uncorrected(max).is_err() {
+                    alerts.push(AlertType::EccUncorrected {
+                        gpu_index,
+                        errors,
+                        max,
+                    });
+                }
+            }
+        }
+        alerts
+    }
+}
+impl AlertHysteresis {
+    /// Creates a tracker with every alert armed
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Records a sample and returns the alerts to dispatch for it
+    ///
+    /// `alerts` are the threshold alerts of `gpus`, e.g. from the monitor's
+    /// own evaluation. The result holds an [`AlertType::Recovered`] for each
+    /// metric that returned to normal, followed by the `alerts` that pass.
+    /// Alerts that are not tied to a threshold, such as
+    /// [`AlertType::GpuInactive`], always pass.
+    pub fn observe(
+        &mut self,
+        gpus: &[GpuInfo],
+        thresholds: &GpuThresholds,
+        alerts: Vec<AlertType>,
+    ) -> Vec<AlertType> {
+        gpu_identity::realign(
+            &mut self.identities,
+            &mut self.disarmed,
+            gpus,
+            BTreeSet::new,
+        );
+        let margin = thresholds.hysteresis.max(0.0);
+        let mut result = Vec::new();
+        for (gpu_index, (gpu, disarmed)) in gpus.iter().zip(&mut self.disarmed).enumerate() {
+            let gpu = fresh_metrics(gpu, thresholds);
+            let breached: BTreeSet<Metric> = disarmed.iter().map(|level| level.metric()).collect();
+            disarmed.retain(|level| {
+                level
+                    .value(&gpu)
+                    .map_or(true, |value| value >= level.threshold(thresholds) - margin)
+            });
+            for metric in breached {
+                if disarmed.iter().all(|level| level.metric() != metric) {
+                    result.push(AlertType::Recovered {
+                        gpu_index,
+                        metric,
+                        value: AlertLevel::metric_value(metric, &gpu).unwrap_or_default(),
+                    });
+                }
+            }
+        }
+        result.extend(alerts.into_iter().filter(|alert| {
+            let Some((gpu_index, level)) = AlertLevel::of(alert) else {
+                return true;
+            };
+            let Some(disarmed) = self.disarmed.get_mut(gpu_index) else {
+                return true;
+            };
+            let newly_breached = disarmed.insert(level);
+            disarmed.extend(level.warning());
+            newly_breached || margin <= 0.0
+        }));
+        result
+    }
+}
+impl AlertLevel {
+    /// GPU index and level of a threshold alert
+    fn of(alert: &AlertType) -> Option<(usize, AlertLevel)> {
+        match *alert {
+            AlertType::HighTemperature { gpu_index, .. } => {
+                Some((gpu_index, AlertLevel::TemperatureWarning))
+            }
+            AlertType::CriticalTemperature { gpu_index, .. } => {
+                Some((gpu_index, AlertLevel::TemperatureCritical))
+            }
+            AlertType::HighMemoryUsage { gpu_index, .. } => {
+                Some((gpu_index, AlertLevel::MemoryWarning))
+            }
+            AlertType::CriticalMemoryUsage { gpu_index, .. } => {
+                Some((gpu_index, AlertLevel::MemoryCritical))
+            }
+            AlertType::MemoryHigh { gpu_index, .. } => Some((gpu_index, AlertLevel::MemoryHigh)),
+            AlertType::HighPowerUsage { gpu_index, .. } => {
+                Some((gpu_index, AlertLevel::PowerWarning))
+            }
+            AlertType::CriticalPowerUsage { gpu_index, .. } => {
+                Some((gpu_index, AlertLevel::PowerCritical))
+            }
+            AlertType::HighUtilization { gpu_index, .. } => {
+                Some((gpu_index, AlertLevel::Utilization))
+            }
+            _ => None,
+        }
+    }
+    /// Warning level implied by a critical level
+    fn warning(self) -> Option<AlertLevel> {
+        match self {
+            AlertLevel::TemperatureCritical => Some(AlertLevel::TemperatureWarning),
+            AlertLevel::MemoryCritical => Some(AlertLevel::MemoryWarning),
+            AlertLevel::PowerCritical => Some(AlertLevel::PowerWarning),
+            _ => None,
+        }
+    }
+    /// Threshold the level is raised at
+    fn threshold(self, thresholds: &GpuThresholds) -> f32 {
+        match self {
+            AlertLevel::TemperatureWarning => thresholds.temperature_warning,
+            AlertLevel::TemperatureCritical => thresholds.temperature_critical,
+            AlertLevel::MemoryWarning => thresholds.memory_warning,
+            AlertLevel::MemoryCritical => thresholds.memory_critical,
+            AlertLevel::MemoryHigh => thresholds.memory_high,
+            AlertLevel::PowerWarning => thresholds.power_warning,
+            AlertLevel::PowerCritical => thresholds.power_critical,
+            AlertLevel::Utilization => thresholds.utilization_warning,
+        }
+    }
+    /// Metric the level is raised for
+    fn metric(self) -> Metric {
+        match self {
+            AlertLevel::TemperatureWarning | AlertLevel::TemperatureCritical => Metric::Temperature,
+            AlertLevel::MemoryWarning | AlertLevel::MemoryCritical => Metric::MemoryUtil,
+            AlertLevel::MemoryHigh => Metric::MemoryUsed,
+            AlertLevel::PowerWarning | AlertLevel::PowerCritical => Metric::PowerUsage,
+            AlertLevel::Utilization => Metric::Utilization,
+        }
+    }
+    /// Value the level is compared against, as in `collect_alerts`
+    fn value(self, gpu: &GpuInfo) -> Option<f32> {
+        Self::metric_value(self.metric(), gpu)
+    }
+    /// Value of a threshold metric; memory use is in percent
+    fn metric_value(metric: Metric, gpu: &GpuInfo) -> Option<f32> {
+        match metric {
+            Metric::Temperature => gpu.temperature,
+            Metric::MemoryUtil => gpu.memory_util.or_else(|| gpu.memory_used_percent()),
+            Metric::MemoryUsed => gpu.memory_used_percent(),
+            Metric::PowerUsage => gpu.power_usage,
+            Metric::Utilization => gpu.utilization,
+            _ => None,
+        }
+    }
+}
+impl PcieErrorTracker {
+    /// Creates a tracker with no samples recorded
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Records the error count of a GPU read at `at` and returns the alert it raises
+    ///
+    /// A `None` count, e.g. from an unreadable counter, is ignored and keeps
+    /// the previous sample.
+    pub fn observe(
+        &mut self,
+        gpu_index: usize,
+        count: Option<u64>,
+        at: Instant,
+        thresholds: &GpuThresholds,
+    ) -> Option<AlertType> {
+        let count = count?;
+        if self.previous.len() <= gpu_index {
+            self.previous.resize(gpu_index + 1, None);
+        }
+        let (previous, since) = self.previous[gpu_index].replace((count, at))?;
+        let minutes = at.checked_duration_since(since)?.as_secs_f32() / 60.0;
+        if thresholds.pcie_errors_per_minute <= 0.0 || minutes <= 0.0 || count <= previous {
+            return None;
+        }
+        let errors_per_minute = (count - previous) as f32 / minutes;
+        (errors_per_minute > thresholds.pcie_errors_per_minute).then_some(
+            AlertType::PcieErrorsIncreasing {
+                gpu_index,
+                errors_per_minute,
+                total: count,
+            },
+        )
+    }
+    /// Records the error counts of `gpus` read at `at` and returns the alerts raised
+    ///
+    /// `count` reads the error count of a GPU, normally
+    /// [`GpuInfoExtensions::pcie_error_count()`]. Counts follow each GPU's
+    /// [`GpuIdentity`]. Nothing is read while
+    /// [`GpuThresholds::pcie_errors_per_minute`] is 0.
+    pub fn observe_gpus(
+        &mut self,
+        gpus: &[GpuInfo],
+        count: impl Fn(&GpuInfo) -> Option<u64>,
+        at: Instant,
+        thresholds: &GpuThresholds,
+    ) -> Vec<AlertType> {
+        gpu_identity::realign(&mut self.identities, &mut self.previous, gpus, || None);
+        if thresholds.pcie_errors_per_minute <= 0.0 {
+            return Vec::new();
+        }
+        gpus.iter()
+            .enumerate()
+            .filter_map(|(gpu_index, gpu)| self.observe(gpu_index, count(gpu), at, thresholds))
+            .collect()
+    }
+}
+impl MemoryErrorTracker {
+    /// Creates a tracker with no samples recorded
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Records the memory error counts of a GPU and returns the alert they raise
+    ///
+    /// Counts with no known total are ignored.
+    pub fn observe(&mut self, gpu_index: usize, counts: &MemoryErrorCounts) -> Option<AlertType> {
+        let total = counts.total()?;
+        if self.high_water.len() <= gpu_index {
+            self.high_water.resize(gpu_index + 1, None);
+        }
+        let high_water = self.high_water[gpu_index].get_or_insert(total);
+        if total <= *high_water {
+            return None;
+        }
+        let previous = std::mem::replace(high_water, total);
+        Some(AlertType::MemoryPageRetired {
+            gpu_index,
+            total,
+            previous,
+        })
+    }
+    /// Records the memory error counts of `gpus` and returns the alerts raised
+    ///
+    /// `counts` reads the counts of a GPU, normally
+    /// [`GpuInfoExtensions::memory_error_counts()`]. Counts follow each GPU's
+    /// [`GpuIdentity`]. Nothing is read unless
+    /// [`GpuThresholds::memory_error_alerts`] is set.
+    pub fn observe_gpus(
+        &mut self,
+        gpus: &[GpuInfo],
+        counts: impl Fn(&GpuInfo) -> Option<MemoryErrorCounts>,
+        thresholds: &GpuThresholds,
+    ) -> Vec<AlertType> {
+        gpu_identity::realign(&mut self.identities, &mut self.high_water, gpus, || None);
+        if !thresholds.memory_error_alerts {
+            return Vec::new();
+        }
+        gpus.iter()
+            .enumerate()
+            .filter_map(|(gpu_index, gpu)| self.observe(gpu_index, &counts(gpu)?))
+            .collect()
+    }
+    /// Reads GPUs `0..gpu_count` from `source` and returns the alerts raised
+    pub fn observe_source(
+        &mut self,
+        source: &dyn MemoryErrorSource,
+        gpu_count: usize,
+    ) -> Vec<AlertType> {
+        (0..gpu_count)
+            .filter_map(|gpu_index| {
+                let counts = source.memory_errors(u32::try_from(gpu_index).ok()?)?;
+                self.observe(gpu_index, &counts)
+            })
+            .collect()
+    }
+}
+impl AdaptiveIntervalState {
+    /// Creates a state machine starting at the base polling interval
+    pub fn new(base: Duration, config: AdaptiveInterval) -> Self {
+        Self {
+            config,
+            base,
+            current: base,
+            stable_count: 0,
+        }
+    }
+    /// Returns the interval to wait before the next sample
+    pub fn current(&self) -> Duration {
+        self.current
+    }
+    /// Records whether the latest sample changed and returns the next interval
+    ///
+    /// A change resets the interval to the base polling interval. After
+    /// `stable_samples` consecutive unchanged samples the interval is doubled,
+    /// capped at `max_interval`, and counting starts again. Doubling saturates
+    /// at `max_interval` instead of overflowing.
+    pub fn record(&mut self, changed: bool) -> Duration {
+        if changed {
+            self.current = self.base;
+            self.stable_count = 0;
+        } else {
+            self.stable_count += 1;
+            if self.stable_count >= self.config.stable_samples.max(1) {
+                let max_interval = self.config.max_interval.max(self.base);
+                self.current = self
+                    .current
+                    .checked_mul(2)
+                    .map_or(max_interval, |doubled| doubled.min(max_interval));
+                self.stable_count = 0;
+            }
+        }
+        self.current
+    }
+    /// Compares two samples with [`GpuInfo::approx_eq`] and records the result
+    ///
+    /// A different number of GPUs counts as a change.
+    pub fn observe(&mut self, previous: &[GpuInfo], current: &[GpuInfo]) -> Duration {
+        let changed = previous.len() != current.len()
+            || previous
+                .iter()
+                .zip(current)
+                .any(|(old, new)| !new.approx_eq(old, self.config.epsilon));
+        self.record(changed)
+    }
+}
+/// Applies `priority` to the calling thread
+#[cfg(target_os = "windows")]
+pub(crate) fn set_current_thread_priority(priority: ThreadPriority) -> std::io::Result<()> {
+    use windows::Win32::System::Threading::{
+        GetCurrentThread, SetThreadPriority, THREAD_PRIORITY_BELOW_NORMAL, THREAD_PRIORITY_NORMAL,
+    };
+    let level = match priority {
+        ThreadPriority::Low => THREAD_PRIORITY_BELOW_NORMAL,
+        ThreadPriority::Normal => THREAD_PRIORITY_NORMAL,
+    };
+    // SAFETY: GetCurrentThread returns a pseudo-handle that is always valid
+    // for the calling thread and does not need to be closed.
+    unsafe { SetThreadPriority(GetCurrentThread(), level) }
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e.to_string()))
+}
+/// Applies `priority` to the calling thread
+///
+/// On Linux `setpriority` with a thread id only affects that thread.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub(crate) fn set_current_thread_priority(priority: ThreadPriority) -> std::io::Result<()> {
+    /// Nice value used for `ThreadPriority::Low`
+    const LOW_PRIORITY_NICE: libc::c_int = 10;
+    if priority == ThreadPriority::Normal {
+        return Ok(());
+    }
+    // SAFETY: gettid has no preconditions, and setpriority only reads its
+    // integer arguments.
+    let result = unsafe {
+        let tid = libc::gettid();
+        libc::setpriority(libc::PRIO_PROCESS, tid as libc::id_t, LOW_PRIORITY_NICE)
+    };
+    if result == 0 {
+        Ok(())
+    } else {
+        Err(std::io::Error::last_os_error())
+    }
+}
+/// Applies `priority` to the calling thread
+///
+/// These systems apply `setpriority` to the whole process, so `Low` moves
+/// the thread to the lowest priority of its scheduling policy through
+/// `pthread_setschedparam` instead.
+#[cfg(any(
+    target_vendor = "apple",
+    target_os = "freebsd",
+    target_os = "dragonfly",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+pub(crate) fn set_current_thread_priority(priority: ThreadPriority) -> std::io::Result<()> {
+    if priority == ThreadPriority::Normal {
+        return Ok(());
+    }
+    let mut policy = 0;
+    // SAFETY: `sched_param` is a plain C struct for which all zeroes is valid
+    let mut param: libc::sched_param = unsafe { std::mem::zeroed() };
+    // SAFETY: pthread_self has no preconditions, and `policy` and `param`
+    // outlive the calls that read and write them.
+    unsafe {
+        let thread = libc::pthread_self();
+        let result = libc::pthread_getschedparam(thread, &mut policy, &mut param);
+        if result != 0 {
+            return Err(std::io::Error::from_raw_os_error(result));
+        }
+        let lowest = libc::sched_get_priority_min(policy);
+        if lowest == -1 {
+            return Err(std::io::Error::last_os_error());
+        }
+        if param.sched_priority <= lowest {
+            return Ok(());
+        }
+        param.sched_priority = lowest;
+        match libc::pthread_setschedparam(thread, policy, &param) {
+            0 => Ok(()),
+            error => Err(std::io::Error::from_raw_os_error(error)),
+        }
+    }
+}
+/// Applies `priority` to the calling thread
+///
+/// Not supported on this platform, so the default priority is kept.
+#[cfg(not(any(
+    target_os = "windows",
+    target_os = "linux",
+    target_os = "android",
+    target_vendor = "apple",
+    target_os = "freebsd",
+    target_os = "dragonfly",
+    target_os = "netbsd",
+    target_os = "openbsd"
+)))]
+pub(crate) fn set_current_thread_priority(priority: ThreadPriority) -> std::io::Result<()> {
+    if priority != ThreadPriority::Normal {
+        debug!(
+            "Thread priority {:?} is not supported on this platform",
+            priority
+        );
+    }
+    Ok(())
+}
 impl GpuHistory {
     /// Creates a new history for the specified number of GPUs
     pub fn new(gpu_count: usize, max_size: usize) -> Self {
@@ -851,6 +1947,20 @@ impl GpuHistory {
         Self {
             gpu_histories,
             max_size,
+            identities: vec![None; gpu_count],
+        }
+    }
+    /// Adds a sample, moving entries to follow the GPUs by identity
+    ///
+    /// GPUs seen for the first time get a new entry and entries of GPUs
+    /// missing from the sample are dropped.
+    pub(crate) fn record(&mut self, gpus: &[GpuInfo], timestamp: Instant) {
+        let max_size = self.max_size;
+        gpu_identity::realign(&mut self.identities, &mut self.gpu_histories, gpus, || {
+            SingleGpuHistory::new(max_size)
+        });
+        for (gpu_history, gpu) in self.gpu_histories.iter_mut().zip(gpus) {
+            gpu_history.add_measurement(gpu, timestamp);
         }
     }
 }
@@ -889,13 +1999,16 @@ impl SingleGpuHistory {
         }
     }
     /// Returns average temperature for the specified period
+    ///
+    /// A `duration` reaching back before the start of the monotonic clock
+    /// covers the whole history. Never panics.
     pub fn avg_temperature(&self, duration: Duration) -> Option<f32> {
-        let cutoff = Instant::now() - duration;
+        let cutoff = Instant::now().checked_sub(duration);
         let values: Vec<f32> = self
             .timestamps
             .iter()
             .zip(self.temperatures.iter())
-            .filter(|(timestamp, _)| **timestamp >= cutoff)
+            .filter(|(timestamp, _)| cutoff.map_or(true, |cutoff| **timestamp >= cutoff))
             .map(|(_, temp)| *temp)
             .collect();
         if values.is_empty() {
@@ -905,12 +2018,15 @@ impl SingleGpuHistory {
         }
     }
     /// Returns maximum temperature for the specified period
+    ///
+    /// A `duration` reaching back before the start of the monotonic clock
+    /// covers the whole history. Never panics.
     pub fn max_temperature(&self, duration: Duration) -> Option<f32> {
-        let cutoff = Instant::now() - duration;
+        let cutoff = Instant::now().checked_sub(duration);
         self.timestamps
             .iter()
             .zip(self.temperatures.iter())
-            .filter(|(timestamp, _)| **timestamp >= cutoff)
+            .filter(|(timestamp, _)| cutoff.map_or(true, |cutoff| **timestamp >= cutoff))
             .map(|(_, temp)| *temp)
             .max_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
     }
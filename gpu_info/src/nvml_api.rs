@@ -12,16 +12,21 @@
 //!
 //! NVML is supported on Windows and Linux. On macOS, NVIDIA GPUs are not supported.
 
+use crate::extended_info::{ConnectionInfo, MemoryErrorCounts, MemoryErrorSource};
 use crate::ffi_utils::{
     ApiResult, ApiTable, DynamicLibrary, LibraryLoader, NvmlResult, SymbolResolver,
 };
-use crate::gpu_info::GpuInfo;
+use crate::gpu_identity::normalize_bus_id;
+use crate::gpu_info::{Confidence, EccStats, GpuError, GpuInfo, Result, ThrottleReasons};
+use crate::process_usage::{running_processes, GpuProcessInfo};
 use crate::vendor::Vendor;
 #[cfg(unix)]
 use libloading::Symbol;
 use log::error;
-use std::ffi::{c_char, c_uint, CStr};
+use std::collections::BTreeMap;
+use std::ffi::{c_char, c_uint, CStr, CString};
 use std::ptr;
+use std::sync::OnceLock;
 
 /// NVML success return code.
 pub const NVML_SUCCESS: i32 = 0;
@@ -32,6 +37,34 @@ pub const NVML_TEMPERATURE_GPU: i32 = 0;
 /// NVML clock type for graphics (core) clock.
 pub const NVML_CLOCK_GRAPHICS: i32 = 0;
 
+/// NVML return code when a buffer is too small for the result.
+pub const NVML_ERROR_INSUFFICIENT_SIZE: i32 = 7;
+
+/// NVML page retirement causes: multiple single-bit and double-bit ECC errors.
+pub const NVML_PAGE_RETIREMENT_CAUSES: [i32; 2] = [0, 1];
+
+/// NVML memory error type of single-bit errors the memory corrected.
+pub const NVML_MEMORY_ERROR_TYPE_CORRECTED: i32 = 0;
+
+/// NVML memory error type of double-bit errors the memory could not correct.
+pub const NVML_MEMORY_ERROR_TYPE_UNCORRECTED: i32 = 1;
+
+/// NVML ECC counter type reset on each driver reload.
+pub const NVML_VOLATILE_ECC: i32 = 0;
+
+/// NVML ECC counter type kept over the lifetime of the GPU.
+pub const NVML_AGGREGATE_ECC: i32 = 1;
+
+/// NVML field ID of the memory temperature in degrees Celsius.
+pub const NVML_FI_DEV_MEMORY_TEMP: c_uint = 82;
+
+/// Buffer size `nvmlDeviceGetUUID` needs for any UUID, including the nul.
+pub const NVML_DEVICE_UUID_V2_BUFFER_SIZE: usize = 96;
+
+/// NVML marker for a value the driver cannot report, e.g. per-process
+/// memory under WDDM.
+pub const NVML_VALUE_NOT_AVAILABLE: u64 = u64::MAX;
+
 /// NVML device handle (opaque pointer).
 ///
 /// This is an opaque type representing an NVML device handle.
@@ -74,6 +107,90 @@ pub struct nvmlMemory_t {
     pub used: u64,
 }
 
+/// NVML process information structure.
+///
+/// This is a direct mirror of the C `nvmlProcessInfo_v1_t` struct returned by
+/// `nvmlDeviceGetComputeRunningProcesses`.
+#[allow(non_camel_case_types)]
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct nvmlProcessInfo_t {
+    /// Process ID.
+    pub pid: c_uint,
+    /// Device memory used by the process in bytes, or
+    /// [`NVML_VALUE_NOT_AVAILABLE`].
+    pub used_gpu_memory: u64,
+}
+
+/// NVML process information structure, version 3.
+///
+/// This is a direct mirror of the C `nvmlProcessInfo_t` struct returned by
+/// `nvmlDeviceGetComputeRunningProcesses_v3` and
+/// `nvmlDeviceGetGraphicsRunningProcesses_v3`.
+#[allow(non_camel_case_types)]
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct nvmlProcessInfo_v3_t {
+    /// Process ID.
+    pub pid: c_uint,
+    /// Device memory used by the process in bytes, or
+    /// [`NVML_VALUE_NOT_AVAILABLE`].
+    pub used_gpu_memory: u64,
+    /// MIG GPU instance ID, or `0xFFFFFFFF` outside MIG mode.
+    pub gpu_instance_id: c_uint,
+    /// MIG compute instance ID, or `0xFFFFFFFF` outside MIG mode.
+    pub compute_instance_id: c_uint,
+}
+
+/// NVML field value structure.
+///
+/// This is a direct mirror of the C `nvmlFieldValue_t` struct filled by
+/// `nvmlDeviceGetFieldValues`. The `nvmlValue_t` union is kept as its raw
+/// 8 bytes; see [`field_value_as_f32()`].
+#[allow(non_camel_case_types)]
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct nvmlFieldValue_t {
+    /// Field ID to query, e.g. [`NVML_FI_DEV_MEMORY_TEMP`].
+    pub field_id: c_uint,
+    /// Scope of the field, e.g. a link or fan index.
+    pub scope_id: c_uint,
+    /// CPU timestamp of the reading in microseconds.
+    pub timestamp: i64,
+    /// How long the reading took in microseconds.
+    pub latency_usec: i64,
+    /// Type of `value` (`nvmlValueType_t`).
+    pub value_type: c_uint,
+    /// NVML code of this field's query.
+    pub nvml_return: i32,
+    /// Raw bytes of the `nvmlValue_t` union.
+    pub value: u64,
+}
+
+/// NVML PCI information structure.
+///
+/// This is a direct mirror of the C `nvmlPciInfo_t` struct filled by
+/// `nvmlDeviceGetPciInfo_v3`.
+#[allow(non_camel_case_types)]
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct nvmlPciInfo_t {
+    /// Legacy bus ID with a 4-digit domain, NUL-terminated.
+    pub bus_id_legacy: [u8; 16],
+    /// PCI domain.
+    pub domain: c_uint,
+    /// PCI bus.
+    pub bus: c_uint,
+    /// PCI device.
+    pub device: c_uint,
+    /// Combined device and vendor ID.
+    pub pci_device_id: c_uint,
+    /// Combined subsystem and subsystem vendor ID.
+    pub pci_sub_system_id: c_uint,
+    /// Bus ID with an 8-digit domain, e.g. `00000000:01:00.0`, NUL-terminated.
+    pub bus_id: [u8; 32],
+}
+
 /// NVML function pointer types for Windows.
 ///
 /// Contains function pointers to NVML library functions loaded at runtime.
@@ -110,6 +227,69 @@ pub struct NvmlFunctions {
     pub device_get_memory_info: unsafe extern "C" fn(*mut nvmlDevice_st, *mut nvmlMemory_t) -> i32,
     /// nvmlSystemGetDriverVersion - Get driver version string.
     pub system_get_driver_version: unsafe extern "C" fn(*mut c_char, c_uint) -> i32,
+    /// nvmlDeviceGetRetiredPages - Get retired page count per cause (optional).
+    pub device_get_retired_pages:
+        Option<unsafe extern "C" fn(*mut nvmlDevice_st, i32, *mut c_uint, *mut u64) -> i32>,
+    /// nvmlDeviceGetRemappedRows - Get remapped row counts, Ampere+ (optional).
+    pub device_get_remapped_rows: Option<
+        unsafe extern "C" fn(
+            *mut nvmlDevice_st,
+            *mut c_uint,
+            *mut c_uint,
+            *mut c_uint,
+            *mut c_uint,
+        ) -> i32,
+    >,
+    /// nvmlDeviceGetComputeRunningProcesses - Get compute processes (optional).
+    pub device_get_compute_running_processes: Option<
+        unsafe extern "C" fn(*mut nvmlDevice_st, *mut c_uint, *mut nvmlProcessInfo_t) -> i32,
+    >,
+    /// nvmlDeviceGetComputeRunningProcesses_v3 - Get compute processes (optional).
+    pub device_get_compute_running_processes_v3: Option<
+        unsafe extern "C" fn(*mut nvmlDevice_st, *mut c_uint, *mut nvmlProcessInfo_v3_t) -> i32,
+    >,
+    /// nvmlDeviceGetGraphicsRunningProcesses_v3 - Get graphics processes (optional).
+    pub device_get_graphics_running_processes_v3: Option<
+        unsafe extern "C" fn(*mut nvmlDevice_st, *mut c_uint, *mut nvmlProcessInfo_v3_t) -> i32,
+    >,
+    /// nvmlDeviceGetFanSpeed - Get fan speed in percent (optional).
+    pub device_get_fan_speed: Option<unsafe extern "C" fn(*mut nvmlDevice_st, *mut c_uint) -> i32>,
+    /// nvmlDeviceGetEncoderUtilization - Get NVENC utilization (optional).
+    pub device_get_encoder_utilization:
+        Option<unsafe extern "C" fn(*mut nvmlDevice_st, *mut c_uint, *mut c_uint) -> i32>,
+    /// nvmlDeviceGetDecoderUtilization - Get NVDEC utilization (optional).
+    pub device_get_decoder_utilization:
+        Option<unsafe extern "C" fn(*mut nvmlDevice_st, *mut c_uint, *mut c_uint) -> i32>,
+    /// nvmlDeviceGetPciInfo_v3 - Get PCI information (optional).
+    pub device_get_pci_info:
+        Option<unsafe extern "C" fn(*mut nvmlDevice_st, *mut nvmlPciInfo_t) -> i32>,
+    /// nvmlDeviceGetUUID - Get the device UUID string (optional).
+    pub device_get_uuid:
+        Option<unsafe extern "C" fn(*mut nvmlDevice_st, *mut c_char, c_uint) -> i32>,
+    /// nvmlDeviceGetCurrentClocksThrottleReasons - Get clock throttle reasons (optional).
+    pub device_get_current_clocks_throttle_reasons:
+        Option<unsafe extern "C" fn(*mut nvmlDevice_st, *mut u64) -> i32>,
+    /// nvmlDeviceGetTotalEccErrors - Get ECC error counts (optional).
+    pub device_get_total_ecc_errors:
+        Option<unsafe extern "C" fn(*mut nvmlDevice_st, i32, i32, *mut u64) -> i32>,
+    /// nvmlDeviceGetHandleByPciBusId_v2 - Get device handle by PCI bus ID (optional).
+    pub device_get_handle_by_pci_bus_id:
+        Option<unsafe extern "C" fn(*const c_char, *mut *mut nvmlDevice_st) -> i32>,
+    /// nvmlDeviceGetFieldValues - Get sampled field values (optional).
+    pub device_get_field_values:
+        Option<unsafe extern "C" fn(*mut nvmlDevice_st, i32, *mut nvmlFieldValue_t) -> i32>,
+    /// nvmlDeviceGetCurrPcieLinkGeneration - Get the negotiated PCIe generation (optional).
+    pub device_get_curr_pcie_link_generation:
+        Option<unsafe extern "C" fn(*mut nvmlDevice_st, *mut c_uint) -> i32>,
+    /// nvmlDeviceGetCurrPcieLinkWidth - Get the negotiated PCIe width (optional).
+    pub device_get_curr_pcie_link_width:
+        Option<unsafe extern "C" fn(*mut nvmlDevice_st, *mut c_uint) -> i32>,
+    /// nvmlDeviceGetMaxPcieLinkGeneration - Get the maximum PCIe generation (optional).
+    pub device_get_max_pcie_link_generation:
+        Option<unsafe extern "C" fn(*mut nvmlDevice_st, *mut c_uint) -> i32>,
+    /// nvmlDeviceGetMaxPcieLinkWidth - Get the maximum PCIe width (optional).
+    pub device_get_max_pcie_link_width:
+        Option<unsafe extern "C" fn(*mut nvmlDevice_st, *mut c_uint) -> i32>,
 }
 
 /// Unix function pointer types for NVML.
@@ -144,6 +324,83 @@ pub struct NvmlFunctions<'a> {
     /// nvmlDeviceGetMemoryInfo - Get memory information.
     pub device_get_memory_info:
         Symbol<'a, unsafe extern "C" fn(*mut nvmlDevice_st, *mut nvmlMemory_t) -> i32>,
+    /// nvmlDeviceGetRetiredPages - Get retired page count per cause (optional).
+    pub device_get_retired_pages: Option<
+        Symbol<'a, unsafe extern "C" fn(*mut nvmlDevice_st, i32, *mut u32, *mut u64) -> i32>,
+    >,
+    /// nvmlDeviceGetRemappedRows - Get remapped row counts, Ampere+ (optional).
+    #[allow(clippy::type_complexity)]
+    pub device_get_remapped_rows: Option<
+        Symbol<
+            'a,
+            unsafe extern "C" fn(*mut nvmlDevice_st, *mut u32, *mut u32, *mut u32, *mut u32) -> i32,
+        >,
+    >,
+    /// nvmlDeviceGetComputeRunningProcesses - Get compute processes (optional).
+    #[allow(clippy::type_complexity)]
+    pub device_get_compute_running_processes: Option<
+        Symbol<
+            'a,
+            unsafe extern "C" fn(*mut nvmlDevice_st, *mut u32, *mut nvmlProcessInfo_t) -> i32,
+        >,
+    >,
+    /// nvmlDeviceGetComputeRunningProcesses_v3 - Get compute processes (optional).
+    #[allow(clippy::type_complexity)]
+    pub device_get_compute_running_processes_v3: Option<
+        Symbol<
+            'a,
+            unsafe extern "C" fn(*mut nvmlDevice_st, *mut u32, *mut nvmlProcessInfo_v3_t) -> i32,
+        >,
+    >,
+    /// nvmlDeviceGetGraphicsRunningProcesses_v3 - Get graphics processes (optional).
+    #[allow(clippy::type_complexity)]
+    pub device_get_graphics_running_processes_v3: Option<
+        Symbol<
+            'a,
+            unsafe extern "C" fn(*mut nvmlDevice_st, *mut u32, *mut nvmlProcessInfo_v3_t) -> i32,
+        >,
+    >,
+    /// nvmlDeviceGetFanSpeed - Get fan speed in percent (optional).
+    pub device_get_fan_speed:
+        Option<Symbol<'a, unsafe extern "C" fn(*mut nvmlDevice_st, *mut u32) -> i32>>,
+    /// nvmlDeviceGetEncoderUtilization - Get NVENC utilization (optional).
+    pub device_get_encoder_utilization:
+        Option<Symbol<'a, unsafe extern "C" fn(*mut nvmlDevice_st, *mut u32, *mut u32) -> i32>>,
+    /// nvmlDeviceGetDecoderUtilization - Get NVDEC utilization (optional).
+    pub device_get_decoder_utilization:
+        Option<Symbol<'a, unsafe extern "C" fn(*mut nvmlDevice_st, *mut u32, *mut u32) -> i32>>,
+    /// nvmlDeviceGetPciInfo_v3 - Get PCI information (optional).
+    pub device_get_pci_info:
+        Option<Symbol<'a, unsafe extern "C" fn(*mut nvmlDevice_st, *mut nvmlPciInfo_t) -> i32>>,
+    /// nvmlDeviceGetUUID - Get the device UUID string (optional).
+    pub device_get_uuid:
+        Option<Symbol<'a, unsafe extern "C" fn(*mut nvmlDevice_st, *mut c_char, u32) -> i32>>,
+    /// nvmlDeviceGetCurrentClocksThrottleReasons - Get clock throttle reasons (optional).
+    pub device_get_current_clocks_throttle_reasons:
+        Option<Symbol<'a, unsafe extern "C" fn(*mut nvmlDevice_st, *mut u64) -> i32>>,
+    /// nvmlDeviceGetTotalEccErrors - Get ECC error counts (optional).
+    pub device_get_total_ecc_errors:
+        Option<Symbol<'a, unsafe extern "C" fn(*mut nvmlDevice_st, i32, i32, *mut u64) -> i32>>,
+    /// nvmlDeviceGetHandleByPciBusId_v2 - Get device handle by PCI bus ID (optional).
+    pub device_get_handle_by_pci_bus_id:
+        Option<Symbol<'a, unsafe extern "C" fn(*const c_char, *mut *mut nvmlDevice_st) -> i32>>,
+    /// nvmlDeviceGetFieldValues - Get sampled field values (optional).
+    #[allow(clippy::type_complexity)]
+    pub device_get_field_values: Option<
+        Symbol<'a, unsafe extern "C" fn(*mut nvmlDevice_st, i32, *mut nvmlFieldValue_t) -> i32>,
+    >,
+    /// nvmlDeviceGetCurrPcieLinkGeneration - Get the negotiated PCIe generation (optional).
+    pub device_get_curr_pcie_link_generation:
+        Option<Symbol<'a, unsafe extern "C" fn(*mut nvmlDevice_st, *mut u32) -> i32>>,
+    /// nvmlDeviceGetCurrPcieLinkWidth - Get the negotiated PCIe width (optional).
+    pub device_get_curr_pcie_link_width:
+        Option<Symbol<'a, unsafe extern "C" fn(*mut nvmlDevice_st, *mut u32) -> i32>>,
+    /// nvmlDeviceGetMaxPcieLinkGeneration - Get the maximum PCIe generation (optional).
+    pub device_get_max_pcie_link_generation:
+        Option<Symbol<'a, unsafe extern "C" fn(*mut nvmlDevice_st, *mut u32) -> i32>>,
+    /// nvmlDeviceGetMaxPcieLinkWidth - Get the maximum PCIe width (optional).
+    pub device_get_max_pcie_link_width:
+        Option<Symbol<'a, unsafe extern "C" fn(*mut nvmlDevice_st, *mut u32) -> i32>>,
 }
 
 /// NVIDIA Management Library (NVML) client for GPU monitoring.
@@ -216,6 +473,30 @@ impl NvmlClient {
                 .resolve("nvmlDeviceGetPowerManagementLimit")?,
             device_get_memory_info: resolver.resolve("nvmlDeviceGetMemoryInfo")?,
             system_get_driver_version: resolver.resolve("nvmlSystemGetDriverVersion")?,
+            device_get_retired_pages: resolver.resolve("nvmlDeviceGetRetiredPages"),
+            device_get_remapped_rows: resolver.resolve("nvmlDeviceGetRemappedRows"),
+            device_get_compute_running_processes: resolver
+                .resolve("nvmlDeviceGetComputeRunningProcesses"),
+            device_get_compute_running_processes_v3: resolver
+                .resolve("nvmlDeviceGetComputeRunningProcesses_v3"),
+            device_get_graphics_running_processes_v3: resolver
+                .resolve("nvmlDeviceGetGraphicsRunningProcesses_v3"),
+            device_get_fan_speed: resolver.resolve("nvmlDeviceGetFanSpeed"),
+            device_get_encoder_utilization: resolver.resolve("nvmlDeviceGetEncoderUtilization"),
+            device_get_decoder_utilization: resolver.resolve("nvmlDeviceGetDecoderUtilization"),
+            device_get_pci_info: resolver.resolve("nvmlDeviceGetPciInfo_v3"),
+            device_get_uuid: resolver.resolve("nvmlDeviceGetUUID"),
+            device_get_current_clocks_throttle_reasons: resolver
+                .resolve("nvmlDeviceGetCurrentClocksThrottleReasons"),
+            device_get_total_ecc_errors: resolver.resolve("nvmlDeviceGetTotalEccErrors"),
+            device_get_handle_by_pci_bus_id: resolver.resolve("nvmlDeviceGetHandleByPciBusId_v2"),
+            device_get_field_values: resolver.resolve("nvmlDeviceGetFieldValues"),
+            device_get_curr_pcie_link_generation: resolver
+                .resolve("nvmlDeviceGetCurrPcieLinkGeneration"),
+            device_get_curr_pcie_link_width: resolver.resolve("nvmlDeviceGetCurrPcieLinkWidth"),
+            device_get_max_pcie_link_generation: resolver
+                .resolve("nvmlDeviceGetMaxPcieLinkGeneration"),
+            device_get_max_pcie_link_width: resolver.resolve("nvmlDeviceGetMaxPcieLinkWidth"),
         };
         Some(Self {
             _library: library,
@@ -255,6 +536,30 @@ impl NvmlClient {
             device_get_power_usage: resolver.resolve(b"nvmlDeviceGetPowerUsage")?,
             device_get_clock_info: resolver.resolve(b"nvmlDeviceGetClockInfo")?,
             device_get_memory_info: resolver.resolve(b"nvmlDeviceGetMemoryInfo")?,
+            device_get_retired_pages: resolver.resolve(b"nvmlDeviceGetRetiredPages"),
+            device_get_remapped_rows: resolver.resolve(b"nvmlDeviceGetRemappedRows"),
+            device_get_compute_running_processes: resolver
+                .resolve(b"nvmlDeviceGetComputeRunningProcesses"),
+            device_get_compute_running_processes_v3: resolver
+                .resolve(b"nvmlDeviceGetComputeRunningProcesses_v3"),
+            device_get_graphics_running_processes_v3: resolver
+                .resolve(b"nvmlDeviceGetGraphicsRunningProcesses_v3"),
+            device_get_fan_speed: resolver.resolve(b"nvmlDeviceGetFanSpeed"),
+            device_get_encoder_utilization: resolver.resolve(b"nvmlDeviceGetEncoderUtilization"),
+            device_get_decoder_utilization: resolver.resolve(b"nvmlDeviceGetDecoderUtilization"),
+            device_get_pci_info: resolver.resolve(b"nvmlDeviceGetPciInfo_v3"),
+            device_get_uuid: resolver.resolve(b"nvmlDeviceGetUUID"),
+            device_get_current_clocks_throttle_reasons: resolver
+                .resolve(b"nvmlDeviceGetCurrentClocksThrottleReasons"),
+            device_get_total_ecc_errors: resolver.resolve(b"nvmlDeviceGetTotalEccErrors"),
+            device_get_handle_by_pci_bus_id: resolver.resolve(b"nvmlDeviceGetHandleByPciBusId_v2"),
+            device_get_field_values: resolver.resolve(b"nvmlDeviceGetFieldValues"),
+            device_get_curr_pcie_link_generation: resolver
+                .resolve(b"nvmlDeviceGetCurrPcieLinkGeneration"),
+            device_get_curr_pcie_link_width: resolver.resolve(b"nvmlDeviceGetCurrPcieLinkWidth"),
+            device_get_max_pcie_link_generation: resolver
+                .resolve(b"nvmlDeviceGetMaxPcieLinkGeneration"),
+            device_get_max_pcie_link_width: resolver.resolve(b"nvmlDeviceGetMaxPcieLinkWidth"),
         };
 
         // SAFETY: We extend the lifetime of Symbol to 'static.
@@ -311,6 +616,21 @@ impl NvmlClient {
             value: device,
         }
     }
+    /// Get device handle by PCI bus ID, e.g. `0000:01:00.0`
+    ///
+    /// Returns `None` if the driver lacks `nvmlDeviceGetHandleByPciBusId_v2`
+    /// or no GPU is at `bus_id`.
+    pub fn get_device_handle_by_bus_id(&self, bus_id: &str) -> Option<*mut nvmlDevice_st> {
+        let function = self
+            .api_table
+            .functions()
+            .device_get_handle_by_pci_bus_id
+            .as_ref()?;
+        let bus_id = CString::new(bus_id).ok()?;
+        let mut device = ptr::null_mut();
+        let code = unsafe { function(bus_id.as_ptr(), &mut device) };
+        (code == NVML_SUCCESS).then_some(device)
+    }
     /// Get device name
     ///
     /// # Safety
@@ -450,6 +770,284 @@ impl NvmlClient {
             value: (memory.total, memory.free, memory.used),
         }
     }
+    /// Get the number of retired memory pages
+    ///
+    /// Returns `None` if the driver lacks `nvmlDeviceGetRetiredPages` or the
+    /// GPU does not support page retirement.
+    ///
+    /// # Safety
+    /// The caller must ensure that `device` is a valid NVML device handle.
+    pub unsafe fn get_device_retired_pages(&self, device: *mut nvmlDevice_st) -> Option<u64> {
+        let function = self
+            .api_table
+            .functions()
+            .device_get_retired_pages
+            .as_ref()?;
+        retired_pages_from(|cause, count| unsafe {
+            // A null address buffer with count 0 only queries the count
+            function(device, cause, count, ptr::null_mut())
+        })
+    }
+    /// Get the number of remapped rows and whether a remapping is pending
+    ///
+    /// Returns `None` before Ampere or if the driver lacks
+    /// `nvmlDeviceGetRemappedRows`.
+    ///
+    /// # Safety
+    /// The caller must ensure that `device` is a valid NVML device handle.
+    pub unsafe fn get_device_remapped_rows(
+        &self,
+        device: *mut nvmlDevice_st,
+    ) -> Option<(u64, bool)> {
+        let function = self
+            .api_table
+            .functions()
+            .device_get_remapped_rows
+            .as_ref()?;
+        remapped_rows_from(|corrected, uncorrected, pending, failure| unsafe {
+            function(device, corrected, uncorrected, pending, failure)
+        })
+    }
+    /// Get the processes running compute work on the device
+    ///
+    /// Returns `None` if the driver lacks
+    /// `nvmlDeviceGetComputeRunningProcesses` or the query fails.
+    ///
+    /// # Safety
+    /// The caller must ensure that `device` is a valid NVML device handle.
+    pub unsafe fn get_device_compute_processes(
+        &self,
+        device: *mut nvmlDevice_st,
+    ) -> Option<Vec<nvmlProcessInfo_t>> {
+        let function = self
+            .api_table
+            .functions()
+            .device_get_compute_running_processes
+            .as_ref()?;
+        compute_processes_from(|count, infos| unsafe {
+            // A null buffer with count 0 only queries the count
+            let buffer = if infos.is_empty() {
+                ptr::null_mut()
+            } else {
+                infos.as_mut_ptr()
+            };
+            function(device, count, buffer)
+        })
+    }
+    /// Get the processes running compute work, with MIG instance IDs
+    ///
+    /// Returns `None` if the driver lacks
+    /// `nvmlDeviceGetComputeRunningProcesses_v3` or the query fails.
+    ///
+    /// # Safety
+    /// The caller must ensure that `device` is a valid NVML device handle.
+    pub unsafe fn get_device_compute_processes_v3(
+        &self,
+        device: *mut nvmlDevice_st,
+    ) -> Option<Vec<nvmlProcessInfo_v3_t>> {
+        let function = self
+            .api_table
+            .functions()
+            .device_get_compute_running_processes_v3
+            .as_ref()?;
+        running_processes_from(|count, infos| unsafe {
+            let buffer = if infos.is_empty() {
+                ptr::null_mut()
+            } else {
+                infos.as_mut_ptr()
+            };
+            function(device, count, buffer)
+        })
+    }
+    /// Get the processes running graphics work, e.g. games and compositors
+    ///
+    /// Returns `None` if the driver lacks
+    /// `nvmlDeviceGetGraphicsRunningProcesses_v3` or the query fails.
+    ///
+    /// # Safety
+    /// The caller must ensure that `device` is a valid NVML device handle.
+    pub unsafe fn get_device_graphics_processes_v3(
+        &self,
+        device: *mut nvmlDevice_st,
+    ) -> Option<Vec<nvmlProcessInfo_v3_t>> {
+        let function = self
+            .api_table
+            .functions()
+            .device_get_graphics_running_processes_v3
+            .as_ref()?;
+        running_processes_from(|count, infos| unsafe {
+            let buffer = if infos.is_empty() {
+                ptr::null_mut()
+            } else {
+                infos.as_mut_ptr()
+            };
+            function(device, count, buffer)
+        })
+    }
+    /// Get the compute and graphics processes running on the device
+    ///
+    /// Returns `None` if the driver lacks both `_v3` process queries or both
+    /// fail. See [`processes_from()`].
+    ///
+    /// # Safety
+    /// The caller must ensure that `device` is a valid NVML device handle.
+    pub unsafe fn get_device_processes(
+        &self,
+        device: *mut nvmlDevice_st,
+    ) -> Option<Vec<GpuProcessInfo>> {
+        processes_from(
+            self.get_device_compute_processes_v3(device),
+            self.get_device_graphics_processes_v3(device),
+        )
+    }
+    /// Get the fan speed in percent of the maximum
+    ///
+    /// Returns `None` if the driver lacks `nvmlDeviceGetFanSpeed` or the GPU
+    /// has no fan, e.g. a passively cooled data center card.
+    ///
+    /// # Safety
+    /// The caller must ensure that `device` is a valid NVML device handle.
+    pub unsafe fn get_device_fan_speed(&self, device: *mut nvmlDevice_st) -> Option<u32> {
+        let function = self.api_table.functions().device_get_fan_speed.as_ref()?;
+        let mut speed = 0;
+        (function(device, &mut speed) == NVML_SUCCESS).then_some(speed)
+    }
+    /// Get the video encoder (NVENC) utilization in percent
+    ///
+    /// Returns `None` if the driver lacks `nvmlDeviceGetEncoderUtilization`
+    /// or the GPU has no encoder.
+    ///
+    /// # Safety
+    /// The caller must ensure that `device` is a valid NVML device handle.
+    pub unsafe fn get_device_encoder_utilization(&self, device: *mut nvmlDevice_st) -> Option<f32> {
+        let function = self
+            .api_table
+            .functions()
+            .device_get_encoder_utilization
+            .as_ref()?;
+        codec_utilization_from(|utilization, period| unsafe {
+            function(device, utilization, period)
+        })
+    }
+    /// Get the video decoder (NVDEC) utilization in percent
+    ///
+    /// Returns `None` if the driver lacks `nvmlDeviceGetDecoderUtilization`
+    /// or the GPU has no decoder.
+    ///
+    /// # Safety
+    /// The caller must ensure that `device` is a valid NVML device handle.
+    pub unsafe fn get_device_decoder_utilization(&self, device: *mut nvmlDevice_st) -> Option<f32> {
+        let function = self
+            .api_table
+            .functions()
+            .device_get_decoder_utilization
+            .as_ref()?;
+        codec_utilization_from(|utilization, period| unsafe {
+            function(device, utilization, period)
+        })
+    }
+    /// Get the PCI bus ID, e.g. `0000:01:00.0`
+    ///
+    /// Returns `None` if the driver lacks `nvmlDeviceGetPciInfo_v3` or the
+    /// query fails.
+    ///
+    /// # Safety
+    /// The caller must ensure that `device` is a valid NVML device handle.
+    pub unsafe fn get_device_bus_id(&self, device: *mut nvmlDevice_st) -> Option<String> {
+        let function = self.api_table.functions().device_get_pci_info.as_ref()?;
+        pci_bus_id_from(|info| unsafe { function(device, info) })
+    }
+    /// Get the device UUID, e.g. `GPU-5a8f...`
+    ///
+    /// Returns `None` if the driver lacks `nvmlDeviceGetUUID` or the query
+    /// fails.
+    ///
+    /// # Safety
+    /// The caller must ensure that `device` is a valid NVML device handle.
+    pub unsafe fn get_device_uuid(&self, device: *mut nvmlDevice_st) -> Option<String> {
+        let function = self.api_table.functions().device_get_uuid.as_ref()?;
+        uuid_from(|buffer, length| unsafe { function(device, buffer, length) })
+    }
+    /// Get the reasons the clocks are held below their maximum
+    ///
+    /// Returns `None` if the driver lacks
+    /// `nvmlDeviceGetCurrentClocksThrottleReasons` or the query fails.
+    ///
+    /// # Safety
+    /// The caller must ensure that `device` is a valid NVML device handle.
+    pub unsafe fn get_device_throttle_reasons(
+        &self,
+        device: *mut nvmlDevice_st,
+    ) -> Option<ThrottleReasons> {
+        let function = self
+            .api_table
+            .functions()
+            .device_get_current_clocks_throttle_reasons
+            .as_ref()?;
+        throttle_reasons_from(|reasons| unsafe { function(device, reasons) })
+    }
+    /// Get the ECC memory error counts
+    ///
+    /// Returns `None` if the driver lacks `nvmlDeviceGetTotalEccErrors` or
+    /// ECC is not enabled on the GPU.
+    ///
+    /// # Safety
+    /// The caller must ensure that `device` is a valid NVML device handle.
+    pub unsafe fn get_device_ecc_errors(&self, device: *mut nvmlDevice_st) -> Option<EccStats> {
+        let function = self
+            .api_table
+            .functions()
+            .device_get_total_ecc_errors
+            .as_ref()?;
+        ecc_stats_from(|error_type, counter_type, count| unsafe {
+            function(device, error_type, counter_type, count)
+        })
+    }
+    /// Reads the negotiated and maximum PCIe link into `connection`
+    ///
+    /// Values the driver does not report are left unchanged.
+    ///
+    /// # Safety
+    /// The caller must ensure that `device` is a valid NVML device handle.
+    pub unsafe fn fill_device_pcie_link(
+        &self,
+        device: *mut nvmlDevice_st,
+        connection: &mut ConnectionInfo,
+    ) {
+        let functions = self.api_table.functions();
+        macro_rules! read {
+            ($function:ident, $field:ident) => {
+                if let Some(function) = functions.$function.as_ref() {
+                    if let Some(value) =
+                        pcie_link_value_from(|value| unsafe { function(device, value) })
+                    {
+                        connection.$field = Some(value);
+                    }
+                }
+            };
+        }
+        read!(device_get_curr_pcie_link_generation, pcie_generation);
+        read!(device_get_curr_pcie_link_width, pcie_width);
+        read!(device_get_max_pcie_link_generation, pcie_generation_max);
+        read!(device_get_max_pcie_link_width, pcie_width_max);
+    }
+    /// Get the memory temperature in degrees Celsius
+    ///
+    /// Returns `None` if the driver lacks `nvmlDeviceGetFieldValues` or the
+    /// GPU has no memory sensor; NVML reports it mostly for HBM cards.
+    ///
+    /// # Safety
+    /// The caller must ensure that `device` is a valid NVML device handle.
+    pub unsafe fn get_device_memory_temperature(&self, device: *mut nvmlDevice_st) -> Option<f32> {
+        let function = self
+            .api_table
+            .functions()
+            .device_get_field_values
+            .as_ref()?;
+        field_value_from(NVML_FI_DEV_MEMORY_TEMP, |value| unsafe {
+            function(device, 1, value)
+        })
+    }
     /// Create GpuInfo from NVML device
     ///
     /// # Safety
@@ -481,6 +1079,10 @@ impl NvmlClient {
             name_gpu: Some(name),
             vendor: Vendor::Nvidia,
             temperature: Some(temperature),
+            temperatures: sensor_temperatures(
+                Some(temperature),
+                self.get_device_memory_temperature(device),
+            ),
             utilization: Some(gpu_util),
             memory_util: Some(mem_util),
             power_usage: Some(power_usage),
@@ -498,8 +1100,388 @@ impl NvmlClient {
             driver_version: None,
             // Could be added later
             max_clock_speed: None,
+            effective_core_clock: None,
+            fan_speed: self.get_device_fan_speed(device),
+            fan_speed_rpm: None,
+            encoder_util: self.get_device_encoder_utilization(device),
+            decoder_util: self.get_device_decoder_utilization(device),
+            throttle_reasons: self.get_device_throttle_reasons(device),
+            uuid: self.get_device_uuid(device),
+            bus_id: self.get_device_bus_id(device),
+            processes: self.get_device_processes(device),
+            ecc_errors: self.get_device_ecc_errors(device),
+            metric_sources: Default::default(),
+            confidence: Confidence::High,
+            adapter_luid: None,
+        })
+    }
+}
+impl NvmlClient {
+    /// Reads retired pages and remapped rows of `device`
+    ///
+    /// # Safety
+    /// The caller must ensure that `device` is a valid NVML device handle.
+    unsafe fn get_device_memory_errors(
+        &self,
+        device: *mut nvmlDevice_st,
+    ) -> Option<MemoryErrorCounts> {
+        // SAFETY: The caller guarantees `device` is valid for this client
+        let retired_pages = unsafe { self.get_device_retired_pages(device) };
+        let remapped = unsafe { self.get_device_remapped_rows(device) };
+        if retired_pages.is_none() && remapped.is_none() {
+            return None;
+        }
+        Some(MemoryErrorCounts {
+            retired_pages,
+            remapped_rows: remapped.map(|(rows, _)| rows),
+            pending_remapping: remapped.map(|(_, pending)| pending),
+            ..Default::default()
+        })
+    }
+}
+impl MemoryErrorSource for NvmlClient {
+    /// Reads retired pages and remapped rows of an initialized client
+    fn memory_errors(&self, gpu_index: u32) -> Option<MemoryErrorCounts> {
+        let device = self.get_device_handle(gpu_index).to_option()?;
+        // SAFETY: `device` was just returned by NVML for this client
+        unsafe { self.get_device_memory_errors(device) }
+    }
+}
+// SAFETY: The client only holds the loaded library and the function
+// pointers resolved from it, and NVML's API is thread-safe
+unsafe impl Send for NvmlClient {}
+unsafe impl Sync for NvmlClient {}
+/// Initialized NVML client shared by the calls that run for every sample
+///
+/// NVML is loaded and initialized on first use and stays initialized for
+/// the rest of the process, so monitoring does not pay for a cold start
+/// each time. Returns `None`, also on later calls, if NVML is unavailable.
+pub fn shared_client() -> Option<&'static NvmlClient> {
+    static CLIENT: OnceLock<Option<NvmlClient>> = OnceLock::new();
+    CLIENT
+        .get_or_init(|| {
+            let client = NvmlClient::new()?;
+            client.initialize().to_option()?;
+            Some(client)
+        })
+        .as_ref()
+}
+/// Reads the memory error counts of the NVIDIA GPU at PCI `bus_id`
+///
+/// Uses the [`shared_client()`]; returns `None` if NVML is unavailable, the
+/// driver lacks `nvmlDeviceGetHandleByPciBusId_v2`, no GPU is at `bus_id`
+/// or it reports neither retired pages nor remapped rows.
+pub fn memory_errors_by_bus_id(bus_id: &str) -> Option<MemoryErrorCounts> {
+    let client = shared_client()?;
+    let device = client.get_device_handle_by_bus_id(bus_id)?;
+    // SAFETY: `device` was just returned by NVML for this client
+    unsafe { client.get_device_memory_errors(device) }
+}
+/// Reads the PCIe link of the NVIDIA GPU at PCI `bus_id`
+///
+/// Loads and initializes NVML for the call; returns `None` if NVML is
+/// unavailable, the driver lacks `nvmlDeviceGetHandleByPciBusId_v2` or no
+/// GPU is at `bus_id`. Only the link fields of the returned
+/// [`ConnectionInfo`] are set.
+#[cfg(windows)]
+pub fn pcie_link_by_bus_id(bus_id: &str) -> Option<ConnectionInfo> {
+    let client = NvmlClient::new()?;
+    client.initialize().to_option()?;
+    let link = client.get_device_handle_by_bus_id(bus_id).map(|device| {
+        let mut connection = ConnectionInfo::default();
+        // SAFETY: `device` was just returned by NVML for this client
+        unsafe { client.fill_device_pcie_link(device, &mut connection) };
+        connection
+    });
+    client.shutdown();
+    link
+}
+/// Sums the retired pages of every retirement cause
+///
+/// `query` calls `nvmlDeviceGetRetiredPages` for a cause with an empty
+/// buffer and stores the page count. NVML answers such a count-only query
+/// with `NVML_ERROR_INSUFFICIENT_SIZE` when pages exist, so both that code
+/// and success are accepted. Any other code makes the total unknown.
+pub fn retired_pages_from(mut query: impl FnMut(i32, &mut c_uint) -> i32) -> Option<u64> {
+    NVML_PAGE_RETIREMENT_CAUSES
+        .iter()
+        .try_fold(0u64, |total, &cause| {
+            let mut count: c_uint = 0;
+            match query(cause, &mut count) {
+                NVML_SUCCESS | NVML_ERROR_INSUFFICIENT_SIZE => Some(total + u64::from(count)),
+                _ => None,
+            }
         })
+}
+/// Lists the processes of a `nvmlDeviceGetComputeRunningProcesses` call
+///
+/// See [`running_processes_from()`].
+pub fn compute_processes_from(
+    query: impl FnMut(&mut c_uint, &mut [nvmlProcessInfo_t]) -> i32,
+) -> Option<Vec<nvmlProcessInfo_t>> {
+    running_processes_from(query)
+}
+/// Lists the processes of a `nvmlDevice*RunningProcesses*` call
+///
+/// `query` receives the in/out process count and the buffer to fill, and
+/// returns the NVML code. It is first called with an empty buffer to learn
+/// the count, then with a buffer of that size plus room for processes
+/// started in between. Any failure other than the expected
+/// `NVML_ERROR_INSUFFICIENT_SIZE` of the first call yields `None`.
+pub fn running_processes_from<T: Clone + Default>(
+    mut query: impl FnMut(&mut c_uint, &mut [T]) -> i32,
+) -> Option<Vec<T>> {
+    /// Extra slots for processes that start between the two calls
+    const SLACK: usize = 8;
+
+    let mut count: c_uint = 0;
+    match query(&mut count, &mut []) {
+        NVML_SUCCESS => return Some(Vec::new()),
+        NVML_ERROR_INSUFFICIENT_SIZE => {}
+        _ => return None,
+    }
+    let mut infos = vec![T::default(); count as usize + SLACK];
+    count = c_uint::try_from(infos.len()).ok()?;
+    if query(&mut count, &mut infos) != NVML_SUCCESS {
+        return None;
+    }
+    infos.truncate(count as usize);
+    Some(infos)
+}
+/// Combines the compute and graphics processes of a device
+///
+/// Yields `None` if neither list could be read; otherwise a list that could
+/// not be read counts as empty. A process in both lists is reported once as
+/// [`ProcessType::ComputeAndGraphics`](crate::ProcessType::ComputeAndGraphics).
+pub fn processes_from(
+    compute: Option<Vec<nvmlProcessInfo_v3_t>>,
+    graphics: Option<Vec<nvmlProcessInfo_v3_t>>,
+) -> Option<Vec<GpuProcessInfo>> {
+    if compute.is_none() && graphics.is_none() {
+        return None;
+    }
+    Some(running_processes(
+        &compute.unwrap_or_default(),
+        &graphics.unwrap_or_default(),
+    ))
+}
+/// Interprets a `nvmlDeviceGetRemappedRows` call
+///
+/// `query` receives the corrected, uncorrected, pending and failure
+/// outputs and returns the NVML code. Yields the total of remapped rows
+/// and whether a remapping waits for a GPU reset.
+pub fn remapped_rows_from(
+    query: impl FnOnce(&mut c_uint, &mut c_uint, &mut c_uint, &mut c_uint) -> i32,
+) -> Option<(u64, bool)> {
+    let (mut corrected, mut uncorrected, mut pending, mut failure) = (0, 0, 0, 0);
+    if query(&mut corrected, &mut uncorrected, &mut pending, &mut failure) != NVML_SUCCESS {
+        return None;
+    }
+    Some((u64::from(corrected) + u64::from(uncorrected), pending != 0))
+}
+/// Interprets a `nvmlDeviceGetPciInfo_v3` call
+///
+/// `query` fills the PCI information and returns the NVML code. Yields the
+/// bus ID with NVML's 8-digit domain shortened to the usual 4 digits.
+pub fn pci_bus_id_from(query: impl FnOnce(&mut nvmlPciInfo_t) -> i32) -> Option<String> {
+    let mut info = nvmlPciInfo_t::default();
+    if query(&mut info) != NVML_SUCCESS {
+        return None;
+    }
+    let bus_id = CStr::from_bytes_until_nul(&info.bus_id)
+        .ok()?
+        .to_str()
+        .ok()?;
+    normalize_bus_id(bus_id)
+}
+/// Interprets a `nvmlDeviceGetCurrentClocksThrottleReasons` call
+///
+/// `query` fills the reason bitmask and returns the NVML code.
+pub fn throttle_reasons_from(query: impl FnOnce(&mut u64) -> i32) -> Option<ThrottleReasons> {
+    let mut reasons = 0u64;
+    (query(&mut reasons) == NVML_SUCCESS).then(|| ThrottleReasons::from_bits(reasons))
+}
+/// Interprets the `nvmlDeviceGetTotalEccErrors` calls of a GPU
+///
+/// `query` fills the count of an error type and counter type and returns
+/// the NVML code. The aggregate counters are preferred since they survive
+/// driver reloads; the volatile ones are the fallback. GPUs without ECC
+/// enabled return `NVML_ERROR_NOT_SUPPORTED` for both.
+pub fn ecc_stats_from(mut query: impl FnMut(i32, i32, &mut u64) -> i32) -> Option<EccStats> {
+    [NVML_AGGREGATE_ECC, NVML_VOLATILE_ECC]
+        .into_iter()
+        .find_map(|counter_type| {
+            let mut read = |error_type| {
+                let mut count = 0;
+                (query(error_type, counter_type, &mut count) == NVML_SUCCESS).then_some(count)
+            };
+            Some(EccStats {
+                single_bit_corrected: read(NVML_MEMORY_ERROR_TYPE_CORRECTED)?,
+                double_bit_uncorrected: read(NVML_MEMORY_ERROR_TYPE_UNCORRECTED)?,
+                aggregate: counter_type == NVML_AGGREGATE_ECC,
+            })
+        })
+}
+/// Interprets a `nvmlDevice{Curr,Max}PcieLink{Generation,Width}` call
+///
+/// `query` fills the value and returns the NVML code. Yields the generation
+/// or lane count, or `None` on failure or for a zero value.
+pub fn pcie_link_value_from(query: impl FnOnce(&mut c_uint) -> i32) -> Option<u8> {
+    let mut value: c_uint = 0;
+    if query(&mut value) != NVML_SUCCESS {
+        return None;
+    }
+    u8::try_from(value).ok().filter(|&value| value > 0)
+}
+/// Interprets a `nvmlDeviceGetUUID` call
+///
+/// `query` fills the buffer it is given, of the given length, and returns
+/// the NVML code. Yields the UUID, or `None` if it is empty.
+pub fn uuid_from(query: impl FnOnce(*mut c_char, c_uint) -> i32) -> Option<String> {
+    let mut buffer = [0u8; NVML_DEVICE_UUID_V2_BUFFER_SIZE];
+    if query(buffer.as_mut_ptr() as *mut c_char, buffer.len() as c_uint) != NVML_SUCCESS {
+        return None;
+    }
+    let uuid = CStr::from_bytes_until_nul(&buffer)
+        .ok()?
+        .to_str()
+        .ok()?
+        .trim();
+    (!uuid.is_empty()).then(|| uuid.to_string())
+}
+/// Interprets a `nvmlDeviceGetEncoderUtilization` or
+/// `nvmlDeviceGetDecoderUtilization` call
+///
+/// `query` fills the utilization and the sampling period in microseconds and
+/// returns the NVML code. Yields the utilization in percent; GPUs without
+/// the engine return `NVML_ERROR_NOT_SUPPORTED`.
+pub fn codec_utilization_from(query: impl FnOnce(&mut c_uint, &mut c_uint) -> i32) -> Option<f32> {
+    let mut utilization = 0;
+    let mut sampling_period_us = 0;
+    if query(&mut utilization, &mut sampling_period_us) != NVML_SUCCESS {
+        return None;
+    }
+    Some(utilization as f32)
+}
+/// Interprets a `nvmlDeviceGetFieldValues` call for one field
+///
+/// `query` fills the value of `field_id` and returns the NVML code. Yields
+/// the value if both the call and the field query succeeded.
+pub fn field_value_from(
+    field_id: c_uint,
+    query: impl FnOnce(&mut nvmlFieldValue_t) -> i32,
+) -> Option<f32> {
+    let mut value = nvmlFieldValue_t {
+        field_id,
+        ..Default::default()
+    };
+    if query(&mut value) != NVML_SUCCESS || value.nvml_return != NVML_SUCCESS {
+        return None;
+    }
+    field_value_as_f32(&value)
+}
+/// Reads the `nvmlValue_t` union of `value` according to its type
+///
+/// The union members all start at its first byte, so narrower types are
+/// read from the leading bytes in native order.
+pub fn field_value_as_f32(value: &nvmlFieldValue_t) -> Option<f32> {
+    let bytes = value.value.to_ne_bytes();
+    let low = [bytes[0], bytes[1], bytes[2], bytes[3]];
+    match value.value_type {
+        // double
+        0 => Some(f64::from_bits(value.value) as f32),
+        // unsigned int
+        1 => Some(u32::from_ne_bytes(low) as f32),
+        // unsigned long, 32 bits on Windows
+        2 if cfg!(windows) => Some(u32::from_ne_bytes(low) as f32),
+        // unsigned long, unsigned long long
+        2 | 3 => Some(value.value as f32),
+        // signed long long
+        4 => Some(value.value as i64 as f32),
+        // signed int
+        5 => Some(i32::from_ne_bytes(low) as f32),
+        _ => None,
+    }
+}
+/// Builds [`GpuInfo::temperatures`] from the NVML core and memory sensors
+///
+/// Returns `None` if neither sensor was read.
+pub fn sensor_temperatures(
+    core: Option<f32>,
+    memory: Option<f32>,
+) -> Option<BTreeMap<String, f32>> {
+    let sensors: BTreeMap<String, f32> = [("core", core), ("memory", memory)]
+        .into_iter()
+        .filter_map(|(sensor, value)| Some((sensor.to_string(), value?)))
+        .collect();
+    (!sensors.is_empty()).then_some(sensors)
+}
+/// Reads the NVIDIA GPU at PCI `bus_id`
+///
+/// Returns `None` if NVML is unavailable, the driver lacks
+/// `nvmlDeviceGetHandleByPciBusId_v2` or no GPU is at `bus_id`.
+pub fn get_nvidia_gpu_by_bus_id(bus_id: &str) -> Option<GpuInfo> {
+    let client = NvmlClient::new()?;
+    client.initialize().to_option()?;
+    let gpu = client
+        .get_device_handle_by_bus_id(bus_id)
+        // SAFETY: `device` was just returned by NVML for this client
+        .and_then(|device| unsafe { client.create_gpu_info(device) });
+    client.shutdown();
+    gpu.map(|mut gpu| {
+        if gpu.bus_id.is_none() {
+            gpu.bus_id = normalize_bus_id(bus_id);
+        }
+        gpu
+    })
+}
+/// Lists the processes running on the NVIDIA GPU described by `gpu`
+///
+/// The device is found by the PCI bus ID of `gpu`, or else by its name.
+/// Compute and graphics processes are combined; see
+/// [`NvidiaProvider::get_running_processes()`](crate::providers::nvidia::NvidiaProvider::get_running_processes).
+///
+/// # Errors
+///
+/// - [`GpuError::DriverNotInstalled`] if NVML cannot be loaded or initialized
+/// - [`GpuError::GpuNotFound`] if no NVML device matches `gpu`
+/// - [`GpuError::Unsupported`] if the driver lacks both `_v3` process queries
+pub fn get_nvidia_running_processes(gpu: &GpuInfo) -> Result<Vec<GpuProcessInfo>> {
+    let client = NvmlClient::new().ok_or(GpuError::DriverNotInstalled)?;
+    client
+        .initialize()
+        .to_option()
+        .ok_or(GpuError::DriverNotInstalled)?;
+    let result = find_device(&client, gpu)
+        .ok_or(GpuError::GpuNotFound)
+        .and_then(|device| {
+            // SAFETY: `device` was just returned by NVML for this client
+            unsafe { client.get_device_processes(device) }.ok_or_else(|| {
+                GpuError::Unsupported("NVML lacks nvmlDevice*RunningProcesses_v3".to_string())
+            })
+        });
+    client.shutdown();
+    result
+}
+/// The device of `gpu`: by PCI bus ID, else the first device with its name
+fn find_device(client: &NvmlClient, gpu: &GpuInfo) -> Option<*mut nvmlDevice_st> {
+    /// Upper bound on the device indices probed
+    const MAX_DEVICES: u32 = 64;
+
+    if let Some(device) = gpu
+        .bus_id
+        .as_deref()
+        .and_then(|bus_id| client.get_device_handle_by_bus_id(bus_id))
+    {
+        return Some(device);
     }
+    (0..MAX_DEVICES)
+        .map_while(|index| client.get_device_handle(index).to_option())
+        .find(|&device| {
+            // SAFETY: `device` was just returned by NVML for this client
+            let name = unsafe { client.get_device_name(device) }.to_option();
+            gpu.name_gpu.is_none() || name == gpu.name_gpu
+        })
 }
 /// Convenience function to get all NVIDIA GPUs using the new abstraction
 pub fn get_nvidia_gpus() -> Vec<GpuInfo> {
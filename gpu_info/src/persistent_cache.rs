@@ -0,0 +1,299 @@
+//! On-disk cache of GPU identity for fast cold starts.
+//!
+//! Full detection can take seconds on some platforms (WMI and DriverStore
+//! scans on Windows), even though the installed hardware rarely changes. With
+//! the `persistent-cache` feature, the static identity of each detected GPU
+//! (vendor, name, UUID, PCI bus ID, total memory, driver version) is written
+//! to a versioned JSON file in the platform cache directory:
+//!
+//! - Linux: `$XDG_CACHE_HOME/gpu_info` (usually `~/.cache/gpu_info`)
+//! - macOS: `~/Library/Caches/gpu_info`
+//! - Windows: `%LOCALAPPDATA%\gpu_info\cache`
+//!
+//! [`GpuManager::with_persistent_cache()`] loads this file to return identity
+//! data immediately, while a background detection validates it. The file is
+//! rewritten whenever the driver version or set of devices changes.
+//!
+//! Missing, corrupt, stale, or incompatible files are ignored silently and
+//! fall back to regular detection. Placeholder entries for undetected GPUs
+//! (vendor [`Vendor::Unknown`]) are never written.
+//!
+//! [`GpuManager::with_persistent_cache()`]: crate::GpuManager::with_persistent_cache
+use crate::gpu_info::{GpuInfo, Result};
+use crate::vendor::Vendor;
+use log::debug;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Version of the on-disk format. Files with another version are ignored.
+pub const CACHE_FORMAT_VERSION: u32 = 2;
+
+/// Name of the cache file inside the cache directory.
+pub const CACHE_FILE_NAME: &str = "gpu_identity.json";
+
+/// Cache files older than this are considered stale.
+pub const DEFAULT_MAX_AGE: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// Static identity of a single GPU.
+///
+/// Holds what is needed to skip detection and tell if the hardware or
+/// driver changed. For a key to per-GPU state, use
+/// [`GpuIdentity`](crate::GpuIdentity) instead.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct CachedGpu {
+    /// GPU vendor
+    pub vendor: Vendor,
+    /// Full GPU name
+    pub name: Option<String>,
+    /// Device UUID
+    pub uuid: Option<String>,
+    /// PCI bus address
+    pub bus_id: Option<String>,
+    /// Total memory in MB
+    pub memory_total: Option<u32>,
+    /// Driver version
+    pub driver_version: Option<String>,
+    /// When this GPU was first detected, in ms since the Unix epoch
+    pub detected_at_ms: u64,
+}
+
+impl CachedGpu {
+    /// Extracts the identity fields of `gpu`, detected at `detected_at_ms`.
+    pub fn from_gpu(gpu: &GpuInfo, detected_at_ms: u64) -> Self {
+        Self {
+            vendor: gpu.vendor,
+            name: gpu.name_gpu.clone(),
+            uuid: gpu.uuid.clone(),
+            bus_id: gpu.bus_id.clone(),
+            memory_total: gpu.memory_total,
+            driver_version: gpu.driver_version.clone(),
+            detected_at_ms,
+        }
+    }
+
+    /// Builds a `GpuInfo` with only the identity fields set.
+    pub fn to_gpu_info(&self) -> GpuInfo {
+        let mut gpu = GpuInfo::write_vendor(self.vendor);
+        gpu.name_gpu = self.name.clone();
+        gpu.uuid = self.uuid.clone();
+        gpu.bus_id = self.bus_id.clone();
+        gpu.memory_total = self.memory_total;
+        gpu.driver_version = self.driver_version.clone();
+        gpu
+    }
+
+    /// Returns `true` if `gpu` is the same device with the same driver.
+    ///
+    /// The detection timestamp and runtime metrics are ignored.
+    pub fn describes(&self, gpu: &GpuInfo) -> bool {
+        self.vendor == gpu.vendor
+            && self.name == gpu.name_gpu
+            && self.uuid == gpu.uuid
+            && self.bus_id == gpu.bus_id
+            && self.memory_total == gpu.memory_total
+            && self.driver_version == gpu.driver_version
+    }
+}
+
+/// Returns the GPUs worth caching, skipping unknown-vendor placeholders.
+fn cacheable(gpus: &[GpuInfo]) -> impl Iterator<Item = &GpuInfo> {
+    gpus.iter().filter(|gpu| gpu.vendor != Vendor::Unknown)
+}
+
+/// Contents of a cache file.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PersistedDetection {
+    /// Format version, see [`CACHE_FORMAT_VERSION`]
+    pub version: u32,
+    /// When this device set was first detected, in ms since the Unix epoch
+    pub detected_at_ms: u64,
+    /// When detection last confirmed this device set, in ms since the Unix epoch
+    pub validated_at_ms: u64,
+    /// Identity of each GPU, in detection order
+    pub gpus: Vec<CachedGpu>,
+}
+
+impl PersistedDetection {
+    /// Returns `true` if `gpus` has the same devices and driver versions.
+    ///
+    /// Runtime metrics and unknown-vendor placeholders are ignored; only the
+    /// identity fields are compared.
+    pub fn matches(&self, gpus: &[GpuInfo]) -> bool {
+        self.gpus.len() == cacheable(gpus).count()
+            && self
+                .gpus
+                .iter()
+                .zip(cacheable(gpus))
+                .all(|(cached, gpu)| cached.describes(gpu))
+    }
+
+    /// Returns the cached GPUs with only their identity fields set.
+    pub fn to_gpu_infos(&self) -> Vec<GpuInfo> {
+        self.gpus.iter().map(CachedGpu::to_gpu_info).collect()
+    }
+}
+
+/// Location and policy of the persistent identity cache.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PersistentCache {
+    /// Path of the cache file
+    path: PathBuf,
+    /// Maximum age before the file is considered stale
+    max_age: Duration,
+}
+
+impl PersistentCache {
+    /// Creates a cache stored in `dir`.
+    pub fn in_dir(dir: impl AsRef<Path>) -> Self {
+        Self {
+            path: dir.as_ref().join(CACHE_FILE_NAME),
+            max_age: DEFAULT_MAX_AGE,
+        }
+    }
+
+    /// Creates a cache in the platform cache directory.
+    ///
+    /// Returns `None` if no home directory can be determined.
+    pub fn default_location() -> Option<Self> {
+        directories::ProjectDirs::from("", "", "gpu_info")
+            .map(|dirs| Self::in_dir(dirs.cache_dir()))
+    }
+
+    /// Sets the age after which a cache file is ignored.
+    pub fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = max_age;
+        self
+    }
+
+    /// Returns the path of the cache file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Loads the cache file.
+    ///
+    /// Returns `None` if the file is missing, unreadable, corrupt, written by
+    /// another format version, or older than the maximum age.
+    pub fn load(&self) -> Option<PersistedDetection> {
+        self.load_at(crate::jsonl::system_clock_ms())
+    }
+
+    pub(crate) fn load_at(&self, now_ms: u64) -> Option<PersistedDetection> {
+        let content = fs::read_to_string(&self.path).ok()?;
+        let persisted: PersistedDetection = match serde_json::from_str(&content) {
+            Ok(persisted) => persisted,
+            Err(e) => {
+                debug!("Ignoring corrupt GPU cache {}: {}", self.path.display(), e);
+                return None;
+            }
+        };
+        if persisted.version != CACHE_FORMAT_VERSION {
+            debug!(
+                "Ignoring GPU cache with format version {}",
+                persisted.version
+            );
+            return None;
+        }
+        let age = Duration::from_millis(now_ms.saturating_sub(persisted.validated_at_ms));
+        if age > self.max_age {
+            debug!("Ignoring stale GPU cache ({:?} old)", age);
+            return None;
+        }
+        Some(persisted)
+    }
+
+    /// Writes the identity of `gpus` to the cache file.
+    ///
+    /// GPUs already present in the existing file keep their original
+    /// detection timestamp, as does the file itself if the device set is
+    /// unchanged. Unknown-vendor placeholders are skipped. The file is
+    /// replaced atomically through a temporary file unique to this write.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GpuError::Io`](crate::GpuError::Io) if the directory or file
+    /// cannot be written.
+    pub fn store(&self, gpus: &[GpuInfo]) -> Result<()> {
+        self.store_at(gpus, crate::jsonl::system_clock_ms())
+    }
+
+    pub(crate) fn store_at(&self, gpus: &[GpuInfo], now_ms: u64) -> Result<()> {
+        let previous = self.load_at(now_ms);
+        let first_seen = |gpu: &GpuInfo| {
+            previous
+                .iter()
+                .flat_map(|previous| &previous.gpus)
+                .find(|cached| cached.describes(gpu))
+                .map_or(now_ms, |cached| cached.detected_at_ms)
+        };
+        let persisted = PersistedDetection {
+            version: CACHE_FORMAT_VERSION,
+            detected_at_ms: previous
+                .as_ref()
+                .filter(|previous| previous.matches(gpus))
+                .map_or(now_ms, |previous| previous.detected_at_ms),
+            validated_at_ms: now_ms,
+            gpus: cacheable(gpus)
+                .map(|gpu| CachedGpu::from_gpu(gpu, first_seen(gpu)))
+                .collect(),
+        };
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let tmp = self.temp_path();
+        let written = serde_json::to_vec(&persisted)
+            .map_err(std::io::Error::from)
+            .and_then(|content| fs::write(&tmp, content))
+            .and_then(|()| fs::rename(&tmp, &self.path));
+        if written.is_err() {
+            let _ = fs::remove_file(&tmp);
+        }
+        Ok(written?)
+    }
+
+    /// Returns a temporary path next to the cache file that no other
+    /// process or concurrent write in this process uses.
+    fn temp_path(&self) -> PathBuf {
+        static NEXT: AtomicU64 = AtomicU64::new(0);
+        let mut name = self.path.file_name().unwrap_or_default().to_os_string();
+        name.push(format!(
+            ".{}.{}.tmp",
+            std::process::id(),
+            NEXT.fetch_add(1, Ordering::Relaxed)
+        ));
+        self.path.with_file_name(name)
+    }
+
+    /// Checks freshly detected `gpus` against the cache file.
+    ///
+    /// Returns `true` if the cache was valid. Otherwise the file is rewritten
+    /// with the new identity, or removed if nothing but unknown-vendor
+    /// placeholders was detected.
+    pub fn validate(&self, gpus: &[GpuInfo]) -> bool {
+        let valid = self.load().is_some_and(|cached| cached.matches(gpus));
+        let result = if cacheable(gpus).next().is_none() {
+            self.clear()
+        } else {
+            self.store(gpus)
+        };
+        if let Err(e) = result {
+            debug!("Failed to update GPU cache {}: {}", self.path.display(), e);
+        }
+        valid
+    }
+
+    /// Removes the cache file.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GpuError::Io`](crate::GpuError::Io) if the file exists but
+    /// cannot be removed.
+    pub fn clear(&self) -> Result<()> {
+        match fs::remove_file(&self.path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,627 @@
+//! GPU usage attribution for the calling process.
+//!
+//! [`self_usage()`] reports how much GPU memory the current process has
+//! allocated and, where the platform exposes it, which share of each GPU it
+//! keeps busy, as opposed to the system-wide numbers of [`GpuInfo`].
+//!
+//! # Backends
+//!
+//! - **Linux**: NVIDIA through `nvmlDeviceGetComputeRunningProcesses` (with
+//!   the `vendor-nvidia` feature), other drivers through the DRM entries of
+//!   `/proc/self/fdinfo`
+//! - **Windows**: the PDH `GPU Engine(pid_<pid>_*)` and
+//!   `GPU Process Memory(pid_<pid>_*)` counters, which cover every vendor
+//! - **macOS and other platforms**: not supported
+//!
+//! [`GpuProcessInfo`] lists every process using a GPU instead; see
+//! [`GpuInfo::running_processes()`] and
+//! [`NvidiaProvider::get_running_processes()`].
+//!
+//! [`GpuInfo`]: crate::GpuInfo
+//! [`GpuInfo::running_processes()`]: crate::GpuInfo::running_processes
+//! [`NvidiaProvider::get_running_processes()`]: crate::providers::nvidia::NvidiaProvider::get_running_processes
+
+use crate::gpu_info::Result;
+use std::collections::BTreeMap;
+#[cfg(target_os = "linux")]
+use std::fs;
+#[cfg(target_os = "linux")]
+use std::path::Path;
+use std::time::Duration;
+
+/// Time between the two samples from which engine utilization is computed
+pub const SELF_USAGE_SAMPLE_INTERVAL: Duration = Duration::from_millis(500);
+
+/// GPU usage of one process.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProcessGpuUsage {
+    /// Process the usage belongs to
+    pub pid: u32,
+    /// Usage per GPU the process has work on; empty if it uses none
+    pub gpus: Vec<GpuProcessShare>,
+}
+
+impl ProcessGpuUsage {
+    /// Memory allocated by the process on every GPU together, in bytes
+    ///
+    /// GPUs whose per-process memory is unknown are not counted.
+    pub fn total_memory_bytes(&self) -> u64 {
+        self.gpus
+            .iter()
+            .filter_map(|gpu| gpu.memory_bytes)
+            .fold(0, u64::saturating_add)
+    }
+}
+
+/// Usage of one GPU by one process.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GpuProcessShare {
+    /// The GPU as the backend identifies it
+    ///
+    /// `nvml:<index>` for NVML devices, the PCI address
+    /// (`0000:03:00.0`) for DRM devices and the adapter LUID
+    /// (`luid_0x00000000_0x0000C2E5_phys_0`) on Windows.
+    pub device: String,
+    /// Memory allocated by the process on this GPU, in bytes
+    pub memory_bytes: Option<u64>,
+    /// Approximate share of the GPU the process keeps busy (0-100%)
+    ///
+    /// Computed over [`SELF_USAGE_SAMPLE_INTERVAL`]. `None` for NVML, which
+    /// only reports per-process memory.
+    pub utilization: Option<f32>,
+}
+
+/// Kind of work a process runs on a GPU.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum ProcessType {
+    /// CUDA, OpenCL or other compute work
+    Compute,
+    /// Rendering, e.g. a game or the desktop compositor
+    Graphics,
+    /// Both compute and graphics work
+    ComputeAndGraphics,
+    /// The backend does not tell the kind of work apart, e.g. the DRM
+    /// clients list or `powermetrics`
+    Unknown,
+}
+
+impl std::fmt::Display for ProcessType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Compute => "compute",
+            Self::Graphics => "graphics",
+            Self::ComputeAndGraphics => "compute+graphics",
+            Self::Unknown => "unknown",
+        })
+    }
+}
+
+/// A process running work on a GPU.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GpuProcessInfo {
+    /// Process ID
+    pub pid: u32,
+    /// GPU memory used by the process in MB; 0 if the driver does not
+    /// report it, e.g. under WDDM
+    pub memory_used_mb: u64,
+    /// Kind of work the process runs
+    pub process_type: ProcessType,
+    /// Name of the process as reported by the backend, if it reports one
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub process_name: Option<String>,
+}
+
+impl GpuProcessInfo {
+    /// Returns the name of the process, e.g. `python3`
+    ///
+    /// The name reported by the backend if any, else read from
+    /// `/proc/<pid>/comm` on Linux. `None` on other platforms or if the
+    /// process has exited.
+    pub fn name(&self) -> Option<String> {
+        if self.process_name.is_some() {
+            return self.process_name.clone();
+        }
+        #[cfg(target_os = "linux")]
+        {
+            fs::read_to_string(Path::new("/proc").join(self.pid.to_string()).join("comm"))
+                .ok()
+                .map(|name| name.trim_end().to_string())
+                .filter(|name| !name.is_empty())
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            None
+        }
+    }
+}
+
+/// Combines the compute and graphics processes NVML lists for a device
+///
+/// A process listed more than once in one list, e.g. per MIG instance, has
+/// its memory summed. A process in both lists is reported once as
+/// [`ProcessType::ComputeAndGraphics`] with the larger of the two amounts,
+/// since both report the same allocations. Sorted by PID.
+#[cfg(feature = "vendor-nvidia")]
+pub(crate) fn running_processes(
+    compute: &[crate::nvml_api::nvmlProcessInfo_v3_t],
+    graphics: &[crate::nvml_api::nvmlProcessInfo_v3_t],
+) -> Vec<GpuProcessInfo> {
+    /// Memory per PID of one list, in bytes
+    fn memory_by_pid(list: &[crate::nvml_api::nvmlProcessInfo_v3_t]) -> BTreeMap<u32, u64> {
+        let mut memory = BTreeMap::new();
+        for process in list {
+            let bytes = match process.used_gpu_memory {
+                crate::nvml_api::NVML_VALUE_NOT_AVAILABLE => 0,
+                bytes => bytes,
+            };
+            let total: &mut u64 = memory.entry(process.pid).or_default();
+            *total = total.saturating_add(bytes);
+        }
+        memory
+    }
+
+    let compute = memory_by_pid(compute);
+    let mut graphics = memory_by_pid(graphics);
+    let mut entries: Vec<(u32, u64, ProcessType)> = compute
+        .into_iter()
+        .map(|(pid, bytes)| match graphics.remove(&pid) {
+            Some(other) => (pid, bytes.max(other), ProcessType::ComputeAndGraphics),
+            None => (pid, bytes, ProcessType::Compute),
+        })
+        .collect();
+    entries.extend(
+        graphics
+            .into_iter()
+            .map(|(pid, bytes)| (pid, bytes, ProcessType::Graphics)),
+    );
+    entries.sort_by_key(|&(pid, _, _)| pid);
+    entries
+        .into_iter()
+        .map(|(pid, bytes, process_type)| GpuProcessInfo {
+            pid,
+            memory_used_mb: bytes / (1024 * 1024),
+            process_type,
+            process_name: None,
+        })
+        .collect()
+}
+
+/// Reports the GPU usage of the calling process.
+///
+/// Samples engine activity twice, so the call blocks for
+/// [`SELF_USAGE_SAMPLE_INTERVAL`] when the process has GPU work to measure.
+///
+/// # Errors
+///
+/// Returns [`GpuError::Unsupported`] on platforms that do not attribute GPU
+/// usage to processes, including macOS.
+///
+/// # Examples
+///
+/// ```no_run
+/// let usage = gpu_info::self_usage()?;
+/// for gpu in &usage.gpus {
+///     println!("{}: {:?} bytes, {:?}%", gpu.device, gpu.memory_bytes, gpu.utilization);
+/// }
+/// # Ok::<(), gpu_info::GpuError>(())
+/// ```
+///
+/// [`GpuError::Unsupported`]: crate::GpuError::Unsupported
+pub fn self_usage() -> Result<ProcessGpuUsage> {
+    process_usage(std::process::id())
+}
+
+#[cfg(target_os = "linux")]
+fn process_usage(pid: u32) -> Result<ProcessGpuUsage> {
+    #[cfg(feature = "vendor-nvidia")]
+    let mut gpus = nvml_shares(pid);
+    #[cfg(not(feature = "vendor-nvidia"))]
+    let mut gpus = Vec::new();
+    let proc_root = Path::new("/proc");
+    let before = read_drm_clients(proc_root, pid);
+    if !before.is_empty() {
+        let start = std::time::Instant::now();
+        std::thread::sleep(SELF_USAGE_SAMPLE_INTERVAL);
+        let after = read_drm_clients(proc_root, pid);
+        gpus.extend(drm_shares(&before, &after, start.elapsed()));
+    }
+    Ok(ProcessGpuUsage { pid, gpus })
+}
+
+#[cfg(target_os = "windows")]
+fn process_usage(pid: u32) -> Result<ProcessGpuUsage> {
+    use crate::providers::windows::pdh;
+
+    let query = pdh::open_query()?;
+    let add = |path: String| -> Vec<(String, pdh::PdhCounter)> {
+        pdh::expand_wildcard_path(&path)
+            .into_iter()
+            .filter_map(|path| pdh::add_counter(query, &path).map(|counter| (path, counter)))
+            .collect()
+    };
+    let engines = add(format!(
+        "\\GPU Engine(pid_{}_*)\\Utilization Percentage",
+        pid
+    ));
+    let memory = add(format!(
+        "\\GPU Process Memory(pid_{}_*)\\Dedicated Usage",
+        pid
+    ));
+    // Rates need two collections
+    let collected = pdh::collect_query_data(query).and_then(|()| {
+        std::thread::sleep(Duration::from_millis(pdh::PDH_COLLECTION_INTERVAL_MS));
+        pdh::collect_query_data(query)
+    });
+    let read = |counters: Vec<(String, pdh::PdhCounter)>| -> Vec<(String, f64)> {
+        counters
+            .into_iter()
+            .filter_map(|(path, counter)| {
+                pdh::get_counter_value(counter)
+                    .ok()
+                    .map(|value| (path, value))
+            })
+            .collect()
+    };
+    let result = collected.map(|()| ProcessGpuUsage {
+        pid,
+        gpus: pdh_shares(pid, &read(engines), &read(memory)),
+    });
+    pdh::close_query(query);
+    result
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "windows")))]
+fn process_usage(_pid: u32) -> Result<ProcessGpuUsage> {
+    Err(crate::gpu_info::GpuError::Unsupported(
+        "per-process GPU usage is not available on this platform".to_string(),
+    ))
+}
+
+/// Reads the memory the process has allocated on each NVML device
+#[cfg(all(target_os = "linux", feature = "vendor-nvidia"))]
+fn nvml_shares(pid: u32) -> Vec<GpuProcessShare> {
+    use crate::ffi_utils::ApiResult;
+    use crate::nvml_api::NvmlClient;
+
+    /// Upper bound on the device indices probed
+    const MAX_DEVICES: u32 = 64;
+
+    let client = match NvmlClient::new() {
+        Some(client) => client,
+        None => return Vec::new(),
+    };
+    if client.initialize().to_option().is_none() {
+        return Vec::new();
+    }
+    let shares = (0..MAX_DEVICES)
+        .map_while(|index| Some((index, client.get_device_handle(index).to_option()?)))
+        .filter_map(|(index, device)| {
+            // SAFETY: `device` was just returned by NVML for this client
+            let processes = unsafe { client.get_device_compute_processes(device) }?;
+            nvml_share(format!("nvml:{}", index), &processes, pid)
+        })
+        .collect();
+    client.shutdown();
+    shares
+}
+
+/// Builds the share of `pid` from the processes NVML lists for a device
+///
+/// Returns `None` if the process is not listed. A process can be listed
+/// more than once, e.g. per MIG instance; its entries are summed.
+#[cfg(feature = "vendor-nvidia")]
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+pub(crate) fn nvml_share(
+    device: String,
+    processes: &[crate::nvml_api::nvmlProcessInfo_t],
+    pid: u32,
+) -> Option<GpuProcessShare> {
+    let mut entries = processes
+        .iter()
+        .filter(|process| process.pid == pid)
+        .peekable();
+    entries.peek()?;
+    let memory_bytes = entries
+        .map(|process| {
+            (process.used_gpu_memory != crate::nvml_api::NVML_VALUE_NOT_AVAILABLE)
+                .then_some(process.used_gpu_memory)
+        })
+        .sum();
+    Some(GpuProcessShare {
+        device,
+        memory_bytes,
+        utilization: None,
+    })
+}
+
+/// Extracts the process ID and adapter from a PDH GPU counter path
+///
+/// Instances look like
+/// `pid_1234_luid_0x00000000_0x0000C2E5_phys_0_eng_0_engtype_3D` for
+/// `GPU Engine` and `pid_1234_luid_0x00000000_0x0000C2E5_phys_0` for
+/// `GPU Process Memory`. The adapter is the part from `luid_` up to the
+/// engine suffix, so both counters map to the same adapter.
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+pub(crate) fn parse_pdh_instance(path: &str) -> Option<(u32, &str)> {
+    let start = path.find('(')? + 1;
+    let end = start + path[start..].find(')')?;
+    let instance = path[start..end].strip_prefix("pid_")?;
+    let (pid, adapter) = instance.split_once('_')?;
+    let pid = pid.parse().ok()?;
+    if !adapter.starts_with("luid_") {
+        return None;
+    }
+    let adapter = adapter
+        .find("_eng_")
+        .map_or(adapter, |engine| &adapter[..engine]);
+    Some((pid, adapter))
+}
+
+/// Groups PDH counter values of `pid` by adapter
+///
+/// `engines` holds `Utilization Percentage` values, which are summed per
+/// adapter and capped at 100%. `memory` holds `Dedicated Usage` values in
+/// bytes. Counters of other processes are ignored.
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+pub(crate) fn pdh_shares(
+    pid: u32,
+    engines: &[(String, f64)],
+    memory: &[(String, f64)],
+) -> Vec<GpuProcessShare> {
+    let mut adapters: BTreeMap<&str, (Option<f64>, Option<f64>)> = BTreeMap::new();
+    for (path, value) in engines {
+        if let Some((_, adapter)) = parse_pdh_instance(path).filter(|&(id, _)| id == pid) {
+            let utilization = &mut adapters.entry(adapter).or_default().0;
+            *utilization = Some(utilization.unwrap_or(0.0) + value.max(0.0));
+        }
+    }
+    for (path, value) in memory {
+        if let Some((_, adapter)) = parse_pdh_instance(path).filter(|&(id, _)| id == pid) {
+            let bytes = &mut adapters.entry(adapter).or_default().1;
+            *bytes = Some(bytes.unwrap_or(0.0) + value.max(0.0));
+        }
+    }
+    adapters
+        .into_iter()
+        .map(|(adapter, (utilization, memory))| GpuProcessShare {
+            device: adapter.to_string(),
+            memory_bytes: memory.map(|bytes| bytes as u64),
+            utilization: utilization.map(|percent| percent.min(100.0) as f32),
+        })
+        .collect()
+}
+
+/// DRM client statistics from one `fdinfo` entry
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+#[derive(Debug, Clone, PartialEq, Default)]
+pub(crate) struct DrmClient {
+    /// PCI address of the device (`drm-pdev`)
+    pub pdev: String,
+    /// Client ID, shared by file descriptors of the same client
+    pub client_id: u64,
+    /// Memory over every region, in bytes
+    pub memory_bytes: Option<u64>,
+    /// Busy time per engine, in nanoseconds
+    pub engines: BTreeMap<String, u64>,
+    /// Number of engines of each class, where the driver reports it
+    pub capacities: BTreeMap<String, u64>,
+}
+
+/// Parses a DRM `fdinfo` file
+///
+/// Returns `None` for file descriptors that are not DRM clients. Memory is
+/// taken from the `drm-total-<region>` keys, or the older
+/// `drm-memory-<region>` keys if the driver has no totals.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+pub(crate) fn parse_drm_fdinfo(content: &str) -> Option<DrmClient> {
+    let mut client = DrmClient::default();
+    let (mut pdev, mut client_id) = (None, None);
+    let (mut total, mut legacy) = (None::<u64>, None::<u64>);
+    for line in content.lines() {
+        let (key, value) = match line.split_once(':') {
+            Some((key, value)) => (key.trim(), value.trim()),
+            None => continue,
+        };
+        if key == "drm-pdev" {
+            pdev = Some(value.to_string());
+        } else if key == "drm-client-id" {
+            client_id = value.parse().ok();
+        } else if let Some(class) = key.strip_prefix("drm-engine-capacity-") {
+            if let Ok(capacity) = value.parse() {
+                client.capacities.insert(class.to_string(), capacity);
+            }
+        } else if let Some(class) = key.strip_prefix("drm-engine-") {
+            if let Some(nanos) = value.strip_suffix("ns").and_then(|n| n.trim().parse().ok()) {
+                client.engines.insert(class.to_string(), nanos);
+            }
+        } else if key.starts_with("drm-total-") {
+            if let Some(bytes) = parse_drm_size(value) {
+                total = Some(total.unwrap_or(0).saturating_add(bytes));
+            }
+        } else if key.starts_with("drm-memory-") {
+            if let Some(bytes) = parse_drm_size(value) {
+                legacy = Some(legacy.unwrap_or(0).saturating_add(bytes));
+            }
+        }
+    }
+    client.pdev = pdev?;
+    client.client_id = client_id?;
+    client.memory_bytes = total.or(legacy);
+    Some(client)
+}
+
+/// Parses a DRM memory size such as `4096`, `256 KiB` or `12 MiB`
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_drm_size(value: &str) -> Option<u64> {
+    let (number, unit) = value.split_once(' ').unwrap_or((value, ""));
+    let multiplier = match unit.trim() {
+        "" | "B" => 1,
+        "KiB" => 1024,
+        "MiB" => 1024 * 1024,
+        "GiB" => 1024 * 1024 * 1024,
+        _ => return None,
+    };
+    number.parse::<u64>().ok()?.checked_mul(multiplier)
+}
+
+/// Width of the right-aligned command column of a DRM `clients` file
+const DRM_CLIENTS_COMMAND_WIDTH: usize = 20;
+
+/// Parses the `clients` file of a DRM device in debugfs
+///
+/// The file lists one line per open file descriptor under a header, e.g.
+/// `Xorg  1234  0  y  y  0  0`: command, PID, minor, master, authenticated,
+/// UID and magic, followed by a client name on newer kernels. The command
+/// is right-aligned in a fixed 20-column field and may contain spaces, as
+/// in Firefox's `Isolated Web Co`, so it is cut by width rather than split
+/// on whitespace. A process with several file descriptors is reported
+/// once. Sorted by PID.
+#[cfg_attr(
+    not(all(target_os = "linux", feature = "vendor-amd")),
+    allow(dead_code)
+)]
+pub(crate) fn parse_drm_clients_list(content: &str) -> Vec<GpuProcessInfo> {
+    let mut processes: BTreeMap<u32, String> = BTreeMap::new();
+    for line in content.lines().skip(1) {
+        let (Some(command), Some(rest)) = (
+            line.get(..DRM_CLIENTS_COMMAND_WIDTH),
+            line.get(DRM_CLIENTS_COMMAND_WIDTH..),
+        ) else {
+            continue;
+        };
+        let command = command.trim();
+        let pid = rest
+            .split_whitespace()
+            .next()
+            .and_then(|pid| pid.parse().ok());
+        match pid {
+            Some(pid) if !command.is_empty() => {
+                processes.entry(pid).or_insert_with(|| command.to_string());
+            }
+            _ => continue,
+        }
+    }
+    processes
+        .into_iter()
+        .map(|(pid, command)| GpuProcessInfo {
+            pid,
+            memory_used_mb: 0,
+            process_type: ProcessType::Unknown,
+            process_name: Some(command),
+        })
+        .collect()
+}
+
+/// Parses the running tasks of `powermetrics --samplers tasks --show-process-gpu`
+///
+/// The task table has a header starting with `Name` and `ID` and ending with
+/// a `GPU ms/s` column. Tasks with GPU time in the sample are reported;
+/// aggregate rows such as `ALL_TASKS` have a negative ID and are skipped.
+/// Sorted by PID.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+pub(crate) fn parse_powermetrics_tasks(output: &str) -> Vec<GpuProcessInfo> {
+    let mut lines = output.lines();
+    let header = lines
+        .by_ref()
+        .find(|line| line.starts_with("Name") && line.trim_end().ends_with("GPU ms/s"));
+    let id_column = match header.and_then(|header| header.find(" ID ")) {
+        Some(space) => space + 1,
+        None => return Vec::new(),
+    };
+    let mut processes: Vec<GpuProcessInfo> = lines
+        .take_while(|line| !line.trim().is_empty())
+        .filter_map(|line| {
+            let name = line.get(..id_column)?.trim();
+            let pid = line[id_column..].split_whitespace().next()?.parse().ok()?;
+            let gpu_ms: f32 = line.split_whitespace().last()?.parse().ok()?;
+            (gpu_ms > 0.0 && !name.is_empty()).then(|| GpuProcessInfo {
+                pid,
+                memory_used_mb: 0,
+                process_type: ProcessType::Unknown,
+                process_name: Some(name.to_string()),
+            })
+        })
+        .collect();
+    processes.sort_by_key(|process| process.pid);
+    processes
+}
+
+/// Reads the DRM clients of `pid` from `<proc_root>/<pid>/fdinfo`
+///
+/// File descriptors of the same client are reported once.
+#[cfg(target_os = "linux")]
+pub(crate) fn read_drm_clients(proc_root: &Path, pid: u32) -> Vec<DrmClient> {
+    let entries = match fs::read_dir(proc_root.join(pid.to_string()).join("fdinfo")) {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+    let mut clients: BTreeMap<(String, u64), DrmClient> = BTreeMap::new();
+    for entry in entries.flatten() {
+        if let Some(client) = fs::read_to_string(entry.path())
+            .ok()
+            .and_then(|content| parse_drm_fdinfo(&content))
+        {
+            clients.insert((client.pdev.clone(), client.client_id), client);
+        }
+    }
+    clients.into_values().collect()
+}
+
+/// Groups two samples of DRM clients by device
+///
+/// Memory comes from `after`. Utilization is the busiest engine class's
+/// share of `elapsed`, from the busy time the clients present in both
+/// samples accumulated; it is `None` if no client was sampled twice.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+pub(crate) fn drm_shares(
+    before: &[DrmClient],
+    after: &[DrmClient],
+    elapsed: Duration,
+) -> Vec<GpuProcessShare> {
+    /// Totals of the clients of one device
+    #[derive(Default)]
+    struct Device<'a> {
+        memory_bytes: Option<u64>,
+        busy: BTreeMap<&'a str, u64>,
+        sampled: bool,
+    }
+
+    let mut devices: BTreeMap<&str, Device> = BTreeMap::new();
+    for client in after {
+        let device = devices.entry(&client.pdev).or_default();
+        if let Some(bytes) = client.memory_bytes {
+            device.memory_bytes = Some(device.memory_bytes.unwrap_or(0).saturating_add(bytes));
+        }
+        let previous = match before
+            .iter()
+            .find(|old| old.pdev == client.pdev && old.client_id == client.client_id)
+        {
+            Some(previous) => previous,
+            None => continue,
+        };
+        device.sampled = true;
+        for (class, nanos) in &client.engines {
+            let delta = nanos.saturating_sub(previous.engines.get(class).copied().unwrap_or(0));
+            let capacity = client.capacities.get(class).copied().unwrap_or(1).max(1);
+            let busy = device.busy.entry(class).or_default();
+            *busy = busy.saturating_add(delta / capacity);
+        }
+    }
+    let elapsed = elapsed.as_nanos() as f64;
+    devices
+        .into_iter()
+        .map(|(pdev, device)| GpuProcessShare {
+            device: pdev.to_string(),
+            memory_bytes: device.memory_bytes,
+            utilization: (device.sampled && elapsed > 0.0).then(|| {
+                let busiest = device.busy.values().copied().max().unwrap_or(0);
+                (busiest as f64 / elapsed * 100.0).min(100.0) as f32
+            }),
+        })
+        .collect()
+}
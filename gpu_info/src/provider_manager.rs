@@ -3,18 +3,23 @@
 //! This module provides a centralized manager for all GPU providers,
 //! allowing unified detection and updating of GPUs from different vendors.
 use crate::gpu_info::{GpuInfo, GpuProvider, Result};
+use crate::metric_source::Metric;
 use crate::vendor::Vendor;
 use log::{error, info, warn};
-use std::collections::HashMap;
+use std::collections::BTreeMap;
+use std::fmt;
 /// Manager for all GPU providers
+///
+/// Providers are kept in [`Vendor`] order, so detection results are always
+/// returned in the same order.
 pub struct GpuProviderManager {
-    providers: HashMap<Vendor, Box<dyn GpuProvider>>,
+    providers: BTreeMap<Vendor, Box<dyn GpuProvider>>,
 }
 impl GpuProviderManager {
     /// Create a new provider manager
     pub fn new() -> Self {
         Self {
-            providers: HashMap::new(),
+            providers: BTreeMap::new(),
         }
     }
     /// Register a provider for a specific vendor
@@ -39,17 +44,35 @@ impl GpuProviderManager {
     }
     /// Update a specific GPU using the appropriate provider
     pub fn update_gpu(&self, gpu: &mut GpuInfo) -> Result<()> {
-        for (vendor, provider) in &self.providers {
-            let is_match = match (vendor, &gpu.vendor) {
-                (Vendor::Intel(_), Vendor::Intel(_)) => true,
-                _ => std::mem::discriminant(vendor) == std::mem::discriminant(&gpu.vendor),
-            };
-            if is_match {
-                return provider.update_gpu(gpu);
+        match self.provider_for(&gpu.vendor) {
+            Some(provider) => provider.update_gpu(gpu),
+            None => {
+                warn!("No provider registered for vendor: {:?}", gpu.vendor);
+                Err(crate::gpu_info::GpuError::GpuNotActive)
+            }
+        }
+    }
+    /// Update only `metrics` of a GPU using the appropriate provider
+    ///
+    /// See [`GpuProvider::update_metrics()`].
+    pub fn update_metrics(&self, gpu: &mut GpuInfo, metrics: &[Metric]) -> Result<()> {
+        match self.provider_for(&gpu.vendor) {
+            Some(provider) => provider.update_metrics(gpu, metrics),
+            None => {
+                warn!("No provider registered for vendor: {:?}", gpu.vendor);
+                Err(crate::gpu_info::GpuError::GpuNotActive)
             }
         }
-        warn!("No provider registered for vendor: {:?}", gpu.vendor);
-        Err(crate::gpu_info::GpuError::GpuNotActive)
+    }
+    /// The provider that updates GPUs of `vendor`; any Intel provider matches any Intel GPU
+    pub(crate) fn provider_for(&self, vendor: &Vendor) -> Option<&dyn GpuProvider> {
+        self.providers
+            .iter()
+            .find(|(registered, _)| match (registered, vendor) {
+                (Vendor::Intel(_), Vendor::Intel(_)) => true,
+                _ => std::mem::discriminant(*registered) == std::mem::discriminant(vendor),
+            })
+            .map(|(_, provider)| provider.as_ref())
     }
     /// Get all registered vendors
     pub fn get_registered_vendors(&self) -> Vec<Vendor> {
@@ -59,6 +82,19 @@ impl GpuProviderManager {
     pub fn is_vendor_supported(&self, vendor: &Vendor) -> bool {
         self.providers.contains_key(vendor)
     }
+    /// Registered providers with their vendors, in vendor order
+    pub(crate) fn providers(&self) -> impl Iterator<Item = (Vendor, &dyn GpuProvider)> {
+        self.providers
+            .iter()
+            .map(|(vendor, provider)| (*vendor, provider.as_ref()))
+    }
+}
+impl fmt::Debug for GpuProviderManager {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GpuProviderManager")
+            .field("vendors", &self.get_registered_vendors())
+            .finish()
+    }
 }
 impl Default for GpuProviderManager {
     fn default() -> Self {
@@ -13,8 +13,18 @@
 
 use crate::adl_api;
 use crate::gpu_info::{GpuInfo, GpuProvider, Result};
+use crate::metric_source::Metric;
 use crate::vendor::Vendor;
 
+/// Metrics ADL reports
+const SUPPORTED_METRICS: &[Metric] = &[
+    Metric::Temperature,
+    Metric::Utilization,
+    Metric::CoreClock,
+    Metric::MemoryClock,
+    Metric::MemoryTotal,
+];
+
 /// AMD GPU provider.
 ///
 /// Implements `GpuProvider` for AMD GPUs using the ADL (AMD Display Library) API.
@@ -51,6 +61,10 @@ impl GpuProvider for AmdProvider {
     fn get_vendor(&self) -> Vendor {
         Vendor::Amd
     }
+    /// Whether `metric` is one this provider reports
+    fn supports_metric(&self, metric: Metric) -> bool {
+        SUPPORTED_METRICS.contains(&metric)
+    }
 }
 
 /// Detect all AMD GPUs in the system.
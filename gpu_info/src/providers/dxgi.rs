@@ -0,0 +1,133 @@
+//! DXGI adapter LUIDs for Windows GPU identification.
+//!
+//! Backends without a device UUID, such as WMI and the Intel MD API, cannot
+//! tell identical adapters apart. DXGI assigns every adapter a locally
+//! unique identifier (LUID), which names the adapter in other Windows APIs
+//! but is assigned anew on every boot. It is therefore stored in
+//! [`GpuInfo::adapter_luid`](crate::GpuInfo::adapter_luid), never as the
+//! UUID, and adapters are matched to GPUs by PCI location.
+//!
+//! The formatting and matching are platform-independent so they can be
+//! tested on any host.
+#![cfg_attr(not(target_os = "windows"), allow(dead_code))]
+
+use crate::gpu_identity::normalize_bus_id;
+
+/// One adapter listed by DXGI
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct DxgiAdapter {
+    /// PCI vendor ID
+    pub vendor_id: u16,
+    /// LUID formatted by [`format_luid`]
+    pub luid: String,
+    /// PCI location formatted by [`format_bus_id`], if the kernel reported it
+    pub bus_id: Option<String>,
+}
+
+/// Formats an adapter LUID as an identifier, e.g. `LUID-00000000-0000d1a3`
+pub(crate) fn format_luid(high_part: i32, low_part: u32) -> String {
+    format!("LUID-{:08x}-{:08x}", high_part as u32, low_part)
+}
+
+/// Formats a PCI location as a bus ID, e.g. `0000:03:00.0`
+///
+/// Windows does not report the PCI domain, which is 0 on desktop systems.
+pub(crate) fn format_bus_id(bus: u32, device: u32, function: u32) -> String {
+    format!("0000:{:02x}:{:02x}.{:x}", bus, device, function)
+}
+
+/// The adapter of `vendor_id` at PCI location `bus_id`
+///
+/// Without a bus ID the adapter is only returned if it is the vendor's
+/// only one, since identical adapters cannot be told apart otherwise.
+pub(crate) fn find_adapter<'a>(
+    adapters: &'a [DxgiAdapter],
+    vendor_id: u16,
+    bus_id: Option<&str>,
+) -> Option<&'a DxgiAdapter> {
+    let mut candidates = adapters.iter().filter(|a| a.vendor_id == vendor_id);
+    match bus_id.and_then(normalize_bus_id) {
+        Some(bus_id) => candidates.find(|a| {
+            a.bus_id.as_deref().and_then(normalize_bus_id).as_deref() == Some(bus_id.as_str())
+        }),
+        None => {
+            let first = candidates.next()?;
+            candidates.next().is_none().then_some(first)
+        }
+    }
+}
+
+/// Every adapter DXGI lists, with its PCI location
+///
+/// The location is queried from the kernel-mode driver through the adapter
+/// LUID. Returns an empty list if DXGI is unavailable.
+#[cfg(target_os = "windows")]
+pub(crate) fn adapters() -> Vec<DxgiAdapter> {
+    use windows::Win32::Graphics::Dxgi::{CreateDXGIFactory1, IDXGIFactory1, DXGI_ADAPTER_DESC1};
+
+    let mut adapters = Vec::new();
+    // SAFETY: The factory and adapters are reference-counted COM objects
+    // released on drop, and `GetDesc1` only writes to `desc`
+    unsafe {
+        let Ok(factory) = CreateDXGIFactory1::<IDXGIFactory1>() else {
+            return adapters;
+        };
+        let mut index = 0;
+        while let Ok(adapter) = factory.EnumAdapters1(index) {
+            let mut desc = DXGI_ADAPTER_DESC1::default();
+            if adapter.GetDesc1(&mut desc).is_ok() {
+                if let Ok(vendor_id) = u16::try_from(desc.VendorId) {
+                    let luid = desc.AdapterLuid;
+                    adapters.push(DxgiAdapter {
+                        vendor_id,
+                        luid: format_luid(luid.HighPart, luid.LowPart),
+                        bus_id: pci_location(luid),
+                    });
+                }
+            }
+            index += 1;
+        }
+    }
+    adapters
+}
+
+/// PCI location of the adapter with `luid`, from the kernel-mode driver
+#[cfg(target_os = "windows")]
+fn pci_location(luid: windows::Win32::Foundation::LUID) -> Option<String> {
+    use windows::Wdk::Graphics::Direct3D::{
+        D3DKMTCloseAdapter, D3DKMTOpenAdapterFromLuid, D3DKMTQueryAdapterInfo,
+        D3DKMT_ADAPTERADDRESS, D3DKMT_CLOSEADAPTER, D3DKMT_OPENADAPTERFROMLUID,
+        D3DKMT_QUERYADAPTERINFO, KMTQAITYPE_ADAPTERADDRESS,
+    };
+
+    let mut open = D3DKMT_OPENADAPTERFROMLUID {
+        AdapterLuid: luid,
+        ..Default::default()
+    };
+    // SAFETY: Each call only reads and writes the structures passed to it,
+    // and `address` outlives the query that fills it. The adapter handle is
+    // closed before returning.
+    unsafe {
+        if D3DKMTOpenAdapterFromLuid(&mut open).is_err() {
+            return None;
+        }
+        let mut address = D3DKMT_ADAPTERADDRESS::default();
+        let mut query = D3DKMT_QUERYADAPTERINFO {
+            hAdapter: open.hAdapter,
+            Type: KMTQAITYPE_ADAPTERADDRESS,
+            pPrivateDriverData: &mut address as *mut _ as *mut core::ffi::c_void,
+            PrivateDriverDataSize: std::mem::size_of::<D3DKMT_ADAPTERADDRESS>() as u32,
+        };
+        let queried = D3DKMTQueryAdapterInfo(&mut query).is_ok();
+        let _ = D3DKMTCloseAdapter(&D3DKMT_CLOSEADAPTER {
+            hAdapter: open.hAdapter,
+        });
+        queried.then(|| {
+            format_bus_id(
+                address.BusNumber,
+                address.DeviceNumber,
+                address.FunctionNumber,
+            )
+        })
+    }
+}
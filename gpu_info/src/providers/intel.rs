@@ -16,11 +16,22 @@
 //! [`GpuProvider`]: crate::gpu_info::GpuProvider
 //! [`linux::IntelLinuxProvider`]: crate::providers::linux::intel::IntelLinuxProvider
 
-use crate::gpu_info::{GpuInfo, GpuProvider, Result};
+use crate::cancel::{self, CancelToken};
+use crate::detection_report::DetectionReport;
+use crate::gpu_info::{Confidence, GpuInfo, GpuProvider, Result};
+use crate::metric_source::Metric;
 use crate::vendor::{IntelGpuType, Vendor};
 #[allow(unused_imports)]
 use log::{debug, error, info, warn};
+use std::io;
 use std::process::Command;
+use std::time::Duration;
+
+/// Maximum time a WMI query through PowerShell may take
+const WMI_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Metrics `Win32_VideoController` reports
+const SUPPORTED_METRICS: &[Metric] = &[Metric::MemoryTotal];
 
 /// Intel GPU provider.
 ///
@@ -102,23 +113,28 @@ impl IntelProvider {
             })
     }
 
-    fn get_intel_gpu_info(&self) -> Result<String> {
-        let output = Command::new("powershell")
-            .args([
-                "Get-WmiObject",
-                "Win32_VideoController",
-                "|",
-                "Where-Object",
-                "{ $_.Name -like '*Intel*' }",
-                "|",
-                "Select-Object",
-                "Name, AdapterRAM, DriverVersion, Status",
-                "|",
-                "Format-List",
-            ])
-            .output()
-            .map_err(|e| {
-                error!("Failed to execute PowerShell command: {}", e);
+    /// Queries Win32_VideoController, killing PowerShell if `token` is cancelled
+    fn get_intel_gpu_info(&self, token: &CancelToken) -> Result<String> {
+        let mut command = Command::new("powershell");
+        command.args([
+            "Get-WmiObject",
+            "Win32_VideoController",
+            "|",
+            "Where-Object",
+            "{ $_.Name -like '*Intel*' }",
+            "|",
+            "Select-Object",
+            "Name, AdapterRAM, DriverVersion, Status",
+            "|",
+            "Format-List",
+        ]);
+        let output =
+            cancel::output_with_cancel(&mut command, Some(WMI_TIMEOUT), token).map_err(|e| {
+                if e.kind() == io::ErrorKind::Interrupted {
+                    debug!("PowerShell WMI query cancelled");
+                } else {
+                    error!("Failed to execute PowerShell command: {}", e);
+                }
                 crate::gpu_info::GpuError::DriverNotInstalled
             })?;
         Ok(String::from_utf8_lossy(&output.stdout).into_owned())
@@ -164,8 +180,23 @@ impl IntelProvider {
             core_clock: None,
             // Will be set by Intel MD API
             max_clock_speed: None,
+            effective_core_clock: None,
+            fan_speed: None,
+            fan_speed_rpm: None,
+            encoder_util: None,
+            decoder_util: None,
+            throttle_reasons: None,
+            // WMI only has the PnP device ID
+            uuid: None,
+            bus_id: None,
+            processes: None,
+            ecc_errors: None,
+            metric_sources: Default::default(),
+            confidence: Confidence::Medium,
+            adapter_luid: None,
             active: status,
             temperature: None,
+            temperatures: None,
             utilization: None,
             power_usage: None,
             power_limit: None,
@@ -182,7 +213,19 @@ impl Default for IntelProvider {
 impl GpuProvider for IntelProvider {
     /// Detect Intel GPUs using PowerShell WMI queries
     fn detect_gpus(&self) -> Result<Vec<GpuInfo>> {
-        let output_str = self.get_intel_gpu_info()?;
+        self.detect_gpus_with_cancel(&CancelToken::new(), &mut DetectionReport::new())
+    }
+
+    /// Detect Intel GPUs, killing the PowerShell query if `token` is cancelled
+    fn detect_gpus_with_cancel(
+        &self,
+        token: &CancelToken,
+        _report: &mut DetectionReport,
+    ) -> Result<Vec<GpuInfo>> {
+        let output_str = match self.get_intel_gpu_info(token) {
+            Err(_) if token.is_cancelled() => return Ok(Vec::new()),
+            result => result?,
+        };
         let gpus = match self.parse_gpu_info(&output_str) {
             Some(gpu) => vec![gpu],
             None => Vec::new(),
@@ -193,7 +236,7 @@ impl GpuProvider for IntelProvider {
     /// Update the information for a specific Intel GPU
     fn update_gpu(&self, gpu: &mut GpuInfo) -> Result<()> {
         info!("Updating Intel GPU information");
-        let output_str = self.get_intel_gpu_info()?;
+        let output_str = self.get_intel_gpu_info(&CancelToken::new())?;
         if let Some(updated_gpu) = self.parse_gpu_info(&output_str) {
             gpu.name_gpu = updated_gpu.name_gpu;
             gpu.vendor = updated_gpu.vendor;
@@ -217,6 +260,10 @@ impl GpuProvider for IntelProvider {
     fn get_vendor(&self) -> Vendor {
         Vendor::Intel(IntelGpuType::Unknown)
     }
+    /// Whether `metric` is one this provider reports
+    fn supports_metric(&self, metric: Metric) -> bool {
+        SUPPORTED_METRICS.contains(&metric)
+    }
 }
 
 /// Detect all Intel GPUs in the system.
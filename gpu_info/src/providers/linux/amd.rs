@@ -6,18 +6,188 @@
 //! # Sysfs Paths
 //!
 //! - `/sys/class/drm/cardX/device/` - Device information
-//! - `/sys/class/drm/cardX/device/hwmon/` - Hardware monitoring (temperature, power)
+//! - `/sys/class/drm/cardX/device/hwmon/` - Hardware monitoring (temperature, power, fan)
 //! - `/sys/class/drm/cardX/device/pp_dpm_sclk` - Core clock states
 //! - `/sys/class/drm/cardX/device/pp_dpm_mclk` - Memory clock states
+//! - `/sys/class/drm/cardX/device/gpu_metrics` - SMU metrics table (effective clocks)
+//! - `/sys/class/drm/cardX/device/power/runtime_status` - Runtime PM state
+//! - `/sys/class/drm/cardX/device/smartshift_*` - SmartShift power split (laptops)
 //!
 //! [`GpuProvider`]: crate::gpu_info::GpuProvider
 
-use crate::gpu_info::{GpuError, GpuInfo, GpuProvider, Result};
+use super::{card_nodes, pcie, runtime_active, DRM_CLASS_PATH};
+use crate::cancel::CancelToken;
+use crate::detection_report::DetectionReport;
+use crate::extended_info::SmartShiftInfo;
+use crate::gpu_identity::normalize_bus_id;
+use crate::gpu_info::{Confidence, GpuError, GpuInfo, GpuProvider, Result};
+use crate::metric_source::Metric;
+use crate::process_usage::{parse_drm_clients_list, GpuProcessInfo};
 use crate::vendor::Vendor;
 use log::{debug, info, warn};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::Path;
 
+/// Offset of `average_gfxclk_frequency` in `gpu_metrics_v1_1` and later (dGPU).
+const GPU_METRICS_V1_GFXCLK_OFFSET: usize = 40;
+/// Offset of `average_gfxclk_frequency` in `gpu_metrics_v2_x` (APU).
+const GPU_METRICS_V2_GFXCLK_OFFSET: usize = 68;
+/// Value the SMU reports for metrics it does not support.
+const GPU_METRICS_UNSUPPORTED: u16 = 0xFFFF;
+/// DRM debugfs directory, with one subdirectory per DRM minor.
+const DRI_DEBUGFS_PATH: &str = "/sys/kernel/debug/dri";
+
+/// Metrics read from sysfs and hwmon
+const SUPPORTED_METRICS: &[Metric] = &[
+    Metric::Temperature,
+    Metric::Utilization,
+    Metric::PowerUsage,
+    Metric::CoreClock,
+    Metric::MemoryUtil,
+    Metric::MemoryClock,
+    Metric::MemoryTotal,
+    Metric::MemoryUsed,
+    Metric::MaxClockSpeed,
+];
+
+/// Extracts `average_gfxclk_frequency` (MHz) from a `gpu_metrics` table.
+///
+/// The table starts with a header of `structure_size: u16`,
+/// `format_revision: u8` and `content_revision: u8`. Format 1 (discrete
+/// GPUs) from content revision 1 onwards, and format 2 (APUs), are
+/// supported; `gpu_metrics_v1_0` uses a different layout and is skipped.
+pub(crate) fn parse_gpu_metrics_gfxclk(table: &[u8]) -> Option<u32> {
+    let (format_revision, content_revision) = (*table.get(2)?, *table.get(3)?);
+    let offset = match (format_revision, content_revision) {
+        (1, 1..) => GPU_METRICS_V1_GFXCLK_OFFSET,
+        (2, _) => GPU_METRICS_V2_GFXCLK_OFFSET,
+        _ => return None,
+    };
+    let bytes = table.get(offset..offset + 2)?;
+    match u16::from_le_bytes([bytes[0], bytes[1]]) {
+        0 | GPU_METRICS_UNSUPPORTED => None,
+        mhz => Some(u32::from(mhz)),
+    }
+}
+
+/// Parses a `smartshift_*_power` file, e.g. `130%`.
+///
+/// amdgpu reports the power of the APU or dGPU as a percentage of its
+/// STAPM limit, and writes the `%` unit into the file. A reading without it
+/// is in no known unit and gives `None`.
+pub(crate) fn parse_smartshift_percent(content: &str) -> Option<f32> {
+    content.trim().strip_suffix('%')?.trim().parse().ok()
+}
+
+/// Reads the SmartShift power split of the AMD device at `device_path`.
+///
+/// # Returns
+/// * `Some(info)` - The device reports SmartShift; unreadable values are `None`.
+/// * `None` - Neither power file exists, as on desktop cards.
+pub(crate) fn smartshift(device_path: &Path) -> Option<SmartShiftInfo> {
+    let read = |file: &str| -> Option<String> {
+        let content = fs::read_to_string(device_path.join(file)).ok()?;
+        Some(content.trim().to_string())
+    };
+    let power = |file: &str| parse_smartshift_percent(&read(file)?);
+    let apu_power_percent = power("smartshift_apu_power");
+    let dgpu_power_percent = power("smartshift_dgpu_power");
+    if apu_power_percent.is_none() && dgpu_power_percent.is_none() {
+        return None;
+    }
+    Some(SmartShiftInfo {
+        apu_power_percent,
+        dgpu_power_percent,
+        bias: read("smartshift_bias").and_then(|bias| bias.parse().ok()),
+    })
+}
+
+/// Reads the `temp*_input` sensors of the AMD device at `device_path`.
+///
+/// Sensors are named by their `temp*_label`; amdgpu labels temp1 to temp3
+/// `edge`, `junction` and `mem`, which are used when a label is missing.
+/// `mem` is reported as `memory`, the name NVML sensors use.
+///
+/// # Returns
+/// * `Some(sensors)` - Readings in degrees Celsius of the first hwmon device
+///   with any readable sensor.
+/// * `None` - No sensor could be read.
+pub(crate) fn hwmon_temperatures(device_path: &Path) -> Option<BTreeMap<String, f32>> {
+    let entries = fs::read_dir(device_path.join("hwmon")).ok()?;
+    for entry in entries.flatten() {
+        let hwmon_device = entry.path();
+        let read = |file: String| fs::read_to_string(hwmon_device.join(file)).ok();
+        let mut sensors = BTreeMap::new();
+        for (index, default_label) in [(1, "edge"), (2, "junction"), (3, "mem")] {
+            let input = read(format!("temp{}_input", index));
+            let Some(millidegrees) = input.and_then(|value| value.trim().parse::<i32>().ok())
+            else {
+                continue;
+            };
+            let label = read(format!("temp{}_label", index))
+                .map(|label| label.trim().to_lowercase())
+                .unwrap_or_else(|| default_label.to_string());
+            let name = if label == "mem" {
+                "memory".to_string()
+            } else {
+                label
+            };
+            sensors.insert(name, millidegrees as f32 / 1000.0);
+        }
+        if !sensors.is_empty() {
+            return Some(sensors);
+        }
+    }
+    None
+}
+
+/// Reads the fan of the AMD device at `device_path`.
+///
+/// The speed is `fan1_input` in RPM, and in percent relative to `fan1_max`
+/// when the card reports a maximum.
+///
+/// # Returns
+/// * `(percent, rpm)` - Of the first hwmon device with a readable
+///   `fan1_input`; both `None` for passively cooled cards.
+pub(crate) fn hwmon_fan(device_path: &Path) -> (Option<u32>, Option<u32>) {
+    let Ok(entries) = fs::read_dir(device_path.join("hwmon")) else {
+        return (None, None);
+    };
+    for entry in entries.flatten() {
+        let hwmon_device = entry.path();
+        let read = |file: &str| -> Option<u32> {
+            fs::read_to_string(hwmon_device.join(file))
+                .ok()?
+                .trim()
+                .parse()
+                .ok()
+        };
+        let Some(rpm) = read("fan1_input") else {
+            continue;
+        };
+        let percent = read("fan1_max")
+            .filter(|&max| max > 0)
+            .map(|max| (u64::from(rpm) * 100 / u64::from(max)).min(100) as u32);
+        return (percent, Some(rpm));
+    }
+    (None, None)
+}
+
+/// Processes with the card open, from its DRM clients list in debugfs
+///
+/// `card_path` is the card node, e.g. `/sys/class/drm/card0`, whose minor
+/// names the debugfs directory. Returns `None` if the list cannot be read,
+/// e.g. because debugfs is only readable by root.
+pub(crate) fn drm_client_processes(
+    debugfs_path: &Path,
+    card_path: &Path,
+) -> Option<Vec<GpuProcessInfo>> {
+    let minor = card_path.file_name()?.to_str()?.strip_prefix("card")?;
+    let content = fs::read_to_string(debugfs_path.join(minor).join("clients")).ok()?;
+    Some(parse_drm_clients_list(&content))
+}
+
 /// AMD GPU provider for Linux.
 ///
 /// Implements [`GpuProvider`] for AMD GPUs on Linux using sysfs and hwmon interfaces.
@@ -26,11 +196,13 @@ use std::path::Path;
 ///
 /// # Supported Metrics
 ///
-/// - Temperature (from hwmon temp1_input)
+/// - Temperature (from hwmon temp1_input, per sensor from temp1-3_input)
+/// - Fan speed (from hwmon fan1_input and fan1_max)
 /// - Power usage (from hwmon power1_average)
 /// - GPU utilization (from gpu_busy_percent)
 /// - Memory info (from mem_info_vram_total/used)
 /// - Clock speeds (from pp_dpm_sclk/mclk)
+/// - Running processes (from the DRM clients list in debugfs, root only)
 ///
 /// [`GpuProvider`]: crate::gpu_info::GpuProvider
 pub struct AmdLinuxProvider;
@@ -41,22 +213,31 @@ impl AmdLinuxProvider {
         Self
     }
 
-    fn detect_amd_gpus(&self) -> Result<Vec<GpuInfo>> {
+    fn detect_amd_gpus(&self, token: &CancelToken) -> Result<Vec<GpuInfo>> {
+        self.detect_amd_gpus_in(Path::new(DRM_CLASS_PATH), token)
+    }
+
+    /// Probes every card node in `drm_path`; connector and render nodes are skipped.
+    ///
+    /// Stops between cards once `token` is cancelled and returns the GPUs
+    /// probed so far.
+    pub(crate) fn detect_amd_gpus_in(
+        &self,
+        drm_path: &Path,
+        token: &CancelToken,
+    ) -> Result<Vec<GpuInfo>> {
         let mut gpus = Vec::new();
-        let drm_path = Path::new("/sys/class/drm");
         if !drm_path.exists() {
             warn!("DRM sysfs path not found, AMD GPU detection unavailable");
             return Ok(gpus);
         }
-        for entry in fs::read_dir(drm_path).map_err(|_| GpuError::GpuNotFound)? {
-            let entry = entry.map_err(|_| GpuError::GpuNotFound)?;
-            let path = entry.path();
-            if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
-                if name.starts_with("card") && !name.contains("-") {
-                    if let Ok(gpu_info) = self.probe_amd_card(&path) {
-                        gpus.push(gpu_info);
-                    }
-                }
+        for path in card_nodes(drm_path).map_err(|_| GpuError::GpuNotFound)? {
+            if token.is_cancelled() {
+                debug!("AMD GPU detection cancelled after {} card(s)", gpus.len());
+                return Ok(gpus);
+            }
+            if let Ok(gpu_info) = self.probe_amd_card(&path) {
+                gpus.push(gpu_info);
             }
         }
         if gpus.is_empty() {
@@ -66,6 +247,63 @@ impl AmdLinuxProvider {
             Ok(gpus)
         }
     }
+    /// Reads only `metrics` of `gpu` from its card in `drm_path`
+    ///
+    /// The card is found by PCI bus ID. Falls back to a full update if it
+    /// cannot be found or is suspended, since reading its sensors would
+    /// resume it. Memory metrics are read together.
+    pub(crate) fn update_metrics_in(
+        &self,
+        drm_path: &Path,
+        gpu: &mut GpuInfo,
+        metrics: &[Metric],
+    ) -> Result<()> {
+        let card = gpu
+            .bus_id
+            .as_deref()
+            .and_then(normalize_bus_id)
+            .and_then(|bus_id| {
+                card_nodes(drm_path).ok()?.into_iter().find(|card| {
+                    pcie::bus_id(&card.join("device")).as_deref() == Some(bus_id.as_str())
+                })
+            });
+        let device_path = match card {
+            Some(card) if runtime_active(&card.join("device")).unwrap_or(true) => {
+                card.join("device")
+            }
+            _ => return self.update_gpu(gpu),
+        };
+        debug!(
+            "Updating {:?} of AMD GPU {}",
+            metrics,
+            device_path.display()
+        );
+        let mut memory_read = false;
+        for metric in metrics {
+            match metric {
+                Metric::Temperature => gpu.temperature = self.get_temperature(&device_path),
+                Metric::Utilization => gpu.utilization = self.get_gpu_utilization(&device_path),
+                Metric::PowerUsage => gpu.power_usage = self.get_power_usage(&device_path),
+                Metric::CoreClock => {
+                    gpu.core_clock = self.get_core_clock(&device_path);
+                    gpu.effective_core_clock = self.get_effective_core_clock(&device_path);
+                }
+                Metric::MemoryClock => gpu.memory_clock = self.get_memory_clock(&device_path),
+                Metric::MaxClockSpeed => {
+                    gpu.max_clock_speed = self.get_max_clock_speed(&device_path);
+                }
+                Metric::MemoryUtil | Metric::MemoryTotal | Metric::MemoryUsed => {
+                    if !memory_read {
+                        (gpu.memory_total, gpu.memory_util, gpu.memory_used) =
+                            self.get_memory_info(&device_path);
+                        memory_read = true;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
     fn probe_amd_card(&self, card_path: &Path) -> Result<GpuInfo> {
         let device_path = card_path.join("device");
         let vendor_id = self.read_hex_file(&device_path.join("vendor"))?;
@@ -75,12 +313,32 @@ impl AmdLinuxProvider {
         // Get basic GPU information
         let name = self.get_gpu_name(&device_path)?;
         let driver_version = self.get_driver_version();
+        let memory_info = self.get_memory_info(&device_path);
+        let active = runtime_active(&device_path).unwrap_or(true);
+        if !active {
+            // Reading sensors or clock tables would resume the device
+            info!("Found AMD GPU: {} (suspended)", name);
+            return Ok(GpuInfo {
+                vendor: Vendor::Amd,
+                name_gpu: Some(name),
+                memory_total: memory_info.0,
+                memory_used: memory_info.2,
+                memory_util: memory_info.1,
+                driver_version,
+                active: Some(false),
+                bus_id: pcie::bus_id(&device_path),
+                confidence: Confidence::High,
+                ..GpuInfo::unknown()
+            });
+        }
         // Get power management info if available
         let power_usage = self.get_power_usage(&device_path);
         let temperature = self.get_temperature(&device_path);
+        let temperatures = hwmon_temperatures(&device_path);
+        let (fan_speed, fan_speed_rpm) = hwmon_fan(&device_path);
         let utilization = self.get_gpu_utilization(&device_path);
-        let memory_info = self.get_memory_info(&device_path);
         let core_clock = self.get_core_clock(&device_path);
+        let effective_core_clock = self.get_effective_core_clock(&device_path);
         let memory_clock = self.get_memory_clock(&device_path);
         let power_limit = self.get_power_limit(&device_path);
         let max_clock_speed = self.get_max_clock_speed(&device_path);
@@ -89,6 +347,7 @@ impl AmdLinuxProvider {
             vendor: Vendor::Amd,
             name_gpu: Some(name),
             temperature,
+            temperatures,
             utilization,
             power_usage,
             memory_total: memory_info.0,
@@ -100,6 +359,19 @@ impl AmdLinuxProvider {
             memory_clock,
             power_limit,
             max_clock_speed,
+            effective_core_clock,
+            fan_speed,
+            fan_speed_rpm,
+            encoder_util: None,
+            decoder_util: None,
+            throttle_reasons: None,
+            uuid: None,
+            bus_id: pcie::bus_id(&device_path),
+            processes: drm_client_processes(Path::new(DRI_DEBUGFS_PATH), card_path),
+            ecc_errors: None,
+            metric_sources: Default::default(),
+            confidence: Confidence::High,
+            adapter_luid: None,
         })
     }
 
@@ -156,7 +428,7 @@ impl AmdLinuxProvider {
     }
 
     fn get_gpu_utilization(&self, device_path: &Path) -> Option<f32> {
-        let drm_path = Path::new("/sys/class/drm");
+        let drm_path = Path::new(DRM_CLASS_PATH);
         if drm_path.exists() {
             if let Some(card_name) = device_path.parent().and_then(|p| p.file_name()) {
                 let engine_path = drm_path.join(card_name).join("engine");
@@ -229,6 +501,15 @@ impl AmdLinuxProvider {
         None
     }
 
+    /// Reads the average graphics clock from `gpu_metrics`.
+    ///
+    /// Unlike the active `pp_dpm_sclk` state, which is the requested DPM
+    /// level, this is the clock the SMU measured, including throttling.
+    fn get_effective_core_clock(&self, device_path: &Path) -> Option<u32> {
+        let table = fs::read(device_path.join("gpu_metrics")).ok()?;
+        parse_gpu_metrics_gfxclk(&table)
+    }
+
     fn get_memory_clock(&self, device_path: &Path) -> Option<u32> {
         // Try to get memory clock from pp_dpm_mclk
         let mclk_path = device_path.join("pp_dpm_mclk");
@@ -333,46 +614,32 @@ impl AmdLinuxProvider {
         None
     }
 
+    /// Reads the VRAM size and usage from `mem_info_vram_total` and
+    /// `mem_info_vram_used`, which amdgpu reports in bytes.
+    ///
+    /// GTT (`mem_info_gtt_*`) is system memory the GPU maps, not VRAM, and is
+    /// deliberately not counted. A total of zero counts as unknown.
+    ///
+    /// # Returns
+    /// * `(total_mb, utilization, used_mb)` - Utilization is used/total in
+    ///   percent and is only known when both sizes are.
     pub(crate) fn get_memory_info(
         &self,
         device_path: &Path,
     ) -> (Option<u32>, Option<f32>, Option<u32>) {
-        // Try to get memory information from sysfs
-        // Check /sys/class/drm/cardX/device/mem_info_* files
-        // Get total VRAM size
-        let vram_total_path = device_path.join("mem_info_vram_total");
-        let vram_total = if let Ok(content) = fs::read_to_string(&vram_total_path) {
-            if let Ok(bytes) = content.trim().parse::<u64>() {
-                // Convert bytes to MB
-                Some((bytes / (1024 * 1024)) as u32)
-            } else {
-                None
-            }
-        } else {
-            None
-        };
-        // Get used VRAM size and calculate utilization
-        let vram_used_path = device_path.join("mem_info_vram_used");
-        let (vram_used, vram_util) = if let Ok(content) = fs::read_to_string(&vram_used_path) {
-            if let Ok(bytes) = content.trim().parse::<u64>() {
-                let used_mb = (bytes / (1024 * 1024)) as u32;
-                // Calculate utilization percentage if we have total memory
-                let util = if let Some(total_mb) = vram_total {
-                    if total_mb > 0 {
-                        Some(((used_mb as f32) / (total_mb as f32)) * 100.0)
-                    } else {
-                        None
-                    }
-                } else {
-                    None
-                };
-                (Some(used_mb), util)
-            } else {
-                (None, None)
-            }
-        } else {
-            (None, None)
+        let read_mb = |file: &str| -> Option<u32> {
+            let bytes: u64 = fs::read_to_string(device_path.join(file))
+                .ok()?
+                .trim()
+                .parse()
+                .ok()?;
+            u32::try_from(bytes / (1024 * 1024)).ok()
         };
+        let vram_total = read_mb("mem_info_vram_total").filter(|&total| total > 0);
+        let vram_used = read_mb("mem_info_vram_used");
+        let vram_util = vram_total
+            .zip(vram_used)
+            .map(|(total, used)| (used as f32 / total as f32 * 100.0).min(100.0));
         (vram_total, vram_util, vram_used)
     }
 }
@@ -385,20 +652,36 @@ impl Default for AmdLinuxProvider {
 
 impl GpuProvider for AmdLinuxProvider {
     fn detect_gpus(&self) -> Result<Vec<GpuInfo>> {
+        self.detect_gpus_with_cancel(&CancelToken::new(), &mut DetectionReport::new())
+    }
+
+    fn detect_gpus_with_cancel(
+        &self,
+        token: &CancelToken,
+        _report: &mut DetectionReport,
+    ) -> Result<Vec<GpuInfo>> {
         debug!("Detecting AMD GPUs on Linux using sysfs");
-        self.detect_amd_gpus()
+        self.detect_amd_gpus(token)
     }
 
     fn update_gpu(&self, gpu: &mut GpuInfo) -> Result<()> {
         debug!("Updating AMD GPU information on Linux");
         let gpus = self.detect_gpus()?;
-        if let Some(updated_gpu) = gpus.first() {
+        if let Some(updated_gpu) = crate::gpu_info::matching_gpu(gpu, &gpus) {
             *gpu = updated_gpu.clone();
         }
         Ok(())
     }
 
+    fn update_metrics(&self, gpu: &mut GpuInfo, metrics: &[Metric]) -> Result<()> {
+        self.update_metrics_in(Path::new(DRM_CLASS_PATH), gpu, metrics)
+    }
+
     fn get_vendor(&self) -> Vendor {
         Vendor::Amd
     }
+
+    fn supports_metric(&self, metric: Metric) -> bool {
+        SUPPORTED_METRICS.contains(&metric)
+    }
 }
@@ -9,15 +9,28 @@
 //! - `/sys/class/drm/cardX/device/hwmon/` - Hardware monitoring (temperature, power)
 //! - `/sys/class/drm/cardX/device/gt_cur_freq_mhz` - Current GPU frequency
 //! - `/sys/class/drm/cardX/device/gt_max_freq_mhz` - Maximum GPU frequency
+//! - `/sys/class/drm/cardX/device/power/runtime_status` - Runtime PM state
 //!
 //! [`GpuProvider`]: crate::gpu_info::GpuProvider
 
-use crate::gpu_info::{GpuError, GpuInfo, GpuProvider, Result};
+use super::{card_nodes, pcie, runtime_active, DRM_CLASS_PATH};
+use crate::gpu_info::{Confidence, GpuError, GpuInfo, GpuProvider, Result};
+use crate::metric_source::Metric;
 use crate::vendor::{IntelGpuType, Vendor};
 use log::{debug, info, warn};
 use std::fs;
 use std::path::Path;
 
+/// Metrics read from sysfs and hwmon; the driver exposes no memory sizes
+const SUPPORTED_METRICS: &[Metric] = &[
+    Metric::Temperature,
+    Metric::Utilization,
+    Metric::PowerUsage,
+    Metric::CoreClock,
+    Metric::MemoryClock,
+    Metric::MaxClockSpeed,
+];
+
 /// Intel GPU provider for Linux.
 ///
 /// Implements [`GpuProvider`] for Intel GPUs on Linux using sysfs interfaces.
@@ -42,21 +55,19 @@ impl IntelLinuxProvider {
     }
 
     fn detect_intel_gpus(&self) -> Result<Vec<GpuInfo>> {
+        self.detect_intel_gpus_in(Path::new(DRM_CLASS_PATH))
+    }
+
+    /// Probes every card node in `drm_path`; connector and render nodes are skipped.
+    pub(crate) fn detect_intel_gpus_in(&self, drm_path: &Path) -> Result<Vec<GpuInfo>> {
         let mut gpus = Vec::new();
-        let drm_path = Path::new("/sys/class/drm");
         if !drm_path.exists() {
             warn!("DRM sysfs path not found, Intel GPU detection unavailable");
             return Ok(gpus);
         }
-        for entry in fs::read_dir(drm_path).map_err(|_| GpuError::GpuNotFound)? {
-            let entry = entry.map_err(|_| GpuError::GpuNotFound)?;
-            let path = entry.path();
-            if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
-                if name.starts_with("card") && !name.contains("-") {
-                    if let Ok(gpu_info) = self.probe_intel_card(&path) {
-                        gpus.push(gpu_info);
-                    }
-                }
+        for path in card_nodes(drm_path).map_err(|_| GpuError::GpuNotFound)? {
+            if let Ok(gpu_info) = self.probe_intel_card(&path) {
+                gpus.push(gpu_info);
             }
         }
         if gpus.is_empty() {
@@ -75,10 +86,23 @@ impl IntelLinuxProvider {
         }
         let name = self.get_gpu_name(&device_path)?;
         let driver_version = self.get_driver_version();
+        let active = runtime_active(&device_path).unwrap_or(true);
+        if !active {
+            // Reading sensors or frequencies would resume the device
+            info!("Found Intel GPU: {} (suspended)", name);
+            return Ok(GpuInfo {
+                vendor: Vendor::Intel(IntelGpuType::Integrated),
+                name_gpu: Some(name),
+                driver_version,
+                active: Some(false),
+                bus_id: pcie::bus_id(&device_path),
+                confidence: Confidence::High,
+                ..GpuInfo::unknown()
+            });
+        }
         let power_usage = self.get_power_usage(&device_path);
         let temperature = self.get_temperature(&device_path);
         let utilization = self.get_gpu_utilization(&device_path);
-        let memory_info = self.get_memory_info(&device_path);
         let core_clock = self.get_core_clock(&device_path);
         let memory_clock = self.get_memory_clock(&device_path);
         let power_limit = self.get_power_limit(&device_path);
@@ -88,17 +112,31 @@ impl IntelLinuxProvider {
             vendor: Vendor::Intel(IntelGpuType::Integrated),
             name_gpu: Some(name),
             temperature,
+            temperatures: None,
             utilization,
             power_usage,
-            memory_total: memory_info.0,
+            memory_total: None,
             memory_used: None,
-            memory_util: memory_info.1,
+            memory_util: None,
             driver_version,
             active: Some(true),
             core_clock,
             memory_clock,
             power_limit,
             max_clock_speed,
+            effective_core_clock: None,
+            fan_speed: None,
+            fan_speed_rpm: None,
+            encoder_util: None,
+            decoder_util: None,
+            throttle_reasons: None,
+            uuid: None,
+            bus_id: pcie::bus_id(&device_path),
+            processes: None,
+            ecc_errors: None,
+            metric_sources: Default::default(),
+            confidence: Confidence::High,
+            adapter_luid: None,
         })
     }
 
@@ -235,10 +273,6 @@ impl IntelLinuxProvider {
         None
     }
 
-    pub(crate) fn get_memory_info(&self, _device_path: &Path) -> (Option<u32>, Option<f32>) {
-        (None, None)
-    }
-
     fn get_card_number(&self, device_path: &Path) -> Option<usize> {
         if let Some(parent) = device_path.parent() {
             if let Some(name) = parent.file_name().and_then(|n| n.to_str()) {
@@ -275,4 +309,8 @@ impl GpuProvider for IntelLinuxProvider {
     fn get_vendor(&self) -> Vendor {
         Vendor::Intel(IntelGpuType::Unknown)
     }
+
+    fn supports_metric(&self, metric: Metric) -> bool {
+        SUPPORTED_METRICS.contains(&metric)
+    }
 }
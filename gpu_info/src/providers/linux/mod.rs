@@ -9,19 +9,100 @@
 //! - `AmdLinuxProvider` - AMD GPU provider using sysfs/hwmon
 //! - `IntelLinuxProvider` - Intel GPU provider using sysfs
 //! - `NvidiaLinuxProvider` - NVIDIA GPU provider using NVML
+//!
+//! Each provider requires its `vendor-*` feature. The sysfs helpers below
+//! are unused when all three are disabled.
+#![cfg_attr(
+    not(any(
+        feature = "vendor-nvidia",
+        feature = "vendor-amd",
+        feature = "vendor-intel"
+    )),
+    allow(dead_code)
+)]
 
 /// AMD GPU provider for Linux using sysfs and hwmon.
+#[cfg(feature = "vendor-amd")]
 pub mod amd;
 
 /// Intel GPU provider for Linux using sysfs.
+#[cfg(feature = "vendor-intel")]
 pub mod intel;
 
 /// NVIDIA GPU provider for Linux using NVML.
+#[cfg(feature = "vendor-nvidia")]
 pub mod nvidia;
 
-#[cfg(target_os = "linux")]
+/// PCIe AER error counters from sysfs.
+pub mod pcie;
+
+/// NVIDIA persistence mode and `nvidia-persistenced` detection.
+#[cfg(feature = "vendor-nvidia")]
+pub mod persistence;
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Sysfs directory listing DRM devices.
+pub(crate) const DRM_CLASS_PATH: &str = "/sys/class/drm";
+
+/// Returns `true` if `name` is a primary DRM card node (`card0`).
+///
+/// Connector sub-nodes (`card0-HDMI-A-1`) and render nodes (`renderD128`)
+/// refer to a card already listed and are excluded.
+pub(crate) fn is_card_node(name: &str) -> bool {
+    name.strip_prefix("card")
+        .is_some_and(|index| !index.is_empty() && index.bytes().all(|b| b.is_ascii_digit()))
+}
+
+/// Lists the card nodes in `drm_path`, ordered by card index.
+pub(crate) fn card_nodes(drm_path: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut cards: Vec<(u32, PathBuf)> = Vec::new();
+    for entry in fs::read_dir(drm_path)? {
+        let entry = entry?;
+        let name = entry.file_name();
+        let Some(name) = name.to_str().filter(|name| is_card_node(name)) else {
+            continue;
+        };
+        if let Ok(index) = name["card".len()..].parse() {
+            cards.push((index, entry.path()));
+        }
+    }
+    cards.sort_by_key(|(index, _)| *index);
+    Ok(cards.into_iter().map(|(_, path)| path).collect())
+}
+
+/// Reads the runtime power management state of a PCI device.
+///
+/// `power/runtime_status` is checked first (`active` or `suspended`); if
+/// runtime PM is unsupported, the PCI `power_state` (`D0`, `D3hot`,
+/// `D3cold`) is used instead.
+///
+/// # Returns
+/// * `Some(true)` - The device is powered up.
+/// * `Some(false)` - The device is suspended, e.g. an idle dGPU in a hybrid laptop.
+/// * `None` - Neither file reports a known state.
+pub(crate) fn runtime_active(device_path: &Path) -> Option<bool> {
+    let runtime_status = fs::read_to_string(device_path.join("power/runtime_status"));
+    match runtime_status.as_deref().map(str::trim) {
+        Ok("active" | "resuming") => return Some(true),
+        Ok("suspended" | "suspending") => return Some(false),
+        _ => {}
+    }
+    match fs::read_to_string(device_path.join("power_state"))
+        .ok()?
+        .trim()
+    {
+        "D0" => Some(true),
+        state if state.starts_with("D3") => Some(false),
+        _ => None,
+    }
+}
+
+#[cfg(all(target_os = "linux", feature = "vendor-amd"))]
 pub use self::amd::AmdLinuxProvider;
-#[cfg(target_os = "linux")]
+#[cfg(all(target_os = "linux", feature = "vendor-intel"))]
 pub use self::intel::IntelLinuxProvider;
-#[cfg(target_os = "linux")]
+#[cfg(all(target_os = "linux", feature = "vendor-nvidia"))]
 pub use self::nvidia::NvidiaLinuxProvider;
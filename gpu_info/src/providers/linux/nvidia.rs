@@ -15,11 +15,24 @@
 //!
 //! [`GpuProvider`]: crate::gpu_info::GpuProvider
 
-use crate::gpu_info::{GpuInfo, GpuProvider, Result};
+use super::{card_nodes, pcie, persistence, runtime_active, DRM_CLASS_PATH};
+use crate::cancel::CancelToken;
+use crate::detection_report::DetectionReport;
+use crate::extended_info::{ConnectionInfo, ExtendedGpuInfo};
+use crate::gpu_info::{update_gpu_from_api, Confidence, GpuInfo, GpuProvider, Result};
+use crate::metric_source::{self, Metric, MetricSource};
+use crate::nvml_api::{
+    codec_utilization_from, ecc_stats_from, field_value_from, nvmlFieldValue_t, nvmlPciInfo_t,
+    nvmlProcessInfo_v3_t, pci_bus_id_from, pcie_link_value_from, processes_from,
+    running_processes_from, sensor_temperatures, throttle_reasons_from, uuid_from,
+    NVML_FI_DEV_MEMORY_TEMP,
+};
 use crate::vendor::Vendor;
 use libloading::{Library, Symbol};
-use log::{debug, error};
-use std::{env, os::raw::c_char, ptr};
+use log::{debug, error, info};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::{env, os::raw::c_char, ptr, time::Instant};
 
 #[repr(C)]
 #[derive(Debug, Clone, Copy)]
@@ -53,6 +66,7 @@ struct NvmlMemory {
 
 type NvmlInitFn = unsafe extern "C" fn() -> nvmlReturn_t;
 type NvmlShutdownFn = unsafe extern "C" fn() -> nvmlReturn_t;
+type NvmlDeviceGetCountFn = unsafe extern "C" fn(*mut u32) -> nvmlReturn_t;
 type NvmlDeviceGetHandleByIndexFn = unsafe extern "C" fn(u32, *mut NvmlDevice_t) -> nvmlReturn_t;
 type NvmlDeviceGetTemperatureFn = unsafe extern "C" fn(NvmlDevice_t, u32, *mut u32) -> nvmlReturn_t;
 type NvmlDeviceGetNameFn = unsafe extern "C" fn(NvmlDevice_t, *mut c_char, u32) -> nvmlReturn_t;
@@ -60,9 +74,133 @@ type NvmlDeviceGetUtilizationRatesFn =
     unsafe extern "C" fn(NvmlDevice_t, *mut NvmlUtilization) -> nvmlReturn_t;
 type NvmlDeviceGetPowerUsageFn = unsafe extern "C" fn(NvmlDevice_t, *mut u32) -> nvmlReturn_t;
 type NvmlDeviceGetClockInfoFn = unsafe extern "C" fn(NvmlDevice_t, u32, *mut u32) -> nvmlReturn_t;
+type NvmlDeviceGetClockFn = unsafe extern "C" fn(NvmlDevice_t, u32, u32, *mut u32) -> nvmlReturn_t;
 type NvmlDeviceGetMemoryInfoFn =
     unsafe extern "C" fn(NvmlDevice_t, *mut NvmlMemory) -> nvmlReturn_t;
+type NvmlDeviceGetPersistenceModeFn = unsafe extern "C" fn(NvmlDevice_t, *mut u32) -> nvmlReturn_t;
+type NvmlDeviceGetCudaComputeCapabilityFn =
+    unsafe extern "C" fn(NvmlDevice_t, *mut i32, *mut i32) -> nvmlReturn_t;
+type NvmlDeviceGetPcieReplayCounterFn =
+    unsafe extern "C" fn(NvmlDevice_t, *mut u32) -> nvmlReturn_t;
+type NvmlDeviceGetPcieLinkFn = unsafe extern "C" fn(NvmlDevice_t, *mut u32) -> nvmlReturn_t;
+type NvmlDeviceGetFanSpeedFn = unsafe extern "C" fn(NvmlDevice_t, *mut u32) -> nvmlReturn_t;
+type NvmlDeviceGetCodecUtilizationFn =
+    unsafe extern "C" fn(NvmlDevice_t, *mut u32, *mut u32) -> nvmlReturn_t;
+type NvmlDeviceGetThrottleReasonsFn = unsafe extern "C" fn(NvmlDevice_t, *mut u64) -> nvmlReturn_t;
+type NvmlDeviceGetTotalEccErrorsFn =
+    unsafe extern "C" fn(NvmlDevice_t, i32, i32, *mut u64) -> nvmlReturn_t;
+type NvmlDeviceGetRunningProcessesFn =
+    unsafe extern "C" fn(NvmlDevice_t, *mut u32, *mut nvmlProcessInfo_v3_t) -> nvmlReturn_t;
+type NvmlDeviceGetUuidFn = unsafe extern "C" fn(NvmlDevice_t, *mut c_char, u32) -> nvmlReturn_t;
+type NvmlDeviceGetPciInfoFn =
+    unsafe extern "C" fn(NvmlDevice_t, *mut nvmlPciInfo_t) -> nvmlReturn_t;
+type NvmlDeviceGetFieldValuesFn =
+    unsafe extern "C" fn(NvmlDevice_t, i32, *mut nvmlFieldValue_t) -> nvmlReturn_t;
 const NVML_CLOCK_GRAPHICS: u32 = 0;
+const NVML_CLOCK_ID_APP_CLOCK_TARGET: u32 = 1;
+const NVML_ERROR_NOT_SUPPORTED: nvmlReturn_t = 3;
+
+/// Metrics NVML reports on Linux
+const SUPPORTED_METRICS: &[Metric] = &[
+    Metric::Temperature,
+    Metric::Utilization,
+    Metric::MemoryUtil,
+    Metric::PowerUsage,
+    Metric::CoreClock,
+    Metric::MemoryTotal,
+    Metric::MemoryUsed,
+];
+
+/// Version of the loaded `nvidia` kernel module
+const NVIDIA_MODULE_VERSION_PATH: &str = "/sys/module/nvidia/version";
+
+/// Reads the NVIDIA card at `bus_id` in `drm_path` from sysfs.
+///
+/// Used as a fallback for values NVML does not report: the hwmon
+/// temperature and power sensors (only exposed by some drivers), the
+/// runtime power state and the driver version from `module_version`.
+/// `bus_id` is the normalized PCI address NVML reports for the device.
+///
+/// # Returns
+/// * `Some(gpu)` - The card was found; unreadable values are `None`.
+/// * `None` - `drm_path` lists no NVIDIA card at `bus_id`.
+pub(crate) fn sysfs_reading(
+    drm_path: &Path,
+    module_version: &Path,
+    bus_id: &str,
+) -> Option<GpuInfo> {
+    let device_path = nvidia_device_path(drm_path, bus_id)?;
+    let hwmon = fs::read_dir(device_path.join("hwmon"))
+        .ok()
+        .and_then(|mut entries| entries.next())
+        .and_then(|entry| entry.ok())
+        .map(|entry| entry.path());
+    let read_sensor = |file: &str| -> Option<f32> {
+        let content = fs::read_to_string(hwmon.as_ref()?.join(file)).ok()?;
+        content.trim().parse::<f32>().ok()
+    };
+    let mut gpu = GpuInfo::write_vendor(Vendor::Nvidia);
+    gpu.confidence = Confidence::High;
+    gpu.temperature = read_sensor("temp1_input").map(|millidegrees| millidegrees / 1000.0);
+    gpu.power_usage = read_sensor("power1_average").map(|microwatts| microwatts / 1_000_000.0);
+    gpu.active = runtime_active(&device_path);
+    gpu.bus_id = pcie::bus_id(&device_path);
+    gpu.driver_version = fs::read_to_string(module_version)
+        .ok()
+        .map(|version| version.trim().to_string())
+        .filter(|version| !version.is_empty());
+    Some(gpu)
+}
+
+/// Returns the sysfs device directory of the NVIDIA card at `bus_id` in `drm_path`.
+fn nvidia_device_path(drm_path: &Path, bus_id: &str) -> Option<PathBuf> {
+    card_nodes(drm_path).ok()?.into_iter().find_map(|card| {
+        let device_path = card.join("device");
+        let vendor = fs::read_to_string(device_path.join("vendor")).ok()?;
+        (vendor.trim() == "0x10de" && pcie::bus_id(&device_path).as_deref() == Some(bus_id))
+            .then_some(device_path)
+    })
+}
+
+/// Interprets the result of an `nvmlDeviceGetCudaComputeCapability` call.
+///
+/// `query` receives the major and minor output parameters and returns the
+/// NVML status code, which makes the FFI call easy to substitute in tests.
+///
+/// # Returns
+///
+/// * `Some((major, minor))` - If the call succeeded
+/// * `None` - If the device does not support the query, or the call failed
+pub fn compute_capability_from<F>(query: F) -> Option<(u32, u32)>
+where
+    F: FnOnce(&mut i32, &mut i32) -> i32,
+{
+    let (mut major, mut minor) = (0i32, 0i32);
+    match query(&mut major, &mut minor) {
+        NVML_SUCCESS => Some((u32::try_from(major).ok()?, u32::try_from(minor).ok()?)),
+        NVML_ERROR_NOT_SUPPORTED => {
+            debug!("CUDA compute capability is not supported by this device");
+            None
+        }
+        code => {
+            debug!(
+                "nvmlDeviceGetCudaComputeCapability failed with code {}",
+                code
+            );
+            None
+        }
+    }
+}
+
+/// Result of querying one device, including the NVML-only attributes that
+/// only appear in [`ExtendedGpuInfo`].
+struct DeviceReading {
+    gpu: GpuInfo,
+    persistence_mode: Option<bool>,
+    compute_capability: Option<(u32, u32)>,
+    /// PCIe replay counter and link state
+    connection_info: ConnectionInfo,
+}
 
 /// NVIDIA GPU provider for Linux.
 ///
@@ -87,16 +225,65 @@ impl NvidiaLinuxProvider {
     pub fn new() -> Self {
         Self
     }
-}
 
-impl Default for NvidiaLinuxProvider {
-    fn default() -> Self {
-        Self::new()
+    /// Detect GPUs and record diagnostics into `report`.
+    ///
+    /// Every device NVML lists is read, in NVML order. The NVML
+    /// initialization latency is recorded under the `nvml_init` stage. If
+    /// initialization was slow while persistence mode is off and
+    /// `nvidia-persistenced` is not running, a hint is added to the report.
+    pub fn detect_with_report(&self, report: &mut DetectionReport) -> Result<Vec<GpuInfo>> {
+        self.detect_with_cancel(&CancelToken::new(), report)
     }
-}
 
-impl GpuProvider for NvidiaLinuxProvider {
-    fn detect_gpus(&self) -> Result<Vec<GpuInfo>> {
+    /// Like [`detect_with_report()`](Self::detect_with_report), but stops
+    /// reading devices once `token` is cancelled
+    fn detect_with_cancel(
+        &self,
+        token: &CancelToken,
+        report: &mut DetectionReport,
+    ) -> Result<Vec<GpuInfo>> {
+        let devices = self.query_devices(report, token)?;
+        Ok(devices.into_iter().map(|device| device.gpu).collect())
+    }
+
+    /// Get extended information for the primary NVIDIA GPU.
+    ///
+    /// The primary GPU is NVML device 0. Fills `persistence_mode`,
+    /// `compute_capability`, the PCIe replay counter and link state and,
+    /// when readable, the AER counters of the device at its NVML bus id in
+    /// addition to the basic metrics.
+    pub fn get_extended_info(&self) -> Result<ExtendedGpuInfo> {
+        let mut report = DetectionReport::new();
+        let device = self
+            .query_devices(&mut report, &CancelToken::new())?
+            .swap_remove(0);
+        let mut extended = ExtendedGpuInfo::from_basic(device.gpu);
+        extended.persistence_mode = device.persistence_mode;
+        extended.compute_capability = device.compute_capability;
+        extended.connection_info = device.connection_info;
+        if let Some(bus_id) = extended.base_info.bus_id.clone() {
+            pcie::fill_errors(
+                &mut extended.connection_info,
+                Path::new(pcie::PCI_DEVICES_PATH),
+                &bus_id,
+            );
+        }
+        Ok(extended)
+    }
+
+    /// Reads every device NVML lists
+    ///
+    /// `token` is checked before each device; devices read until then are
+    /// returned. Fails with [`GpuError::GpuNotFound`] if no device could be
+    /// read.
+    ///
+    /// [`GpuError::GpuNotFound`]: crate::gpu_info::GpuError::GpuNotFound
+    fn query_devices(
+        &self,
+        report: &mut DetectionReport,
+        token: &CancelToken,
+    ) -> Result<Vec<DeviceReading>> {
         debug!("Detecting NVIDIA GPUs using dynamic NVML loading on Linux");
         unsafe {
             let nvml_lib_path = env::var("NVML_LIB_PATH")
@@ -122,6 +309,13 @@ impl GpuProvider for NvidiaLinuxProvider {
                     return Err(crate::gpu_info::GpuError::DriverNotInstalled);
                 }
             };
+            let get_count: Symbol<NvmlDeviceGetCountFn> = match lib.get(b"nvmlDeviceGetCount_v2") {
+                Ok(symbol) => symbol,
+                Err(e) => {
+                    error!("Failed to get nvmlDeviceGetCount_v2 symbol: {}", e);
+                    return Err(crate::gpu_info::GpuError::DriverNotInstalled);
+                }
+            };
             let get_device_handle: Symbol<NvmlDeviceGetHandleByIndexFn> =
                 match lib.get(b"nvmlDeviceGetHandleByIndex_v2") {
                     Ok(symbol) => symbol,
@@ -177,91 +371,326 @@ impl GpuProvider for NvidiaLinuxProvider {
                         return Err(crate::gpu_info::GpuError::DriverNotInstalled);
                     }
                 };
+            // Optional: not exported by older drivers
+            let get_persistence: Option<Symbol<NvmlDeviceGetPersistenceModeFn>> =
+                lib.get(b"nvmlDeviceGetPersistenceMode").ok();
+            let get_compute_capability: Option<Symbol<NvmlDeviceGetCudaComputeCapabilityFn>> =
+                lib.get(b"nvmlDeviceGetCudaComputeCapability").ok();
+            let get_clock_by_id: Option<Symbol<NvmlDeviceGetClockFn>> =
+                lib.get(b"nvmlDeviceGetClock").ok();
+            let get_replay_counter: Option<Symbol<NvmlDeviceGetPcieReplayCounterFn>> =
+                lib.get(b"nvmlDeviceGetPcieReplayCounter").ok();
+            let get_pcie_link: [Option<Symbol<NvmlDeviceGetPcieLinkFn>>; 4] = [
+                lib.get(b"nvmlDeviceGetCurrPcieLinkGeneration").ok(),
+                lib.get(b"nvmlDeviceGetCurrPcieLinkWidth").ok(),
+                lib.get(b"nvmlDeviceGetMaxPcieLinkGeneration").ok(),
+                lib.get(b"nvmlDeviceGetMaxPcieLinkWidth").ok(),
+            ];
+            let get_fan_speed: Option<Symbol<NvmlDeviceGetFanSpeedFn>> =
+                lib.get(b"nvmlDeviceGetFanSpeed").ok();
+            let get_encoder_util: Option<Symbol<NvmlDeviceGetCodecUtilizationFn>> =
+                lib.get(b"nvmlDeviceGetEncoderUtilization").ok();
+            let get_decoder_util: Option<Symbol<NvmlDeviceGetCodecUtilizationFn>> =
+                lib.get(b"nvmlDeviceGetDecoderUtilization").ok();
+            let get_throttle_reasons: Option<Symbol<NvmlDeviceGetThrottleReasonsFn>> =
+                lib.get(b"nvmlDeviceGetCurrentClocksThrottleReasons").ok();
+            let get_ecc_errors: Option<Symbol<NvmlDeviceGetTotalEccErrorsFn>> =
+                lib.get(b"nvmlDeviceGetTotalEccErrors").ok();
+            let get_uuid: Option<Symbol<NvmlDeviceGetUuidFn>> = lib.get(b"nvmlDeviceGetUUID").ok();
+            let get_compute_processes: Option<Symbol<NvmlDeviceGetRunningProcessesFn>> =
+                lib.get(b"nvmlDeviceGetComputeRunningProcesses_v3").ok();
+            let get_graphics_processes: Option<Symbol<NvmlDeviceGetRunningProcessesFn>> =
+                lib.get(b"nvmlDeviceGetGraphicsRunningProcesses_v3").ok();
+            let get_pci_info: Option<Symbol<NvmlDeviceGetPciInfoFn>> =
+                lib.get(b"nvmlDeviceGetPciInfo_v3").ok();
+            let get_field_values: Option<Symbol<NvmlDeviceGetFieldValuesFn>> =
+                lib.get(b"nvmlDeviceGetFieldValues").ok();
+            let init_started = Instant::now();
             init();
-            let mut device: NvmlDevice_t = ptr::null_mut();
-            if get_device_handle(0, &mut device) != NVML_SUCCESS {
-                error!("Failed to get NVML device handle");
+            report.record_latency(persistence::NVML_INIT_STAGE, init_started.elapsed());
+            let mut count = 0u32;
+            if get_count(&mut count) != NVML_SUCCESS {
+                error!("Failed to get NVML device count");
                 shutdown();
                 return Err(crate::gpu_info::GpuError::GpuNotFound);
             }
-            let mut temp = 0u32;
-            let temperature = if get_temp(device, NVML_TEMPERATURE_GPU, &mut temp) == NVML_SUCCESS {
-                Some(temp as f32)
-            } else {
-                None
-            };
-            let mut name_buf = [0i8; 64];
-            let name = if get_name(device, name_buf.as_mut_ptr(), 64) == NVML_SUCCESS {
-                Some(
-                    std::ffi::CStr::from_ptr(name_buf.as_ptr())
-                        .to_string_lossy()
-                        .to_string(),
-                )
-            } else {
-                Some("NVIDIA GPU".to_string())
-            };
-            let mut util = NvmlUtilization { gpu: 0, memory: 0 };
-            let (gpu_util, mem_util) = if get_util(device, &mut util) == NVML_SUCCESS {
-                (Some(util.gpu as f32), Some(util.memory as f32))
-            } else {
-                (None, None)
-            };
-            let mut power = 0u32;
-            let power_usage = if get_power(device, &mut power) == NVML_SUCCESS {
-                Some((power as f32) / 1000.0)
-            } else {
-                None
-            };
-            let mut clock = 0u32;
-            let core_clock = if get_clock(device, NVML_CLOCK_GRAPHICS, &mut clock) == NVML_SUCCESS {
-                Some(clock)
-            } else {
-                None
-            };
-            let mut mem_info = NvmlMemory {
-                total: 0,
-                free: 0,
-                used: 0,
-            };
-            let (memory_total, memory_used) = if get_meminfo(device, &mut mem_info) == NVML_SUCCESS
-            {
-                (
-                    Some((mem_info.total / 1024 / 1024) as u32),
-                    Some((mem_info.used / 1024 / 1024) as u32),
-                )
-            } else {
-                (None, None)
-            };
+            let mut devices = Vec::new();
+            for index in 0..count {
+                if token.is_cancelled() {
+                    break;
+                }
+                let mut device: NvmlDevice_t = ptr::null_mut();
+                if get_device_handle(index, &mut device) != NVML_SUCCESS {
+                    error!("Failed to get handle of NVML device {}", index);
+                    continue;
+                }
+                let mut temp = 0u32;
+                let temperature =
+                    if get_temp(device, NVML_TEMPERATURE_GPU, &mut temp) == NVML_SUCCESS {
+                        Some(temp as f32)
+                    } else {
+                        None
+                    };
+                // Mostly reported by HBM cards
+                let memory_temperature = get_field_values.as_ref().and_then(|get_field_values| {
+                    field_value_from(NVML_FI_DEV_MEMORY_TEMP, |value| {
+                        get_field_values(device, 1, value)
+                    })
+                });
+                let mut name_buf = [0i8; 64];
+                let name = if get_name(device, name_buf.as_mut_ptr(), 64) == NVML_SUCCESS {
+                    Some(
+                        std::ffi::CStr::from_ptr(name_buf.as_ptr())
+                            .to_string_lossy()
+                            .to_string(),
+                    )
+                } else {
+                    Some("NVIDIA GPU".to_string())
+                };
+                let mut util = NvmlUtilization { gpu: 0, memory: 0 };
+                let (gpu_util, mem_util) = if get_util(device, &mut util) == NVML_SUCCESS {
+                    (Some(util.gpu as f32), Some(util.memory as f32))
+                } else {
+                    (None, None)
+                };
+                let mut power = 0u32;
+                let power_usage = if get_power(device, &mut power) == NVML_SUCCESS {
+                    Some((power as f32) / 1000.0)
+                } else {
+                    None
+                };
+                let mut clock = 0u32;
+                let current_clock = (get_clock(device, NVML_CLOCK_GRAPHICS, &mut clock)
+                    == NVML_SUCCESS)
+                    .then_some(clock);
+                // NVML reports no requested clock like AMD's DPM state. The
+                // application clock target is what the driver aims for, and the
+                // current clock falls below it under power or thermal throttling
+                let target_clock = get_clock_by_id.as_ref().and_then(|get_clock_by_id| {
+                    let mut clock = 0u32;
+                    (get_clock_by_id(
+                        device,
+                        NVML_CLOCK_GRAPHICS,
+                        NVML_CLOCK_ID_APP_CLOCK_TARGET,
+                        &mut clock,
+                    ) == NVML_SUCCESS)
+                        .then_some(clock)
+                });
+                let (core_clock, effective_core_clock) = match target_clock {
+                    Some(target_clock) => (Some(target_clock), current_clock),
+                    None => (current_clock, None),
+                };
+                // Passively cooled GPUs report NVML_ERROR_NOT_SUPPORTED
+                let fan_speed = get_fan_speed.as_ref().and_then(|get_fan_speed| {
+                    let mut speed = 0u32;
+                    (get_fan_speed(device, &mut speed) == NVML_SUCCESS).then_some(speed)
+                });
+                // GPUs without NVENC or NVDEC report NVML_ERROR_NOT_SUPPORTED
+                let encoder_util = get_encoder_util.as_ref().and_then(|get_encoder_util| {
+                    codec_utilization_from(|utilization, period| {
+                        get_encoder_util(device, utilization, period)
+                    })
+                });
+                let decoder_util = get_decoder_util.as_ref().and_then(|get_decoder_util| {
+                    codec_utilization_from(|utilization, period| {
+                        get_decoder_util(device, utilization, period)
+                    })
+                });
+                let throttle_reasons =
+                    get_throttle_reasons
+                        .as_ref()
+                        .and_then(|get_throttle_reasons| {
+                            throttle_reasons_from(|reasons| get_throttle_reasons(device, reasons))
+                        });
+                let ecc_errors = get_ecc_errors.as_ref().and_then(|get_ecc_errors| {
+                    ecc_stats_from(|error_type, counter_type, count| {
+                        get_ecc_errors(device, error_type, counter_type, count)
+                    })
+                });
+                let uuid = get_uuid.as_ref().and_then(|get_uuid| {
+                    uuid_from(|buffer, length| get_uuid(device, buffer, length))
+                });
+                let bus_id = get_pci_info
+                    .as_ref()
+                    .and_then(|get_pci_info| pci_bus_id_from(|info| get_pci_info(device, info)));
+                let list_processes = |query: Option<&Symbol<NvmlDeviceGetRunningProcessesFn>>| {
+                    query.and_then(|query| {
+                        running_processes_from(|count, infos| {
+                            let buffer = if infos.is_empty() {
+                                ptr::null_mut()
+                            } else {
+                                infos.as_mut_ptr()
+                            };
+                            query(device, count, buffer)
+                        })
+                    })
+                };
+                let processes = processes_from(
+                    list_processes(get_compute_processes.as_ref()),
+                    list_processes(get_graphics_processes.as_ref()),
+                );
+                let mut mem_info = NvmlMemory {
+                    total: 0,
+                    free: 0,
+                    used: 0,
+                };
+                let (memory_total, memory_used) =
+                    if get_meminfo(device, &mut mem_info) == NVML_SUCCESS {
+                        (
+                            Some((mem_info.total / 1024 / 1024) as u32),
+                            Some((mem_info.used / 1024 / 1024) as u32),
+                        )
+                    } else {
+                        (None, None)
+                    };
+                let persistence_mode = get_persistence.as_ref().and_then(|get_persistence| {
+                    persistence::persistence_mode_from(|mode| get_persistence(device, mode))
+                });
+                let compute_capability =
+                    get_compute_capability
+                        .as_ref()
+                        .and_then(|get_compute_capability| {
+                            compute_capability_from(|major, minor| {
+                                get_compute_capability(device, major, minor)
+                            })
+                        });
+                let pcie_replay_count =
+                    get_replay_counter.as_ref().and_then(|get_replay_counter| {
+                        let mut count = 0u32;
+                        (get_replay_counter(device, &mut count) == NVML_SUCCESS)
+                            .then_some(count as u64)
+                    });
+                let [pcie_generation, pcie_width, pcie_generation_max, pcie_width_max] =
+                    [0, 1, 2, 3].map(|query| {
+                        get_pcie_link[query]
+                            .as_ref()
+                            .and_then(|get| pcie_link_value_from(|value| get(device, value)))
+                    });
+                let connection_info = ConnectionInfo {
+                    pcie_generation,
+                    pcie_width,
+                    pcie_generation_max,
+                    pcie_width_max,
+                    pcie_replay_count,
+                    ..Default::default()
+                };
+                let nvml_info = GpuInfo {
+                    vendor: Vendor::Nvidia,
+                    name_gpu: name,
+                    temperature,
+                    temperatures: sensor_temperatures(temperature, memory_temperature),
+                    utilization: gpu_util,
+                    memory_util: mem_util,
+                    power_usage,
+                    core_clock,
+                    memory_clock: None,
+                    max_clock_speed: None,
+                    effective_core_clock,
+                    fan_speed,
+                    fan_speed_rpm: None,
+                    encoder_util,
+                    decoder_util,
+                    throttle_reasons,
+                    uuid,
+                    bus_id,
+                    processes,
+                    ecc_errors,
+                    metric_sources: Default::default(),
+                    confidence: Confidence::High,
+                    adapter_luid: None,
+                    active: Some(true),
+                    power_limit: None,
+                    memory_total,
+                    memory_used,
+                    driver_version: None,
+                };
+                let gpu_info = match nvml_info.bus_id.as_deref().and_then(|bus_id| {
+                    sysfs_reading(
+                        Path::new(DRM_CLASS_PATH),
+                        Path::new(NVIDIA_MODULE_VERSION_PATH),
+                        bus_id,
+                    )
+                }) {
+                    Some(sysfs_info) => metric_source::compose(
+                        Vendor::Nvidia,
+                        &[
+                            (MetricSource::Nvml, &nvml_info),
+                            (MetricSource::Sysfs, &sysfs_info),
+                        ],
+                    ),
+                    None => nvml_info,
+                };
+                devices.push(DeviceReading {
+                    gpu: gpu_info,
+                    persistence_mode,
+                    compute_capability,
+                    connection_info,
+                });
+            }
             shutdown();
-            let gpu_info = GpuInfo {
-                vendor: Vendor::Nvidia,
-                name_gpu: name,
-                temperature,
-                utilization: gpu_util,
-                memory_util: mem_util,
-                power_usage,
-                core_clock,
-                memory_clock: None,
-                max_clock_speed: None,
-                active: Some(true),
-                power_limit: None,
-                memory_total,
-                memory_used,
-                driver_version: None,
-            };
-            Ok(vec![gpu_info])
+            // A single device without persistence mode makes initialization slow
+            let persistence_mode = devices
+                .iter()
+                .map(|device| device.persistence_mode)
+                .find(|mode| *mode != Some(true))
+                .unwrap_or(Some(true));
+            if let Some(hint) = persistence::persistence_hint(
+                report,
+                persistence_mode,
+                persistence::persistenced_running,
+            ) {
+                info!("NVML initialization was slow: {}", hint);
+                report.add_hint(hint);
+            }
+            if devices.is_empty() {
+                return Err(crate::gpu_info::GpuError::GpuNotFound);
+            }
+            Ok(devices)
+        }
+    }
+}
+
+impl Default for NvidiaLinuxProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GpuProvider for NvidiaLinuxProvider {
+    fn detect_gpus(&self) -> Result<Vec<GpuInfo>> {
+        self.detect_with_report(&mut DetectionReport::new())
+    }
+
+    /// The token is checked before NVML is loaded and before each device
+    /// is read.
+    fn detect_gpus_with_cancel(
+        &self,
+        token: &CancelToken,
+        report: &mut DetectionReport,
+    ) -> Result<Vec<GpuInfo>> {
+        if token.is_cancelled() {
+            return Ok(Vec::new());
         }
+        self.detect_with_cancel(token, report)
     }
 
+    /// Reads every device again and takes the one with the bus ID or UUID
+    /// of `gpu`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GpuError::GpuNotFound`](crate::gpu_info::GpuError::GpuNotFound)
+    /// if NVML no longer lists the GPU.
     fn update_gpu(&self, gpu: &mut GpuInfo) -> Result<()> {
         let gpus = self.detect_gpus()?;
-        if let Some(updated_gpu) = gpus.first() {
-            *gpu = updated_gpu.clone();
-        }
-        Ok(())
+        update_gpu_from_api(gpu, || gpus)
     }
 
     fn get_vendor(&self) -> Vendor {
         Vendor::Nvidia
     }
+
+    fn supports_metric(&self, metric: Metric) -> bool {
+        SUPPORTED_METRICS.contains(&metric)
+    }
 }
@@ -0,0 +1,163 @@
+//! PCIe link state and error counters for Linux.
+//!
+//! Flaky risers and marginal links show up as a stream of correctable
+//! errors long before a GPU falls off the bus. The kernel exposes these
+//! Advanced Error Reporting (AER) counters per PCI device, for any vendor,
+//! next to the negotiated and maximum link speed and width.
+//!
+//! # Data Sources
+//!
+//! - `/sys/bus/pci/devices/<bus_id>/aer_dev_correctable` - Correctable errors per type
+//! - `/sys/bus/pci/devices/<bus_id>/{current,max}_link_{speed,width}` - Link state
+//! - `max_link_{speed,width}` of the parent directory - Upstream port (slot) maximum
+//! - `/sys/class/drm/cardX/device` - Resolves to the device's bus id
+
+use crate::extended_info::{ConnectionInfo, PcieAerCounters};
+use crate::gpu_identity::is_bus_id;
+use log::debug;
+use std::fs;
+use std::path::Path;
+
+/// Sysfs directory listing PCI devices by bus id.
+pub const PCI_DEVICES_PATH: &str = "/sys/bus/pci/devices";
+
+/// Line holding the total in `aer_dev_correctable`.
+const AER_TOTAL_KEY: &str = "TOTAL_ERR_COR";
+
+/// Transfer rate in GT/s of each PCIe generation.
+const LINK_SPEEDS: [(f32, u8); 6] = [
+    (2.5, 1),
+    (5.0, 2),
+    (8.0, 3),
+    (16.0, 4),
+    (32.0, 5),
+    (64.0, 6),
+];
+
+/// Parses the contents of an `aer_dev_correctable` file.
+///
+/// Each line holds an error type and its count, e.g. `BadTLP 3`, followed
+/// by a `TOTAL_ERR_COR` line. If the total line is missing the per-type
+/// counts are summed. Malformed lines are skipped.
+///
+/// # Returns
+/// * `Some(counters)` - At least one counter was parsed.
+/// * `None` - The file holds no counters.
+pub fn parse_aer_counters(content: &str) -> Option<PcieAerCounters> {
+    let mut total = None;
+    let mut by_type = Vec::new();
+    for line in content.lines() {
+        let mut fields = line.split_whitespace();
+        let (Some(name), Some(count), None) = (fields.next(), fields.next(), fields.next()) else {
+            continue;
+        };
+        let Ok(count) = count.parse::<u64>() else {
+            continue;
+        };
+        if name == AER_TOTAL_KEY {
+            total = Some(count);
+        } else {
+            by_type.push((name.to_string(), count));
+        }
+    }
+    if total.is_none() && by_type.is_empty() {
+        return None;
+    }
+    Some(PcieAerCounters {
+        total: total.unwrap_or_else(|| by_type.iter().map(|(_, count)| count).sum()),
+        by_type,
+    })
+}
+
+/// Reads the correctable AER counters of the device at `bus_id`.
+///
+/// `pci_devices_path` is normally [`PCI_DEVICES_PATH`]. Devices without
+/// AER support and files the process may not read (the counters are
+/// root-only on some kernels) both yield `None` without an error.
+pub fn aer_correctable(pci_devices_path: &Path, bus_id: &str) -> Option<PcieAerCounters> {
+    let path = pci_devices_path.join(bus_id).join("aer_dev_correctable");
+    match fs::read_to_string(&path) {
+        Ok(content) => parse_aer_counters(&content),
+        Err(e) => {
+            debug!("AER counters unavailable at {}: {}", path.display(), e);
+            None
+        }
+    }
+}
+
+/// Reads the bus id and AER counters of the device at `bus_id` into `connection`.
+///
+/// `pci_devices_path` is normally [`PCI_DEVICES_PATH`]. Works for any
+/// vendor; counters that cannot be read are left unchanged.
+pub fn fill_errors(connection: &mut ConnectionInfo, pci_devices_path: &Path, bus_id: &str) {
+    connection.pcie_aer_correctable =
+        aer_correctable(pci_devices_path, bus_id).or(connection.pcie_aer_correctable.take());
+    connection.bus_id = Some(bus_id.to_string());
+}
+
+/// Parses a `current_link_speed` or `max_link_speed` file into a PCIe generation.
+///
+/// The kernel writes the transfer rate, e.g. `16.0 GT/s PCIe`, or `Unknown`
+/// for a link that is down.
+///
+/// # Returns
+/// * `Some(generation)` - The rate of a known generation, e.g. `4` for 16 GT/s.
+/// * `None` - An unknown or unparsable rate.
+pub fn parse_link_speed(content: &str) -> Option<u8> {
+    let rate: f32 = content.split_whitespace().next()?.parse().ok()?;
+    LINK_SPEEDS
+        .iter()
+        .find(|(speed, _)| (speed - rate).abs() < 0.1)
+        .map(|&(_, generation)| generation)
+}
+
+/// Parses a `current_link_width` or `max_link_width` file, e.g. `16`.
+///
+/// A width of 0, reported for a link that is down, yields `None`.
+pub fn parse_link_width(content: &str) -> Option<u8> {
+    content.trim().parse().ok().filter(|&width| width > 0)
+}
+
+/// Reads the negotiated and maximum link of the device at `bus_id`.
+///
+/// `pci_devices_path` is normally [`PCI_DEVICES_PATH`]. The device's
+/// `max_link_*` files report what the device supports, not the slot, so
+/// the maximum is capped by that of the upstream port, the parent directory
+/// of the resolved device. Only the values that could be read are written
+/// to `connection`.
+pub fn fill_link(connection: &mut ConnectionInfo, pci_devices_path: &Path, bus_id: &str) {
+    let device = pci_devices_path.join(bus_id);
+    let read = |dir: &Path, name: &str, parse: fn(&str) -> Option<u8>| {
+        fs::read_to_string(dir.join(name))
+            .ok()
+            .and_then(|content| parse(&content))
+    };
+    let upstream = fs::canonicalize(&device).ok().and_then(|resolved| {
+        let port = resolved.parent()?;
+        is_bus_id(port.file_name()?.to_str()?).then(|| port.to_path_buf())
+    });
+    let max = |name: &str, parse: fn(&str) -> Option<u8>| {
+        let device_max = read(&device, name, parse)?;
+        let port_max = upstream.as_deref().and_then(|port| read(port, name, parse));
+        Some(port_max.map_or(device_max, |port_max| device_max.min(port_max)))
+    };
+    connection.pcie_generation =
+        read(&device, "current_link_speed", parse_link_speed).or(connection.pcie_generation);
+    connection.pcie_width =
+        read(&device, "current_link_width", parse_link_width).or(connection.pcie_width);
+    connection.pcie_generation_max =
+        max("max_link_speed", parse_link_speed).or(connection.pcie_generation_max);
+    connection.pcie_width_max =
+        max("max_link_width", parse_link_width).or(connection.pcie_width_max);
+}
+
+/// Returns the PCI bus id of a device, e.g. `0000:01:00.0`.
+///
+/// `device_path` is a sysfs device directory such as
+/// `/sys/class/drm/card0/device`, which links to the PCI device directory
+/// named after the bus id.
+pub(crate) fn bus_id(device_path: &Path) -> Option<String> {
+    let resolved = fs::canonicalize(device_path).ok()?;
+    let name = resolved.file_name()?.to_str()?;
+    is_bus_id(name).then(|| name.to_string())
+}
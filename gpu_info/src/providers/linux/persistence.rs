@@ -0,0 +1,118 @@
+//! NVIDIA persistence mode detection for Linux.
+//!
+//! When persistence mode is disabled and `nvidia-persistenced` is not running,
+//! the driver tears down GPU state as soon as the last client exits. Every
+//! NVML initialization then has to bring the GPU back up, which can take
+//! several seconds. This module detects that situation so it can be reported
+//! as an actionable hint instead of just being slow.
+//!
+//! # Detection
+//!
+//! - Persistence mode is read through `nvmlDeviceGetPersistenceMode`
+//! - The daemon is detected through its pidfile, falling back to a `/proc` scan
+
+use crate::detection_report::DetectionReport;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+/// Default location of the `nvidia-persistenced` pidfile.
+pub const PERSISTENCED_PIDFILE: &str = "/var/run/nvidia-persistenced/nvidia-persistenced.pid";
+
+/// Stage under which the NVML initialization latency is recorded in the
+/// [`DetectionReport`].
+pub const NVML_INIT_STAGE: &str = "nvml_init";
+
+/// NVML initialization slower than this is considered a cold start.
+pub const PERSISTENCE_HINT_THRESHOLD: Duration = Duration::from_millis(500);
+
+/// Hint attached to the detection report when a slow cold start is detected.
+pub const PERSISTENCE_HINT: &str = "enable persistence mode to speed up GPU queries";
+
+/// NVML value for `NVML_FEATURE_ENABLED`.
+const NVML_FEATURE_ENABLED: u32 = 1;
+
+/// Process name as it appears in `/proc/<pid>/comm` (truncated to 15 bytes).
+const PERSISTENCED_COMM: &str = "nvidia-persiste";
+
+/// Interprets the result of an `nvmlDeviceGetPersistenceMode` call.
+///
+/// `query` receives the output parameter and returns the NVML status code,
+/// which makes the FFI call easy to substitute in tests.
+///
+/// # Returns
+///
+/// * `Some(true)` / `Some(false)` - If the call succeeded
+/// * `None` - If the call failed or is not supported by the driver
+pub fn persistence_mode_from<F>(query: F) -> Option<bool>
+where
+    F: FnOnce(&mut u32) -> i32,
+{
+    let mut mode = 0u32;
+    if query(&mut mode) == 0 {
+        Some(mode == NVML_FEATURE_ENABLED)
+    } else {
+        None
+    }
+}
+
+/// Returns `true` if `nvidia-persistenced` is running.
+pub fn persistenced_running() -> bool {
+    persistenced_running_at(Path::new(PERSISTENCED_PIDFILE), Path::new("/proc"))
+}
+
+/// Returns `true` if `nvidia-persistenced` is running, using the given
+/// pidfile and procfs root.
+///
+/// The pidfile is checked first; if it is missing or stale, every numeric
+/// entry under `proc_root` is scanned for a matching `comm`.
+pub(crate) fn persistenced_running_at(pidfile: &Path, proc_root: &Path) -> bool {
+    if let Ok(content) = fs::read_to_string(pidfile) {
+        if let Ok(pid) = content.trim().parse::<u32>() {
+            if proc_root.join(pid.to_string()).exists() {
+                return true;
+            }
+        }
+    }
+    let entries = match fs::read_dir(proc_root) {
+        Ok(entries) => entries,
+        Err(_) => return false,
+    };
+    entries.flatten().any(|entry| {
+        let is_pid = entry
+            .file_name()
+            .to_str()
+            .is_some_and(|name| name.bytes().all(|b| b.is_ascii_digit()));
+        is_pid
+            && fs::read_to_string(entry.path().join("comm"))
+                .map(|comm| comm.trim() == PERSISTENCED_COMM)
+                .unwrap_or(false)
+    })
+}
+
+/// Decides whether a persistence hint should be reported.
+///
+/// A hint is produced when the NVML initialization latency recorded in
+/// `report` under [`NVML_INIT_STAGE`] exceeded
+/// [`PERSISTENCE_HINT_THRESHOLD`], persistence mode is not known to be
+/// enabled, and the persistence daemon is not running.
+///
+/// `daemon_running` is only called once the first two conditions hold, so
+/// the `/proc` scan is skipped on the common fast path.
+pub fn persistence_hint<F>(
+    report: &DetectionReport,
+    persistence_mode: Option<bool>,
+    daemon_running: F,
+) -> Option<&'static str>
+where
+    F: FnOnce() -> bool,
+{
+    let slow = report
+        .latency(NVML_INIT_STAGE)
+        .is_some_and(|latency| latency > PERSISTENCE_HINT_THRESHOLD);
+    if slow && persistence_mode != Some(true) && !daemon_running() {
+        Some(PERSISTENCE_HINT)
+    } else {
+        None
+    }
+}
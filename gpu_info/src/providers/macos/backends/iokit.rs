@@ -28,11 +28,11 @@
 //! # }
 //! ```
 
-use crate::gpu_info::{GpuInfo, Result};
+use crate::gpu_info::{Confidence, GpuInfo, Result};
 
 #[cfg(not(feature = "macos-iokit"))]
 use crate::gpu_info::GpuError;
-use crate::vendor::{IntelGpuType, Vendor};
+use crate::vendor::Vendor;
 use log::{debug, warn};
 
 #[cfg(feature = "macos-iokit")]
@@ -115,13 +115,7 @@ impl PciInfo {
 
     /// Determines the vendor from the vendor ID
     pub fn vendor(&self) -> Vendor {
-        match self.vendor_id {
-            0x10DE => Vendor::Nvidia,
-            0x1002 => Vendor::Amd,
-            0x8086 => Vendor::Intel(IntelGpuType::Unknown),
-            0x106B => Vendor::Apple,
-            _ => Vendor::Unknown,
-        }
+        Vendor::from_pci_id(self.vendor_id)
     }
 }
 
@@ -368,6 +362,7 @@ impl IOKitBackend {
     fn create_gpu_info_from_pci(&self, pci_info: &PciInfo) -> Result<GpuInfo> {
         let mut gpu = GpuInfo {
             vendor: pci_info.vendor(),
+            confidence: Confidence::High,
             ..Default::default()
         };
 
@@ -532,6 +527,7 @@ impl IOKitBackend {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::vendor::IntelGpuType;
 
     #[test]
     fn test_pci_info_creation() {
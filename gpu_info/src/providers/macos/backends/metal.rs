@@ -30,6 +30,7 @@
 //! # }
 //! ```
 
+use crate::extended_info::MetalInfo;
 #[cfg(not(feature = "macos-metal"))]
 use crate::gpu_info::GpuError;
 use crate::gpu_info::{GpuInfo, Result};
@@ -41,7 +42,7 @@ use log::{debug, warn};
 //
 // Required imports when implementing:
 // #[cfg(feature = "macos-metal")]
-// use metal::{Device, MTLResourceOptions, MTLSize};
+// use metal_rs::{Device, MTLResourceOptions, MTLSize};
 //
 // Metal API documentation:
 // https://developer.apple.com/documentation/metal
@@ -52,7 +53,7 @@ mod ffi {
     // When implementing, replace these with actual Metal types
 
     /// Placeholder for Metal Device
-    /// Should be: metal::Device
+    /// Should be: metal_rs::Device
     pub type MetalDevice = usize;
 
     /// Placeholder for Metal command queue
@@ -108,6 +109,89 @@ impl MemoryMetrics {
     }
 }
 
+/// Metal GPU family names, most capable first.
+///
+/// Apple families come before Mac families: Apple silicon supports both, and
+/// the Apple family describes it more precisely.
+pub const GPU_FAMILIES: [&str; 10] = [
+    "Apple8", "Apple7", "Apple6", "Apple5", "Apple4", "Apple3", "Apple2", "Apple1", "Mac2", "Mac1",
+];
+
+/// Capability values as reported by an `MTLDevice`
+///
+/// Kept free of Metal types so the mapping to [`MetalInfo`] can be tested
+/// on any platform.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DeviceCapabilities {
+    /// Names from [`GPU_FAMILIES`] for which `supportsFamily:` returned true
+    pub supported_families: Vec<&'static str>,
+
+    /// Result of `supportsRaytracing`
+    pub supports_raytracing: bool,
+
+    /// Result of `maxBufferLength` in bytes
+    pub max_buffer_length: u64,
+
+    /// Result of `recommendedMaxWorkingSetSize` in bytes
+    pub recommended_max_working_set: u64,
+
+    /// Result of `hasUnifiedMemory`
+    pub has_unified_memory: bool,
+}
+
+impl DeviceCapabilities {
+    /// Returns the most capable family in `supported_families`
+    pub fn highest_gpu_family(&self) -> Option<&'static str> {
+        GPU_FAMILIES
+            .iter()
+            .copied()
+            .find(|family| self.supported_families.contains(family))
+    }
+
+    /// Converts the raw values into [`MetalInfo`]
+    ///
+    /// Zero sizes mean the device did not report a value and map to `None`.
+    pub fn to_metal_info(&self) -> MetalInfo {
+        MetalInfo {
+            metal_gpu_family: self.highest_gpu_family().map(str::to_string),
+            supports_raytracing: Some(self.supports_raytracing),
+            max_buffer_length: Some(self.max_buffer_length).filter(|len| *len > 0),
+            recommended_max_working_set: Some(self.recommended_max_working_set)
+                .filter(|size| *size > 0),
+            has_unified_memory: Some(self.has_unified_memory),
+        }
+    }
+}
+
+/// Reads the capabilities of a Metal device
+#[cfg(all(target_os = "macos", feature = "macos-metal"))]
+fn read_capabilities(device: &metal_rs::DeviceRef) -> DeviceCapabilities {
+    use metal_rs::MTLGPUFamily;
+    let families = [
+        (MTLGPUFamily::Apple8, "Apple8"),
+        (MTLGPUFamily::Apple7, "Apple7"),
+        (MTLGPUFamily::Apple6, "Apple6"),
+        (MTLGPUFamily::Apple5, "Apple5"),
+        (MTLGPUFamily::Apple4, "Apple4"),
+        (MTLGPUFamily::Apple3, "Apple3"),
+        (MTLGPUFamily::Apple2, "Apple2"),
+        (MTLGPUFamily::Apple1, "Apple1"),
+        (MTLGPUFamily::Mac2, "Mac2"),
+        (MTLGPUFamily::Mac1, "Mac1"),
+    ];
+    DeviceCapabilities {
+        supported_families: families
+            .iter()
+            .filter(|(family, _)| device.supports_family(*family))
+            .map(|(_, name)| *name)
+            .collect(),
+        supports_raytracing: device.supports_raytracing(),
+        max_buffer_length: device.max_buffer_length() as u64,
+        recommended_max_working_set: device.recommended_max_working_set_size(),
+        has_unified_memory: device.has_unified_memory(),
+    }
+}
+
 /// GPU utilization metrics
 ///
 /// Tracks GPU compute and render utilization over time.
@@ -321,6 +405,28 @@ impl MetalBackend {
         Ok(())
     }
 
+    /// Returns the capabilities of the Metal device named `name`
+    ///
+    /// Uses the system default device if `name` is `None`. Returns `None`
+    /// if no matching device exists or Metal is unavailable.
+    pub fn metal_info(&self, name: Option<&str>) -> Option<MetalInfo> {
+        #[cfg(target_os = "macos")]
+        {
+            let device = match name {
+                Some(name) => metal_rs::Device::all()
+                    .into_iter()
+                    .find(|device| device.name() == name),
+                None => metal_rs::Device::system_default(),
+            };
+            device.map(|device| read_capabilities(&device).to_metal_info())
+        }
+        #[cfg(not(target_os = "macos"))]
+        {
+            debug!("Metal is not available, no capabilities for {:?}", name);
+            None
+        }
+    }
+
     /// Enumerates all Metal devices
     ///
     /// TODO: Implement Metal device enumeration (REQUIRES macOS hardware)
@@ -333,7 +439,7 @@ impl MetalBackend {
     ///
     /// Example implementation:
     /// ```rust,ignore
-    /// use metal::Device;
+    /// use metal_rs::Device;
     ///
     /// let devices = Device::all();
     /// let gpu_devices: Vec<_> = devices
@@ -579,6 +685,50 @@ mod tests {
         assert!(heavy.is_heavy_load());
     }
 
+    #[test]
+    fn test_highest_gpu_family_prefers_apple_families() {
+        let caps = DeviceCapabilities {
+            supported_families: vec!["Mac2", "Apple6", "Apple7", "Apple1"],
+            ..Default::default()
+        };
+        assert_eq!(caps.highest_gpu_family(), Some("Apple7"));
+
+        let intel_mac = DeviceCapabilities {
+            supported_families: vec!["Mac1", "Mac2"],
+            ..Default::default()
+        };
+        assert_eq!(intel_mac.highest_gpu_family(), Some("Mac2"));
+        assert_eq!(DeviceCapabilities::default().highest_gpu_family(), None);
+    }
+
+    #[test]
+    fn test_capabilities_to_metal_info() {
+        let caps = DeviceCapabilities {
+            supported_families: vec!["Apple8", "Mac2"],
+            supports_raytracing: true,
+            max_buffer_length: 17_179_869_184,
+            recommended_max_working_set: 22_906_503_168,
+            has_unified_memory: true,
+        };
+        let info = caps.to_metal_info();
+        assert_eq!(info.metal_gpu_family.as_deref(), Some("Apple8"));
+        assert_eq!(info.supports_raytracing, Some(true));
+        assert_eq!(info.max_buffer_length, Some(17_179_869_184));
+        assert_eq!(info.recommended_max_working_set, Some(22_906_503_168));
+        assert_eq!(info.has_unified_memory, Some(true));
+        assert_eq!(info.format_max_buffer_length(), "16.00 GB");
+    }
+
+    #[test]
+    fn test_unreported_sizes_map_to_none() {
+        let info = DeviceCapabilities::default().to_metal_info();
+        assert_eq!(info.metal_gpu_family, None);
+        assert_eq!(info.max_buffer_length, None);
+        assert_eq!(info.recommended_max_working_set, None);
+        assert_eq!(info.supports_raytracing, Some(false));
+        assert_eq!(info.format_gpu_family(), "N/A");
+    }
+
     #[cfg(feature = "macos-metal")]
     #[test]
     fn test_metal_backend_creation() {
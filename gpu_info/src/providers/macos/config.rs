@@ -138,6 +138,23 @@ impl MacosBackend {
         }
     }
 
+    /// Returns true if this backend runs an external program
+    ///
+    /// Such backends cannot be used inside the App Sandbox, see
+    /// [`sandbox`](crate::providers::macos::sandbox).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gpu_info::providers::macos::MacosBackend;
+    ///
+    /// assert!(MacosBackend::SystemProfiler.requires_subprocess());
+    /// assert!(!MacosBackend::Hybrid.requires_subprocess());
+    /// ```
+    pub fn requires_subprocess(&self) -> bool {
+        matches!(self, Self::PowerMetrics | Self::SystemProfiler)
+    }
+
     /// Returns true if this backend is available on the current system
     ///
     /// # Examples
@@ -36,10 +36,19 @@
 //! }
 //! # }
 //! ```
-use crate::gpu_info::{GpuInfo, GpuProvider, Result};
+use crate::gpu_info::{Confidence, GpuInfo, GpuProvider, Result};
+use crate::metric_source::Metric;
 use crate::vendor::Vendor;
 use log::{debug, info, warn};
 use std::process::Command;
+
+/// Metrics this provider reports
+const SUPPORTED_METRICS: &[Metric] = &[
+    Metric::Temperature,
+    Metric::Utilization,
+    Metric::CoreClock,
+    Metric::MemoryTotal,
+];
 /// GPU provider for macOS systems.
 ///
 /// This provider uses system_profiler and IOKit to detect and query
@@ -262,6 +271,7 @@ impl MacosProvider {
                 core_clock: self.estimate_apple_gpu_clock(&cpu_info),
                 utilization: self.get_apple_gpu_utilization(),
                 temperature: self.get_apple_gpu_temperature(),
+                confidence: Confidence::Medium,
                 ..Default::default()
             });
         }
@@ -449,6 +459,10 @@ impl GpuProvider for MacosProvider {
         // This is a generic provider that can detect multiple vendors
         Vendor::Unknown
     }
+    /// Whether `metric` is one this provider reports
+    fn supports_metric(&self, metric: Metric) -> bool {
+        SUPPORTED_METRICS.contains(&metric)
+    }
 }
 
 // TODO: there should be no tests here. Transfer them to gpu_info\src\test
@@ -513,6 +527,18 @@ mod tests {
         assert_eq!(provider.extract_vram_from_name(""), None);
     }
 
+    #[test]
+    fn test_system_profiler_name_heuristics_are_low_confidence() {
+        let provider = MacosProvider::new();
+        let xml =
+            "<dict>\n<key>sppci_model</key>\n<string>AMD Radeon Pro 5500M 8GB</string>\n</dict>";
+        let gpus = provider.parse_system_profiler_output(xml);
+        assert_eq!(gpus.len(), 1);
+        assert_eq!(gpus[0].vendor, Vendor::Amd);
+        assert_eq!(gpus[0].memory_total, Some(8));
+        assert_eq!(gpus[0].confidence, Confidence::Low);
+    }
+
     #[test]
     fn test_determine_vendor() {
         let provider = MacosProvider::new();
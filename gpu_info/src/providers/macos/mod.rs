@@ -11,6 +11,9 @@
 //! - **PowerMetrics**: CLI tool, ~100ms (metrics fallback)
 //! - **Hybrid**: Auto-selects best available backend (recommended)
 //!
+//! Inside the App Sandbox, the subprocess-based backends (SystemProfiler and
+//! PowerMetrics) are disabled; see [`sandbox`].
+//!
 //! # Feature Flags
 //!
 //! - `macos-iokit`: Enables IOKit backend for fast detection
@@ -50,6 +53,7 @@ pub mod config;
 pub mod metrics;
 pub mod provider;
 pub mod router;
+pub mod sandbox;
 
 // Legacy module (will be migrated in future tasks)
 #[allow(clippy::module_inception)]
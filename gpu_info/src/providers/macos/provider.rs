@@ -7,7 +7,10 @@ use super::cache::GpuCache;
 use super::config::{MacosBackend, MacosConfig};
 use super::metrics::MacosMetrics;
 use super::router::{BackendRouter, Operation};
+use super::sandbox::{self, CommandRunner, Environment, SystemCommandRunner, SystemEnvironment};
+use crate::detection_report::DetectionReport;
 use crate::gpu_info::{GpuInfo, GpuProvider, Result};
+use crate::metric_source::{self, Metric, MetricSource, PriorityTable};
 use log::{debug, info, warn};
 use std::time::Instant;
 
@@ -17,6 +20,29 @@ use super::backends::IOKitBackend;
 #[cfg(feature = "macos-metal")]
 use super::backends::MetalBackend;
 
+/// Source priority per metric for GPUs on macOS
+///
+/// Follows the router's order for dynamic metrics (Metal before
+/// PowerMetrics) and for temperature (IOKit and Metal before PowerMetrics).
+pub(crate) const METRIC_PRIORITY: &PriorityTable = &[
+    (
+        Metric::Utilization,
+        &[MetricSource::Metal, MetricSource::PowerMetrics],
+    ),
+    (
+        Metric::Temperature,
+        &[
+            MetricSource::IoKit,
+            MetricSource::Metal,
+            MetricSource::PowerMetrics,
+        ],
+    ),
+    (Metric::PowerUsage, &[MetricSource::PowerMetrics]),
+    (Metric::CoreClock, &[MetricSource::PowerMetrics]),
+    (Metric::MemoryUsed, &[MetricSource::Metal]),
+    (Metric::MemoryUtil, &[MetricSource::Metal]),
+];
+
 /// Main GPU provider for macOS
 ///
 /// Supports multiple backends with automatic fallback and smart caching.
@@ -102,9 +128,26 @@ impl MacosProvider {
     /// let provider = MacosProvider::with_config(config).expect("Failed to create provider");
     /// ```
     pub fn with_config(config: MacosConfig) -> Result<Self> {
+        Self::with_environment(config, &SystemEnvironment, &SystemCommandRunner)
+    }
+
+    /// Creates a provider using `env` for sandbox detection and `runner` for
+    /// probing command-line tools
+    ///
+    /// Inside the App Sandbox, the SystemProfiler and PowerMetrics backends
+    /// are skipped entirely and no process is spawned.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if provider initialization fails.
+    pub fn with_environment(
+        config: MacosConfig,
+        env: &dyn Environment,
+        runner: &dyn CommandRunner,
+    ) -> Result<Self> {
         info!("Initializing MacosProvider with config: {:?}", config);
 
-        let router = BackendRouter::new(config.clone());
+        let router = BackendRouter::with_environment(config.clone(), env, runner);
         let cache = GpuCache::new(config.cache_ttl);
 
         // Initialize backends
@@ -153,6 +196,28 @@ impl MacosProvider {
         self.router.is_backend_available(backend)
     }
 
+    /// Returns `true` if the process runs inside the App Sandbox
+    pub fn is_sandboxed(&self) -> bool {
+        self.router.is_sandboxed()
+    }
+
+    /// Detects GPUs and records diagnostics in `report`
+    ///
+    /// Inside the App Sandbox, adds the [`sandbox::SANDBOX_HINT`] hint.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the selected backend fails.
+    pub fn detect_with_report(&self, report: &mut DetectionReport) -> Result<Vec<GpuInfo>> {
+        if self.is_sandboxed() {
+            report.add_hint(sandbox::SANDBOX_HINT);
+        }
+        let start = Instant::now();
+        let gpus = self.detect_gpus()?;
+        report.record_latency("macos_detect", start.elapsed());
+        Ok(gpus)
+    }
+
     /// Clears the static information cache
     pub fn clear_cache(&mut self) {
         info!("Clearing GPU cache");
@@ -181,6 +246,18 @@ impl MacosProvider {
         self.last_metrics = Some(metrics);
     }
 
+    /// Detects GPUs with system_profiler, unless the sandbox forbids it
+    fn detect_with_system_profiler(&self) -> Result<Vec<GpuInfo>> {
+        if !self
+            .router
+            .is_backend_permitted(MacosBackend::SystemProfiler)
+        {
+            debug!("Skipping system_profiler: not permitted in the App Sandbox");
+            return Ok(Vec::new());
+        }
+        self.system_profiler.detect_gpus()
+    }
+
     /// Detects GPUs using the selected backend
     fn detect_gpus_with_backend(&self, backend: MacosBackend) -> Result<Vec<GpuInfo>> {
         match backend {
@@ -190,7 +267,7 @@ impl MacosProvider {
                     return iokit.detect_gpus();
                 }
                 warn!("IOKit backend not available, falling back");
-                self.system_profiler.detect_gpus()
+                self.detect_with_system_profiler()
             }
             #[cfg(feature = "macos-metal")]
             MacosBackend::Metal => {
@@ -198,39 +275,83 @@ impl MacosProvider {
                     return metal.detect_gpus();
                 }
                 warn!("Metal backend not available, falling back");
-                self.system_profiler.detect_gpus()
+                self.detect_with_system_profiler()
             }
             MacosBackend::PowerMetrics => {
                 // PowerMetrics is primarily for metrics, not detection
                 warn!("PowerMetrics not suitable for detection, using SystemProfiler");
-                self.system_profiler.detect_gpus()
+                self.detect_with_system_profiler()
             }
-            MacosBackend::SystemProfiler => self.system_profiler.detect_gpus(),
+            MacosBackend::SystemProfiler => self.detect_with_system_profiler(),
             MacosBackend::Hybrid => {
                 // This should not happen as router should resolve Hybrid
-                self.system_profiler.detect_gpus()
+                self.detect_with_system_profiler()
             }
         }
     }
 
     /// Updates GPU metrics using the selected backend
+    ///
+    /// Readings of every backend consulted are merged into `gpu` according
+    /// to [`METRIC_PRIORITY`].
     fn update_gpu_with_backend(&self, gpu: &mut GpuInfo, backend: MacosBackend) -> Result<()> {
+        let readings = self.read_metrics(gpu, backend)?;
+        let readings: Vec<_> = readings
+            .iter()
+            .map(|(source, reading)| (*source, reading))
+            .collect();
+        metric_source::apply(gpu, METRIC_PRIORITY, &readings);
+        Ok(())
+    }
+
+    /// Returns the sources metrics are read from with `backend` selected
+    fn metric_sources(&self, backend: MacosBackend) -> Vec<MetricSource> {
+        let powermetrics_permitted = self.router.is_backend_permitted(MacosBackend::PowerMetrics);
         match backend {
             #[cfg(feature = "macos-metal")]
-            MacosBackend::Metal => {
-                if let Some(ref metal) = self.metal {
-                    return metal.update_gpu(gpu);
-                }
-                // Fallback to powermetrics
-                self.powermetrics.update_gpu(gpu)
+            MacosBackend::Metal if self.metal.is_some() => vec![MetricSource::Metal],
+            // Fallback to powermetrics
+            #[cfg(feature = "macos-metal")]
+            MacosBackend::Metal if powermetrics_permitted => vec![MetricSource::PowerMetrics],
+            MacosBackend::PowerMetrics if powermetrics_permitted => {
+                vec![MetricSource::PowerMetrics]
             }
-            MacosBackend::PowerMetrics => self.powermetrics.update_gpu(gpu),
-            _ => {
-                // Other backends don't provide real-time metrics yet
-                // Just return Ok without updating
-                Ok(())
+            // Other backends don't provide real-time metrics yet
+            _ => Vec::new(),
+        }
+    }
+
+    /// Reads metrics for `gpu` from the selected backend and its fallbacks
+    fn read_metrics(
+        &self,
+        gpu: &GpuInfo,
+        backend: MacosBackend,
+    ) -> Result<Vec<(MetricSource, GpuInfo)>> {
+        let mut readings = Vec::new();
+        for source in self.metric_sources(backend) {
+            let mut reading = blank_reading(gpu);
+            match source {
+                #[cfg(feature = "macos-metal")]
+                MetricSource::Metal => {
+                    if let Some(ref metal) = self.metal {
+                        metal.update_gpu(&mut reading)?;
+                    }
+                }
+                MetricSource::PowerMetrics => self.powermetrics.update_gpu(&mut reading)?,
+                _ => continue,
             }
+            readings.push((source, reading));
         }
+        Ok(readings)
+    }
+}
+
+/// Returns an empty reading carrying only the identity of `gpu`
+fn blank_reading(gpu: &GpuInfo) -> GpuInfo {
+    GpuInfo {
+        vendor: gpu.vendor,
+        name_gpu: gpu.name_gpu.clone(),
+        ..GpuInfo::unknown()
     }
 }
 
@@ -276,6 +397,19 @@ impl GpuProvider for MacosProvider {
             crate::vendor::Vendor::Unknown
         }
     }
+
+    /// Whether the backend selected for dynamic metrics, or its fallback,
+    /// has a source for `metric` in [`METRIC_PRIORITY`]
+    ///
+    /// The system_profiler backend reports no dynamic metrics, so it
+    /// supports none of them.
+    fn supports_metric(&self, metric: Metric) -> bool {
+        let backend = self.router.select_backend(Operation::GetDynamicMetrics);
+        let sources = self.metric_sources(backend);
+        metric_source::priority(METRIC_PRIORITY, metric)
+            .iter()
+            .any(|source| sources.contains(source))
+    }
 }
 
 #[cfg(test)]
@@ -4,6 +4,8 @@
 //! based on availability, performance, and configuration.
 
 use super::config::{MacosBackend, MacosConfig};
+use super::sandbox::{self, CommandRunner, Environment, SystemCommandRunner, SystemEnvironment};
+use log::debug;
 
 /// Type of GPU operation to perform
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -26,9 +28,18 @@ pub enum Operation {
 /// - Backend availability
 /// - Operation type
 /// - Fallback chain when enabled
+///
+/// Availability has two dimensions: a backend is *present* if it exists on
+/// this system, and *permitted* if the process may use it. Inside the App
+/// Sandbox, subprocess-based backends are present but not permitted. Only
+/// backends that are both count as available.
 pub struct BackendRouter {
-    /// Available backends on this system
+    /// Backends present and permitted on this system
     available_backends: Vec<MacosBackend>,
+    /// Backends present on this system, whether permitted or not
+    present_backends: Vec<MacosBackend>,
+    /// Whether the process runs inside the App Sandbox
+    sandboxed: bool,
     /// Configuration preferences
     config: MacosConfig,
 }
@@ -38,16 +49,40 @@ impl BackendRouter {
     ///
     /// Automatically detects which backends are available.
     pub fn new(config: MacosConfig) -> Self {
-        let available_backends = Self::detect_available_backends();
+        Self::with_environment(config, &SystemEnvironment, &SystemCommandRunner)
+    }
+
+    /// Creates a router using `env` for sandbox detection and `runner` for
+    /// probing command-line tools
+    ///
+    /// When sandboxed, subprocess-based backends are assumed present and
+    /// are never probed, so `runner` is not called.
+    pub fn with_environment(
+        config: MacosConfig,
+        env: &dyn Environment,
+        runner: &dyn CommandRunner,
+    ) -> Self {
+        let sandboxed = sandbox::is_sandboxed(env);
+        if sandboxed {
+            debug!("Running inside the App Sandbox, subprocess backends disabled");
+        }
+        let present_backends = Self::detect_present_backends(sandboxed, runner);
+        let available_backends = present_backends
+            .iter()
+            .copied()
+            .filter(|backend| !(sandboxed && backend.requires_subprocess()))
+            .collect();
 
         Self {
             available_backends,
+            present_backends,
+            sandboxed,
             config,
         }
     }
 
-    /// Detects which backends are currently available
-    fn detect_available_backends() -> Vec<MacosBackend> {
+    /// Detects which backends are present on this system
+    fn detect_present_backends(sandboxed: bool, runner: &dyn CommandRunner) -> Vec<MacosBackend> {
         let mut backends = Vec::new();
 
         // Check IOKit
@@ -66,12 +101,18 @@ impl BackendRouter {
             }
         }
 
-        // Check PowerMetrics
-        if MacosBackend::PowerMetrics.is_available() {
+        // Check PowerMetrics; it ships with macOS, so assume it is present
+        // when the sandbox forbids running `which`
+        let powermetrics_present = sandboxed
+            || runner
+                .output("which", &["powermetrics"])
+                .map(|output| output.status.success())
+                .unwrap_or(false);
+        if powermetrics_present {
             backends.push(MacosBackend::PowerMetrics);
         }
 
-        // SystemProfiler is always available
+        // SystemProfiler is always present
         backends.push(MacosBackend::SystemProfiler);
 
         backends
@@ -79,6 +120,10 @@ impl BackendRouter {
 
     /// Selects the optimal backend for the given operation
     ///
+    /// SystemProfiler is the last resort even when it is not permitted;
+    /// check [`is_backend_permitted()`](Self::is_backend_permitted) before
+    /// using the result.
+    ///
     /// # Examples
     ///
     /// ```
@@ -163,11 +208,28 @@ impl BackendRouter {
         MacosBackend::SystemProfiler
     }
 
-    /// Checks if a backend is available
+    /// Checks if a backend is available (present and permitted)
     pub fn is_backend_available(&self, backend: MacosBackend) -> bool {
         self.available_backends.contains(&backend)
     }
 
+    /// Checks if a backend is present on this system, even if not permitted
+    pub fn is_backend_present(&self, backend: MacosBackend) -> bool {
+        self.present_backends.contains(&backend)
+    }
+
+    /// Checks if the process may use a backend
+    ///
+    /// Subprocess-based backends are not permitted inside the App Sandbox.
+    pub fn is_backend_permitted(&self, backend: MacosBackend) -> bool {
+        !(self.sandboxed && backend.requires_subprocess())
+    }
+
+    /// Returns `true` if the process runs inside the App Sandbox
+    pub fn is_sandboxed(&self) -> bool {
+        self.sandboxed
+    }
+
     /// Returns all available backends
     pub fn available_backends(&self) -> &[MacosBackend] {
         &self.available_backends
@@ -0,0 +1,114 @@
+//! App Sandbox detection for macOS
+//!
+//! Inside a sandboxed app (e.g. a Mac App Store build) spawning
+//! `system_profiler`, `powermetrics` or `sysctl` is denied, while Metal and
+//! IOKit remain accessible. When the sandbox is detected, subprocess-based
+//! backends are not permitted and are skipped without logging errors.
+//!
+//! Environment access, the sandbox query and process spawning go through
+//! the [`Environment`] and [`CommandRunner`] traits so that tests can
+//! simulate a sandbox and verify that no process is spawned.
+
+use std::io;
+use std::process::{Command, Output};
+
+/// Environment variable set by macOS for every process in an App Sandbox
+pub const SANDBOX_CONTAINER_ENV: &str = "APP_SANDBOX_CONTAINER_ID";
+
+/// Hint recorded in the [`DetectionReport`](crate::DetectionReport) when sandboxed
+pub const SANDBOX_HINT: &str = "sandboxed: subprocess backends disabled";
+
+/// Read access to process environment variables and the sandbox state
+pub trait Environment: Send + Sync {
+    /// Returns the value of `key`, or `None` if it is unset or not Unicode
+    fn var(&self, key: &str) -> Option<String>;
+
+    /// Returns `true` if the sandbox itself reports the process as sandboxed
+    ///
+    /// The default reports no sandbox, leaving detection to
+    /// [`SANDBOX_CONTAINER_ENV`].
+    fn sandbox_check(&self) -> bool {
+        false
+    }
+}
+
+/// [`Environment`] backed by the real process environment
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemEnvironment;
+
+impl Environment for SystemEnvironment {
+    fn var(&self, key: &str) -> Option<String> {
+        std::env::var(key).ok()
+    }
+
+    fn sandbox_check(&self) -> bool {
+        sandbox_check_self()
+    }
+}
+
+/// Runs external programs
+pub trait CommandRunner: Send + Sync {
+    /// Runs `program` with `args` and returns its output
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the process cannot be spawned.
+    fn output(&self, program: &str, args: &[&str]) -> io::Result<Output>;
+}
+
+/// [`CommandRunner`] spawning real processes
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemCommandRunner;
+
+impl CommandRunner for SystemCommandRunner {
+    fn output(&self, program: &str, args: &[&str]) -> io::Result<Output> {
+        Command::new(program).args(args).output()
+    }
+}
+
+/// Returns `true` if the current process runs inside the App Sandbox
+///
+/// Checks the [`SANDBOX_CONTAINER_ENV`] variable and
+/// [`Environment::sandbox_check`], which for [`SystemEnvironment`] asks the
+/// sandbox itself via `sandbox_check()` on macOS.
+///
+/// # Examples
+///
+/// ```
+/// use gpu_info::providers::macos::sandbox::{is_sandboxed, SystemEnvironment};
+///
+/// if is_sandboxed(&SystemEnvironment) {
+///     println!("subprocess backends are disabled");
+/// }
+/// ```
+pub fn is_sandboxed(env: &dyn Environment) -> bool {
+    env.var(SANDBOX_CONTAINER_ENV)
+        .is_some_and(|id| !id.is_empty())
+        || env.sandbox_check()
+}
+
+#[cfg(target_os = "macos")]
+fn sandbox_check_self() -> bool {
+    use std::os::raw::{c_char, c_int};
+
+    /// `SANDBOX_FILTER_NONE` from `<sandbox.h>`
+    const SANDBOX_FILTER_NONE: c_int = 0;
+
+    extern "C" {
+        fn sandbox_check(pid: c_int, operation: *const c_char, filter: c_int, ...) -> c_int;
+    }
+
+    // With no operation, sandbox_check() reports whether the process is sandboxed at all
+    unsafe {
+        sandbox_check(
+            std::process::id() as c_int,
+            std::ptr::null(),
+            SANDBOX_FILTER_NONE,
+        ) != 0
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+fn sandbox_check_self() -> bool {
+    false
+}
@@ -10,6 +10,8 @@
 //! implementations in submodules:
 //!
 //! - `nvidia` - NVIDIA GPU provider using NVML
+//! - `nvml_wrapper` - NVIDIA GPU provider using the `nvml-wrapper` crate
+//!   (`nvml-wrapper-compat` feature)
 //! - `amd` - AMD GPU provider using ADL
 //! - `intel` - Intel GPU provider using WMI/sysfs
 //! - `linux` - Linux-specific provider implementations
@@ -19,12 +21,14 @@
 ///
 /// Provides GPU detection and metrics for AMD/Radeon GPUs using the
 /// AMD Display Library (ADL) on Windows.
+#[cfg(feature = "vendor-amd")]
 pub mod amd;
 
 /// Intel GPU provider implementation.
 ///
 /// Provides GPU detection and metrics for Intel integrated and discrete GPUs
 /// using WMI on Windows and sysfs on Linux.
+#[cfg(feature = "vendor-intel")]
 pub mod intel;
 
 /// Linux-specific GPU provider implementations.
@@ -37,30 +41,48 @@ pub mod linux;
 ///
 /// Contains provider implementations that use macOS-specific APIs like
 /// IOKit and Metal for GPU metrics collection.
+#[cfg(feature = "vendor-apple")]
 pub mod macos;
 
 /// NVIDIA GPU provider implementation.
 ///
 /// Provides GPU detection and metrics for NVIDIA GPUs using the
 /// NVIDIA Management Library (NVML).
+#[cfg(feature = "vendor-nvidia")]
 pub mod nvidia;
 
+/// NVIDIA GPU provider built on the `nvml-wrapper` crate.
+///
+/// Converts `nvml-wrapper` devices to [`GpuInfo`](crate::GpuInfo) as an
+/// alternative to the built-in NVML bindings.
+#[cfg(feature = "nvml-wrapper-compat")]
+pub mod nvml_wrapper;
+
+// `Win32_VideoController` parsing, built everywhere so it can be tested
+pub(crate) mod wmi;
+
+// DXGI adapter LUIDs, formatting built everywhere so it can be tested
+pub(crate) mod dxgi;
+
 // Internal Windows-specific utility modules (not part of public API)
 #[cfg(target_os = "windows")]
 pub(crate) mod windows;
 
 // Platform-specific provider re-exports
-#[cfg(target_os = "windows")]
+#[cfg(all(target_os = "windows", feature = "vendor-amd"))]
 pub use self::amd::AmdProvider;
-#[cfg(target_os = "windows")]
+#[cfg(all(target_os = "windows", feature = "vendor-intel"))]
 pub use self::intel::IntelProvider;
-#[cfg(target_os = "windows")]
+#[cfg(all(target_os = "windows", feature = "vendor-nvidia"))]
 pub use self::nvidia::NvidiaProvider;
 
-#[cfg(target_os = "linux")]
+#[cfg(feature = "nvml-wrapper-compat")]
+pub use self::nvml_wrapper::NvmlWrapperProvider;
+
+#[cfg(all(target_os = "linux", feature = "vendor-amd"))]
 pub use self::linux::AmdLinuxProvider;
-#[cfg(target_os = "linux")]
+#[cfg(all(target_os = "linux", feature = "vendor-nvidia"))]
 pub use self::linux::NvidiaLinuxProvider;
 
-#[cfg(target_os = "macos")]
+#[cfg(all(target_os = "macos", feature = "vendor-apple"))]
 pub use self::macos::MacosProvider;
@@ -14,9 +14,22 @@
 //! [`GpuProvider`]: crate::gpu_info::GpuProvider
 
 use crate::gpu_info::{GpuInfo, GpuProvider, Result};
+use crate::metric_source::Metric;
 use crate::nvml_api;
+use crate::process_usage::GpuProcessInfo;
 use crate::vendor::Vendor;
 
+/// Metrics NVML reports
+const SUPPORTED_METRICS: &[Metric] = &[
+    Metric::Temperature,
+    Metric::Utilization,
+    Metric::MemoryUtil,
+    Metric::PowerUsage,
+    Metric::CoreClock,
+    Metric::MemoryTotal,
+    Metric::MemoryUsed,
+];
+
 /// NVIDIA GPU provider.
 ///
 /// Implements [`GpuProvider`] for NVIDIA GPUs using the NVML (NVIDIA Management Library) API.
@@ -31,6 +44,33 @@ impl NvidiaProvider {
     pub fn new() -> Self {
         Self
     }
+
+    /// Lists the processes running compute or graphics work on `gpu`
+    ///
+    /// Uses `nvmlDeviceGetComputeRunningProcesses_v3` and
+    /// `nvmlDeviceGetGraphicsRunningProcesses_v3`. The device is found by
+    /// the PCI bus ID of `gpu`, or else by its name. Without the privileges
+    /// to see other users' processes, NVML lists only the caller's own.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if NVML is not available, no device matches `gpu`
+    /// or the driver lacks both queries.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use gpu_info::providers::nvidia::NvidiaProvider;
+    ///
+    /// let gpu = gpu_info::get();
+    /// for process in NvidiaProvider::new().get_running_processes(&gpu)? {
+    ///     println!("{} ({}): {} MB", process.pid, process.process_type, process.memory_used_mb);
+    /// }
+    /// # Ok::<(), gpu_info::GpuError>(())
+    /// ```
+    pub fn get_running_processes(&self, gpu: &GpuInfo) -> Result<Vec<GpuProcessInfo>> {
+        nvml_api::get_nvidia_running_processes(gpu)
+    }
 }
 
 impl Default for NvidiaProvider {
@@ -47,7 +87,18 @@ impl GpuProvider for NvidiaProvider {
     }
 
     /// Update the information for a specific NVIDIA GPU.
+    ///
+    /// A GPU with a known PCI bus ID is read directly by its bus ID, so two
+    /// identical cards never read each other's metrics.
     fn update_gpu(&self, gpu: &mut GpuInfo) -> Result<()> {
+        if let Some(updated) = gpu
+            .bus_id
+            .as_deref()
+            .and_then(nvml_api::get_nvidia_gpu_by_bus_id)
+        {
+            *gpu = updated;
+            return Ok(());
+        }
         crate::gpu_info::update_gpu_from_api(gpu, nvml_api::get_nvidia_gpus)
     }
 
@@ -55,6 +106,11 @@ impl GpuProvider for NvidiaProvider {
     fn get_vendor(&self) -> Vendor {
         Vendor::Nvidia
     }
+
+    /// Whether `metric` is one this provider reports
+    fn supports_metric(&self, metric: Metric) -> bool {
+        SUPPORTED_METRICS.contains(&metric)
+    }
 }
 
 /// Detect all NVIDIA GPUs in the system.
@@ -0,0 +1,308 @@
+//! NVIDIA GPU provider built on the `nvml-wrapper` crate.
+//!
+//! Applications that already use [`nvml_wrapper`] can convert its devices
+//! with [`GpuInfo::from_nvml_device()`] or register [`NvmlWrapperProvider`]
+//! as an alternative NVIDIA backend, instead of having this crate load NVML
+//! a second time through its own FFI bindings.
+//!
+//! Only available with the `nvml-wrapper-compat` feature.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use gpu_info::providers::nvml_wrapper::NvmlWrapperProvider;
+//! use gpu_info::{GpuProviderManager, Vendor};
+//!
+//! let mut manager = GpuProviderManager::new();
+//! manager.register_provider(Vendor::Nvidia, NvmlWrapperProvider::new()?);
+//! let gpus = manager.detect_all_gpus();
+//! # Ok::<(), gpu_info::GpuError>(())
+//! ```
+
+use crate::gpu_info::{
+    update_gpu_from_api, Confidence, EccStats, GpuError, GpuInfo, GpuProvider, Result,
+    ThrottleReasons,
+};
+use crate::metric_source::Metric;
+use crate::vendor::Vendor;
+use log::debug;
+use nvml_wrapper::enum_wrappers::device::{
+    Clock, ClockId, EccCounter, MemoryError, TemperatureSensor,
+};
+use nvml_wrapper::error::NvmlError;
+use nvml_wrapper::struct_wrappers::device::{MemoryInfo, Utilization};
+use nvml_wrapper::{Device, Nvml};
+
+/// Metrics read through `nvml-wrapper`
+const SUPPORTED_METRICS: &[Metric] = &[
+    Metric::Temperature,
+    Metric::Utilization,
+    Metric::MemoryUtil,
+    Metric::PowerUsage,
+    Metric::CoreClock,
+    Metric::MemoryClock,
+    Metric::MaxClockSpeed,
+    Metric::MemoryTotal,
+    Metric::MemoryUsed,
+];
+
+/// Values read from a device, in the units `nvml-wrapper` returns them
+///
+/// Kept separate from [`Device`] so the unit conversions can be tested
+/// without a GPU.
+#[derive(Debug, Default)]
+pub(crate) struct NvmlReadings {
+    pub name: Option<String>,
+    /// Degrees Celsius
+    pub temperature: Option<u32>,
+    pub utilization: Option<Utilization>,
+    /// Bytes
+    pub memory: Option<MemoryInfo>,
+    /// Milliwatts
+    pub power_usage: Option<u32>,
+    /// Milliwatts
+    pub power_limit: Option<u32>,
+    /// MHz, as are the other clocks
+    pub graphics_clock: Option<u32>,
+    pub memory_clock: Option<u32>,
+    pub max_graphics_clock: Option<u32>,
+    /// Application clock target of the graphics clock
+    pub graphics_clock_target: Option<u32>,
+    /// Percent of the first fan's maximum speed
+    pub fan_speed: Option<u32>,
+    /// Percent, as is the decoder utilization
+    pub encoder_util: Option<u32>,
+    pub decoder_util: Option<u32>,
+    pub driver_version: Option<String>,
+    /// NVML clock throttle reason bitmask
+    pub throttle_reasons: Option<u64>,
+    pub uuid: Option<String>,
+    /// PCI bus ID with NVML's 8-digit domain
+    pub bus_id: Option<String>,
+    pub ecc_errors: Option<EccStats>,
+}
+
+impl NvmlReadings {
+    /// Queries every value from `device`
+    ///
+    /// Only the name is required; other queries the device does not
+    /// support are left as `None`.
+    fn read(device: &Device) -> Result<Self> {
+        Ok(Self {
+            name: Some(device.name().map_err(gpu_error)?),
+            temperature: optional("temperature", device.temperature(TemperatureSensor::Gpu)),
+            utilization: optional("utilization", device.utilization_rates()),
+            memory: optional("memory info", device.memory_info()),
+            power_usage: optional("power usage", device.power_usage()),
+            power_limit: optional("power limit", device.enforced_power_limit()),
+            graphics_clock: optional("graphics clock", device.clock_info(Clock::Graphics)),
+            memory_clock: optional("memory clock", device.clock_info(Clock::Memory)),
+            max_graphics_clock: optional(
+                "max graphics clock",
+                device.max_clock_info(Clock::Graphics),
+            ),
+            graphics_clock_target: optional(
+                "graphics clock target",
+                device.clock(Clock::Graphics, ClockId::TargetAppClock),
+            ),
+            fan_speed: optional("fan speed", device.fan_speed(0)),
+            encoder_util: optional("encoder utilization", device.encoder_utilization())
+                .map(|info| info.utilization),
+            decoder_util: optional("decoder utilization", device.decoder_utilization())
+                .map(|info| info.utilization),
+            driver_version: optional("driver version", device.nvml().sys_driver_version()),
+            throttle_reasons: optional("throttle reasons", device.current_throttle_reasons())
+                .map(|reasons| reasons.bits()),
+            uuid: optional("UUID", device.uuid()),
+            bus_id: optional("PCI info", device.pci_info()).map(|pci| pci.bus_id),
+            ecc_errors: read_ecc_errors(device),
+        })
+    }
+
+    /// Converts the readings to the units of [`GpuInfo`]
+    pub(crate) fn into_gpu_info(self) -> GpuInfo {
+        let (memory_total, memory_used) = match self.memory {
+            Some(memory) => (
+                Some((memory.total / 1024 / 1024) as u32),
+                Some((memory.used / 1024 / 1024) as u32),
+            ),
+            None => (None, None),
+        };
+        GpuInfo {
+            vendor: Vendor::Nvidia,
+            name_gpu: self.name,
+            temperature: self.temperature.map(|celsius| celsius as f32),
+            temperatures: None,
+            utilization: self.utilization.as_ref().map(|rates| rates.gpu as f32),
+            power_usage: self.power_usage.map(milliwatts_to_watts),
+            core_clock: self.graphics_clock_target.or(self.graphics_clock),
+            memory_util: self.utilization.as_ref().map(|rates| rates.memory as f32),
+            memory_clock: self.memory_clock,
+            active: Some(true),
+            power_limit: self.power_limit.map(milliwatts_to_watts),
+            memory_total,
+            memory_used,
+            driver_version: self.driver_version,
+            max_clock_speed: self.max_graphics_clock,
+            // NVML reports no requested clock like AMD's DPM state. The
+            // application clock target is what the driver aims for, and the
+            // current clock falls below it under power or thermal throttling
+            effective_core_clock: self.graphics_clock_target.and(self.graphics_clock),
+            fan_speed: self.fan_speed,
+            fan_speed_rpm: None,
+            encoder_util: self.encoder_util.map(|percent| percent as f32),
+            decoder_util: self.decoder_util.map(|percent| percent as f32),
+            throttle_reasons: self.throttle_reasons.map(ThrottleReasons::from_bits),
+            uuid: self.uuid,
+            bus_id: self.bus_id,
+            processes: None,
+            ecc_errors: self.ecc_errors,
+            metric_sources: Default::default(),
+            confidence: Confidence::High,
+            adapter_luid: None,
+        }
+    }
+}
+
+/// Reads the aggregate ECC counters, or the volatile ones if the device
+/// keeps no aggregate counts
+fn read_ecc_errors(device: &Device) -> Option<EccStats> {
+    [EccCounter::Aggregate, EccCounter::Volatile]
+        .into_iter()
+        .find_map(|counter| {
+            Some(EccStats {
+                single_bit_corrected: device
+                    .total_ecc_errors(MemoryError::Corrected, counter)
+                    .ok()?,
+                double_bit_uncorrected: device
+                    .total_ecc_errors(MemoryError::Uncorrected, counter)
+                    .ok()?,
+                aggregate: counter == EccCounter::Aggregate,
+            })
+        })
+}
+
+fn milliwatts_to_watts(milliwatts: u32) -> f32 {
+    milliwatts as f32 / 1000.0
+}
+
+/// Discards a failed optional query, logging why
+fn optional<T>(what: &str, result: std::result::Result<T, NvmlError>) -> Option<T> {
+    result
+        .map_err(|e| debug!("nvml-wrapper: {} unavailable: {}", what, e))
+        .ok()
+}
+
+/// Maps an `nvml-wrapper` error to the closest [`GpuError`]
+pub(crate) fn gpu_error(error: NvmlError) -> GpuError {
+    match error {
+        NvmlError::LibloadingError(_)
+        | NvmlError::LibraryNotFound
+        | NvmlError::DriverNotLoaded
+        | NvmlError::FailedToLoadSymbol(_) => GpuError::DriverNotInstalled,
+        NvmlError::NotFound | NvmlError::GpuLost => GpuError::GpuNotFound,
+        other => GpuError::Ffi(other.to_string()),
+    }
+}
+
+impl GpuInfo {
+    /// Builds a `GpuInfo` from an `nvml-wrapper` device.
+    ///
+    /// Reads the name, temperature, utilization rates, memory info, power
+    /// usage and limit, clocks, fan speed, encoder and decoder utilization,
+    /// driver version, UUID and PCI bus ID through
+    /// the wrapper's safe API. Metrics the device does not support are left
+    /// as `None`.
+    ///
+    /// Requires the `nvml-wrapper-compat` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the device name cannot be read, which means the
+    /// device is not usable at all.
+    pub fn from_nvml_device(device: &Device) -> Result<GpuInfo> {
+        NvmlReadings::read(device).map(NvmlReadings::into_gpu_info)
+    }
+}
+
+/// NVIDIA GPU provider using an [`Nvml`] instance from `nvml-wrapper`.
+///
+/// Reports every device NVML enumerates, in NVML index order. Register it in
+/// a [`GpuProviderManager`](crate::GpuProviderManager) for
+/// [`Vendor::Nvidia`] to use it in place of the built-in NVML bindings.
+pub struct NvmlWrapperProvider {
+    nvml: Nvml,
+}
+
+impl NvmlWrapperProvider {
+    /// Initializes NVML through `nvml-wrapper`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GpuError::DriverNotInstalled`] if the NVML library cannot
+    /// be loaded.
+    pub fn new() -> Result<Self> {
+        Nvml::init().map(Self::from_nvml).map_err(gpu_error)
+    }
+
+    /// Uses an already initialized `Nvml` instance.
+    pub fn from_nvml(nvml: Nvml) -> Self {
+        Self { nvml }
+    }
+
+    /// Returns the underlying `Nvml` instance.
+    pub fn nvml(&self) -> &Nvml {
+        &self.nvml
+    }
+}
+
+impl GpuProvider for NvmlWrapperProvider {
+    /// Detect all NVIDIA GPUs NVML enumerates.
+    fn detect_gpus(&self) -> Result<Vec<GpuInfo>> {
+        let count = self.nvml.device_count().map_err(gpu_error)?;
+        let gpus = (0..count)
+            .filter_map(|index| {
+                let device = self
+                    .nvml
+                    .device_by_index(index)
+                    .map_err(|e| debug!("nvml-wrapper: device {} unavailable: {}", index, e))
+                    .ok()?;
+                GpuInfo::from_nvml_device(&device)
+                    .map_err(|e| debug!("nvml-wrapper: device {} skipped: {}", index, e))
+                    .ok()
+            })
+            .collect();
+        crate::gpu_info::handle_empty_result(gpus)
+    }
+
+    /// Update a GPU from the device at its PCI bus ID, or with its UUID.
+    ///
+    /// A GPU with neither is matched against every device as in
+    /// [`matching_gpu()`](crate::gpu_info::matching_gpu).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GpuError::GpuNotFound`] if NVML has no such device.
+    fn update_gpu(&self, gpu: &mut GpuInfo) -> Result<()> {
+        let device = match (gpu.bus_id.as_deref(), gpu.uuid.as_deref()) {
+            (Some(bus_id), _) => self.nvml.device_by_pci_bus_id(bus_id),
+            (None, Some(uuid)) => self.nvml.device_by_uuid(uuid),
+            (None, None) => {
+                let gpus = self.detect_gpus()?;
+                return update_gpu_from_api(gpu, || gpus);
+            }
+        };
+        *gpu = GpuInfo::from_nvml_device(&device.map_err(gpu_error)?)?;
+        Ok(())
+    }
+
+    /// Get the vendor for this provider.
+    fn get_vendor(&self) -> Vendor {
+        Vendor::Nvidia
+    }
+
+    /// Whether `metric` is one this provider reports
+    fn supports_metric(&self, metric: Metric) -> bool {
+        SUPPORTED_METRICS.contains(&metric)
+    }
+}
@@ -51,17 +51,19 @@
 //!   ├─► get_basic_gpu_info()        [WMI]
 //!   │     └─► Name, Driver, Memory Total
 //!   │
-//!   ├─► enhance_with_md_api()       [Intel MD API]
-//!   │     ├─► Temperature  (Primary)
-//!   │     ├─► Power Usage  (Primary)
-//!   │     ├─► Frequency    (Primary)
-//!   │     └─► Utilization  (Primary if available)
+//!   ├─► read_md_api()               [Intel MD API]
+//!   │     └─► Temperature, Power Usage, Frequencies
 //!   │
-//!   └─► enhance_with_pdh()          [PDH Fallback]
-//!         ├─► Utilization  (Fallback if MD API failed)
-//!         └─► Memory Usage (Primary - MD API doesn't provide)
+//!   ├─► read_pdh()                  [PDH]
+//!   │     └─► Utilization, Memory Usage
+//!   │
+//!   └─► metric_source::apply()      [METRIC_PRIORITY]
+//!         └─► Highest-priority reading per field
 //! ```
 //!
+//! Each backend fills a partial [`GpuInfo`]; the per-field source order in
+//! [`METRIC_PRIORITY`] decides which reading wins when several are present.
+//!
 //! # Why This Design?
 //!
 //! - **Centralized**: All Intel GPU logic in one place (`intel.rs`)
@@ -74,8 +76,12 @@
 //! - Header: <https://github.com/intel/metrics-discovery/blob/master/instrumentation/metrics_discovery/common/inc/metrics_discovery_api.h>
 
 // Allow dead_code for FFI infrastructure that will be used in future enhancements
+use crate::cancel::CancelToken;
+use crate::detection_report::DetectionReport;
 use crate::gpu_info::{GpuError, GpuInfo, GpuProvider, Result};
-use crate::vendor::{IntelGpuType, Vendor};
+use crate::metric_source::{self, Metric, MetricSource, PriorityTable};
+use crate::providers::dxgi;
+use crate::vendor::{IntelGpuType, Vendor, PCI_VENDOR_INTEL};
 use libloading::Library;
 use log::{debug, error, info, warn};
 use std::path::{Path, PathBuf};
@@ -1139,12 +1145,36 @@ impl Drop for IntelMetricsDevice {
 
 // Intel Windows Provider implementation
 
+/// Source priority per metric for Intel GPUs on Windows
+///
+/// PDH is preferred for utilization and memory usage: the Intel MD API's
+/// utilization metric requires IoStream sampling and is unreliable.
+pub(crate) const METRIC_PRIORITY: &PriorityTable = &[
+    (Metric::Temperature, &[MetricSource::IntelMdApi]),
+    (Metric::PowerUsage, &[MetricSource::IntelMdApi]),
+    (Metric::CoreClock, &[MetricSource::IntelMdApi]),
+    (
+        Metric::MaxClockSpeed,
+        &[MetricSource::IntelMdApi, MetricSource::Wmi],
+    ),
+    (Metric::MemoryClock, &[MetricSource::IntelMdApi]),
+    (
+        Metric::Utilization,
+        &[MetricSource::Pdh, MetricSource::IntelMdApi],
+    ),
+    (Metric::MemoryUsed, &[MetricSource::Pdh]),
+    (Metric::MemoryUtil, &[MetricSource::Pdh]),
+    (Metric::MemoryTotal, &[MetricSource::Wmi]),
+];
+
 /// Intel GPU provider for Windows
 ///
 /// Uses a three-tier approach for GPU metrics:
 /// 1. WMI (Windows Management Instrumentation) - Basic GPU detection and info
 /// 2. Intel Metrics Discovery API - Temperature, power, frequency (if available)
-/// 3. PDH (Performance Data Helper) - Utilization and memory usage fallback
+/// 3. PDH (Performance Data Helper) - Utilization and memory usage
+///
+/// Readings are merged per field according to [`METRIC_PRIORITY`].
 pub struct IntelWindowsProvider;
 
 impl IntelWindowsProvider {
@@ -1157,84 +1187,73 @@ impl IntelWindowsProvider {
     /// Uses the platform-agnostic IntelProvider which queries WMI for:
     /// - GPU name, vendor, driver version
     /// - Total memory size
-    fn get_basic_gpu_info(&self) -> Result<GpuInfo> {
+    ///
+    /// The DXGI adapter at the GPU's PCI location supplies the adapter LUID
+    /// and, if WMI lacks it, the bus ID. The WMI query is killed if `token`
+    /// is cancelled.
+    fn get_basic_gpu_info(
+        &self,
+        token: &CancelToken,
+        report: &mut DetectionReport,
+    ) -> Result<GpuInfo> {
         let intel_provider = super::super::intel::IntelProvider::new();
-        let gpus = intel_provider.detect_gpus()?;
-        gpus.into_iter().next().ok_or(GpuError::GpuNotFound)
+        let gpus = intel_provider.detect_gpus_with_cancel(token, report)?;
+        let mut gpu = gpus.into_iter().next().ok_or(GpuError::GpuNotFound)?;
+        let adapters = dxgi::adapters();
+        if let Some(adapter) =
+            dxgi::find_adapter(&adapters, PCI_VENDOR_INTEL, gpu.bus_id.as_deref())
+        {
+            gpu.adapter_luid = Some(adapter.luid.clone());
+            if gpu.bus_id.is_none() {
+                gpu.bus_id = adapter.bus_id.clone();
+            }
+        }
+        Ok(gpu)
     }
 
-    /// Enhance GPU info with Intel Metrics Discovery API
+    /// Read metrics from the Intel Metrics Discovery API
     ///
     /// # Metrics from Intel MD API
-    /// - Temperature (primary source)
-    /// - Power usage (primary source)
-    /// - Core frequency (primary source)
-    /// - Max frequency (primary source)
-    /// - Memory frequency (primary source)
+    /// - Temperature
+    /// - Power usage
+    /// - Core frequency
+    /// - Max frequency
+    /// - Memory frequency
     ///
     /// # Note
     /// Utilization is NOT collected here - PDH is more reliable for that metric.
-    fn enhance_with_md_api(&self, gpu: &mut GpuInfo) {
+    fn read_md_api(&self) -> GpuInfo {
+        let mut reading = GpuInfo::unknown();
         if let Ok(device) = IntelMetricsDevice::new() {
             debug!("Intel Metrics Discovery API available, collecting metrics");
-            // Temperature - primary source
-            if let Ok(temp) = device.get_temperature() {
-                gpu.temperature = Some(temp);
-                info!("Temperature from Intel MD API: {:.1}°C", temp);
-            } else {
-                debug!("Temperature not available from Intel MD API");
-            }
-            // Power usage - primary source
-            if let Ok(power) = device.get_power() {
-                gpu.power_usage = Some(power);
-                info!("Power usage from Intel MD API: {:.1}W", power);
-            } else {
-                debug!("Power usage not available from Intel MD API");
-            }
-            // Core frequency - primary source
-            if let Ok(freq) = device.get_frequency() {
-                gpu.core_clock = Some(freq);
-                info!("Core clock from Intel MD API: {} MHz", freq);
-            } else {
-                debug!("Core clock not available from Intel MD API");
-            }
-            // Max frequency - primary source
-            if let Ok(max_freq) = device.get_max_frequency() {
-                gpu.max_clock_speed = Some(max_freq);
-                info!("Max clock from Intel MD API: {} MHz", max_freq);
-            } else {
-                debug!("Max clock not available from Intel MD API");
-            }
-            // Memory frequency - primary source
-            if let Ok(mem_freq) = device.get_memory_frequency() {
-                gpu.memory_clock = Some(mem_freq);
-                info!("Memory clock from Intel MD API: {} MHz", mem_freq);
-            } else {
-                debug!("Memory clock not available from Intel MD API");
-            }
+            reading.temperature = device.get_temperature().ok();
+            reading.power_usage = device.get_power().ok();
+            reading.core_clock = device.get_frequency().ok();
+            reading.max_clock_speed = device.get_max_frequency().ok();
+            reading.memory_clock = device.get_memory_frequency().ok();
             debug!("Intel MD API metrics collection complete");
         } else {
             debug!("Intel Metrics Discovery API not available (igdmd64.dll not found)");
         }
+        reading
     }
 
-    /// Get GPU utilization and memory usage via PDH
+    /// Read GPU utilization and memory usage via PDH
     ///
     /// # Metrics from PDH
-    /// - GPU utilization (primary source - more reliable than Intel MD API)
-    /// - Memory usage (primary source - Intel MD API doesn't provide this)
+    /// - GPU utilization (more reliable than Intel MD API)
+    /// - Memory usage (Intel MD API doesn't provide this)
     ///
-    /// # Strategy
-    /// PDH is the PRIMARY source for utilization and memory metrics.
-    /// Intel MD API's utilization metric is complex and unreliable (requires IoStream).
-    fn get_utilization(&self, gpu: &mut GpuInfo) {
+    /// `memory_total` is used to derive the memory utilization percentage.
+    fn read_pdh(&self, memory_total: Option<u32>) -> GpuInfo {
+        let mut reading = GpuInfo::unknown();
         debug!("→ Collecting PDH metrics (utilization & memory)");
         // Open PDH query
         let query = match super::pdh::open_query() {
             Ok(q) => q,
             Err(e) => {
                 warn!("Failed to open PDH query: {:?}", e);
-                return;
+                return reading;
             }
         };
         // Build counter paths for GPU utilization and memory
@@ -1260,13 +1279,13 @@ impl IntelWindowsProvider {
         if util_counters.is_empty() && mem_counters.is_empty() {
             warn!("No PDH counters available");
             super::pdh::close_query(query);
-            return;
+            return reading;
         }
         // Collect first snapshot
         if let Err(e) = super::pdh::collect_query_data(query) {
             warn!("First PDH collection failed: {:?}", e);
             super::pdh::close_query(query);
-            return;
+            return reading;
         }
         // Wait for PDH collection interval
         std::thread::sleep(std::time::Duration::from_millis(
@@ -1276,7 +1295,7 @@ impl IntelWindowsProvider {
         if let Err(e) = super::pdh::collect_query_data(query) {
             warn!("Second PDH collection failed: {:?}", e);
             super::pdh::close_query(query);
-            return;
+            return reading;
         }
         // Calculate total GPU utilization
         // Note: PDH returns multiple GPU Engine counters (one per execution unit).
@@ -1289,8 +1308,9 @@ impl IntelWindowsProvider {
                     total_util += value;
                 }
             }
-            // Total utilization is the sum of all engine utilizations
-            gpu.utilization = Some(total_util as f32);
+            // Total utilization is the sum of all engine utilizations; engines
+            // running in parallel can add up to more than 100%
+            reading.utilization = Some((total_util as f32).clamp(0.0, 100.0));
             info!("Utilization from PDH: {:.2}%", total_util);
         }
         // Calculate shared memory usage
@@ -1308,11 +1328,11 @@ impl IntelWindowsProvider {
             if valid_count > 0 {
                 let mem_mb = (total_mem_bytes / (1024.0 * 1024.0)) as u32;
                 // Store absolute used memory value
-                gpu.memory_used = Some(mem_mb);
+                reading.memory_used = Some(mem_mb);
                 // Calculate percentage from absolute values
-                if let Some(total_mb) = gpu.memory_total {
+                if let Some(total_mb) = memory_total.filter(|&total_mb| total_mb > 0) {
                     let mem_percent = (mem_mb as f32 / total_mb as f32) * 100.0;
-                    gpu.memory_util = Some(mem_percent.min(100.0));
+                    reading.memory_util = Some(mem_percent.min(100.0));
                     info!(
                         "Memory from PDH: {} MB used / {} MB total ({:.2}%)",
                         mem_mb, total_mb, mem_percent
@@ -1327,6 +1347,24 @@ impl IntelWindowsProvider {
         // Close PDH query
         super::pdh::close_query(query);
         debug!("PDH metrics collection complete");
+        reading
+    }
+
+    /// Collect metrics from the Intel MD API and PDH and merge them into `gpu`
+    ///
+    /// `wmi` is the basic information from [`get_basic_gpu_info()`](Self::get_basic_gpu_info).
+    fn collect_metrics(&self, gpu: &mut GpuInfo, wmi: &GpuInfo) {
+        let md_api = self.read_md_api();
+        let pdh = self.read_pdh(wmi.memory_total);
+        metric_source::apply(
+            gpu,
+            METRIC_PRIORITY,
+            &[
+                (MetricSource::Wmi, wmi),
+                (MetricSource::IntelMdApi, &md_api),
+                (MetricSource::Pdh, &pdh),
+            ],
+        );
     }
 }
 
@@ -1339,12 +1377,28 @@ impl Default for IntelWindowsProvider {
 impl GpuProvider for IntelWindowsProvider {
     /// Detect Intel GPUs on Windows
     fn detect_gpus(&self) -> Result<Vec<GpuInfo>> {
+        self.detect_gpus_with_cancel(&CancelToken::new(), &mut DetectionReport::new())
+    }
+
+    /// Detect Intel GPUs on Windows, killing the WMI query if `token` is
+    /// cancelled
+    ///
+    /// A GPU found before cancellation is returned without its metrics.
+    fn detect_gpus_with_cancel(
+        &self,
+        token: &CancelToken,
+        report: &mut DetectionReport,
+    ) -> Result<Vec<GpuInfo>> {
         debug!("Detecting Intel GPUs on Windows");
-        let mut gpu = self.get_basic_gpu_info()?;
-        // Enhance with Intel Metrics Discovery API
-        self.enhance_with_md_api(&mut gpu);
-        // Get utilization and memory via PDH
-        self.get_utilization(&mut gpu);
+        let basic_info = match self.get_basic_gpu_info(token, report) {
+            Err(_) if token.is_cancelled() => return Ok(Vec::new()),
+            result => result?,
+        };
+        if token.is_cancelled() {
+            return Ok(vec![basic_info]);
+        }
+        let mut gpu = basic_info.clone();
+        self.collect_metrics(&mut gpu, &basic_info);
         info!("Successfully detected Intel GPU: {:?}", gpu.name_gpu);
         Ok(vec![gpu])
     }
@@ -1353,17 +1407,17 @@ impl GpuProvider for IntelWindowsProvider {
     fn update_gpu(&self, gpu: &mut GpuInfo) -> Result<()> {
         debug!("Updating Intel GPU information on Windows");
         // Get fresh basic info
-        let basic_info = self.get_basic_gpu_info()?;
+        let basic_info =
+            self.get_basic_gpu_info(&CancelToken::new(), &mut DetectionReport::new())?;
         // Update basic fields
-        gpu.name_gpu = basic_info.name_gpu;
+        gpu.name_gpu.clone_from(&basic_info.name_gpu);
         gpu.vendor = basic_info.vendor;
-        gpu.driver_version = basic_info.driver_version;
-        gpu.memory_total = basic_info.memory_total;
+        gpu.driver_version.clone_from(&basic_info.driver_version);
         gpu.active = basic_info.active;
-        // Enhance with Intel Metrics Discovery API
-        self.enhance_with_md_api(gpu);
-        // Get utilization and memory via PDH
-        self.get_utilization(gpu);
+        if basic_info.uuid.is_some() {
+            gpu.uuid.clone_from(&basic_info.uuid);
+        }
+        self.collect_metrics(gpu, &basic_info);
         if !gpu.is_valid() {
             warn!("GPU data validation failed");
             return Err(GpuError::GpuNotActive);
@@ -1376,4 +1430,9 @@ impl GpuProvider for IntelWindowsProvider {
     fn get_vendor(&self) -> Vendor {
         Vendor::Intel(IntelGpuType::Unknown)
     }
+
+    /// Whether `metric` has a source in [`METRIC_PRIORITY`]
+    fn supports_metric(&self, metric: Metric) -> bool {
+        !metric_source::priority(METRIC_PRIORITY, metric).is_empty()
+    }
 }
@@ -36,7 +36,7 @@
 //! - **Fallback Strategy**: Graceful degradation when advanced APIs unavailable
 
 // Public module - use this for Intel GPU monitoring
-#[cfg(target_os = "windows")]
+#[cfg(all(target_os = "windows", feature = "vendor-intel"))]
 pub mod intel;
 
 // Internal utility module - do NOT use directly
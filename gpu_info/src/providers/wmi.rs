@@ -0,0 +1,499 @@
+//! `Win32_VideoController` records for Windows multi-GPU detection.
+//!
+//! WMI is queried in-process over COM, see [`query_video_controllers_com`].
+//! Each display adapter is mapped to a vendor and matched with the GPUs the
+//! vendor backends (NVML, ADL, Intel MD API) report, so an adapter whose
+//! backend is missing or failing still shows up with its WMI data. Adapters
+//! are matched by PCI location, which `Win32_PnPSignedDriver` reports for
+//! the adapter's `PNPDeviceID`.
+//!
+//! The mapping and matching are platform-independent so they can be tested
+//! on any host.
+#![cfg_attr(not(target_os = "windows"), allow(dead_code))]
+
+use crate::gpu_identity::normalize_bus_id;
+use crate::gpu_info::{Confidence, GpuInfo};
+use crate::providers::dxgi::format_bus_id;
+use crate::vendor::{self, Vendor};
+use std::mem;
+
+/// WQL query for the `Win32_VideoController` properties, in-process through COM
+///
+/// `PNPDeviceID` must be selected for [`VideoController::is_software()`] to
+/// recognise software adapters and for [`attach_bus_ids()`] to find the
+/// adapter's PCI location.
+pub(crate) const VIDEO_CONTROLLER_QUERY: &str =
+    "SELECT Name, AdapterRAM, DriverVersion, Status, PNPDeviceID FROM Win32_VideoController";
+
+/// WQL query for the PCI location of every display device
+pub(crate) const PCI_LOCATION_QUERY: &str =
+    "SELECT DeviceID, Location FROM Win32_PnPSignedDriver WHERE DeviceClass = 'DISPLAY'";
+
+/// WQL query for the hardware error events the WHEA logger wrote to the
+/// System event log
+pub(crate) const WHEA_EVENT_QUERY: &str = "SELECT Message FROM Win32_NTLogEvent \
+     WHERE Logfile = 'System' AND SourceName = 'Microsoft-Windows-WHEA-Logger'";
+
+/// Vendors with a Windows backend, probed when WMI lists no adapter
+const BACKEND_VENDORS: [Vendor; 3] = [
+    Vendor::Nvidia,
+    Vendor::Amd,
+    Vendor::Intel(vendor::IntelGpuType::Unknown),
+];
+
+/// One `Win32_VideoController` record
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct VideoController {
+    pub(crate) name: String,
+    /// `AdapterRAM` in bytes; WMI caps it at 4 GiB
+    pub(crate) adapter_ram: Option<u64>,
+    pub(crate) driver_version: Option<String>,
+    pub(crate) status: Option<String>,
+    /// e.g. `PCI\VEN_10DE&DEV_2484&SUBSYS_...`
+    pub(crate) pnp_device_id: Option<String>,
+    /// PCI location formatted by [`format_bus_id`]; see [`attach_bus_ids()`]
+    pub(crate) bus_id: Option<String>,
+}
+
+impl VideoController {
+    /// Vendor from the adapter name, falling back to the PCI vendor ID
+    pub(crate) fn vendor(&self) -> Vendor {
+        match vendor::determine_vendor_from_name(&self.name) {
+            Vendor::Unknown => self
+                .pci_vendor_id()
+                .map_or(Vendor::Unknown, Vendor::from_pci_id),
+            vendor => vendor,
+        }
+    }
+
+    /// `VEN_xxxx` of a `PCI\` device ID
+    fn pci_vendor_id(&self) -> Option<u16> {
+        let id = self.pnp_device_id.as_deref()?;
+        let start = id.to_ascii_uppercase().find("VEN_")? + 4;
+        u16::from_str_radix(id.get(start..start + 4)?, 16).ok()
+    }
+
+    /// Software adapters such as the Microsoft Basic or Remote Display Adapter
+    pub(crate) fn is_software(&self) -> bool {
+        self.pnp_device_id.as_deref().is_some_and(|id| {
+            let id = id.to_ascii_uppercase();
+            id.starts_with("ROOT\\") || id.starts_with("SWD\\")
+        })
+    }
+
+    /// GPU built from the WMI data alone
+    pub(crate) fn to_gpu_info(&self) -> GpuInfo {
+        let mut builder = GpuInfo::builder()
+            .vendor(self.vendor())
+            .name(self.name.clone())
+            .confidence(Confidence::Medium);
+        if let Some(version) = &self.driver_version {
+            builder = builder.driver_version(version.clone());
+        }
+        if let Some(bytes) = self.adapter_ram.filter(|&bytes| bytes > 0) {
+            builder = builder.memory_total((bytes / 1024 / 1024) as u32);
+        }
+        if let Some(status) = &self.status {
+            builder = builder.active(status == "OK");
+        }
+        if let Some(bus_id) = &self.bus_id {
+            builder = builder.bus_id(bus_id.clone());
+        }
+        builder.build()
+    }
+}
+
+/// Parses a `Win32_PnPSignedDriver` `Location`, e.g. `PCI bus 1, device 0, function 0`
+///
+/// # Returns
+/// * `Some(bus_id)` - The location formatted by [`format_bus_id`], e.g. `0000:01:00.0`.
+/// * `None` - Not a PCI location, e.g. for a software adapter.
+pub(crate) fn parse_pci_location(location: &str) -> Option<String> {
+    let mut numbers = location
+        .strip_prefix("PCI bus ")?
+        .split(',')
+        .map(|part| part.trim().rsplit(' ').next()?.parse::<u32>().ok());
+    let (Some(Some(bus)), Some(Some(device)), Some(Some(function)), None) = (
+        numbers.next(),
+        numbers.next(),
+        numbers.next(),
+        numbers.next(),
+    ) else {
+        return None;
+    };
+    Some(format_bus_id(bus, device, function))
+}
+
+/// Fills the bus id of each controller from `locations`
+///
+/// `locations` holds `(DeviceID, Location)` pairs of `Win32_PnPSignedDriver`.
+/// A pair belongs to the controller whose `PNPDeviceID` equals its
+/// `DeviceID`, ignoring case. Controllers without a PCI location keep
+/// `None`.
+pub(crate) fn attach_bus_ids(controllers: &mut [VideoController], locations: &[(String, String)]) {
+    for controller in controllers {
+        let Some(pnp_device_id) = controller.pnp_device_id.as_deref() else {
+            continue;
+        };
+        controller.bus_id = locations
+            .iter()
+            .find(|(device_id, _)| device_id.eq_ignore_ascii_case(pnp_device_id))
+            .and_then(|(_, location)| parse_pci_location(location));
+    }
+}
+
+/// Vendor of the adapter the single-GPU path should use
+///
+/// NVIDIA adapters are preferred over AMD ones, and AMD over Intel, so the
+/// discrete GPU of a hybrid laptop wins over its iGPU. Software adapters and
+/// other vendors are ignored.
+pub(crate) fn primary_vendor(controllers: &[VideoController]) -> Option<Vendor> {
+    controllers
+        .iter()
+        .filter(|c| !c.is_software())
+        .map(VideoController::vendor)
+        .filter_map(|vendor| {
+            let rank = match vendor {
+                Vendor::Nvidia => 0,
+                Vendor::Amd => 1,
+                Vendor::Intel(_) => 2,
+                _ => return None,
+            };
+            Some((rank, vendor))
+        })
+        .min_by_key(|&(rank, _)| rank)
+        .map(|(_, vendor)| vendor)
+}
+
+/// Parses the PCI location of the device a WHEA event message reports
+///
+/// PCI Express events name it on a `Bus:Device:Function: 0x1:0x0:0x0` line,
+/// or `Primary Bus:Device:Function:` for errors a root port reported.
+///
+/// # Returns
+/// * `Some(bus_id)` - The location formatted by [`format_bus_id`], e.g. `0000:01:00.0`.
+/// * `None` - The event does not name a PCI device.
+pub(crate) fn whea_bus_id(message: &str) -> Option<String> {
+    let location = message.lines().find_map(|line| {
+        let line = line.trim();
+        line.strip_prefix("Primary ")
+            .unwrap_or(line)
+            .strip_prefix("Bus:Device:Function:")
+    })?;
+    let mut numbers = location.trim().split(':').map(|part| {
+        let digits = part.trim().strip_prefix("0x")?;
+        u32::from_str_radix(digits, 16).ok()
+    });
+    let (Some(Some(bus)), Some(Some(device)), Some(Some(function)), None) = (
+        numbers.next(),
+        numbers.next(),
+        numbers.next(),
+        numbers.next(),
+    ) else {
+        return None;
+    };
+    Some(format_bus_id(bus, device, function))
+}
+
+/// Counts the WHEA event `messages` that report the device at `bus_id`
+pub(crate) fn count_whea_events<S: AsRef<str>>(messages: &[S], bus_id: &str) -> u64 {
+    let bus_id = normalize_bus_id(bus_id);
+    messages
+        .iter()
+        .filter_map(|message| whea_bus_id(message.as_ref()))
+        .filter(|location| normalize_bus_id(location) == bus_id)
+        .count() as u64
+}
+
+/// Number of WHEA hardware error events logged for the device at `bus_id`
+///
+/// `None` if the System event log cannot be queried.
+#[cfg(target_os = "windows")]
+pub(crate) fn whea_error_count(bus_id: &str) -> Option<u64> {
+    use windows::core::w;
+
+    let messages = com::query(WHEA_EVENT_QUERY, |object| {
+        com::string_property(object, w!("Message"))
+    })
+    .map_err(|e| log::debug!("Failed to query WHEA events: {}", e))
+    .ok()?;
+    Some(count_whea_events(&messages, bus_id))
+}
+
+/// Runs a `Win32_VideoController` WQL query in-process through `IWbemServices`
+///
+/// Properties missing from the `SELECT` list are left as `None`, and so is
+/// the bus id, which [`attach_bus_ids()`] fills.
+#[cfg(target_os = "windows")]
+pub(crate) fn query_video_controllers_com(
+    query: &str,
+) -> windows::core::Result<Vec<VideoController>> {
+    use windows::core::w;
+
+    com::query(query, |object| {
+        let controller = VideoController {
+            name: com::string_property(object, w!("Name")).unwrap_or_default(),
+            adapter_ram: com::u64_property(object, w!("AdapterRAM")),
+            driver_version: com::string_property(object, w!("DriverVersion")),
+            status: com::string_property(object, w!("Status")),
+            pnp_device_id: com::string_property(object, w!("PNPDeviceID")),
+            bus_id: None,
+        };
+        (!controller.name.is_empty()).then_some(controller)
+    })
+}
+
+/// Runs [`PCI_LOCATION_QUERY`] in-process and returns its `(DeviceID, Location)` pairs
+#[cfg(target_os = "windows")]
+pub(crate) fn query_pci_locations_com() -> windows::core::Result<Vec<(String, String)>> {
+    use windows::core::w;
+
+    com::query(PCI_LOCATION_QUERY, |object| {
+        Some((
+            com::string_property(object, w!("DeviceID"))?,
+            com::string_property(object, w!("Location"))?,
+        ))
+    })
+}
+
+/// Lists the `Win32_VideoController` records with their PCI locations
+///
+/// An adapter whose location cannot be read keeps a `None` bus id and is
+/// then matched to backend GPUs by position; see [`merge_adapters()`].
+#[cfg(target_os = "windows")]
+pub(crate) fn video_controllers() -> windows::core::Result<Vec<VideoController>> {
+    let mut controllers = query_video_controllers_com(VIDEO_CONTROLLER_QUERY)?;
+    match query_pci_locations_com() {
+        Ok(locations) => attach_bus_ids(&mut controllers, &locations),
+        Err(e) => log::debug!("Failed to query Win32_PnPSignedDriver: {}", e),
+    }
+    Ok(controllers)
+}
+
+/// In-process WQL queries and `IWbemClassObject` property readers
+#[cfg(target_os = "windows")]
+mod com {
+    use windows::core::{BSTR, PCWSTR};
+    use windows::Win32::Foundation::RPC_E_CHANGED_MODE;
+    use windows::Win32::System::Com::{
+        CoCreateInstance, CoInitializeEx, CoSetProxyBlanket, CoTaskMemFree, CoUninitialize,
+        CLSCTX_INPROC_SERVER, COINIT_MULTITHREADED, EOAC_NONE, RPC_C_AUTHN_LEVEL_CALL,
+        RPC_C_IMP_LEVEL_IMPERSONATE,
+    };
+    use windows::Win32::System::Rpc::{RPC_C_AUTHN_WINNT, RPC_C_AUTHZ_NONE};
+    use windows::Win32::System::Variant::{
+        VariantClear, VariantToStringAlloc, VariantToUInt64, VARIANT,
+    };
+    use windows::Win32::System::Wmi::{
+        IWbemClassObject, IWbemLocator, WbemLocator, WBEM_FLAG_FORWARD_ONLY,
+        WBEM_FLAG_RETURN_IMMEDIATELY, WBEM_INFINITE,
+    };
+
+    /// Balances a successful `CoInitializeEx`
+    struct ComGuard;
+    impl Drop for ComGuard {
+        fn drop(&mut self) {
+            // SAFETY: Only constructed after `CoInitializeEx` succeeded on
+            // this thread, and declared before every COM object so it drops last
+            unsafe { CoUninitialize() };
+        }
+    }
+
+    /// Runs a WQL query against `ROOT\CIMV2` and maps each row with `row`
+    ///
+    /// Rows `row` returns `None` for are skipped. COM is initialised for
+    /// the calling thread if needed; a thread already in a single-threaded
+    /// apartment is used as is. Process-wide security is left alone, only
+    /// the proxy blanket of the connection is set.
+    pub(super) fn query<T>(
+        query: &str,
+        mut row: impl FnMut(&IWbemClassObject) -> Option<T>,
+    ) -> windows::core::Result<Vec<T>> {
+        // SAFETY: `CoInitializeEx` takes no reserved pointer
+        let _guard = match unsafe { CoInitializeEx(None, COINIT_MULTITHREADED) } {
+            Ok(()) => Some(ComGuard),
+            Err(e) if e.code() == RPC_E_CHANGED_MODE => None,
+            Err(e) => return Err(e),
+        };
+        let mut rows = Vec::new();
+        // SAFETY: The locator, services, enumerator and class objects are
+        // reference-counted COM objects released on drop, before `_guard`, and
+        // every pointer handed to WMI outlives the call
+        unsafe {
+            let locator: IWbemLocator = CoCreateInstance(&WbemLocator, None, CLSCTX_INPROC_SERVER)?;
+            let services = locator.ConnectServer(
+                &BSTR::from("ROOT\\CIMV2"),
+                &BSTR::new(),
+                &BSTR::new(),
+                &BSTR::new(),
+                0,
+                &BSTR::new(),
+                None,
+            )?;
+            CoSetProxyBlanket(
+                &services,
+                RPC_C_AUTHN_WINNT,
+                RPC_C_AUTHZ_NONE,
+                PCWSTR::null(),
+                RPC_C_AUTHN_LEVEL_CALL,
+                RPC_C_IMP_LEVEL_IMPERSONATE,
+                None,
+                EOAC_NONE,
+            )?;
+            let enumerator = services.ExecQuery(
+                &BSTR::from("WQL"),
+                &BSTR::from(query),
+                WBEM_FLAG_FORWARD_ONLY | WBEM_FLAG_RETURN_IMMEDIATELY,
+                None,
+            )?;
+            loop {
+                let mut next: [Option<IWbemClassObject>; 1] = [None];
+                let mut returned = 0;
+                enumerator
+                    .Next(WBEM_INFINITE, &mut next, &mut returned)
+                    .ok()?;
+                let Some(object) = next[0].take().filter(|_| returned > 0) else {
+                    break;
+                };
+                rows.extend(row(&object));
+            }
+        }
+        Ok(rows)
+    }
+
+    /// Reads `name` into a `VARIANT`, `None` if the property is missing
+    fn property(object: &IWbemClassObject, name: PCWSTR) -> Option<VARIANT> {
+        let mut value = VARIANT::default();
+        // SAFETY: `value` is a valid, empty `VARIANT` that `Get` writes into
+        unsafe { object.Get(name, 0, &mut value, None, None) }.ok()?;
+        Some(value)
+    }
+
+    /// Non-empty string property, `None` if missing or `NULL`
+    pub(super) fn string_property(object: &IWbemClassObject, name: PCWSTR) -> Option<String> {
+        let mut value = property(object, name)?;
+        // SAFETY: `value` was filled by `Get` and is cleared exactly once;
+        // the buffer `VariantToStringAlloc` returns is freed after copying
+        unsafe {
+            let text = VariantToStringAlloc(&value).ok();
+            let _ = VariantClear(&mut value);
+            let text = text?;
+            let string = text.to_string().ok();
+            CoTaskMemFree(Some(text.0 as *const _));
+            string.filter(|s| !s.is_empty())
+        }
+    }
+
+    /// Integer property, `None` if missing or `NULL`
+    pub(super) fn u64_property(object: &IWbemClassObject, name: PCWSTR) -> Option<u64> {
+        let mut value = property(object, name)?;
+        // SAFETY: `value` was filled by `Get` and is cleared exactly once
+        unsafe {
+            let number = VariantToUInt64(&value).ok();
+            let _ = VariantClear(&mut value);
+            number
+        }
+    }
+}
+
+/// Matches WMI adapters with the GPUs their vendor backends report
+///
+/// `detect` is called at most once per vendor and only for vendors WMI lists,
+/// or for every backend vendor if WMI lists nothing. An adapter is merged
+/// into the backend GPU of its vendor at the same PCI location, keeping the
+/// backend's values. Adapters or GPUs without a location are paired with
+/// the remaining ones of their vendor in order; an adapter and GPU at
+/// different locations are never paired. Adapters without a backend GPU are
+/// kept with their WMI data, and backend GPUs WMI missed are appended.
+/// Software adapters are dropped.
+pub(crate) fn merge_adapters(
+    controllers: &[VideoController],
+    mut detect: impl FnMut(Vendor) -> Vec<GpuInfo>,
+) -> Vec<GpuInfo> {
+    let mut detected: Vec<(Vendor, Vec<Option<GpuInfo>>)> = Vec::new();
+    if controllers.is_empty() {
+        for vendor in BACKEND_VENDORS {
+            detected.push((vendor, detect(vendor).into_iter().map(Some).collect()));
+        }
+    }
+    let adapters: Vec<GpuInfo> = controllers
+        .iter()
+        .filter(|c| !c.is_software())
+        .map(VideoController::to_gpu_info)
+        .collect();
+    let mut backends: Vec<Option<GpuInfo>> = adapters
+        .iter()
+        .map(|wmi| {
+            if !BACKEND_VENDORS.iter().any(|&v| same_vendor(v, wmi.vendor)) {
+                return None;
+            }
+            let gpus = vendor_gpus(&mut detected, wmi.vendor, &mut detect);
+            let bus_id = wmi.bus_id.as_deref().and_then(normalize_bus_id)?;
+            gpus.iter_mut()
+                .find(|gpu| {
+                    gpu.as_ref()
+                        .and_then(|gpu| gpu.bus_id.as_deref())
+                        .and_then(normalize_bus_id)
+                        .as_deref()
+                        == Some(bus_id.as_str())
+                })?
+                .take()
+        })
+        .collect();
+    for (wmi, backend) in adapters.iter().zip(&mut backends) {
+        if backend.is_some() {
+            continue;
+        }
+        let Some((_, gpus)) = detected
+            .iter_mut()
+            .find(|(v, _)| same_vendor(*v, wmi.vendor))
+        else {
+            continue;
+        };
+        *backend = gpus
+            .iter_mut()
+            .find(|gpu| {
+                gpu.as_ref()
+                    .is_some_and(|gpu| wmi.bus_id.is_none() || gpu.bus_id.is_none())
+            })
+            .and_then(Option::take);
+    }
+    let mut gpus: Vec<GpuInfo> = adapters
+        .into_iter()
+        .zip(backends)
+        .map(|(wmi, backend)| match backend {
+            Some(mut gpu) => {
+                gpu.merge(&wmi);
+                gpu
+            }
+            None => wmi,
+        })
+        .collect();
+    gpus.extend(
+        detected
+            .into_iter()
+            .flat_map(|(_, rest)| rest.into_iter().flatten()),
+    );
+    gpus
+}
+
+/// Backend GPUs of `vendor` not matched yet, running `detect` on first use
+fn vendor_gpus<'a>(
+    detected: &'a mut Vec<(Vendor, Vec<Option<GpuInfo>>)>,
+    vendor: Vendor,
+    detect: &mut impl FnMut(Vendor) -> Vec<GpuInfo>,
+) -> &'a mut Vec<Option<GpuInfo>> {
+    let index = match detected.iter().position(|(v, _)| same_vendor(*v, vendor)) {
+        Some(index) => index,
+        None => {
+            detected.push((vendor, detect(vendor).into_iter().map(Some).collect()));
+            detected.len() - 1
+        }
+    };
+    &mut detected[index].1
+}
+
+/// Whether `a` and `b` are the same vendor, ignoring the Intel GPU type
+fn same_vendor(a: Vendor, b: Vendor) -> bool {
+    mem::discriminant(&a) == mem::discriminant(&b)
+}
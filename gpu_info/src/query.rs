@@ -7,7 +7,7 @@
 //! # Examples
 //!
 //! ```
-//! use gpu_info::{GpuManager, Vendor};
+//! use gpu_info::{GpuManager, SortOrder, Vendor};
 //!
 //! let manager = GpuManager::new();
 //!
@@ -29,17 +29,53 @@
 //!     .query()
 //!     .active_only()
 //!     .first();
+//!
+//! // Get the two GPUs with the most free memory
+//! let roomiest = manager
+//!     .query()
+//!     .sort_by_free_memory(SortOrder::Descending)
+//!     .take(2);
 //! ```
 
+use crate::gpu_index::GpuIndex;
 use crate::gpu_info::GpuInfo;
 use crate::gpu_manager::GpuManager;
 use crate::vendor::Vendor;
+use std::cmp::Ordering;
+use std::fmt::{self, Debug, Formatter};
 use std::sync::Arc;
 
+/// Direction of a [`GpuQuery`] sort.
+///
+/// GPUs missing the sorted value come last in both directions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SortOrder {
+    /// Smallest value first.
+    #[default]
+    Ascending,
+    /// Largest value first.
+    Descending,
+}
+
+/// Compares two GPUs for sorting
+type Comparator<'a> = dyn Fn(&GpuInfo, &GpuInfo) -> Ordering + Send + Sync + 'a;
+
+/// Comparison set by the `sort_by_*` methods of a [`GpuQuery`]
+#[derive(Clone)]
+struct GpuOrder<'a>(Arc<Comparator<'a>>);
+
+impl Debug for GpuOrder<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("GpuOrder")
+    }
+}
+
 /// Query builder for filtering GPUs.
 ///
 /// Created by calling [`GpuManager::query()`]. The query is lazy - no filtering
-/// happens until a terminal method (`collect()`, `first()`, `count()`) is called.
+/// happens until a terminal method (`collect()`, `take()`, `first()`,
+/// `count()`) is called. Without a `sort_by_*` method, GPUs keep the order of
+/// the manager.
 ///
 /// # Examples
 ///
@@ -64,9 +100,14 @@ pub struct GpuQuery<'a> {
     max_temp: Option<f32>,
     min_util: Option<f32>,
     max_util: Option<f32>,
+    min_memory_total: Option<u32>,
+    max_memory_total: Option<u32>,
+    min_encoder_util: Option<f32>,
     active_only: bool,
     has_temperature: Option<bool>,
     has_power: Option<bool>,
+    process_name: Option<String>,
+    order: Option<GpuOrder<'a>>,
 }
 
 impl<'a> GpuQuery<'a> {
@@ -81,9 +122,14 @@ impl<'a> GpuQuery<'a> {
             max_temp: None,
             min_util: None,
             max_util: None,
+            min_memory_total: None,
+            max_memory_total: None,
+            min_encoder_util: None,
             active_only: false,
             has_temperature: None,
             has_power: None,
+            process_name: None,
+            order: None,
         }
     }
 
@@ -162,6 +208,59 @@ impl<'a> GpuQuery<'a> {
         self
     }
 
+    /// Filters GPUs with total memory at or above the specified value in MB.
+    ///
+    /// GPUs with unknown total memory do not match.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gpu_info::GpuManager;
+    ///
+    /// let manager = GpuManager::new();
+    /// let large_gpus = manager.query().min_memory_total_mb(8192).collect();
+    /// ```
+    pub fn min_memory_total_mb(mut self, mb: u32) -> Self {
+        self.min_memory_total = Some(mb);
+        self
+    }
+
+    /// Filters GPUs with total memory at or below the specified value in MB.
+    ///
+    /// GPUs with unknown total memory do not match.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gpu_info::GpuManager;
+    ///
+    /// let manager = GpuManager::new();
+    /// let small_gpus = manager.query().max_memory_total_mb(4096).collect();
+    /// ```
+    pub fn max_memory_total_mb(mut self, mb: u32) -> Self {
+        self.max_memory_total = Some(mb);
+        self
+    }
+
+    /// Filters GPUs whose video encoder (NVENC) utilization is strictly
+    /// above the specified percentage.
+    ///
+    /// GPUs with unknown encoder utilization do not match, so
+    /// `encoder_util_above(0.0)` finds the GPUs currently encoding video.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gpu_info::GpuManager;
+    ///
+    /// let manager = GpuManager::new();
+    /// let encoding_gpus = manager.query().encoder_util_above(0.0).collect();
+    /// ```
+    pub fn encoder_util_above(mut self, util: f32) -> Self {
+        self.min_encoder_util = Some(util);
+        self
+    }
+
     /// Filters to only include active GPUs.
     ///
     /// # Examples
@@ -207,6 +306,107 @@ impl<'a> GpuQuery<'a> {
         self
     }
 
+    /// Filters GPUs running a process with the given name, e.g. `python3`.
+    ///
+    /// Names are compared exactly, as returned by [`GpuProcessInfo::name()`].
+    /// GPUs whose processes are unknown do not match.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gpu_info::GpuManager;
+    ///
+    /// let manager = GpuManager::new();
+    /// let training_gpus = manager.query().filter_by_process_name("python3").collect();
+    /// ```
+    ///
+    /// [`GpuProcessInfo::name()`]: crate::GpuProcessInfo::name
+    pub fn filter_by_process_name(mut self, name: &str) -> Self {
+        self.process_name = Some(name.to_string());
+        self
+    }
+
+    /// Sorts matching GPUs by temperature.
+    ///
+    /// GPUs without a temperature come last. Replaces any earlier sort.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gpu_info::{GpuManager, SortOrder, Vendor};
+    ///
+    /// let manager = GpuManager::new();
+    /// let coolest_nvidia = manager
+    ///     .query()
+    ///     .vendor(Vendor::Nvidia)
+    ///     .sort_by_temperature(SortOrder::Ascending)
+    ///     .first();
+    /// ```
+    pub fn sort_by_temperature(self, order: SortOrder) -> Self {
+        self.sort_by_key(|gpu| gpu.temperature, order)
+    }
+
+    /// Sorts matching GPUs by utilization.
+    ///
+    /// GPUs without a utilization come last. Replaces any earlier sort.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gpu_info::{GpuManager, SortOrder};
+    ///
+    /// let manager = GpuManager::new();
+    /// let idlest = manager.query().sort_by_utilization(SortOrder::Ascending).first();
+    /// ```
+    pub fn sort_by_utilization(self, order: SortOrder) -> Self {
+        self.sort_by_key(|gpu| gpu.utilization, order)
+    }
+
+    /// Sorts matching GPUs by free memory (total minus used, in MB).
+    ///
+    /// GPUs without total or used memory come last. Replaces any earlier sort.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gpu_info::{GpuManager, SortOrder};
+    ///
+    /// let manager = GpuManager::new();
+    /// let roomiest = manager.query().sort_by_free_memory(SortOrder::Descending).take(2);
+    /// ```
+    pub fn sort_by_free_memory(self, order: SortOrder) -> Self {
+        self.sort_by_key(free_memory_mb, order)
+    }
+
+    /// Sorts matching GPUs by a key.
+    ///
+    /// GPUs for which `key` returns `None`, or a value that does not compare
+    /// equal to itself such as `f32::NAN`, come last in both directions. The
+    /// sort is stable, so GPUs with equal keys keep the manager's order.
+    /// Replaces any earlier sort.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gpu_info::{GpuManager, SortOrder};
+    ///
+    /// let manager = GpuManager::new();
+    /// let fastest = manager
+    ///     .query()
+    ///     .sort_by_key(|gpu| gpu.max_clock_speed(), SortOrder::Descending)
+    ///     .first();
+    /// ```
+    pub fn sort_by_key<K, F>(mut self, key: F, order: SortOrder) -> Self
+    where
+        K: PartialOrd,
+        F: Fn(&GpuInfo) -> Option<K> + Send + Sync + 'a,
+    {
+        self.order = Some(GpuOrder(Arc::new(move |a, b| {
+            compare_keys(key(a), key(b), order)
+        })));
+        self
+    }
+
     /// Collects all matching GPUs (terminal method).
     ///
     /// Returns `Arc<GpuInfo>` for zero-copy access. Use this when you need
@@ -214,7 +414,7 @@ impl<'a> GpuQuery<'a> {
     ///
     /// # Time Complexity
     ///
-    /// O(n) where n is the number of GPUs in the manager.
+    /// O(n) where n is the number of GPUs in the manager, O(n log n) if sorted.
     ///
     /// # Examples
     ///
@@ -226,20 +426,41 @@ impl<'a> GpuQuery<'a> {
     /// println!("Found {} NVIDIA GPUs", nvidia_gpus.len());
     /// ```
     pub fn collect(self) -> Vec<Arc<GpuInfo>> {
-        (0..self.manager.gpu_count())
-            .filter_map(|i| self.manager.get_gpu_cached(i))
-            .filter(|gpu| self.matches(gpu))
-            .collect()
+        let mut gpus: Vec<_> = self.matching().collect();
+        if let Some(order) = &self.order {
+            gpus.sort_by(|a, b| (order.0)(a, b));
+        }
+        gpus
+    }
+
+    /// Returns the first `n` matching GPUs (terminal method).
+    ///
+    /// Combine with a `sort_by_*` method to select the top GPUs by a metric.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gpu_info::{GpuManager, SortOrder};
+    ///
+    /// let manager = GpuManager::new();
+    /// let busiest = manager.query().sort_by_utilization(SortOrder::Descending).take(2);
+    /// assert!(busiest.len() <= 2);
+    /// ```
+    pub fn take(self, n: usize) -> Vec<Arc<GpuInfo>> {
+        let mut gpus = self.collect();
+        gpus.truncate(n);
+        gpus
     }
 
     /// Returns the first matching GPU (terminal method).
     ///
     /// Returns `Arc<GpuInfo>` for zero-copy access. Use this when you only
-    /// need one matching GPU.
+    /// need one matching GPU. If the query is sorted, this is the first GPU
+    /// in sort order.
     ///
     /// # Time Complexity
     ///
-    /// O(n) worst case, but short-circuits on first match.
+    /// O(n) worst case, but short-circuits on first match if not sorted.
     ///
     /// # Examples
     ///
@@ -252,9 +473,11 @@ impl<'a> GpuQuery<'a> {
     /// }
     /// ```
     pub fn first(self) -> Option<Arc<GpuInfo>> {
-        (0..self.manager.gpu_count())
-            .filter_map(|i| self.manager.get_gpu_cached(i))
-            .find(|gpu| self.matches(gpu))
+        match &self.order {
+            // `min_by` keeps the first of equal GPUs, as the stable sort does
+            Some(order) => self.matching().min_by(|a, b| (order.0)(a, b)),
+            None => self.matching().next(),
+        }
     }
 
     /// Counts matching GPUs (terminal method).
@@ -275,10 +498,7 @@ impl<'a> GpuQuery<'a> {
     /// println!("Found {} NVIDIA GPUs", nvidia_count);
     /// ```
     pub fn count(self) -> usize {
-        (0..self.manager.gpu_count())
-            .filter_map(|i| self.manager.get_gpu_cached(i))
-            .filter(|gpu| self.matches(gpu))
-            .count()
+        self.matching().count()
     }
 
     /// Checks if any GPU matches the query (terminal method).
@@ -299,6 +519,13 @@ impl<'a> GpuQuery<'a> {
         self.first().is_some()
     }
 
+    /// Iterates over the matching GPUs in the manager's order.
+    fn matching(&self) -> impl Iterator<Item = Arc<GpuInfo>> + '_ {
+        (0..self.manager.gpu_count())
+            .filter_map(|i| self.manager.get_cached_gpu_at(GpuIndex(i)))
+            .filter(|gpu| self.matches(gpu))
+    }
+
     /// Checks if a GPU matches all the query filters.
     fn matches(&self, gpu: &GpuInfo) -> bool {
         // Vendor filter
@@ -338,6 +565,29 @@ impl<'a> GpuQuery<'a> {
             }
         }
 
+        // Memory filters
+        if let Some(min_memory) = self.min_memory_total {
+            match gpu.memory_total {
+                Some(memory) if memory >= min_memory => {}
+                _ => return false,
+            }
+        }
+
+        if let Some(max_memory) = self.max_memory_total {
+            match gpu.memory_total {
+                Some(memory) if memory <= max_memory => {}
+                _ => return false,
+            }
+        }
+
+        // Encoder filter
+        if let Some(min_encoder_util) = self.min_encoder_util {
+            match gpu.encoder_util {
+                Some(util) if util > min_encoder_util => {}
+                _ => return false,
+            }
+        }
+
         // Active filter
         if self.active_only && !gpu.active.unwrap_or(false) {
             return false;
@@ -357,10 +607,44 @@ impl<'a> GpuQuery<'a> {
             }
         }
 
+        // Process filter
+        if let Some(name) = &self.process_name {
+            let processes = gpu.processes.as_deref().unwrap_or_default();
+            if !processes
+                .iter()
+                .any(|process| process.name().as_deref() == Some(name.as_str()))
+            {
+                return false;
+            }
+        }
+
         true
     }
 }
 
+/// Returns the free memory of a GPU in MB.
+fn free_memory_mb(gpu: &GpuInfo) -> Option<u32> {
+    gpu.memory_total?.checked_sub(gpu.memory_used?)
+}
+
+/// Compares two sort keys, placing missing and unordered keys last.
+fn compare_keys<K: PartialOrd>(a: Option<K>, b: Option<K>, order: SortOrder) -> Ordering {
+    // A key that is not equal to itself, e.g. NaN, cannot be ordered
+    let ordered = |key: &K| key.partial_cmp(key).is_some();
+    match (a.filter(ordered), b.filter(ordered)) {
+        (Some(a), Some(b)) => {
+            let ordering = a.partial_cmp(&b).unwrap_or(Ordering::Equal);
+            match order {
+                SortOrder::Ascending => ordering,
+                SortOrder::Descending => ordering.reverse(),
+            }
+        }
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
 // TODO: there should be no tests here. Transfer them to gpu_info\src\test
 #[cfg(test)]
 mod tests {
@@ -479,6 +763,66 @@ mod tests {
         assert!(!query.matches(&no_temp_gpu));
     }
 
+    #[test]
+    fn test_matches_min_memory_total() {
+        let manager = GpuManager::new();
+        let query = GpuQuery::new(&manager).min_memory_total_mb(8192);
+        let gpus = [GpuInfo::mock_nvidia(), GpuInfo::mock_intel()];
+        let matching: Vec<_> = gpus.iter().filter(|gpu| query.matches(gpu)).collect();
+        assert_eq!(matching.len(), 1);
+        assert_eq!(matching[0].vendor, Vendor::Nvidia);
+    }
+
+    #[test]
+    fn test_matches_memory_total_range() {
+        let manager = GpuManager::new();
+        let query = GpuQuery::new(&manager)
+            .vendor(Vendor::Nvidia)
+            .min_memory_total_mb(4096)
+            .max_memory_total_mb(16384);
+        let mut small = GpuInfo::mock_nvidia();
+        small.memory_total = Some(2048);
+        let mut unknown = GpuInfo::mock_nvidia();
+        unknown.memory_total = None;
+        assert!(query.matches(&GpuInfo::mock_nvidia()));
+        assert!(!query.matches(&small));
+        assert!(!query.matches(&unknown));
+        assert!(!query.matches(&GpuInfo::mock_amd()));
+    }
+
+    #[test]
+    fn test_matches_encoder_util_above() {
+        let manager = GpuManager::new();
+        let query = GpuQuery::new(&manager).encoder_util_above(0.0);
+        let encoding = GpuInfo::builder().encoder_util(35.0).build();
+        let idle = GpuInfo::builder().encoder_util(0.0).build();
+        assert!(query.matches(&encoding));
+        assert!(!query.matches(&idle));
+        assert!(!query.matches(&GpuInfo::unknown()));
+    }
+
+    #[test]
+    fn test_matches_process_name() {
+        use crate::process_usage::{GpuProcessInfo, ProcessType};
+
+        let manager = GpuManager::new();
+        let query = GpuQuery::new(&manager).filter_by_process_name("python3");
+        let process = |name: &str| GpuProcessInfo {
+            pid: 4242,
+            memory_used_mb: 512,
+            process_type: ProcessType::Compute,
+            process_name: Some(name.to_string()),
+        };
+        let training = GpuInfo::builder()
+            .processes([process("Xorg"), process("python3")])
+            .build();
+        let desktop = GpuInfo::builder().processes([process("Xorg")]).build();
+        assert!(query.matches(&training));
+        assert!(!query.matches(&desktop));
+        assert!(!query.matches(&GpuInfo::builder().processes([]).build()));
+        assert!(!query.matches(&GpuInfo::unknown()));
+    }
+
     #[test]
     fn test_matches_active_only() {
         let manager = GpuManager::new();
@@ -0,0 +1,205 @@
+//! Redaction of machine-identifying fields in exported data.
+//!
+//! Metrics are safe to ship off the machine, but some fields identify the
+//! hardware itself, such as the device UUID. [`Redaction`] is the policy
+//! applied to those fields wherever `gpu_info` data is exported: through the
+//! [`Redacted`] serde wrapper, or by
+//! [`SampleWriter::with_redaction`](crate::jsonl::SampleWriter::with_redaction)
+//! for JSONL samples.
+//!
+//! The fields a policy applies to are listed in [`IDENTIFYING_FIELDS`].
+//! Every other serialized field is listed as non-identifying next to it, and
+//! a test fails when a new field is in neither list, so each field is
+//! classified when it is added.
+//!
+//! # Examples
+//!
+//! ```
+//! use gpu_info::redaction::{Redact, Redaction};
+//! use gpu_info::GpuInfo;
+//!
+//! let mut gpu = GpuInfo::builder()
+//!     .name("NVIDIA GeForce RTX 4090")
+//!     .uuid("GPU-5a8f3b2e-1c4d-4e6f-9a0b-7c8d9e0f1a2b")
+//!     .temperature(61.0)
+//!     .build();
+//! gpu.redact(&Redaction::hash_identifiers("fleet-salt"));
+//! assert!(gpu.uuid.as_deref().unwrap().starts_with("sha256:"));
+//! assert_eq!(gpu.temperature, Some(61.0));
+//! ```
+use crate::gpu_info::GpuInfo;
+use crate::monitoring::GpuMetricsSnapshot;
+use sha2::{Digest, Sha256};
+
+/// Serialized `GpuInfo` fields that identify the machine.
+///
+/// A [`Redaction`] policy applies to these fields only.
+pub const IDENTIFYING_FIELDS: &[&str] = &["uuid"];
+
+/// Serialized `GpuInfo` fields reviewed as safe to export.
+///
+/// The PCI bus ID is a slot address shared by every machine with the same
+/// board layout, so it is kept to tell identical cards apart.
+#[cfg_attr(not(all(test, feature = "serde_json")), allow(dead_code))]
+pub(crate) const NON_IDENTIFYING_FIELDS: &[&str] = &[
+    "vendor",
+    "name_gpu",
+    "temperature",
+    "temperatures",
+    "utilization",
+    "power_usage",
+    "core_clock",
+    "memory_util",
+    "memory_clock",
+    "active",
+    "power_limit",
+    "memory_total",
+    "memory_used",
+    "driver_version",
+    "max_clock_speed",
+    "effective_core_clock",
+    "fan_speed",
+    "fan_speed_rpm",
+    "encoder_util",
+    "decoder_util",
+    "throttle_reasons",
+    "processes",
+    "ecc_errors",
+    "bus_id",
+    "adapter_luid",
+    "metric_sources",
+    "confidence",
+];
+
+/// How identifying fields are exported.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub enum Redaction {
+    /// Export identifying fields unchanged.
+    #[default]
+    None,
+    /// Replace each identifying value with a salted hash.
+    ///
+    /// The value becomes `sha256:` followed by the first 16 hex digits of
+    /// the SHA-256 of the salt followed by the value. The same salt gives
+    /// the same hash on every run and machine, so exported records can be
+    /// correlated without revealing the values.
+    HashIdentifiers {
+        /// Prepended to each value before hashing
+        salt: String,
+    },
+    /// Remove identifying values.
+    StripIdentifiers,
+}
+
+impl Redaction {
+    /// Creates a [`Redaction::HashIdentifiers`] policy with `salt`.
+    pub fn hash_identifiers(salt: impl Into<String>) -> Self {
+        Redaction::HashIdentifiers { salt: salt.into() }
+    }
+
+    /// Returns `value` as exported under this policy.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gpu_info::redaction::Redaction;
+    ///
+    /// let policy = Redaction::hash_identifiers("salt");
+    /// assert_eq!(policy.apply("GPU-1"), policy.apply("GPU-1"));
+    /// assert_ne!(policy.apply("GPU-1"), Some("GPU-1".to_string()));
+    /// assert_eq!(Redaction::StripIdentifiers.apply("GPU-1"), None);
+    /// ```
+    pub fn apply(&self, value: &str) -> Option<String> {
+        match self {
+            Redaction::None => Some(value.to_string()),
+            Redaction::HashIdentifiers { salt } => {
+                let digest = Sha256::new()
+                    .chain_update(salt)
+                    .chain_update(value)
+                    .finalize();
+                let hex: String = digest[..8]
+                    .iter()
+                    .map(|byte| format!("{:02x}", byte))
+                    .collect();
+                Some(format!("sha256:{}", hex))
+            }
+            Redaction::StripIdentifiers => None,
+        }
+    }
+
+    /// Applies the policy to an optional identifying field in place.
+    pub fn apply_to(&self, field: &mut Option<String>) {
+        if *self != Redaction::None {
+            *field = field.as_deref().and_then(|value| self.apply(value));
+        }
+    }
+}
+
+/// Types whose identifying fields can be redacted.
+pub trait Redact {
+    /// Applies `policy` to every field in [`IDENTIFYING_FIELDS`].
+    fn redact(&mut self, policy: &Redaction);
+}
+
+impl Redact for GpuInfo {
+    fn redact(&mut self, policy: &Redaction) {
+        policy.apply_to(&mut self.uuid);
+    }
+}
+
+impl Redact for GpuMetricsSnapshot {
+    fn redact(&mut self, policy: &Redaction) {
+        self.gpus.redact(policy);
+    }
+}
+
+impl<T: Redact> Redact for Vec<T> {
+    fn redact(&mut self, policy: &Redaction) {
+        for value in self {
+            value.redact(policy);
+        }
+    }
+}
+
+/// Serializes a value with its identifying fields redacted.
+///
+/// The value is cloned and redacted on each serialization; the original is
+/// left unchanged.
+///
+/// # Examples
+///
+/// ```
+/// use gpu_info::redaction::{Redacted, Redaction};
+/// use gpu_info::GpuInfo;
+///
+/// let gpu = GpuInfo::builder().uuid("GPU-1").build();
+/// let json = serde_json::to_value(Redacted::new(&gpu, &Redaction::StripIdentifiers)).unwrap();
+/// assert!(json["uuid"].is_null());
+/// assert_eq!(gpu.uuid.as_deref(), Some("GPU-1"));
+/// ```
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, Copy)]
+pub struct Redacted<'a, T> {
+    value: &'a T,
+    policy: &'a Redaction,
+}
+
+#[cfg(feature = "serde")]
+impl<'a, T> Redacted<'a, T> {
+    /// Wraps `value` to be serialized under `policy`.
+    pub fn new(value: &'a T, policy: &'a Redaction) -> Self {
+        Self { value, policy }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: Redact + Clone + serde::Serialize> serde::Serialize for Redacted<'_, T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if *self.policy == Redaction::None {
+            return self.value.serialize(serializer);
+        }
+        let mut value = self.value.clone();
+        value.redact(self.policy);
+        value.serialize(serializer)
+    }
+}
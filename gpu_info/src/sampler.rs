@@ -0,0 +1,407 @@
+//! Shared GPU sampling for multiple monitors.
+//!
+//! A [`Sampler`] owns the polling thread and the GPU source, and delivers
+//! every sample to its registered [`SampleSubscriber`]s. Several
+//! [`GpuMonitor`]s can share one sampler so that, for example, a UI monitor
+//! with loose thresholds and a safety monitor with tight thresholds cost a
+//! single provider update per tick instead of one each.
+//!
+//! The sampler runs at the shortest interval requested by its active
+//! subscribers, and recomputes it whenever a subscriber is added, removed,
+//! paused or resumed. The thread is started with the first subscriber and
+//! exits after the last one unsubscribes.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use gpu_info::{GpuMonitor, GpuThresholds, MonitorConfig, Sampler};
+//! use std::sync::Arc;
+//! use std::time::Duration;
+//!
+//! let sampler = Arc::new(Sampler::default());
+//! let ui = GpuMonitor::with_sampler(
+//!     MonitorConfig::default().with_polling_interval(Duration::from_secs(1)),
+//!     Arc::clone(&sampler),
+//! );
+//! let safety = GpuMonitor::with_sampler(
+//!     MonitorConfig::default()
+//!         .with_polling_interval(Duration::from_millis(250))
+//!         .with_thresholds(GpuThresholds {
+//!             temperature_critical: 80.0,
+//!             ..Default::default()
+//!         }),
+//!     Arc::clone(&sampler),
+//! );
+//! ui.start_monitoring().unwrap();
+//! safety.start_monitoring().unwrap();
+//! assert_eq!(sampler.interval(), Some(Duration::from_millis(250)));
+//! ```
+//!
+//! [`GpuMonitor`]: crate::GpuMonitor
+use crate::gpu_info::{GpuError, GpuInfo, Result};
+use crate::gpu_manager::GpuManager;
+use crate::monitoring::{set_current_thread_priority, ThreadPriority};
+use log::{debug, info, warn};
+use std::fmt::{Debug, Formatter};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// Source of GPU samples, called once per sampler tick.
+pub type SampleSourceFn = Box<dyn FnMut() -> Result<Vec<GpuInfo>> + Send>;
+
+/// Identifier returned by [`Sampler::subscribe()`].
+pub type SubscriberId = u64;
+
+/// Consecutive collection errors before the sampler backs off
+const MAX_CONSECUTIVE_ERRORS: u32 = 10;
+
+/// Receiver of samples collected by a [`Sampler`].
+///
+/// Subscribers are called from the sampler thread and must not block for
+/// long, since the next tick waits for all of them.
+pub trait SampleSubscriber: Send + Sync {
+    /// Returns the interval at which this subscriber wants samples, or
+    /// `None` while it is paused.
+    ///
+    /// Paused subscribers do not receive samples. The sampler only collects
+    /// while at least one subscriber is active.
+    fn requested_interval(&self) -> Option<Duration>;
+
+    /// Returns the scheduling priority this subscriber wants for the
+    /// sampler thread.
+    ///
+    /// The thread runs at [`ThreadPriority::Normal`] if any active
+    /// subscriber asks for it.
+    fn thread_priority(&self) -> ThreadPriority {
+        ThreadPriority::Normal
+    }
+
+    /// Handles a successfully collected sample.
+    fn on_sample(&self, gpus: &[GpuInfo], collected_at: Instant);
+
+    /// Handles a failed collection.
+    fn on_error(&self, error: &GpuError);
+}
+
+/// Registered subscribers and thread bookkeeping
+struct SamplerState {
+    /// Subscribers in registration order
+    subscribers: Vec<(SubscriberId, Arc<dyn SampleSubscriber>)>,
+    /// Whether the sampling thread is alive
+    running: bool,
+    /// Set when the subscriber set or their intervals changed
+    dirty: bool,
+    /// Handle of the current (or last exited) sampling thread
+    thread_handle: Option<JoinHandle<()>>,
+}
+
+/// State shared between a sampler and its thread
+struct SamplerShared {
+    /// GPU source
+    source: Mutex<SampleSourceFn>,
+    /// Subscribers and thread state
+    state: Mutex<SamplerState>,
+    /// Wakes the thread when `state.dirty` is set
+    wakeup: Condvar,
+    /// Held for the duration of a tick, from subscriber snapshot to delivery
+    tick_lock: Mutex<()>,
+    /// Next subscriber id
+    next_id: AtomicU64,
+    /// Number of completed source calls
+    ticks: AtomicU64,
+}
+
+/// Polling thread shared by several subscribers.
+///
+/// See the [module documentation](self) for details.
+pub struct Sampler {
+    shared: Arc<SamplerShared>,
+}
+
+impl Debug for Sampler {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Sampler")
+            .field("subscribers", &self.subscriber_count())
+            .field("interval", &self.interval())
+            .field("ticks", &self.tick_count())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for Sampler {
+    fn default() -> Self {
+        Self::new(Arc::new(Mutex::new(GpuManager::new())))
+    }
+}
+
+impl Sampler {
+    /// Creates a sampler that refreshes `manager` on every tick.
+    ///
+    /// Use one sampler per manager; refreshing the same manager from two
+    /// samplers doubles the provider calls this type exists to avoid.
+    pub fn new(manager: Arc<Mutex<GpuManager>>) -> Self {
+        Self::with_source(move || {
+            let mut manager = manager.lock().map_err(|_| GpuError::GpuNotActive)?;
+            if let Err(e) = manager.refresh_all_gpus() {
+                if manager.gpu_count() == 0 {
+                    debug!("No GPUs found, attempting detection...");
+                }
+                return Err(e);
+            }
+            Ok(manager.get_all_gpus_owned())
+        })
+    }
+
+    /// Creates a sampler with a custom GPU source.
+    pub fn with_source<F>(source: F) -> Self
+    where
+        F: FnMut() -> Result<Vec<GpuInfo>> + Send + 'static,
+    {
+        Self {
+            shared: Arc::new(SamplerShared {
+                source: Mutex::new(Box::new(source)),
+                state: Mutex::new(SamplerState {
+                    subscribers: Vec::new(),
+                    running: false,
+                    dirty: false,
+                    thread_handle: None,
+                }),
+                wakeup: Condvar::new(),
+                tick_lock: Mutex::new(()),
+                next_id: AtomicU64::new(0),
+                ticks: AtomicU64::new(0),
+            }),
+        }
+    }
+
+    /// Registers `subscriber`, starting the sampling thread if needed.
+    pub fn subscribe(&self, subscriber: Arc<dyn SampleSubscriber>) -> Result<SubscriberId> {
+        let id = self.shared.next_id.fetch_add(1, Ordering::SeqCst);
+        let mut state = self.shared.lock_state()?;
+        state.subscribers.push((id, subscriber));
+        state.dirty = true;
+        if !state.running {
+            if let Some(previous) = state.thread_handle.take() {
+                // The previous thread has already marked itself stopped
+                let _ = previous.join();
+            }
+            let shared = Arc::clone(&self.shared);
+            state.thread_handle = Some(thread::spawn(move || shared.run()));
+            state.running = true;
+            info!("Started GPU sampler thread");
+        }
+        self.shared.wakeup.notify_all();
+        Ok(id)
+    }
+
+    /// Removes the subscriber with the given id.
+    ///
+    /// Returns `false` if no such subscriber was registered. Once this
+    /// returns, the subscriber receives no further samples. The sampling
+    /// thread exits after the last subscriber is removed.
+    pub fn unsubscribe(&self, id: SubscriberId) -> bool {
+        let removed = match self.shared.lock_state() {
+            Ok(mut state) => {
+                let before = state.subscribers.len();
+                state.subscribers.retain(|(sub_id, _)| *sub_id != id);
+                state.dirty = true;
+                self.shared.wakeup.notify_all();
+                state.subscribers.len() != before
+            }
+            Err(_) => false,
+        };
+        // Wait for a tick that may still be delivering to the subscriber
+        drop(self.shared.tick_lock.lock());
+        removed
+    }
+
+    /// Asks the sampler to recompute its interval.
+    ///
+    /// Call this after a subscriber's [`requested_interval()`] changed, e.g.
+    /// when it was paused or resumed.
+    ///
+    /// [`requested_interval()`]: SampleSubscriber::requested_interval
+    pub fn wake(&self) {
+        if let Ok(mut state) = self.shared.lock_state() {
+            state.dirty = true;
+            self.shared.wakeup.notify_all();
+        }
+    }
+
+    /// Returns the current sampling interval, or `None` if no subscriber is
+    /// active.
+    pub fn interval(&self) -> Option<Duration> {
+        let subscribers = self.shared.snapshot().ok()?;
+        min_interval(&subscribers)
+    }
+
+    /// Returns the number of registered subscribers.
+    pub fn subscriber_count(&self) -> usize {
+        self.shared
+            .lock_state()
+            .map(|state| state.subscribers.len())
+            .unwrap_or(0)
+    }
+
+    /// Returns the number of samples collected so far.
+    ///
+    /// Each tick calls the source exactly once, however many subscribers
+    /// receive the result.
+    pub fn tick_count(&self) -> u64 {
+        self.shared.ticks.load(Ordering::SeqCst)
+    }
+
+    /// Checks if the sampling thread is running.
+    pub fn is_running(&self) -> bool {
+        self.shared
+            .lock_state()
+            .map(|state| state.running)
+            .unwrap_or(false)
+    }
+}
+
+impl SamplerShared {
+    fn lock_state(&self) -> Result<MutexGuard<'_, SamplerState>> {
+        self.state.lock().map_err(|_| GpuError::GpuNotActive)
+    }
+
+    fn snapshot(&self) -> Result<Vec<Arc<dyn SampleSubscriber>>> {
+        let state = self.lock_state()?;
+        Ok(state
+            .subscribers
+            .iter()
+            .map(|(_, subscriber)| Arc::clone(subscriber))
+            .collect())
+    }
+
+    /// Sampling thread body
+    fn run(&self) {
+        let mut consecutive_errors = 0;
+        let mut last_tick: Option<Instant> = None;
+        let mut priority: Option<ThreadPriority> = None;
+        loop {
+            let interval = match self.lock_state() {
+                Ok(mut state) => {
+                    if state.subscribers.is_empty() {
+                        state.running = false;
+                        info!("GPU sampler thread stopped, no subscribers left");
+                        return;
+                    }
+                    state.dirty = false;
+                    drop(state);
+                    self.snapshot().ok().and_then(|subs| min_interval(&subs))
+                }
+                Err(_) => return,
+            };
+            if let Some(interval) = interval {
+                if last_tick.map_or(true, |at| at.elapsed() >= interval) {
+                    last_tick = Some(Instant::now());
+                    if self.tick(&mut priority) {
+                        consecutive_errors = 0;
+                    } else {
+                        consecutive_errors += 1;
+                        if consecutive_errors >= MAX_CONSECUTIVE_ERRORS {
+                            warn!(
+                                "Too many consecutive errors ({}), taking a longer break",
+                                consecutive_errors
+                            );
+                            thread::sleep(Duration::from_secs(1));
+                            consecutive_errors = 0;
+                        }
+                    }
+                    continue;
+                }
+            }
+            let Ok(state) = self.lock_state() else {
+                return;
+            };
+            if state.dirty {
+                continue;
+            }
+            let timeout = interval.map(|interval| {
+                interval.saturating_sub(last_tick.map_or(Duration::ZERO, |at| at.elapsed()))
+            });
+            let poisoned = match timeout {
+                Some(timeout) => self
+                    .wakeup
+                    .wait_timeout_while(state, timeout, |state| !state.dirty)
+                    .is_err(),
+                None => self.wakeup.wait_while(state, |state| !state.dirty).is_err(),
+            };
+            if poisoned {
+                return;
+            }
+        }
+    }
+
+    /// Collects one sample and delivers it to the active subscribers.
+    ///
+    /// Returns `false` if the collection failed.
+    fn tick(&self, priority: &mut Option<ThreadPriority>) -> bool {
+        let _tick = self.tick_lock.lock();
+        let Ok(subscribers) = self.snapshot() else {
+            return false;
+        };
+        let active: Vec<_> = subscribers
+            .into_iter()
+            .filter(|subscriber| subscriber.requested_interval().is_some())
+            .collect();
+        if active.is_empty() {
+            return true;
+        }
+        let wanted = if active
+            .iter()
+            .any(|subscriber| subscriber.thread_priority() == ThreadPriority::Normal)
+        {
+            ThreadPriority::Normal
+        } else {
+            ThreadPriority::Low
+        };
+        if *priority != Some(wanted) {
+            if let Err(e) = set_current_thread_priority(wanted) {
+                warn!(
+                    "Failed to set sampler thread priority to {:?}: {}",
+                    wanted, e
+                );
+            }
+            *priority = Some(wanted);
+        }
+
+        let collected_at = Instant::now();
+        let result = match self.source.lock() {
+            Ok(mut source) => (source)(),
+            Err(_) => Err(GpuError::GpuNotActive),
+        };
+        self.ticks.fetch_add(1, Ordering::SeqCst);
+        match result {
+            Ok(gpus) => {
+                debug!(
+                    "Sampled {} GPUs for {} subscribers",
+                    gpus.len(),
+                    active.len()
+                );
+                for subscriber in &active {
+                    subscriber.on_sample(&gpus, collected_at);
+                }
+                true
+            }
+            Err(e) => {
+                debug!("GPU data collection failed: {}", e);
+                for subscriber in &active {
+                    subscriber.on_error(&e);
+                }
+                false
+            }
+        }
+    }
+}
+
+/// Returns the shortest interval requested by an active subscriber
+fn min_interval(subscribers: &[Arc<dyn SampleSubscriber>]) -> Option<Duration> {
+    subscribers
+        .iter()
+        .filter_map(|subscriber| subscriber.requested_interval())
+        .min()
+}
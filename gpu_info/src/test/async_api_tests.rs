@@ -5,9 +5,15 @@
 
 #[cfg(test)]
 mod tests {
-    use crate::async_api::{get_all_async, get_all_async_owned, get_async, update_gpu_async};
+    use crate::async_api::{
+        get_all_async, get_all_async_owned, get_async, prefetch, primary_cached_async,
+        spawn_polling, spawn_subscription, update_gpu_async, warm_async, GpuStream,
+        SubscribeOptions,
+    };
     use crate::gpu_info::GpuInfo;
+    use crate::gpu_manager::GpuManager;
     use crate::vendor::Vendor;
+    use std::sync::atomic::{AtomicUsize, Ordering};
     use std::sync::Arc;
     use std::time::{Duration, Instant};
     use tokio::sync::Mutex;
@@ -252,4 +258,249 @@ mod tests {
             avg_get_all_time
         );
     }
+
+    static WARM_CALLS: AtomicUsize = AtomicUsize::new(0);
+    static SINGLE_FLIGHT_CALLS: AtomicUsize = AtomicUsize::new(0);
+    static THREAD_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+    fn count_warm(_gpu: &mut GpuInfo) -> crate::Result<()> {
+        WARM_CALLS.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }
+
+    fn count_single_flight(_gpu: &mut GpuInfo) -> crate::Result<()> {
+        SINGLE_FLIGHT_CALLS.fetch_add(1, Ordering::SeqCst);
+        std::thread::sleep(Duration::from_millis(20));
+        Ok(())
+    }
+
+    fn count_threads(_gpu: &mut GpuInfo) -> crate::Result<()> {
+        THREAD_CALLS.fetch_add(1, Ordering::SeqCst);
+        std::thread::sleep(Duration::from_millis(20));
+        Ok(())
+    }
+
+    fn counting_manager(
+        updater: fn(&mut GpuInfo) -> crate::Result<()>,
+    ) -> Arc<std::sync::Mutex<GpuManager>> {
+        let gpus = vec![GpuInfo::mock_nvidia(), GpuInfo::mock_amd()];
+        Arc::new(std::sync::Mutex::new(GpuManager::with_updater(
+            gpus,
+            updater,
+            Duration::from_secs(60),
+        )))
+    }
+
+    /// After a prefetch, the first cached read does not call providers again
+    #[tokio::test]
+    async fn test_prefetch_serves_first_read_from_cache() {
+        let manager = counting_manager(count_warm);
+        let shared = Arc::clone(&manager);
+        warm_async(move || shared).await;
+        assert_eq!(WARM_CALLS.load(Ordering::SeqCst), 2);
+
+        let shared = Arc::clone(&manager);
+        let gpu = primary_cached_async(move || shared)
+            .await
+            .expect("primary GPU should be cached");
+        assert_eq!(gpu.vendor, Vendor::Nvidia);
+        assert_eq!(WARM_CALLS.load(Ordering::SeqCst), 2);
+    }
+
+    /// Parallel prefetches share one detection
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_concurrent_prefetch_is_single_flight() {
+        let manager = counting_manager(count_single_flight);
+        let mut tasks = JoinSet::new();
+        for _ in 0..8 {
+            let shared = Arc::clone(&manager);
+            tasks.spawn(warm_async(move || shared));
+        }
+        while tasks.join_next().await.is_some() {}
+        assert_eq!(SINGLE_FLIGHT_CALLS.load(Ordering::SeqCst), 2);
+    }
+
+    /// `GpuManager::warm()` is idempotent on a shared manager
+    #[test]
+    fn test_warm_is_single_flight_across_threads() {
+        let manager = Arc::new(GpuManager::with_updater(
+            vec![GpuInfo::mock_intel()],
+            count_threads,
+            Duration::from_secs(60),
+        ));
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let manager = Arc::clone(&manager);
+                std::thread::spawn(move || manager.warm())
+            })
+            .collect();
+        for handle in handles {
+            handle.join().expect("warm thread panicked");
+        }
+        assert_eq!(THREAD_CALLS.load(Ordering::SeqCst), 1);
+    }
+
+    /// The global prefetch functions never fail, even without GPUs
+    #[tokio::test]
+    async fn test_global_prefetch() {
+        prefetch().await;
+        crate::prefetch_blocking();
+        let _ = get_async().await;
+    }
+
+    /// Subscription whose source returns a GPU at `temperatures[call]`,
+    /// repeating the last one, and counts its calls
+    fn temperature_subscription(
+        options: SubscribeOptions,
+        temperatures: &'static [f32],
+    ) -> (GpuStream<Arc<GpuInfo>>, Arc<AtomicUsize>) {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&calls);
+        let stream = spawn_subscription(options, move || {
+            let call = counter.fetch_add(1, Ordering::SeqCst);
+            let temperature = temperatures[call.min(temperatures.len() - 1)];
+            std::future::ready(Ok(Arc::new(
+                GpuInfo::builder().temperature(temperature).build(),
+            )))
+        });
+        (stream, calls)
+    }
+
+    /// Unchanged polls are not yielded by default
+    #[tokio::test(start_paused = true)]
+    async fn test_subscription_yields_changes_only() {
+        let start = tokio::time::Instant::now();
+        let (mut stream, calls) = temperature_subscription(
+            SubscribeOptions::new(Duration::from_secs(1)),
+            &[50.0, 50.0, 50.0, 61.0],
+        );
+        let first = stream.recv().await.unwrap();
+        assert_eq!(first.temperature, Some(50.0));
+        assert_eq!(start.elapsed(), Duration::ZERO);
+
+        let second = stream.recv().await.unwrap();
+        assert_eq!(second.temperature, Some(61.0));
+        assert_eq!(start.elapsed(), Duration::from_secs(3));
+        assert_eq!(calls.load(Ordering::SeqCst), 4);
+    }
+
+    /// With `emit_unchanged`, every tick is yielded
+    #[tokio::test(start_paused = true)]
+    async fn test_subscription_emit_unchanged() {
+        let start = tokio::time::Instant::now();
+        let options = SubscribeOptions::new(Duration::from_millis(250)).with_emit_unchanged(true);
+        assert!(options.emit_unchanged);
+        let (mut stream, _) = temperature_subscription(options, &[50.0]);
+        for tick in 0..4 {
+            assert_eq!(stream.recv().await.unwrap().temperature, Some(50.0));
+            assert_eq!(start.elapsed(), Duration::from_millis(250) * tick);
+        }
+    }
+
+    /// Dropping the stream stops the polling task
+    #[tokio::test(start_paused = true)]
+    async fn test_subscription_stops_when_dropped() {
+        let (mut stream, calls) = temperature_subscription(
+            SubscribeOptions::new(Duration::from_secs(1)).with_emit_unchanged(true),
+            &[50.0],
+        );
+        stream.recv().await.unwrap();
+        drop(stream);
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        let after_drop = calls.load(Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_secs(10)).await;
+        assert_eq!(calls.load(Ordering::SeqCst), after_drop);
+        assert!(after_drop <= 2);
+    }
+
+    /// Failed polls are skipped and `GpuStream` works as a `Stream`
+    #[tokio::test(start_paused = true)]
+    async fn test_subscribe_all_skips_failed_polls() {
+        use futures_core::Stream;
+        use std::pin::Pin;
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&calls);
+        let mut stream =
+            spawn_subscription(SubscribeOptions::new(Duration::from_secs(1)), move || {
+                let result = match counter.fetch_add(1, Ordering::SeqCst) {
+                    0 => Err(crate::gpu_info::GpuError::GpuNotActive),
+                    _ => Ok(vec![
+                        Arc::new(GpuInfo::mock_nvidia()),
+                        Arc::new(GpuInfo::mock_intel()),
+                    ]),
+                };
+                std::future::ready(result)
+            });
+        let gpus = std::future::poll_fn(|cx| Pin::new(&mut stream).poll_next(cx))
+            .await
+            .unwrap();
+        assert_eq!(gpus.len(), 2);
+        assert_eq!(gpus[0].vendor, Vendor::Nvidia);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    /// Polling task sending `GpuInfo::mock_nvidia()` on every tick, like
+    /// `stream_gpu_metrics()` without a real GPU, and counting its polls
+    fn mock_metrics_stream(
+        interval: Duration,
+    ) -> (
+        tokio::sync::mpsc::Receiver<GpuInfo>,
+        tokio::task::JoinHandle<()>,
+        Arc<AtomicUsize>,
+    ) {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&calls);
+        let (receiver, task) = spawn_polling(
+            SubscribeOptions::new(interval).with_emit_unchanged(true),
+            move || {
+                counter.fetch_add(1, Ordering::SeqCst);
+                std::future::ready(Ok(GpuInfo::mock_nvidia()))
+            },
+        );
+        (receiver, task, calls)
+    }
+
+    /// A frame arrives within twice the interval, and unchanged frames keep coming
+    #[tokio::test(start_paused = true)]
+    async fn test_stream_gpu_metrics_frames_arrive() {
+        let interval = Duration::from_millis(500);
+        let (mut metrics, _task, _) = mock_metrics_stream(interval);
+        for _ in 0..3 {
+            let gpu = tokio::time::timeout(interval * 2, metrics.recv())
+                .await
+                .expect("no frame within 2x the interval")
+                .unwrap();
+            assert_eq!(gpu.vendor, Vendor::Nvidia);
+        }
+    }
+
+    /// Dropping the receiver ends the task behind its `JoinHandle`
+    #[tokio::test(start_paused = true)]
+    async fn test_stream_gpu_metrics_stops_when_receiver_dropped() {
+        let interval = Duration::from_secs(1);
+        let (mut metrics, task, calls) = mock_metrics_stream(interval);
+        metrics.recv().await.unwrap();
+        drop(metrics);
+        tokio::time::timeout(interval * 2, task)
+            .await
+            .expect("task still running after the receiver was dropped")
+            .unwrap();
+        assert!(calls.load(Ordering::SeqCst) <= 2);
+    }
+
+    /// The real entry points send frames, or none without GPUs, and stop on drop
+    #[tokio::test]
+    async fn test_stream_all_gpus_metrics_stops_when_receiver_dropped() {
+        let interval = Duration::from_millis(100);
+        let (mut metrics, task) = crate::async_api::stream_all_gpus_metrics(interval);
+        if let Ok(Some(gpus)) = tokio::time::timeout(Duration::from_secs(5), metrics.recv()).await {
+            println!("Streamed {} GPUs", gpus.len());
+        }
+        drop(metrics);
+        tokio::time::timeout(Duration::from_secs(30), task)
+            .await
+            .expect("task still running after the receiver was dropped")
+            .unwrap();
+    }
 }
@@ -6,55 +6,80 @@
 mod tests {
     use crate::cache_utils::*;
     use crate::gpu_info::GpuInfo;
+    use crate::metric_source::Metric;
     use crate::vendor::Vendor;
-    use std::thread;
-    use std::time::Duration;
+    use std::sync::{Arc, Mutex};
+    use std::time::{Duration, Instant};
 
     fn create_test_gpu(vendor: Vendor) -> GpuInfo {
         GpuInfo::write_vendor(vendor)
     }
 
+    /// Clock that only moves when the test advances it
+    #[derive(Clone)]
+    struct ManualClock(Arc<Mutex<Instant>>);
+
+    impl ManualClock {
+        fn new() -> Self {
+            Self(Arc::new(Mutex::new(Instant::now())))
+        }
+
+        fn now(&self) -> Instant {
+            *self.0.lock().unwrap()
+        }
+
+        fn advance(&self, by: Duration) {
+            *self.0.lock().unwrap() += by;
+        }
+
+        /// Clock function for `with_clock()`
+        fn source(&self) -> impl Fn() -> Instant + Send + Sync + 'static {
+            let clock = self.clone();
+            move || clock.now()
+        }
+    }
+
     #[test]
     fn test_cache_entry_creation() {
         let gpu_info = create_test_gpu(Vendor::Nvidia);
         let entry = CacheEntry::new(gpu_info.clone());
         assert_eq!(entry.value.vendor, Vendor::Nvidia);
-        assert!(entry.timestamp.elapsed() < Duration::from_millis(100));
-        assert!(entry.last_accessed.elapsed() < Duration::from_millis(100));
+        assert_eq!(entry.last_accessed, entry.timestamp);
         assert_eq!(entry.access_count, 0);
     }
 
     #[test]
     fn test_cache_entry_is_valid() {
-        let gpu_info = create_test_gpu(Vendor::Nvidia);
-        let entry = CacheEntry::new(gpu_info);
-        let ttl = Duration::from_millis(100);
-        assert!(entry.is_valid(ttl));
-        thread::sleep(Duration::from_millis(150));
-        assert!(!entry.is_valid(ttl));
+        let created = Instant::now();
+        let entry = CacheEntry::created_at(create_test_gpu(Vendor::Nvidia), created);
+        let ttl = Duration::from_secs(1);
+        assert!(entry.is_valid_at(ttl, created));
+        assert!(entry.is_valid_at(ttl, created + Duration::from_millis(999)));
+        assert!(!entry.is_valid_at(ttl, created + ttl));
     }
 
     #[test]
     fn test_cache_entry_age() {
-        let gpu_info = create_test_gpu(Vendor::Nvidia);
-        let entry = CacheEntry::new(gpu_info);
-        thread::sleep(Duration::from_millis(50));
-        let age = entry.age();
-        assert!(age >= Duration::from_millis(50));
-        assert!(age < Duration::from_millis(100));
+        let created = Instant::now();
+        let entry = CacheEntry::created_at(create_test_gpu(Vendor::Nvidia), created);
+        assert_eq!(entry.age_at(created), Duration::ZERO);
+        assert_eq!(
+            entry.age_at(created + Duration::from_secs(5)),
+            Duration::from_secs(5)
+        );
     }
 
     #[test]
     fn test_cache_entry_record_access() {
-        let gpu_info = create_test_gpu(Vendor::Nvidia);
-        let mut entry = CacheEntry::new(gpu_info);
+        let created = Instant::now();
+        let mut entry = CacheEntry::created_at(create_test_gpu(Vendor::Nvidia), created);
         assert_eq!(entry.access_count, 0);
-        assert!(entry.last_accessed.elapsed() < Duration::from_millis(100));
-        thread::sleep(Duration::from_millis(10));
-        let before_access = entry.last_accessed.elapsed();
-        entry.record_access();
+        assert_eq!(entry.last_accessed, created);
+        let accessed = created + Duration::from_secs(3);
+        entry.record_access_at(accessed);
         assert_eq!(entry.access_count, 1);
-        assert!(entry.last_accessed.elapsed() < before_access);
+        assert_eq!(entry.last_accessed, accessed);
+        assert_eq!(entry.timestamp, created);
     }
 
     #[test]
@@ -75,25 +100,25 @@ mod tests {
 
     #[test]
     fn test_gpu_info_cache_set_and_get() {
-        let cache = GpuInfoCache::new(Duration::from_secs(1));
+        let clock = ManualClock::new();
+        let cache = GpuInfoCache::new(Duration::from_secs(1)).with_clock(clock.source());
         let gpu_info = create_test_gpu(Vendor::Nvidia);
         cache.set(gpu_info.clone());
         assert!(cache.has_entry());
         assert_eq!(cache.get_owned(), Some(gpu_info));
-        if let Some(age) = cache.age() {
-            assert!(age < Duration::from_millis(100));
-        } else {
-            panic!("cache should have age after set()");
-        }
+        assert_eq!(cache.age(), Some(Duration::ZERO));
     }
 
     #[test]
     fn test_gpu_info_cache_expiration() {
-        let cache = GpuInfoCache::new(Duration::from_millis(10));
+        let clock = ManualClock::new();
+        let cache = GpuInfoCache::new(Duration::from_secs(10)).with_clock(clock.source());
         let gpu_info = create_test_gpu(Vendor::Nvidia);
         cache.set(gpu_info.clone());
+        clock.advance(Duration::from_secs(9));
         assert_eq!(cache.get_owned(), Some(gpu_info));
-        thread::sleep(Duration::from_millis(20));
+        assert_eq!(cache.age(), Some(Duration::from_secs(9)));
+        clock.advance(Duration::from_secs(1));
         assert_eq!(cache.get_owned(), None);
     }
 
@@ -176,12 +201,14 @@ mod tests {
 
     #[test]
     fn test_multi_gpu_info_cache_expiration() {
-        let cache = MultiGpuInfoCache::new(Duration::from_millis(10));
+        let clock = ManualClock::new();
+        let cache = MultiGpuInfoCache::new(Duration::from_secs(10)).with_clock(clock.source());
         let gpu_info = create_test_gpu(Vendor::Nvidia);
         let key = 0;
         cache.set(key, gpu_info.clone());
+        clock.advance(Duration::from_secs(9));
         assert_eq!(cache.get_owned(&key), Some(gpu_info));
-        thread::sleep(Duration::from_millis(20));
+        clock.advance(Duration::from_secs(1));
         assert_eq!(cache.get_owned(&key), None);
         assert!(!cache.has_entry(&key));
     }
@@ -260,7 +287,8 @@ mod tests {
 
     #[test]
     fn test_multi_gpu_info_cache_stats() {
-        let cache = MultiGpuInfoCache::new(Duration::from_secs(1));
+        let clock = ManualClock::new();
+        let cache = MultiGpuInfoCache::new(Duration::from_secs(1)).with_clock(clock.source());
         let gpu_info1 = create_test_gpu(Vendor::Nvidia);
         let gpu_info2 = create_test_gpu(Vendor::Amd);
         let key1 = 0;
@@ -273,7 +301,7 @@ mod tests {
         if let Some(stats) = cache.get_stats() {
             assert_eq!(stats.total_entries, 2);
             assert_eq!(stats.total_accesses, 3);
-            assert!(stats.oldest_entry_age < Duration::from_millis(100));
+            assert_eq!(stats.oldest_entry_age, Duration::ZERO);
         } else {
             panic!("cache stats should be available");
         }
@@ -285,10 +313,15 @@ mod tests {
             total_entries: 0,
             total_accesses: 0,
             oldest_entry_age: Duration::from_secs(0),
+            hits: 0,
+            misses: 0,
+            evictions: 0,
+            approx_bytes: 0,
         };
         assert_eq!(stats.total_entries, 0);
         assert_eq!(stats.total_accesses, 0);
         assert_eq!(stats.oldest_entry_age, Duration::from_secs(0));
+        assert_eq!(stats.hits, 0);
     }
 
     #[test]
@@ -375,4 +408,141 @@ mod tests {
         cache.set(gpu_info.clone());
         assert_eq!(cache.get_owned(), Some(gpu_info));
     }
+
+    #[test]
+    fn test_set_capacity_evicts_least_recently_used_first() {
+        let cache = MultiGpuInfoCache::new(Duration::from_secs(60));
+        for key in 0..5 {
+            cache.set(key, create_test_gpu(Vendor::Nvidia));
+        }
+        // Recency from oldest to newest: 1, 3, 4, 0, 2
+        cache.get(&0);
+        cache.get(&2);
+        cache.set_capacity(3);
+        assert_eq!(cache.capacity(), 3);
+        assert_eq!(cache.len(), 3);
+        assert!(!cache.has_entry(&1));
+        assert!(!cache.has_entry(&3));
+        cache.set_capacity(1);
+        assert!(cache.has_entry(&2));
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.get_stats().unwrap().evictions, 4);
+        // Growing the capacity keeps the remaining entry
+        cache.set_capacity(0);
+        cache.set(7, create_test_gpu(Vendor::Amd));
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn test_stats_after_scripted_accesses() {
+        let clock = ManualClock::new();
+        let cache = MultiGpuInfoCache::with_max_entries(Duration::from_secs(60), 2)
+            .with_clock(clock.source());
+        cache.set(0, create_test_gpu(Vendor::Nvidia));
+        cache.set(1, create_test_gpu(Vendor::Amd));
+        assert!(cache.get(&0).is_some()); // hit
+        assert!(cache.get(&0).is_some()); // hit
+        assert!(cache.get(&5).is_none()); // miss
+        cache.set(2, create_test_gpu(Vendor::Apple)); // evicts 1
+        assert!(cache.get(&1).is_none()); // miss
+        assert!(cache.get_owned(&2).is_some()); // hit
+        let stats = cache.get_stats().unwrap();
+        assert_eq!((stats.hits, stats.misses, stats.evictions), (3, 2, 1));
+        assert_eq!(stats.total_entries, 2);
+        clock.advance(Duration::from_secs(60));
+        assert_eq!(
+            cache.get_stats().unwrap().oldest_entry_age,
+            Duration::from_secs(60)
+        );
+        assert!(cache.get(&2).is_none()); // expired: miss and eviction
+        let stats = cache.get_stats().unwrap();
+        assert_eq!((stats.hits, stats.misses, stats.evictions), (3, 3, 2));
+        assert_eq!(stats.total_entries, 1);
+        cache.clear_all();
+        let stats = cache.get_stats().unwrap();
+        assert_eq!((stats.total_entries, stats.evictions), (0, 2));
+        assert_eq!(stats.approx_bytes, 0);
+    }
+
+    #[test]
+    fn test_stats_approx_bytes_grows_with_strings() {
+        let cache = MultiGpuInfoCache::new(Duration::from_secs(60));
+        cache.set(0, create_test_gpu(Vendor::Nvidia));
+        let bare = cache.get_stats().unwrap().approx_bytes;
+        assert!(bare >= std::mem::size_of::<GpuInfo>());
+        let mut named = create_test_gpu(Vendor::Nvidia);
+        named.name_gpu = Some("NVIDIA GeForce RTX 4090".to_string());
+        cache.set(1, named);
+        let stats = cache.get_stats().unwrap();
+        assert!(stats.approx_bytes >= 2 * bare + "NVIDIA GeForce RTX 4090".len());
+    }
+
+    #[test]
+    fn test_capacity_is_shared_between_clones() {
+        let cache = MultiGpuInfoCache::new(Duration::from_secs(60));
+        let clone = cache.clone();
+        for key in 0..3 {
+            cache.set(key, create_test_gpu(Vendor::Nvidia));
+        }
+        clone.set_capacity(2);
+        assert_eq!(cache.capacity(), 2);
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn test_cache_entry_metric_staleness() {
+        let created = Instant::now();
+        let mut entry = CacheEntry::created_at(create_test_gpu(Vendor::Nvidia), created);
+        let utilization = MetricKind::Metric(Metric::Utilization);
+        let ttl = Duration::from_secs(20);
+        assert!(entry.is_metric_stale_at(utilization, Duration::ZERO, created));
+        assert!(!entry.is_metric_stale_at(MetricKind::Name, ttl, created));
+        assert_eq!(entry.metric_refreshed_at(MetricKind::Name), entry.timestamp);
+
+        let refreshed = created + ttl;
+        entry.mark_metric_refreshed_at(utilization, refreshed);
+        assert_eq!(entry.metric_refreshed_at(utilization), refreshed);
+        assert!(!entry.is_metric_stale_at(utilization, ttl, refreshed));
+        assert!(entry.is_metric_stale_at(MetricKind::Name, ttl, refreshed));
+    }
+
+    #[test]
+    fn test_stale_metrics_follow_the_clock() {
+        let clock = ManualClock::new();
+        let cache = MultiGpuInfoCache::new(Duration::from_secs(60)).with_clock(clock.source());
+        cache.set(0, create_test_gpu(Vendor::Nvidia));
+        let utilization = MetricKind::Metric(Metric::Utilization);
+        let ttls = [(utilization, Duration::from_secs(2))];
+        clock.advance(Duration::from_secs(1));
+        assert!(cache.stale_metrics(&0, &ttls).is_empty());
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(cache.stale_metrics(&0, &ttls), vec![utilization]);
+        cache.refresh_metrics(&0, &create_test_gpu(Vendor::Nvidia), &[utilization]);
+        assert!(cache.stale_metrics(&0, &ttls).is_empty());
+    }
+
+    #[test]
+    fn test_refresh_stale_metrics_only() {
+        let cache = MultiGpuInfoCache::new(Duration::from_secs(60));
+        let mut cached = create_test_gpu(Vendor::Nvidia);
+        cached.name_gpu = Some("cached".to_string());
+        cached.utilization = Some(10.0);
+        cache.set(0, cached);
+        let ttls = [
+            (MetricKind::Metric(Metric::Utilization), Duration::ZERO),
+            (MetricKind::Name, Duration::from_secs(60)),
+        ];
+        let stale = cache.stale_metrics(&0, &ttls);
+        assert_eq!(stale, vec![MetricKind::Metric(Metric::Utilization)]);
+        assert!(cache.stale_metrics(&1, &ttls).is_empty());
+
+        let mut fresh = create_test_gpu(Vendor::Nvidia);
+        fresh.name_gpu = Some("fresh".to_string());
+        fresh.utilization = Some(90.0);
+        let updated = cache.refresh_metrics(&0, &fresh, &stale).unwrap();
+        assert_eq!(updated.utilization, Some(90.0));
+        assert_eq!(updated.name_gpu.as_deref(), Some("cached"));
+        assert_eq!(cache.get(&0).unwrap(), updated);
+        assert!(cache.refresh_metrics(&1, &fresh, &stale).is_none());
+    }
 }
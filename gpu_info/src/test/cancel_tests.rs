@@ -0,0 +1,204 @@
+//! Tests for cooperative cancellation of GPU detection
+
+#[cfg(test)]
+mod tests {
+    use crate::cancel::{output_with_cancel, CancelToken};
+    use crate::detection_report::DetectionReport;
+    use crate::gpu_info::{GpuInfo, GpuProvider, Result};
+    use crate::gpu_manager::GpuManager;
+    use crate::vendor::Vendor;
+    use std::io;
+    use std::process::Command;
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    /// How long the slow provider would take if never cancelled
+    const SLOW_DETECTION: Duration = Duration::from_secs(10);
+    /// Upper bound for returning after cancellation
+    const PROMPT: Duration = Duration::from_secs(2);
+
+    /// Provider returning one GPU immediately
+    struct FastProvider;
+
+    impl GpuProvider for FastProvider {
+        fn detect_gpus(&self) -> Result<Vec<GpuInfo>> {
+            Ok(vec![GpuInfo::mock_nvidia()])
+        }
+
+        fn update_gpu(&self, _gpu: &mut GpuInfo) -> Result<()> {
+            Ok(())
+        }
+
+        fn get_vendor(&self) -> Vendor {
+            Vendor::Nvidia
+        }
+    }
+
+    /// Provider probing two devices, each taking [`SLOW_DETECTION`] unless cancelled
+    struct SlowProvider;
+
+    impl GpuProvider for SlowProvider {
+        fn detect_gpus(&self) -> Result<Vec<GpuInfo>> {
+            self.detect_gpus_with_cancel(&CancelToken::new(), &mut DetectionReport::new())
+        }
+
+        fn detect_gpus_with_cancel(
+            &self,
+            token: &CancelToken,
+            _report: &mut DetectionReport,
+        ) -> Result<Vec<GpuInfo>> {
+            let mut gpus = Vec::new();
+            for _ in 0..2 {
+                if token.wait_timeout(SLOW_DETECTION) {
+                    return Ok(gpus);
+                }
+                gpus.push(GpuInfo::mock_amd());
+            }
+            Ok(gpus)
+        }
+
+        fn update_gpu(&self, _gpu: &mut GpuInfo) -> Result<()> {
+            Ok(())
+        }
+
+        fn get_vendor(&self) -> Vendor {
+            Vendor::Amd
+        }
+    }
+
+    /// Detects from the fast provider, then the slow one, with `token`
+    fn detect(token: &CancelToken) -> GpuManager {
+        let mut manager: GpuManager = std::iter::empty().collect();
+        manager.detect_from_providers(&[("fast", &FastProvider), ("slow", &SlowProvider)], token);
+        manager.finish_detection(token);
+        manager
+    }
+
+    fn cancel_after(token: &CancelToken, delay: Duration) -> thread::JoinHandle<()> {
+        let token = token.clone();
+        thread::spawn(move || {
+            thread::sleep(delay);
+            token.cancel();
+        })
+    }
+
+    #[test]
+    fn test_token_is_shared_between_clones() {
+        let token = CancelToken::new();
+        let clone = token.clone();
+        assert!(!clone.is_cancelled());
+        assert!(!token.wait_timeout(Duration::from_millis(1)));
+        token.cancel();
+        assert!(clone.is_cancelled());
+        assert!(clone.wait_timeout(SLOW_DETECTION));
+    }
+
+    #[test]
+    fn test_cancel_slow_provider_keeps_fast_results() {
+        let token = CancelToken::new();
+        let canceller = cancel_after(&token, Duration::from_millis(100));
+        let start = Instant::now();
+        let manager = detect(&token);
+        let elapsed = start.elapsed();
+        canceller.join().unwrap();
+
+        assert!(elapsed < PROMPT, "detection took {:?}", elapsed);
+        assert!(manager.detection_report().is_cancelled());
+        assert_eq!(manager.get_all_gpus(), &vec![GpuInfo::mock_nvidia()]);
+    }
+
+    #[test]
+    fn test_detection_without_cancel_is_not_marked() {
+        let mut manager: GpuManager = std::iter::empty().collect();
+        let token = CancelToken::new();
+        manager.detect_from_providers(&[("fast", &FastProvider)], &token);
+        manager.finish_detection(&token);
+        assert!(!manager.detection_report().is_cancelled());
+        assert_eq!(manager.gpu_count(), 1);
+    }
+
+    #[test]
+    fn test_cancelled_before_start_skips_providers() {
+        let token = CancelToken::new();
+        token.cancel();
+        let manager = detect(&token);
+        assert!(manager.detection_report().is_cancelled());
+        // The manager still holds one placeholder GPU
+        assert_eq!(manager.get_all_gpus(), &vec![GpuInfo::unknown()]);
+    }
+
+    #[test]
+    fn test_detect_with_cancel_marks_report() {
+        let token = CancelToken::new();
+        token.cancel();
+        let manager = GpuManager::detect_with_cancel(token);
+        assert!(manager.detection_report().is_cancelled());
+        assert!(manager.gpu_count() >= 1);
+    }
+
+    #[tokio::test]
+    async fn test_detect_async_with_cancel_returns_promptly() {
+        let token = CancelToken::new();
+        token.cancel();
+        let start = Instant::now();
+        let manager = crate::detect_async_with_cancel(token).await.unwrap();
+        assert!(start.elapsed() < PROMPT);
+        assert!(manager.detection_report().is_cancelled());
+    }
+
+    #[test]
+    fn test_report_cancelled_marker() {
+        let mut report = DetectionReport::new();
+        assert!(!report.is_cancelled());
+        report.mark_cancelled();
+        assert!(report.is_cancelled());
+        assert!(!report.is_empty());
+
+        let mut merged = DetectionReport::new();
+        merged.merge(report.clone());
+        assert!(merged.is_cancelled());
+        merged.clear();
+        assert!(!merged.is_cancelled());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_command_output_with_cancel() {
+        let token = CancelToken::new();
+        let output = output_with_cancel(
+            Command::new("sh").args(["-c", "echo out; echo err >&2"]),
+            Some(PROMPT),
+            &token,
+        )
+        .unwrap();
+        assert!(output.status.success());
+        assert_eq!(output.stdout, b"out\n");
+        assert_eq!(output.stderr, b"err\n");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_command_killed_when_cancelled() {
+        let token = CancelToken::new();
+        let canceller = cancel_after(&token, Duration::from_millis(100));
+        let start = Instant::now();
+        let error = output_with_cancel(Command::new("sleep").arg("10"), None, &token).unwrap_err();
+        canceller.join().unwrap();
+        assert_eq!(error.kind(), io::ErrorKind::Interrupted);
+        assert!(start.elapsed() < PROMPT);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_command_killed_on_timeout() {
+        let start = Instant::now();
+        let error = output_with_cancel(
+            Command::new("sleep").arg("10"),
+            Some(Duration::from_millis(100)),
+            &CancelToken::new(),
+        )
+        .unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::TimedOut);
+        assert!(start.elapsed() < PROMPT);
+    }
+}
@@ -0,0 +1,284 @@
+//! Tests for cross-field consistency checking and strict mode
+
+#[cfg(test)]
+mod tests {
+    use crate::consistency::{check, enforce, repair, ConsistencyViolation};
+    use crate::gpu_index::GpuIndex;
+    use crate::gpu_info::{GpuInfo, GpuProvider, Result};
+    use crate::gpu_manager::GpuManager;
+    use crate::provider_manager::GpuProviderManager;
+    use crate::vendor::{IntelGpuType, Vendor};
+
+    /// Provider reporting an Intel GPU at 350% utilization
+    struct OverloadedProvider;
+
+    impl GpuProvider for OverloadedProvider {
+        fn detect_gpus(&self) -> Result<Vec<GpuInfo>> {
+            let mut gpu = GpuInfo::mock_intel();
+            gpu.utilization = Some(350.0);
+            Ok(vec![gpu])
+        }
+
+        fn update_gpu(&self, gpu: &mut GpuInfo) -> Result<()> {
+            gpu.utilization = Some(350.0);
+            Ok(())
+        }
+
+        fn get_vendor(&self) -> Vendor {
+            Vendor::Intel(IntelGpuType::Integrated)
+        }
+    }
+
+    #[test]
+    fn test_mocks_are_consistent() {
+        assert!(GpuInfo::mock_nvidia().check_consistency().is_empty());
+        assert!(GpuInfo::mock_amd().check_consistency().is_empty());
+        assert!(GpuInfo::mock_intel().check_consistency().is_empty());
+        assert!(GpuInfo::unknown().check_consistency().is_empty());
+    }
+
+    #[test]
+    fn test_memory_used_exceeds_total() {
+        let gpu = GpuInfo::builder()
+            .memory_total(8192)
+            .memory_used(9000)
+            .build();
+        assert_eq!(
+            check(&gpu),
+            vec![ConsistencyViolation::MemoryUsedExceedsTotal {
+                used: 9000,
+                total: 8192
+            }]
+        );
+    }
+
+    #[test]
+    fn test_memory_used_equal_to_total_is_consistent() {
+        let gpu = GpuInfo::builder()
+            .memory_total(8192)
+            .memory_used(8192)
+            .build();
+        assert!(check(&gpu).is_empty());
+    }
+
+    #[test]
+    fn test_memory_used_without_total_is_not_checked() {
+        let gpu = GpuInfo::builder().memory_used(9000).build();
+        assert!(check(&gpu).is_empty());
+    }
+
+    #[test]
+    fn test_utilization_out_of_range() {
+        let gpu = GpuInfo::builder().utilization(350.0).build();
+        assert_eq!(
+            check(&gpu),
+            vec![ConsistencyViolation::UtilizationOutOfRange(350.0)]
+        );
+        let gpu = GpuInfo::builder().utilization(-1.0).build();
+        assert_eq!(
+            check(&gpu),
+            vec![ConsistencyViolation::UtilizationOutOfRange(-1.0)]
+        );
+    }
+
+    #[test]
+    fn test_utilization_bounds_are_consistent() {
+        for util in [0.0, 100.0] {
+            let gpu = GpuInfo::builder()
+                .utilization(util)
+                .memory_util(util)
+                .build();
+            assert!(check(&gpu).is_empty(), "{} should be consistent", util);
+        }
+    }
+
+    #[test]
+    fn test_utilization_nan_is_violation() {
+        let gpu = GpuInfo::builder().utilization(f32::NAN).build();
+        let violations = check(&gpu);
+        assert_eq!(violations.len(), 1);
+        assert!(matches!(
+            violations[0],
+            ConsistencyViolation::UtilizationOutOfRange(v) if v.is_nan()
+        ));
+    }
+
+    #[test]
+    fn test_memory_util_out_of_range() {
+        let gpu = GpuInfo::builder().memory_util(101.0).build();
+        assert_eq!(
+            check(&gpu),
+            vec![ConsistencyViolation::MemoryUtilOutOfRange(101.0)]
+        );
+    }
+
+    #[test]
+    fn test_core_clock_exceeds_max() {
+        let gpu = GpuInfo::builder()
+            .core_clock(2100)
+            .max_clock_speed(1995)
+            .build();
+        assert_eq!(
+            check(&gpu),
+            vec![ConsistencyViolation::CoreClockExceedsMax {
+                clock: 2100,
+                max: 1995
+            }]
+        );
+    }
+
+    #[test]
+    fn test_core_clock_equal_to_max_is_consistent() {
+        let gpu = GpuInfo::builder()
+            .core_clock(1995)
+            .max_clock_speed(1995)
+            .build();
+        assert!(check(&gpu).is_empty());
+    }
+
+    #[test]
+    fn test_inactive_with_utilization() {
+        let gpu = GpuInfo::builder().active(false).utilization(12.0).build();
+        assert_eq!(
+            check(&gpu),
+            vec![ConsistencyViolation::InactiveWithUtilization(12.0)]
+        );
+    }
+
+    #[test]
+    fn test_inactive_with_zero_utilization_is_consistent() {
+        let gpu = GpuInfo::builder().active(false).utilization(0.0).build();
+        assert!(check(&gpu).is_empty());
+    }
+
+    #[test]
+    fn test_multiple_violations_in_stable_order() {
+        let gpu = GpuInfo::builder()
+            .active(false)
+            .utilization(350.0)
+            .memory_util(120.0)
+            .memory_total(1024)
+            .memory_used(2048)
+            .core_clock(3000)
+            .max_clock_speed(2000)
+            .build();
+        assert_eq!(
+            check(&gpu),
+            vec![
+                ConsistencyViolation::MemoryUsedExceedsTotal {
+                    used: 2048,
+                    total: 1024
+                },
+                ConsistencyViolation::UtilizationOutOfRange(350.0),
+                ConsistencyViolation::MemoryUtilOutOfRange(120.0),
+                ConsistencyViolation::CoreClockExceedsMax {
+                    clock: 3000,
+                    max: 2000
+                },
+                ConsistencyViolation::InactiveWithUtilization(350.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_repair_clamps_and_drops() {
+        let mut gpu = GpuInfo::builder()
+            .utilization(350.0)
+            .memory_util(-5.0)
+            .memory_total(1024)
+            .memory_used(2048)
+            .core_clock(3000)
+            .max_clock_speed(2000)
+            .build();
+        let violations = check(&gpu);
+        repair(&mut gpu, &violations);
+        assert_eq!(gpu.utilization, Some(100.0));
+        assert_eq!(gpu.memory_util, Some(0.0));
+        assert_eq!(gpu.memory_used, Some(1024));
+        assert_eq!(gpu.core_clock, None);
+        assert_eq!(gpu.max_clock_speed, Some(2000));
+        assert!(check(&gpu).is_empty());
+    }
+
+    #[test]
+    fn test_repair_drops_nan_and_inactive_utilization() {
+        let mut gpu = GpuInfo::builder().memory_util(f32::NAN).build();
+        let violations = check(&gpu);
+        repair(&mut gpu, &violations);
+        assert_eq!(gpu.memory_util, None);
+
+        let mut gpu = GpuInfo::builder().active(false).utilization(40.0).build();
+        let violations = check(&gpu);
+        repair(&mut gpu, &violations);
+        assert_eq!(gpu.utilization, None);
+        assert!(check(&gpu).is_empty());
+    }
+
+    #[test]
+    fn test_enforce_consistent_data_is_untouched() {
+        let mut gpu = GpuInfo::mock_nvidia();
+        let expected = gpu.clone();
+        assert!(enforce(&mut gpu, "NVIDIA").is_empty());
+        assert_eq!(gpu, expected);
+    }
+
+    #[test]
+    #[cfg_attr(
+        debug_assertions,
+        should_panic(expected = "Provider NvidiaLinuxProvider returned inconsistent GPU data")
+    )]
+    fn test_enforce_repairs_in_release_builds() {
+        let mut gpu = GpuInfo::builder().utilization(350.0).build();
+        assert_eq!(
+            enforce(&mut gpu, "NvidiaLinuxProvider"),
+            vec![ConsistencyViolation::UtilizationOutOfRange(350.0)]
+        );
+        assert_eq!(gpu.utilization, Some(100.0));
+    }
+
+    #[test]
+    fn test_violation_display_names_fields() {
+        let violation = ConsistencyViolation::MemoryUsedExceedsTotal {
+            used: 9000,
+            total: 8192,
+        };
+        assert_eq!(
+            violation.to_string(),
+            "memory_used (9000 MB) exceeds memory_total (8192 MB)"
+        );
+    }
+
+    #[test]
+    fn test_manager_strict_toggle() {
+        let mut manager = GpuManager::from_iter(vec![GpuInfo::mock_nvidia()]);
+        assert_eq!(manager.is_strict(), cfg!(feature = "strict-validation"));
+        manager.set_strict(true);
+        assert!(manager.is_strict());
+        manager.set_strict(false);
+        assert!(!manager.is_strict());
+    }
+
+    #[test]
+    fn test_provider_name_defaults_to_type_name() {
+        assert_eq!(OverloadedProvider.name(), "OverloadedProvider");
+    }
+
+    #[test]
+    #[cfg_attr(
+        debug_assertions,
+        should_panic(expected = "Provider OverloadedProvider returned inconsistent GPU data")
+    )]
+    fn test_strict_manager_repairs_provider_output() {
+        let mut providers = GpuProviderManager::new();
+        providers.register_provider(Vendor::Intel(IntelGpuType::Integrated), OverloadedProvider);
+        let mut manager = GpuManager::with_providers(providers);
+        manager.set_strict(true);
+        manager.refresh_all_gpus().unwrap();
+        assert_eq!(
+            manager.get_gpu_at(GpuIndex(0)).unwrap().utilization,
+            Some(100.0)
+        );
+        let cached = manager.get_cached_gpu_at(GpuIndex(0)).unwrap();
+        assert_eq!(cached.utilization, Some(100.0));
+    }
+}
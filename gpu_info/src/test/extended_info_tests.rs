@@ -7,10 +7,10 @@
 mod tests {
     use crate::extended_info::{
         ConnectionInfo, EncoderInfo, ExtendedGpuInfo, FanInfo, GpuInfoExtensions,
-        IndividualFanInfo, MemoryInfo, OverclockingInfo, PerformanceState, ThermalInfo,
-        ThrottleReason,
+        IndividualFanInfo, MemoryInfo, MetalInfo, OverclockingInfo, PcieAerCounters,
+        PerformanceState, SmartShiftInfo, ThermalInfo, ThrottleReason,
     };
-    use crate::gpu_info::GpuInfo;
+    use crate::gpu_info::{EccStats, GpuInfo};
     use crate::vendor::Vendor;
 
     /// Test basic ExtendedGpuInfo creation
@@ -40,7 +40,7 @@ mod tests {
         extended_gpu.thermal_info.gpu_temperature = Some(65.0);
         extended_gpu.thermal_info.is_throttling = Some(false);
         extended_gpu.base_info.memory_util = Some(70.0);
-        extended_gpu.memory_info.ecc_errors = Some(0);
+        extended_gpu.base_info.ecc_errors = Some(EccStats::default());
         let health_score = extended_gpu.health_score();
         println!("Health score with good conditions: {:.1}%", health_score);
         assert!(
@@ -58,7 +58,11 @@ mod tests {
         extended_gpu.thermal_info.gpu_temperature = Some(90.0);
         extended_gpu.thermal_info.is_throttling = Some(true);
         extended_gpu.base_info.memory_util = Some(98.0);
-        extended_gpu.memory_info.ecc_errors = Some(5);
+        extended_gpu.base_info.ecc_errors = Some(EccStats {
+            single_bit_corrected: 12,
+            double_bit_uncorrected: 5,
+            aggregate: true,
+        });
         let health_score = extended_gpu.health_score();
         println!("Health score with poor conditions: {:.1}%", health_score);
         assert!(
@@ -76,7 +80,7 @@ mod tests {
         extended_gpu.thermal_info.gpu_temperature = Some(78.0);
         extended_gpu.thermal_info.is_throttling = Some(false);
         extended_gpu.base_info.memory_util = Some(85.0);
-        extended_gpu.memory_info.ecc_errors = Some(0);
+        extended_gpu.base_info.ecc_errors = Some(EccStats::default());
         let health_score = extended_gpu.health_score();
         println!("Health score with mixed conditions: {:.1}%", health_score);
         assert!(
@@ -193,7 +197,6 @@ mod tests {
         memory_info.memory_type = Some("GDDR6".to_string());
         memory_info.memory_bus_width = Some(256);
         memory_info.ecc_enabled = Some(false);
-        memory_info.ecc_errors = Some(0);
         assert_eq!(memory_info.total_memory_mb, Some(8192));
         assert_eq!(memory_info.memory_type, Some("GDDR6".to_string()));
         assert_eq!(memory_info.ecc_enabled, Some(false));
@@ -218,6 +221,55 @@ mod tests {
         assert_eq!(connection_info.bus_id, Some("0000:01:00.0".to_string()));
     }
 
+    /// Test that AER totals take precedence over the replay counter
+    #[test]
+    fn test_pcie_error_count() {
+        let mut connection_info = ConnectionInfo::default();
+        assert_eq!(connection_info.pcie_error_count(), None);
+        connection_info.pcie_replay_count = Some(7);
+        assert_eq!(connection_info.pcie_error_count(), Some(7));
+        connection_info.pcie_aer_correctable = Some(PcieAerCounters {
+            total: 19,
+            by_type: vec![("BadDLLP".to_string(), 19)],
+        });
+        assert_eq!(connection_info.pcie_error_count(), Some(19));
+    }
+
+    /// Test the negotiated PCIe link against its maximum
+    #[test]
+    fn test_pcie_link_degraded() {
+        let mut connection_info = ConnectionInfo::default();
+        assert_eq!(connection_info.pcie_link_degraded(), None);
+        assert_eq!(connection_info.format_pcie_link(), "N/A");
+        connection_info.pcie_width = Some(16);
+        connection_info.pcie_width_max = Some(16);
+        assert_eq!(connection_info.pcie_link_degraded(), Some(false));
+        assert_eq!(connection_info.format_pcie_link(), "PCIe x16");
+        // An idle GPU drops to a lower generation at full width
+        connection_info.pcie_generation = Some(1);
+        connection_info.pcie_generation_max = Some(4);
+        assert_eq!(connection_info.pcie_link_degraded(), Some(false));
+        assert_eq!(connection_info.format_pcie_link(), "PCIe 1.0 x16");
+        assert_eq!(connection_info.format_pcie_link_max(), "PCIe 4.0 x16");
+        connection_info.pcie_width = Some(8);
+        assert_eq!(connection_info.pcie_link_degraded(), Some(true));
+        // A link the GPU has not reported a maximum for is not degraded
+        connection_info.pcie_width_max = None;
+        connection_info.pcie_generation_max = None;
+        assert_eq!(connection_info.pcie_link_degraded(), None);
+        assert_eq!(connection_info.format_pcie_link_max(), "N/A");
+    }
+
+    /// Test that a GPU without a readable link is not reported as degraded
+    #[test]
+    fn test_gpu_pcie_link_degraded_unknown_link() {
+        let mut gpu = GpuInfo::mock_nvidia();
+        gpu.bus_id = None;
+        assert_eq!(GpuInfoExtensions::pcie_link_degraded(&gpu), None);
+        gpu.bus_id = Some("ffff:ff:1f.7".to_string());
+        assert_eq!(GpuInfoExtensions::pcie_link_degraded(&gpu), None);
+    }
+
     /// Test encoder info functionality
     #[test]
     #[allow(clippy::field_reassign_with_default)]
@@ -263,6 +315,33 @@ mod tests {
         println!("Display output:\n{}", display_string);
     }
 
+    /// SmartShift is only displayed when reported
+    #[test]
+    fn test_display_smartshift() {
+        let mut extended_gpu = create_test_extended_gpu();
+        assert!(!format!("{}", extended_gpu).contains("SmartShift"));
+        extended_gpu.smartshift = Some(SmartShiftInfo {
+            apu_power_percent: Some(130.0),
+            dgpu_power_percent: Some(0.0),
+            bias: None,
+        });
+        assert!(format!("{}", extended_gpu).contains("SmartShift: APU 130%, dGPU 0%, bias N/A"));
+    }
+
+    /// The PCIe link is displayed with its maximum only when degraded
+    #[test]
+    fn test_display_pcie_link() {
+        let mut extended_gpu = create_test_extended_gpu();
+        extended_gpu.connection_info.pcie_generation = Some(4);
+        extended_gpu.connection_info.pcie_width = Some(16);
+        extended_gpu.connection_info.pcie_generation_max = Some(4);
+        extended_gpu.connection_info.pcie_width_max = Some(16);
+        let output = format!("{}", extended_gpu);
+        assert!(output.contains("PCIe Link: PCIe 4.0 x16\n"));
+        extended_gpu.connection_info.pcie_width = Some(8);
+        assert!(format!("{}", extended_gpu).contains("PCIe Link: PCIe 4.0 x8 (max PCIe 4.0 x16)\n"));
+    }
+
     /// Test GpuInfoExtensions trait
     #[test]
     fn test_gpu_info_extensions() {
@@ -337,6 +416,38 @@ mod tests {
         );
     }
 
+    /// Test compute capability formatting
+    #[test]
+    fn test_format_compute_capability() {
+        let mut extended_gpu = create_test_extended_gpu();
+        assert_eq!(extended_gpu.format_compute_capability(), "N/A");
+        extended_gpu.compute_capability = Some((8, 6));
+        assert_eq!(extended_gpu.format_compute_capability(), "8.6");
+    }
+
+    /// Test Metal capability formatting
+    #[test]
+    fn test_metal_info_formatting() {
+        let mut extended_gpu = create_test_extended_gpu();
+        assert!(extended_gpu.metal_info.is_empty());
+        assert!(!format!("{}", extended_gpu).contains("Metal Family"));
+
+        extended_gpu.metal_info = MetalInfo {
+            metal_gpu_family: Some("Apple8".to_string()),
+            supports_raytracing: Some(false),
+            max_buffer_length: Some(4 * 1024 * 1024 * 1024),
+            recommended_max_working_set: None,
+            has_unified_memory: Some(true),
+        };
+        let info = &extended_gpu.metal_info;
+        assert_eq!(info.format_gpu_family(), "Apple8");
+        assert_eq!(info.format_raytracing(), "No");
+        assert_eq!(info.format_max_buffer_length(), "4.00 GB");
+        assert_eq!(info.format_recommended_max_working_set(), "N/A");
+        assert_eq!(info.format_unified_memory(), "Yes");
+        assert!(format!("{}", extended_gpu).contains("Metal Family: Apple8"));
+    }
+
     /// Integration test: Full extended GPU workflow
     #[test]
     fn test_full_extended_gpu_workflow() {
@@ -397,7 +508,7 @@ mod tests {
         extended_gpu.memory_info.used_memory_mb = Some(12288);
         extended_gpu.memory_info.memory_type = Some("GDDR6X".to_string());
         extended_gpu.memory_info.ecc_enabled = Some(false);
-        extended_gpu.memory_info.ecc_errors = Some(0);
+        extended_gpu.base_info.ecc_errors = Some(EccStats::default());
         extended_gpu.connection_info.pcie_generation = Some(4);
         extended_gpu.connection_info.pcie_width = Some(16);
         extended_gpu.connection_info.bus_id = Some("0000:01:00.0".to_string());
@@ -19,6 +19,7 @@ fn test_format_methods_with_unknown_gpu() {
     assert_eq!(gpu.format_memory_total(), "N/A");
     assert_eq!(gpu.format_memory_used(), "N/A");
     assert_eq!(gpu.format_max_clock_speed(), "N/A");
+    assert_eq!(gpu.format_fan_speed(), "N/A");
     assert_eq!(gpu.format_name_gpu(), "Unknown GPU");
     assert_eq!(gpu.format_driver_version(), "Unknown Driver Version");
     assert_eq!(gpu.format_active(), "Inactive");
@@ -40,6 +41,7 @@ fn test_format_methods_with_populated_gpu() {
         .memory_total(24576)
         .memory_used(12288)
         .max_clock_speed(2610)
+        .fan_speed(38)
         .driver_version("545.92")
         .active(true)
         .build();
@@ -54,6 +56,7 @@ fn test_format_methods_with_populated_gpu() {
     assert_eq!(gpu.format_memory_total(), "24.00 GB");
     assert_eq!(gpu.format_memory_used(), "12.00 GB");
     assert_eq!(gpu.format_max_clock_speed(), "2610 MHz");
+    assert_eq!(gpu.format_fan_speed(), "38%");
     assert_eq!(gpu.format_name_gpu(), "NVIDIA GeForce RTX 4090");
     assert_eq!(gpu.format_driver_version(), "545.92");
     assert_eq!(gpu.format_active(), "Active");
@@ -135,6 +138,30 @@ fn test_format_methods_edge_cases() {
     assert_eq!(gpu_max.format_power_limit(), "1000.00W");
 }
 
+/// Amounts too large for an exact `f32` are formatted exactly
+#[test]
+fn test_format_memory_large_amounts() {
+    let gpu = GpuInfo::builder()
+        .memory_total(4_294_966_911)
+        .memory_used(u32::MAX)
+        .build();
+    assert_eq!(gpu.format_memory_total(), "4194303.62 GB");
+    assert_eq!(gpu.format_memory_used(), "4194304.00 GB");
+}
+
+/// Non-finite readings are formatted rather than panicking
+#[test]
+fn test_format_methods_non_finite() {
+    let gpu = GpuInfo::builder()
+        .temperature(f32::NAN)
+        .utilization(f32::INFINITY)
+        .power_usage(f32::NEG_INFINITY)
+        .build();
+    assert_eq!(gpu.format_temperature(), "NaN°C");
+    assert_eq!(gpu.format_utilization(), "inf%");
+    assert_eq!(gpu.format_power_usage(), "-infW");
+}
+
 /// Test format methods with empty string values
 #[test]
 fn test_format_methods_empty_strings() {
@@ -0,0 +1,373 @@
+//! Tests for `GpuIdentity` and the state keyed by it
+
+#[cfg(test)]
+mod tests {
+    use crate::gpu_identity::GpuIdentity;
+    use crate::gpu_index::GpuIndex;
+    use crate::gpu_info::{matching_gpu, GpuError, GpuInfo};
+    use crate::gpu_manager::GpuManager;
+    use crate::monitoring::{GpuHistory, GpuThresholds, ThrottleTracker};
+    use crate::vendor::Vendor;
+    use std::collections::hash_map::DefaultHasher;
+    use std::collections::HashMap;
+    use std::hash::{Hash, Hasher};
+    use std::time::Instant;
+
+    /// RTX 3080 in the PCI slot `bus_id`, at `temperature` degrees
+    fn rtx_3080(bus_id: &str, temperature: f32) -> GpuInfo {
+        GpuInfo::builder()
+            .vendor(Vendor::Nvidia)
+            .name("NVIDIA GeForce RTX 3080")
+            .bus_id(bus_id)
+            .temperature(temperature)
+            .build()
+    }
+
+    fn hash_of(value: &impl Hash) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn test_identity_stable_across_metric_changes() {
+        let first = rtx_3080("0000:01:00.0", 45.0);
+        let mut later = first.clone();
+        later.temperature = Some(83.0);
+        later.utilization = Some(99.0);
+        later.core_clock = Some(1905);
+        assert_ne!(first, later);
+        assert_eq!(first.identity(), later.identity());
+        assert_eq!(hash_of(&first), hash_of(&later));
+
+        let mut peaks: HashMap<GpuIdentity, f32> = HashMap::new();
+        for sample in [&first, &later] {
+            let peak = peaks.entry(sample.identity()).or_insert(0.0);
+            *peak = peak.max(sample.temperature.unwrap_or(0.0));
+        }
+        assert_eq!(peaks.len(), 1);
+        assert_eq!(peaks.get(&first.identity()), Some(&83.0));
+    }
+
+    #[test]
+    fn test_identical_models_distinguished_by_bus_id() {
+        let slot_a = rtx_3080("0000:01:00.0", 60.0);
+        let slot_b = rtx_3080("0000:02:00.0", 60.0);
+        assert_ne!(slot_a.identity(), slot_b.identity());
+        assert!(slot_a.identity() < slot_b.identity());
+
+        let mut labels = HashMap::new();
+        labels.insert(slot_a.identity(), "display");
+        labels.insert(slot_b.identity(), "compute");
+        assert_eq!(
+            labels.get(&rtx_3080("0000:02:00.0", 75.0).identity()),
+            Some(&"compute")
+        );
+
+        // Without a bus ID or UUID the two cards are indistinguishable
+        let bare = GpuIdentity::new(Vendor::Nvidia, Some("NVIDIA GeForce RTX 3080"));
+        assert_eq!(
+            bare,
+            GpuIdentity::new(Vendor::Nvidia, Some("nvidia geforce rtx 3080"))
+        );
+    }
+
+    #[test]
+    fn test_identity_normalization() {
+        // NVML reports an 8-digit domain, sysfs a 4-digit one
+        let nvml =
+            GpuIdentity::new(Vendor::Nvidia, Some("Tesla T4")).with_bus_id("00000000:3B:00.0");
+        let sysfs = GpuIdentity::new(Vendor::Nvidia, Some("Tesla  T4")).with_bus_id("0000:3b:00.0");
+        assert_eq!(nvml, sysfs);
+        assert_eq!(nvml.bus_id(), Some("0000:3b:00.0"));
+
+        let uuid = GpuIdentity::new(Vendor::Nvidia, None).with_uuid(" GPU-5A8F3B2E ");
+        assert_eq!(uuid.uuid(), Some("gpu-5a8f3b2e"));
+        assert_eq!(GpuIdentity::new(Vendor::Amd, Some("  ")).name(), None);
+        assert_eq!(nvml.to_string(), "NVIDIA tesla t4 @ 0000:3b:00.0");
+    }
+
+    #[test]
+    fn test_manager_cache_keeps_identical_models_apart() {
+        let manager = GpuManager::from_iter(vec![
+            rtx_3080("0000:01:00.0", 50.0),
+            rtx_3080("0000:02:00.0", 70.0),
+            GpuInfo::write_vendor(Vendor::Nvidia),
+            GpuInfo::write_vendor(Vendor::Nvidia),
+        ]);
+        assert_eq!(manager.cache_stats().total_entries, 4);
+        let temperatures: Vec<Option<f32>> = (0..4)
+            .map(|index| {
+                manager
+                    .get_cached_gpu_at(GpuIndex(index))
+                    .unwrap()
+                    .temperature
+            })
+            .collect();
+        assert_eq!(temperatures, vec![Some(50.0), Some(70.0), None, None]);
+    }
+
+    #[test]
+    fn test_history_follows_gpus_when_reordered() {
+        let mut history = GpuHistory::new(2, 10);
+        let start = Instant::now();
+        history.record(
+            &[
+                rtx_3080("0000:01:00.0", 50.0),
+                rtx_3080("0000:02:00.0", 70.0),
+            ],
+            start,
+        );
+        history.record(
+            &[
+                rtx_3080("0000:02:00.0", 71.0),
+                rtx_3080("0000:01:00.0", 51.0),
+            ],
+            start,
+        );
+        let temperatures: Vec<Vec<f32>> = history
+            .gpu_histories
+            .iter()
+            .map(|gpu| gpu.temperatures.iter().copied().collect())
+            .collect();
+        assert_eq!(temperatures, vec![vec![70.0, 71.0], vec![50.0, 51.0]]);
+
+        // A GPU that disappears loses its history, a new one starts empty
+        history.record(
+            &[
+                rtx_3080("0000:03:00.0", 40.0),
+                rtx_3080("0000:01:00.0", 52.0),
+            ],
+            start,
+        );
+        assert_eq!(history.gpu_histories[0].temperatures.len(), 1);
+        assert_eq!(history.gpu_histories[1].temperatures.len(), 3);
+    }
+
+    #[test]
+    fn test_throttle_streaks_follow_gpus_when_reordered() {
+        let thresholds = GpuThresholds {
+            clock_deficit_warning: 10.0,
+            clock_deficit_samples: 3,
+            ..Default::default()
+        };
+        let throttled = |bus_id: &str, effective: u32| {
+            GpuInfo::builder()
+                .vendor(Vendor::Nvidia)
+                .bus_id(bus_id)
+                .core_clock(2500)
+                .effective_core_clock(effective)
+                .build()
+        };
+        let mut tracker = ThrottleTracker::new();
+        tracker.observe(
+            &[
+                throttled("0000:01:00.0", 2000),
+                throttled("0000:02:00.0", 2500),
+            ],
+            &thresholds,
+        );
+        tracker.observe(
+            &[
+                throttled("0000:02:00.0", 2500),
+                throttled("0000:01:00.0", 2000),
+            ],
+            &thresholds,
+        );
+        assert_eq!(tracker.streak(0), 0);
+        assert_eq!(tracker.streak(1), 2);
+        let alerts = tracker.observe(
+            &[
+                throttled("0000:02:00.0", 2500),
+                throttled("0000:01:00.0", 2000),
+            ],
+            &thresholds,
+        );
+        assert_eq!(alerts.len(), 1);
+    }
+
+    #[test]
+    fn test_matching_gpu_prefers_bus_id_over_name() {
+        let candidates = [
+            rtx_3080("0000:01:00.0", 40.0),
+            rtx_3080("0000:02:00.0", 80.0),
+        ];
+        let second = rtx_3080("0000:02:00.0", 0.0);
+        let found = matching_gpu(&second, &candidates).unwrap();
+        assert_eq!(found.temperature, Some(80.0));
+
+        // A bus ID missing from readings that report bus IDs matches nothing
+        assert!(matching_gpu(&rtx_3080("0000:03:00.0", 0.0), &candidates).is_none());
+    }
+
+    #[test]
+    fn test_matching_gpu_falls_back_to_name() {
+        let mut amd = GpuInfo::mock_amd();
+        amd.bus_id = None;
+        let candidates = [GpuInfo::mock_nvidia(), amd];
+        let mut wanted = GpuInfo::builder()
+            .vendor(Vendor::Amd)
+            .name("amd radeon rx 6800 xt")
+            .build();
+        wanted.bus_id = Some("0000:0a:00.0".to_string());
+        let found = matching_gpu(&wanted, &candidates).unwrap();
+        assert_eq!(found.vendor, Vendor::Amd);
+
+        let unnamed = GpuInfo::unknown();
+        assert_eq!(matching_gpu(&unnamed, &candidates), candidates.first());
+        assert_eq!(matching_gpu(&unnamed, &[]), None);
+    }
+
+    #[test]
+    fn test_get_gpu_by_pci_id() {
+        let manager: GpuManager = vec![
+            rtx_3080("0000:01:00.0", 40.0),
+            rtx_3080("0000:02:00.0", 80.0),
+        ]
+        .into_iter()
+        .collect();
+        let gpu = manager.get_gpu_by_pci_id("0000:02:00.0").unwrap();
+        assert_eq!(gpu.temperature, Some(80.0));
+        let gpu = manager.get_gpu_by_pci_id(" 00000000:01:00.0 ").unwrap();
+        assert_eq!(gpu.temperature, Some(40.0));
+        assert!(manager.get_gpu_by_pci_id("0000:03:00.0").is_none());
+        assert!(manager.get_gpu_by_pci_id("").is_none());
+    }
+
+    #[cfg(feature = "vendor-nvidia")]
+    #[test]
+    fn test_pci_bus_id_from_nvml() {
+        use crate::nvml_api::{pci_bus_id_from, NVML_SUCCESS};
+
+        let bus_id = pci_bus_id_from(|info| {
+            let id = b"00000000:65:00.0";
+            info.bus_id[..id.len()].copy_from_slice(id);
+            NVML_SUCCESS
+        });
+        assert_eq!(bus_id.as_deref(), Some("0000:65:00.0"));
+        assert_eq!(pci_bus_id_from(|_| NVML_SUCCESS), None);
+        assert_eq!(pci_bus_id_from(|_| 3), None);
+    }
+
+    #[test]
+    fn test_hash_prefers_uuid() {
+        let with_uuid = |uuid: &str| {
+            GpuInfo::builder()
+                .vendor(Vendor::Nvidia)
+                .name("NVIDIA GeForce RTX 3080")
+                .uuid(uuid)
+                .build()
+        };
+        let a = with_uuid("GPU-5a8f0c21-7d1e-4b7a-9f7e-0c1d2e3f4a5b");
+        let b = with_uuid("GPU-0e9d8c7b-6a59-4837-2615-f4e3d2c1b0a9");
+        assert_ne!(hash_of(&a), hash_of(&b));
+        assert_eq!(a.uuid(), Some("GPU-5a8f0c21-7d1e-4b7a-9f7e-0c1d2e3f4a5b"));
+
+        // A renamed reading of the same card keeps its hash
+        let mut renamed = a.clone();
+        renamed.name_gpu = Some("RTX 3080".to_string());
+        assert_eq!(hash_of(&a), hash_of(&renamed));
+
+        // Without a UUID, vendor and name decide
+        let slot_a = rtx_3080("0000:01:00.0", 60.0);
+        let slot_b = rtx_3080("0000:02:00.0", 60.0);
+        assert_eq!(hash_of(&slot_a), hash_of(&slot_b));
+        assert_eq!(slot_a.pci_bus_id(), Some("0000:01:00.0"));
+    }
+
+    #[test]
+    fn test_validate_bus_id() {
+        assert!(rtx_3080("0000:01:00.0", 60.0).validate().is_ok());
+        assert!(rtx_3080("00000000:3B:00.0", 60.0).validate().is_ok());
+        for bus_id in ["", "01:00", "0000:01:00.0.1", "pci-0000:01:00.0"] {
+            assert!(matches!(
+                rtx_3080(bus_id, 60.0).validate(),
+                Err(GpuError::InvalidBusId(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_display_shows_identifiers() {
+        let gpu = GpuInfo::builder()
+            .uuid("GPU-5a8f")
+            .bus_id("0000:01:00.0")
+            .build();
+        let text = gpu.to_string();
+        assert!(text.contains("  UUID: GPU-5a8f\n"));
+        assert!(text.contains("  PCI Bus ID: 0000:01:00.0\n"));
+        assert!(GpuInfo::unknown().to_string().contains("  UUID: N/A\n"));
+    }
+
+    #[test]
+    fn test_format_luid() {
+        use crate::providers::dxgi::format_luid;
+
+        assert_eq!(format_luid(0, 0xd1a3), "LUID-00000000-0000d1a3");
+        assert_eq!(format_luid(-1, u32::MAX), "LUID-ffffffff-ffffffff");
+    }
+
+    #[test]
+    fn test_find_dxgi_adapter_by_bus_id() {
+        use crate::providers::dxgi::{find_adapter, format_bus_id, DxgiAdapter};
+
+        let adapter = |luid: &str, bus_id: Option<&str>| DxgiAdapter {
+            vendor_id: 0x8086,
+            luid: luid.to_string(),
+            bus_id: bus_id.map(str::to_string),
+        };
+        let adapters = [
+            adapter("LUID-00000000-0000a001", Some(&format_bus_id(0, 2, 0))),
+            adapter("LUID-00000000-0000a002", Some(&format_bus_id(3, 0, 0))),
+            DxgiAdapter {
+                vendor_id: 0x10de,
+                luid: "LUID-00000000-0000b001".to_string(),
+                bus_id: Some(format_bus_id(1, 0, 0)),
+            },
+        ];
+
+        assert_eq!(format_bus_id(3, 0, 0), "0000:03:00.0");
+        let found = find_adapter(&adapters, 0x8086, Some("00000000:03:00.0")).unwrap();
+        assert_eq!(found.luid, "LUID-00000000-0000a002");
+        assert!(find_adapter(&adapters, 0x8086, Some("0000:01:00.0")).is_none());
+        // Two Intel adapters cannot be told apart without a bus ID
+        assert!(find_adapter(&adapters, 0x8086, None).is_none());
+        let found = find_adapter(&adapters, 0x10de, None).unwrap();
+        assert_eq!(found.luid, "LUID-00000000-0000b001");
+    }
+
+    #[test]
+    fn test_adapter_luid_is_not_identity() {
+        let gpu = GpuInfo::builder()
+            .bus_id("0000:00:02.0")
+            .adapter_luid("LUID-00000000-0000a001")
+            .build();
+        assert_eq!(gpu.adapter_luid(), Some("LUID-00000000-0000a001"));
+        assert_eq!(gpu.uuid(), None);
+        let rebooted = GpuInfo::builder()
+            .bus_id("0000:00:02.0")
+            .adapter_luid("LUID-00000000-0000c7f2")
+            .build();
+        assert_eq!(gpu.identity(), rebooted.identity());
+    }
+
+    #[cfg(feature = "vendor-nvidia")]
+    #[test]
+    fn test_uuid_from_nvml() {
+        use crate::nvml_api::{uuid_from, NVML_SUCCESS};
+
+        let uuid = uuid_from(|buffer, length| {
+            let id = b"GPU-5a8f0c21-7d1e-4b7a-9f7e-0c1d2e3f4a5b";
+            assert!(length as usize > id.len());
+            // SAFETY: `buffer` holds `length` bytes
+            unsafe { std::ptr::copy_nonoverlapping(id.as_ptr(), buffer as *mut u8, id.len()) };
+            NVML_SUCCESS
+        });
+        assert_eq!(
+            uuid.as_deref(),
+            Some("GPU-5a8f0c21-7d1e-4b7a-9f7e-0c1d2e3f4a5b")
+        );
+        assert_eq!(uuid_from(|_, _| NVML_SUCCESS), None);
+        assert_eq!(uuid_from(|_, _| 3), None);
+    }
+}
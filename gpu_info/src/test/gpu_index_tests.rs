@@ -0,0 +1,112 @@
+//! Tests for typed GPU indices and the CUDA ordinal mapping
+
+#[cfg(test)]
+mod tests {
+    use crate::gpu_index::{cuda_index_map, CudaIndex, GpuIndex};
+    use crate::gpu_info::GpuInfo;
+    use crate::gpu_manager::GpuManager;
+    use std::collections::BTreeMap;
+
+    /// Manager indices of the NVIDIA GPUs in a mixed system
+    const NVIDIA: [GpuIndex; 3] = [GpuIndex(1), GpuIndex(2), GpuIndex(4)];
+
+    fn map(pairs: &[(usize, usize)]) -> BTreeMap<CudaIndex, GpuIndex> {
+        pairs
+            .iter()
+            .map(|(cuda, gpu)| (CudaIndex(*cuda), GpuIndex(*gpu)))
+            .collect()
+    }
+
+    #[test]
+    fn test_index_conversions() {
+        assert_eq!(GpuIndex(3).get(), 3);
+        assert_eq!(usize::from(GpuIndex(3)), 3);
+        assert_eq!(CudaIndex::from(2).get(), 2);
+        assert_eq!(usize::from(CudaIndex(2)), 2);
+        assert_eq!(GpuIndex(3).to_string(), "#3");
+        assert_eq!(CudaIndex(2).to_string(), "cuda:2");
+    }
+
+    #[test]
+    fn test_cuda_map_counts_nvidia_gpus_only() {
+        assert_eq!(
+            cuda_index_map(&NVIDIA, None),
+            map(&[(0, 1), (1, 2), (2, 4)])
+        );
+        assert!(cuda_index_map(&[], None).is_empty());
+    }
+
+    #[test]
+    fn test_cuda_map_honours_visible_devices() {
+        assert_eq!(
+            cuda_index_map(&NVIDIA, Some("2, 0")),
+            map(&[(0, 4), (1, 1)])
+        );
+        assert!(cuda_index_map(&NVIDIA, Some("")).is_empty());
+    }
+
+    #[test]
+    fn test_cuda_map_stops_at_invalid_entry() {
+        // Out of range, duplicate and UUID entries end the list, as in CUDA
+        assert_eq!(cuda_index_map(&NVIDIA, Some("1,7,0")), map(&[(0, 2)]));
+        assert_eq!(cuda_index_map(&NVIDIA, Some("0,0,1")), map(&[(0, 1)]));
+        assert!(cuda_index_map(&NVIDIA, Some("GPU-8f3c1c1e,0")).is_empty());
+    }
+
+    #[test]
+    fn test_manager_converts_cuda_ordinal() {
+        if std::env::var_os("CUDA_VISIBLE_DEVICES").is_some() {
+            return;
+        }
+        let manager: GpuManager = vec![
+            GpuInfo::mock_amd(),
+            GpuInfo::mock_nvidia(),
+            GpuInfo::mock_nvidia(),
+        ]
+        .into_iter()
+        .collect();
+        let index = manager.cuda_to_gpu_index(CudaIndex(1)).unwrap();
+        assert_eq!(index, GpuIndex(2));
+        assert_eq!(manager.get_gpu_at(index), Some(&GpuInfo::mock_nvidia()));
+        assert_eq!(manager.cuda_to_gpu_index(CudaIndex(2)), None);
+        assert_eq!(manager.cuda_indices().len(), 2);
+    }
+
+    #[test]
+    fn test_manager_takes_typed_indices() {
+        let mut manager: GpuManager = vec![GpuInfo::mock_amd(), GpuInfo::mock_nvidia()]
+            .into_iter()
+            .collect();
+        assert_eq!(
+            manager.get_gpu_at(GpuIndex(1)),
+            Some(&GpuInfo::mock_nvidia())
+        );
+        manager.set_primary_gpu_at(GpuIndex(1)).unwrap();
+        assert_eq!(manager.get_primary_gpu(), Some(&GpuInfo::mock_nvidia()));
+        assert!(manager.set_primary_gpu_at(GpuIndex(2)).is_err());
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_deprecated_usize_methods_forward() {
+        let mut manager: GpuManager = vec![GpuInfo::mock_amd(), GpuInfo::mock_nvidia()]
+            .into_iter()
+            .collect();
+        assert_eq!(manager.get_gpu_by_index(1), manager.get_gpu_at(GpuIndex(1)));
+        assert_eq!(
+            manager.get_gpu_by_index_owned(1),
+            manager.get_gpu_at_owned(GpuIndex(1))
+        );
+        assert_eq!(
+            manager.get_gpu_cached(1).as_deref(),
+            manager.get_cached_gpu_at(GpuIndex(1)).as_deref()
+        );
+        assert_eq!(
+            manager.get_gpu_cached_owned(0),
+            manager.get_cached_gpu_at_owned(GpuIndex(0))
+        );
+        manager.set_primary_gpu(1).unwrap();
+        assert_eq!(manager.get_primary_gpu(), Some(&GpuInfo::mock_nvidia()));
+        assert!(manager.refresh_gpu(2).is_err());
+    }
+}
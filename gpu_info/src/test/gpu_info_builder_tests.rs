@@ -1,7 +1,11 @@
 #[cfg(test)]
 mod tests {
-    use crate::gpu_info::GpuInfo;
+    use crate::gpu_info::{
+        ClockThrottleReason, Confidence, EccStats, FieldAvailability, GpuError, GpuInfo,
+        ThrottleReasons, UsageSummary,
+    };
     use crate::vendor::Vendor;
+    use std::time::{Duration, UNIX_EPOCH};
 
     #[test]
     fn test_builder_basic() {
@@ -33,6 +37,7 @@ mod tests {
             .memory_total(16)
             .driver_version("23.11.1")
             .max_clock_speed(2500)
+            .fan_speed(42)
             .build();
         assert_eq!(gpu.vendor(), Vendor::Amd);
         assert_eq!(gpu.name_gpu(), Some("AMD Radeon RX 6800 XT"));
@@ -47,6 +52,7 @@ mod tests {
         assert_eq!(gpu.memory_total(), Some(16));
         assert_eq!(gpu.driver_version(), Some("23.11.1"));
         assert_eq!(gpu.max_clock_speed(), Some(2500));
+        assert_eq!(gpu.fan_speed(), Some(42));
     }
 
     #[test]
@@ -57,6 +63,26 @@ mod tests {
         assert_eq!(gpu.temperature(), None);
         assert_eq!(gpu.utilization(), None);
         assert_eq!(gpu.power_usage(), None);
+        assert_eq!(gpu.confidence(), Confidence::Low);
+    }
+
+    #[test]
+    fn test_merge_takes_confidence_with_vendor() {
+        let fallback = GpuInfo::builder()
+            .vendor(Vendor::Nvidia)
+            .confidence(Confidence::High)
+            .build();
+        let mut unknown = GpuInfo::builder().temperature(60.0).build();
+        unknown.merge(&fallback);
+        assert_eq!(unknown.confidence(), Confidence::High);
+
+        let mut guessed = GpuInfo::builder()
+            .vendor(Vendor::Amd)
+            .confidence(Confidence::Medium)
+            .build();
+        guessed.merge(&fallback);
+        assert_eq!(guessed.confidence(), Confidence::Medium);
+        assert!(Confidence::Low < Confidence::Medium && Confidence::Medium < Confidence::High);
     }
 
     #[test]
@@ -313,6 +339,386 @@ mod tests {
         assert_eq!(dest, source);
     }
 
+    #[test]
+    fn test_gpu_info_clone_from_copies_fan_speed() {
+        let source = GpuInfo::builder()
+            .vendor(Vendor::Nvidia)
+            .fan_speed(65)
+            .build();
+        let mut dest = GpuInfo::mock_amd();
+        dest.clone_from(&source);
+        assert_eq!(dest.fan_speed(), Some(65));
+        dest.clone_from(&GpuInfo::unknown());
+        assert_eq!(dest.fan_speed(), None);
+    }
+
+    #[test]
+    fn test_fan_speed_validation_and_display() {
+        let gpu = GpuInfo::builder().fan_speed(100).build();
+        assert!(gpu.is_valid());
+        assert!(gpu.has_fan_speed());
+        assert!(GpuInfo::builder()
+            .fan_speed_rpm(900)
+            .build()
+            .has_fan_speed());
+        assert!(!GpuInfo::unknown().has_fan_speed());
+        assert!(gpu.to_string().contains("Fan Speed: 100"));
+        assert!(matches!(
+            GpuInfo::builder().fan_speed(101).build().validate(),
+            Err(crate::gpu_info::GpuError::InvalidFanSpeed(101))
+        ));
+    }
+
+    #[test]
+    fn test_fan_speed_rpm() {
+        let gpu = GpuInfo::builder().fan_speed(43).fan_speed_rpm(1290).build();
+        assert_eq!(gpu.fan_speed_rpm(), Some(1290));
+        assert_eq!(gpu.format_fan_speed(), "43% (1290 RPM)");
+        assert!(gpu.to_string().contains("Fan Speed (RPM): 1290"));
+        assert_eq!(
+            GpuInfo::builder()
+                .fan_speed_rpm(800)
+                .build()
+                .format_fan_speed(),
+            "800 RPM"
+        );
+
+        let mut merged = GpuInfo::builder().fan_speed(43).build();
+        merged.merge(&gpu);
+        assert_eq!(merged.fan_speed_rpm(), Some(1290));
+        assert!(!merged.approx_eq(&GpuInfo::builder().fan_speed(43).build(), 0.0));
+    }
+
+    #[test]
+    fn test_codec_utilization() {
+        let gpu = GpuInfo::builder()
+            .encoder_util(12.0)
+            .decoder_util(3.5)
+            .build();
+        assert_eq!(gpu.encoder_util(), Some(12.0));
+        assert_eq!(gpu.decoder_util(), Some(3.5));
+        assert_eq!(gpu.format_encoder_util(), "12.00%");
+        assert_eq!(gpu.format_decoder_util(), "3.50%");
+        assert!(gpu.to_string().contains("Encoder Utilization: 12"));
+        assert_eq!(GpuInfo::unknown().format_encoder_util(), "N/A");
+        assert_eq!(GpuInfo::unknown().decoder_util(), None);
+
+        let mut merged = GpuInfo::builder().decoder_util(1.0).build();
+        merged.merge(&gpu);
+        assert_eq!(merged.encoder_util(), Some(12.0));
+        assert_eq!(merged.decoder_util(), Some(1.0));
+        assert!(!merged.approx_eq(&gpu, 0.5));
+        assert!(merged.approx_eq(&gpu, 3.0));
+    }
+
+    #[cfg(feature = "vendor-nvidia")]
+    #[test]
+    fn test_nvml_codec_utilization() {
+        use crate::nvml_api::{codec_utilization_from, NVML_SUCCESS};
+
+        let encoder = codec_utilization_from(|utilization, period| {
+            *utilization = 42;
+            *period = 167_000;
+            NVML_SUCCESS
+        });
+        assert_eq!(encoder, Some(42.0));
+        // NVML_ERROR_NOT_SUPPORTED on GPUs without NVENC
+        assert_eq!(codec_utilization_from(|_, _| 3), None);
+    }
+
+    #[test]
+    fn test_throttle_reasons() {
+        let reasons: ThrottleReasons = [
+            ClockThrottleReason::SwThermalSlowdown,
+            ClockThrottleReason::GpuIdle,
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(reasons.bits(), 0x21);
+        assert_eq!(
+            reasons.iter().collect::<Vec<_>>(),
+            vec![
+                ClockThrottleReason::GpuIdle,
+                ClockThrottleReason::SwThermalSlowdown
+            ]
+        );
+        assert!(reasons.is_throttling());
+        assert!(!ThrottleReasons::from(ClockThrottleReason::GpuIdle).is_throttling());
+        assert!(!ThrottleReasons::default().is_throttling());
+        assert_eq!(ThrottleReasons::default().to_string(), "None");
+        // Bits of reasons added by newer drivers are kept
+        assert_eq!(
+            ThrottleReasons::from_bits(0x404).to_string(),
+            "SwPowerCap, 0x400"
+        );
+
+        let gpu = GpuInfo::builder()
+            .throttle_reasons(ClockThrottleReason::HwSlowdown)
+            .build();
+        assert_eq!(
+            gpu.throttle_reasons(),
+            Some(ThrottleReasons::from_bits(0x8))
+        );
+        assert!(gpu.to_string().contains("  Throttle Reasons: HwSlowdown\n"));
+        assert_eq!(GpuInfo::unknown().throttle_reasons(), None);
+    }
+
+    #[test]
+    fn test_running_processes() {
+        use crate::process_usage::{GpuProcessInfo, ProcessType};
+
+        let process = GpuProcessInfo {
+            pid: 4242,
+            memory_used_mb: 2048,
+            process_type: ProcessType::Compute,
+            process_name: Some("python3".to_string()),
+        };
+        let gpu = GpuInfo::builder().processes([process.clone()]).build();
+        assert_eq!(gpu.running_processes(), vec![process.clone()]);
+        assert!(gpu.to_string().contains("  Processes: 1\n"));
+        assert!(GpuInfo::unknown().running_processes().is_empty());
+        assert!(GpuInfo::unknown()
+            .to_string()
+            .contains("  Processes: N/A\n"));
+
+        // Merging fills an unknown process list from the fallback
+        let mut merged = GpuInfo::unknown();
+        merged.merge(&gpu);
+        assert_eq!(merged.processes, Some(vec![process]));
+    }
+
+    #[cfg(feature = "vendor-nvidia")]
+    #[test]
+    fn test_nvml_throttle_reasons() {
+        use crate::nvml_api::{throttle_reasons_from, NVML_SUCCESS};
+
+        let reasons = throttle_reasons_from(|reasons| {
+            *reasons = 0x44;
+            NVML_SUCCESS
+        });
+        assert_eq!(
+            reasons,
+            Some(
+                [
+                    ClockThrottleReason::SwPowerCap,
+                    ClockThrottleReason::HwThermalSlowdown
+                ]
+                .into_iter()
+                .collect()
+            )
+        );
+        // NVML_ERROR_NOT_SUPPORTED on GPUs without throttle reporting
+        assert_eq!(throttle_reasons_from(|_| 3), None);
+    }
+
+    #[test]
+    fn test_ecc_errors() {
+        let ecc = EccStats {
+            single_bit_corrected: 41,
+            double_bit_uncorrected: 3,
+            aggregate: false,
+        };
+        assert!(ecc.check_uncorrected(3).is_ok());
+        assert!(matches!(
+            ecc.check_uncorrected(2),
+            Err(GpuError::EccError(3))
+        ));
+        assert_eq!(ecc.to_string(), "41 corrected, 3 uncorrected (volatile)");
+
+        let gpu = GpuInfo::builder().ecc_errors(ecc).build();
+        assert_eq!(gpu.ecc_errors(), Some(ecc));
+        assert!(gpu
+            .to_string()
+            .contains("  ECC Errors: 41 corrected, 3 uncorrected (volatile)\n"));
+        assert_eq!(GpuInfo::unknown().ecc_errors(), None);
+    }
+
+    #[cfg(feature = "vendor-nvidia")]
+    #[test]
+    fn test_nvml_ecc_stats() {
+        use crate::nvml_api::{
+            ecc_stats_from, NVML_AGGREGATE_ECC, NVML_MEMORY_ERROR_TYPE_CORRECTED, NVML_SUCCESS,
+        };
+
+        let aggregate = ecc_stats_from(|error_type, _, count| {
+            *count = if error_type == NVML_MEMORY_ERROR_TYPE_CORRECTED {
+                120
+            } else {
+                2
+            };
+            NVML_SUCCESS
+        });
+        assert_eq!(
+            aggregate,
+            Some(EccStats {
+                single_bit_corrected: 120,
+                double_bit_uncorrected: 2,
+                aggregate: true,
+            })
+        );
+        // Volatile counters when the GPU keeps no aggregate counts
+        let volatile = ecc_stats_from(|_, counter_type, count| {
+            if counter_type == NVML_AGGREGATE_ECC {
+                return 3;
+            }
+            *count = 1;
+            NVML_SUCCESS
+        });
+        assert_eq!(volatile.map(|ecc| ecc.aggregate), Some(false));
+        // NVML_ERROR_NOT_SUPPORTED on GPUs without ECC enabled
+        assert_eq!(ecc_stats_from(|_, _, _| 3), None);
+    }
+
+    #[cfg(feature = "vendor-nvidia")]
+    #[test]
+    fn test_nvml_pcie_link_value() {
+        use crate::nvml_api::{pcie_link_value_from, NVML_SUCCESS};
+
+        assert_eq!(
+            pcie_link_value_from(|value| {
+                *value = 16;
+                NVML_SUCCESS
+            }),
+            Some(16)
+        );
+        // NVML_ERROR_NOT_SUPPORTED, and a link that reports no lanes
+        assert_eq!(pcie_link_value_from(|_| 3), None);
+        assert_eq!(pcie_link_value_from(|_| NVML_SUCCESS), None);
+    }
+
+    #[test]
+    fn test_temperature_prefers_core_sensor() {
+        let gpu = GpuInfo::builder()
+            .temperature(70.0)
+            .temperature_sensor("memory", 84.0)
+            .temperature_sensor("core", 62.0)
+            .build();
+        assert_eq!(gpu.temperatures().map(|sensors| sensors.len()), Some(2));
+        assert_eq!(gpu.temperature(), Some(62.0));
+        assert_eq!(gpu.format_temperature(), "62.00°C");
+
+        let gpu = GpuInfo::builder()
+            .temperature_sensor("junction", 80.0)
+            .temperature_sensor("edge", 55.0)
+            .build();
+        assert_eq!(gpu.temperature(), Some(55.0));
+        assert!(gpu.availability().temperature);
+
+        // Without a core or edge sensor the scalar reading is kept
+        let gpu = GpuInfo::builder()
+            .temperature(70.0)
+            .temperature_sensor("memory", 84.0)
+            .build();
+        assert_eq!(gpu.temperature(), Some(70.0));
+        assert_eq!(GpuInfo::unknown().temperatures(), None);
+    }
+
+    #[test]
+    fn test_temperatures_merge_and_approx_eq() {
+        let sensors = GpuInfo::builder()
+            .temperature_sensor("core", 62.0)
+            .temperature_sensor("memory", 84.0)
+            .build();
+        let mut gpu = GpuInfo::builder().vendor(Vendor::Nvidia).build();
+        gpu.merge(&sensors);
+        assert_eq!(gpu.temperatures(), sensors.temperatures());
+
+        let mut close = sensors.clone();
+        close
+            .temperatures
+            .as_mut()
+            .unwrap()
+            .insert("memory".to_string(), 84.005);
+        assert!(sensors.approx_eq(&close, 0.01));
+        assert!(!sensors.approx_eq(&close, 0.001));
+        let core_only = GpuInfo::builder().temperature_sensor("core", 62.0).build();
+        assert!(!sensors.approx_eq(&core_only, 0.01));
+    }
+
+    #[cfg(feature = "vendor-nvidia")]
+    #[test]
+    fn test_nvml_memory_temperature_field() {
+        use crate::nvml_api::{
+            field_value_from, sensor_temperatures, NVML_FI_DEV_MEMORY_TEMP, NVML_SUCCESS,
+        };
+
+        let memory = field_value_from(NVML_FI_DEV_MEMORY_TEMP, |value| {
+            assert_eq!(value.field_id, NVML_FI_DEV_MEMORY_TEMP);
+            value.value_type = 1;
+            value.value = u64::from_ne_bytes([84, 0, 0, 0, 0, 0, 0, 0]);
+            NVML_SUCCESS
+        });
+        assert_eq!(memory, Some(84.0));
+        // The call succeeds but the GPU has no memory sensor
+        let unsupported = field_value_from(NVML_FI_DEV_MEMORY_TEMP, |value| {
+            value.nvml_return = 3;
+            NVML_SUCCESS
+        });
+        assert_eq!(unsupported, None);
+
+        let gpu = GpuInfo::builder().temperature(62.0).build();
+        let gpu = GpuInfo {
+            temperatures: sensor_temperatures(gpu.temperature, memory),
+            ..gpu
+        };
+        assert_eq!(gpu.temperatures().unwrap()["memory"], 84.0);
+        assert_eq!(gpu.temperature(), Some(62.0));
+        assert_eq!(sensor_temperatures(None, None), None);
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn test_json_round_trip() {
+        let gpu = GpuInfo::builder()
+            .vendor(Vendor::Nvidia)
+            .name("NVIDIA GeForce RTX 4090")
+            .temperature(65.5)
+            .temperature_sensor("core", 65.5)
+            .temperature_sensor("memory", 78.0)
+            .utilization(45.0)
+            .power_usage(250.25)
+            .core_clock(2520)
+            .memory_total(24564)
+            .memory_used(8192)
+            .driver_version("550.54.14")
+            .fan_speed(40)
+            .active(true)
+            .build();
+        for json in [gpu.to_json().unwrap(), gpu.to_json_pretty().unwrap()] {
+            assert_eq!(GpuInfo::try_from(json.as_str()).unwrap(), gpu);
+        }
+        assert!(gpu.to_json_pretty().unwrap().contains('\n'));
+        assert!(!gpu.to_json().unwrap().contains('\n'));
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn test_from_json_round_trip() {
+        let gpu = GpuInfo::mock_nvidia();
+        assert_eq!(GpuInfo::from_json(&gpu.to_json().unwrap()).unwrap(), gpu);
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn test_from_json_malformed() {
+        for json in ["", "{", r#"{"vendor":42}"#, "[]"] {
+            assert!(matches!(
+                GpuInfo::from_json(json),
+                Err(crate::gpu_info::GpuError::Ffi(_))
+            ));
+        }
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn test_json_round_trip_without_optional_fields() {
+        for gpu in [GpuInfo::builder().build(), GpuInfo::unknown()] {
+            let json = gpu.to_json().unwrap();
+            assert!(json.contains(r#""temperature":null"#));
+            assert_eq!(GpuInfo::try_from(json.as_str()).unwrap(), gpu);
+        }
+    }
+
     #[test]
     fn test_gpu_info_clone_from_with_none_strings() {
         let source = GpuInfo::builder()
@@ -342,4 +748,221 @@ mod tests {
         assert_eq!(dest.name_gpu(), Some("RTX 3080"));
         assert_eq!(dest.driver_version(), Some("535.154.05"));
     }
+
+    #[test]
+    fn test_availability_matches_populated_fields() {
+        let gpu = GpuInfo::builder()
+            .vendor(Vendor::Amd)
+            .name("AMD Radeon RX 7600")
+            .temperature(55.0)
+            .memory_total(16384)
+            .driver_version("23.40.2")
+            .build();
+        let availability = gpu.availability();
+        assert_eq!(
+            availability,
+            FieldAvailability {
+                name: true,
+                temperature: true,
+                memory_total: true,
+                driver_version: true,
+                ..FieldAvailability::default()
+            }
+        );
+        assert_eq!(availability.temperature, gpu.has_temperature());
+        assert_eq!(availability.utilization, gpu.has_utilization());
+        assert_eq!(availability.power_usage, gpu.has_power_usage());
+        assert_eq!(availability.driver_version, gpu.has_driver());
+        assert_eq!(
+            GpuInfo::unknown().availability(),
+            FieldAvailability::default()
+        );
+    }
+
+    #[test]
+    fn test_availability_of_codec_fan_and_identity_fields() {
+        let gpu = GpuInfo::builder()
+            .vendor(Vendor::Nvidia)
+            .fan_speed_rpm(1450)
+            .encoder_util(12.0)
+            .decoder_util(3.0)
+            .uuid("GPU-8d1f0c4e-1b2a-4c3d-9e8f-0a1b2c3d4e5f")
+            .bus_id("0000:01:00.0")
+            .adapter_luid("LUID-00000000-0000d1a3")
+            .build();
+        assert_eq!(
+            gpu.availability(),
+            FieldAvailability {
+                fan_speed_rpm: true,
+                encoder_util: true,
+                decoder_util: true,
+                uuid: true,
+                bus_id: true,
+                adapter_luid: true,
+                ..FieldAvailability::default()
+            }
+        );
+        let availability = GpuInfo::builder()
+            .temperature_sensor("hotspot", 71.0)
+            .ecc_errors(EccStats::default())
+            .processes(Vec::new())
+            .build()
+            .availability();
+        assert!(availability.temperatures);
+        assert!(availability.ecc_errors);
+        assert!(availability.processes);
+        assert!(!availability.throttle_reasons);
+    }
+
+    #[test]
+    fn test_usage_summary_percentages() {
+        let gpu = GpuInfo::builder()
+            .vendor(Vendor::Nvidia)
+            .utilization(87.5)
+            .memory_total(24576)
+            .memory_used(6144)
+            .memory_util(10.0)
+            .power_usage(337.5)
+            .power_limit(450.0)
+            .temperature(61.0)
+            .build();
+        assert_eq!(
+            gpu.usage_summary(),
+            UsageSummary {
+                gpu: Some(87.5),
+                memory: Some(25.0),
+                power: Some(75.0),
+                thermal: Some(61.0),
+            }
+        );
+        assert_eq!(gpu.usage_summary_with_shutdown(80.0).thermal, Some(76.25));
+
+        // Without memory amounts the reported memory utilization is used
+        let amounts_unknown = GpuInfo::builder().memory_util(42.0).build();
+        assert_eq!(amounts_unknown.usage_summary().memory, Some(42.0));
+
+        // Readings beyond the limits are clamped
+        let over = GpuInfo::builder()
+            .power_usage(500.0)
+            .power_limit(450.0)
+            .temperature(120.0)
+            .build();
+        assert_eq!(over.usage_summary().power, Some(100.0));
+        assert_eq!(over.usage_summary().thermal, Some(100.0));
+    }
+
+    #[test]
+    fn test_usage_summary_missing_inputs() {
+        assert_eq!(GpuInfo::unknown().usage_summary(), UsageSummary::default());
+
+        // Power needs both the usage and a positive limit
+        let no_limit = GpuInfo::builder().power_usage(150.0).build();
+        assert_eq!(no_limit.usage_summary().power, None);
+        let zero_limit = GpuInfo::builder()
+            .power_usage(150.0)
+            .power_limit(0.0)
+            .build();
+        assert_eq!(zero_limit.usage_summary().power, None);
+
+        // Memory needs a positive total or a reported utilization
+        let no_total = GpuInfo::builder().memory_used(2048).build();
+        assert_eq!(no_total.usage_summary().memory, None);
+
+        let hot = GpuInfo::builder().temperature(70.0).build();
+        assert_eq!(hot.usage_summary_with_shutdown(0.0).thermal, None);
+        assert_eq!(hot.usage_summary().gpu, None);
+
+        // NaN readings are treated as missing
+        let nan = GpuInfo::builder().temperature(f32::NAN).build();
+        assert_eq!(nan.usage_summary().thermal, None);
+    }
+
+    #[test]
+    fn test_clock_deficit_percent() {
+        let throttled = GpuInfo::builder()
+            .core_clock(2500)
+            .effective_core_clock(1875)
+            .build();
+        assert_eq!(throttled.effective_core_clock(), Some(1875));
+        assert_eq!(throttled.clock_deficit_percent(), Some(25.0));
+        assert!(throttled.availability().effective_core_clock);
+
+        let boosted = GpuInfo::builder()
+            .core_clock(2500)
+            .effective_core_clock(2600)
+            .build();
+        assert_eq!(boosted.clock_deficit_percent(), Some(0.0));
+
+        let idle = GpuInfo::builder()
+            .core_clock(0)
+            .effective_core_clock(0)
+            .build();
+        assert_eq!(idle.clock_deficit_percent(), None);
+        assert_eq!(
+            GpuInfo::builder()
+                .effective_core_clock(1875)
+                .build()
+                .clock_deficit_percent(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_driver_is_older_than() {
+        let gpu = GpuInfo::builder().driver_version("535.154.05").build();
+        assert_eq!(gpu.driver_is_older_than("550.54.14"), Some(true));
+        assert_eq!(gpu.driver_is_older_than("535.154.10"), Some(true));
+        assert_eq!(gpu.driver_is_older_than("535.104.05"), Some(false));
+        assert_eq!(gpu.driver_is_older_than("470"), Some(false));
+        // Equal versions, also with differing component counts
+        assert_eq!(gpu.driver_is_older_than("535.154.05"), Some(false));
+        assert_eq!(gpu.driver_is_older_than("535.154.5.0"), Some(false));
+        assert_eq!(gpu.driver_is_older_than("535.154.5.1"), Some(true));
+
+        assert_eq!(gpu.driver_is_older_than("latest"), None);
+        assert_eq!(gpu.driver_is_older_than("535..1"), None);
+        let unparseable = GpuInfo::builder().driver_version("amdgpu-pro").build();
+        assert_eq!(unparseable.driver_is_older_than("23.40"), None);
+        assert_eq!(GpuInfo::unknown().driver_is_older_than("535"), None);
+    }
+
+    #[test]
+    fn test_to_influx_escapes_tags() {
+        let gpu = GpuInfo::builder()
+            .vendor(Vendor::Nvidia)
+            .name("NVIDIA GeForce RTX 3080")
+            .temperature(65.0)
+            .utilization(45.5)
+            .core_clock(1710)
+            .build();
+        assert_eq!(
+            gpu.to_influx(
+                "gpu stats",
+                &[("rack", "a,1"), ("role=x", "y"), ("empty", "")]
+            ),
+            "gpu\\ stats,vendor=NVIDIA,name=NVIDIA\\ GeForce\\ RTX\\ 3080,rack=a\\,1,\
+             role\\=x=y temperature=65,utilization=45.5,core_clock=1710"
+        );
+    }
+
+    #[test]
+    fn test_to_influx_omits_missing_fields() {
+        let gpu = GpuInfo::builder()
+            .vendor(Vendor::Amd)
+            .power_usage(120.0)
+            .memory_used(2048)
+            .build();
+        assert_eq!(
+            gpu.to_influx("gpu", &[]),
+            "gpu,vendor=AMD power_usage=120,memory_used=2048"
+        );
+        assert_eq!(GpuInfo::unknown().to_influx("gpu", &[]), "");
+
+        let at = UNIX_EPOCH + Duration::from_millis(1_500);
+        assert_eq!(
+            gpu.to_influx_at("gpu", &[], at),
+            "gpu,vendor=AMD power_usage=120,memory_used=2048 1500000000"
+        );
+        assert_eq!(GpuInfo::unknown().to_influx_at("gpu", &[], at), "");
+    }
 }
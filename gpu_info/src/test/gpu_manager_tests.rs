@@ -14,8 +14,11 @@
 
 #[cfg(test)]
 mod tests {
+    use crate::gpu_index::GpuIndex;
+    use crate::gpu_info::GpuInfo;
     use crate::gpu_manager::GpuManager;
     use crate::vendor::Vendor;
+    use std::sync::atomic::{AtomicUsize, Ordering};
     use std::sync::Arc;
     use std::time::Duration;
     use tokio::task::JoinSet;
@@ -61,7 +64,7 @@ mod tests {
         let all_gpus = manager.get_all_gpus();
         assert_eq!(gpu_count, all_gpus.len());
         if gpu_count > 0 {
-            let cached_gpu = manager.get_gpu_cached(0);
+            let cached_gpu = manager.get_cached_gpu_at(GpuIndex(0));
             assert!(cached_gpu.is_some());
         }
         println!("Manager with cache config detected {} GPUs", gpu_count);
@@ -86,12 +89,12 @@ mod tests {
             println!("No primary GPU available");
         }
         if manager.gpu_count() > 0 {
-            let gpu_0 = manager.get_gpu_by_index(0);
-            let gpu_0_owned = manager.get_gpu_by_index_owned(0);
+            let gpu_0 = manager.get_gpu_at(GpuIndex(0));
+            let gpu_0_owned = manager.get_gpu_at_owned(GpuIndex(0));
             assert!(gpu_0.is_some());
             assert!(gpu_0_owned.is_some());
         }
-        let invalid_gpu = manager.get_gpu_by_index(9999);
+        let invalid_gpu = manager.get_gpu_at(GpuIndex(9999));
         assert!(invalid_gpu.is_none());
     }
 
@@ -120,9 +123,9 @@ mod tests {
             println!("No GPUs to test primary GPU management");
             return;
         }
-        let result = manager.set_primary_gpu(0);
+        let result = manager.set_primary_gpu_at(GpuIndex(0));
         assert!(result.is_ok());
-        let invalid_result = manager.set_primary_gpu(9999);
+        let invalid_result = manager.set_primary_gpu_at(GpuIndex(9999));
         assert!(invalid_result.is_err());
         println!("Primary GPU management test completed");
     }
@@ -140,7 +143,7 @@ mod tests {
             Ok(()) => println!("Successfully refreshed all GPUs"),
             Err(e) => println!("Failed to refresh all GPUs (expected in test env): {}", e),
         }
-        let refresh_gpu_result = manager.refresh_gpu(0);
+        let refresh_gpu_result = manager.refresh_gpu_at(GpuIndex(0));
         match refresh_gpu_result {
             Ok(()) => println!("Successfully refreshed GPU #0"),
             Err(e) => println!("Failed to refresh GPU #0 (expected in test env): {}", e),
@@ -153,7 +156,7 @@ mod tests {
                 e
             ),
         }
-        let refresh_invalid_result = manager.refresh_gpu(9999);
+        let refresh_invalid_result = manager.refresh_gpu_at(GpuIndex(9999));
         assert!(refresh_invalid_result.is_err());
     }
 
@@ -165,8 +168,8 @@ mod tests {
             println!("No GPUs to test caching");
             return;
         }
-        let cached_gpu_1 = manager.get_gpu_cached(0);
-        let cached_gpu_2 = manager.get_gpu_cached(0);
+        let cached_gpu_1 = manager.get_cached_gpu_at(GpuIndex(0));
+        let cached_gpu_2 = manager.get_cached_gpu_at(GpuIndex(0));
         if let (Some(gpu1), Some(gpu2)) = (cached_gpu_1, cached_gpu_2) {
             assert_eq!(gpu1.vendor, gpu2.vendor);
             assert_eq!(gpu1.name_gpu, gpu2.name_gpu);
@@ -346,7 +349,7 @@ mod tests {
         let mut cache_hits = 0;
         for i in 0..ACCESS_COUNT {
             let gpu_index = i % manager.gpu_count();
-            if manager.get_gpu_cached(gpu_index).is_some() {
+            if manager.get_cached_gpu_at(GpuIndex(gpu_index)).is_some() {
                 cache_hits += 1;
             }
         }
@@ -451,7 +454,7 @@ mod tests {
         let get_stats_time = start.elapsed();
         let start = std::time::Instant::now();
         for _ in 0..ITERATIONS {
-            let _ = manager.get_gpu_cached(0);
+            let _ = manager.get_cached_gpu_at(GpuIndex(0));
         }
         let cached_access_time = start.elapsed();
         println!(
@@ -529,7 +532,7 @@ mod tests {
         let cache_start = std::time::Instant::now();
         for i in 0..100 {
             let gpu_index = i % manager.gpu_count().max(1);
-            let _ = manager.get_gpu_cached(gpu_index);
+            let _ = manager.get_cached_gpu_at(GpuIndex(gpu_index));
         }
         let cache_time = cache_start.elapsed();
         println!("100 cached accesses in {:?}", cache_time);
@@ -555,4 +558,290 @@ mod tests {
         assert_eq!(final_stats.total_gpus, manager.gpu_count());
         assert!(cache_time < Duration::from_millis(100));
     }
+
+    /// Test that only GPUs with changed metrics are reported
+    #[test]
+    fn test_detect_changes_since_reports_changed_index() {
+        let prev = vec![
+            GpuInfo::mock_nvidia(),
+            GpuInfo::mock_amd(),
+            GpuInfo::mock_intel(),
+        ];
+        let mut current = prev.clone();
+        current[1].temperature = current[1].temperature.map(|t| t + 5.0);
+        let manager: GpuManager = current.into_iter().collect();
+        assert_eq!(manager.detect_changes_since(&prev), vec![1]);
+    }
+
+    /// Test that jitter below the epsilon is not reported as a change
+    #[test]
+    fn test_detect_changes_since_ignores_float_jitter() {
+        let prev = vec![GpuInfo::mock_nvidia()];
+        let mut current = prev.clone();
+        current[0].utilization = current[0].utilization.map(|u| u + 0.001);
+        let manager: GpuManager = current.into_iter().collect();
+        assert!(manager.detect_changes_since(&prev).is_empty());
+    }
+
+    /// Test that GPUs without a previous snapshot are reported as changed
+    #[test]
+    fn test_detect_changes_since_new_gpu() {
+        let prev = vec![GpuInfo::mock_nvidia()];
+        let manager: GpuManager = vec![GpuInfo::mock_nvidia(), GpuInfo::mock_amd()]
+            .into_iter()
+            .collect();
+        assert_eq!(manager.detect_changes_since(&prev), vec![1]);
+        assert_eq!(manager.detect_changes_since(&[]), vec![0, 1]);
+    }
+
+    /// Test that GPUs are sorted by utilization with unknown values last
+    #[test]
+    fn test_sorted_by_utilization() {
+        let gpu = |name: &str, utilization: Option<f32>| {
+            let mut gpu = GpuInfo::builder().name(name).build();
+            gpu.utilization = utilization;
+            gpu
+        };
+        let manager: GpuManager = vec![
+            gpu("unknown", None),
+            gpu("busy", Some(80.0)),
+            gpu("nan", Some(f32::NAN)),
+            gpu("idle-a", Some(10.0)),
+            gpu("idle-b", Some(10.0)),
+        ]
+        .into_iter()
+        .collect();
+        let names: Vec<_> = manager
+            .sorted_by_utilization()
+            .iter()
+            .map(|gpu| gpu.name_gpu().unwrap())
+            .collect();
+        // Equal utilization keeps detection order
+        assert_eq!(names, ["idle-a", "idle-b", "busy", "unknown", "nan"]);
+        assert_eq!(
+            manager.least_utilized().and_then(|gpu| gpu.name_gpu()),
+            Some("idle-a")
+        );
+    }
+
+    /// Test that a GPU with unknown utilization is only chosen if all are unknown
+    #[test]
+    fn test_least_utilized_all_unknown() {
+        let manager: GpuManager = vec![
+            GpuInfo::builder().name("first").build(),
+            GpuInfo::builder().name("second").build(),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(
+            manager.least_utilized().and_then(|gpu| gpu.name_gpu()),
+            Some("first")
+        );
+        let empty: GpuManager = Vec::new().into_iter().collect();
+        assert!(empty.least_utilized().is_none());
+        assert!(empty.sorted_by_utilization().is_empty());
+    }
+
+    /// Test the runtime cache controls of the manager
+    #[test]
+    fn test_cache_stats_clear_and_capacity() {
+        let manager: GpuManager = vec![
+            GpuInfo::mock_nvidia(),
+            GpuInfo::mock_amd(),
+            GpuInfo::mock_intel(),
+        ]
+        .into_iter()
+        .collect();
+        assert!(manager.get_cached_gpu_at(GpuIndex(0)).is_some());
+        assert!(manager.get_cached_gpu_at(GpuIndex(2)).is_some());
+        let stats = manager.cache_stats();
+        assert_eq!((stats.total_entries, stats.hits, stats.misses), (3, 2, 0));
+        assert!(stats.approx_bytes > 0);
+        // GPU 1 is least recently used
+        manager.set_cache_capacity(2);
+        let stats = manager.cache_stats();
+        assert_eq!((stats.total_entries, stats.evictions), (2, 1));
+        manager.clear_cache();
+        assert_eq!(manager.cache_stats().total_entries, 0);
+        assert_eq!(manager.cache_stats().hits, 2);
+    }
+
+    static STALE_REFRESHES: AtomicUsize = AtomicUsize::new(0);
+
+    /// Backend returning a new name and utilization on every call
+    fn counting_updater(gpu: &mut GpuInfo) -> crate::Result<()> {
+        let call = STALE_REFRESHES.fetch_add(1, Ordering::SeqCst) + 1;
+        gpu.name_gpu = Some(format!("GPU {call}"));
+        gpu.utilization = Some(call as f32);
+        Ok(())
+    }
+
+    /// Test that a cached read refreshes only metrics past their TTL
+    #[test]
+    fn test_get_gpu_cached_refreshes_stale_metrics_only() {
+        use crate::cache_utils::MetricKind;
+        use crate::metric_source::Metric;
+
+        let mut manager = GpuManager::with_updater(
+            vec![GpuInfo::mock_nvidia()],
+            counting_updater,
+            Duration::from_secs(60),
+        );
+        let utilization = MetricKind::Metric(Metric::Utilization);
+        manager.set_metric_ttl(utilization, Duration::ZERO);
+        manager.set_metric_ttl(MetricKind::Name, Duration::from_secs(60));
+        assert_eq!(manager.metric_ttl(utilization), Some(Duration::ZERO));
+
+        let first = manager.get_cached_gpu_at(GpuIndex(0)).unwrap();
+        assert_eq!(first.name_gpu.as_deref(), Some("GPU 1"));
+        assert_eq!(first.utilization, Some(1.0));
+        let second = manager.get_cached_gpu_at(GpuIndex(0)).unwrap();
+        assert_eq!(second.name_gpu.as_deref(), Some("GPU 1"));
+        assert_eq!(second.utilization, Some(2.0));
+
+        manager.set_metric_ttl(utilization, Duration::from_secs(60));
+        let third = manager.get_cached_gpu_at(GpuIndex(0)).unwrap();
+        assert_eq!(third, second);
+        assert_eq!(STALE_REFRESHES.load(Ordering::SeqCst), 2);
+    }
+
+    static TEMPERATURE_READS: AtomicUsize = AtomicUsize::new(0);
+
+    /// Backend that only returns the temperature on its first call
+    fn temperature_once(gpu: &mut GpuInfo) -> crate::Result<()> {
+        let first = TEMPERATURE_READS.fetch_add(1, Ordering::SeqCst) == 0;
+        gpu.temperature = first.then_some(70.0);
+        gpu.utilization = Some(40.0);
+        Ok(())
+    }
+
+    /// Test that a metric missing from a refresh keeps its value and ages
+    #[test]
+    fn test_metric_age_tracking() {
+        let mut gpu = GpuInfo::mock_nvidia();
+        gpu.temperature = None;
+        let mut manager =
+            GpuManager::with_updater(vec![gpu], temperature_once, Duration::from_secs(60));
+        assert!(!manager.is_metric_age_tracking());
+        manager.set_metric_age_tracking(true);
+
+        manager.refresh_gpu_at(GpuIndex(0)).unwrap();
+        std::thread::sleep(Duration::from_millis(20));
+        manager.refresh_gpu_at(GpuIndex(0)).unwrap();
+
+        let gpu = manager.get_gpu_at(GpuIndex(0)).unwrap();
+        assert_eq!(gpu.temperature, Some(70.0));
+        let temperature_age = gpu.metric_age("temperature").unwrap();
+        let utilization_age = gpu.metric_age("utilization").unwrap();
+        assert!(temperature_age >= Duration::from_millis(20));
+        assert!(utilization_age < temperature_age);
+        assert_eq!(gpu.metric_age("fan_speed"), None);
+        assert!(gpu.metric_ages().unwrap().contains_key("temperature"));
+        // Read times do not affect equality
+        let untracked = GpuInfo {
+            metric_sources: Default::default(),
+            ..gpu.clone()
+        };
+        assert_eq!(untracked.metric_ages(), None);
+        assert_eq!(untracked, *gpu);
+
+        let mut stale = gpu.clone();
+        stale.clear_stale_metrics(temperature_age / 2);
+        assert_eq!(stale.temperature, None);
+        assert_eq!(stale.utilization, Some(40.0));
+    }
+
+    /// Test that metric ages are not recorded unless enabled
+    #[test]
+    fn test_metric_age_tracking_disabled() {
+        let mut manager = GpuManager::with_updater(
+            vec![GpuInfo::mock_nvidia()],
+            |gpu| {
+                gpu.temperature = None;
+                Ok(())
+            },
+            Duration::from_secs(60),
+        );
+        manager.refresh_gpu_at(GpuIndex(0)).unwrap();
+        let gpu = manager.get_gpu_at(GpuIndex(0)).unwrap();
+        assert_eq!(gpu.temperature, None);
+        assert_eq!(gpu.metric_age("utilization"), None);
+        assert_eq!(gpu.metric_ages(), None);
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn test_export_all_json() {
+        let gpus = vec![GpuInfo::mock_nvidia(), GpuInfo::unknown()];
+        let manager: GpuManager = gpus.clone().into_iter().collect();
+        let json = manager.export_all_json().unwrap();
+        let restored: Vec<GpuInfo> = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, gpus);
+        let empty: GpuManager = Vec::new().into_iter().collect();
+        assert_eq!(empty.export_all_json().unwrap(), "[]");
+    }
+
+    /// Backend taking 200ms per GPU that fails for GPUs named "faulty"
+    fn slow_updater(gpu: &mut GpuInfo) -> crate::Result<()> {
+        std::thread::sleep(Duration::from_millis(200));
+        if gpu.name_gpu() == Some("faulty") {
+            gpu.utilization = Some(0.0);
+            return Err(crate::gpu_info::GpuError::DriverNotInstalled);
+        }
+        gpu.utilization = Some(75.0);
+        Ok(())
+    }
+
+    fn slow_manager() -> GpuManager {
+        let gpus = ["GPU 0", "faulty", "GPU 2", "GPU 3"]
+            .iter()
+            .map(|name| GpuInfo::builder().name(*name).utilization(10.0).build())
+            .collect();
+        GpuManager::with_updater(gpus, slow_updater, Duration::from_secs(60))
+    }
+
+    fn assert_refreshed(manager: &GpuManager, errors: &[(usize, crate::gpu_info::GpuError)]) {
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].0, 1);
+        assert!(matches!(
+            errors[0].1,
+            crate::gpu_info::GpuError::DriverNotInstalled
+        ));
+        let names: Vec<_> = manager.iter().map(|gpu| gpu.name_gpu().unwrap()).collect();
+        assert_eq!(names, ["GPU 0", "faulty", "GPU 2", "GPU 3"]);
+        let utilization: Vec<_> = manager.iter().map(|gpu| gpu.utilization).collect();
+        // The failed GPU keeps its previous metrics
+        assert_eq!(
+            utilization,
+            [Some(75.0), Some(10.0), Some(75.0), Some(75.0)]
+        );
+        // Only updated GPUs are cached
+        assert_eq!(manager.cache_stats().total_entries, 3);
+        assert_eq!(
+            manager.get_cached_gpu_at(GpuIndex(3)).unwrap().utilization,
+            Some(75.0)
+        );
+        assert_eq!(manager.cache_stats().hits, 1);
+    }
+
+    /// Test that a parallel refresh overlaps slow GPUs and isolates failures
+    #[test]
+    fn test_refresh_parallel() {
+        let mut manager = slow_manager();
+        let start = std::time::Instant::now();
+        let errors = manager.refresh_parallel();
+        // Sequential updates would take 800ms
+        assert!(start.elapsed() < Duration::from_millis(600));
+        assert_refreshed(&manager, &errors);
+    }
+
+    /// Test that an async refresh overlaps slow GPUs and isolates failures
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_refresh_async() {
+        let mut manager = slow_manager();
+        let start = std::time::Instant::now();
+        let errors = manager.refresh_async().await;
+        assert!(start.elapsed() < Duration::from_millis(600));
+        assert_refreshed(&manager, &errors);
+    }
 }
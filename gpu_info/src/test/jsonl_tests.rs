@@ -0,0 +1,137 @@
+//! Tests for the JSONL sample writer and reader
+
+#[cfg(all(test, feature = "serde_json"))]
+mod tests {
+    use crate::gpu_info::GpuInfo;
+    use crate::jsonl::{Sample, SampleReader, SampleRecord, SampleWriter};
+    use crate::monitoring::GpuMetricsSnapshot;
+    use std::io::{self, Write};
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    /// Writer that records how often it was flushed
+    #[derive(Debug, Default, Clone)]
+    struct CountingWriter {
+        data: Arc<Mutex<Vec<u8>>>,
+        flushes: Arc<AtomicU64>,
+    }
+
+    impl Write for CountingWriter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.data.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+        fn flush(&mut self) -> io::Result<()> {
+            self.flushes.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    fn read_all(bytes: &[u8]) -> Vec<SampleRecord> {
+        SampleReader::new(bytes)
+            .collect::<crate::gpu_info::Result<_>>()
+            .expect("records should parse")
+    }
+
+    #[test]
+    fn test_round_trip_samples_and_snapshots() {
+        let tick = AtomicU64::new(100);
+        let mut writer =
+            SampleWriter::new(Vec::new()).with_clock(move || tick.fetch_add(1, Ordering::SeqCst));
+        let snapshot = GpuMetricsSnapshot {
+            gpus: vec![GpuInfo::mock_nvidia(), GpuInfo::mock_amd()],
+        };
+        writer.write_sample(&GpuInfo::mock_intel()).unwrap();
+        writer.write_snapshot(&snapshot).unwrap();
+        let bytes = writer.into_inner().unwrap();
+
+        assert_eq!(bytes.iter().filter(|b| **b == b'\n').count(), 2);
+        let records = read_all(&bytes);
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].timestamp_ms, 100);
+        assert_eq!(records[0].sample, Sample::Gpu(GpuInfo::mock_intel()));
+        assert_eq!(records[1].timestamp_ms, 101);
+        assert_eq!(records[1].sample, Sample::Snapshot(snapshot));
+    }
+
+    #[test]
+    fn test_reader_tolerates_truncated_final_line() {
+        let mut writer = SampleWriter::new(Vec::new()).with_clock(|| 1);
+        writer.write_sample(&GpuInfo::mock_nvidia()).unwrap();
+        writer.write_sample(&GpuInfo::mock_amd()).unwrap();
+        let mut bytes = writer.into_inner().unwrap();
+        bytes.extend_from_slice(br#"{"timestamp_ms":2,"sample":{"kind":"gpu","da"#);
+
+        let records = read_all(&bytes);
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[1].sample, Sample::Gpu(GpuInfo::mock_amd()));
+    }
+
+    #[test]
+    fn test_reader_accepts_final_line_without_newline() {
+        let mut writer = SampleWriter::new(Vec::new()).with_clock(|| 1);
+        writer.write_sample(&GpuInfo::mock_nvidia()).unwrap();
+        let mut bytes = writer.into_inner().unwrap();
+        bytes.pop();
+        assert_eq!(read_all(&bytes).len(), 1);
+    }
+
+    #[test]
+    fn test_reader_reports_corrupt_middle_line() {
+        let mut bytes = b"not json\n".to_vec();
+        let mut writer = SampleWriter::new(Vec::new()).with_clock(|| 1);
+        writer.write_sample(&GpuInfo::mock_nvidia()).unwrap();
+        bytes.extend(writer.into_inner().unwrap());
+
+        let mut reader = SampleReader::new(bytes.as_slice());
+        assert!(reader.next().unwrap().is_err());
+        assert!(reader.next().unwrap().is_ok());
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn test_flush_cadence() {
+        let sink = CountingWriter::default();
+        let flushes = Arc::clone(&sink.flushes);
+        let mut writer = SampleWriter::new(sink).with_flush_every(3);
+        for _ in 0..7 {
+            writer.write_sample(&GpuInfo::mock_nvidia()).unwrap();
+        }
+        assert_eq!(flushes.load(Ordering::SeqCst), 2);
+        writer.flush().unwrap();
+        assert_eq!(flushes.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn test_rotation_on_size_threshold() {
+        let rotated: Arc<Mutex<Vec<Vec<u8>>>> = Arc::default();
+        let sink = Arc::clone(&rotated);
+        let mut writer = SampleWriter::new(Vec::new())
+            .with_clock(|| 1)
+            .with_rotation(1, move |old: Vec<u8>| {
+                sink.lock().unwrap().push(old);
+                Ok(Vec::new())
+            });
+        writer.write_sample(&GpuInfo::mock_nvidia()).unwrap();
+        writer.write_sample(&GpuInfo::mock_amd()).unwrap();
+        assert_eq!(writer.bytes_written(), 0);
+        assert!(writer.into_inner().unwrap().is_empty());
+
+        let rotated = rotated.lock().unwrap();
+        assert_eq!(rotated.len(), 2);
+        assert_eq!(read_all(&rotated[0]).len(), 1);
+        assert_eq!(
+            read_all(&rotated[1])[0].sample,
+            Sample::Gpu(GpuInfo::mock_amd())
+        );
+    }
+
+    #[test]
+    fn test_failed_rotation_reports_error() {
+        let mut writer = SampleWriter::new(Vec::new()).with_rotation(1, |_old| {
+            Err(io::Error::new(io::ErrorKind::Other, "disk full"))
+        });
+        assert!(writer.write_sample(&GpuInfo::mock_nvidia()).is_err());
+        assert!(writer.write_sample(&GpuInfo::mock_nvidia()).is_err());
+    }
+}
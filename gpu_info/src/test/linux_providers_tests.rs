@@ -1,13 +1,30 @@
 #[cfg(test)]
 mod tests {
     use crate::{
-        gpu_info::GpuProvider,
+        cancel::CancelToken,
+        extended_info::{ConnectionInfo, SmartShiftInfo},
+        gpu_info::{Confidence, GpuInfo, GpuProvider},
+        metric_source::Metric,
         providers::linux::{
-            amd::AmdLinuxProvider, intel::IntelLinuxProvider, nvidia::NvidiaLinuxProvider,
+            amd::{
+                drm_client_processes, hwmon_fan, hwmon_temperatures, parse_gpu_metrics_gfxclk,
+                parse_smartshift_percent, smartshift, AmdLinuxProvider,
+            },
+            card_nodes,
+            intel::IntelLinuxProvider,
+            is_card_node,
+            nvidia::{compute_capability_from, sysfs_reading, NvidiaLinuxProvider},
+            pcie::{
+                aer_correctable, bus_id, fill_errors, fill_link, parse_aer_counters,
+                parse_link_speed, parse_link_width,
+            },
+            runtime_active,
         },
+        test::scratch_dir,
         vendor::Vendor,
     };
-    use std::path::Path;
+    use std::fs;
+    use std::path::{Path, PathBuf};
 
     #[test]
     fn test_nvidia_linux_provider_vendor() {
@@ -15,6 +32,17 @@ mod tests {
         assert_eq!(provider.get_vendor(), Vendor::Nvidia);
     }
 
+    #[test]
+    fn test_nvidia_compute_capability_not_supported() {
+        let supported = compute_capability_from(|major, minor| {
+            *major = 8;
+            *minor = 6;
+            0
+        });
+        assert_eq!(supported, Some((8, 6)));
+        assert_eq!(compute_capability_from(|_, _| 3), None);
+    }
+
     #[test]
     fn test_amd_linux_provider_vendor() {
         let provider = AmdLinuxProvider::new();
@@ -29,6 +57,78 @@ mod tests {
         assert_eq!(result, (None, None, None));
     }
 
+    #[test]
+    fn test_amd_get_memory_info() {
+        let provider = AmdLinuxProvider::new();
+        let device = scratch_dir("amd_vram");
+        // 12 GiB of VRAM, 3 GiB used; GTT is not VRAM and is ignored
+        fs::write(device.join("mem_info_vram_total"), "12884901888\n").unwrap();
+        fs::write(device.join("mem_info_vram_used"), "3221225472\n").unwrap();
+        fs::write(device.join("mem_info_gtt_total"), "16777216000\n").unwrap();
+        fs::write(device.join("mem_info_gtt_used"), "8388608000\n").unwrap();
+        assert_eq!(
+            provider.get_memory_info(&device),
+            (Some(12288), Some(25.0), Some(3072))
+        );
+
+        // A zero total is unknown and yields no utilization
+        fs::write(device.join("mem_info_vram_total"), "0\n").unwrap();
+        assert_eq!(provider.get_memory_info(&device), (None, None, Some(3072)));
+
+        fs::write(device.join("mem_info_vram_total"), "not a number\n").unwrap();
+        fs::remove_file(device.join("mem_info_vram_used")).unwrap();
+        assert_eq!(provider.get_memory_info(&device), (None, None, None));
+    }
+
+    #[test]
+    fn test_amd_detect_reports_vram() {
+        let drm = pm_card("amd_vram_detect", "0x1002", "active\n");
+        let device = drm.join("card0/device");
+        fs::write(device.join("mem_info_vram_total"), "12884901888\n").unwrap();
+        fs::write(device.join("mem_info_vram_used"), "1288490188\n").unwrap();
+        let gpus = AmdLinuxProvider::new()
+            .detect_amd_gpus_in(&drm, &CancelToken::new())
+            .unwrap();
+        assert_eq!(gpus[0].memory_total(), Some(12288));
+        assert_eq!(gpus[0].memory_used(), Some(1228));
+        let util = gpus[0].memory_util().unwrap();
+        assert!((util - 9.993).abs() < 0.01, "utilization {}", util);
+    }
+
+    /// `gpu_metrics` table with `gfxclk` written at `offset`
+    fn gpu_metrics(format: u8, content: u8, offset: usize, gfxclk: u16) -> Vec<u8> {
+        let mut table = vec![0u8; 128];
+        table[..2].copy_from_slice(&128u16.to_le_bytes());
+        table[2] = format;
+        table[3] = content;
+        table[offset..offset + 2].copy_from_slice(&gfxclk.to_le_bytes());
+        table
+    }
+
+    #[test]
+    fn test_amd_gpu_metrics_gfxclk() {
+        assert_eq!(
+            parse_gpu_metrics_gfxclk(&gpu_metrics(1, 3, 40, 2105)),
+            Some(2105)
+        );
+        assert_eq!(
+            parse_gpu_metrics_gfxclk(&gpu_metrics(2, 2, 68, 1600)),
+            Some(1600)
+        );
+        // Unsupported value, unknown layout and truncated tables
+        assert_eq!(
+            parse_gpu_metrics_gfxclk(&gpu_metrics(1, 3, 40, 0xFFFF)),
+            None
+        );
+        assert_eq!(parse_gpu_metrics_gfxclk(&gpu_metrics(1, 0, 40, 2105)), None);
+        assert_eq!(parse_gpu_metrics_gfxclk(&gpu_metrics(3, 0, 40, 2105)), None);
+        assert_eq!(parse_gpu_metrics_gfxclk(&[0, 0, 1]), None);
+        assert_eq!(
+            parse_gpu_metrics_gfxclk(&gpu_metrics(1, 3, 40, 2105)[..41]),
+            None
+        );
+    }
+
     #[test]
     fn test_intel_linux_provider_creation() {
         let provider = IntelLinuxProvider::new();
@@ -37,18 +137,494 @@ mod tests {
         assert!(matches!(default_provider.get_vendor(), Vendor::Intel(_)));
     }
 
-    #[test]
-    fn test_intel_get_memory_info_with_nonexistent_paths() {
-        let provider = IntelLinuxProvider::new();
-        let temp_dir = std::env::temp_dir();
-        let result = provider.get_memory_info(&temp_dir);
-        assert_eq!(result, (None, None));
-    }
-
     #[test]
     fn test_intel_read_hex_file_invalid_path() {
         let provider = IntelLinuxProvider::new();
         let result = provider.read_hex_file(Path::new("/nonexistent/path"));
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_is_card_node() {
+        for name in ["card0", "card1", "card12"] {
+            assert!(is_card_node(name), "{}", name);
+        }
+        for name in [
+            "card",
+            "card0-HDMI-A-1",
+            "card1-eDP-1",
+            "renderD128",
+            "version",
+            "cardX",
+        ] {
+            assert!(!is_card_node(name), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_only_card_nodes_are_probed() {
+        let drm = scratch_dir("nodes");
+        // Every entry looks like an AMD device, so only the filter keeps them apart
+        for node in ["card0", "card0-HDMI-A-1", "renderD128"] {
+            let device = drm.join(node).join("device");
+            fs::create_dir_all(&device).unwrap();
+            fs::write(device.join("vendor"), "0x1002\n").unwrap();
+            fs::write(device.join("product_name"), format!("{}\n", node)).unwrap();
+        }
+
+        assert_eq!(card_nodes(&drm).unwrap(), vec![drm.join("card0")]);
+        let gpus = AmdLinuxProvider::new()
+            .detect_amd_gpus_in(&drm, &CancelToken::new())
+            .unwrap();
+        assert_eq!(gpus.len(), 1);
+        assert_eq!(gpus[0].name_gpu.as_deref(), Some("card0"));
+        let _ = fs::remove_dir_all(&drm);
+    }
+
+    #[test]
+    fn test_card_nodes_are_ordered_by_index() {
+        let drm = scratch_dir("order");
+        for node in ["card10", "card2", "card2-DP-1"] {
+            fs::create_dir_all(drm.join(node)).unwrap();
+        }
+        assert_eq!(
+            card_nodes(&drm).unwrap(),
+            vec![drm.join("card2"), drm.join("card10")]
+        );
+        let _ = fs::remove_dir_all(&drm);
+    }
+
+    /// DRM tree with one `card0` of `vendor`, its runtime PM status and a temperature sensor
+    fn pm_card(name: &str, vendor: &str, runtime_status: &str) -> PathBuf {
+        let drm = scratch_dir(name);
+        let device = drm.join("card0").join("device");
+        fs::create_dir_all(device.join("power")).unwrap();
+        fs::create_dir_all(device.join("hwmon").join("hwmon0")).unwrap();
+        fs::write(device.join("vendor"), format!("{}\n", vendor)).unwrap();
+        fs::write(device.join("product_name"), "Hybrid dGPU\n").unwrap();
+        fs::write(device.join("power").join("runtime_status"), runtime_status).unwrap();
+        fs::write(device.join("hwmon/hwmon0/temp1_input"), "45000\n").unwrap();
+        drm
+    }
+
+    #[test]
+    fn test_runtime_active() {
+        let device = scratch_dir("runtime_pm");
+        assert_eq!(runtime_active(&device), None);
+        fs::write(device.join("power_state"), "D3cold\n").unwrap();
+        assert_eq!(runtime_active(&device), Some(false));
+        fs::write(device.join("power_state"), "D0\n").unwrap();
+        assert_eq!(runtime_active(&device), Some(true));
+        // runtime_status takes precedence over power_state
+        fs::create_dir_all(device.join("power")).unwrap();
+        fs::write(device.join("power/runtime_status"), "suspended\n").unwrap();
+        assert_eq!(runtime_active(&device), Some(false));
+        fs::write(device.join("power/runtime_status"), "active\n").unwrap();
+        assert_eq!(runtime_active(&device), Some(true));
+        fs::write(device.join("power/runtime_status"), "unsupported\n").unwrap();
+        assert_eq!(runtime_active(&device), Some(true));
+        let _ = fs::remove_dir_all(&device);
+    }
+
+    #[test]
+    fn test_suspended_amd_gpu_is_inactive() {
+        let drm = pm_card("amd_suspended", "0x1002", "suspended\n");
+        let gpus = AmdLinuxProvider::new()
+            .detect_amd_gpus_in(&drm, &CancelToken::new())
+            .unwrap();
+        assert_eq!(gpus[0].active, Some(false));
+        assert_eq!(gpus[0].name_gpu.as_deref(), Some("Hybrid dGPU"));
+        // Sensors are not read, as that would wake the GPU up
+        assert_eq!(gpus[0].temperature, None);
+
+        fs::write(drm.join("card0/device/power/runtime_status"), "active\n").unwrap();
+        let gpus = AmdLinuxProvider::new()
+            .detect_amd_gpus_in(&drm, &CancelToken::new())
+            .unwrap();
+        assert_eq!(gpus[0].active, Some(true));
+        assert_eq!(gpus[0].temperature, Some(45.0));
+        let _ = fs::remove_dir_all(&drm);
+    }
+
+    #[test]
+    fn test_amd_update_metrics_reads_only_requested() {
+        let drm = scratch_dir("amd_update_metrics");
+        let device = drm.join("0000:03:00.0");
+        fs::create_dir_all(device.join("power")).unwrap();
+        fs::create_dir_all(device.join("hwmon/hwmon0")).unwrap();
+        fs::write(device.join("power/runtime_status"), "active\n").unwrap();
+        fs::write(device.join("hwmon/hwmon0/temp1_input"), "45000\n").unwrap();
+        fs::write(device.join("mem_info_vram_total"), "12884901888\n").unwrap();
+        fs::write(device.join("mem_info_vram_used"), "3221225472\n").unwrap();
+        fs::create_dir_all(drm.join("card0")).unwrap();
+        std::os::unix::fs::symlink(&device, drm.join("card0/device")).unwrap();
+
+        let provider = AmdLinuxProvider::new();
+        let mut gpu = GpuInfo::builder()
+            .vendor(Vendor::Amd)
+            .bus_id("0000:03:00.0")
+            .temperature(80.0)
+            .memory_total(1)
+            .build();
+        provider
+            .update_metrics_in(&drm, &mut gpu, &[Metric::Temperature])
+            .unwrap();
+        assert_eq!(gpu.temperature, Some(45.0));
+        assert_eq!(gpu.memory_total, Some(1));
+
+        provider
+            .update_metrics_in(&drm, &mut gpu, &[Metric::MemoryUsed])
+            .unwrap();
+        assert_eq!(gpu.memory_total, Some(12288));
+        assert_eq!(gpu.memory_used, Some(3072));
+        assert_eq!(gpu.memory_util, Some(25.0));
+        let _ = fs::remove_dir_all(&drm);
+    }
+
+    #[test]
+    fn test_amd_hwmon_temperatures() {
+        let drm = pm_card("amd_sensors", "0x1002", "active\n");
+        let hwmon = drm.join("card0/device/hwmon/hwmon0");
+        fs::write(hwmon.join("temp1_label"), "edge\n").unwrap();
+        fs::write(hwmon.join("temp2_input"), "61000\n").unwrap();
+        fs::write(hwmon.join("temp2_label"), "junction\n").unwrap();
+        fs::write(hwmon.join("temp3_input"), "72000\n").unwrap();
+        let gpus = AmdLinuxProvider::new()
+            .detect_amd_gpus_in(&drm, &CancelToken::new())
+            .unwrap();
+        let sensors = gpus[0].temperatures().unwrap();
+        assert_eq!(sensors.len(), 3);
+        assert_eq!(sensors["junction"], 61.0);
+        // An unlabelled temp3 is the memory sensor
+        assert_eq!(sensors["memory"], 72.0);
+        // Several sensors still report the edge sensor as the temperature
+        assert_eq!(gpus[0].temperature(), Some(45.0));
+        let _ = fs::remove_dir_all(&drm);
+
+        assert_eq!(hwmon_temperatures(&scratch_dir("amd_no_sensors")), None);
+    }
+
+    #[test]
+    fn test_amd_drm_client_processes() {
+        let debugfs = scratch_dir("amd_debugfs");
+        let card = Path::new("/sys/class/drm/card1");
+        assert_eq!(drm_client_processes(&debugfs, card), None);
+
+        fs::create_dir_all(debugfs.join("1")).unwrap();
+        fs::write(
+            debugfs.join("1/clients"),
+            concat!(
+                "             command   tgid dev master a   uid      magic\n",
+                "                Xorg  1290   1   y    y     0          0\n",
+            ),
+        )
+        .unwrap();
+        let processes = drm_client_processes(&debugfs, card).unwrap();
+        assert_eq!(processes.len(), 1);
+        assert_eq!(processes[0].pid, 1290);
+        assert_eq!(processes[0].name().as_deref(), Some("Xorg"));
+        assert_eq!(
+            drm_client_processes(&debugfs, Path::new("/sys/class/drm/renderD128")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_amd_hwmon_fan() {
+        let drm = pm_card("amd_fan", "0x1002", "active\n");
+        let hwmon = drm.join("card0/device/hwmon/hwmon0");
+        fs::write(hwmon.join("fan1_input"), "1290\n").unwrap();
+        fs::write(hwmon.join("fan1_max"), "3000\n").unwrap();
+        let gpus = AmdLinuxProvider::new()
+            .detect_amd_gpus_in(&drm, &CancelToken::new())
+            .unwrap();
+        assert_eq!(gpus[0].fan_speed(), Some(43));
+        assert_eq!(gpus[0].fan_speed_rpm(), Some(1290));
+
+        // Without a maximum only the RPM is known
+        fs::remove_file(hwmon.join("fan1_max")).unwrap();
+        assert_eq!(hwmon_fan(&drm.join("card0/device")), (None, Some(1290)));
+        let _ = fs::remove_dir_all(&drm);
+
+        assert_eq!(hwmon_fan(&scratch_dir("amd_no_fan")), (None, None));
+    }
+
+    #[test]
+    fn test_amd_detection_stops_when_cancelled() {
+        let drm = pm_card("amd_cancelled", "0x1002", "active\n");
+        let token = CancelToken::new();
+        token.cancel();
+        let gpus = AmdLinuxProvider::new()
+            .detect_amd_gpus_in(&drm, &token)
+            .unwrap();
+        assert!(gpus.is_empty());
+        let _ = fs::remove_dir_all(&drm);
+    }
+
+    #[test]
+    fn test_suspended_intel_gpu_is_inactive() {
+        let drm = pm_card("intel_suspended", "0x8086", "suspended\n");
+        let gpus = IntelLinuxProvider::new()
+            .detect_intel_gpus_in(&drm)
+            .unwrap();
+        assert_eq!(gpus[0].active, Some(false));
+        let _ = fs::remove_dir_all(&drm);
+    }
+
+    #[test]
+    fn test_pci_vendor_id_detection_is_high_confidence() {
+        let drm = pm_card("amd_confidence", "0x1002", "active\n");
+        let gpus = AmdLinuxProvider::new()
+            .detect_amd_gpus_in(&drm, &CancelToken::new())
+            .unwrap();
+        assert_eq!(gpus[0].confidence, Confidence::High);
+        let _ = fs::remove_dir_all(&drm);
+
+        let drm = pm_card("intel_confidence", "0x8086", "suspended\n");
+        let gpus = IntelLinuxProvider::new()
+            .detect_intel_gpus_in(&drm)
+            .unwrap();
+        assert_eq!(gpus[0].confidence, Confidence::High);
+        let _ = fs::remove_dir_all(&drm);
+    }
+
+    /// Lists NVIDIA cards at `bus_ids`, each with a temperature of 40 °C plus its position
+    fn nvidia_cards(name: &str, bus_ids: &[&str]) -> PathBuf {
+        let root = scratch_dir(name);
+        for (card, bus_id) in bus_ids.iter().enumerate() {
+            let device = root.join("pci").join(bus_id);
+            fs::create_dir_all(device.join("power")).unwrap();
+            fs::create_dir_all(device.join("hwmon/hwmon0")).unwrap();
+            fs::write(device.join("vendor"), "0x10de\n").unwrap();
+            fs::write(device.join("power/runtime_status"), "active\n").unwrap();
+            fs::write(
+                device.join("hwmon/hwmon0/temp1_input"),
+                format!("{}\n", (40 + card) * 1000),
+            )
+            .unwrap();
+            let node = root.join("drm").join(format!("card{}", card));
+            fs::create_dir_all(&node).unwrap();
+            std::os::unix::fs::symlink(&device, node.join("device")).unwrap();
+        }
+        root
+    }
+
+    #[test]
+    fn test_nvidia_sysfs_reading() {
+        let root = nvidia_cards("nvidia_sysfs", &["0000:01:00.0", "0000:02:00.0"]);
+        let drm = root.join("drm");
+        fs::write(
+            drm.join("card1/device/hwmon/hwmon0/power1_average"),
+            "35500000\n",
+        )
+        .unwrap();
+        let module_version = root.join("version");
+        fs::write(&module_version, "550.54.14\n").unwrap();
+
+        // The second card is read, not the first one listed
+        let gpu = sysfs_reading(&drm, &module_version, "0000:02:00.0").unwrap();
+        assert_eq!(gpu.vendor, Vendor::Nvidia);
+        assert_eq!(gpu.bus_id.as_deref(), Some("0000:02:00.0"));
+        assert_eq!(gpu.temperature, Some(41.0));
+        assert_eq!(gpu.power_usage, Some(35.5));
+        assert_eq!(gpu.active, Some(true));
+        assert_eq!(gpu.driver_version.as_deref(), Some("550.54.14"));
+        assert_eq!(gpu.confidence, Confidence::High);
+        // Identity comes from NVML
+        assert_eq!(gpu.name_gpu, None);
+        let gpu = sysfs_reading(&drm, &module_version, "0000:01:00.0").unwrap();
+        assert_eq!(gpu.temperature, Some(40.0));
+        assert!(sysfs_reading(&drm, &module_version, "0000:03:00.0").is_none());
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_nvidia_sysfs_reading_skips_other_vendors() {
+        let root = nvidia_cards("nvidia_sysfs_amd", &["0000:01:00.0"]);
+        fs::write(root.join("pci/0000:01:00.0/vendor"), "0x1002\n").unwrap();
+        let drm = root.join("drm");
+        assert!(sysfs_reading(&drm, &root.join("version"), "0000:01:00.0").is_none());
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    const AER_CORRECTABLE: &str = "RxErr 0\nBadTLP 12\nBadDLLP 3\nRollover 0\nTimeout 1\n\
+                                   NonFatalErr 0\nCorrIntErr 0\nHeaderOF 0\nTOTAL_ERR_COR 16\n";
+
+    #[test]
+    fn test_parse_aer_counters() {
+        let counters = parse_aer_counters(AER_CORRECTABLE).unwrap();
+        assert_eq!(counters.total, 16);
+        assert_eq!(counters.by_type.len(), 8);
+        assert_eq!(counters.by_type[1], ("BadTLP".to_string(), 12));
+        assert_eq!(counters.by_type[4], ("Timeout".to_string(), 1));
+    }
+
+    #[test]
+    fn test_parse_aer_counters_without_total() {
+        let counters = parse_aer_counters("BadTLP 2\ngarbage\nBadDLLP x\nTimeout 5\n").unwrap();
+        assert_eq!(counters.total, 7);
+        assert_eq!(counters.by_type.len(), 2);
+        assert_eq!(parse_aer_counters(""), None);
+        assert_eq!(parse_aer_counters("not an aer file\n"), None);
+    }
+
+    #[test]
+    fn test_parse_link_speed_and_width() {
+        assert_eq!(parse_link_speed("2.5 GT/s PCIe\n"), Some(1));
+        assert_eq!(parse_link_speed("16.0 GT/s PCIe\n"), Some(4));
+        assert_eq!(parse_link_speed("32.0 GT/s PCIe\n"), Some(5));
+        assert_eq!(parse_link_speed("Unknown\n"), None);
+        assert_eq!(parse_link_width("16\n"), Some(16));
+        assert_eq!(parse_link_width("0\n"), None);
+        assert_eq!(parse_link_width(""), None);
+    }
+
+    #[test]
+    fn test_fill_link_from_sysfs() {
+        let pci = scratch_dir("pcie_link");
+        let device = pci.join("0000:03:00.0");
+        fs::create_dir_all(&device).unwrap();
+        fs::write(device.join("current_link_speed"), "16.0 GT/s PCIe\n").unwrap();
+        fs::write(device.join("current_link_width"), "8\n").unwrap();
+        fs::write(device.join("max_link_speed"), "16.0 GT/s PCIe\n").unwrap();
+        fs::write(device.join("max_link_width"), "16\n").unwrap();
+
+        let mut connection = ConnectionInfo::default();
+        fill_link(&mut connection, &pci, "0000:03:00.0");
+        assert_eq!(connection.pcie_generation, Some(4));
+        assert_eq!(connection.pcie_width, Some(8));
+        assert_eq!(connection.pcie_generation_max, Some(4));
+        assert_eq!(connection.pcie_width_max, Some(16));
+        assert_eq!(connection.pcie_link_degraded(), Some(true));
+
+        // A missing device keeps what was already known
+        let mut connection = ConnectionInfo {
+            pcie_generation: Some(3),
+            ..Default::default()
+        };
+        fill_link(&mut connection, &pci, "0000:04:00.0");
+        assert_eq!(connection.pcie_generation, Some(3));
+        assert_eq!(connection.pcie_width_max, None);
+        let _ = fs::remove_dir_all(&pci);
+    }
+
+    #[test]
+    fn test_fill_link_capped_by_upstream_port() {
+        let root = scratch_dir("pcie_link_port");
+        // A Gen4 x16 GPU in a Gen3 x8 slot, found below its upstream port
+        let port = root.join("0000:00:01.0");
+        let device = port.join("0000:01:00.0");
+        fs::create_dir_all(&device).unwrap();
+        fs::write(port.join("max_link_speed"), "8.0 GT/s PCIe\n").unwrap();
+        fs::write(port.join("max_link_width"), "8\n").unwrap();
+        fs::write(device.join("current_link_speed"), "2.5 GT/s PCIe\n").unwrap();
+        fs::write(device.join("current_link_width"), "8\n").unwrap();
+        fs::write(device.join("max_link_speed"), "16.0 GT/s PCIe\n").unwrap();
+        fs::write(device.join("max_link_width"), "16\n").unwrap();
+
+        let mut connection = ConnectionInfo::default();
+        fill_link(&mut connection, &port, "0000:01:00.0");
+        assert_eq!(connection.pcie_generation, Some(1));
+        assert_eq!(connection.pcie_generation_max, Some(3));
+        assert_eq!(connection.pcie_width_max, Some(8));
+        // Full slot width at an idle generation is not degraded
+        assert_eq!(connection.pcie_link_degraded(), Some(false));
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_aer_correctable_unreadable_is_none() {
+        let pci = scratch_dir("aer_unreadable");
+        assert_eq!(aer_correctable(&pci, "0000:01:00.0"), None);
+        // A directory in place of the file fails to read like a denied file
+        fs::create_dir_all(pci.join("0000:01:00.0/aer_dev_correctable")).unwrap();
+        assert_eq!(aer_correctable(&pci, "0000:01:00.0"), None);
+        let _ = fs::remove_dir_all(&pci);
+    }
+
+    #[test]
+    fn test_pcie_errors_keyed_by_bus_id() {
+        let root = scratch_dir("pcie_aer");
+        let pci = root.join("pci");
+        let first = pci.join("0000:01:00.0");
+        let second = pci.join("0000:03:00.0");
+        fs::create_dir_all(&first).unwrap();
+        fs::create_dir_all(&second).unwrap();
+        fs::write(first.join("vendor"), "0x10de\n").unwrap();
+        fs::write(second.join("vendor"), "0x1002\n").unwrap();
+        fs::write(second.join("aer_dev_correctable"), AER_CORRECTABLE).unwrap();
+        let drm = root.join("drm");
+        fs::create_dir_all(drm.join("card1")).unwrap();
+        std::os::unix::fs::symlink(&second, drm.join("card1/device")).unwrap();
+
+        assert_eq!(
+            bus_id(&drm.join("card1/device")).as_deref(),
+            Some("0000:03:00.0")
+        );
+        assert_eq!(bus_id(&root), None);
+        let mut connection = ConnectionInfo::default();
+        fill_errors(&mut connection, &pci, "0000:03:00.0");
+        assert_eq!(connection.bus_id.as_deref(), Some("0000:03:00.0"));
+        assert_eq!(connection.pcie_error_count(), Some(16));
+        // The NVIDIA card listed first has no AER support
+        let mut connection = ConnectionInfo::default();
+        fill_errors(&mut connection, &pci, "0000:01:00.0");
+        assert_eq!(connection.bus_id.as_deref(), Some("0000:01:00.0"));
+        assert_eq!(connection.pcie_error_count(), None);
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_parse_smartshift_percent() {
+        assert_eq!(parse_smartshift_percent("130%\n"), Some(130.0));
+        assert_eq!(parse_smartshift_percent("0%"), Some(0.0));
+        // The unit is part of the file; a bare number is not guessed at
+        assert_eq!(parse_smartshift_percent("45000\n"), None);
+        assert_eq!(parse_smartshift_percent("%"), None);
+        assert_eq!(parse_smartshift_percent(""), None);
+    }
+
+    #[test]
+    fn test_amd_smartshift() {
+        let device = scratch_dir("smartshift");
+        fs::write(device.join("smartshift_apu_power"), "130%\n").unwrap();
+        fs::write(device.join("smartshift_dgpu_power"), "0%\n").unwrap();
+        fs::write(device.join("smartshift_bias"), "-20\n").unwrap();
+        assert_eq!(
+            smartshift(&device),
+            Some(SmartShiftInfo {
+                apu_power_percent: Some(130.0),
+                dgpu_power_percent: Some(0.0),
+                bias: Some(-20),
+            })
+        );
+        let _ = fs::remove_dir_all(&device);
+    }
+
+    #[test]
+    fn test_amd_smartshift_without_bias() {
+        let device = scratch_dir("smartshift_without_bias");
+        fs::write(device.join("smartshift_apu_power"), "100%\n").unwrap();
+        fs::write(device.join("smartshift_dgpu_power"), "eh\n").unwrap();
+        // Older kernels have no smartshift_bias
+        assert_eq!(
+            smartshift(&device),
+            Some(SmartShiftInfo {
+                apu_power_percent: Some(100.0),
+                dgpu_power_percent: None,
+                bias: None,
+            })
+        );
+        let _ = fs::remove_dir_all(&device);
+    }
+
+    #[test]
+    fn test_amd_smartshift_absent_on_desktop_card() {
+        let drm = pm_card("smartshift_desktop", "0x1002", "active\n");
+        assert_eq!(smartshift(&drm.join("card0/device")), None);
+        // The bias alone is not a SmartShift reading
+        fs::write(drm.join("card0/device/smartshift_bias"), "0\n").unwrap();
+        assert_eq!(smartshift(&drm.join("card0/device")), None);
+        let _ = fs::remove_dir_all(&drm);
+    }
 }
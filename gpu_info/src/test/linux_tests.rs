@@ -18,7 +18,12 @@ mod tests {
         assert!(
             matches!(
                 vendor,
-                Vendor::Nvidia | Vendor::Amd | Vendor::Intel(_) | Vendor::Unknown
+                Vendor::Nvidia
+                    | Vendor::Amd
+                    | Vendor::Intel(_)
+                    | Vendor::Apple
+                    | Vendor::Virtual(_)
+                    | Vendor::Unknown
             ),
             "Expected valid vendor, got: {:?}",
             vendor
@@ -33,7 +38,12 @@ mod tests {
         assert!(
             matches!(
                 gpu.vendor,
-                Vendor::Nvidia | Vendor::Amd | Vendor::Intel(_) | Vendor::Unknown
+                Vendor::Nvidia
+                    | Vendor::Amd
+                    | Vendor::Intel(_)
+                    | Vendor::Apple
+                    | Vendor::Virtual(_)
+                    | Vendor::Unknown
             ),
             "Expected valid vendor, got: {:?}",
             gpu.vendor
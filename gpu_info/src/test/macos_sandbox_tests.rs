@@ -0,0 +1,181 @@
+//! Tests for App Sandbox handling in the macOS provider
+
+#[cfg(test)]
+mod tests {
+    use crate::detection_report::DetectionReport;
+    use crate::gpu_info::{GpuInfo, GpuProvider};
+    use crate::metric_source::Metric;
+    use crate::providers::macos::router::{BackendRouter, Operation};
+    use crate::providers::macos::sandbox::{
+        is_sandboxed, CommandRunner, Environment, SANDBOX_CONTAINER_ENV, SANDBOX_HINT,
+    };
+    use crate::providers::macos::{MacosBackend, MacosConfig, MacosProvider};
+    use std::io;
+    #[cfg(unix)]
+    use std::os::unix::process::ExitStatusExt;
+    #[cfg(windows)]
+    use std::os::windows::process::ExitStatusExt;
+    use std::process::{ExitStatus, Output};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Environment containing only the sandbox container id, if any, and
+    /// a fixed answer to the sandbox query
+    struct FakeEnvironment {
+        container_id: Option<&'static str>,
+        sandbox_check: bool,
+    }
+
+    impl Environment for FakeEnvironment {
+        fn var(&self, key: &str) -> Option<String> {
+            if key == SANDBOX_CONTAINER_ENV {
+                self.container_id.map(str::to_owned)
+            } else {
+                None
+            }
+        }
+
+        fn sandbox_check(&self) -> bool {
+            self.sandbox_check
+        }
+    }
+
+    const SANDBOXED: FakeEnvironment = FakeEnvironment {
+        container_id: Some("com.example.app"),
+        sandbox_check: false,
+    };
+    const UNSANDBOXED: FakeEnvironment = FakeEnvironment {
+        container_id: None,
+        sandbox_check: false,
+    };
+
+    /// Runner counting its calls and answering every spawn with `status`,
+    /// or failing it if there is none
+    #[derive(Default)]
+    struct CountingRunner {
+        calls: AtomicUsize,
+        status: Option<u8>,
+    }
+
+    impl CountingRunner {
+        fn succeeding() -> Self {
+            Self {
+                status: Some(0),
+                ..Self::default()
+            }
+        }
+
+        fn calls(&self) -> usize {
+            self.calls.load(Ordering::SeqCst)
+        }
+    }
+
+    impl CommandRunner for CountingRunner {
+        fn output(&self, _program: &str, _args: &[&str]) -> io::Result<Output> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            let code = self
+                .status
+                .ok_or_else(|| io::Error::new(io::ErrorKind::PermissionDenied, "sandboxed"))?;
+            Ok(Output {
+                status: ExitStatus::from_raw(code.into()),
+                stdout: b"/usr/bin/powermetrics\n".to_vec(),
+                stderr: Vec::new(),
+            })
+        }
+    }
+
+    /// Config pinned to the PowerMetrics backend, so the selection does not
+    /// depend on which native backends the host has
+    fn powermetrics_config() -> MacosConfig {
+        MacosConfig {
+            preferred_backend: MacosBackend::PowerMetrics,
+            fallback_enabled: false,
+            ..MacosConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_sandbox_detection() {
+        assert!(is_sandboxed(&SANDBOXED));
+        assert!(!is_sandboxed(&UNSANDBOXED));
+        assert!(!is_sandboxed(&FakeEnvironment {
+            container_id: Some(""),
+            sandbox_check: false,
+        }));
+        assert!(is_sandboxed(&FakeEnvironment {
+            container_id: None,
+            sandbox_check: true,
+        }));
+    }
+
+    #[test]
+    fn test_sandboxed_router_disables_subprocess_backends() {
+        let runner = CountingRunner::default();
+        let router = BackendRouter::with_environment(MacosConfig::default(), &SANDBOXED, &runner);
+
+        assert_eq!(runner.calls(), 0);
+        assert!(router.is_sandboxed());
+        for backend in [MacosBackend::SystemProfiler, MacosBackend::PowerMetrics] {
+            assert!(router.is_backend_present(backend), "{:?}", backend);
+            assert!(!router.is_backend_permitted(backend), "{:?}", backend);
+            assert!(!router.available_backends().contains(&backend));
+        }
+    }
+
+    #[test]
+    fn test_sandboxed_provider_reports_hint_without_spawning() {
+        let runner = CountingRunner::default();
+        let provider =
+            MacosProvider::with_environment(MacosConfig::default(), &SANDBOXED, &runner).unwrap();
+        assert!(provider.is_sandboxed());
+
+        let mut report = DetectionReport::new();
+        provider.detect_with_report(&mut report).unwrap();
+
+        assert_eq!(runner.calls(), 0);
+        assert!(report.hints().iter().any(|hint| hint == SANDBOX_HINT));
+    }
+
+    #[test]
+    fn test_sandboxed_provider_falls_back_to_nothing() {
+        let runner = CountingRunner::succeeding();
+        let provider =
+            MacosProvider::with_environment(powermetrics_config(), &SANDBOXED, &runner).unwrap();
+
+        assert_eq!(provider.detect_gpus().unwrap(), Vec::new());
+        let mut gpu = GpuInfo::unknown();
+        gpu.name_gpu = Some("Apple M2".to_string());
+        let before = gpu.clone();
+        provider.update_gpu(&mut gpu).unwrap();
+        assert_eq!(gpu, before);
+        for metric in Metric::ALL {
+            assert!(!provider.supports_metric(metric), "{:?}", metric);
+        }
+        assert_eq!(runner.calls(), 0);
+    }
+
+    #[test]
+    fn test_unsandboxed_router_probes_powermetrics() {
+        let runner = CountingRunner::default();
+        let router = BackendRouter::with_environment(MacosConfig::default(), &UNSANDBOXED, &runner);
+
+        assert_eq!(runner.calls(), 1);
+        assert!(!router.is_sandboxed());
+        // `which` failed, so powermetrics is treated as missing
+        assert!(!router.is_backend_present(MacosBackend::PowerMetrics));
+        assert!(router.is_backend_permitted(MacosBackend::SystemProfiler));
+        assert!(router.is_backend_available(MacosBackend::SystemProfiler));
+    }
+
+    #[test]
+    fn test_unsandboxed_router_finds_powermetrics() {
+        let runner = CountingRunner::succeeding();
+        let router = BackendRouter::with_environment(powermetrics_config(), &UNSANDBOXED, &runner);
+
+        assert_eq!(runner.calls(), 1);
+        assert!(router.is_backend_available(MacosBackend::PowerMetrics));
+        assert_eq!(
+            router.select_backend(Operation::GetDynamicMetrics),
+            MacosBackend::PowerMetrics
+        );
+    }
+}
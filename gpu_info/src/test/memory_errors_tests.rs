@@ -0,0 +1,205 @@
+//! Tests for retired page and remapped row counts and their alert
+
+#[cfg(test)]
+mod tests {
+    use crate::extended_info::{MemoryErrorCounts, MemoryErrorSource};
+    use crate::gpu_info::GpuInfo;
+    use crate::monitoring::{AlertType, GpuThresholds, MemoryErrorTracker};
+    use crate::nvml_api::{
+        remapped_rows_from, retired_pages_from, NVML_ERROR_INSUFFICIENT_SIZE, NVML_SUCCESS,
+    };
+    use crate::vendor::Vendor;
+    use std::cell::Cell;
+
+    const NVML_ERROR_NOT_SUPPORTED: i32 = 3;
+
+    /// Mock NVML client replaying one retired page count per refresh
+    struct MockClient {
+        refreshes: Vec<Option<u64>>,
+        next: Cell<usize>,
+    }
+
+    impl MockClient {
+        fn new(refreshes: &[Option<u64>]) -> Self {
+            Self {
+                refreshes: refreshes.to_vec(),
+                next: Cell::new(0),
+            }
+        }
+    }
+
+    impl MemoryErrorSource for MockClient {
+        fn memory_errors(&self, gpu_index: u32) -> Option<MemoryErrorCounts> {
+            if gpu_index != 0 {
+                return None;
+            }
+            let refresh = self.next.replace(self.next.get() + 1);
+            Some(MemoryErrorCounts {
+                retired_pages: *self.refreshes.get(refresh)?,
+                ..Default::default()
+            })
+        }
+    }
+
+    fn retired(pages: u64) -> MemoryErrorCounts {
+        MemoryErrorCounts {
+            retired_pages: Some(pages),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_retired_pages_sums_causes() {
+        let pages = retired_pages_from(|cause, count| {
+            *count = if cause == 0 { 3 } else { 2 };
+            NVML_ERROR_INSUFFICIENT_SIZE
+        });
+        assert_eq!(pages, Some(5));
+        assert_eq!(retired_pages_from(|_, _| NVML_SUCCESS), Some(0));
+        let unsupported = retired_pages_from(|cause, count| {
+            *count = 4;
+            if cause == 0 {
+                NVML_SUCCESS
+            } else {
+                NVML_ERROR_NOT_SUPPORTED
+            }
+        });
+        assert_eq!(unsupported, None);
+    }
+
+    #[test]
+    fn test_remapped_rows() {
+        let rows = remapped_rows_from(|corrected, uncorrected, pending, _| {
+            *corrected = 2;
+            *uncorrected = 1;
+            *pending = 1;
+            NVML_SUCCESS
+        });
+        assert_eq!(rows, Some((3, true)));
+        assert_eq!(
+            remapped_rows_from(|_, _, _, _| NVML_SUCCESS),
+            Some((0, false))
+        );
+        assert_eq!(
+            remapped_rows_from(|_, _, _, _| NVML_ERROR_NOT_SUPPORTED),
+            None
+        );
+    }
+
+    #[test]
+    fn test_memory_error_total() {
+        assert_eq!(MemoryErrorCounts::default().total(), None);
+        assert_eq!(retired(4).total(), Some(4));
+        let both = MemoryErrorCounts {
+            retired_pages: Some(4),
+            remapped_rows: Some(2),
+            pending_remapping: Some(true),
+            whea_errors: None,
+        };
+        assert_eq!(both.total(), Some(6));
+        let whea = MemoryErrorCounts {
+            whea_errors: Some(3),
+            ..Default::default()
+        };
+        assert_eq!(whea.total(), Some(3));
+        assert_eq!(
+            MemoryErrorCounts {
+                whea_errors: Some(1),
+                ..both
+            }
+            .total(),
+            Some(7)
+        );
+    }
+
+    /// A growing count raises one alert per increase, none while it holds
+    #[test]
+    fn test_growing_retired_pages_alert_once_per_increase() {
+        let client = MockClient::new(&[Some(2), Some(2), Some(3), Some(3), None, Some(5)]);
+        let mut tracker = MemoryErrorTracker::new();
+        let alerts: Vec<Vec<AlertType>> =
+            (0..6).map(|_| tracker.observe_source(&client, 2)).collect();
+        assert_eq!(
+            alerts,
+            vec![
+                vec![],
+                vec![],
+                vec![AlertType::MemoryPageRetired {
+                    gpu_index: 0,
+                    total: 3,
+                    previous: 2,
+                }],
+                vec![],
+                vec![],
+                vec![AlertType::MemoryPageRetired {
+                    gpu_index: 0,
+                    total: 5,
+                    previous: 3,
+                }],
+            ]
+        );
+    }
+
+    /// A count that drops must pass its previous high before alerting again
+    #[test]
+    fn test_retired_pages_hysteresis() {
+        let mut tracker = MemoryErrorTracker::new();
+        assert_eq!(tracker.observe(1, &retired(10)), None);
+        assert_eq!(tracker.observe(1, &retired(4)), None);
+        assert_eq!(tracker.observe(1, &retired(10)), None);
+        assert_eq!(
+            tracker.observe(1, &retired(11)),
+            Some(AlertType::MemoryPageRetired {
+                gpu_index: 1,
+                total: 11,
+                previous: 10,
+            })
+        );
+        assert_eq!(tracker.observe(1, &MemoryErrorCounts::default()), None);
+        assert_eq!(tracker.observe(0, &retired(50)), None);
+    }
+
+    /// Counts read per sample follow each GPU's bus id, and only when enabled
+    #[test]
+    fn test_memory_errors_observe_gpus() {
+        let gpu = |bus_id: &str| GpuInfo {
+            vendor: Vendor::Nvidia,
+            bus_id: Some(bus_id.to_string()),
+            ..GpuInfo::unknown()
+        };
+        let counts = |totals: [(&'static str, u64); 2]| {
+            move |gpu: &GpuInfo| {
+                totals
+                    .iter()
+                    .find(|(bus_id, _)| gpu.bus_id.as_deref() == Some(bus_id))
+                    .map(|&(_, pages)| retired(pages))
+            }
+        };
+        let gpus = [gpu("0000:01:00.0"), gpu("0000:02:00.0")];
+        let mut tracker = MemoryErrorTracker::new();
+        let unread = |_: &GpuInfo| -> Option<MemoryErrorCounts> {
+            panic!("memory errors read while disabled")
+        };
+        assert!(tracker
+            .observe_gpus(&gpus, unread, &GpuThresholds::default())
+            .is_empty());
+
+        let thresholds = GpuThresholds {
+            memory_error_alerts: true,
+            ..Default::default()
+        };
+        let first = counts([("0000:01:00.0", 1), ("0000:02:00.0", 8)]);
+        assert!(tracker.observe_gpus(&gpus, first, &thresholds).is_empty());
+        // The second GPU is now listed first and retired another page
+        let swapped = [gpu("0000:02:00.0"), gpu("0000:01:00.0")];
+        let second = counts([("0000:01:00.0", 1), ("0000:02:00.0", 9)]);
+        assert_eq!(
+            tracker.observe_gpus(&swapped, second, &thresholds),
+            vec![AlertType::MemoryPageRetired {
+                gpu_index: 0,
+                total: 9,
+                previous: 8,
+            }]
+        );
+    }
+}
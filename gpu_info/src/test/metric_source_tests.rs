@@ -0,0 +1,302 @@
+//! Tests for per-field metric source priority
+
+#[cfg(test)]
+mod tests {
+    use crate::gpu_info::GpuInfo;
+    use crate::metric_source::{
+        apply, compose, priority, resolve, source_order, Metric, MetricSource, PriorityTable,
+    };
+    use crate::vendor::{IntelGpuType, Vendor};
+
+    const TABLE: &PriorityTable = &[
+        (
+            Metric::Utilization,
+            &[MetricSource::Pdh, MetricSource::IntelMdApi],
+        ),
+        (
+            Metric::Temperature,
+            &[MetricSource::IntelMdApi, MetricSource::Wmi],
+        ),
+        (Metric::MemoryTotal, &[MetricSource::Wmi]),
+    ];
+
+    fn reading(temperature: Option<f32>, utilization: Option<f32>) -> GpuInfo {
+        GpuInfo {
+            temperature,
+            utilization,
+            ..GpuInfo::unknown()
+        }
+    }
+
+    #[test]
+    fn test_resolve_prefers_higher_priority_regardless_of_order() {
+        let order = [MetricSource::Pdh, MetricSource::IntelMdApi];
+        let forward = resolve(
+            Metric::Utilization,
+            &order,
+            [
+                (MetricSource::Pdh, Some(20.0)),
+                (MetricSource::IntelMdApi, Some(80.0)),
+            ],
+        );
+        let backward = resolve(
+            Metric::Utilization,
+            &order,
+            [
+                (MetricSource::IntelMdApi, Some(80.0)),
+                (MetricSource::Pdh, Some(20.0)),
+            ],
+        );
+        assert_eq!(forward, Some((20.0, MetricSource::Pdh)));
+        assert_eq!(backward, forward);
+    }
+
+    #[test]
+    fn test_resolve_falls_back_and_ignores_unranked_sources() {
+        let order = [MetricSource::Pdh, MetricSource::IntelMdApi];
+        assert_eq!(
+            resolve(
+                Metric::Utilization,
+                &order,
+                [
+                    (MetricSource::Pdh, None),
+                    (MetricSource::Wmi, Some(99.0)),
+                    (MetricSource::IntelMdApi, Some(80.0)),
+                ],
+            ),
+            Some((80.0, MetricSource::IntelMdApi))
+        );
+        assert_eq!(
+            resolve(
+                Metric::Utilization,
+                &order,
+                [(MetricSource::Wmi, Some(99.0))]
+            ),
+            None
+        );
+        assert_eq!(resolve::<f32>(Metric::Utilization, &[], []), None);
+    }
+
+    #[test]
+    fn test_priority_lookup() {
+        assert_eq!(
+            priority(TABLE, Metric::Temperature),
+            &[MetricSource::IntelMdApi, MetricSource::Wmi]
+        );
+        assert!(priority(TABLE, Metric::PowerUsage).is_empty());
+    }
+
+    #[test]
+    fn test_apply_respects_priority_per_field() {
+        let wmi = GpuInfo {
+            memory_total: Some(4096),
+            ..reading(Some(50.0), Some(90.0))
+        };
+        let md_api = reading(Some(61.5), Some(70.0));
+        let pdh = reading(Some(10.0), Some(35.0));
+
+        let mut gpu = GpuInfo {
+            power_usage: Some(12.0),
+            ..GpuInfo::unknown()
+        };
+        apply(
+            &mut gpu,
+            TABLE,
+            &[
+                (MetricSource::Wmi, &wmi),
+                (MetricSource::IntelMdApi, &md_api),
+                (MetricSource::Pdh, &pdh),
+            ],
+        );
+
+        assert_eq!(gpu.utilization, Some(35.0));
+        assert_eq!(gpu.temperature, Some(61.5));
+        assert_eq!(gpu.memory_total, Some(4096));
+        // Fields outside the table are left untouched
+        assert_eq!(gpu.power_usage, Some(12.0));
+    }
+
+    #[test]
+    fn test_apply_keeps_value_when_no_source_reports() {
+        let mut gpu = reading(Some(55.0), None);
+        apply(
+            &mut gpu,
+            TABLE,
+            &[(MetricSource::IntelMdApi, &GpuInfo::unknown())],
+        );
+        assert_eq!(gpu.temperature, Some(55.0));
+        assert_eq!(gpu.utilization, None);
+    }
+
+    #[test]
+    fn test_winning_sources_recorded_with_debug_capture() {
+        let mut gpu = GpuInfo::unknown();
+        apply(
+            &mut gpu,
+            TABLE,
+            &[
+                (MetricSource::Wmi, &reading(Some(50.0), None)),
+                (MetricSource::Pdh, &reading(None, Some(35.0))),
+            ],
+        );
+        if cfg!(feature = "debug-capture") {
+            assert_eq!(
+                gpu.metric_sources.get(Metric::Temperature),
+                Some(MetricSource::Wmi)
+            );
+            assert_eq!(
+                gpu.metric_sources.get(Metric::Utilization),
+                Some(MetricSource::Pdh)
+            );
+            assert_eq!(gpu.metric_sources.get(Metric::MemoryTotal), None);
+        } else {
+            assert!(gpu.metric_sources.is_empty());
+        }
+    }
+
+    #[cfg(feature = "vendor-apple")]
+    #[test]
+    fn test_macos_priority_prefers_metal_over_powermetrics() {
+        use crate::providers::macos::provider::METRIC_PRIORITY;
+
+        let metal = reading(None, Some(42.0));
+        let powermetrics = GpuInfo {
+            power_usage: Some(8.5),
+            ..reading(Some(48.0), Some(60.0))
+        };
+        let mut gpu = GpuInfo::unknown();
+        apply(
+            &mut gpu,
+            METRIC_PRIORITY,
+            &[
+                (MetricSource::PowerMetrics, &powermetrics),
+                (MetricSource::Metal, &metal),
+            ],
+        );
+
+        assert_eq!(gpu.utilization, Some(42.0));
+        assert_eq!(gpu.temperature, Some(48.0));
+        assert_eq!(gpu.power_usage, Some(8.5));
+    }
+
+    #[test]
+    fn test_merge_fills_only_unset_fields() {
+        let mut gpu = GpuInfo {
+            name_gpu: Some("NVIDIA GeForce RTX 4070".to_string()),
+            ..reading(Some(61.0), None)
+        };
+        let fallback = GpuInfo {
+            vendor: Vendor::Nvidia,
+            name_gpu: Some("NVIDIA Corporation AD104".to_string()),
+            driver_version: Some("550.54.14".to_string()),
+            ..reading(Some(70.0), Some(12.0))
+        };
+        gpu.merge(&fallback);
+
+        assert_eq!(gpu.vendor, Vendor::Nvidia);
+        assert_eq!(gpu.name_gpu.as_deref(), Some("NVIDIA GeForce RTX 4070"));
+        assert_eq!(gpu.temperature, Some(61.0));
+        assert_eq!(gpu.utilization, Some(12.0));
+        assert_eq!(gpu.driver_version.as_deref(), Some("550.54.14"));
+    }
+
+    #[test]
+    fn test_source_order_per_vendor() {
+        assert_eq!(source_order(Vendor::Nvidia)[0], MetricSource::Nvml);
+        assert_eq!(source_order(Vendor::Amd)[0], MetricSource::Adl);
+        assert_eq!(
+            source_order(Vendor::Intel(IntelGpuType::Integrated))[0],
+            MetricSource::IntelMdApi
+        );
+        assert_eq!(source_order(Vendor::Apple)[0], MetricSource::Metal);
+        assert!(source_order(Vendor::Unknown).contains(&MetricSource::Sysfs));
+    }
+
+    #[test]
+    fn test_compose_nvml_with_sysfs_fallback() {
+        let nvml = GpuInfo {
+            vendor: Vendor::Nvidia,
+            name_gpu: Some("NVIDIA GeForce RTX 4070".to_string()),
+            utilization: Some(37.0),
+            power_usage: Some(142.5),
+            memory_total: Some(12282),
+            active: Some(true),
+            ..GpuInfo::unknown()
+        };
+        let sysfs = GpuInfo {
+            vendor: Vendor::Nvidia,
+            temperature: Some(54.0),
+            power_usage: Some(120.0),
+            active: Some(false),
+            driver_version: Some("550.54.14".to_string()),
+            ..GpuInfo::unknown()
+        };
+        let gpu = compose(
+            Vendor::Nvidia,
+            &[(MetricSource::Sysfs, &sysfs), (MetricSource::Nvml, &nvml)],
+        );
+
+        assert_eq!(gpu.vendor, Vendor::Nvidia);
+        assert_eq!(gpu.name_gpu.as_deref(), Some("NVIDIA GeForce RTX 4070"));
+        // NVML wins where both report a value
+        assert_eq!(gpu.power_usage, Some(142.5));
+        assert_eq!(gpu.active, Some(true));
+        // sysfs fills the gaps
+        assert_eq!(gpu.temperature, Some(54.0));
+        assert_eq!(gpu.driver_version.as_deref(), Some("550.54.14"));
+        if cfg!(feature = "debug-capture") {
+            assert_eq!(
+                gpu.metric_sources.get(Metric::Temperature),
+                Some(MetricSource::Sysfs)
+            );
+            assert_eq!(
+                gpu.metric_sources.get(Metric::PowerUsage),
+                Some(MetricSource::Nvml)
+            );
+        }
+    }
+
+    #[test]
+    fn test_compose_ignores_sources_outside_vendor_order() {
+        let metal = reading(Some(48.0), Some(20.0));
+        let gpu = compose(Vendor::Nvidia, &[(MetricSource::Metal, &metal)]);
+        assert_eq!(gpu.vendor, Vendor::Nvidia);
+        assert_eq!(gpu.temperature, None);
+        assert_eq!(gpu.utilization, None);
+    }
+
+    #[cfg(all(target_os = "windows", feature = "vendor-intel"))]
+    #[test]
+    fn test_windows_intel_priority_prefers_pdh_utilization() {
+        use crate::providers::windows::intel::METRIC_PRIORITY;
+
+        let wmi = GpuInfo {
+            memory_total: Some(2048),
+            ..GpuInfo::unknown()
+        };
+        let md_api = GpuInfo {
+            max_clock_speed: Some(1450),
+            ..reading(Some(58.0), Some(90.0))
+        };
+        let pdh = GpuInfo {
+            memory_used: Some(512),
+            ..reading(None, Some(25.0))
+        };
+        let mut gpu = GpuInfo::unknown();
+        apply(
+            &mut gpu,
+            METRIC_PRIORITY,
+            &[
+                (MetricSource::Wmi, &wmi),
+                (MetricSource::IntelMdApi, &md_api),
+                (MetricSource::Pdh, &pdh),
+            ],
+        );
+
+        assert_eq!(gpu.utilization, Some(25.0));
+        assert_eq!(gpu.temperature, Some(58.0));
+        assert_eq!(gpu.max_clock_speed, Some(1450));
+        assert_eq!(gpu.memory_used, Some(512));
+        assert_eq!(gpu.memory_total, Some(2048));
+    }
+}
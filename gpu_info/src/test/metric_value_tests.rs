@@ -108,3 +108,74 @@ fn test_copy() {
     assert_eq!(original, copied);
     assert_eq!(copied.value(), Some(&42));
 }
+
+#[test]
+fn test_with_kind_unit() {
+    use crate::metric_source::Metric;
+
+    let power = MetricValue::Available(120.5).with_kind(Metric::PowerUsage);
+    assert_eq!(power.kind, Metric::PowerUsage);
+    assert_eq!(power.unit(), "watts");
+    assert_eq!(power.value, MetricValue::Available(120.5));
+}
+
+#[cfg(feature = "serde_json")]
+mod typed_serde {
+    use crate::metric_source::Metric;
+    use crate::metric_value::{MetricValue, TypedMetricValue};
+    use serde_json::json;
+
+    fn round_trip(metric: TypedMetricValue<f32>, expected: serde_json::Value) {
+        let serialized = serde_json::to_value(metric).unwrap();
+        assert_eq!(serialized, expected);
+        let parsed: TypedMetricValue<f32> = serde_json::from_value(serialized).unwrap();
+        assert_eq!(parsed, metric);
+    }
+
+    #[test]
+    fn test_available_serialized_form() {
+        round_trip(
+            MetricValue::Available(65.0).with_kind(Metric::Temperature),
+            json!({"value": 65.0, "unit": "celsius", "kind": "temperature"}),
+        );
+        round_trip(
+            MetricValue::Available(1800.0).with_kind(Metric::CoreClock),
+            json!({"value": 1800.0, "unit": "megahertz", "kind": "core_clock"}),
+        );
+    }
+
+    #[test]
+    fn test_unavailable_serialized_form() {
+        round_trip(
+            MetricValue::Unavailable.with_kind(Metric::Utilization),
+            json!({"value": null, "unit": "percent", "kind": "utilization", "status": "unavailable"}),
+        );
+    }
+
+    #[test]
+    fn test_not_supported_serialized_form() {
+        round_trip(
+            MetricValue::NotSupported.with_kind(Metric::PowerUsage),
+            json!({"value": null, "unit": "watts", "kind": "power_usage", "status": "not_supported"}),
+        );
+    }
+
+    #[test]
+    fn test_deserialize_rejects_inconsistent_input() {
+        let parse = |value| serde_json::from_value::<TypedMetricValue<f32>>(value);
+        assert!(parse(json!({"value": 65.0, "unit": "watts", "kind": "temperature"})).is_err());
+        assert!(parse(json!({"value": 65.0, "unit": "celsius", "kind": "warp_drive"})).is_err());
+        assert!(parse(json!({
+            "value": 65.0,
+            "unit": "celsius",
+            "kind": "temperature",
+            "status": "not_supported"
+        }))
+        .is_err());
+        // A missing status with a null value means unavailable
+        assert_eq!(
+            parse(json!({"value": null, "unit": "megabytes", "kind": "memory_used"})).unwrap(),
+            MetricValue::Unavailable.with_kind(Metric::MemoryUsed)
+        );
+    }
+}
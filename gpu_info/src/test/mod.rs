@@ -1,17 +1,69 @@
+#[cfg(feature = "vendor-amd")]
 mod amd_provider_tests;
 mod async_api_tests;
 mod cache_edge_cases;
 mod cache_tests;
+mod cancel_tests;
+mod consistency_tests;
 mod extended_info_tests;
 mod ffi_utils_tests;
 mod format_methods_tests;
+mod gpu_identity_tests;
+mod gpu_index_tests;
 mod gpu_info_builder_tests;
 mod gpu_manager_tests;
+mod jsonl_tests;
+#[cfg(all(
+    feature = "vendor-nvidia",
+    feature = "vendor-amd",
+    feature = "vendor-intel"
+))]
 mod linux_providers_tests;
 mod linux_tests;
+#[cfg(feature = "vendor-apple")]
+mod macos_sandbox_tests;
+#[cfg(feature = "vendor-nvidia")]
+mod memory_errors_tests;
+mod metric_source_tests;
 mod metric_value_tests;
 mod monitoring_tests;
+mod no_panic_tests;
+#[cfg(feature = "vendor-nvidia")]
+mod nvidia_persistence_tests;
+mod nvml_wrapper_tests;
+mod persistent_cache_tests;
+mod process_usage_tests;
 mod property_tests;
 mod provider_manager_tests;
+mod query_tests;
+mod redaction_tests;
+mod sampler_tests;
+mod sorting_tests;
+#[cfg(all(
+    feature = "vendor-amd",
+    feature = "vendor-intel",
+    feature = "vendor-apple"
+))]
+mod supports_metric_tests;
 #[allow(clippy::module_inception)]
 mod test;
+mod unknown_gpu_tests;
+mod vendor_tests;
+mod wmi_tests;
+
+use std::path::PathBuf;
+
+/// Creates an empty directory for the fixtures of the test `name`
+///
+/// The directory lives in the system temp dir and is named after `name`,
+/// which must be unique across the test modules, and the process id, so
+/// concurrent test runs do not share it. A directory an earlier run left
+/// behind is removed first.
+// Unused when the test modules that need fixtures are disabled by features
+#[allow(dead_code)]
+pub(crate) fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("gpu_info_{}_{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
@@ -13,11 +13,18 @@
 //!
 #[cfg(test)]
 mod tests {
+    use crate::gpu_info::{ClockThrottleReason, EccStats, GpuInfo, ThrottleReasons};
+    use crate::gpu_manager::GpuManager;
+    use crate::metric_source::Metric;
     use crate::monitoring::{
-        AlertHandler, AlertType, GpuMonitor, GpuThresholds, LogAlertHandler, MonitorConfig,
+        AdaptiveInterval, AdaptiveIntervalState, AlertHandler, AlertHysteresis, AlertType,
+        EccTracker, GpuMonitor, GpuThresholds, LogAlertHandler, MonitorConfig, PcieErrorTracker,
+        ThreadPriority, ThrottleTracker,
     };
+    use crate::vendor::Vendor;
+    use std::sync::atomic::{AtomicUsize, Ordering};
     use std::sync::{Arc, Mutex};
-    use std::time::Duration;
+    use std::time::{Duration, Instant};
     use tokio::time::sleep;
     #[derive(Debug)]
     struct MockAlertHandler {
@@ -77,14 +84,24 @@ mod tests {
                 temperature_critical: 80.0,
                 memory_warning: 70.0,
                 memory_critical: 90.0,
+                memory_high: 85.0,
                 power_warning: 200.0,
                 power_critical: 250.0,
                 utilization_warning: 90.0,
                 fan_speed_min: 20.0,
+                clock_deficit_warning: 10.0,
+                clock_deficit_samples: 3,
+                pcie_errors_per_minute: 5.0,
+                ecc_uncorrected_max: Some(10),
+                memory_error_alerts: true,
+                hysteresis: 1.0,
+                max_metric_age: None,
             },
             enable_alerts: true,
             log_metrics: false,
             save_to_file: None,
+            thread_priority: ThreadPriority::Low,
+            adaptive_interval: Some(AdaptiveInterval::default()),
         };
         let monitor = GpuMonitor::new(config.clone());
         assert!(!monitor.is_monitoring());
@@ -112,10 +129,12 @@ mod tests {
         assert_eq!(thresholds.temperature_critical, 85.0);
         assert_eq!(thresholds.memory_warning, 80.0);
         assert_eq!(thresholds.memory_critical, 95.0);
+        assert_eq!(thresholds.memory_high, 90.0);
         assert_eq!(thresholds.power_warning, 250.0);
         assert_eq!(thresholds.power_critical, 300.0);
         assert_eq!(thresholds.utilization_warning, 95.0);
         assert_eq!(thresholds.fan_speed_min, 10.0);
+        assert_eq!(thresholds.pcie_errors_per_minute, 10.0);
         println!("Default thresholds verified");
     }
 
@@ -141,6 +160,12 @@ mod tests {
                 gpu_index: 1,
                 usage: 98.0,
             },
+            AlertType::MemoryHigh {
+                gpu_index: 1,
+                used_mb: 7500,
+                total_mb: 8192,
+                usage: 91.5,
+            },
             AlertType::HighPowerUsage {
                 gpu_index: 0,
                 power: 280.0,
@@ -157,6 +182,30 @@ mod tests {
                 gpu_index: 0,
                 fan_speed: 5.0,
             },
+            AlertType::PcieErrorsIncreasing {
+                gpu_index: 0,
+                errors_per_minute: 42.0,
+                total: 1200,
+            },
+            AlertType::MemoryPageRetired {
+                gpu_index: 0,
+                total: 3,
+                previous: 2,
+            },
+            AlertType::ClockThrottle {
+                gpu_index: 0,
+                reasons: ClockThrottleReason::HwThermalSlowdown.into(),
+            },
+            AlertType::EccUncorrected {
+                gpu_index: 0,
+                errors: 4,
+                max: 0,
+            },
+            AlertType::Recovered {
+                gpu_index: 0,
+                metric: Metric::Temperature,
+                value: 70.0,
+            },
             AlertType::GpuInactive { gpu_index: 2 },
             AlertType::CollectionError {
                 gpu_index: 0,
@@ -494,4 +543,567 @@ mod tests {
         assert!(final_stats.start_time.is_some());
         println!("Full monitoring workflow test completed successfully");
     }
+
+    /// Test that memory alerts fire when only used/total memory is reported
+    #[test]
+    fn test_memory_alert_from_used_and_total() {
+        let gpu = GpuInfo::builder()
+            .memory_total(8192)
+            .memory_used(7900)
+            .build();
+        assert_eq!(gpu.memory_util, None);
+        let alerts = GpuMonitor::collect_alerts(&[gpu], &GpuThresholds::default());
+        assert!(alerts
+            .iter()
+            .any(|alert| matches!(alert, AlertType::CriticalMemoryUsage { gpu_index: 0, .. })));
+        assert!(!alerts
+            .iter()
+            .any(|alert| matches!(alert, AlertType::MemoryHigh { .. })));
+    }
+
+    /// Test that `MemoryHigh` reports used/total next to a low `memory_util`
+    #[test]
+    fn test_memory_high_alongside_memory_util() {
+        let gpu = GpuInfo::builder()
+            .memory_util(40.0)
+            .memory_total(8192)
+            .memory_used(7900)
+            .build();
+        let alerts = GpuMonitor::collect_alerts(&[gpu], &GpuThresholds::default());
+        assert_eq!(alerts.len(), 1);
+        assert!(matches!(
+            alerts[0],
+            AlertType::MemoryHigh {
+                gpu_index: 0,
+                used_mb: 7900,
+                total_mb: 8192,
+                ..
+            }
+        ));
+    }
+
+    static HOT_READS: AtomicUsize = AtomicUsize::new(0);
+
+    /// Backend that stops returning the temperature after the first sample
+    fn hot_once(gpu: &mut GpuInfo) -> crate::Result<()> {
+        let first = HOT_READS.fetch_add(1, Ordering::SeqCst) == 0;
+        gpu.temperature = first.then_some(95.0);
+        gpu.utilization = Some(99.0);
+        Ok(())
+    }
+
+    /// Test that alerts ignore metrics older than `max_metric_age`
+    #[test]
+    fn test_stale_metrics_do_not_alert() {
+        let mut manager = GpuManager::with_updater(
+            vec![GpuInfo::mock_nvidia()],
+            hot_once,
+            Duration::from_secs(60),
+        );
+        manager.set_metric_age_tracking(true);
+        manager.refresh_all_gpus().unwrap();
+        std::thread::sleep(Duration::from_millis(200));
+        manager.refresh_all_gpus().unwrap();
+        let gpus = manager.get_all_gpus_owned();
+        assert_eq!(gpus[0].temperature, Some(95.0));
+
+        let is_temperature = |alert: &AlertType| {
+            matches!(alert, AlertType::CriticalTemperature { gpu_index: 0, .. })
+        };
+        let alerts = GpuMonitor::collect_alerts(&gpus, &GpuThresholds::default());
+        assert!(alerts.iter().any(is_temperature));
+
+        let thresholds = GpuThresholds {
+            max_metric_age: Some(Duration::from_millis(100)),
+            ..GpuThresholds::default()
+        };
+        let alerts = GpuMonitor::collect_alerts(&gpus, &thresholds);
+        assert!(!alerts.iter().any(is_temperature), "stale: {:?}", alerts);
+        assert!(alerts
+            .iter()
+            .any(|alert| matches!(alert, AlertType::HighUtilization { gpu_index: 0, .. })));
+    }
+
+    /// Test that clock throttling alerts when it starts, but an idle GPU does not
+    #[test]
+    fn test_clock_throttle_alert() {
+        let thresholds = GpuThresholds::default();
+        let with_reasons = |reasons: ThrottleReasons| {
+            let mut gpu = GpuInfo::mock_nvidia();
+            gpu.throttle_reasons = Some(reasons);
+            gpu
+        };
+        let thermal = with_reasons(ClockThrottleReason::SwThermalSlowdown.into());
+        let idle = with_reasons(ClockThrottleReason::GpuIdle.into());
+        let mut tracker = ThrottleTracker::new();
+        assert_eq!(
+            tracker.observe(std::slice::from_ref(&thermal), &thresholds),
+            vec![AlertType::ClockThrottle {
+                gpu_index: 0,
+                reasons: ThrottleReasons::from_bits(0x20),
+            }]
+        );
+        // Throttling that continues does not alert again
+        assert!(tracker
+            .observe(std::slice::from_ref(&thermal), &thresholds)
+            .is_empty());
+        assert!(tracker.observe(&[idle], &thresholds).is_empty());
+        assert_eq!(tracker.observe(&[thermal], &thresholds).len(), 1);
+        assert!(
+            GpuMonitor::collect_alerts(&[GpuInfo::mock_nvidia()], &thresholds)
+                .iter()
+                .all(|alert| !matches!(alert, AlertType::ClockThrottle { .. }))
+        );
+    }
+
+    /// Test that configured clock limits are not reported as throttling
+    #[test]
+    fn test_clock_settings_are_not_throttling() {
+        let settings: ThrottleReasons = [
+            ClockThrottleReason::GpuIdle,
+            ClockThrottleReason::ApplicationClockSetting,
+            ClockThrottleReason::DisplayClockSetting,
+        ]
+        .into_iter()
+        .collect();
+        assert!(!settings.is_throttling());
+        let mut tracker = ThrottleTracker::new();
+        let mut gpu = GpuInfo::mock_nvidia();
+        gpu.throttle_reasons = Some(settings);
+        assert!(tracker
+            .observe(&[gpu], &GpuThresholds::default())
+            .is_empty());
+
+        let mut capped = settings;
+        capped.insert(ClockThrottleReason::SwPowerCap);
+        assert!(capped.is_throttling());
+    }
+
+    /// Test that uncorrected ECC errors alert once over the threshold and again when they rise
+    #[test]
+    fn test_ecc_uncorrected_alert() {
+        let with_errors = |errors: u64| {
+            let mut gpu = GpuInfo::mock_nvidia();
+            gpu.ecc_errors = Some(EccStats {
+                single_bit_corrected: 250,
+                double_bit_uncorrected: errors,
+                aggregate: true,
+            });
+            gpu
+        };
+        let strict = GpuThresholds {
+            ecc_uncorrected_max: Some(0),
+            ..GpuThresholds::default()
+        };
+        let mut tracker = EccTracker::new();
+        assert_eq!(
+            tracker.observe(&[with_errors(2)], &strict),
+            vec![AlertType::EccUncorrected {
+                gpu_index: 0,
+                errors: 2,
+                max: 0,
+            }]
+        );
+        // The lifetime count stays over the maximum without new errors
+        assert!(tracker.observe(&[with_errors(2)], &strict).is_empty());
+        assert_eq!(
+            tracker.observe(&[with_errors(3)], &strict),
+            vec![AlertType::EccUncorrected {
+                gpu_index: 0,
+                errors: 3,
+                max: 0,
+            }]
+        );
+        // Volatile counters reset on a driver reload
+        assert!(tracker.observe(&[with_errors(0)], &strict).is_empty());
+        assert_eq!(tracker.observe(&[with_errors(1)], &strict).len(), 1);
+
+        let lenient = GpuThresholds {
+            ecc_uncorrected_max: Some(2),
+            ..GpuThresholds::default()
+        };
+        assert!(EccTracker::new()
+            .observe(&[with_errors(2)], &lenient)
+            .is_empty());
+
+        assert_eq!(GpuThresholds::default().ecc_uncorrected_max, None);
+        assert!(EccTracker::new()
+            .observe(&[with_errors(2)], &GpuThresholds::default())
+            .is_empty());
+    }
+
+    /// Test that provider-reported memory utilization takes precedence
+    #[test]
+    fn test_memory_util_preferred_over_used_total() {
+        let gpu = GpuInfo::builder()
+            .memory_util(50.0)
+            .memory_total(8192)
+            .memory_used(4096)
+            .build();
+        let alerts = GpuMonitor::collect_alerts(&[gpu], &GpuThresholds::default());
+        assert!(alerts.is_empty(), "unexpected alerts: {:?}", alerts);
+    }
+
+    fn adaptive_state() -> AdaptiveIntervalState {
+        AdaptiveIntervalState::new(
+            Duration::from_millis(500),
+            AdaptiveInterval {
+                epsilon: 0.5,
+                stable_samples: 3,
+                max_interval: Duration::from_secs(3),
+            },
+        )
+    }
+
+    /// Test that the interval doubles after each stable streak and is capped
+    #[test]
+    fn test_adaptive_interval_backs_off_when_stable() {
+        let mut state = adaptive_state();
+        let intervals: Vec<u64> = (0..12)
+            .map(|_| state.record(false).as_millis() as u64)
+            .collect();
+        assert_eq!(
+            intervals,
+            vec![500, 500, 1000, 1000, 1000, 2000, 2000, 2000, 3000, 3000, 3000, 3000]
+        );
+    }
+
+    /// Test that any change snaps the interval back to the base
+    #[test]
+    fn test_adaptive_interval_snaps_back_on_change() {
+        let mut state = adaptive_state();
+        let script = [false, false, false, false, false, false, true, false, false];
+        let intervals: Vec<u64> = script
+            .iter()
+            .map(|changed| state.record(*changed).as_millis() as u64)
+            .collect();
+        assert_eq!(
+            intervals,
+            vec![500, 500, 1000, 1000, 1000, 2000, 500, 500, 500]
+        );
+        assert_eq!(state.current(), Duration::from_millis(500));
+    }
+
+    /// Test that an unstable sequence never lengthens the interval
+    #[test]
+    fn test_adaptive_interval_unstable_sequence() {
+        let mut state = adaptive_state();
+        for changed in [false, false, true, false, false, true, false, false, true] {
+            assert_eq!(state.record(changed), Duration::from_millis(500));
+        }
+    }
+
+    /// Test that sample comparison honours the epsilon and GPU count
+    #[test]
+    fn test_adaptive_interval_observe() {
+        let mut state = adaptive_state();
+        let base = vec![GpuInfo::mock_nvidia()];
+        let mut jitter = base.clone();
+        jitter[0].temperature = jitter[0].temperature.map(|t| t + 0.2);
+        for _ in 0..3 {
+            state.observe(&base, &jitter);
+        }
+        assert_eq!(state.current(), Duration::from_millis(1000));
+
+        let mut hot = base.clone();
+        hot[0].temperature = hot[0].temperature.map(|t| t + 5.0);
+        assert_eq!(state.observe(&base, &hot), Duration::from_millis(500));
+        for _ in 0..3 {
+            state.observe(&base, &base);
+        }
+        assert_eq!(state.observe(&base, &[]), Duration::from_millis(500));
+    }
+
+    fn throttle_thresholds() -> GpuThresholds {
+        GpuThresholds {
+            clock_deficit_warning: 10.0,
+            clock_deficit_samples: 3,
+            ..Default::default()
+        }
+    }
+
+    /// GPU requesting 2500 MHz and running at `effective` MHz
+    fn clocked(effective: u32) -> GpuInfo {
+        GpuInfo::builder()
+            .core_clock(2500)
+            .effective_core_clock(effective)
+            .build()
+    }
+
+    /// Feeds one GPU's effective clocks and returns the samples that alerted
+    fn alerting_samples(tracker: &mut ThrottleTracker, effective: &[u32]) -> Vec<usize> {
+        let thresholds = throttle_thresholds();
+        effective
+            .iter()
+            .enumerate()
+            .filter(|(_, clock)| !tracker.observe(&[clocked(**clock)], &thresholds).is_empty())
+            .map(|(sample, _)| sample)
+            .collect()
+    }
+
+    /// Test that throttling alerts only after N consecutive samples
+    #[test]
+    fn test_throttling_alerts_after_consecutive_samples() {
+        let mut tracker = ThrottleTracker::new();
+        // 2000 MHz is a 20% deficit, 2400 MHz only 4%
+        let samples = [2000, 2000, 2400, 2000, 2000, 2000, 2000, 2000];
+        assert_eq!(alerting_samples(&mut tracker, &samples), vec![5]);
+        assert_eq!(tracker.streak(0), 5);
+
+        let alerts = tracker.observe(&[clocked(2400)], &throttle_thresholds());
+        assert!(alerts.is_empty());
+        assert_eq!(tracker.streak(0), 0);
+    }
+
+    /// Test that each throttling episode alerts once
+    #[test]
+    fn test_throttling_alerts_once_per_episode() {
+        let mut tracker = ThrottleTracker::new();
+        let samples = [2000, 2000, 2000, 2000, 2000, 2500, 2000, 2000, 2000];
+        assert_eq!(alerting_samples(&mut tracker, &samples), vec![2, 8]);
+
+        let mut tracker = ThrottleTracker::new();
+        tracker.observe(&[clocked(2000)], &throttle_thresholds());
+        tracker.observe(&[clocked(2000)], &throttle_thresholds());
+        let alerts = tracker.observe(&[clocked(2000)], &throttle_thresholds());
+        assert_eq!(
+            alerts,
+            vec![AlertType::SustainedThrottling {
+                gpu_index: 0,
+                deficit_percent: 20.0,
+                samples: 3,
+            }]
+        );
+    }
+
+    /// Test that missing clocks reset the streak and GPUs are tracked separately
+    #[test]
+    fn test_throttling_tracks_gpus_independently() {
+        let thresholds = throttle_thresholds();
+        let mut tracker = ThrottleTracker::new();
+        let unknown = GpuInfo::builder().core_clock(2500).build();
+        tracker.observe(&[clocked(2000), clocked(2000)], &thresholds);
+        tracker.observe(&[clocked(2000), unknown], &thresholds);
+        let alerts = tracker.observe(&[clocked(2000), clocked(2000)], &thresholds);
+        assert_eq!(alerts.len(), 1);
+        assert!(matches!(
+            alerts[0],
+            AlertType::SustainedThrottling { gpu_index: 0, .. }
+        ));
+        assert_eq!((tracker.streak(0), tracker.streak(1)), (3, 1));
+
+        let disabled = GpuThresholds {
+            clock_deficit_samples: 0,
+            ..thresholds
+        };
+        for _ in 0..5 {
+            assert!(tracker.observe(&[clocked(1000)], &disabled).is_empty());
+        }
+    }
+
+    /// Feeds one GPU's temperatures and returns the alerts that passed
+    fn hysteresis_alerts(
+        tracker: &mut AlertHysteresis,
+        thresholds: &GpuThresholds,
+        temperatures: &[f32],
+    ) -> Vec<AlertType> {
+        temperatures
+            .iter()
+            .flat_map(|temperature| {
+                let gpus = [GpuInfo::builder().temperature(*temperature).build()];
+                let alerts = GpuMonitor::collect_alerts(&gpus, thresholds);
+                tracker.observe(&gpus, thresholds, alerts)
+            })
+            .collect()
+    }
+
+    /// Test that a temperature hovering around the limit alerts once per level
+    #[test]
+    fn test_hysteresis_suppresses_repeated_alerts() {
+        let thresholds = GpuThresholds {
+            hysteresis: 2.0,
+            ..Default::default()
+        };
+        let mut tracker = AlertHysteresis::new();
+        let alerts = hysteresis_alerts(
+            &mut tracker,
+            &thresholds,
+            &[84.0, 85.0, 86.0, 85.0, 84.0, 85.0],
+        );
+        assert_eq!(
+            alerts,
+            vec![
+                AlertType::HighTemperature {
+                    gpu_index: 0,
+                    temperature: 84.0,
+                },
+                AlertType::CriticalTemperature {
+                    gpu_index: 0,
+                    temperature: 85.0,
+                },
+            ]
+        );
+    }
+
+    /// Test that an alert is re-armed once the metric drops below the margin
+    #[test]
+    fn test_hysteresis_rearms_below_margin() {
+        let thresholds = GpuThresholds {
+            hysteresis: 2.0,
+            ..Default::default()
+        };
+        let mut tracker = AlertHysteresis::new();
+        let alerts = hysteresis_alerts(&mut tracker, &thresholds, &[86.0, 83.5, 82.0, 85.0]);
+        assert_eq!(alerts.len(), 2);
+        assert!(alerts
+            .iter()
+            .all(|alert| matches!(alert, AlertType::CriticalTemperature { .. })));
+
+        // Without a margin every sample over the threshold alerts
+        let mut tracker = AlertHysteresis::new();
+        let alerts = hysteresis_alerts(
+            &mut tracker,
+            &GpuThresholds::default(),
+            &[84.0, 85.0, 86.0, 85.0, 84.0, 85.0],
+        );
+        assert_eq!(alerts.len(), 6);
+    }
+
+    /// Test that a breached metric reports its recovery once
+    #[test]
+    fn test_recovery_after_breach() {
+        let mut tracker = AlertHysteresis::new();
+        let alerts = hysteresis_alerts(&mut tracker, &GpuThresholds::default(), &[90.0, 70.0]);
+        assert_eq!(
+            alerts,
+            vec![
+                AlertType::CriticalTemperature {
+                    gpu_index: 0,
+                    temperature: 90.0,
+                },
+                AlertType::Recovered {
+                    gpu_index: 0,
+                    metric: Metric::Temperature,
+                    value: 70.0,
+                },
+            ]
+        );
+        // Staying normal does not repeat the recovery
+        assert!(hysteresis_alerts(&mut tracker, &GpuThresholds::default(), &[70.0]).is_empty());
+    }
+
+    /// Test that PCIe errors alert on the rate between samples, not the total
+    #[test]
+    fn test_pcie_errors_alert_on_rate() {
+        let thresholds = GpuThresholds::default();
+        let start = Instant::now();
+        let minute = |n: u64| start + Duration::from_secs(60 * n);
+        let mut tracker = PcieErrorTracker::new();
+        // A high total alone does not alert
+        assert_eq!(tracker.observe(0, Some(5000), minute(0), &thresholds), None);
+        assert_eq!(tracker.observe(0, Some(5004), minute(1), &thresholds), None);
+        assert_eq!(
+            tracker.observe(0, Some(5064), minute(3), &thresholds),
+            Some(AlertType::PcieErrorsIncreasing {
+                gpu_index: 0,
+                errors_per_minute: 30.0,
+                total: 5064,
+            })
+        );
+        // Unreadable counters keep the previous sample
+        assert_eq!(tracker.observe(0, None, minute(4), &thresholds), None);
+        assert_eq!(tracker.observe(0, Some(5069), minute(5), &thresholds), None);
+    }
+
+    /// Test that counter resets, other GPUs and a zero threshold do not alert
+    #[test]
+    fn test_pcie_errors_reset_and_disabled() {
+        let thresholds = GpuThresholds::default();
+        let start = Instant::now();
+        let later = start + Duration::from_secs(60);
+        let mut tracker = PcieErrorTracker::new();
+        tracker.observe(0, Some(900), start, &thresholds);
+        assert_eq!(tracker.observe(0, Some(3), later, &thresholds), None);
+        assert_eq!(tracker.observe(2, Some(100), later, &thresholds), None);
+
+        let disabled = GpuThresholds {
+            pcie_errors_per_minute: 0.0,
+            ..thresholds
+        };
+        let mut tracker = PcieErrorTracker::new();
+        tracker.observe(0, Some(0), start, &disabled);
+        assert_eq!(tracker.observe(0, Some(10_000), later, &disabled), None);
+    }
+
+    /// Test that PCIe error counts read per sample follow each GPU's bus id
+    #[test]
+    fn test_pcie_errors_observe_gpus() {
+        let thresholds = GpuThresholds::default();
+        let start = Instant::now();
+        let later = start + Duration::from_secs(60);
+        let gpu = |bus_id: &str| GpuInfo {
+            vendor: Vendor::Amd,
+            bus_id: Some(bus_id.to_string()),
+            ..GpuInfo::unknown()
+        };
+        let counts = |totals: [(&'static str, u64); 2]| {
+            move |gpu: &GpuInfo| {
+                totals
+                    .iter()
+                    .find(|(bus_id, _)| gpu.bus_id.as_deref() == Some(bus_id))
+                    .map(|&(_, total)| total)
+            }
+        };
+        let mut tracker = PcieErrorTracker::new();
+        let gpus = [gpu("0000:01:00.0"), gpu("0000:02:00.0")];
+        let first = counts([("0000:01:00.0", 10), ("0000:02:00.0", 500)]);
+        assert!(tracker
+            .observe_gpus(&gpus, first, start, &thresholds)
+            .is_empty());
+        // The second GPU is now listed first and its counter rose
+        let swapped = [gpu("0000:02:00.0"), gpu("0000:01:00.0")];
+        let second = counts([("0000:01:00.0", 10), ("0000:02:00.0", 560)]);
+        assert_eq!(
+            tracker.observe_gpus(&swapped, second, later, &thresholds),
+            vec![AlertType::PcieErrorsIncreasing {
+                gpu_index: 0,
+                errors_per_minute: 60.0,
+                total: 560,
+            }]
+        );
+
+        let disabled = GpuThresholds {
+            pcie_errors_per_minute: 0.0,
+            ..thresholds
+        };
+        let unread = |_: &GpuInfo| -> Option<u64> { panic!("counters read while disabled") };
+        assert!(tracker
+            .observe_gpus(&gpus, unread, later, &disabled)
+            .is_empty());
+    }
+
+    /// Test pausing and resuming a running monitor
+    #[tokio::test]
+    async fn test_pause_resume() {
+        let config = MonitorConfig::default()
+            .with_polling_interval(Duration::from_millis(20))
+            .with_thread_priority(ThreadPriority::Low);
+        let monitor = GpuMonitor::new(config);
+        monitor.pause();
+        assert!(!monitor.is_paused());
+        monitor.start_monitoring().unwrap();
+        monitor.pause();
+        assert!(monitor.is_paused());
+        assert!(monitor.is_monitoring());
+        // Let any collection that was already in flight finish
+        sleep(Duration::from_millis(200)).await;
+        let paused_measurements = monitor.get_stats().total_measurements;
+        sleep(Duration::from_millis(100)).await;
+        assert_eq!(monitor.get_stats().total_measurements, paused_measurements);
+        monitor.resume();
+        assert!(!monitor.is_paused());
+        monitor.stop_monitoring().unwrap();
+        assert!(!monitor.is_monitoring());
+        assert!(!monitor.is_paused());
+    }
 }
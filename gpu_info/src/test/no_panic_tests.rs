@@ -0,0 +1,317 @@
+//! Property tests for the no-panic guarantee of read-only methods.
+//!
+//! Every public accessor, formatting, validation and aggregation method is
+//! called on arbitrary `GpuInfo` values, including NaN and infinite floats,
+//! `u32::MAX` amounts and arbitrary Unicode strings. A test fails if any
+//! call panics.
+
+use proptest::prelude::*;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use crate::extended_info::ExtendedGpuInfo;
+use crate::gpu_index::GpuIndex;
+use crate::gpu_info::{sort_stable, Confidence, GpuInfo};
+use crate::gpu_manager::GpuManager;
+use crate::monitoring::{
+    AdaptiveInterval, AdaptiveIntervalState, GpuMonitor, GpuThresholds, SingleGpuHistory,
+    ThrottleTracker,
+};
+use crate::redaction::{Redact, Redaction};
+use crate::vendor::{IntelGpuType, Vendor, VirtualKind};
+
+fn proptest_config() -> ProptestConfig {
+    ProptestConfig {
+        cases: 64,
+        ..ProptestConfig::default()
+    }
+}
+
+/// Floats including NaN, infinities, extremes and negative zero.
+fn arb_f32() -> impl Strategy<Value = f32> {
+    prop_oneof![
+        Just(f32::NAN),
+        Just(f32::INFINITY),
+        Just(f32::NEG_INFINITY),
+        Just(f32::MAX),
+        Just(f32::MIN),
+        Just(f32::MIN_POSITIVE),
+        Just(-0.0f32),
+        any::<f32>(),
+    ]
+}
+
+/// Integers weighted towards the boundaries.
+fn arb_u32() -> impl Strategy<Value = u32> {
+    prop_oneof![Just(0u32), Just(1u32), Just(u32::MAX), any::<u32>()]
+}
+
+/// Strings including empty, whitespace-only and multi-byte ones.
+fn arb_string() -> impl Strategy<Value = String> {
+    prop_oneof![
+        Just(String::new()),
+        Just("   ".to_string()),
+        Just("0000ü:01:00.0".to_string()),
+        Just("0000üü0:01:00.0".to_string()),
+        Just("535..1".to_string()),
+        any::<String>(),
+    ]
+}
+
+fn arb_vendor() -> impl Strategy<Value = Vendor> {
+    prop_oneof![
+        Just(Vendor::Nvidia),
+        Just(Vendor::Amd),
+        Just(Vendor::Intel(IntelGpuType::Integrated)),
+        Just(Vendor::Intel(IntelGpuType::Discrete)),
+        Just(Vendor::Intel(IntelGpuType::Unknown)),
+        Just(Vendor::Apple),
+        Just(Vendor::Virtual(VirtualKind::Virtio)),
+        Just(Vendor::Unknown),
+    ]
+}
+
+/// Arbitrary `GpuInfo`, with every field independently unset or set to any value.
+pub fn arb_gpu_info_any() -> impl Strategy<Value = GpuInfo> {
+    let floats = (
+        prop::option::of(arb_f32()),
+        prop::option::of(arb_f32()),
+        prop::option::of(arb_f32()),
+        prop::option::of(arb_f32()),
+        prop::option::of(arb_f32()),
+    );
+    let integers = (
+        prop::option::of(arb_u32()),
+        prop::option::of(arb_u32()),
+        prop::option::of(arb_u32()),
+        prop::option::of(arb_u32()),
+        prop::option::of(arb_u32()),
+        prop::option::of(arb_u32()),
+    );
+    let strings = (
+        prop::option::of(arb_string()),
+        prop::option::of(arb_string()),
+        prop::option::of(arb_string()),
+        prop::option::of(arb_string()),
+    );
+    (
+        arb_vendor(),
+        prop::option::of(any::<bool>()),
+        floats,
+        integers,
+        strings,
+    )
+        .prop_map(|(vendor, active, floats, integers, strings)| {
+            let mut gpu = GpuInfo::builder().vendor(vendor).build();
+            gpu.active = active;
+            (
+                gpu.temperature,
+                gpu.utilization,
+                gpu.power_usage,
+                gpu.memory_util,
+                gpu.power_limit,
+            ) = floats;
+            (
+                gpu.core_clock,
+                gpu.memory_clock,
+                gpu.memory_total,
+                gpu.memory_used,
+                gpu.max_clock_speed,
+                gpu.effective_core_clock,
+            ) = integers;
+            (gpu.name_gpu, gpu.driver_version, gpu.uuid, gpu.bus_id) = strings;
+            gpu.confidence = Confidence::Medium;
+            gpu
+        })
+}
+
+/// Calls every read-only method of `gpu`.
+fn exercise(gpu: &GpuInfo, text: &str, value: f32) {
+    let _ = (
+        gpu.vendor(),
+        gpu.name_gpu(),
+        gpu.name_or_default(),
+        gpu.temperature(),
+        gpu.utilization(),
+        gpu.power_usage(),
+        gpu.core_clock(),
+        gpu.memory_util(),
+        gpu.memory_clock(),
+        gpu.active(),
+        gpu.power_limit(),
+        gpu.memory_total(),
+        gpu.memory_used(),
+        gpu.memory_used_percent(),
+        gpu.driver_version(),
+        gpu.driver_is_older_than(text),
+        gpu.max_clock_speed(),
+        gpu.effective_core_clock(),
+        gpu.confidence(),
+        gpu.clock_deficit_percent(),
+        gpu.detection_error(),
+    );
+    let _ = (
+        gpu.format_name_gpu(),
+        gpu.format_utilization(),
+        gpu.format_temperature(),
+        gpu.format_power_usage(),
+        gpu.format_power_limit(),
+        gpu.format_memory_util(),
+        gpu.format_core_clock(),
+        gpu.format_memory_clock(),
+        gpu.format_max_clock_speed(),
+        gpu.format_memory_total(),
+        gpu.format_memory_used(),
+        gpu.format_driver_version(),
+        gpu.format_active(),
+    );
+    let _ = (
+        gpu.has_temperature(),
+        gpu.has_utilization(),
+        gpu.has_power_usage(),
+        gpu.has_driver(),
+        gpu.has_memory_info(),
+        gpu.has_clock_info(),
+        gpu.has_fan_speed(),
+        gpu.availability(),
+        gpu.usage_summary(),
+        gpu.usage_summary_with_shutdown(value),
+        gpu.can_report_power(),
+        gpu.can_report_thermals(),
+        gpu.is_active(),
+        gpu.is_vendor_known(),
+        gpu.validate(),
+        gpu.is_valid(),
+        gpu.check_consistency(),
+    );
+    let _ = (
+        gpu.approx_eq(gpu, value),
+        gpu.approx_eq(&GpuInfo::unknown(), value),
+        gpu.identity().to_string(),
+        gpu.to_influx(text, &[(text, text), ("host", "node1")]),
+        gpu.to_influx_at("gpu", &[], UNIX_EPOCH),
+        gpu.to_influx_at("gpu", &[], SystemTime::now()),
+        gpu.to_string(),
+        format!("{:?}", gpu),
+    );
+    let mut hasher = DefaultHasher::new();
+    gpu.hash(&mut hasher);
+    let _ = hasher.finish();
+    let _ = gpu == &gpu.clone();
+}
+
+proptest! {
+    #![proptest_config(proptest_config())]
+
+    /// Accessors, formatting, validation and export never panic.
+    #[test]
+    fn prop_gpu_info_methods_never_panic(
+        gpu in arb_gpu_info_any(),
+        text in arb_string(),
+        value in arb_f32(),
+    ) {
+        exercise(&gpu, &text, value);
+
+        let mut merged = GpuInfo::unknown();
+        merged.merge(&gpu);
+        exercise(&merged, &text, value);
+
+        let mut redacted = gpu.clone();
+        redacted.redact(&Redaction::hash_identifiers(text.clone()));
+        exercise(&redacted, &text, value);
+    }
+
+    /// Extended information derived from any `GpuInfo` never panics.
+    #[test]
+    fn prop_extended_info_never_panics(gpu in arb_gpu_info_any(), temperature in arb_f32()) {
+        let mut extended = ExtendedGpuInfo::from_basic(gpu);
+        extended.thermal_info.gpu_temperature = Some(temperature);
+        extended.fan_info.fan_speed_percent = Some(temperature);
+        let _ = (
+            extended.health_score(),
+            extended.needs_attention(),
+            extended.cooling_efficiency(),
+            extended.format_compute_capability(),
+            extended.to_string(),
+        );
+    }
+
+    /// Sorting, statistics, queries and alerts over any set of GPUs never panic.
+    #[test]
+    fn prop_aggregates_never_panic(
+        gpus in prop::collection::vec(arb_gpu_info_any(), 0..6),
+        bound in arb_f32(),
+        index in any::<usize>(),
+    ) {
+        let mut sorted = gpus.clone();
+        sort_stable(&mut sorted);
+
+        let manager = GpuManager::from_iter(gpus.clone());
+        let statistics = manager.get_gpu_statistics();
+        let _ = (
+            statistics.average_temperature(),
+            statistics.total_power_consumption(),
+            manager.get_primary_gpu(),
+            manager.get_gpu_at(GpuIndex(index)),
+            manager.get_cached_gpu_at(GpuIndex(index)),
+            manager.get_active_gpu_indices(),
+            manager.all_gpus_active(),
+            manager.cuda_indices(),
+            manager.detect_changes_since(&sorted),
+            manager.cache_stats(),
+        );
+        let _ = manager
+            .query()
+            .min_temperature(bound)
+            .max_temperature(bound)
+            .min_utilization(bound)
+            .max_utilization(bound)
+            .with_power()
+            .collect();
+
+        let thresholds = GpuThresholds {
+            temperature_warning: bound,
+            memory_high: bound,
+            ..GpuThresholds::default()
+        };
+        let _ = GpuMonitor::collect_alerts(&gpus, &thresholds);
+        let mut tracker = ThrottleTracker::new();
+        for _ in 0..3 {
+            let _ = tracker.observe(&gpus, &thresholds);
+        }
+        let _ = tracker.streak(index);
+    }
+
+    /// History statistics accept any look-back duration.
+    #[test]
+    fn prop_history_statistics_never_panic(
+        gpus in prop::collection::vec(arb_gpu_info_any(), 1..4),
+        seconds in any::<u64>(),
+        nanos in 0u32..1_000_000_000,
+    ) {
+        let mut history = SingleGpuHistory::new(2);
+        for gpu in &gpus {
+            history.add_measurement(gpu, Instant::now());
+        }
+        for duration in [Duration::new(seconds, nanos), Duration::MAX, Duration::ZERO] {
+            let _ = history.avg_temperature(duration);
+            let _ = history.max_temperature(duration);
+        }
+    }
+
+    /// The adaptive polling interval never overflows while backing off.
+    #[test]
+    fn prop_adaptive_interval_never_panics(seconds in any::<u64>(), samples in 0usize..4) {
+        let config = AdaptiveInterval {
+            epsilon: 0.0,
+            stable_samples: samples,
+            max_interval: Duration::MAX,
+        };
+        let mut state = AdaptiveIntervalState::new(Duration::from_secs(seconds), config);
+        for _ in 0..80 {
+            let _ = state.record(false);
+        }
+        prop_assert!(state.current() >= Duration::from_secs(seconds));
+    }
+}
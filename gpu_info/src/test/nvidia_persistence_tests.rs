@@ -0,0 +1,146 @@
+//! Tests for NVIDIA persistence mode detection and the detection report hint
+
+#[cfg(test)]
+mod tests {
+    use crate::detection_report::DetectionReport;
+    use crate::providers::linux::persistence::{
+        persistence_hint, persistence_mode_from, persistenced_running_at, NVML_INIT_STAGE,
+        PERSISTENCE_HINT, PERSISTENCE_HINT_THRESHOLD,
+    };
+    use crate::test::scratch_dir;
+    use std::fs;
+    use std::time::Duration;
+
+    const NVML_SUCCESS: i32 = 0;
+    const NVML_ERROR_NOT_SUPPORTED: i32 = 3;
+
+    #[test]
+    fn test_persistence_mode_enabled() {
+        let mode = persistence_mode_from(|out| {
+            *out = 1;
+            NVML_SUCCESS
+        });
+        assert_eq!(mode, Some(true));
+    }
+
+    #[test]
+    fn test_persistence_mode_disabled() {
+        let mode = persistence_mode_from(|out| {
+            *out = 0;
+            NVML_SUCCESS
+        });
+        assert_eq!(mode, Some(false));
+    }
+
+    #[test]
+    fn test_persistence_mode_not_supported() {
+        let mode = persistence_mode_from(|out| {
+            *out = 1;
+            NVML_ERROR_NOT_SUPPORTED
+        });
+        assert_eq!(mode, None);
+    }
+
+    #[test]
+    fn test_persistenced_detected_via_pidfile() {
+        let dir = scratch_dir("persistence_pidfile");
+        let proc_root = dir.join("proc");
+        fs::create_dir_all(proc_root.join("4242")).unwrap();
+        let pidfile = dir.join("nvidia-persistenced.pid");
+        fs::write(&pidfile, "4242\n").unwrap();
+        assert!(persistenced_running_at(&pidfile, &proc_root));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_persistenced_detected_via_proc_scan() {
+        let dir = scratch_dir("persistence_scan");
+        let proc_root = dir.join("proc");
+        fs::create_dir_all(proc_root.join("1")).unwrap();
+        fs::write(proc_root.join("1").join("comm"), "systemd\n").unwrap();
+        fs::create_dir_all(proc_root.join("977")).unwrap();
+        fs::write(proc_root.join("977").join("comm"), "nvidia-persiste\n").unwrap();
+        assert!(persistenced_running_at(
+            &dir.join("missing.pid"),
+            &proc_root
+        ));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_persistenced_not_running_with_stale_pidfile() {
+        let dir = scratch_dir("persistence_stale");
+        let proc_root = dir.join("proc");
+        fs::create_dir_all(proc_root.join("1")).unwrap();
+        fs::write(proc_root.join("1").join("comm"), "systemd\n").unwrap();
+        let pidfile = dir.join("nvidia-persistenced.pid");
+        fs::write(&pidfile, "4242\n").unwrap();
+        assert!(!persistenced_running_at(&pidfile, &proc_root));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    fn report_with_init(latency: Duration) -> DetectionReport {
+        let mut report = DetectionReport::new();
+        report.record_latency(NVML_INIT_STAGE, latency);
+        report
+    }
+
+    #[test]
+    fn test_hint_on_slow_init_without_persistence() {
+        let slow = report_with_init(PERSISTENCE_HINT_THRESHOLD + Duration::from_millis(1));
+        assert_eq!(
+            persistence_hint(&slow, Some(false), || false),
+            Some(PERSISTENCE_HINT)
+        );
+        assert_eq!(
+            persistence_hint(&slow, None, || false),
+            Some(PERSISTENCE_HINT)
+        );
+    }
+
+    #[test]
+    fn test_no_hint_when_persistence_active() {
+        let slow = report_with_init(PERSISTENCE_HINT_THRESHOLD * 4);
+        assert_eq!(persistence_hint(&slow, Some(true), || false), None);
+        assert_eq!(persistence_hint(&slow, Some(false), || true), None);
+    }
+
+    #[test]
+    fn test_no_hint_on_fast_or_unrecorded_init() {
+        let fast = report_with_init(Duration::from_millis(20));
+        assert_eq!(persistence_hint(&fast, Some(false), || false), None);
+        assert_eq!(
+            persistence_hint(&DetectionReport::new(), Some(false), || false),
+            None
+        );
+    }
+
+    #[test]
+    fn test_daemon_is_only_checked_after_slow_init() {
+        let fast = report_with_init(Duration::from_millis(20));
+        let slow = report_with_init(PERSISTENCE_HINT_THRESHOLD * 4);
+        let scanned = std::cell::Cell::new(0);
+        let daemon_running = || {
+            scanned.set(scanned.get() + 1);
+            false
+        };
+        persistence_hint(&fast, Some(false), daemon_running);
+        persistence_hint(&slow, Some(true), daemon_running);
+        assert_eq!(scanned.get(), 0);
+        persistence_hint(&slow, Some(false), daemon_running);
+        assert_eq!(scanned.get(), 1);
+    }
+
+    #[test]
+    fn test_detection_report_collects_hint_once() {
+        let mut report = DetectionReport::new();
+        report.record_latency(NVML_INIT_STAGE, Duration::from_secs(2));
+        report.add_hint(PERSISTENCE_HINT);
+        report.add_hint(PERSISTENCE_HINT);
+        assert_eq!(report.hints(), &[PERSISTENCE_HINT.to_string()]);
+        assert_eq!(
+            report.latency(NVML_INIT_STAGE),
+            Some(Duration::from_secs(2))
+        );
+    }
+}
@@ -0,0 +1,156 @@
+//! Tests for the `nvml-wrapper` conversion and provider
+
+#[cfg(all(test, feature = "nvml-wrapper-compat"))]
+mod tests {
+    use crate::gpu_info::{ClockThrottleReason, Confidence, GpuError, GpuInfo, GpuProvider};
+    use crate::metric_source::Metric;
+    use crate::providers::nvml_wrapper::{gpu_error, NvmlReadings, NvmlWrapperProvider};
+    use crate::vendor::Vendor;
+    use nvml_wrapper::error::NvmlError;
+    use nvml_wrapper::struct_wrappers::device::{MemoryInfo, Utilization};
+
+    const MIB: u64 = 1024 * 1024;
+
+    fn rtx_4090_readings() -> NvmlReadings {
+        NvmlReadings {
+            name: Some("NVIDIA GeForce RTX 4090".to_string()),
+            temperature: Some(61),
+            utilization: Some(Utilization {
+                gpu: 87,
+                memory: 42,
+            }),
+            memory: Some(MemoryInfo {
+                free: 8 * 1024 * MIB,
+                reserved: 300 * MIB,
+                total: 24 * 1024 * MIB,
+                used: 16 * 1024 * MIB - 300 * MIB,
+                version: 0,
+            }),
+            power_usage: Some(312_450),
+            power_limit: Some(450_000),
+            graphics_clock: Some(2505),
+            memory_clock: Some(10501),
+            max_graphics_clock: Some(3105),
+            graphics_clock_target: Some(2520),
+            fan_speed: Some(38),
+            encoder_util: Some(12),
+            decoder_util: Some(0),
+            driver_version: Some("550.54.14".to_string()),
+            // SwPowerCap
+            throttle_reasons: Some(0x4),
+            uuid: Some("GPU-5a8f3b2e-1c4d-4e6f-9a0b-7c8d9e0f1a2b".to_string()),
+            bus_id: Some("00000000:01:00.0".to_string()),
+            // GeForce cards ship with ECC disabled
+            ecc_errors: None,
+        }
+    }
+
+    #[test]
+    fn test_readings_convert_to_gpu_info_units() {
+        let gpu = rtx_4090_readings().into_gpu_info();
+        assert_eq!(gpu.vendor, Vendor::Nvidia);
+        assert_eq!(gpu.name_gpu.as_deref(), Some("NVIDIA GeForce RTX 4090"));
+        assert_eq!(gpu.temperature, Some(61.0));
+        assert_eq!(gpu.utilization, Some(87.0));
+        assert_eq!(gpu.memory_util, Some(42.0));
+        assert_eq!(gpu.memory_total, Some(24576));
+        assert_eq!(gpu.memory_used, Some(16084));
+        assert_eq!(gpu.power_usage, Some(312.45));
+        assert_eq!(gpu.power_limit, Some(450.0));
+        assert_eq!(gpu.core_clock, Some(2520));
+        assert_eq!(gpu.memory_clock, Some(10501));
+        assert_eq!(gpu.max_clock_speed, Some(3105));
+        assert_eq!(gpu.effective_core_clock, Some(2505));
+        assert_eq!(gpu.clock_deficit_percent(), Some(15.0 / 2520.0 * 100.0));
+        assert_eq!(gpu.fan_speed, Some(38));
+        assert_eq!(gpu.encoder_util, Some(12.0));
+        assert_eq!(gpu.decoder_util, Some(0.0));
+
+        // Without an application clock target there is nothing to fall short of
+        let gpu = NvmlReadings {
+            graphics_clock_target: None,
+            ..rtx_4090_readings()
+        }
+        .into_gpu_info();
+        assert_eq!(gpu.core_clock, Some(2505));
+        assert_eq!(gpu.effective_core_clock, None);
+        assert_eq!(gpu.driver_version.as_deref(), Some("550.54.14"));
+        assert_eq!(gpu.active, Some(true));
+        assert_eq!(
+            gpu.throttle_reasons,
+            Some(ClockThrottleReason::SwPowerCap.into())
+        );
+        assert_eq!(gpu.identity().bus_id(), Some("0000:01:00.0"));
+    }
+
+    #[test]
+    fn test_missing_readings_stay_unset() {
+        let gpu = NvmlReadings {
+            name: Some("Tesla T4".to_string()),
+            ..Default::default()
+        }
+        .into_gpu_info();
+        assert_eq!(
+            gpu,
+            GpuInfo {
+                vendor: Vendor::Nvidia,
+                name_gpu: Some("Tesla T4".to_string()),
+                active: Some(true),
+                confidence: Confidence::High,
+                ..GpuInfo::unknown()
+            }
+        );
+    }
+
+    #[test]
+    fn test_error_mapping() {
+        assert!(matches!(
+            gpu_error(NvmlError::LibraryNotFound),
+            GpuError::DriverNotInstalled
+        ));
+        assert!(matches!(
+            gpu_error(NvmlError::DriverNotLoaded),
+            GpuError::DriverNotInstalled
+        ));
+        assert!(matches!(
+            gpu_error(NvmlError::GpuLost),
+            GpuError::GpuNotFound
+        ));
+        assert!(matches!(
+            gpu_error(NvmlError::NoPermission),
+            GpuError::Ffi(_)
+        ));
+    }
+
+    #[test]
+    #[ignore = "requires an NVIDIA GPU and driver"]
+    fn test_provider_detects_devices() {
+        let provider = NvmlWrapperProvider::new().unwrap();
+        let gpus = provider.detect_gpus().unwrap();
+        assert!(gpus.iter().all(|gpu| gpu.vendor == Vendor::Nvidia));
+        assert!(gpus.iter().all(|gpu| gpu.name_gpu.is_some()));
+        assert!(provider.supports_metric(Metric::MemoryClock));
+        let device = provider.nvml().device_by_index(0).unwrap();
+        let gpu = GpuInfo::from_nvml_device(&device).unwrap();
+        assert_eq!(gpu.name_gpu, gpus[0].name_gpu);
+        for detected in &gpus {
+            let mut updated = detected.clone();
+            provider.update_gpu(&mut updated).unwrap();
+            assert_eq!(updated.uuid, detected.uuid);
+            let mut by_uuid = GpuInfo {
+                bus_id: None,
+                ..detected.clone()
+            };
+            provider.update_gpu(&mut by_uuid).unwrap();
+            assert_eq!(by_uuid.bus_id, detected.bus_id);
+        }
+        let mut removed = GpuInfo {
+            bus_id: Some("0000:ff:1f.7".to_string()),
+            ..gpus[0].clone()
+        };
+        assert!(matches!(
+            provider.update_gpu(&mut removed),
+            Err(GpuError::GpuNotFound)
+        ));
+    }
+}
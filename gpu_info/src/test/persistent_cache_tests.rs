@@ -0,0 +1,191 @@
+//! Tests for the persistent GPU identity cache
+
+#[cfg(all(test, feature = "persistent-cache"))]
+mod tests {
+    use crate::gpu_info::GpuInfo;
+    use crate::gpu_manager::GpuManager;
+    use crate::persistent_cache::{PersistentCache, CACHE_FILE_NAME, CACHE_FORMAT_VERSION};
+    use crate::test::scratch_dir;
+    use std::fs;
+    use std::time::Duration;
+
+    const DAY_MS: u64 = 24 * 60 * 60 * 1000;
+
+    fn gpus() -> Vec<GpuInfo> {
+        let mut nvidia = GpuInfo::mock_nvidia();
+        nvidia.uuid = Some("GPU-5a8f3b2e-0000-0000-0000-000000000000".to_string());
+        nvidia.bus_id = Some("00000000:01:00.0".to_string());
+        vec![nvidia, GpuInfo::mock_amd()]
+    }
+
+    #[test]
+    fn test_store_and_load_round_trip() {
+        let cache = PersistentCache::in_dir(scratch_dir("persistent_cache_round_trip"));
+        cache.store_at(&gpus(), 1_000).unwrap();
+
+        let loaded = cache.load_at(2_000).expect("cache should load");
+        assert_eq!(loaded.version, CACHE_FORMAT_VERSION);
+        assert_eq!(loaded.detected_at_ms, 1_000);
+        assert_eq!(loaded.validated_at_ms, 1_000);
+        assert!(loaded.matches(&gpus()));
+
+        let restored = loaded.to_gpu_infos();
+        assert_eq!(restored[0].name_gpu, GpuInfo::mock_nvidia().name_gpu);
+        assert_eq!(restored[1].memory_total, GpuInfo::mock_amd().memory_total);
+        assert_eq!(restored[0].temperature, None);
+        assert_eq!(restored[0].identity(), gpus()[0].identity());
+        assert_eq!(restored[0].bus_id, gpus()[0].bus_id);
+    }
+
+    #[test]
+    fn test_bus_id_change_invalidates() {
+        let cache = PersistentCache::in_dir(scratch_dir("persistent_cache_bus_id"));
+        cache.store_at(&gpus(), 1_000).unwrap();
+        let mut current = gpus();
+        current[0].bus_id = Some("00000000:02:00.0".to_string());
+        assert!(!cache.load_at(1_000).unwrap().matches(&current));
+    }
+
+    #[test]
+    fn test_gpus_keep_their_own_detection_timestamp() {
+        let cache = PersistentCache::in_dir(scratch_dir("persistent_cache_per_gpu"));
+        cache.store_at(&gpus()[..1], 1_000).unwrap();
+        cache.store_at(&gpus(), 5_000).unwrap();
+        let loaded = cache.load_at(5_000).unwrap();
+        assert_eq!(loaded.detected_at_ms, 5_000);
+        assert_eq!(loaded.gpus[0].detected_at_ms, 1_000);
+        assert_eq!(loaded.gpus[1].detected_at_ms, 5_000);
+    }
+
+    #[test]
+    fn test_unknown_placeholders_are_not_cached() {
+        let cache = PersistentCache::in_dir(scratch_dir("persistent_cache_unknown"));
+        let mut detected = gpus();
+        detected.push(GpuInfo::unknown());
+        cache.store_at(&detected, 1_000).unwrap();
+        let loaded = cache.load_at(1_000).unwrap();
+        assert_eq!(loaded.gpus.len(), 2);
+        assert!(loaded.matches(&detected));
+        assert!(loaded.matches(&gpus()));
+
+        assert!(!cache.validate(&[GpuInfo::unknown()]));
+        assert!(!cache.path().exists());
+    }
+
+    #[test]
+    fn test_store_leaves_no_temporary_files() {
+        let dir = scratch_dir("persistent_cache_temp_files");
+        let cache = PersistentCache::in_dir(&dir);
+        cache.store_at(&gpus(), 1_000).unwrap();
+        cache.store_at(&gpus(), 2_000).unwrap();
+        let entries: Vec<_> = fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect();
+        assert_eq!(entries, vec![std::ffi::OsString::from(CACHE_FILE_NAME)]);
+    }
+
+    #[test]
+    fn test_matches_ignores_runtime_metrics() {
+        let cache = PersistentCache::in_dir(scratch_dir("persistent_cache_metrics"));
+        cache.store_at(&gpus(), 1_000).unwrap();
+        let mut current = gpus();
+        current[0].temperature = Some(90.0);
+        current[1].utilization = Some(3.0);
+        assert!(cache.load_at(1_000).unwrap().matches(&current));
+    }
+
+    #[test]
+    fn test_driver_change_invalidates() {
+        let cache = PersistentCache::in_dir(scratch_dir("persistent_cache_driver"));
+        cache.store_at(&gpus(), 1_000).unwrap();
+        let mut current = gpus();
+        current[0].driver_version = Some("999.99".to_string());
+        assert!(!cache.load_at(1_000).unwrap().matches(&current));
+    }
+
+    #[test]
+    fn test_device_set_change_invalidates() {
+        let cache = PersistentCache::in_dir(scratch_dir("persistent_cache_devices"));
+        cache.store_at(&gpus(), 1_000).unwrap();
+        let persisted = cache.load_at(1_000).unwrap();
+        assert!(!persisted.matches(&[GpuInfo::mock_nvidia()]));
+        assert!(!persisted.matches(&[GpuInfo::mock_amd(), GpuInfo::mock_nvidia()]));
+    }
+
+    #[test]
+    fn test_revalidation_keeps_detection_timestamp() {
+        let cache = PersistentCache::in_dir(scratch_dir("persistent_cache_timestamps"));
+        cache.store_at(&gpus(), 1_000).unwrap();
+        cache.store_at(&gpus(), 5_000).unwrap();
+        let loaded = cache.load_at(5_000).unwrap();
+        assert_eq!(loaded.detected_at_ms, 1_000);
+        assert_eq!(loaded.validated_at_ms, 5_000);
+
+        cache.store_at(&[GpuInfo::mock_intel()], 9_000).unwrap();
+        assert_eq!(cache.load_at(9_000).unwrap().detected_at_ms, 9_000);
+    }
+
+    #[test]
+    fn test_validate_rewrites_outdated_cache() {
+        let cache = PersistentCache::in_dir(scratch_dir("persistent_cache_validate"));
+        cache.store(&gpus()).unwrap();
+        assert!(cache.validate(&gpus()));
+        assert!(!cache.validate(&[GpuInfo::mock_intel()]));
+        assert!(cache.load().unwrap().matches(&[GpuInfo::mock_intel()]));
+        assert!(!cache.validate(&[]));
+        assert!(!cache.path().exists());
+    }
+
+    #[test]
+    fn test_stale_file_is_ignored() {
+        let cache = PersistentCache::in_dir(scratch_dir("persistent_cache_stale"))
+            .with_max_age(Duration::from_secs(60 * 60 * 24));
+        cache.store_at(&gpus(), DAY_MS).unwrap();
+        assert!(cache.load_at(DAY_MS + 1).is_some());
+        assert!(cache.load_at(3 * DAY_MS).is_none());
+    }
+
+    #[test]
+    fn test_missing_or_corrupt_file_is_ignored() {
+        let dir = scratch_dir("persistent_cache_corrupt");
+        let cache = PersistentCache::in_dir(&dir);
+        assert!(cache.load().is_none());
+
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(cache.path(), b"{\"version\":1,\"gpus\":[").unwrap();
+        assert!(cache.load().is_none());
+
+        fs::write(cache.path(), b"not json at all").unwrap();
+        assert!(cache.load().is_none());
+    }
+
+    #[test]
+    fn test_other_format_version_is_ignored() {
+        let cache = PersistentCache::in_dir(scratch_dir("persistent_cache_version"));
+        cache.store_at(&gpus(), 1_000).unwrap();
+        let content = fs::read_to_string(cache.path()).unwrap();
+        let bumped = content.replace(
+            &format!("\"version\":{}", CACHE_FORMAT_VERSION),
+            &format!("\"version\":{}", CACHE_FORMAT_VERSION + 1),
+        );
+        assert_ne!(content, bumped);
+        fs::write(cache.path(), bumped).unwrap();
+        assert!(cache.load_at(1_000).is_none());
+    }
+
+    #[test]
+    fn test_manager_prepopulates_from_cache() {
+        let cache = PersistentCache::in_dir(scratch_dir("persistent_cache_manager"));
+        cache.store(&gpus()).unwrap();
+        let manager = GpuManager::with_persistent_cache(cache);
+        let names: Vec<_> = manager.iter().map(|gpu| gpu.name_gpu.clone()).collect();
+        assert_eq!(
+            names,
+            vec![
+                GpuInfo::mock_nvidia().name_gpu,
+                GpuInfo::mock_amd().name_gpu
+            ]
+        );
+    }
+}
@@ -0,0 +1,461 @@
+//! Tests for per-process GPU usage attribution
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "vendor-nvidia")]
+    use crate::nvml_api::{
+        compute_processes_from, nvmlProcessInfo_t, nvmlProcessInfo_v3_t, running_processes_from,
+        NVML_ERROR_INSUFFICIENT_SIZE, NVML_SUCCESS, NVML_VALUE_NOT_AVAILABLE,
+    };
+    use crate::process_usage::{
+        drm_shares, parse_drm_clients_list, parse_drm_fdinfo, parse_pdh_instance,
+        parse_powermetrics_tasks, pdh_shares, GpuProcessInfo, GpuProcessShare, ProcessGpuUsage,
+        ProcessType,
+    };
+    #[cfg(feature = "vendor-nvidia")]
+    use crate::process_usage::{nvml_share, running_processes};
+    use std::time::Duration;
+
+    /// Process the fixtures attribute usage to
+    const PID: u32 = 4242;
+
+    #[cfg(feature = "vendor-nvidia")]
+    fn process(pid: u32, used_gpu_memory: u64) -> nvmlProcessInfo_t {
+        nvmlProcessInfo_t {
+            pid,
+            used_gpu_memory,
+        }
+    }
+
+    #[cfg(feature = "vendor-nvidia")]
+    #[test]
+    fn test_compute_processes_two_calls() {
+        let running = [process(PID, 512 << 20), process(7, 1 << 30)];
+        let mut calls = 0;
+        let processes = compute_processes_from(|count, infos| {
+            calls += 1;
+            if (*count as usize) < running.len() {
+                *count = running.len() as u32;
+                return NVML_ERROR_INSUFFICIENT_SIZE;
+            }
+            infos[..running.len()].copy_from_slice(&running);
+            *count = running.len() as u32;
+            NVML_SUCCESS
+        });
+        assert_eq!(processes, Some(running.to_vec()));
+        assert_eq!(calls, 2);
+    }
+
+    #[cfg(feature = "vendor-nvidia")]
+    #[test]
+    fn test_compute_processes_none_running() {
+        assert_eq!(
+            compute_processes_from(|_, _| NVML_SUCCESS),
+            Some(Vec::new())
+        );
+    }
+
+    #[cfg(feature = "vendor-nvidia")]
+    #[test]
+    fn test_compute_processes_failure() {
+        const NVML_ERROR_NOT_SUPPORTED: i32 = 3;
+        assert_eq!(
+            compute_processes_from(|_, _| NVML_ERROR_NOT_SUPPORTED),
+            None
+        );
+    }
+
+    #[cfg(feature = "vendor-nvidia")]
+    #[test]
+    fn test_nvml_share_filters_pid() {
+        let processes = [
+            process(7, 1 << 30),
+            process(PID, 256 << 20),
+            process(PID, 256 << 20),
+        ];
+        assert_eq!(
+            nvml_share("nvml:0".to_string(), &processes, PID),
+            Some(GpuProcessShare {
+                device: "nvml:0".to_string(),
+                memory_bytes: Some(512 << 20),
+                utilization: None,
+            })
+        );
+        assert_eq!(nvml_share("nvml:0".to_string(), &processes, 8), None);
+    }
+
+    #[cfg(feature = "vendor-nvidia")]
+    #[test]
+    fn test_nvml_share_memory_not_available() {
+        let processes = [process(PID, NVML_VALUE_NOT_AVAILABLE)];
+        let share = nvml_share("nvml:1".to_string(), &processes, PID).unwrap();
+        assert_eq!(share.memory_bytes, None);
+    }
+
+    #[cfg(feature = "vendor-nvidia")]
+    fn process_v3(pid: u32, used_gpu_memory: u64) -> nvmlProcessInfo_v3_t {
+        nvmlProcessInfo_v3_t {
+            pid,
+            used_gpu_memory,
+            ..Default::default()
+        }
+    }
+
+    #[cfg(feature = "vendor-nvidia")]
+    #[test]
+    fn test_compute_processes_v3_two_calls() {
+        let running = [process_v3(PID, 64 << 20)];
+        let processes = running_processes_from(|count, infos: &mut [nvmlProcessInfo_v3_t]| {
+            if infos.is_empty() {
+                *count = 1;
+                return NVML_ERROR_INSUFFICIENT_SIZE;
+            }
+            infos[0] = running[0];
+            *count = 1;
+            NVML_SUCCESS
+        });
+        assert_eq!(processes, Some(running.to_vec()));
+    }
+
+    #[cfg(feature = "vendor-nvidia")]
+    #[test]
+    fn test_running_processes_merges_lists() {
+        let compute = [
+            process_v3(PID, 256 << 20),
+            process_v3(PID, 256 << 20),
+            process_v3(900, 1 << 30),
+        ];
+        let graphics = [
+            process_v3(900, 1 << 30),
+            process_v3(12, NVML_VALUE_NOT_AVAILABLE),
+        ];
+        assert_eq!(
+            running_processes(&compute, &graphics),
+            vec![
+                GpuProcessInfo {
+                    pid: 12,
+                    memory_used_mb: 0,
+                    process_type: ProcessType::Graphics,
+                    process_name: None,
+                },
+                GpuProcessInfo {
+                    pid: 900,
+                    memory_used_mb: 1024,
+                    process_type: ProcessType::ComputeAndGraphics,
+                    process_name: None,
+                },
+                GpuProcessInfo {
+                    pid: PID,
+                    memory_used_mb: 512,
+                    process_type: ProcessType::Compute,
+                    process_name: None,
+                },
+            ]
+        );
+        assert!(running_processes(&[], &[]).is_empty());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_gpu_process_name() {
+        let process = crate::process_usage::GpuProcessInfo {
+            pid: std::process::id(),
+            memory_used_mb: 0,
+            process_type: crate::process_usage::ProcessType::Compute,
+            process_name: None,
+        };
+        assert!(process.name().is_some_and(|name| !name.is_empty()));
+    }
+
+    #[test]
+    fn test_gpu_process_name_from_backend() {
+        let process = GpuProcessInfo {
+            pid: u32::MAX,
+            memory_used_mb: 0,
+            process_type: ProcessType::Unknown,
+            process_name: Some("WindowServer".to_string()),
+        };
+        assert_eq!(process.name().as_deref(), Some("WindowServer"));
+        assert_eq!(process.process_type.to_string(), "unknown");
+    }
+
+    #[cfg(feature = "vendor-nvidia")]
+    #[test]
+    fn test_processes_from() {
+        use crate::nvml_api::processes_from;
+
+        assert_eq!(processes_from(None, None), None);
+        let compute = vec![process_v3(PID, 256 << 20)];
+        let processes = processes_from(Some(compute), None).unwrap();
+        assert_eq!(processes.len(), 1);
+        assert_eq!(processes[0].process_type, ProcessType::Compute);
+        assert_eq!(processes_from(Some(Vec::new()), None), Some(Vec::new()));
+    }
+
+    #[test]
+    fn test_parse_drm_clients_list() {
+        let content = "\
+             command   tgid dev master a   uid      magic                                                             name
+                Xorg   1290   0   y    y     0          0                                                          <unset>
+                Xorg   1290   0   n    y     0          2                                                          <unset>
+             firefox   4242 128   n    n  1000          0                                                          <unset>
+     Isolated Web Co   4377 128   n    n  1000          0                                                          <unset>
+";
+        let processes = parse_drm_clients_list(content);
+        let summary: Vec<_> = processes
+            .iter()
+            .map(|process| (process.pid, process.name().unwrap()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (1290, "Xorg".to_string()),
+                (PID, "firefox".to_string()),
+                (4377, "Isolated Web Co".to_string()),
+            ]
+        );
+        assert!(processes
+            .iter()
+            .all(|process| process.process_type == ProcessType::Unknown));
+        // Older kernels have no name column
+        assert_eq!(
+            parse_drm_clients_list(
+                "             command   pid dev master a   uid      magic\n                Xorg  1290   0   y    y     0          0\n"
+            )
+            .len(),
+            1
+        );
+        assert!(parse_drm_clients_list("").is_empty());
+    }
+
+    #[test]
+    fn test_parse_powermetrics_tasks() {
+        let output = "\
+*** Sampled system activity (Fri Oct 17 10:00:00 2026 +0000) (104.21ms elapsed) ***
+
+*** Running tasks ***
+
+Name                               ID     CPU ms/s  User%  Deadlines (<2 ms, 2-5 ms)  Wakeups (Intr, Pkg idle)  GPU ms/s
+WindowServer                       151    30.11     61.47  0.00    0.00              120.33  9.60             12.50
+Google Chrome Helper (GPU)         4242   12.80     70.01  0.00    0.00              40.10   0.00             3.25
+kernel_task                        0      60.21     0.00   0.00    0.00              470.71  42.81            0.00
+ALL_TASKS                          -2     103.12    55.12  0.00    0.00              631.14  52.41            15.75
+
+**** Battery and backlight usage ****
+";
+        let processes = parse_powermetrics_tasks(output);
+        let summary: Vec<_> = processes
+            .iter()
+            .map(|process| (process.pid, process.process_name.as_deref().unwrap()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![(151, "WindowServer"), (PID, "Google Chrome Helper (GPU)")]
+        );
+        assert!(parse_powermetrics_tasks("*** Running tasks ***\n").is_empty());
+    }
+
+    #[test]
+    fn test_parse_pdh_instance() {
+        assert_eq!(
+            parse_pdh_instance(
+                "\\GPU Engine(pid_4242_luid_0x00000000_0x0000C2E5_phys_0_eng_0_engtype_3D)\\Utilization Percentage"
+            ),
+            Some((PID, "luid_0x00000000_0x0000C2E5_phys_0"))
+        );
+        assert_eq!(
+            parse_pdh_instance(
+                "\\GPU Process Memory(pid_4242_luid_0x00000000_0x0000C2E5_phys_0)\\Dedicated Usage"
+            ),
+            Some((PID, "luid_0x00000000_0x0000C2E5_phys_0"))
+        );
+        assert_eq!(
+            parse_pdh_instance(
+                "\\GPU Adapter Memory(luid_0x00000000_0x0000C2E5_phys_0)\\Dedicated Usage"
+            ),
+            None
+        );
+        assert_eq!(
+            parse_pdh_instance("\\GPU Engine(pid_x_luid_0x0)\\Utilization Percentage"),
+            None
+        );
+        assert_eq!(parse_pdh_instance("\\GPU Engine(pid_4242_"), None);
+    }
+
+    #[test]
+    fn test_pdh_shares_groups_by_adapter() {
+        let engine = |pid: u32, adapter: &str, engine: &str| {
+            format!(
+                "\\GPU Engine(pid_{}_{}_eng_{})\\Utilization Percentage",
+                pid, adapter, engine
+            )
+        };
+        let memory = |pid: u32, adapter: &str| {
+            format!(
+                "\\GPU Process Memory(pid_{}_{})\\Dedicated Usage",
+                pid, adapter
+            )
+        };
+        let dgpu = "luid_0x00000000_0x0000C2E5_phys_0";
+        let igpu = "luid_0x00000000_0x0000D1A0_phys_0";
+        let engines = [
+            (engine(PID, dgpu, "0_engtype_3D"), 30.0),
+            (engine(PID, dgpu, "1_engtype_Copy"), 5.0),
+            (engine(PID, igpu, "0_engtype_3D"), 70.0),
+            (engine(PID, igpu, "2_engtype_VideoDecode"), 45.0),
+            (engine(7, dgpu, "0_engtype_3D"), 90.0),
+        ];
+        let memory = [(memory(PID, dgpu), 268_435_456.0), (memory(7, dgpu), 1e9)];
+        assert_eq!(
+            pdh_shares(PID, &engines, &memory),
+            vec![
+                GpuProcessShare {
+                    device: dgpu.to_string(),
+                    memory_bytes: Some(268_435_456),
+                    utilization: Some(35.0),
+                },
+                GpuProcessShare {
+                    device: igpu.to_string(),
+                    memory_bytes: None,
+                    utilization: Some(100.0),
+                },
+            ]
+        );
+        assert!(pdh_shares(8, &engines, &memory).is_empty());
+    }
+
+    const AMDGPU_FDINFO: &str = "\
+pos:\t0
+flags:\t02100002
+mnt_id:\t25
+ino:\t1045
+drm-driver:\tamdgpu
+drm-client-id:\t17
+drm-pdev:\t0000:03:00.0
+pasid:\t32771
+drm-total-vram:\t262144 KiB
+drm-total-gtt:\t2048 KiB
+drm-resident-vram:\t262144 KiB
+drm-engine-gfx:\t1500000000 ns
+drm-engine-compute:\t0 ns
+";
+
+    const I915_FDINFO: &str = "\
+pos:\t0
+flags:\t02100002
+drm-driver:\ti915
+drm-client-id:\t5
+drm-pdev:\t0000:00:02.0
+drm-memory-system0:\t4 MiB
+drm-engine-render:\t9000000 ns
+drm-engine-video:\t200000000 ns
+drm-engine-capacity-video:\t2
+";
+
+    #[test]
+    fn test_parse_drm_fdinfo_totals() {
+        let client = parse_drm_fdinfo(AMDGPU_FDINFO).unwrap();
+        assert_eq!(client.pdev, "0000:03:00.0");
+        assert_eq!(client.client_id, 17);
+        assert_eq!(client.memory_bytes, Some((262_144 + 2_048) * 1024));
+        assert_eq!(client.engines.get("gfx"), Some(&1_500_000_000));
+        assert_eq!(client.engines.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_drm_fdinfo_legacy_memory_and_capacity() {
+        let client = parse_drm_fdinfo(I915_FDINFO).unwrap();
+        assert_eq!(client.memory_bytes, Some(4 << 20));
+        assert_eq!(client.capacities.get("video"), Some(&2));
+        assert!(!client.engines.contains_key("capacity-video"));
+    }
+
+    #[test]
+    fn test_parse_drm_fdinfo_not_drm() {
+        assert_eq!(parse_drm_fdinfo("pos:\t0\nflags:\t02\nmnt_id:\t15\n"), None);
+    }
+
+    #[test]
+    fn test_drm_shares_utilization() {
+        let before = vec![
+            parse_drm_fdinfo(AMDGPU_FDINFO).unwrap(),
+            parse_drm_fdinfo(I915_FDINFO).unwrap(),
+        ];
+        let mut after = before.clone();
+        // 250 ms of graphics work during a 500 ms interval
+        *after[0].engines.get_mut("gfx").unwrap() += 250_000_000;
+        // 400 ms on two video engines: 200 ms each
+        *after[1].engines.get_mut("video").unwrap() += 400_000_000;
+        let shares = drm_shares(&before, &after, Duration::from_millis(500));
+        assert_eq!(shares.len(), 2);
+        assert_eq!(shares[0].device, "0000:00:02.0");
+        assert_eq!(shares[0].utilization, Some(40.0));
+        assert_eq!(shares[1].device, "0000:03:00.0");
+        assert_eq!(shares[1].utilization, Some(50.0));
+        assert_eq!(shares[1].memory_bytes, Some((262_144 + 2_048) * 1024));
+    }
+
+    #[test]
+    fn test_drm_shares_new_client_has_no_utilization() {
+        let after = vec![parse_drm_fdinfo(AMDGPU_FDINFO).unwrap()];
+        let shares = drm_shares(&[], &after, Duration::from_millis(500));
+        assert_eq!(shares[0].utilization, None);
+        assert!(shares[0].memory_bytes.is_some());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_read_drm_clients_fake_proc() {
+        use crate::process_usage::read_drm_clients;
+        use std::fs;
+
+        let root = crate::test::scratch_dir("process_usage");
+        let fdinfo = root.join(PID.to_string()).join("fdinfo");
+        fs::create_dir_all(&fdinfo).unwrap();
+        fs::write(fdinfo.join("0"), "pos:\t0\nflags:\t02\n").unwrap();
+        // Two descriptors of the same client
+        fs::write(fdinfo.join("5"), AMDGPU_FDINFO).unwrap();
+        fs::write(fdinfo.join("6"), AMDGPU_FDINFO).unwrap();
+        fs::write(fdinfo.join("7"), I915_FDINFO).unwrap();
+
+        let clients = read_drm_clients(&root, PID);
+        assert_eq!(clients.len(), 2);
+        assert!(read_drm_clients(&root, PID + 1).is_empty());
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_total_memory_bytes() {
+        let usage = ProcessGpuUsage {
+            pid: PID,
+            gpus: vec![
+                GpuProcessShare {
+                    memory_bytes: Some(1024),
+                    ..Default::default()
+                },
+                GpuProcessShare::default(),
+                GpuProcessShare {
+                    memory_bytes: Some(u64::MAX),
+                    ..Default::default()
+                },
+            ],
+        };
+        assert_eq!(usage.total_memory_bytes(), u64::MAX);
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "windows")))]
+    #[test]
+    fn test_self_usage_unsupported() {
+        assert!(matches!(
+            crate::self_usage(),
+            Err(crate::GpuError::Unsupported(_))
+        ));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_self_usage_reports_own_pid() {
+        let usage = crate::self_usage().unwrap();
+        assert_eq!(usage.pid, std::process::id());
+    }
+}
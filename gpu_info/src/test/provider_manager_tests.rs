@@ -5,11 +5,15 @@
 
 #[cfg(test)]
 mod tests {
+    use crate::gpu_index::GpuIndex;
     use crate::gpu_info::{GpuInfo, GpuProvider, Result};
+    use crate::gpu_manager::GpuManager;
+    use crate::metric_source::Metric;
     use crate::provider_manager::GpuProviderManager;
     use crate::vendor::{IntelGpuType, Vendor};
     use std::sync::Arc;
     use std::sync::Mutex;
+    use std::time::Duration;
 
     /// Mock provider for testing different vendors
     #[derive(Debug)]
@@ -580,4 +584,105 @@ mod tests {
         println!("Unknown vendor update correctly failed");
         println!("Full provider manager workflow test completed successfully");
     }
+
+    /// Test that detection results follow vendor order, not registration order
+    #[test]
+    fn test_detection_order_is_deterministic() {
+        let mut manager = GpuProviderManager::new();
+        manager.register_provider(
+            Vendor::Intel(IntelGpuType::Integrated),
+            MockProvider::new(Vendor::Intel(IntelGpuType::Integrated), 1),
+        );
+        manager.register_provider(Vendor::Amd, MockProvider::new(Vendor::Amd, 1));
+        manager.register_provider(Vendor::Nvidia, MockProvider::new(Vendor::Nvidia, 1));
+        let vendors: Vec<Vendor> = manager
+            .detect_all_gpus()
+            .iter()
+            .map(|gpu| gpu.vendor)
+            .collect();
+        assert_eq!(
+            vendors,
+            vec![
+                Vendor::Nvidia,
+                Vendor::Amd,
+                Vendor::Intel(IntelGpuType::Integrated)
+            ]
+        );
+    }
+
+    /// Provider counting full and per-metric updates
+    struct MetricProvider {
+        updates: Arc<Mutex<(usize, Vec<Metric>)>>,
+    }
+
+    impl GpuProvider for MetricProvider {
+        fn detect_gpus(&self) -> Result<Vec<GpuInfo>> {
+            Ok(vec![GpuInfo::mock_amd()])
+        }
+
+        fn update_gpu(&self, _gpu: &mut GpuInfo) -> Result<()> {
+            self.updates.lock().unwrap().0 += 1;
+            Ok(())
+        }
+
+        fn update_metrics(&self, gpu: &mut GpuInfo, metrics: &[Metric]) -> Result<()> {
+            self.updates.lock().unwrap().1.extend_from_slice(metrics);
+            gpu.utilization = Some(99.0);
+            Ok(())
+        }
+
+        fn get_vendor(&self) -> Vendor {
+            Vendor::Amd
+        }
+    }
+
+    /// Test that a stale metric is refreshed through the per-metric update
+    #[test]
+    fn test_gpu_manager_refreshes_stale_metrics_only() {
+        let updates = Arc::new(Mutex::new((0, Vec::new())));
+        let mut providers = GpuProviderManager::new();
+        providers.register_provider(
+            Vendor::Amd,
+            MetricProvider {
+                updates: Arc::clone(&updates),
+            },
+        );
+        let mut manager = GpuManager::with_providers(providers);
+        manager.set_metric_ttl(Metric::Utilization.into(), Duration::ZERO);
+        manager.get_cached_gpu_at(GpuIndex(0)).unwrap();
+        assert_eq!(*updates.lock().unwrap(), (1, Vec::new()));
+
+        let gpu = manager.get_cached_gpu_at(GpuIndex(0)).unwrap();
+        assert_eq!(gpu.utilization, Some(99.0));
+        assert_eq!(*updates.lock().unwrap(), (1, vec![Metric::Utilization]));
+    }
+
+    /// Test that a `GpuManager` built from providers detects and refreshes through them
+    #[test]
+    fn test_gpu_manager_with_providers() {
+        let amd = MockProvider::new(Vendor::Amd, 2);
+        let amd_updates = Arc::clone(&amd.update_count);
+        let mut providers = GpuProviderManager::new();
+        providers.register_provider(
+            Vendor::Intel(IntelGpuType::Integrated),
+            MockProvider::new(Vendor::Intel(IntelGpuType::Integrated), 1),
+        );
+        providers.register_provider(Vendor::Amd, amd);
+        let mut manager = GpuManager::with_providers(providers);
+        assert_eq!(manager.gpu_count(), 3);
+        assert_eq!(
+            manager
+                .get_primary_gpu()
+                .and_then(|gpu| gpu.name_gpu.as_deref()),
+            Some("Mock AMD GPU #0")
+        );
+        manager.refresh_gpu_at(GpuIndex(1)).unwrap();
+        assert_eq!(*amd_updates.lock().unwrap(), 1);
+        assert_eq!(
+            manager.get_gpu_at(GpuIndex(1)).unwrap().temperature,
+            Some(56.0)
+        );
+        manager.refresh_all_gpus().unwrap();
+        assert_eq!(*amd_updates.lock().unwrap(), 3);
+    }
 }
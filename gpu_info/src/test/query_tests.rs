@@ -0,0 +1,189 @@
+//! Tests for sorting and top-N selection in `GpuQuery`
+
+#[cfg(test)]
+mod tests {
+    use crate::gpu_info::GpuInfo;
+    use crate::gpu_manager::GpuManager;
+    use crate::query::SortOrder;
+    use crate::vendor::Vendor;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    /// Manager over `gpus` whose metrics never change
+    fn manager(gpus: Vec<GpuInfo>) -> GpuManager {
+        GpuManager::with_updater(gpus, |_| Ok(()), Duration::from_secs(60))
+    }
+
+    fn names(gpus: &[Arc<GpuInfo>]) -> Vec<&str> {
+        gpus.iter().filter_map(|gpu| gpu.name_gpu()).collect()
+    }
+
+    fn named(mut gpu: GpuInfo, name: &str) -> GpuInfo {
+        gpu.name_gpu = Some(name.to_string());
+        gpu
+    }
+
+    /// NVIDIA at 65°C, AMD at 70°C, Intel at 55°C and an NVIDIA without a
+    /// temperature, in that order
+    fn mixed() -> Vec<GpuInfo> {
+        let mut no_temperature = named(GpuInfo::mock_nvidia(), "no temperature");
+        no_temperature.temperature = None;
+        vec![
+            no_temperature,
+            GpuInfo::mock_nvidia(),
+            GpuInfo::mock_amd(),
+            GpuInfo::mock_intel(),
+        ]
+    }
+
+    #[test]
+    fn test_sort_by_temperature() {
+        let manager = manager(mixed());
+        let ascending = manager
+            .query()
+            .sort_by_temperature(SortOrder::Ascending)
+            .collect();
+        assert_eq!(
+            names(&ascending),
+            vec![
+                "Intel UHD Graphics 630",
+                "NVIDIA GeForce RTX 3080",
+                "AMD Radeon RX 6800 XT",
+                "no temperature",
+            ]
+        );
+        let descending = manager
+            .query()
+            .sort_by_temperature(SortOrder::Descending)
+            .collect();
+        assert_eq!(
+            names(&descending),
+            vec![
+                "AMD Radeon RX 6800 XT",
+                "NVIDIA GeForce RTX 3080",
+                "Intel UHD Graphics 630",
+                "no temperature",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sort_by_utilization() {
+        let manager = manager(mixed());
+        let busiest = manager
+            .query()
+            .sort_by_utilization(SortOrder::Descending)
+            .take(2);
+        // Both NVIDIA GPUs run at 45%; the first one listed wins the tie
+        assert_eq!(
+            names(&busiest),
+            vec!["AMD Radeon RX 6800 XT", "no temperature"]
+        );
+    }
+
+    #[test]
+    fn test_sort_by_free_memory() {
+        // Intel reports no used memory, so its free memory is unknown
+        let manager = manager(mixed());
+        let free = |order| {
+            let gpus = manager.query().sort_by_free_memory(order).collect();
+            names(&gpus)
+                .into_iter()
+                .map(String::from)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            free(SortOrder::Descending),
+            vec![
+                "AMD Radeon RX 6800 XT",
+                "no temperature",
+                "NVIDIA GeForce RTX 3080",
+                "Intel UHD Graphics 630",
+            ]
+        );
+        assert_eq!(
+            free(SortOrder::Ascending),
+            vec![
+                "no temperature",
+                "NVIDIA GeForce RTX 3080",
+                "AMD Radeon RX 6800 XT",
+                "Intel UHD Graphics 630",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sort_is_stable() {
+        let manager = manager(vec![
+            named(GpuInfo::mock_nvidia(), "first"),
+            GpuInfo::mock_amd(),
+            named(GpuInfo::mock_nvidia(), "second"),
+            named(GpuInfo::mock_nvidia(), "third"),
+        ]);
+        for order in [SortOrder::Ascending, SortOrder::Descending] {
+            let gpus = manager
+                .query()
+                .vendor(Vendor::Nvidia)
+                .sort_by_temperature(order)
+                .collect();
+            assert_eq!(names(&gpus), vec!["first", "second", "third"]);
+            let first = manager
+                .query()
+                .vendor(Vendor::Nvidia)
+                .sort_by_temperature(order)
+                .first();
+            assert_eq!(first.unwrap().name_gpu(), Some("first"));
+        }
+    }
+
+    #[test]
+    fn test_first_and_take_follow_sort() {
+        let manager = manager(mixed());
+        let coolest = manager
+            .query()
+            .vendor(Vendor::Nvidia)
+            .sort_by_temperature(SortOrder::Ascending)
+            .first();
+        assert_eq!(coolest.unwrap().name_gpu(), Some("NVIDIA GeForce RTX 3080"));
+        let unsorted = manager.query().vendor(Vendor::Nvidia).first();
+        assert_eq!(unsorted.unwrap().name_gpu(), Some("no temperature"));
+
+        assert!(manager.query().take(0).is_empty());
+        assert_eq!(manager.query().take(10).len(), 4);
+        assert_eq!(
+            names(&manager.query().take(2)),
+            vec!["no temperature", "NVIDIA GeForce RTX 3080"]
+        );
+    }
+
+    #[test]
+    fn test_sort_by_key_puts_unordered_last() {
+        let mut not_a_number = named(GpuInfo::mock_intel(), "NaN");
+        not_a_number.utilization = Some(f32::NAN);
+        let mut manager = manager(vec![
+            not_a_number,
+            GpuInfo::mock_amd(),
+            GpuInfo::mock_nvidia(),
+        ]);
+        // NaN is a consistency violation; keep it so the sort sees it
+        manager.set_strict(false);
+        for order in [SortOrder::Ascending, SortOrder::Descending] {
+            let gpus = manager
+                .query()
+                .sort_by_key(|gpu| gpu.utilization, order)
+                .collect();
+            assert_eq!(names(&gpus)[2], "NaN");
+        }
+
+        // A later sort replaces the earlier one
+        let gpus = manager
+            .query()
+            .sort_by_temperature(SortOrder::Descending)
+            .sort_by_key(|gpu| gpu.max_clock_speed(), SortOrder::Ascending)
+            .collect();
+        assert_eq!(
+            names(&gpus),
+            vec!["NaN", "NVIDIA GeForce RTX 3080", "AMD Radeon RX 6800 XT"]
+        );
+    }
+}
@@ -0,0 +1,124 @@
+//! Tests for the redaction policies
+
+#[cfg(all(test, feature = "serde_json"))]
+mod tests {
+    use crate::gpu_info::{Confidence, GpuInfo};
+    use crate::jsonl::{Sample, SampleReader, SampleWriter};
+    use crate::monitoring::GpuMetricsSnapshot;
+    use crate::redaction::{
+        Redact, Redacted, Redaction, IDENTIFYING_FIELDS, NON_IDENTIFYING_FIELDS,
+    };
+    use crate::vendor::Vendor;
+    use serde_json::{json, Value};
+
+    const UUID: &str = "GPU-5a8f3b2e-1c4d-4e6f-9a0b-7c8d9e0f1a2b";
+
+    /// GPU with every field set
+    fn full_gpu() -> GpuInfo {
+        GpuInfo::builder()
+            .vendor(Vendor::Nvidia)
+            .name("NVIDIA GeForce RTX 4090")
+            .temperature(61.0)
+            .utilization(87.0)
+            .power_usage(312.5)
+            .core_clock(2520)
+            .memory_util(42.0)
+            .memory_clock(10501)
+            .active(true)
+            .power_limit(450.0)
+            .memory_total(24576)
+            .memory_used(16084)
+            .driver_version("550.54.14")
+            .max_clock_speed(3105)
+            .effective_core_clock(2505)
+            .uuid(UUID)
+            .bus_id("0000:01:00.0")
+            .confidence(Confidence::High)
+            .build()
+    }
+
+    fn serialize(policy: &Redaction) -> Value {
+        serde_json::to_value(Redacted::new(&full_gpu(), policy)).unwrap()
+    }
+
+    /// Every field other than the identifying ones, as serialized
+    fn metrics(mut value: Value) -> Value {
+        for field in IDENTIFYING_FIELDS {
+            value.as_object_mut().unwrap().remove(*field);
+        }
+        value
+    }
+
+    #[test]
+    fn test_every_field_is_classified() {
+        let serialized = serde_json::to_value(full_gpu()).unwrap();
+        for field in serialized.as_object().unwrap().keys() {
+            assert!(
+                IDENTIFYING_FIELDS.contains(&field.as_str())
+                    ^ NON_IDENTIFYING_FIELDS.contains(&field.as_str()),
+                "classify `{}` in IDENTIFYING_FIELDS or NON_IDENTIFYING_FIELDS",
+                field
+            );
+        }
+    }
+
+    #[test]
+    fn test_policy_none_keeps_identifiers() {
+        let value = serialize(&Redaction::None);
+        assert_eq!(value, serde_json::to_value(full_gpu()).unwrap());
+        assert_eq!(value["uuid"], json!(UUID));
+    }
+
+    #[test]
+    fn test_policy_hash_identifiers() {
+        let value = serialize(&Redaction::hash_identifiers("fleet-salt"));
+        // SHA-256 of "fleet-salt" followed by the UUID, truncated to 64 bits
+        assert_eq!(value["uuid"], json!("sha256:75b82278e860a67d"));
+        assert_eq!(
+            serialize(&Redaction::hash_identifiers("other-salt"))["uuid"],
+            json!("sha256:ff051eeb8bdfdc29")
+        );
+        assert_eq!(
+            metrics(value),
+            metrics(serialize(&Redaction::None)),
+            "metrics are exported unchanged"
+        );
+    }
+
+    #[test]
+    fn test_policy_strip_identifiers() {
+        let value = serialize(&Redaction::StripIdentifiers);
+        assert_eq!(value["uuid"], Value::Null);
+        assert_eq!(metrics(value), metrics(serialize(&Redaction::None)));
+    }
+
+    #[test]
+    fn test_redact_snapshot() {
+        let mut snapshot = GpuMetricsSnapshot {
+            gpus: vec![full_gpu(), GpuInfo::mock_amd()],
+        };
+        snapshot.redact(&Redaction::StripIdentifiers);
+        assert_eq!(snapshot.gpus[0].uuid, None);
+        assert_eq!(snapshot.gpus[0].bus_id.as_deref(), Some("0000:01:00.0"));
+        assert_eq!(snapshot.gpus[1], GpuInfo::mock_amd());
+    }
+
+    #[test]
+    fn test_sample_writer_redacts() {
+        let mut writer = SampleWriter::new(Vec::new())
+            .with_clock(|| 1_000)
+            .with_redaction(Redaction::hash_identifiers("fleet-salt"));
+        writer.write_sample(&full_gpu()).unwrap();
+        let bytes = writer.into_inner().unwrap();
+        let records: Vec<_> = SampleReader::new(bytes.as_slice())
+            .collect::<Result<_, _>>()
+            .unwrap();
+        match &records[0].sample {
+            Sample::Gpu(gpu) => {
+                assert_eq!(gpu.uuid.as_deref(), Some("sha256:75b82278e860a67d"));
+                assert_eq!(gpu.temperature, Some(61.0));
+            }
+            sample => panic!("unexpected sample {:?}", sample),
+        }
+    }
+}
@@ -0,0 +1,191 @@
+//! Tests for sharing one sampler between several monitors
+
+#[cfg(test)]
+mod tests {
+    use crate::gpu_info::{GpuInfo, Result};
+    use crate::monitoring::{AlertHandler, AlertType, GpuMonitor, GpuThresholds, MonitorConfig};
+    use crate::sampler::Sampler;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    /// Alert handler recording every alert it receives
+    #[derive(Debug, Default, Clone)]
+    struct RecordingHandler {
+        alerts: Arc<Mutex<Vec<AlertType>>>,
+    }
+
+    impl AlertHandler for RecordingHandler {
+        fn handle_alert(&self, alert: &AlertType) -> Result<()> {
+            self.alerts.lock().unwrap().push(alert.clone());
+            Ok(())
+        }
+
+        fn name(&self) -> &str {
+            "RecordingHandler"
+        }
+    }
+
+    /// Sampler whose source counts its calls and returns `gpu`
+    fn counting_sampler(gpu: GpuInfo) -> (Arc<Sampler>, Arc<AtomicU64>) {
+        let calls = Arc::new(AtomicU64::new(0));
+        let counter = Arc::clone(&calls);
+        let sampler = Sampler::with_source(move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+            Ok(vec![gpu.clone()])
+        });
+        (Arc::new(sampler), calls)
+    }
+
+    fn monitor(sampler: &Arc<Sampler>, interval_ms: u64) -> GpuMonitor {
+        let config =
+            MonitorConfig::default().with_polling_interval(Duration::from_millis(interval_ms));
+        GpuMonitor::with_sampler(config, Arc::clone(sampler))
+    }
+
+    fn wait_for_ticks(sampler: &Sampler, ticks: u64) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while sampler.tick_count() < ticks && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(5));
+        }
+    }
+
+    #[test]
+    fn test_two_subscribers_share_one_update_per_tick() {
+        let (sampler, calls) = counting_sampler(GpuInfo::mock_nvidia());
+        let slow = monitor(&sampler, 50);
+        let fast = monitor(&sampler, 10);
+
+        slow.start_monitoring().unwrap();
+        assert_eq!(sampler.interval(), Some(Duration::from_millis(50)));
+        fast.start_monitoring().unwrap();
+        assert_eq!(sampler.interval(), Some(Duration::from_millis(10)));
+        assert_eq!(sampler.subscriber_count(), 2);
+        wait_for_ticks(&sampler, 10);
+
+        fast.stop_monitoring().unwrap();
+        assert_eq!(sampler.interval(), Some(Duration::from_millis(50)));
+        let fast_measurements = fast.get_stats().total_measurements;
+        slow.stop_monitoring().unwrap();
+        assert_eq!(sampler.interval(), None);
+        assert_eq!(sampler.subscriber_count(), 0);
+
+        let ticks = sampler.tick_count();
+        assert!(ticks >= 10);
+        assert_eq!(calls.load(Ordering::SeqCst), ticks);
+        // `slow` was subscribed for every tick, `fast` for a subset of them
+        assert_eq!(slow.get_stats().total_measurements, ticks);
+        assert!(fast_measurements > 0 && fast_measurements <= ticks);
+        assert_eq!(fast.get_stats().total_measurements, fast_measurements);
+        assert_eq!(
+            slow.get_gpu_history(0).unwrap().timestamps.len() as u64,
+            ticks.min(MonitorConfig::default().history_size as u64)
+        );
+
+        let deadline = Instant::now() + Duration::from_secs(1);
+        while sampler.is_running() && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(5));
+        }
+        assert!(!sampler.is_running());
+    }
+
+    #[test]
+    fn test_subscribers_evaluate_thresholds_independently() {
+        let mut gpu = GpuInfo::mock_nvidia();
+        gpu.temperature = Some(85.0);
+        let (sampler, _) = counting_sampler(gpu);
+        let tight = GpuMonitor::with_sampler(
+            MonitorConfig::default()
+                .with_polling_interval(Duration::from_millis(10))
+                .with_thresholds(GpuThresholds {
+                    temperature_warning: 70.0,
+                    temperature_critical: 80.0,
+                    ..Default::default()
+                }),
+            Arc::clone(&sampler),
+        );
+        let loose = GpuMonitor::with_sampler(
+            MonitorConfig::default()
+                .with_polling_interval(Duration::from_millis(100))
+                .with_thresholds(GpuThresholds {
+                    temperature_warning: 90.0,
+                    temperature_critical: 95.0,
+                    ..Default::default()
+                }),
+            Arc::clone(&sampler),
+        );
+        let tight_alerts = RecordingHandler::default();
+        let loose_alerts = RecordingHandler::default();
+        tight
+            .add_alert_handler(Box::new(tight_alerts.clone()))
+            .unwrap();
+        loose
+            .add_alert_handler(Box::new(loose_alerts.clone()))
+            .unwrap();
+
+        tight.start_monitoring().unwrap();
+        loose.start_monitoring().unwrap();
+        let start = sampler.tick_count();
+        wait_for_ticks(&sampler, start + 3);
+        loose.stop_monitoring().unwrap();
+        tight.stop_monitoring().unwrap();
+
+        let is_temperature = |alert: &AlertType| {
+            matches!(
+                alert,
+                AlertType::HighTemperature { .. } | AlertType::CriticalTemperature { .. }
+            )
+        };
+        let tight_alerts = tight_alerts.alerts.lock().unwrap();
+        assert!(tight_alerts
+            .iter()
+            .any(|alert| matches!(alert, AlertType::CriticalTemperature { .. })));
+        assert!(!loose_alerts
+            .alerts
+            .lock()
+            .unwrap()
+            .iter()
+            .any(is_temperature));
+        assert!(loose.get_stats().total_measurements > 0);
+    }
+
+    #[test]
+    fn test_paused_subscriber_does_not_drive_interval() {
+        let (sampler, calls) = counting_sampler(GpuInfo::mock_amd());
+        let fast = monitor(&sampler, 10);
+        let idle = monitor(&sampler, 60 * 60 * 1000);
+        fast.start_monitoring().unwrap();
+        idle.start_monitoring().unwrap();
+        assert_eq!(sampler.interval(), Some(Duration::from_millis(10)));
+
+        fast.pause();
+        assert_eq!(sampler.interval(), Some(Duration::from_secs(60 * 60)));
+        idle.pause();
+        assert_eq!(sampler.interval(), None);
+        // Let a tick that was already in flight finish
+        thread::sleep(Duration::from_millis(50));
+        let paused_calls = calls.load(Ordering::SeqCst);
+        thread::sleep(Duration::from_millis(50));
+        assert_eq!(calls.load(Ordering::SeqCst), paused_calls);
+
+        fast.resume();
+        assert_eq!(sampler.interval(), Some(Duration::from_millis(10)));
+        wait_for_ticks(&sampler, paused_calls + 2);
+        assert!(sampler.tick_count() >= paused_calls + 2);
+        fast.stop_monitoring().unwrap();
+        idle.stop_monitoring().unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), sampler.tick_count());
+    }
+
+    #[test]
+    fn test_legacy_monitor_owns_its_sampler() {
+        let first = GpuMonitor::with_defaults();
+        let second = GpuMonitor::with_defaults();
+        assert!(!Arc::ptr_eq(first.sampler(), second.sampler()));
+        let shared =
+            GpuMonitor::with_sampler(MonitorConfig::default(), Arc::clone(first.sampler()));
+        assert!(Arc::ptr_eq(first.sampler(), shared.sampler()));
+        assert_eq!(first.sampler().subscriber_count(), 0);
+    }
+}
@@ -0,0 +1,125 @@
+//! Tests for deterministic GPU ordering and unordered comparison
+
+#[cfg(test)]
+mod tests {
+    use crate::gpu_info::{sort_stable, GpuInfo};
+    use crate::testing::{assert_gpus_eq_unordered, diff_unordered};
+    use crate::vendor::{Vendor, VirtualKind};
+
+    #[test]
+    fn test_sort_key_ignores_metrics() {
+        let mut hot = GpuInfo::mock_nvidia();
+        hot.temperature = Some(95.0);
+        hot.utilization = Some(100.0);
+        hot.core_clock = Some(2100);
+        assert!(hot.sort_key() == GpuInfo::mock_nvidia().sort_key());
+    }
+
+    #[test]
+    fn test_ties_broken_by_identity_fields() {
+        // No bus ID or UUID in GpuInfo: identical models are ordered by
+        // total memory, then driver version
+        let model = |memory_total: u32, driver: &str| {
+            GpuInfo::builder()
+                .vendor(Vendor::Nvidia)
+                .name("NVIDIA  GeForce RTX 3060")
+                .memory_total(memory_total)
+                .driver_version(driver)
+                .build()
+        };
+        let mut gpus = vec![
+            model(12288, "550.54"),
+            model(8192, "550.54"),
+            model(12288, "535.104"),
+        ];
+        sort_stable(&mut gpus);
+        let order: Vec<_> = gpus
+            .iter()
+            .map(|gpu| (gpu.memory_total.unwrap(), gpu.driver_version().unwrap()))
+            .collect();
+        assert_eq!(
+            order,
+            vec![(8192, "550.54"), (12288, "535.104"), (12288, "550.54")]
+        );
+
+        // Names differing only in case and whitespace tie
+        let mut renamed = model(8192, "550.54");
+        renamed.name_gpu = Some("nvidia geforce rtx 3060".to_string());
+        assert!(renamed.sort_key() == model(8192, "550.54").sort_key());
+    }
+
+    #[test]
+    fn test_fully_unknown_gpus_sort_last() {
+        let unknown_with_name = GpuInfo::builder().name("Matrox G200eW").build();
+        let virtual_gpu = GpuInfo::write_vendor(Vendor::Virtual(VirtualKind::Virtio));
+        let mut gpus = vec![
+            GpuInfo::unknown(),
+            unknown_with_name.clone(),
+            virtual_gpu.clone(),
+            GpuInfo::mock_nvidia(),
+            GpuInfo::unknown(),
+            GpuInfo::mock_amd(),
+        ];
+        sort_stable(&mut gpus);
+        assert_eq!(
+            gpus,
+            vec![
+                GpuInfo::mock_amd(),
+                GpuInfo::mock_nvidia(),
+                virtual_gpu,
+                unknown_with_name,
+                GpuInfo::unknown(),
+                GpuInfo::unknown(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_assert_gpus_eq_unordered() {
+        assert_gpus_eq_unordered(
+            &[
+                GpuInfo::mock_nvidia(),
+                GpuInfo::mock_amd(),
+                GpuInfo::mock_intel(),
+            ],
+            &[
+                GpuInfo::mock_intel(),
+                GpuInfo::mock_nvidia(),
+                GpuInfo::mock_amd(),
+            ],
+        );
+        assert_gpus_eq_unordered(&[], &[]);
+    }
+
+    #[test]
+    fn test_diff_unordered_names_mismatched_fields() {
+        let mut changed = GpuInfo::mock_amd();
+        changed.temperature = Some(99.0);
+        changed.memory_used = None;
+        let diff = diff_unordered(
+            &[GpuInfo::mock_nvidia(), GpuInfo::mock_amd()],
+            &[changed, GpuInfo::mock_nvidia()],
+        )
+        .expect("lists differ");
+        assert!(diff.contains("temperature: left="), "{}", diff);
+        assert!(diff.contains("memory_used: left="), "{}", diff);
+        assert!(!diff.contains("name_gpu"), "{}", diff);
+
+        let mut changed = GpuInfo::mock_nvidia();
+        changed.uuid = Some("GPU-1".to_string());
+        changed.fan_speed = Some(40);
+        let diff = diff_unordered(&[GpuInfo::mock_nvidia()], &[changed]).expect("lists differ");
+        assert!(diff.contains("uuid: left="), "{}", diff);
+        assert!(diff.contains("fan_speed: left="), "{}", diff);
+
+        let diff = diff_unordered(&[GpuInfo::mock_nvidia()], &[]).unwrap();
+        assert!(diff.contains("length: left=1 right=0"), "{}", diff);
+        assert!(diff.contains("only in left"), "{}", diff);
+    }
+
+    #[test]
+    #[should_panic(expected = "GPU lists differ")]
+    fn test_assert_gpus_eq_unordered_panics_on_mismatch() {
+        assert_gpus_eq_unordered(&[GpuInfo::mock_nvidia()], &[GpuInfo::mock_amd()]);
+    }
+}
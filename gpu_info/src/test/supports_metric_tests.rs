@@ -0,0 +1,92 @@
+//! Tests for the `GpuProvider::supports_metric` capability query
+
+#[cfg(test)]
+mod tests {
+    use crate::gpu_info::{GpuInfo, GpuProvider, Result};
+    use crate::metric_source::Metric;
+    use crate::providers::macos::{MacosBackend, MacosConfig, MacosProvider};
+    use crate::vendor::Vendor;
+
+    /// Provider relying on the default implementation
+    struct DefaultProvider;
+
+    impl GpuProvider for DefaultProvider {
+        fn detect_gpus(&self) -> Result<Vec<GpuInfo>> {
+            Ok(vec![GpuInfo::mock_nvidia()])
+        }
+
+        fn update_gpu(&self, _gpu: &mut GpuInfo) -> Result<()> {
+            Ok(())
+        }
+
+        fn get_vendor(&self) -> Vendor {
+            Vendor::Nvidia
+        }
+    }
+
+    /// Provider without a temperature sensor
+    struct NoTemperatureProvider;
+
+    impl GpuProvider for NoTemperatureProvider {
+        fn detect_gpus(&self) -> Result<Vec<GpuInfo>> {
+            Ok(vec![GpuInfo::mock_amd()])
+        }
+
+        fn update_gpu(&self, gpu: &mut GpuInfo) -> Result<()> {
+            gpu.utilization = Some(25.0);
+            Ok(())
+        }
+
+        fn get_vendor(&self) -> Vendor {
+            Vendor::Amd
+        }
+
+        fn supports_metric(&self, metric: Metric) -> bool {
+            metric != Metric::Temperature
+        }
+    }
+
+    #[test]
+    fn test_default_supports_every_metric() {
+        assert!(DefaultProvider.supports_metric(Metric::Temperature));
+        assert!(DefaultProvider.supports_metric(Metric::MemoryUsed));
+    }
+
+    #[test]
+    fn test_provider_declaring_no_temperature() {
+        let providers: Vec<Box<dyn GpuProvider>> =
+            vec![Box::new(DefaultProvider), Box::new(NoTemperatureProvider)];
+        let with_temperature: Vec<Vendor> = providers
+            .iter()
+            .filter(|provider| provider.supports_metric(Metric::Temperature))
+            .map(|provider| provider.get_vendor())
+            .collect();
+        assert_eq!(with_temperature, vec![Vendor::Nvidia]);
+        assert!(NoTemperatureProvider.supports_metric(Metric::Utilization));
+    }
+
+    #[test]
+    fn test_macos_system_profiler_has_no_temperature() {
+        let config = MacosConfig {
+            preferred_backend: MacosBackend::SystemProfiler,
+            fallback_enabled: false,
+            ..Default::default()
+        };
+        let provider = MacosProvider::with_config(config).unwrap();
+        assert!(!provider.supports_metric(Metric::Temperature));
+        assert!(!provider.supports_metric(Metric::Utilization));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_linux_intel_does_not_report_memory() {
+        use crate::providers::linux::{amd::AmdLinuxProvider, intel::IntelLinuxProvider};
+
+        let intel = IntelLinuxProvider::new();
+        assert!(intel.supports_metric(Metric::Temperature));
+        for metric in [Metric::MemoryTotal, Metric::MemoryUsed, Metric::MemoryUtil] {
+            assert!(!intel.supports_metric(metric), "{:?}", metric);
+        }
+        assert!(AmdLinuxProvider::new().supports_metric(Metric::MemoryUsed));
+    }
+}
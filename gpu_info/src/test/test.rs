@@ -591,6 +591,7 @@ mod gpu_info_tests {
             vendor: Vendor::Nvidia,
             name_gpu: Some("NVIDIA GeForce RTX 3080".to_string()),
             temperature: Some(70.5),
+            temperatures: None,
             utilization: Some(85.0),
             power_usage: Some(120.5),
             core_clock: Some(1500),
@@ -602,8 +603,22 @@ mod gpu_info_tests {
             memory_used: None,
             driver_version: Some("470.57.02".to_string()),
             max_clock_speed: Some(2100),
+            effective_core_clock: Some(1450),
+            fan_speed: None,
+            fan_speed_rpm: None,
+            encoder_util: None,
+            decoder_util: None,
+            throttle_reasons: None,
+            uuid: None,
+            bus_id: None,
+            processes: None,
+            ecc_errors: None,
+            metric_sources: Default::default(),
+            confidence: Default::default(),
+            adapter_luid: None,
         };
         let display_output = format!("{}", gpu_info);
+        assert!(display_output.contains("Effective Core Clock: 1450"));
         assert!(display_output.contains("NVIDIA"));
         assert!(display_output.contains("NVIDIA GeForce RTX 3080"));
         assert!(display_output.contains("70.5"));
@@ -715,6 +730,7 @@ mod gpu_info_tests {
             vendor: Vendor::Nvidia,
             name_gpu: Some("Test GPU".to_string()),
             temperature: None,
+            temperatures: None,
             utilization: Some(75.0),
             power_usage: Some(100.0),
             core_clock: Some(1500),
@@ -726,6 +742,19 @@ mod gpu_info_tests {
             memory_used: None,
             driver_version: None,
             max_clock_speed: None,
+            effective_core_clock: None,
+            fan_speed: None,
+            fan_speed_rpm: None,
+            encoder_util: None,
+            decoder_util: None,
+            throttle_reasons: None,
+            uuid: None,
+            bus_id: None,
+            processes: None,
+            ecc_errors: None,
+            metric_sources: Default::default(),
+            confidence: Default::default(),
+            adapter_luid: None,
         };
         assert_eq!(gpu.name_gpu(), Some("Test GPU"));
         assert!(matches!(gpu.vendor(), Vendor::Nvidia));
@@ -838,7 +867,7 @@ fn test_get_vendor_nvidia() {
 }
 
 #[cfg(test)]
-#[cfg(target_os = "linux")]
+#[cfg(all(target_os = "linux", feature = "vendor-nvidia"))]
 mod linux_nvidia_provider_test {
     use crate::gpu_info::GpuProvider;
     use crate::providers::linux::NvidiaLinuxProvider;
@@ -0,0 +1,48 @@
+//! Tests for unknown GPUs and the reason detection failed
+
+#[cfg(test)]
+mod tests {
+    use crate::gpu_info::{GpuError, GpuInfo};
+    use crate::unknown as unknown_platform;
+    use crate::vendor::Vendor;
+
+    #[test]
+    fn test_unknown_platform_attaches_unsupported_reason() {
+        let gpu = unknown_platform::info_gpu();
+        assert_eq!(gpu.vendor, Vendor::Unknown);
+        match gpu.detection_error() {
+            Some(GpuError::Unsupported(what)) => assert!(what.contains(std::env::consts::OS)),
+            other => panic!("expected Unsupported, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_unknown_has_no_reason() {
+        assert!(GpuInfo::unknown().detection_error().is_none());
+        assert!(GpuInfo::mock_nvidia().detection_error().is_none());
+    }
+
+    #[test]
+    fn test_reason_survives_clone_and_ignores_equality() {
+        let gpu = GpuInfo::unknown_with_reason(GpuError::DriverNotInstalled);
+        let cloned = gpu.clone();
+        assert!(matches!(
+            cloned.detection_error(),
+            Some(GpuError::DriverNotInstalled)
+        ));
+        let mut target = GpuInfo::mock_amd();
+        target.clone_from(&gpu);
+        assert!(target.detection_error().is_some());
+        assert_eq!(gpu, GpuInfo::unknown());
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn test_reason_is_not_serialized() {
+        let gpu = GpuInfo::unknown_with_reason(GpuError::GpuNotFound);
+        let json = serde_json::to_string(&gpu).unwrap();
+        assert!(!json.contains("detection_error"));
+        let restored: GpuInfo = serde_json::from_str(&json).unwrap();
+        assert!(restored.detection_error().is_none());
+    }
+}
@@ -0,0 +1,99 @@
+//! Tests for vendor detection from PCI IDs and names
+
+#[cfg(test)]
+mod tests {
+    use crate::vendor::{determine_vendor_from_name, IntelGpuType, Vendor, VirtualKind};
+    use std::str::FromStr;
+
+    #[test]
+    fn test_virtual_pci_ids_map_to_virtual_kind() {
+        assert_eq!(
+            Vendor::from_pci_id(0x1af4),
+            Vendor::Virtual(VirtualKind::Virtio)
+        );
+        assert_eq!(
+            Vendor::from_pci_id(0x1b36),
+            Vendor::Virtual(VirtualKind::Qxl)
+        );
+        assert_eq!(
+            Vendor::from_pci_id(0x15ad),
+            Vendor::Virtual(VirtualKind::VmwareSvga)
+        );
+        assert!(Vendor::from_pci_id(0x15ad).is_virtual());
+    }
+
+    #[test]
+    fn test_hardware_pci_ids_are_unchanged() {
+        assert_eq!(Vendor::from_pci_id(0x10de), Vendor::Nvidia);
+        assert_eq!(Vendor::from_pci_id(0x1002), Vendor::Amd);
+        assert_eq!(
+            Vendor::from_pci_id(0x8086),
+            Vendor::Intel(IntelGpuType::Unknown)
+        );
+        assert_eq!(Vendor::from_pci_id(0x106b), Vendor::Apple);
+        assert_eq!(Vendor::from_pci_id(0x0000), Vendor::Unknown);
+        assert!(!Vendor::Nvidia.is_virtual());
+    }
+
+    #[test]
+    fn test_ansi_color() {
+        assert_eq!(Vendor::Nvidia.ansi_color(), "\x1b[32m");
+        assert_eq!(Vendor::Amd.ansi_color(), "\x1b[31m");
+        assert_eq!(
+            Vendor::Intel(IntelGpuType::Discrete).ansi_color(),
+            Vendor::Intel(IntelGpuType::Integrated).ansi_color()
+        );
+        let vendors = [
+            Vendor::Nvidia,
+            Vendor::Amd,
+            Vendor::Intel(IntelGpuType::Unknown),
+            Vendor::Apple,
+            Vendor::Virtual(VirtualKind::Virtio),
+            Vendor::Unknown,
+        ];
+        for vendor in vendors {
+            let color = vendor.ansi_color();
+            assert!(
+                color.starts_with("\x1b[") && color.ends_with('m'),
+                "{}",
+                vendor
+            );
+        }
+    }
+
+    #[test]
+    fn test_virtual_adapter_names() {
+        assert_eq!(
+            determine_vendor_from_name("Red Hat, Inc. Virtio GPU"),
+            Vendor::Virtual(VirtualKind::Virtio)
+        );
+        assert_eq!(
+            determine_vendor_from_name("Red Hat, Inc. QXL paravirtual graphic card"),
+            Vendor::Virtual(VirtualKind::Qxl)
+        );
+        assert_eq!(
+            determine_vendor_from_name("VMware SVGA II Adapter"),
+            Vendor::Virtual(VirtualKind::VmwareSvga)
+        );
+        assert_eq!(
+            Vendor::from_str("virtio").unwrap(),
+            Vendor::Virtual(VirtualKind::Virtio)
+        );
+        assert_eq!(
+            Vendor::from_str("QXL").unwrap(),
+            Vendor::Virtual(VirtualKind::Qxl)
+        );
+    }
+
+    #[test]
+    fn test_virtual_display() {
+        assert_eq!(
+            Vendor::Virtual(VirtualKind::Virtio).to_string(),
+            "VIRTUAL (virtio-gpu)"
+        );
+        assert_eq!(
+            Vendor::Virtual(VirtualKind::VmwareSvga).to_string(),
+            "VIRTUAL (VMware SVGA)"
+        );
+    }
+}
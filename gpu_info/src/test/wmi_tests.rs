@@ -0,0 +1,300 @@
+//! Tests for `Win32_VideoController` mapping and multi-adapter merging
+
+#[cfg(test)]
+mod tests {
+    use crate::gpu_info::{Confidence, GpuInfo};
+    use crate::providers::wmi::{
+        attach_bus_ids, count_whea_events, merge_adapters, parse_pci_location, primary_vendor,
+        whea_bus_id, VideoController, PCI_LOCATION_QUERY, VIDEO_CONTROLLER_QUERY, WHEA_EVENT_QUERY,
+    };
+    use crate::vendor::{IntelGpuType, Vendor};
+
+    const INTEL_PNP: &str = r"PCI\VEN_8086&DEV_3E9B&SUBSYS_09261028&REV_02\3&11583659&0&10";
+    const NVIDIA_PNP: &str = r"PCI\VEN_10DE&DEV_2520&SUBSYS_0A831028&REV_A1\4&2C3D1F6A&0&0008";
+
+    /// Adapters of an Intel iGPU + NVIDIA dGPU laptop over RDP
+    fn hybrid() -> Vec<VideoController> {
+        vec![
+            VideoController {
+                name: "Intel(R) UHD Graphics 630".to_string(),
+                adapter_ram: Some(1_073_741_824),
+                driver_version: Some("31.0.101.2115".to_string()),
+                status: Some("OK".to_string()),
+                pnp_device_id: Some(INTEL_PNP.to_string()),
+                bus_id: None,
+            },
+            VideoController {
+                name: "NVIDIA GeForce RTX 3060 Laptop GPU".to_string(),
+                adapter_ram: Some(4_293_918_720),
+                driver_version: Some("31.0.15.3623".to_string()),
+                status: Some("OK".to_string()),
+                pnp_device_id: Some(NVIDIA_PNP.to_string()),
+                bus_id: None,
+            },
+            VideoController {
+                name: "Microsoft Remote Display Adapter".to_string(),
+                adapter_ram: None,
+                driver_version: Some("10.0.22621.1".to_string()),
+                status: Some("OK".to_string()),
+                pnp_device_id: Some(
+                    r"SWD\REMOTEDISPLAYENUM\RDPIDD_INDIRECTDISPLAY&SESSIONID_0001".to_string(),
+                ),
+                bus_id: None,
+            },
+        ]
+    }
+
+    /// One of two identical NVIDIA adapters, at `bus_id`
+    fn nvidia_adapter(bus_id: &str) -> VideoController {
+        VideoController {
+            name: "NVIDIA GeForce RTX 4090".to_string(),
+            pnp_device_id: Some(NVIDIA_PNP.to_string()),
+            bus_id: Some(bus_id.to_string()),
+            ..Default::default()
+        }
+    }
+
+    /// NVIDIA backend GPU at `bus_id` reading `temperature`
+    fn nvidia_gpu(bus_id: Option<&str>, temperature: f32) -> GpuInfo {
+        let builder = GpuInfo::builder()
+            .vendor(Vendor::Nvidia)
+            .temperature(temperature);
+        match bus_id {
+            Some(bus_id) => builder.bus_id(bus_id).build(),
+            None => builder.build(),
+        }
+    }
+
+    fn no_backends(_: Vendor) -> Vec<GpuInfo> {
+        Vec::new()
+    }
+
+    #[test]
+    fn test_parse_pci_location() {
+        assert_eq!(
+            parse_pci_location("PCI bus 1, device 0, function 0").as_deref(),
+            Some("0000:01:00.0")
+        );
+        assert_eq!(
+            parse_pci_location("PCI bus 193, device 31, function 3").as_deref(),
+            Some("0000:c1:1f.3")
+        );
+        assert_eq!(parse_pci_location("PCI bus 1, device 0"), None);
+        assert_eq!(parse_pci_location("PCI bus x, device 0, function 0"), None);
+        assert_eq!(parse_pci_location("Internal"), None);
+        assert_eq!(parse_pci_location(""), None);
+    }
+
+    #[test]
+    fn test_attach_bus_ids_by_pnp_device_id() {
+        let mut controllers = hybrid();
+        let locations = [
+            (
+                NVIDIA_PNP.to_ascii_lowercase(),
+                "PCI bus 1, device 0, function 0".to_string(),
+            ),
+            (
+                INTEL_PNP.to_string(),
+                "PCI bus 0, device 2, function 0".to_string(),
+            ),
+        ];
+        attach_bus_ids(&mut controllers, &locations);
+        assert_eq!(controllers[0].bus_id.as_deref(), Some("0000:00:02.0"));
+        assert_eq!(controllers[1].bus_id.as_deref(), Some("0000:01:00.0"));
+        assert_eq!(controllers[2].bus_id, None);
+        assert_eq!(
+            controllers[1].to_gpu_info().bus_id.as_deref(),
+            Some("0000:01:00.0")
+        );
+    }
+
+    #[test]
+    fn test_vendor_falls_back_to_pci_id() {
+        let controller = VideoController {
+            name: "Display adapter".to_string(),
+            pnp_device_id: Some(r"PCI\VEN_1002&DEV_73BF&SUBSYS_0E3A1002&REV_C1\6&1".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(controller.vendor(), Vendor::Amd);
+        assert_eq!(VideoController::default().vendor(), Vendor::Unknown);
+    }
+
+    #[test]
+    fn test_hybrid_without_backends_keeps_both_gpus() {
+        let gpus = merge_adapters(&hybrid(), no_backends);
+        assert_eq!(gpus.len(), 2);
+        assert_eq!(gpus[0].vendor, Vendor::Intel(IntelGpuType::Integrated));
+        assert_eq!(gpus[0].memory_total, Some(1024));
+        assert_eq!(gpus[0].active, Some(true));
+        assert_eq!(gpus[0].confidence(), Confidence::Medium);
+        assert_eq!(gpus[1].vendor, Vendor::Nvidia);
+        assert_eq!(gpus[1].driver_version.as_deref(), Some("31.0.15.3623"));
+    }
+
+    #[test]
+    fn test_backend_metrics_are_merged() {
+        let mut probed = Vec::new();
+        let gpus = merge_adapters(&hybrid(), |vendor| {
+            probed.push(vendor);
+            match vendor {
+                Vendor::Nvidia => vec![GpuInfo::builder()
+                    .vendor(Vendor::Nvidia)
+                    .name("NVIDIA GeForce RTX 3060 Laptop GPU")
+                    .memory_total(6144)
+                    .temperature(52.0)
+                    .build()],
+                _ => Vec::new(),
+            }
+        });
+        assert_eq!(probed.len(), 2);
+        assert_eq!(gpus.len(), 2);
+        // The backend's VRAM wins over the 4 GiB WMI cap
+        assert_eq!(gpus[1].memory_total, Some(6144));
+        assert_eq!(gpus[1].temperature, Some(52.0));
+        assert_eq!(gpus[1].driver_version.as_deref(), Some("31.0.15.3623"));
+    }
+
+    #[test]
+    fn test_backend_gpus_missing_from_wmi_are_appended() {
+        let gpus = merge_adapters(&hybrid(), |vendor| match vendor {
+            Vendor::Nvidia => vec![GpuInfo::builder().vendor(Vendor::Nvidia).build(); 2],
+            _ => Vec::new(),
+        });
+        assert_eq!(gpus.len(), 3);
+        assert_eq!(gpus[2].vendor, Vendor::Nvidia);
+        assert_eq!(gpus[2].name_gpu, None);
+
+        let gpus = merge_adapters(&[], |vendor| match vendor {
+            Vendor::Amd => vec![GpuInfo::builder().vendor(Vendor::Amd).build()],
+            _ => Vec::new(),
+        });
+        assert_eq!(gpus.len(), 1);
+        assert_eq!(gpus[0].vendor, Vendor::Amd);
+    }
+
+    #[test]
+    fn test_adapters_match_backend_gpus_by_bus_id() {
+        // WMI and NVML list two identical cards in opposite orders
+        let adapters = [
+            nvidia_adapter("0000:02:00.0"),
+            nvidia_adapter("0000:01:00.0"),
+        ];
+        let gpus = merge_adapters(&adapters, |_| {
+            vec![
+                nvidia_gpu(Some("0000:01:00.0"), 40.0),
+                nvidia_gpu(Some("0000:02:00.0"), 80.0),
+            ]
+        });
+        assert_eq!(gpus.len(), 2);
+        assert_eq!(gpus[0].bus_id.as_deref(), Some("0000:02:00.0"));
+        assert_eq!(gpus[0].temperature, Some(80.0));
+        assert_eq!(gpus[1].temperature, Some(40.0));
+        assert!(gpus
+            .iter()
+            .all(|gpu| gpu.name_gpu.as_deref() == Some("NVIDIA GeForce RTX 4090")));
+    }
+
+    #[test]
+    fn test_adapters_at_other_locations_are_not_paired() {
+        let adapters = [nvidia_adapter("0000:02:00.0")];
+        let gpus = merge_adapters(&adapters, |_| {
+            vec![
+                nvidia_gpu(Some("0000:01:00.0"), 40.0),
+                nvidia_gpu(None, 60.0),
+            ]
+        });
+        // The GPU without a location is paired; the other one is appended
+        assert_eq!(gpus.len(), 2);
+        assert_eq!(gpus[0].temperature, Some(60.0));
+        assert_eq!(gpus[0].bus_id.as_deref(), Some("0000:02:00.0"));
+        assert_eq!(gpus[1].temperature, Some(40.0));
+        assert_eq!(gpus[1].name_gpu, None);
+    }
+
+    #[test]
+    fn test_primary_vendor_prefers_discrete() {
+        assert_eq!(primary_vendor(&hybrid()), Some(Vendor::Nvidia));
+        let amd_and_intel = [
+            VideoController {
+                name: "Intel(R) UHD Graphics 770".to_string(),
+                ..Default::default()
+            },
+            VideoController {
+                name: "AMD Radeon RX 7600".to_string(),
+                ..Default::default()
+            },
+        ];
+        assert_eq!(primary_vendor(&amd_and_intel), Some(Vendor::Amd));
+        assert_eq!(
+            primary_vendor(&amd_and_intel[..1]),
+            Some(Vendor::Intel(IntelGpuType::Integrated))
+        );
+    }
+
+    #[test]
+    fn test_primary_vendor_ignores_software_adapters() {
+        let basic = VideoController {
+            name: "Microsoft Basic Display Adapter".to_string(),
+            pnp_device_id: Some(r"ROOT\BasicDisplay\0000".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(primary_vendor(&[basic]), None);
+        assert_eq!(primary_vendor(&[]), None);
+    }
+
+    #[test]
+    fn test_wql_queries_select_matching_properties() {
+        assert!(VIDEO_CONTROLLER_QUERY.contains("PNPDeviceID"));
+        assert!(PCI_LOCATION_QUERY.contains("DeviceID"));
+        assert!(PCI_LOCATION_QUERY.contains("Location"));
+        assert!(WHEA_EVENT_QUERY.contains("Message"));
+        assert!(WHEA_EVENT_QUERY.contains("WHEA-Logger"));
+    }
+
+    /// WHEA event 17, a corrected PCI Express error of the GPU at 01:00.0
+    const WHEA_GPU_EVENT: &str = "A corrected hardware error has occurred.\r\n\r\n\
+        Component: PCI Express Endpoint\r\n\
+        Error Source: Advanced Error Reporting (PCI Express)\r\n\r\n\
+        Bus:Device:Function: 0x1:0x0:0x0\r\n\
+        Vendor ID:Device ID: 0x10DE:0x2520\r\n\
+        Class Code: 0x30000\r\n";
+
+    /// WHEA event 17 a root port reported for its link
+    const WHEA_ROOT_PORT_EVENT: &str = "A corrected hardware error has occurred.\r\n\r\n\
+        Component: PCI Express Root Port\r\n\
+        Error Source: Advanced Error Reporting (PCI Express)\r\n\r\n\
+        Primary Bus:Device:Function: 0x0:0x1C:0x4\r\n\
+        Secondary Bus:Device:Function: 0x0:0x0:0x0\r\n";
+
+    /// WHEA event 19, a corrected machine check without a PCI device
+    const WHEA_CPU_EVENT: &str = "A corrected hardware error has occurred.\r\n\r\n\
+        Reported by component: Processor Core\r\n\
+        Error Source: Corrected Machine Check\r\n\
+        Error Type: Cache Hierarchy Error\r\n";
+
+    #[test]
+    fn test_whea_bus_id() {
+        assert_eq!(whea_bus_id(WHEA_GPU_EVENT).as_deref(), Some("0000:01:00.0"));
+        assert_eq!(
+            whea_bus_id(WHEA_ROOT_PORT_EVENT).as_deref(),
+            Some("0000:00:1c.4")
+        );
+        assert_eq!(whea_bus_id(WHEA_CPU_EVENT), None);
+        assert_eq!(whea_bus_id("Bus:Device:Function: 1:0:0"), None);
+    }
+
+    #[test]
+    fn test_count_whea_events_by_bus_id() {
+        let messages = [
+            WHEA_GPU_EVENT,
+            WHEA_ROOT_PORT_EVENT,
+            WHEA_CPU_EVENT,
+            WHEA_GPU_EVENT,
+        ];
+        assert_eq!(count_whea_events(&messages, "0000:01:00.0"), 2);
+        // NVML reports bus ids with an eight-digit domain
+        assert_eq!(count_whea_events(&messages, "00000000:01:00.0"), 2);
+        assert_eq!(count_whea_events(&messages, "0000:02:00.0"), 0);
+        assert_eq!(count_whea_events::<&str>(&[], "0000:01:00.0"), 0);
+    }
+}
@@ -0,0 +1,126 @@
+//! Helpers for testing code that works with [`GpuInfo`].
+//!
+//! Available with the `testing` feature, intended for use from
+//! `[dev-dependencies]`:
+//!
+//! ```toml
+//! [dev-dependencies]
+//! gpu_info = { version = "*", features = ["testing"] }
+//! ```
+use crate::gpu_info::{sort_stable, GpuInfo};
+use std::fmt::Write;
+
+/// Asserts that two GPU lists contain the same GPUs, ignoring their order.
+///
+/// Both lists are ordered with [`sort_stable()`] and then compared pairwise.
+/// On mismatch, the panic message lists every differing field of every
+/// differing pair, instead of two full `Debug` dumps.
+///
+/// # Panics
+///
+/// Panics if the lists differ in length or in any field of any GPU.
+///
+/// # Examples
+///
+/// ```
+/// use gpu_info::testing::assert_gpus_eq_unordered;
+/// use gpu_info::GpuInfo;
+///
+/// assert_gpus_eq_unordered(
+///     &[GpuInfo::mock_nvidia(), GpuInfo::mock_amd()],
+///     &[GpuInfo::mock_amd(), GpuInfo::mock_nvidia()],
+/// );
+/// ```
+#[track_caller]
+pub fn assert_gpus_eq_unordered(left: &[GpuInfo], right: &[GpuInfo]) {
+    if let Some(diff) = diff_unordered(left, right) {
+        panic!("GPU lists differ (ignoring order):\n{}", diff);
+    }
+}
+
+/// Describes the differences between two GPU lists, ignoring their order.
+///
+/// Returns `None` if both lists contain the same GPUs.
+pub fn diff_unordered(left: &[GpuInfo], right: &[GpuInfo]) -> Option<String> {
+    let mut left = left.to_vec();
+    let mut right = right.to_vec();
+    sort_stable(&mut left);
+    sort_stable(&mut right);
+
+    let mut diff = String::new();
+    if left.len() != right.len() {
+        let _ = writeln!(diff, "  length: left={} right={}", left.len(), right.len());
+    }
+    for (index, (l, r)) in left.iter().zip(&right).enumerate() {
+        let fields = field_diff(l, r);
+        if !fields.is_empty() {
+            let _ = writeln!(diff, "  [{}] {}:", index, l.format_name_gpu());
+            for (field, l, r) in fields {
+                let _ = writeln!(diff, "    {}: left={} right={}", field, l, r);
+            }
+        }
+    }
+    for (side, extra) in [("left", &left), ("right", &right)] {
+        for gpu in extra.iter().skip(left.len().min(right.len())) {
+            let _ = writeln!(diff, "  only in {}: {:?}", side, gpu);
+        }
+    }
+    if diff.is_empty() {
+        None
+    } else {
+        Some(diff)
+    }
+}
+
+/// Lists the fields that differ between `left` and `right` as `(name, left, right)`.
+///
+/// Every field is compared. `GpuInfo` is destructured without `..`, so
+/// adding a field fails to compile until it is listed here.
+fn field_diff(left: &GpuInfo, right: &GpuInfo) -> Vec<(&'static str, String, String)> {
+    let mut fields = Vec::new();
+    macro_rules! compare {
+        ($($field:ident),*) => {
+            let GpuInfo { $($field: _,)* } = left;
+            $(
+                if left.$field != right.$field {
+                    fields.push((
+                        stringify!($field),
+                        format!("{:?}", left.$field),
+                        format!("{:?}", right.$field),
+                    ));
+                }
+            )*
+        };
+    }
+    compare!(
+        vendor,
+        name_gpu,
+        temperature,
+        temperatures,
+        utilization,
+        power_usage,
+        core_clock,
+        memory_util,
+        memory_clock,
+        active,
+        power_limit,
+        memory_total,
+        memory_used,
+        driver_version,
+        max_clock_speed,
+        effective_core_clock,
+        fan_speed,
+        fan_speed_rpm,
+        encoder_util,
+        decoder_util,
+        throttle_reasons,
+        uuid,
+        bus_id,
+        adapter_luid,
+        processes,
+        ecc_errors,
+        metric_sources,
+        confidence
+    );
+    fields
+}
@@ -3,9 +3,21 @@
 //! This module provides stub implementations for platforms that are not
 //! explicitly supported. All functions return empty or no-op results.
 
-use crate::gpu_info::GpuInfo;
+use crate::gpu_info::{GpuError, GpuInfo};
 use log::warn;
 
+/// Returns an unknown GPU on unsupported platforms.
+///
+/// The GPU carries a [`GpuError::Unsupported`] reason naming the platform,
+/// available through [`GpuInfo::detection_error()`].
+pub fn info_gpu() -> GpuInfo {
+    warn!("Unknown platform: no GPU info available.");
+    GpuInfo::unknown_with_reason(GpuError::Unsupported(format!(
+        "GPU detection on {}",
+        std::env::consts::OS
+    )))
+}
+
 /// Initializes GPU detection on unsupported platforms.
 ///
 /// This function always returns an empty vector and logs a warning,
@@ -31,6 +31,8 @@ pub enum Vendor {
     Intel(IntelGpuType),
     /// Apple Silicon GPUs (M1, M2, M3, etc.)
     Apple,
+    /// Virtual display adapters emulated by a hypervisor
+    Virtual(VirtualKind),
     /// Unknown or unrecognized GPU vendor
     Unknown,
 }
@@ -55,6 +57,98 @@ pub enum IntelGpuType {
     #[default]
     Unknown,
 }
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+/// Kind of virtual display adapter.
+///
+/// Reported in virtual machines, where the guest sees an emulated or
+/// paravirtualized GPU instead of the host hardware.
+///
+/// This enum is `#[non_exhaustive]` to allow adding new adapter
+/// kinds in future versions without breaking changes.
+pub enum VirtualKind {
+    /// virtio-gpu (QEMU/KVM, crosvm), PCI vendor `0x1af4`
+    Virtio,
+    /// QXL paravirtual graphics (QEMU/SPICE), PCI vendor `0x1b36`
+    Qxl,
+    /// VMware SVGA II, PCI vendor `0x15ad`
+    VmwareSvga,
+}
+
+/// PCI vendor ID of NVIDIA Corporation
+pub const PCI_VENDOR_NVIDIA: u16 = 0x10de;
+/// PCI vendor ID of Advanced Micro Devices (ATI)
+pub const PCI_VENDOR_AMD: u16 = 0x1002;
+/// PCI vendor ID of Intel Corporation
+pub const PCI_VENDOR_INTEL: u16 = 0x8086;
+/// PCI vendor ID of Apple Inc.
+pub const PCI_VENDOR_APPLE: u16 = 0x106b;
+/// PCI vendor ID of Red Hat, Inc. (virtio devices)
+pub const PCI_VENDOR_VIRTIO: u16 = 0x1af4;
+/// PCI vendor ID of Red Hat, Inc. (QEMU emulated devices such as QXL)
+pub const PCI_VENDOR_QEMU: u16 = 0x1b36;
+/// PCI vendor ID of VMware
+pub const PCI_VENDOR_VMWARE: u16 = 0x15ad;
+
+impl Vendor {
+    /// Determines the vendor from a PCI vendor ID.
+    ///
+    /// Intel GPUs are reported as [`IntelGpuType::Unknown`], since the type
+    /// cannot be told from the vendor ID alone. Unrecognized IDs map to
+    /// [`Vendor::Unknown`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gpu_info::vendor::{Vendor, VirtualKind};
+    ///
+    /// assert_eq!(Vendor::from_pci_id(0x10de), Vendor::Nvidia);
+    /// assert_eq!(Vendor::from_pci_id(0x1af4), Vendor::Virtual(VirtualKind::Virtio));
+    /// assert_eq!(Vendor::from_pci_id(0xffff), Vendor::Unknown);
+    /// ```
+    pub fn from_pci_id(vendor_id: u16) -> Self {
+        match vendor_id {
+            PCI_VENDOR_NVIDIA => Vendor::Nvidia,
+            PCI_VENDOR_AMD => Vendor::Amd,
+            PCI_VENDOR_INTEL => Vendor::Intel(IntelGpuType::Unknown),
+            PCI_VENDOR_APPLE => Vendor::Apple,
+            PCI_VENDOR_VIRTIO => Vendor::Virtual(VirtualKind::Virtio),
+            PCI_VENDOR_QEMU => Vendor::Virtual(VirtualKind::Qxl),
+            PCI_VENDOR_VMWARE => Vendor::Virtual(VirtualKind::VmwareSvga),
+            _ => Vendor::Unknown,
+        }
+    }
+
+    /// Returns `true` for virtual display adapters.
+    pub fn is_virtual(&self) -> bool {
+        matches!(self, Vendor::Virtual(_))
+    }
+
+    /// Returns the ANSI escape sequence selecting the vendor's brand color.
+    ///
+    /// Reset the color with `"\x1b[0m"` after the colored text.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gpu_info::Vendor;
+    ///
+    /// let vendor = Vendor::Nvidia;
+    /// println!("{}{}\x1b[0m", vendor.ansi_color(), vendor);
+    /// ```
+    pub fn ansi_color(&self) -> &'static str {
+        match self {
+            Vendor::Nvidia => "\x1b[32m",
+            Vendor::Amd => "\x1b[31m",
+            Vendor::Intel(_) => "\x1b[34m",
+            Vendor::Apple => "\x1b[37m",
+            Vendor::Virtual(_) => "\x1b[35m",
+            Vendor::Unknown => "\x1b[90m",
+        }
+    }
+}
+
 /// Converts a string to a Vendor enum
 impl Default for Vendor {
     fn default() -> Self {
@@ -70,6 +164,7 @@ impl Display for Vendor {
             Vendor::Amd => write!(f, "AMD"),
             Vendor::Intel(gpu_type) => write!(f, "INTEL ({})", gpu_type),
             Vendor::Apple => write!(f, "APPLE"),
+            Vendor::Virtual(kind) => write!(f, "VIRTUAL ({})", kind),
             Vendor::Unknown => write!(f, "UNKNOWN"),
         }
     }
@@ -84,6 +179,29 @@ impl Display for IntelGpuType {
     }
 }
 
+impl Display for VirtualKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            VirtualKind::Virtio => write!(f, "virtio-gpu"),
+            VirtualKind::Qxl => write!(f, "QXL"),
+            VirtualKind::VmwareSvga => write!(f, "VMware SVGA"),
+        }
+    }
+}
+
+/// Determines the virtual adapter kind from a GPU name, if it is one.
+fn virtual_kind_from_name(name_lower: &str) -> Option<VirtualKind> {
+    if name_lower.contains("virtio") {
+        Some(VirtualKind::Virtio)
+    } else if name_lower.contains("qxl") {
+        Some(VirtualKind::Qxl)
+    } else if name_lower.contains("vmware") || name_lower.contains("svga") {
+        Some(VirtualKind::VmwareSvga)
+    } else {
+        None
+    }
+}
+
 /// Error type for parsing a `Vendor` from a string.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ParseVendorError {
@@ -110,6 +228,7 @@ impl std::error::Error for ParseVendorError {}
 /// - AMD: "amd", "radeon", "ati"
 /// - Intel: "intel", "arc", "iris", "uhd"
 /// - Apple: "apple", "m1", "m2", "m3"
+/// - Virtual: "virtio", "qxl", "vmware", "svga"
 ///
 /// # Examples
 ///
@@ -188,6 +307,11 @@ impl FromStr for Vendor {
             return Ok(Vendor::Apple);
         }
 
+        // Virtual display adapters
+        if let Some(kind) = virtual_kind_from_name(trimmed) {
+            return Ok(Vendor::Virtual(kind));
+        }
+
         // Unknown - return error instead of Unknown variant
         Err(ParseVendorError {
             input: s.to_string(),
@@ -223,6 +347,10 @@ impl FromStr for Vendor {
 /// ```
 pub fn determine_vendor_from_name(name: &str) -> Vendor {
     let name_lower = name.to_lowercase();
+    // Virtual adapters, checked first as their names mention the host vendor
+    if let Some(kind) = virtual_kind_from_name(&name_lower) {
+        return Vendor::Virtual(kind);
+    }
     // Apple Silicon
     if name_lower.contains("apple")
         || name_lower.contains("m1")
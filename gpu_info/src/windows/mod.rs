@@ -1,12 +1,21 @@
-use crate::gpu_info::GpuInfo;
-use crate::providers::{amd, intel, nvidia};
+use crate::cancel::CancelToken;
+use crate::detection_report::DetectionReport;
+use crate::gpu_info::{GpuError, GpuInfo};
+#[cfg(feature = "vendor-amd")]
+use crate::providers::amd;
+#[cfg(feature = "vendor-intel")]
+use crate::providers::intel;
+#[cfg(feature = "vendor-nvidia")]
+use crate::providers::nvidia;
+use crate::providers::wmi;
 use crate::vendor::Vendor;
-use log::{error, info};
-use std::process::Command;
+use log::{error, info, warn};
+
 /// Returns information about the GPU.
 /// Automatically detects GPU vendor and returns appropriate information.
 pub fn info_gpu() -> GpuInfo {
     match detect_gpu_vendor() {
+        #[cfg(feature = "vendor-nvidia")]
         Some(Vendor::Nvidia) => match nvidia::detect_nvidia_gpus() {
             Ok(nvidia_gpus) if !nvidia_gpus.is_empty() => {
                 let mut gpu = nvidia_gpus[0].clone();
@@ -18,6 +27,9 @@ pub fn info_gpu() -> GpuInfo {
                 error!("Failed to get NVIDIA GPU information");
             }
         },
+        #[cfg(not(feature = "vendor-nvidia"))]
+        Some(Vendor::Nvidia) => return pruned("vendor-nvidia"),
+        #[cfg(feature = "vendor-amd")]
         Some(Vendor::Amd) => {
             if let Ok(amd_gpus) = amd::detect_amd_gpus() {
                 if !amd_gpus.is_empty() {
@@ -28,6 +40,9 @@ pub fn info_gpu() -> GpuInfo {
                 }
             }
         }
+        #[cfg(not(feature = "vendor-amd"))]
+        Some(Vendor::Amd) => return pruned("vendor-amd"),
+        #[cfg(feature = "vendor-intel")]
         Some(Vendor::Intel(_)) => {
             use crate::gpu_info::GpuProvider;
             let provider = crate::providers::windows::intel::IntelWindowsProvider::new();
@@ -44,38 +59,110 @@ pub fn info_gpu() -> GpuInfo {
                 }
             }
         }
+        #[cfg(not(feature = "vendor-intel"))]
+        Some(Vendor::Intel(_)) => return pruned("vendor-intel"),
         _ => {
             error!("No supported GPU detected");
         }
     }
     error!("Failed to get GPU information");
-    GpuInfo::unknown()
+    GpuInfo::unknown_with_reason(GpuError::GpuNotFound)
 }
+/// Returns every display adapter listed by `Win32_VideoController`.
+///
+/// Each adapter is handed to its vendor backend (NVML, ADL or the Intel
+/// provider) and merged with the metrics it reports, so hybrid systems such
+/// as an Intel iGPU plus an NVIDIA dGPU return both GPUs. Adapters whose
+/// backend is unavailable or disabled by a `vendor-*` feature keep their WMI
+/// data. Software adapters like the Microsoft Basic Display Adapter are
+/// skipped.
+pub fn get_all_gpus() -> Vec<GpuInfo> {
+    get_all_gpus_with_cancel(&CancelToken::new(), &mut DetectionReport::new())
+}
+/// [`get_all_gpus()`], stopping between queries if `token` is cancelled
+///
+/// The vendor backends record their diagnostics in `report`.
+pub(crate) fn get_all_gpus_with_cancel(
+    token: &CancelToken,
+    report: &mut DetectionReport,
+) -> Vec<GpuInfo> {
+    if token.is_cancelled() {
+        return Vec::new();
+    }
+    let controllers = wmi::video_controllers().unwrap_or_else(|e| {
+        warn!("Failed to query Win32_VideoController: {}", e);
+        Vec::new()
+    });
+    if token.is_cancelled() {
+        return Vec::new();
+    }
+    wmi::merge_adapters(&controllers, |vendor| {
+        if token.is_cancelled() {
+            return Vec::new();
+        }
+        detect_vendor_gpus(vendor, token, report)
+    })
+}
+/// GPUs the backend of `vendor` reports
+#[allow(unused_variables)]
+fn detect_vendor_gpus(
+    vendor: Vendor,
+    token: &CancelToken,
+    report: &mut DetectionReport,
+) -> Vec<GpuInfo> {
+    match vendor {
+        #[cfg(feature = "vendor-nvidia")]
+        Vendor::Nvidia => nvidia::detect_nvidia_gpus().unwrap_or_default(),
+        #[cfg(feature = "vendor-amd")]
+        Vendor::Amd => amd::detect_amd_gpus().unwrap_or_default(),
+        #[cfg(feature = "vendor-intel")]
+        Vendor::Intel(_) => {
+            use crate::gpu_info::GpuProvider;
+            crate::providers::windows::intel::IntelWindowsProvider::new()
+                .detect_gpus_with_cancel(token, report)
+                .unwrap_or_default()
+        }
+        _ => Vec::new(),
+    }
+}
+/// Unknown GPU for a vendor whose backend was pruned at compile time
+#[cfg(not(all(
+    feature = "vendor-nvidia",
+    feature = "vendor-amd",
+    feature = "vendor-intel"
+)))]
+fn pruned(feature: &str) -> GpuInfo {
+    error!(
+        "GPU detected, but its backend is disabled by the `{}` feature",
+        feature
+    );
+    GpuInfo::unknown_with_reason(GpuError::FeatureNotEnabled(feature.to_string()))
+}
+/// Vendor of the primary GPU, from an in-process WMI query
 fn detect_gpu_vendor() -> Option<Vendor> {
-    let output = Command::new("powershell")
-        .args([
-            "Get-WmiObject",
-            "Win32_VideoController",
-            "|",
-            "Select-Object",
-            "Name",
-            "|",
-            "Format-List",
-        ])
-        .output()
-        .ok()?;
-    let output_str = String::from_utf8_lossy(&output.stdout);
-    if output_str.contains("NVIDIA") {
-        info!("Detected NVIDIA GPU");
-        Some(Vendor::Nvidia)
-    } else if output_str.contains("AMD") || output_str.contains("Radeon") {
-        info!("Detected AMD GPU");
-        Some(Vendor::Amd)
-    } else if output_str.contains("Intel") {
-        info!("Detected Intel GPU");
-        Some(Vendor::Intel(crate::vendor::IntelGpuType::Integrated))
-    } else {
-        info!("Unknown GPU vendor");
-        None
+    let controllers = match wmi::query_video_controllers_com(wmi::VIDEO_CONTROLLER_QUERY) {
+        Ok(controllers) => controllers,
+        Err(e) => {
+            warn!("Failed to query Win32_VideoController: {}", e);
+            return None;
+        }
+    };
+    match wmi::primary_vendor(&controllers) {
+        Some(Vendor::Nvidia) => {
+            info!("Detected NVIDIA GPU");
+            Some(Vendor::Nvidia)
+        }
+        Some(Vendor::Amd) => {
+            info!("Detected AMD GPU");
+            Some(Vendor::Amd)
+        }
+        Some(Vendor::Intel(_)) => {
+            info!("Detected Intel GPU");
+            Some(Vendor::Intel(crate::vendor::IntelGpuType::Integrated))
+        }
+        _ => {
+            info!("Unknown GPU vendor");
+            None
+        }
     }
 }
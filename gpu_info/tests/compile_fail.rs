@@ -0,0 +1,20 @@
+//! Compile tests for misuse the type system is meant to prevent
+//!
+//! The expected `.stderr` files hold rustc diagnostics, whose wording
+//! changes between releases. They were generated with stable 1.95, so the
+//! test is skipped on older and non-stable toolchains; regenerate them with
+//! `TRYBUILD=overwrite` when bumping that version. The cases that must
+//! compile have no expected output and run on every toolchain.
+
+#[rustversion::attr(any(not(stable), before(1.95)), ignore)]
+#[test]
+fn compile_fail() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/*.rs");
+}
+
+#[test]
+fn compile_pass() {
+    let cases = trybuild::TestCases::new();
+    cases.pass("tests/ui/pass/*.rs");
+}
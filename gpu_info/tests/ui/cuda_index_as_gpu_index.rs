@@ -0,0 +1,9 @@
+//! A CUDA device ordinal cannot be used as a manager index.
+
+use gpu_info::{CudaIndex, GpuManager};
+
+fn main() {
+    let manager = GpuManager::new();
+    let ordinal = CudaIndex(1);
+    let _ = manager.get_gpu_at(ordinal);
+}
@@ -0,0 +1,9 @@
+//! A manager index cannot be converted as if it were a CUDA device ordinal.
+
+use gpu_info::{GpuIndex, GpuManager};
+
+fn main() {
+    let manager = GpuManager::new();
+    let index = GpuIndex(0);
+    let _ = manager.cuda_to_gpu_index(index);
+}
@@ -0,0 +1,12 @@
+//! A `GpuInfo` can be built with struct update syntax outside the crate.
+
+use gpu_info::{GpuInfo, Vendor};
+
+fn main() {
+    let gpu = GpuInfo {
+        vendor: Vendor::Nvidia,
+        temperature: Some(65.0),
+        ..GpuInfo::unknown()
+    };
+    assert_eq!(gpu.vendor, Vendor::Nvidia);
+}
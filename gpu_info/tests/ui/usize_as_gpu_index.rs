@@ -0,0 +1,9 @@
+//! A plain `usize` cannot be used as a manager index.
+
+use gpu_info::GpuManager;
+
+fn main() {
+    let manager = GpuManager::new();
+    let index: usize = 1;
+    let _ = manager.get_gpu_at(index);
+}
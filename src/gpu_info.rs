@@ -1 +0,0 @@
-pub use ::gpu_info::*;
@@ -1,3 +1,38 @@
+//! System and GPU information in one crate.
+//!
+//! The [`system`] module re-exports the operating system API of
+//! `system_info_lib`, and the [`gpu`] module the entry points of `gpu_info`.
+//! Both crates are also re-exported whole for everything else.
+//!
+//! GPU information and `system_cli` are enabled by the `full` feature, which
+//! is on by default.
+//!
+//! # Examples
+//!
+//! ```
+//! use sysinfo_utils::system::{self, InfoExt};
+//!
+//! let info = system::get();
+//! println!("{} ({}-bit: {})", info, info.bit_depth(), info.is_64bit());
+//! ```
+
+/// Operating system information from `system_info_lib`.
+pub mod system {
+    pub use system_info_lib::{
+        get, BitDepth, Info, InfoBuilder, InfoExt, SystemVersion, SystemVersionExt, Type,
+    };
+}
+
+/// GPU information from `gpu_info`.
+#[cfg(feature = "full")]
+pub mod gpu {
+    #[cfg(any(target_os = "linux", target_os = "macos", target_os = "windows"))]
+    pub use gpu_info::get_all;
+    pub use gpu_info::{get, GpuInfo, GpuManager};
+}
+
+#[cfg(feature = "full")]
 pub use gpu_info;
+#[cfg(feature = "full")]
 pub use system_cli;
 pub use system_info_lib;
@@ -1 +0,0 @@
-pub use system_info_lib::*;
@@ -1,4 +1,8 @@
+use crate::color::ColorChoice;
+use crate::output::OutputFormat;
 use clap::Parser;
+#[cfg(feature = "tui")]
+use clap::Subcommand;
 
 #[derive(Parser)]
 #[clap(about, version)]
@@ -21,4 +25,39 @@ pub struct Options {
     /// Show GPU information.
     #[clap(short = 'g', long = "gpu")]
     pub gpu: bool,
+    /// List processes using the GPU (NVIDIA only).
+    #[clap(long)]
+    pub gpu_processes: bool,
+    /// Show size and usage of mounted filesystems.
+    #[clap(long)]
+    pub storage: bool,
+    /// Show network interfaces.
+    #[clap(long)]
+    pub network: bool,
+    /// Clear the screen and re-print GPU information until Ctrl-C.
+    #[clap(long)]
+    pub watch: bool,
+    /// Refresh interval of --watch in milliseconds.
+    #[clap(long, default_value_t = 1000, value_parser = clap::value_parser!(u64).range(1..))]
+    pub interval: u64,
+    /// When to color the output.
+    #[clap(long, value_enum, default_value_t = ColorChoice::Auto)]
+    pub color: ColorChoice,
+    /// Output format; `json` prints the requested sections as one object.
+    #[clap(long, value_enum, default_value_t = OutputFormat::Text)]
+    pub format: OutputFormat,
+    #[cfg(feature = "tui")]
+    #[clap(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[cfg(feature = "tui")]
+#[derive(Subcommand)]
+pub enum Command {
+    /// Show a live per-GPU dashboard (q to quit, p to pause).
+    Top {
+        /// Refresh interval in milliseconds.
+        #[clap(long, default_value_t = 1000)]
+        interval_ms: u64,
+    },
 }
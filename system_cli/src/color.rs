@@ -0,0 +1,70 @@
+use clap::ValueEnum;
+use gpu_info::GpuThresholds;
+use std::io::IsTerminal;
+
+/// Resets all ANSI colors and styles.
+const RESET: &str = "\x1b[0m";
+const GREEN: &str = "\x1b[32m";
+const YELLOW: &str = "\x1b[33m";
+const RED: &str = "\x1b[31m";
+
+/// When to emit ANSI colors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum ColorChoice {
+    /// Color when stdout is a terminal and `NO_COLOR` is not set.
+    #[default]
+    Auto,
+    /// Always color.
+    Always,
+    /// Never color.
+    Never,
+}
+
+impl ColorChoice {
+    /// Returns whether output should be colored.
+    pub fn enabled(self) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => {
+                std::env::var_os("NO_COLOR").map_or(true, |value| value.is_empty())
+                    && std::io::stdout().is_terminal()
+            }
+        }
+    }
+}
+
+/// Wraps text in ANSI colors, or passes it through unchanged when disabled.
+#[derive(Debug, Clone, Copy)]
+pub struct Painter {
+    enabled: bool,
+}
+
+impl Painter {
+    /// Creates a painter for the given color choice.
+    pub fn new(choice: ColorChoice) -> Self {
+        Painter {
+            enabled: choice.enabled(),
+        }
+    }
+
+    /// Wraps `text` in the ANSI escape sequence `color`.
+    pub fn paint(&self, text: &str, color: &str) -> String {
+        if self.enabled {
+            format!("{}{}{}", color, text, RESET)
+        } else {
+            text.to_owned()
+        }
+    }
+
+    /// Colors a temperature by severity, using the default monitoring thresholds.
+    pub fn temperature(&self, text: &str, celsius: Option<f32>) -> String {
+        let thresholds = GpuThresholds::default();
+        match celsius {
+            Some(celsius) if celsius >= thresholds.temperature_critical => self.paint(text, RED),
+            Some(celsius) if celsius >= thresholds.temperature_warning => self.paint(text, YELLOW),
+            Some(_) => self.paint(text, GREEN),
+            None => text.to_owned(),
+        }
+    }
+}
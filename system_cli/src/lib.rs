@@ -1,2 +1,8 @@
 pub mod cli;
+pub mod color;
+pub mod output;
+#[cfg(feature = "tui")]
+pub mod top;
 pub use cli::*;
+pub use color::*;
+pub use output::*;
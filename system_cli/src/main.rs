@@ -1,19 +1,56 @@
 //! System information CLI tool
 //!
-//! Displays OS and GPU information.
+//! Displays OS, GPU, storage and network information.
 use clap::Parser;
-use system_cli::Options;
+use gpu_info::{GpuIndex, GpuInfo, GpuManager, GpuProcessInfo};
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+use system_cli::{Options, OutputFormat, Painter};
+use system_info_lib::{Info, InterfaceInfo, MountInfo};
+
+const GIB: f64 = 1024.0 * 1024.0 * 1024.0;
+/// Clears the terminal and moves the cursor to the top left.
+const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+const HIDE_CURSOR: &str = "\x1b[?25l";
+const SHOW_CURSOR: &str = "\x1b[?25h";
+/// How often --watch checks for Ctrl-C while waiting for the next refresh.
+const WATCH_POLL: Duration = Duration::from_millis(50);
 
 fn main() {
     env_logger::init();
     let options = Options::parse();
+    #[cfg(feature = "tui")]
+    if let Some(system_cli::Command::Top { interval_ms }) = options.command {
+        if let Err(e) = system_cli::top::run(std::time::Duration::from_millis(interval_ms)) {
+            eprintln!("top: {}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+    let painter = Painter::new(options.color);
 
     let show_all = options.all
         || (!options.system_type
             && !options.system_version
             && !options.bit_depth
             && !options.architecture
-            && !options.gpu);
+            && !options.gpu
+            && !options.gpu_processes
+            && !options.storage
+            && !options.network);
+
+    if options.watch {
+        watch_gpus(&painter, Duration::from_millis(options.interval));
+        return;
+    }
+
+    if options.format == OutputFormat::Json {
+        print_json(&options, show_all);
+        return;
+    }
 
     // Show OS information
     if show_all
@@ -26,11 +63,17 @@ fn main() {
 
         if show_all {
             println!("OS information:");
-            println!("  Type: {}", info.system_type());
-            println!("  Version: {}", info.version());
+            println!("  Type: {}", system_type(&info));
+            match info.update_build_revision() {
+                Some(ubr) => println!("  Version: {}.{}", info.version(), ubr),
+                None => println!("  Version: {}", info.version()),
+            }
             if let Some(edition) = info.edition() {
                 println!("  Edition: {}", edition);
             }
+            if let Some(display_version) = info.display_version() {
+                println!("  Display version: {}", display_version);
+            }
             if let Some(codename) = info.codename() {
                 println!("  Codename: {}", codename);
             }
@@ -38,9 +81,12 @@ fn main() {
             if let Some(arch) = info.architecture() {
                 println!("  Architecture: {}", arch);
             }
+            if let Some(kernel) = info.kernel_version() {
+                println!("  Kernel: {}", kernel);
+            }
         } else {
             if options.system_type {
-                println!("OS type: {}", info.system_type());
+                println!("OS type: {}", system_type(&info));
             }
             if options.system_version {
                 println!("OS version: {}", info.version());
@@ -64,15 +110,296 @@ fn main() {
             println!();
         }
         println!("GPU information:");
-        println!("  Vendor: {}", gpu.vendor());
-        println!("  Name: {}", gpu.format_name_gpu());
-        println!("  Driver: {}", gpu.format_driver_version());
-        println!("  Temperature: {} C", gpu.format_temperature());
-        println!("  Utilization: {}%", gpu.format_utilization());
-        println!("  Core Clock: {} MHz", gpu.format_core_clock());
-        println!("  Memory: {} GB", gpu.format_memory_total());
-        println!("  Memory Usage: {}%", gpu.format_memory_util());
-        println!("  Power: {} W", gpu.format_power_usage());
-        println!("  Status: {}", gpu.format_active());
+        print_gpu(&gpu, &painter);
+    }
+
+    // Show processes using the GPU
+    if options.gpu_processes {
+        if options.gpu
+            || options.system_type
+            || options.system_version
+            || options.bit_depth
+            || options.architecture
+        {
+            println!();
+        }
+        println!("GPU processes:");
+        print_gpu_processes(&gpu_info::get());
+    }
+
+    // Show storage information
+    if options.storage {
+        if options.gpu_processes
+            || options.gpu
+            || options.system_type
+            || options.system_version
+            || options.bit_depth
+            || options.architecture
+        {
+            println!();
+        }
+        println!("Storage:");
+        match system_info_lib::get().storage() {
+            Some(mounts) => print_storage(mounts),
+            None => println!("  N/A"),
+        }
+    }
+
+    // Show network information
+    if options.network {
+        if options.storage
+            || options.gpu_processes
+            || options.gpu
+            || options.system_type
+            || options.system_version
+            || options.bit_depth
+            || options.architecture
+        {
+            println!();
+        }
+        println!("Network:");
+        match system_info_lib::get().network_interfaces() {
+            Some(interfaces) => print_network(interfaces),
+            None => println!("  N/A"),
+        }
+    }
+}
+
+/// Prints the fields of `gpu`, one indented line each.
+fn print_gpu(gpu: &GpuInfo, painter: &Painter) {
+    let vendor = gpu.vendor();
+    println!(
+        "  Vendor: {}",
+        painter.paint(&vendor.to_string(), vendor.ansi_color())
+    );
+    println!("  Name: {}", gpu.format_name_gpu());
+    println!("  Driver: {}", gpu.format_driver_version());
+    println!(
+        "  Temperature: {}",
+        painter.temperature(
+            &format!("{} C", gpu.format_temperature()),
+            gpu.temperature()
+        )
+    );
+    println!("  Utilization: {}%", gpu.format_utilization());
+    println!("  Core Clock: {} MHz", gpu.format_core_clock());
+    println!("  Memory: {} GB", gpu.format_memory_total());
+    println!("  Memory Usage: {}%", gpu.format_memory_util());
+    println!("  Power: {} W", gpu.format_power_usage());
+    if gpu.has_fan_speed() {
+        println!("  Fan: {}", gpu.format_fan_speed());
+    }
+    if gpu.encoder_util().is_some() || gpu.decoder_util().is_some() {
+        println!(
+            "  Video Engines: encoder {}, decoder {}",
+            gpu.format_encoder_util(),
+            gpu.format_decoder_util()
+        );
+    }
+    println!("  Status: {}", gpu.format_active());
+}
+
+/// Re-prints every GPU each `interval` until SIGINT.
+///
+/// Readings come from a [`GpuManager`] whose cache lives for one interval.
+/// The cursor is hidden while watching and shown again on exit.
+fn watch_gpus(painter: &Painter, interval: Duration) {
+    let stop = Arc::new(AtomicBool::new(false));
+    if let Err(e) = signal_hook::flag::register(signal_hook::consts::SIGINT, Arc::clone(&stop)) {
+        eprintln!("Failed to handle Ctrl-C: {}", e);
+        std::process::exit(1);
+    }
+    let manager = GpuManager::with_cache_ttl(interval);
+    print!("{}", HIDE_CURSOR);
+    while !stop.load(Ordering::Relaxed) {
+        print!("{}", CLEAR_SCREEN);
+        if manager.gpu_count() == 0 {
+            println!("No GPU detected");
+        }
+        for index in 0..manager.gpu_count() {
+            if let Some(gpu) = manager.get_cached_gpu_at(GpuIndex(index)) {
+                if index > 0 {
+                    println!();
+                }
+                println!("GPU #{} information:", index);
+                print_gpu(&gpu, painter);
+            }
+        }
+        let _ = io::stdout().flush();
+        let deadline = Instant::now() + interval;
+        while !stop.load(Ordering::Relaxed) {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                break;
+            }
+            thread::sleep(remaining.min(WATCH_POLL));
+        }
+    }
+    println!("{}", SHOW_CURSOR);
+}
+
+/// The processes using `gpu`, `None` if they cannot be listed for its vendor.
+fn gpu_processes(gpu: &GpuInfo) -> Option<gpu_info::Result<Vec<GpuProcessInfo>>> {
+    (gpu.vendor() == gpu_info::Vendor::Nvidia)
+        .then(|| gpu_info::providers::nvidia::NvidiaProvider::new().get_running_processes(gpu))
+}
+
+fn print_gpu_processes(gpu: &GpuInfo) {
+    let processes = match gpu_processes(gpu) {
+        Some(Ok(processes)) => processes,
+        Some(Err(e)) => {
+            println!("  N/A ({})", e);
+            return;
+        }
+        None => {
+            println!("  N/A");
+            return;
+        }
+    };
+    if processes.is_empty() {
+        println!("  None");
+        return;
+    }
+    println!("  {:>8}  {:<16}  {:>10}  Name", "PID", "Type", "VRAM");
+    for process in processes {
+        println!(
+            "  {:>8}  {:<16}  {:>7} MB  {}",
+            process.pid,
+            process.process_type.to_string(),
+            process.memory_used_mb,
+            process.name().as_deref().unwrap_or("N/A")
+        );
+    }
+}
+
+fn print_storage(mounts: &[MountInfo]) {
+    let width = mounts
+        .iter()
+        .map(|mount| mount.mount_point.len())
+        .max()
+        .unwrap_or(0)
+        .max("Mount".len());
+    println!(
+        "  {:<width$}  {:<10}  {:>10}  {:>10}  {:>6}",
+        "Mount",
+        "Filesystem",
+        "Size",
+        "Available",
+        "Use%",
+        width = width
+    );
+    for mount in mounts {
+        println!(
+            "  {:<width$}  {:<10}  {:>7.1} GB  {:>7.1} GB  {:>5.1}%",
+            mount.mount_point,
+            mount.filesystem,
+            mount.total_bytes as f64 / GIB,
+            mount.available_bytes as f64 / GIB,
+            mount.used_percent(),
+            width = width
+        );
+    }
+}
+
+fn print_network(interfaces: &[InterfaceInfo]) {
+    let width = interfaces
+        .iter()
+        .map(|interface| interface.name.len())
+        .max()
+        .unwrap_or(0)
+        .max("Interface".len());
+    println!(
+        "    {:<width$}  {:<21}  {:>10}  {:<8}  {:<9}  Status",
+        "Interface",
+        "MAC",
+        "Speed",
+        "Type",
+        "IP",
+        width = width
+    );
+    for interface in interfaces {
+        let speed = interface
+            .link_speed_mbps
+            .map_or_else(|| "N/A".to_owned(), |speed| format!("{} Mb/s", speed));
+        let ip = match (interface.ipv4, interface.ipv6) {
+            (true, true) => "IPv4/IPv6",
+            (true, false) => "IPv4",
+            (false, true) => "IPv6",
+            (false, false) => "-",
+        };
+        println!(
+            "  {} {:<width$}  {:<21}  {:>10}  {:<8}  {:<9}  {}",
+            if interface.primary { '*' } else { ' ' },
+            interface.name,
+            interface.mac_address.as_deref().unwrap_or("N/A"),
+            speed,
+            if interface.wireless {
+                "wireless"
+            } else {
+                "wired"
+            },
+            ip,
+            if interface.up { "up" } else { "down" },
+            width = width
+        );
+    }
+}
+
+/// Prints the requested sections as a JSON object.
+#[cfg(feature = "json")]
+fn print_json(options: &Options, show_all: bool) {
+    let show_os = show_all
+        || options.system_type
+        || options.system_version
+        || options.bit_depth
+        || options.architecture;
+    let info = (show_os || options.storage || options.network).then(system_info_lib::get);
+    let gpu = (show_all || options.gpu || options.gpu_processes).then(gpu_info::get);
+    let processes = match gpu
+        .as_ref()
+        .filter(|_| options.gpu_processes)
+        .and_then(gpu_processes)
+    {
+        Some(Ok(processes)) => Some(processes),
+        Some(Err(e)) => {
+            log::warn!("Failed to list GPU processes: {}", e);
+            None
+        }
+        None => None,
+    };
+    let sections = system_cli::JsonSections {
+        os: info.as_ref().filter(|_| show_os),
+        gpu: gpu.as_ref().filter(|_| show_all || options.gpu),
+        gpu_processes: processes.as_deref(),
+        storage: info
+            .as_ref()
+            .filter(|_| options.storage)
+            .and_then(Info::storage),
+        network: info
+            .as_ref()
+            .filter(|_| options.network)
+            .and_then(Info::network_interfaces),
+    };
+    match system_cli::to_json(&sections) {
+        Ok(json) => println!("{}", json),
+        Err(e) => {
+            eprintln!("Failed to serialize to JSON: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+#[cfg(not(feature = "json"))]
+fn print_json(_options: &Options, _show_all: bool) {
+    eprintln!("--format json requires system_cli to be built with the `json` feature");
+    std::process::exit(1);
+}
+
+/// The OS type, telling Windows 11 apart from earlier Windows versions.
+fn system_type(info: &Info) -> String {
+    if info.is_windows_11() {
+        "Windows 11".to_string()
+    } else {
+        info.system_type().to_string()
     }
 }
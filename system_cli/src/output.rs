@@ -0,0 +1,60 @@
+use clap::ValueEnum;
+#[cfg(feature = "json")]
+use gpu_info::{GpuInfo, GpuProcessInfo};
+#[cfg(feature = "json")]
+use system_info_lib::{Info, InterfaceInfo, MountInfo};
+
+/// How to print the information.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable lines.
+    #[default]
+    Text,
+    /// A single JSON object (requires the `json` feature).
+    Json,
+}
+
+/// The sections `--format json` prints, each under its own key.
+///
+/// A section that is `None`, because it was not requested or is not
+/// available, is left out.
+#[cfg(feature = "json")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonSections<'a> {
+    /// OS information, under `os`.
+    pub os: Option<&'a Info>,
+    /// GPU information, under `gpu`.
+    pub gpu: Option<&'a GpuInfo>,
+    /// Processes using the GPU, under `gpu_processes`.
+    pub gpu_processes: Option<&'a [GpuProcessInfo]>,
+    /// Mounted filesystems, under `storage`.
+    pub storage: Option<&'a [MountInfo]>,
+    /// Network interfaces, under `network`.
+    pub network: Option<&'a [InterfaceInfo]>,
+}
+
+/// Serializes the requested sections into a single JSON object.
+///
+/// # Errors
+///
+/// Returns an error if a section cannot be serialized.
+#[cfg(feature = "json")]
+pub fn to_json(sections: &JsonSections<'_>) -> serde_json::Result<String> {
+    let mut object = serde_json::Map::new();
+    if let Some(os) = sections.os {
+        object.insert("os".to_owned(), serde_json::to_value(os)?);
+    }
+    if let Some(gpu) = sections.gpu {
+        object.insert("gpu".to_owned(), serde_json::to_value(gpu)?);
+    }
+    if let Some(processes) = sections.gpu_processes {
+        object.insert("gpu_processes".to_owned(), serde_json::to_value(processes)?);
+    }
+    if let Some(mounts) = sections.storage {
+        object.insert("storage".to_owned(), serde_json::to_value(mounts)?);
+    }
+    if let Some(interfaces) = sections.network {
+        object.insert("network".to_owned(), serde_json::to_value(interfaces)?);
+    }
+    serde_json::to_string_pretty(&object)
+}
@@ -0,0 +1,253 @@
+//! Live GPU dashboard for `system_cli top`.
+//!
+//! Requires the `tui` feature. [`Dashboard`] renders only public `gpu_info`
+//! data, a [`GpuMetricsSnapshot`] and the monitor's [`SingleGpuHistory`],
+//! so it can be drawn to any ratatui backend. [`run()`] drives it in the
+//! terminal from a shared [`Sampler`].
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use gpu_info::monitoring::SingleGpuHistory;
+use gpu_info::{
+    GpuError, GpuInfo, GpuManager, GpuMetricsSnapshot, GpuMonitor, GpuThresholds, MonitorConfig,
+    SampleSubscriber, Sampler,
+};
+use log::debug;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, LineGauge, Paragraph, Sparkline};
+use ratatui::{DefaultTerminal, Frame};
+use std::io;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Terminal rows per GPU: a bordered block around three gauge lines.
+pub const GPU_ROW_HEIGHT: u16 = 5;
+
+/// How long to wait for a key press before redrawing.
+const INPUT_POLL: Duration = Duration::from_millis(250);
+
+/// One frame of the dashboard.
+#[derive(Debug, Default)]
+pub struct Dashboard {
+    /// Latest metrics of every GPU.
+    pub snapshot: GpuMetricsSnapshot,
+    /// Metrics history per GPU, indexed like `snapshot.gpus`.
+    pub history: Vec<Option<SingleGpuHistory>>,
+    /// Whether sampling is paused.
+    pub paused: bool,
+}
+
+impl Dashboard {
+    /// Draws a header line and one row per GPU into `frame`.
+    pub fn render(&self, frame: &mut Frame) {
+        let gpus = &self.snapshot.gpus;
+        let mut constraints = vec![Constraint::Length(1)];
+        constraints.extend(gpus.iter().map(|_| Constraint::Length(GPU_ROW_HEIGHT)));
+        constraints.push(Constraint::Min(0));
+        let rows = Layout::vertical(constraints).split(frame.area());
+        frame.render_widget(self.header(), rows[0]);
+        if gpus.is_empty() {
+            frame.render_widget(Paragraph::new("No GPU detected"), rows[1]);
+        }
+        for (index, gpu) in gpus.iter().enumerate() {
+            let history = self.history.get(index).and_then(Option::as_ref);
+            render_gpu(frame, rows[index + 1], index, gpu, history);
+        }
+    }
+
+    fn header(&self) -> Line<'static> {
+        let mut spans = vec![
+            Span::styled("GPU top", Style::new().add_modifier(Modifier::BOLD)),
+            Span::raw(format!(
+                " | {} GPU(s) | q quit  p pause",
+                self.snapshot.gpus.len()
+            )),
+        ];
+        if self.paused {
+            spans.push(Span::styled(
+                "  PAUSED",
+                Style::new().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+            ));
+        }
+        Line::from(spans)
+    }
+}
+
+fn render_gpu(
+    frame: &mut Frame,
+    area: Rect,
+    index: usize,
+    gpu: &GpuInfo,
+    history: Option<&SingleGpuHistory>,
+) {
+    let block = Block::bordered().title(format!(" #{} {} ", index, gpu.format_name_gpu()));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+    let [gauges, sparkline] =
+        Layout::horizontal([Constraint::Percentage(60), Constraint::Percentage(40)]).areas(inner);
+    let [utilization, memory, temperature] =
+        Layout::vertical([Constraint::Length(1); 3]).areas(gauges);
+
+    frame.render_widget(
+        gauge("Util", gpu.utilization, "%", Color::Cyan),
+        utilization,
+    );
+    frame.render_widget(
+        gauge(
+            "Mem",
+            gpu.memory_used_percent().or(gpu.memory_util),
+            "%",
+            Color::Magenta,
+        ),
+        memory,
+    );
+    frame.render_widget(
+        gauge(
+            "Temp",
+            gpu.temperature,
+            "C",
+            temperature_color(gpu.temperature),
+        ),
+        temperature,
+    );
+
+    let data: Vec<u64> = history.map_or_else(Vec::new, |history| {
+        let skip = history
+            .utilizations
+            .len()
+            .saturating_sub(usize::from(sparkline.width));
+        history
+            .utilizations
+            .iter()
+            .skip(skip)
+            .map(|utilization| utilization.clamp(0.0, 100.0) as u64)
+            .collect()
+    });
+    frame.render_widget(
+        Sparkline::default()
+            .data(&data)
+            .max(100)
+            .style(Style::new().fg(Color::Cyan)),
+        sparkline,
+    );
+}
+
+/// A one-line gauge of a 0-100 value, labelled `N/A` when unknown.
+fn gauge(name: &str, value: Option<f32>, unit: &str, color: Color) -> LineGauge<'static> {
+    let text = value.map_or_else(|| "N/A".to_owned(), |value| format!("{:.0}{}", value, unit));
+    LineGauge::default()
+        .label(format!("{:<4} {:>4} ", name, text))
+        .ratio(f64::from(value.unwrap_or(0.0).clamp(0.0, 100.0)) / 100.0)
+        .filled_style(Style::new().fg(color))
+}
+
+/// Colors a temperature by severity, using the default monitoring thresholds.
+fn temperature_color(celsius: Option<f32>) -> Color {
+    let thresholds = GpuThresholds::default();
+    match celsius {
+        Some(celsius) if celsius >= thresholds.temperature_critical => Color::Red,
+        Some(celsius) if celsius >= thresholds.temperature_warning => Color::Yellow,
+        _ => Color::Green,
+    }
+}
+
+/// Keeps the latest sample for the dashboard.
+struct LatestSnapshot {
+    interval: Duration,
+    paused: AtomicBool,
+    snapshot: Mutex<GpuMetricsSnapshot>,
+}
+
+impl SampleSubscriber for LatestSnapshot {
+    fn requested_interval(&self) -> Option<Duration> {
+        (!self.paused.load(Ordering::Relaxed)).then_some(self.interval)
+    }
+
+    fn on_sample(&self, gpus: &[GpuInfo], _collected_at: Instant) {
+        if let Ok(mut snapshot) = self.snapshot.lock() {
+            snapshot.gpus = gpus.to_vec();
+        }
+    }
+
+    fn on_error(&self, error: &GpuError) {
+        debug!("GPU sample failed: {}", error);
+    }
+}
+
+/// Runs the dashboard until `q` is pressed, refreshing every `interval`.
+///
+/// The terminal is restored on exit and, through the panic hook installed
+/// by [`ratatui::init()`], on panic.
+pub fn run(interval: Duration) -> io::Result<()> {
+    let sampler = Arc::new(Sampler::new(Arc::new(Mutex::new(GpuManager::new()))));
+    let monitor = GpuMonitor::with_sampler(
+        MonitorConfig::default()
+            .with_polling_interval(interval)
+            .with_alerts_enabled(false),
+        Arc::clone(&sampler),
+    );
+    let latest = Arc::new(LatestSnapshot {
+        interval,
+        paused: AtomicBool::new(false),
+        snapshot: Mutex::new(GpuMetricsSnapshot::default()),
+    });
+    let subscription = sampler
+        .subscribe(Arc::clone(&latest) as Arc<dyn SampleSubscriber>)
+        .map_err(io::Error::other)?;
+    monitor.start_monitoring().map_err(io::Error::other)?;
+
+    let mut terminal = ratatui::init();
+    let result = event_loop(&mut terminal, &monitor, &latest);
+    ratatui::restore();
+
+    let _ = monitor.stop_monitoring();
+    sampler.unsubscribe(subscription);
+    result
+}
+
+fn event_loop(
+    terminal: &mut DefaultTerminal,
+    monitor: &GpuMonitor,
+    latest: &LatestSnapshot,
+) -> io::Result<()> {
+    loop {
+        let snapshot = latest
+            .snapshot
+            .lock()
+            .map(|snapshot| snapshot.clone())
+            .unwrap_or_default();
+        let dashboard = Dashboard {
+            history: (0..snapshot.gpus.len())
+                .map(|index| monitor.get_gpu_history(index))
+                .collect(),
+            snapshot,
+            paused: monitor.is_paused(),
+        };
+        terminal.draw(|frame| dashboard.render(frame))?;
+
+        if !event::poll(INPUT_POLL)? {
+            continue;
+        }
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return Ok(()),
+            KeyCode::Char('p') => {
+                let paused = !monitor.is_paused();
+                latest.paused.store(paused, Ordering::Relaxed);
+                if paused {
+                    monitor.pause();
+                } else {
+                    monitor.resume();
+                }
+            }
+            _ => {}
+        }
+    }
+}
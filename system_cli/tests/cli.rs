@@ -81,6 +81,67 @@ fn bitness_long() {
         .stdout(bitness_predicate());
 }
 
+#[test]
+fn gpu_processes() {
+    Command::new(BIN_NAME)
+        .arg("--gpu-processes")
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::starts_with("GPU processes:")
+                .and(predicate::str::contains("OS information").not()),
+        );
+}
+
+#[cfg(unix)]
+#[test]
+fn storage() {
+    Command::new(BIN_NAME)
+        .arg("--storage")
+        .assert()
+        .success()
+        .stdout(storage_predicate().and(predicate::str::is_match(r"(?m)^  /\s").unwrap()));
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn network() {
+    Command::new(BIN_NAME)
+        .arg("--network")
+        .assert()
+        .success()
+        .stdout(network_predicate());
+}
+
+#[test]
+fn color_never() {
+    Command::new(BIN_NAME)
+        .args(["--all", "--color", "never"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\x1b[").not());
+}
+
+#[test]
+fn color_always() {
+    Command::new(BIN_NAME)
+        .args(["--gpu", "--color", "always"])
+        .env("NO_COLOR", "1")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\x1b["));
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn all_shows_kernel() {
+    Command::new(BIN_NAME)
+        .arg("--all")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("  Kernel: "));
+}
+
 fn all_predicate() -> impl Predicate<str> {
     predicate::str::starts_with("OS information:")
         .and(predicate::str::contains("Type"))
@@ -105,3 +166,117 @@ fn bitness_predicate() -> impl Predicate<str> {
         .and(predicate::str::starts_with("OS version").not())
         .and(predicate::str::starts_with("OS type").not())
 }
+
+fn storage_predicate() -> impl Predicate<str> {
+    predicate::str::starts_with("Storage:")
+        .and(predicate::str::contains("Use%"))
+        .and(predicate::str::contains("OS information").not())
+}
+
+#[cfg(target_os = "linux")]
+fn network_predicate() -> impl Predicate<str> {
+    predicate::str::starts_with("Network:")
+        .and(predicate::str::contains("OS information").not())
+        .and(predicate::str::contains("Storage").not())
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn format_json_gpu() {
+    let output = Command::new(BIN_NAME)
+        .args(["--format", "json", "--gpu"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let json: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert!(json["gpu"].is_object());
+    assert!(json["gpu"].get("vendor").is_some());
+    assert!(json.get("os").is_none());
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn format_json_all() {
+    let output = Command::new(BIN_NAME)
+        .args(["--format", "json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let json: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert!(json["os"]["system_type"].is_string());
+    assert!(json["gpu"].is_object());
+}
+
+#[cfg(all(feature = "json", target_os = "linux"))]
+#[test]
+fn format_json_storage_and_network() {
+    let output = Command::new(BIN_NAME)
+        .args(["--format", "json", "--storage", "--network"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let json: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert!(json["storage"].is_array());
+    assert!(json["network"].is_array());
+    assert!(json.get("os").is_none());
+    assert!(json.get("gpu").is_none());
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn format_json_gpu_processes() {
+    let output = Command::new(BIN_NAME)
+        .args(["--format", "json", "--gpu-processes"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let json: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    // Processes are only listed on NVIDIA GPUs
+    assert!(json
+        .get("gpu_processes")
+        .map_or(true, |processes| processes.is_array()));
+    assert!(json.get("gpu").is_none());
+    assert!(json.get("os").is_none());
+}
+
+#[test]
+fn watch_rejects_zero_interval() {
+    Command::new(BIN_NAME)
+        .args(["--gpu", "--watch", "--interval", "0"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--interval"));
+}
+
+#[cfg(unix)]
+#[test]
+fn watch_stops_on_sigint() {
+    use std::process::Stdio;
+    use std::time::Duration;
+
+    let child = std::process::Command::new(BIN_NAME)
+        .args(["--gpu", "--watch", "--interval", "100"])
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    std::thread::sleep(Duration::from_millis(500));
+    let killed = std::process::Command::new("kill")
+        .args(["-INT", &child.id().to_string()])
+        .status()
+        .unwrap();
+    assert!(killed.success());
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.starts_with("\x1b[?25l"));
+    assert!(stdout.contains("information:") || stdout.contains("No GPU detected"));
+    assert!(stdout.trim_end().ends_with("\x1b[?25h"));
+}
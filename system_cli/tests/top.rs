@@ -0,0 +1,107 @@
+#![cfg(feature = "tui")]
+
+use std::time::Instant;
+
+use gpu_info::monitoring::SingleGpuHistory;
+use gpu_info::{GpuInfo, GpuMetricsSnapshot, Vendor};
+use ratatui::backend::TestBackend;
+use ratatui::buffer::Buffer;
+use ratatui::Terminal;
+use system_cli::top::{Dashboard, GPU_ROW_HEIGHT};
+
+const WIDTH: u16 = 80;
+const HEIGHT: u16 = 24;
+
+fn gpu(index: usize) -> GpuInfo {
+    GpuInfo::builder()
+        .vendor(Vendor::Nvidia)
+        .name(format!("Test GPU {}", index))
+        .utilization(25.0 * index as f32)
+        .temperature(50.0 + index as f32)
+        .memory_total(8192)
+        .memory_used(2048)
+        .build()
+}
+
+fn dashboard(gpu_count: usize) -> Dashboard {
+    let gpus: Vec<GpuInfo> = (0..gpu_count).map(gpu).collect();
+    let history = gpus
+        .iter()
+        .map(|gpu| {
+            let mut history = SingleGpuHistory::new(60);
+            for _ in 0..10 {
+                history.add_measurement(gpu, Instant::now());
+            }
+            Some(history)
+        })
+        .collect();
+    Dashboard {
+        snapshot: GpuMetricsSnapshot { gpus },
+        history,
+        paused: false,
+    }
+}
+
+fn draw(dashboard: &Dashboard) -> Buffer {
+    let mut terminal = Terminal::new(TestBackend::new(WIDTH, HEIGHT)).unwrap();
+    terminal.draw(|frame| dashboard.render(frame)).unwrap();
+    terminal.backend().buffer().clone()
+}
+
+fn row(buffer: &Buffer, y: u16) -> String {
+    (0..buffer.area.width)
+        .map(|x| buffer[(x, y)].symbol())
+        .collect()
+}
+
+#[test]
+fn single_gpu_layout() {
+    let buffer = draw(&dashboard(1));
+    assert!(row(&buffer, 0).starts_with("GPU top | 1 GPU(s)"));
+    assert!(row(&buffer, 1).starts_with("┌ #0 Test GPU 0 "));
+    assert!(row(&buffer, 2).contains("Util   0%"));
+    assert!(row(&buffer, 3).contains("Mem   25%"));
+    assert!(row(&buffer, 4).contains("Temp  50C"));
+    assert!(row(&buffer, GPU_ROW_HEIGHT).starts_with('└'));
+    assert!(row(&buffer, GPU_ROW_HEIGHT + 1).trim().is_empty());
+}
+
+#[test]
+fn four_gpu_layout() {
+    let buffer = draw(&dashboard(4));
+    assert!(row(&buffer, 0).starts_with("GPU top | 4 GPU(s)"));
+    for index in 0..4u16 {
+        let top = 1 + index * GPU_ROW_HEIGHT;
+        assert!(
+            row(&buffer, top).starts_with(&format!("┌ #{} Test GPU {} ", index, index)),
+            "GPU {} block starts at row {}",
+            index,
+            top
+        );
+        assert!(row(&buffer, top + 1).contains(&format!("Util {:>3}%", 25 * index)));
+        assert!(row(&buffer, top + GPU_ROW_HEIGHT - 1).starts_with('└'));
+    }
+}
+
+#[test]
+fn paused_and_empty() {
+    let mut dashboard = dashboard(0);
+    dashboard.paused = true;
+    let buffer = draw(&dashboard);
+    assert!(row(&buffer, 0).contains("PAUSED"));
+    assert!(row(&buffer, 1).starts_with("No GPU detected"));
+}
+
+#[test]
+fn unknown_metrics_render_as_na() {
+    let dashboard = Dashboard {
+        snapshot: GpuMetricsSnapshot {
+            gpus: vec![GpuInfo::unknown()],
+        },
+        history: vec![None],
+        paused: false,
+    };
+    let buffer = draw(&dashboard);
+    assert!(row(&buffer, 1).contains("Unknown GPU"));
+    assert!(row(&buffer, 2).contains("Util  N/A"));
+}
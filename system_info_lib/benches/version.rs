@@ -0,0 +1,45 @@
+//! Version parsing and ordering benchmarks.
+//!
+//! The inputs are fixed release strings rather than the host's, so the
+//! numbers do not depend on the system the benchmark runs on.
+//!
+//! Run with `cargo bench -p system_info_lib`.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use system_info_lib::SystemVersion;
+
+/// Versions as reported by os-release, `sw_vers`, `uname -r` and the registry
+const RELEASES: [&str; 8] = [
+    "22.04",
+    "24.04.1",
+    "14.6.1",
+    "15.0-beta",
+    "13.2-RELEASE-p4",
+    "6.9.0-rc2",
+    "10.0.22631",
+    "2023.12-LTS",
+];
+
+fn version(c: &mut Criterion) {
+    let mut group = c.benchmark_group("system_version");
+    group.bench_function("from_string", |b| {
+        b.iter(|| {
+            RELEASES
+                .iter()
+                .map(|release| SystemVersion::from_string(black_box(*release)))
+                .collect::<Vec<_>>()
+        })
+    });
+    let versions: Vec<SystemVersion> = RELEASES.map(SystemVersion::from_string).to_vec();
+    group.bench_function("sort", |b| {
+        b.iter(|| {
+            let mut sorted = black_box(versions.clone());
+            sorted.sort();
+            sorted
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, version);
+criterion_main!(benches);
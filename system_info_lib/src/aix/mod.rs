@@ -1,6 +1,9 @@
 //src/aix/mod.rs
-use crate::system_os::Type;
-use crate::system_uname::uname;
+use log::trace;
+
+use crate::{
+    bit_depth, hostname, system_info::Info, system_os::Type, system_uname::uname, SystemVersion,
+};
 
 /// Retrieves system information for the AIX platform.
 ///
@@ -18,8 +21,19 @@ pub fn current_platform() -> Info {
     trace!("Getting system information for AIX");
 
     let version = get_system_version()
-        .map(Version::from_string)
+        .map(SystemVersion::from_string)
         .unwrap_or_else(|| SystemVersion::Unknown);
+
+    let info = Info {
+        system_type: get_system_os(),
+        version,
+        bit_depth: bit_depth::get(),
+        hostname: hostname::get(),
+        ..Default::default()
+    };
+
+    trace!("Returning {:?}", info);
+    info
 }
 
 /// Retrieves the system version for the AIX platform.
@@ -44,7 +58,7 @@ fn get_system_version() -> Option<String> {
 ///
 /// * `Type` - The system type as determined from the `uname` command.
 fn get_system_os() -> Type {
-    match uname("-s").as_ref() {
+    match uname("-s").as_deref() {
         Some("AIX") => Type::AIX,
         _ => Type::Unknown,
     }
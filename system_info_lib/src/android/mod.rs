@@ -1,12 +1,14 @@
 //src/android/mod.rs
 use crate::system_os::Type;
-use crate::Info;
+use crate::{hostname, uptime, Info};
 use log::trace;
 
 pub fn current_platform() -> Info {
     trace!("android::current_platform() is called");
 
-    let info = Info::with_type(Type::Android);
+    let mut info = Info::with_type(Type::Android);
+    info.hostname = hostname::get();
+    info.uptime = uptime::get();
     trace!("Returning system information: {:?}", info);
     info
 }
@@ -0,0 +1,50 @@
+//src/cache.rs
+use std::sync::{Arc, OnceLock, PoisonError, RwLock};
+
+use crate::{detect, Info};
+
+/// Process-wide detection result, filled on first use.
+static CACHE: OnceLock<RwLock<Arc<Info>>> = OnceLock::new();
+
+/// Returns the cached system information, detecting it on first use.
+pub fn cached() -> Arc<Info> {
+    let slot = CACHE.get_or_init(|| RwLock::new(Arc::new(detect())));
+    let info = slot.read().unwrap_or_else(PoisonError::into_inner);
+    Arc::clone(&info)
+}
+
+/// Re-runs platform detection and replaces the cached value.
+pub fn refresh() -> Arc<Info> {
+    let fresh = Arc::new(detect());
+    let slot = CACHE.get_or_init(|| RwLock::new(Arc::clone(&fresh)));
+    *slot.write().unwrap_or_else(PoisonError::into_inner) = Arc::clone(&fresh);
+    fresh
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn get_returns_cached_instance_until_refresh() {
+        let first = cached();
+        let second = cached();
+        assert!(Arc::ptr_eq(&first, &second));
+        assert_eq!(crate::get(), *first);
+
+        let refreshed = refresh();
+        assert!(!Arc::ptr_eq(&first, &refreshed));
+        assert!(Arc::ptr_eq(&refreshed, &cached()));
+        // Free space and the uptime change between the two detections
+        let (mut refreshed, mut first) = (Info::clone(&refreshed), Info::clone(&first));
+        refreshed.uptime = None;
+        first.uptime = None;
+        #[cfg(feature = "storage")]
+        {
+            refreshed.storage = None;
+            first.storage = None;
+        }
+        assert_eq!(refreshed, first);
+    }
+}
@@ -0,0 +1,44 @@
+//src/container_runtime.rs
+// spell-checker:ignore kubepods, podman
+
+use std::fmt::{self, Display, Formatter};
+
+/// The container runtime a process runs under.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ContainerRuntime {
+    /// Docker, detected from `/.dockerenv` or the cgroup path.
+    Docker,
+    /// LXC or LXD system containers.
+    Lxc,
+    /// Podman, which sets `container=podman`.
+    Podman,
+    /// A Kubernetes pod, whatever runtime the node uses.
+    Kubernetes,
+    /// A container whose runtime is not recognized, e.g. `systemd-nspawn`.
+    Unknown,
+}
+
+impl ContainerRuntime {
+    /// Maps the value of the `container` environment variable to a runtime.
+    pub(crate) fn from_env_value(value: &str) -> ContainerRuntime {
+        match value.trim() {
+            "docker" => ContainerRuntime::Docker,
+            "lxc" | "lxc-libvirt" => ContainerRuntime::Lxc,
+            "podman" => ContainerRuntime::Podman,
+            _ => ContainerRuntime::Unknown,
+        }
+    }
+}
+
+impl Display for ContainerRuntime {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match *self {
+            ContainerRuntime::Docker => write!(f, "Docker"),
+            ContainerRuntime::Lxc => write!(f, "LXC"),
+            ContainerRuntime::Podman => write!(f, "Podman"),
+            ContainerRuntime::Kubernetes => write!(f, "Kubernetes"),
+            ContainerRuntime::Unknown => write!(f, "Unknown"),
+        }
+    }
+}
@@ -0,0 +1,149 @@
+//src/cpu_features.rs
+use log::trace;
+
+/// Returns the CPU feature flags reported by the operating system.
+///
+/// On Linux the `flags` (x86) or `Features` (ARM) line of `/proc/cpuinfo` is
+/// used. On macOS `sysctl machdep.cpu.features` and
+/// `machdep.cpu.leaf7_features` are queried on Intel, and `hw.optional.neon`
+/// on Apple Silicon.
+///
+/// Flags are lowercased and deduplicated. Common umbrella names are added
+/// when a more specific flag is present (`avx512` for any `avx512*` flag,
+/// `neon` for `asimd`), so callers can check for them directly.
+///
+/// # Returns
+///
+/// * `Vec<String>` - The feature flags, or an empty vector if they cannot be determined.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn get() -> Vec<String> {
+    match std::fs::read_to_string("/proc/cpuinfo") {
+        Ok(cpuinfo) => parse_cpuinfo(&cpuinfo),
+        Err(e) => {
+            trace!("Failed to read /proc/cpuinfo: {:?}", e);
+            Vec::new()
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+pub fn get() -> Vec<String> {
+    let mut raw = String::new();
+    for key in ["machdep.cpu.features", "machdep.cpu.leaf7_features"] {
+        if let Some(value) = sysctl(key) {
+            raw.push(' ');
+            raw.push_str(&value);
+        }
+    }
+    if sysctl("hw.optional.neon").as_deref() == Some("1") {
+        raw.push_str(" neon");
+    }
+    normalize(raw.split_whitespace())
+}
+
+#[cfg(target_os = "macos")]
+fn sysctl(key: &str) -> Option<String> {
+    use std::process::Command;
+
+    let output = Command::new("sysctl").arg("-n").arg(key).output().ok()?;
+    if output.status.success() {
+        Some(String::from_utf8_lossy(&output.stdout).trim().to_owned())
+    } else {
+        trace!("'sysctl -n {}' failed: {:?}", key, output.status);
+        None
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android", target_os = "macos")))]
+pub fn get() -> Vec<String> {
+    Vec::new()
+}
+
+/// Extracts feature flags from `/proc/cpuinfo` content.
+///
+/// Only the first `flags` or `Features` line is used; all cores report the
+/// same set.
+#[cfg_attr(not(any(target_os = "linux", target_os = "android")), allow(dead_code))]
+pub(crate) fn parse_cpuinfo(cpuinfo: &str) -> Vec<String> {
+    cpuinfo
+        .lines()
+        .find_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            if matches!(key.trim(), "flags" | "Features") {
+                Some(value)
+            } else {
+                None
+            }
+        })
+        .map(|flags| normalize(flags.split_whitespace()))
+        .unwrap_or_default()
+}
+
+fn normalize<'a>(flags: impl Iterator<Item = &'a str>) -> Vec<String> {
+    let mut result: Vec<String> = Vec::new();
+    let mut push = |flag: String| {
+        if !result.contains(&flag) {
+            result.push(flag);
+        }
+    };
+    for flag in flags {
+        let flag = flag.to_lowercase().replace('.', "_");
+        if flag.starts_with("avx512") {
+            push("avx512".to_owned());
+        }
+        if flag == "asimd" {
+            push("neon".to_owned());
+        }
+        push(flag);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parse_x86_flags_line() {
+        let cpuinfo = "processor\t: 0\n\
+                       vendor_id\t: GenuineIntel\n\
+                       flags\t\t: fpu vme sse sse2 avx avx2 avx512f avx512bw\n\
+                       bugs\t\t: spectre_v1\n";
+        let features = parse_cpuinfo(cpuinfo);
+        assert!(features.contains(&"avx2".to_owned()));
+        assert!(features.contains(&"avx512".to_owned()));
+        assert!(features.contains(&"avx512f".to_owned()));
+        assert!(!features.contains(&"spectre_v1".to_owned()));
+    }
+
+    #[test]
+    fn parse_arm_features_line() {
+        let cpuinfo = "processor\t: 0\n\
+                       Features\t: fp asimd evtstrm aes sve\n";
+        let features = parse_cpuinfo(cpuinfo);
+        assert!(features.contains(&"neon".to_owned()));
+        assert!(features.contains(&"sve".to_owned()));
+    }
+
+    #[test]
+    fn parse_uses_first_core_only() {
+        let cpuinfo = "flags\t: sse2 avx2\n\nflags\t: sse2 avx2\n";
+        assert_eq!(parse_cpuinfo(cpuinfo), vec!["sse2", "avx2"]);
+    }
+
+    #[test]
+    fn parse_without_flags_is_empty() {
+        assert!(parse_cpuinfo("processor\t: 0\nmodel name\t: Unknown\n").is_empty());
+        assert!(parse_cpuinfo("").is_empty());
+    }
+
+    #[test]
+    fn normalize_macos_sysctl_output() {
+        let features = normalize("FPU VME SSE4.1 SSE4.2 AVX1.0 AVX2".split_whitespace());
+        assert_eq!(
+            features,
+            vec!["fpu", "vme", "sse4_1", "sse4_2", "avx1_0", "avx2"]
+        );
+    }
+}
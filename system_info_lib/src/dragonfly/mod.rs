@@ -1,6 +1,6 @@
 //src/dragonfly/mod.rs
 use crate::system_uname::uname;
-use crate::{bit_depth, system_os::Type, Info, SystemVersion};
+use crate::{bit_depth, hostname, kernel_version, system_os::Type, uptime, Info, SystemVersion};
 use log::trace;
 
 pub fn current_platform() -> Info {
@@ -14,6 +14,9 @@ pub fn current_platform() -> Info {
         system_type: Type::DragonFly,
         version,
         bit_depth: bit_depth::get(),
+        hostname: hostname::get(),
+        uptime: uptime::get(),
+        kernel_version: kernel_version::get(),
         ..Default::default()
     };
 
@@ -165,7 +165,10 @@ pub trait SystemVersionExt {
 
 impl SystemVersionExt for SystemVersion {
     fn is_semantic(&self) -> bool {
-        matches!(self, SystemVersion::Semantic(_, _, _))
+        matches!(
+            self,
+            SystemVersion::Semantic(..) | SystemVersion::PreRelease(..)
+        )
     }
 
     fn is_rolling(&self) -> bool {
@@ -182,21 +185,27 @@ impl SystemVersionExt for SystemVersion {
 
     fn major(&self) -> Option<u64> {
         match self {
-            SystemVersion::Semantic(major, _, _) => Some(*major),
+            SystemVersion::Semantic(major, ..) | SystemVersion::PreRelease(major, ..) => {
+                Some(*major)
+            }
             _ => None,
         }
     }
 
     fn minor(&self) -> Option<u64> {
         match self {
-            SystemVersion::Semantic(_, minor, _) => Some(*minor),
+            SystemVersion::Semantic(_, minor, _) | SystemVersion::PreRelease(_, minor, ..) => {
+                Some(*minor)
+            }
             _ => None,
         }
     }
 
     fn patch(&self) -> Option<u64> {
         match self {
-            SystemVersion::Semantic(_, _, patch) => Some(*patch),
+            SystemVersion::Semantic(_, _, patch) | SystemVersion::PreRelease(_, _, patch, _) => {
+                Some(*patch)
+            }
             _ => None,
         }
     }
@@ -373,6 +382,28 @@ pub trait InfoExt {
     /// assert!(!info.has_kernel_version());
     /// ```
     fn has_kernel_version(&self) -> bool;
+
+    /// Calls `f` with the key and value of every known field.
+    ///
+    /// Meant for attaching OS information to structured log records one
+    /// field at a time, without building a map. Keys match the serialized
+    /// field names; unknown values and `None` fields are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use system_info_lib::{BitDepth, Info, InfoExt, Type};
+    ///
+    /// let info = Info::builder()
+    ///     .system_type(Type::Debian)
+    ///     .bit_depth(BitDepth::X64)
+    ///     .build();
+    ///
+    /// let mut fields = Vec::new();
+    /// info.record_fields(|key, value| fields.push(format!("{}={}", key, value)));
+    /// assert_eq!(fields, ["system_type=Debian", "bit_depth=64-bit"]);
+    /// ```
+    fn record_fields(&self, f: impl FnMut(&str, &str));
 }
 
 impl InfoExt for Info {
@@ -461,11 +492,37 @@ impl InfoExt for Info {
     fn has_kernel_version(&self) -> bool {
         self.kernel_version().is_some()
     }
+
+    fn record_fields(&self, mut f: impl FnMut(&str, &str)) {
+        f("system_type", &self.system_type().to_string());
+        if !self.version().is_unknown() {
+            f("version", &self.version().to_string());
+        }
+        if let Some(edition) = self.edition() {
+            f("edition", edition);
+        }
+        if let Some(codename) = self.codename() {
+            f("codename", codename);
+        }
+        if self.bit_depth() != BitDepth::Unknown {
+            f("bit_depth", &self.bit_depth().to_string());
+        }
+        if let Some(architecture) = self.architecture() {
+            f("architecture", architecture);
+        }
+        if let Some(kernel_version) = self.kernel_version() {
+            f("kernel_version", kernel_version);
+        }
+        if let Some(patch_level) = self.patch_level() {
+            f("patch_level", &patch_level.to_string());
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::BTreeMap;
 
     mod system_version_ext {
         use super::*;
@@ -669,5 +726,45 @@ mod tests {
             assert!(!linux_64.is_windows());
             assert!(!linux_64.is_32bit());
         }
+
+        #[test]
+        fn test_record_fields() {
+            let info = Info::builder()
+                .system_type(Type::FreeBSD)
+                .version(SystemVersion::custom("13.2-RELEASE"))
+                .bit_depth(BitDepth::X64)
+                .architecture("amd64")
+                .kernel_version("13.2-RELEASE-p4")
+                .patch_level(4)
+                .build();
+
+            let mut fields = BTreeMap::new();
+            info.record_fields(|key, value| {
+                fields.insert(key.to_owned(), value.to_owned());
+            });
+
+            let keys: Vec<&str> = fields.keys().map(String::as_str).collect();
+            assert_eq!(
+                keys,
+                [
+                    "architecture",
+                    "bit_depth",
+                    "kernel_version",
+                    "patch_level",
+                    "system_type",
+                    "version"
+                ]
+            );
+            assert_eq!(fields["system_type"], "FreeBSD");
+            assert_eq!(fields["version"], "13.2-RELEASE");
+            assert_eq!(fields["patch_level"], "4");
+        }
+
+        #[test]
+        fn test_record_fields_skips_unknown() {
+            let mut keys = Vec::new();
+            Info::unknown().record_fields(|key, _| keys.push(key.to_owned()));
+            assert_eq!(keys, ["system_type"]);
+        }
     }
 }
@@ -1,5 +1,9 @@
 //src/freebsd/mod.rs
-use crate::{bit_depth, system_os::Type, system_uname::uname, Info, SystemVersion};
+use crate::freebsd_parse::{is_hardened, parse_jailed, release_version, split_patch_level};
+use crate::{
+    bit_depth, hostname, kernel_version, system_os::Type, system_uname::uname, uptime, Info,
+    SystemVersion,
+};
 use log::{error, trace};
 use std::process::Command;
 
@@ -12,8 +16,10 @@ use std::process::Command;
 ///
 /// The returned `Info` structure contains the following data:
 /// - `system_type`: operating system type (e.g., FreeBSD, MidnightBSD).
-/// - `version`: system version determined using `uname -r`.
+/// - `version`: system version determined using `uname -r`, including the patch level.
+/// - `patch_level`: the number of the `-pN` suffix of `uname -r`, if present.
 /// - `bit_depth`: system bitness.
+/// - `kernel_version`: the kernel release from `uname -r`.
 ///
 /// # Example
 /// ```
@@ -25,14 +31,19 @@ use std::process::Command;
 pub fn current_platform() -> Info {
     trace!("freebsd::current_platform is called");
 
-    let version = uname("-r")
-        .map(SystemVersion::from_string)
-        .unwrap_or_else(|| SystemVersion::Unknown);
+    let (version, patch_level) = match uname("-r") {
+        Some(release) => (release_version(&release), split_patch_level(&release).1),
+        None => (SystemVersion::Unknown, None),
+    };
 
     let info = Info {
         system_type: get_os(),
         version,
+        patch_level,
         bit_depth: bit_depth::get(),
+        hostname: hostname::get(),
+        uptime: uptime::get(),
+        kernel_version: kernel_version::get(),
         ..Default::default()
     };
 
@@ -40,34 +51,53 @@ pub fn current_platform() -> Info {
     info
 }
 
-/// Executes the `/sbin/sysctl` command with the argument `hardening.version`
-/// to check the hardening version of the system. If the command is successful,
-/// it returns the output. If the command fails, it logs an error message and
-/// returns `Type::FreeBSD`.
+/// Returns `true` if the process runs inside a FreeBSD jail.
+///
+/// Reads the `security.jail.jailed` sysctl, which is `1` inside a jail and
+/// `0` on the host.
+pub fn is_jailed() -> Option<bool> {
+    sysctl("security.jail.jailed").and_then(|value| parse_jailed(&value))
+}
+
+/// Determines the system type from `uname -s`.
+///
+/// HardenedBSD reports itself as FreeBSD, but additionally provides the
+/// `hardening.version` sysctl, which stock FreeBSD rejects as an unknown oid.
 fn get_os() -> Type {
     match uname("-s").as_deref() {
         Some("MidnightBSD") => Type::MidnightBSD,
         Some("FreeBSD") => {
-            let check_hardening = match Command::new("/sbin/sysctl")
-                .arg("hardening.version")
-                .output()
-            {
-                Ok(ok) => ok,
-                Err(error) => {
-                    error!("Failed to invoke '/sbin/sysctl': {:?}", error);
-                    return Type::FreeBSD;
-                }
-            };
-            match std::str::from_utf8(&check_hardening.stderr) {
-                Ok("0\n") => Type::HardenedBSD,
-                Ok(_) => Type::FreeBSD,
-                Err(_) => Type::FreeBSD,
+            if is_hardened(sysctl("hardening.version").as_deref()) {
+                Type::HardenedBSD
+            } else {
+                Type::FreeBSD
             }
         }
         _ => Type::Unknown,
     }
 }
 
+/// Runs `/sbin/sysctl -n <name>`, returning its trimmed output on success.
+fn sysctl(name: &str) -> Option<String> {
+    let output = match Command::new("/sbin/sysctl").arg("-n").arg(name).output() {
+        Ok(output) => output,
+        Err(error) => {
+            error!("Failed to invoke '/sbin/sysctl': {:?}", error);
+            return None;
+        }
+    };
+    if output.status.success() {
+        Some(String::from_utf8_lossy(&output.stdout).trim().to_owned())
+    } else {
+        trace!(
+            "'sysctl -n {}' failed: {}",
+            name,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+        None
+    }
+}
+
 #[cfg(test)]
 mod freebsd_tests {
     use super::*;
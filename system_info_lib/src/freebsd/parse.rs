@@ -0,0 +1,143 @@
+//src/freebsd/parse.rs
+//! Parsers for FreeBSD command output.
+//!
+//! They do not depend on the target, so their tests run on every platform.
+use crate::SystemVersion;
+
+/// Interprets the output of a successful `sysctl -n hardening.version`.
+///
+/// `None` means the sysctl failed, i.e. the oid is unknown.
+pub(crate) fn is_hardened(hardening_version: Option<&str>) -> bool {
+    hardening_version
+        .is_some_and(|version| !version.is_empty() && version.bytes().all(|b| b.is_ascii_digit()))
+}
+
+/// Interprets the output of `sysctl -n security.jail.jailed`.
+pub(crate) fn parse_jailed(value: &str) -> Option<bool> {
+    match value.trim() {
+        "0" => Some(false),
+        "1" => Some(true),
+        _ => None,
+    }
+}
+
+/// Splits the `-pN` patch level suffix off a `uname -r` release string.
+///
+/// `13.2-RELEASE-p4` becomes `("13.2-RELEASE", Some(4))`; releases without a
+/// patch level are returned unchanged.
+pub(crate) fn split_patch_level(release: &str) -> (&str, Option<u32>) {
+    let release = release.trim();
+    match release.rsplit_once("-p") {
+        Some((base, level)) if !level.is_empty() && level.bytes().all(|b| b.is_ascii_digit()) => {
+            match level.parse() {
+                Ok(level) => (base, Some(level)),
+                Err(_) => (release, None),
+            }
+        }
+        _ => (release, None),
+    }
+}
+
+/// Parses a `uname -r` release such as `14.1-RELEASE-p5` or `14.2-BETA1`.
+///
+/// The `-pN` patch level stays part of the version, so `13.2-RELEASE-p10`
+/// orders after `13.2-RELEASE-p4`. Only `ALPHA`, `BETA` and `RC` releases
+/// are pre-releases. `RELEASE`, `STABLE` and `CURRENT` stay custom versions,
+/// so that they order with their numbers instead of before them.
+pub(crate) fn release_version(release: &str) -> SystemVersion {
+    let release = release.trim();
+    let pre_release = release.split_once('-').is_some_and(|(_, tag)| {
+        ["ALPHA", "BETA", "RC"]
+            .iter()
+            .any(|prefix| tag.starts_with(prefix))
+    });
+    if pre_release {
+        SystemVersion::from_string(release)
+    } else {
+        SystemVersion::custom(release)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn hardening_version_on_stock_freebsd() {
+        // `sysctl -n hardening.version` exits with 1 and prints
+        // "sysctl: unknown oid 'hardening.version'" to stderr
+        assert!(!is_hardened(None));
+    }
+
+    #[test]
+    fn hardening_version_on_hardenedbsd() {
+        assert!(is_hardened(Some("1400004")));
+        assert!(!is_hardened(Some("")));
+        assert!(!is_hardened(Some("unknown")));
+    }
+
+    #[test]
+    fn jailed_sysctl_output() {
+        assert_eq!(parse_jailed("0\n"), Some(false));
+        assert_eq!(parse_jailed("1\n"), Some(true));
+        assert_eq!(parse_jailed(""), None);
+    }
+
+    #[test]
+    fn patch_level_of_stock_release() {
+        assert_eq!(
+            split_patch_level("13.2-RELEASE-p4\n"),
+            ("13.2-RELEASE", Some(4))
+        );
+        assert_eq!(split_patch_level("14.0-RELEASE"), ("14.0-RELEASE", None));
+    }
+
+    #[test]
+    fn patch_level_of_hardenedbsd_and_jail() {
+        assert_eq!(
+            split_patch_level("14.0-STABLE-HBSD"),
+            ("14.0-STABLE-HBSD", None)
+        );
+        // A jail reports the host kernel release
+        assert_eq!(
+            split_patch_level("13.3-RELEASE-p1"),
+            ("13.3-RELEASE", Some(1))
+        );
+        assert_eq!(
+            split_patch_level("15.0-CURRENT-p"),
+            ("15.0-CURRENT-p", None)
+        );
+    }
+
+    #[test]
+    fn release_versions() {
+        assert_eq!(
+            release_version("14.1-RELEASE"),
+            SystemVersion::custom("14.1-RELEASE")
+        );
+        assert_eq!(
+            release_version("14.2-BETA1"),
+            SystemVersion::semantic_pre(14, 2, 0, "BETA1")
+        );
+        assert!(release_version("14.2-RC1") < release_version("14.2-RELEASE"));
+        assert!(release_version("14.2-RELEASE") < release_version("14.2-STABLE"));
+        assert!(release_version("14.1-STABLE") < release_version("14.2-BETA1"));
+    }
+
+    #[test]
+    fn release_versions_keep_patch_level() {
+        assert_eq!(
+            release_version("13.2-RELEASE-p4\n"),
+            SystemVersion::custom("13.2-RELEASE-p4")
+        );
+        assert_eq!(
+            release_version("13.2-RELEASE-p4").to_string(),
+            "13.2-RELEASE-p4"
+        );
+        assert!(release_version("13.2-RELEASE") < release_version("13.2-RELEASE-p4"));
+        assert!(release_version("13.2-RELEASE-p4") < release_version("13.2-RELEASE-p10"));
+        assert!(release_version("13.2-RELEASE-p10") < release_version("13.3-RELEASE"));
+        assert!(release_version("13.2-RELEASE-p4").at_least(13, 2));
+    }
+}
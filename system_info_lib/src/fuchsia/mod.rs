@@ -0,0 +1,25 @@
+//src/fuchsia/mod.rs
+use log::trace;
+
+use crate::{system_os::Type, Info};
+
+pub fn current_platform() -> Info {
+    trace!("fuchsia::current_platform() is called");
+
+    let info = Info::with_type(Type::Fuchsia);
+    trace!("Returning system information: {:?}", info);
+    info
+}
+
+#[cfg(test)]
+mod fuchsia_test {
+    use crate::system_os::Type;
+
+    use super::*;
+    use pretty_assertions::assert_eq;
+    #[test]
+    fn system_type() {
+        let version = current_platform();
+        assert_eq!(Type::Fuchsia, version.system_type());
+    }
+}
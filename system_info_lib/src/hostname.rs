@@ -0,0 +1,158 @@
+//src/hostname.rs
+// `gethostname` and `GetComputerNameExW` write into caller-provided buffers
+
+#![cfg_attr(
+    any(windows, all(unix, not(target_os = "android"))),
+    allow(unsafe_code)
+)]
+
+/// Returns the hostname of the current machine.
+///
+/// On Unix-like systems this calls `gethostname(2)`, on Windows
+/// `GetComputerNameExW` with `ComputerNameDnsHostname`. Android reads the
+/// `ro.product.device` property from `/system/build.prop` and falls back to
+/// `uname -n`.
+///
+/// # Returns
+///
+/// * `Option<String>` - The hostname, if it can be determined.
+pub fn get() -> Option<String> {
+    imp::hostname().filter(|name| !name.is_empty())
+}
+
+#[cfg(all(unix, not(target_os = "android")))]
+mod imp {
+    use log::error;
+    use std::ffi::CStr;
+
+    /// `HOST_NAME_MAX` is 64 on Linux and 255 on most BSDs; one more byte
+    /// leaves room for the terminating NUL.
+    const BUFFER_LEN: usize = 256;
+
+    pub fn hostname() -> Option<String> {
+        let mut buffer = [0 as libc::c_char; BUFFER_LEN];
+        if unsafe { libc::gethostname(buffer.as_mut_ptr(), buffer.len() - 1) } != 0 {
+            error!(
+                "gethostname() failed: {:?}",
+                std::io::Error::last_os_error()
+            );
+            return None;
+        }
+        let name = unsafe { CStr::from_ptr(buffer.as_ptr()) };
+        Some(name.to_string_lossy().into_owned())
+    }
+}
+
+#[cfg(target_os = "android")]
+mod imp {
+    use log::error;
+    use std::process::Command;
+
+    pub fn hostname() -> Option<String> {
+        std::fs::read_to_string("/system/build.prop")
+            .ok()
+            .and_then(|content| super::property(&content, "ro.product.device"))
+            .or_else(uname_node)
+    }
+
+    fn uname_node() -> Option<String> {
+        Command::new("uname")
+            .arg("-n")
+            .output()
+            .map_err(|e| {
+                error!("Failed to invoke 'uname -n': {:?}", e);
+            })
+            .ok()
+            .and_then(|out| {
+                if out.status.success() {
+                    Some(String::from_utf8_lossy(&out.stdout).trim_end().to_owned())
+                } else {
+                    error!("'uname -n' invocation failed: {:?}", out);
+                    None
+                }
+            })
+    }
+}
+
+#[cfg(windows)]
+mod imp {
+    use log::error;
+    use std::{ffi::OsString, os::windows::ffi::OsStringExt, ptr};
+    use windows_sys::Win32::System::SystemInformation::{
+        ComputerNameDnsHostname, GetComputerNameExW,
+    };
+
+    pub fn hostname() -> Option<String> {
+        // The first call fails and reports the required size, including the NUL.
+        let mut size = 0u32;
+        unsafe { GetComputerNameExW(ComputerNameDnsHostname, ptr::null_mut(), &mut size) };
+        if size == 0 {
+            return None;
+        }
+        let mut buffer = vec![0u16; size as usize];
+        if unsafe { GetComputerNameExW(ComputerNameDnsHostname, buffer.as_mut_ptr(), &mut size) }
+            == 0
+        {
+            error!(
+                "GetComputerNameExW() failed: {:?}",
+                std::io::Error::last_os_error()
+            );
+            return None;
+        }
+        // On success `size` no longer counts the NUL.
+        buffer.truncate(size as usize);
+        OsString::from_wide(&buffer).into_string().ok()
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+mod imp {
+    pub fn hostname() -> Option<String> {
+        None
+    }
+}
+
+/// Returns the value of `key` in the `key=value` lines of a `build.prop` file.
+#[cfg(any(target_os = "android", test))]
+fn property(content: &str, key: &str) -> Option<String> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .find(|(name, _)| name.trim() == key)
+        .map(|(_, value)| value.trim().to_owned())
+        .filter(|value| !value.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    #[cfg(any(unix, windows))]
+    fn hostname_nonempty() {
+        let name = get().expect("hostname failed");
+        assert!(!name.is_empty());
+        assert!(!name.contains('\0'));
+    }
+
+    #[test]
+    fn build_prop_property() {
+        let content = "# begin build properties\n\
+                       ro.build.id=UP1A.231005.007\n\
+                       ro.product.device = oriole \n\
+                       ro.product.model=Pixel 6\n";
+        assert_eq!(
+            property(content, "ro.product.device"),
+            Some("oriole".to_owned())
+        );
+        assert_eq!(property(content, "ro.product.name"), None);
+        assert_eq!(property("ro.product.device=\n", "ro.product.device"), None);
+        assert_eq!(
+            property("#ro.product.device=x\n", "ro.product.device"),
+            None
+        );
+    }
+}
@@ -1,7 +1,10 @@
 //src/illumos/mod.rs
 use log::trace;
 
-use crate::{bit_depth, system_info::Info, system_os::Type, system_uname::uname, SystemVersion};
+use crate::{
+    bit_depth, hostname, kernel_version, system_info::Info, system_os::Type, system_uname::uname,
+    uptime, SystemVersion,
+};
 
 pub fn current_platform() -> Info {
     trace!("illumos::current_platform() is called");
@@ -14,6 +17,9 @@ pub fn current_platform() -> Info {
         system_type: get_os(),
         version,
         bit_depth: bit_depth::get(),
+        hostname: hostname::get(),
+        uptime: uptime::get(),
+        kernel_version: kernel_version::get(),
         ..Default::default()
     };
 
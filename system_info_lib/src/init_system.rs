@@ -0,0 +1,167 @@
+//src/init_system.rs
+// spell-checker:ignore openrc, runit, sysvinit, inittab
+
+use std::fmt::{self, Display, Formatter};
+use std::path::PathBuf;
+
+/// The init system (PID 1) managing services.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum InitSystem {
+    /// systemd.
+    Systemd,
+    /// OpenRC, either as PID 1 (`openrc-init`) or started from another init.
+    OpenRc,
+    /// runit.
+    Runit,
+    /// System V init with `/etc/inittab`.
+    SysVinit,
+}
+
+impl Display for InitSystem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match *self {
+            InitSystem::Systemd => write!(f, "systemd"),
+            InitSystem::OpenRc => write!(f, "OpenRC"),
+            InitSystem::Runit => write!(f, "runit"),
+            InitSystem::SysVinit => write!(f, "SysVinit"),
+        }
+    }
+}
+
+/// Returns the init system of the running Linux system.
+///
+/// See [`detect`] for the checks; other systems return `None`.
+pub fn get() -> Option<InitSystem> {
+    #[cfg(target_os = "linux")]
+    {
+        detect(
+            |path| std::path::Path::new(path).exists(),
+            |path| std::fs::read_link(path).ok(),
+        )
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        None
+    }
+}
+
+/// Identifies the init system from path checks, in order of reliability.
+///
+/// 1. `/run/systemd/system`, which systemd creates when it is PID 1.
+/// 2. The runtime directories `/run/openrc` and `/run/runit`.
+/// 3. The file name the `/sbin/init` symlink points to.
+/// 4. The installed `/sbin/openrc` and `/etc/runit` for inits without a
+///    runtime directory, then `/etc/inittab` for SysVinit.
+///
+/// `exists` reports whether a path exists and `read_link` returns the
+/// target of a symlink.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+pub(crate) fn detect(
+    exists: impl Fn(&str) -> bool,
+    read_link: impl Fn(&str) -> Option<PathBuf>,
+) -> Option<InitSystem> {
+    if exists("/run/systemd/system") {
+        return Some(InitSystem::Systemd);
+    }
+    if exists("/run/openrc") {
+        return Some(InitSystem::OpenRc);
+    }
+    if exists("/run/runit") {
+        return Some(InitSystem::Runit);
+    }
+    if let Some(init) = read_link("/sbin/init").as_ref().and_then(|target| {
+        target
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(from_init_name)
+    }) {
+        return Some(init);
+    }
+    if exists("/sbin/openrc") {
+        Some(InitSystem::OpenRc)
+    } else if exists("/etc/runit") {
+        Some(InitSystem::Runit)
+    } else if exists("/etc/inittab") {
+        Some(InitSystem::SysVinit)
+    } else {
+        None
+    }
+}
+
+/// Maps the file name of the `/sbin/init` target.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn from_init_name(name: &str) -> Option<InitSystem> {
+    match name {
+        "systemd" => Some(InitSystem::Systemd),
+        "openrc-init" => Some(InitSystem::OpenRc),
+        "runit-init" | "runit" => Some(InitSystem::Runit),
+        "sysvinit" => Some(InitSystem::SysVinit),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn detect_with(paths: &[&str], init_target: Option<&str>) -> Option<InitSystem> {
+        detect(
+            |path| paths.contains(&path),
+            |path| {
+                if path == "/sbin/init" {
+                    init_target.map(PathBuf::from)
+                } else {
+                    None
+                }
+            },
+        )
+    }
+
+    #[test]
+    fn systemd() {
+        assert_eq!(
+            detect_with(&["/run/systemd/system", "/etc/inittab"], None),
+            Some(InitSystem::Systemd)
+        );
+        assert_eq!(
+            detect_with(&[], Some("/lib/systemd/systemd")),
+            Some(InitSystem::Systemd)
+        );
+    }
+
+    #[test]
+    fn openrc() {
+        assert_eq!(
+            detect_with(&["/run/openrc", "/etc/inittab"], None),
+            Some(InitSystem::OpenRc)
+        );
+        assert_eq!(
+            detect_with(&["/sbin/openrc", "/etc/inittab"], Some("/bin/busybox")),
+            Some(InitSystem::OpenRc)
+        );
+        assert_eq!(
+            detect_with(&[], Some("/sbin/openrc-init")),
+            Some(InitSystem::OpenRc)
+        );
+    }
+
+    #[test]
+    fn runit_and_sysvinit() {
+        assert_eq!(
+            detect_with(&[], Some("runit-init")),
+            Some(InitSystem::Runit)
+        );
+        assert_eq!(
+            detect_with(&["/etc/inittab"], None),
+            Some(InitSystem::SysVinit)
+        );
+    }
+
+    #[test]
+    fn undeterminable() {
+        assert_eq!(detect_with(&[], None), None);
+        assert_eq!(detect_with(&[], Some("/bin/busybox")), None);
+    }
+}
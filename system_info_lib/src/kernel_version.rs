@@ -1,11 +1,109 @@
+use std::fmt::{self, Display};
+
+/// A kernel release as reported by `uname -r`, e.g. `6.5.0-14-generic`.
+///
+/// The leading `major.minor.patch` numbers are parsed so releases can be
+/// compared: versions order by these numbers, then by the full release
+/// string. Serialized as the release string.
+///
+/// # Examples
+///
+/// ```
+/// use system_info_lib::KernelVersion;
+///
+/// let kernel = KernelVersion::parse("6.5.0-14-generic");
+/// assert_eq!((kernel.major(), kernel.minor(), kernel.patch()), (6, 5, 0));
+/// assert_eq!(kernel.release(), "6.5.0-14-generic");
+/// assert!(kernel > KernelVersion::parse("5.15.0-76-generic"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "String", into = "String")
+)]
+pub struct KernelVersion {
+    major: u64,
+    minor: u64,
+    patch: u64,
+    release: String,
+}
+
+impl KernelVersion {
+    /// Parses a kernel release string.
+    ///
+    /// The leading dot-separated numbers become the major, minor and patch
+    /// version; a suffix such as `-14-generic` or `-RELEASE-p4` is kept in
+    /// the release string only. Numbers that are missing are 0.
+    pub fn parse(release: impl Into<String>) -> Self {
+        let release = release.into().trim().to_string();
+        let mut numbers = [0u64; 3];
+        for (number, part) in numbers.iter_mut().zip(release.split('.')) {
+            let digits = part.len() - part.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+            match part[..digits].parse() {
+                Ok(value) => *number = value,
+                Err(_) => break,
+            }
+            if digits < part.len() {
+                break;
+            }
+        }
+        let [major, minor, patch] = numbers;
+        Self {
+            major,
+            minor,
+            patch,
+            release,
+        }
+    }
+
+    /// Returns the major version, e.g. `6` for `6.5.0-14-generic`.
+    pub fn major(&self) -> u64 {
+        self.major
+    }
+
+    /// Returns the minor version, e.g. `5` for `6.5.0-14-generic`.
+    pub fn minor(&self) -> u64 {
+        self.minor
+    }
+
+    /// Returns the patch version, e.g. `0` for `6.5.0-14-generic`.
+    pub fn patch(&self) -> u64 {
+        self.patch
+    }
+
+    /// Returns the full release string, e.g. `6.5.0-14-generic`.
+    pub fn release(&self) -> &str {
+        &self.release
+    }
+}
+
+impl Display for KernelVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.release)
+    }
+}
+
+impl From<String> for KernelVersion {
+    fn from(release: String) -> Self {
+        Self::parse(release)
+    }
+}
+
+impl From<KernelVersion> for String {
+    fn from(kernel: KernelVersion) -> Self {
+        kernel.release
+    }
+}
+
 /// Returns the kernel version of the operating system.
 ///
-/// On Unix-like systems (Linux, macOS, BSD), this calls `uname -r`.
-/// On Windows, kernel version is not separately available.
+/// On Unix-like systems (Linux, macOS, the BSDs, illumos), this calls
+/// `uname -r`. On Windows, kernel version is not separately available.
 ///
 /// # Returns
 ///
-/// * `Option<String>` - The kernel version, if it can be determined.
+/// * `Option<KernelVersion>` - The kernel version, if it can be determined.
 ///
 /// # Examples
 ///
@@ -27,9 +125,10 @@
     target_os = "freebsd",
     target_os = "dragonfly",
     target_os = "netbsd",
-    target_os = "openbsd"
+    target_os = "openbsd",
+    target_os = "illumos"
 ))]
-pub fn get() -> Option<String> {
+pub fn get() -> Option<KernelVersion> {
     use log::error;
     use std::process::Command;
 
@@ -42,7 +141,7 @@ pub fn get() -> Option<String> {
         .ok()
         .and_then(|out| {
             if out.status.success() {
-                Some(String::from_utf8_lossy(&out.stdout).trim_end().to_owned())
+                Some(KernelVersion::parse(String::from_utf8_lossy(&out.stdout)))
             } else {
                 error!("'uname -r' invocation failed: {:?}", out);
                 None
@@ -51,7 +150,7 @@ pub fn get() -> Option<String> {
 }
 
 #[cfg(target_os = "windows")]
-pub fn get() -> Option<String> {
+pub fn get() -> Option<KernelVersion> {
     None
 }
 
@@ -63,9 +162,10 @@ pub fn get() -> Option<String> {
     target_os = "dragonfly",
     target_os = "netbsd",
     target_os = "openbsd",
+    target_os = "illumos",
     target_os = "windows"
 )))]
-pub fn get() -> Option<String> {
+pub fn get() -> Option<KernelVersion> {
     None
 }
 
@@ -81,7 +181,8 @@ mod tests {
         target_os = "freebsd",
         target_os = "dragonfly",
         target_os = "netbsd",
-        target_os = "openbsd"
+        target_os = "openbsd",
+        target_os = "illumos"
     ))]
     fn test_get_kernel_version() {
         let version = get();
@@ -89,13 +190,52 @@ mod tests {
             version.is_some(),
             "Kernel version should be available on Unix-like systems"
         );
-        let version_str = version.unwrap();
+        let version = version.unwrap();
         assert!(
-            !version_str.is_empty(),
+            !version.release().is_empty(),
             "Kernel version should not be empty"
         );
     }
 
+    #[test]
+    fn test_parse_kernel_release() {
+        let data = [
+            ("6.5.0-14-generic", (6, 5, 0)),
+            ("6.6.8-200.fc39.x86_64", (6, 6, 8)),
+            ("13.2-RELEASE-p4", (13, 2, 0)),
+            ("23.1.0", (23, 1, 0)),
+            ("5.11", (5, 11, 0)),
+            ("6.6.7+rpt-rpi-v8", (6, 6, 7)),
+            ("unknown", (0, 0, 0)),
+        ];
+        for (release, expected) in data {
+            let kernel = KernelVersion::parse(release);
+            assert_eq!((kernel.major(), kernel.minor(), kernel.patch()), expected);
+            assert_eq!(kernel.release(), release);
+            assert_eq!(kernel.to_string(), release);
+        }
+        assert_eq!(KernelVersion::parse("6.1.0\n").release(), "6.1.0");
+    }
+
+    #[test]
+    fn test_kernel_versions_compare_numerically() {
+        assert!(KernelVersion::parse("5.15.0-76-generic") < KernelVersion::parse("5.16.0"));
+        assert!(KernelVersion::parse("5.9") < KernelVersion::parse("5.10"));
+        assert!(KernelVersion::parse("6.1.0") < KernelVersion::parse("6.1.0-13-amd64"));
+    }
+
+    /// Tests that a kernel version is serialized as its release string.
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serialize_as_release() {
+        let kernel = KernelVersion::parse("6.8.0-45-generic");
+        let json = serde_json::to_string(&kernel).unwrap();
+        assert_eq!(json, r#""6.8.0-45-generic""#);
+        let parsed: KernelVersion = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, kernel);
+        assert_eq!(parsed.minor(), 8);
+    }
+
     #[test]
     #[cfg(target_os = "windows")]
     fn test_get_kernel_version_windows() {
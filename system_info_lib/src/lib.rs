@@ -10,12 +10,22 @@
 //!
 //! - `architecture`: Handles system architecture information.
 //! - `bit_depth`: Manages bit depth details.
+//! - `cache`: Process-wide cache of the detected information (`cache` feature).
+//! - `cpu_features`: Detects CPU feature flags.
+//! - `hostname`: Reads the hostname of the machine.
 //! - `imp`: Platform-specific implementations (e.g., AIX).
+//! - `logical_cpus`: Counts the logical CPUs.
+//! - `memory`: Installed memory modules (`memory` feature).
+//! - `network`: Network interface summary (`network` feature).
+//! - `server_edition`: Server vs desktop heuristics.
+//! - `storage`: Mounted filesystem usage (`storage` feature).
 //! - `system_info_lib`: Core system information functionalities.
 //! - `system_matcher`: Utilities for matching system properties.
 //! - `system_os`: Operating system-related information.
 //! - `system_uname`: Uname system call wrapper.
 //! - `system_version`: System version details.
+//! - `uptime`: Reads how long the system has been running.
+//! - `ui_theme`: Light, dark or high contrast desktop theme (`desktop` feature).
 
 #![deny(missing_debug_implementations, missing_docs, unsafe_code)]
 
@@ -39,6 +49,10 @@ mod imp;
 #[path = "freebsd/mod.rs"]
 mod imp;
 
+#[cfg(target_os = "fuchsia")]
+#[path = "fuchsia/mod.rs"]
+mod imp;
+
 #[cfg(target_os = "illumos")]
 #[path = "illumos/mod.rs"]
 mod imp;
@@ -73,6 +87,7 @@ mod imp;
     target_os = "dragonfly",
     target_os = "emscripten",
     target_os = "freebsd",
+    target_os = "fuchsia",
     target_os = "illumos",
     target_os = "linux",
     target_os = "macos",
@@ -92,9 +107,29 @@ mod imp;
 ))]
 mod architecture;
 mod bit_depth;
+#[cfg(feature = "cache")]
+mod cache;
+mod container_runtime;
+mod cpu_features;
 pub mod ext;
+#[cfg(any(target_os = "freebsd", test))]
+#[path = "freebsd/parse.rs"]
+mod freebsd_parse;
+mod hostname;
+mod init_system;
 mod kernel_version;
+mod logical_cpus;
+#[cfg(feature = "memory")]
+mod memory;
+#[cfg(feature = "network")]
+mod network;
+#[cfg(any(all(unix, not(target_os = "macos")), test))]
+mod os_release;
 pub mod prelude;
+mod redaction;
+mod server_edition;
+#[cfg(feature = "storage")]
+mod storage;
 mod system_info;
 #[cfg(not(windows))]
 mod system_matcher;
@@ -109,15 +144,33 @@ mod system_os;
 ))]
 mod system_uname;
 mod system_version;
+#[cfg(feature = "desktop")]
+mod ui_theme;
+mod uptime;
 
 pub use crate::{
     bit_depth::BitDepth,
+    container_runtime::ContainerRuntime,
     ext::{InfoExt, SystemVersionExt},
+    init_system::InitSystem,
+    kernel_version::KernelVersion,
+    redaction::{Redact, Redaction, IDENTIFYING_FIELDS},
     system_info::{Info, InfoBuilder},
     system_os::Type,
     system_version::SystemVersion,
 };
 
+#[cfg(feature = "memory")]
+pub use crate::memory::{MemoryInfo, MemoryModule};
+#[cfg(feature = "network")]
+pub use crate::network::InterfaceInfo;
+#[cfg(feature = "serde")]
+pub use crate::redaction::Redacted;
+#[cfg(feature = "storage")]
+pub use crate::storage::MountInfo;
+#[cfg(feature = "desktop")]
+pub use crate::ui_theme::UiTheme;
+
 /// Returns information about the current operating system (type, version, edition, etc.).
 ///
 /// # Examples
@@ -138,6 +191,62 @@ pub use crate::{
 /// println!("BitDepth: {}", info.bit_depth());
 /// println!("Architecture: {:?}", info.architecture());
 /// ```
+///
+/// With the `cache` feature enabled, detection runs only on the first call
+/// and later calls return a copy of the cached result. Operating system
+/// information rarely changes while a process is running, so this is safe
+/// for most users; see `refresh()` for the exceptions.
 pub fn get() -> Info {
-    imp::current_platform()
+    #[cfg(feature = "cache")]
+    {
+        Info::clone(&cache::cached())
+    }
+    #[cfg(not(feature = "cache"))]
+    {
+        detect()
+    }
+}
+
+/// Detects the platform and reads the sections enabled by features, such
+/// as storage and network interfaces, so that accessors and serialization
+/// do no I/O.
+pub(crate) fn detect() -> Info {
+    #[allow(unused_mut)]
+    let mut info = imp::current_platform();
+    #[cfg(feature = "storage")]
+    {
+        info.storage = storage::get();
+    }
+    #[cfg(feature = "network")]
+    {
+        info.network_interfaces = network::get();
+    }
+    #[cfg(feature = "memory")]
+    {
+        info.memory = memory::get();
+    }
+    #[cfg(feature = "desktop")]
+    {
+        info.ui_theme = ui_theme::get();
+    }
+    info
+}
+
+/// Re-runs platform detection, replaces the cached information and returns it.
+///
+/// Only needed in edge cases where the system changes under a running
+/// process, e.g. after an in-place OS upgrade. Later calls to [`get()`]
+/// return the refreshed information.
+///
+/// Requires the `cache` feature, which needs Rust 1.70 or newer.
+///
+/// # Examples
+///
+/// ```
+/// let info = system_info_lib::refresh();
+/// assert_eq!(system_info_lib::get(), info);
+/// ```
+#[cfg(feature = "cache")]
+pub fn refresh() -> Info {
+    Info::clone(&cache::refresh())
 }
@@ -2,8 +2,12 @@
 mod system_file_release;
 mod system_release_lsb;
 
-use crate::{architecture, bit_depth, kernel_version, system_os::Type, Info};
+use crate::{
+    architecture, bit_depth, hostname, kernel_version, system_os::Type, uptime, ContainerRuntime,
+    Info,
+};
 use log::trace;
+use std::{env, fs, path::Path};
 
 pub fn current_platform() -> Info {
     trace!("linux::current_platform() is called");
@@ -14,11 +18,81 @@ pub fn current_platform() -> Info {
     info.bit_depth = bit_depth::get();
     info.architecture = architecture::get();
     info.kernel_version = kernel_version::get();
+    info.hostname = hostname::get();
+    info.uptime = uptime::get();
+    info.wsl_version = wsl_version();
+    info.container_runtime = container_runtime();
+    system_file_release::OsReleaseFields::read("/").apply(&mut info);
 
     trace!("Returns {:?}", info);
     info
 }
 
+/// Detects Windows Subsystem for Linux and its version.
+fn wsl_version() -> Option<u8> {
+    let proc_version = fs::read_to_string("/proc/version").unwrap_or_default();
+    let os_release = fs::read_to_string("/proc/sys/kernel/osrelease").unwrap_or_default();
+    let interop = Path::new("/proc/sys/fs/binfmt_misc/WSLInterop").exists();
+    parse_wsl_version(&proc_version, &os_release, interop)
+}
+
+/// WSL version from `/proc/version`, `/proc/sys/kernel/osrelease` and
+/// whether the `WSLInterop` binfmt entry exists.
+fn parse_wsl_version(proc_version: &str, os_release: &str, interop: bool) -> Option<u8> {
+    if !interop && !proc_version.to_lowercase().contains("microsoft") {
+        return None;
+    }
+    // WSL 2 kernels are built as `microsoft-standard`, newer ones add `-WSL2`
+    let os_release = os_release.to_lowercase();
+    if os_release.contains("wsl2") || os_release.contains("microsoft-standard") {
+        Some(2)
+    } else {
+        Some(1)
+    }
+}
+
+/// Detects the container runtime the process runs under.
+fn container_runtime() -> Option<ContainerRuntime> {
+    let dockerenv = Path::new("/.dockerenv").exists();
+    let cgroup = fs::read_to_string("/proc/1/cgroup").unwrap_or_default();
+    let variable = env::var("container").ok();
+    let environ = fs::read("/proc/self/environ")
+        .map(|environ| String::from_utf8_lossy(&environ).into_owned())
+        .unwrap_or_default();
+    parse_container_runtime(dockerenv, &cgroup, variable.as_deref(), &environ)
+}
+
+/// Container runtime from whether `/.dockerenv` exists, `/proc/1/cgroup`,
+/// the `container` environment variable and `/proc/self/environ`.
+fn parse_container_runtime(
+    dockerenv: bool,
+    cgroup: &str,
+    variable: Option<&str>,
+    environ: &str,
+) -> Option<ContainerRuntime> {
+    // Pods also match `docker` or `containerd`, so Kubernetes goes first
+    if cgroup.contains("kubepods") {
+        return Some(ContainerRuntime::Kubernetes);
+    }
+    let value = variable
+        .or_else(|| {
+            environ
+                .split('\0')
+                .find_map(|entry| entry.strip_prefix("container="))
+        })
+        .filter(|value| !value.is_empty());
+    if let Some(value) = value {
+        return Some(ContainerRuntime::from_env_value(value));
+    }
+    if dockerenv || cgroup.contains("docker") {
+        Some(ContainerRuntime::Docker)
+    } else if cgroup.contains("lxc") {
+        Some(ContainerRuntime::Lxc)
+    } else {
+        None
+    }
+}
+
 #[cfg(test)]
 mod linux_tests {
     use super::*;
@@ -74,4 +148,86 @@ mod linux_tests {
             system_type
         );
     }
+
+    #[test]
+    fn wsl_detection() {
+        let wsl2 = "Linux version 5.15.153.1-microsoft-standard-WSL2 (root@941d701f84f1) \
+                    (gcc (GCC) 11.2.0, GNU ld (GNU Binutils) 2.37) #1 SMP Fri Mar 29 23:14:13 UTC 2024";
+        assert_eq!(
+            parse_wsl_version(wsl2, "5.15.153.1-microsoft-standard-WSL2\n", true),
+            Some(2)
+        );
+        assert_eq!(
+            parse_wsl_version(
+                "Linux version 4.19.128-microsoft-standard (oe-user@oe-host)",
+                "4.19.128-microsoft-standard\n",
+                false
+            ),
+            Some(2)
+        );
+        assert_eq!(
+            parse_wsl_version(
+                "Linux version 4.4.0-19041-Microsoft (Microsoft@Microsoft.com) (gcc version 5.4.0)",
+                "4.4.0-19041-Microsoft\n",
+                true
+            ),
+            Some(1)
+        );
+        assert_eq!(
+            parse_wsl_version(
+                "Linux version 6.8.0-45-generic (buildd@lcy02-amd64-115)",
+                "6.8.0-45-generic\n",
+                false
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn container_detection() {
+        let host_cgroup = "0::/init.scope\n";
+        assert_eq!(parse_container_runtime(false, host_cgroup, None, ""), None);
+        assert_eq!(
+            parse_container_runtime(true, host_cgroup, None, ""),
+            Some(ContainerRuntime::Docker)
+        );
+        assert_eq!(
+            parse_container_runtime(
+                false,
+                "12:pids:/docker/3f1b2c9e8d7a\n11:memory:/docker/3f1b2c9e8d7a\n",
+                None,
+                ""
+            ),
+            Some(ContainerRuntime::Docker)
+        );
+        assert_eq!(
+            parse_container_runtime(
+                true,
+                "0::/kubepods/besteffort/pod5c6e0d4b/9a8b7c6d\n",
+                None,
+                ""
+            ),
+            Some(ContainerRuntime::Kubernetes)
+        );
+        assert_eq!(
+            parse_container_runtime(false, "2:cpu:/lxc/web01\n", None, ""),
+            Some(ContainerRuntime::Lxc)
+        );
+        assert_eq!(
+            parse_container_runtime(false, host_cgroup, Some("podman"), ""),
+            Some(ContainerRuntime::Podman)
+        );
+        assert_eq!(
+            parse_container_runtime(false, host_cgroup, None, "PATH=/usr/bin\0container=lxc\0"),
+            Some(ContainerRuntime::Lxc)
+        );
+        assert_eq!(
+            parse_container_runtime(false, host_cgroup, Some("systemd-nspawn"), ""),
+            Some(ContainerRuntime::Unknown)
+        );
+        assert_eq!(
+            parse_container_runtime(false, host_cgroup, Some(""), ""),
+            None
+        );
+    }
 }
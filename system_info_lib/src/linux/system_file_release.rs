@@ -1,4 +1,5 @@
 //src/linux/system_file_release.rs
+use crate::os_release::os_release_value;
 use crate::system_matcher::SystemMatcher;
 use crate::system_os::Type;
 use crate::{BitDepth, Info, SystemVersion};
@@ -11,6 +12,48 @@ pub fn get() -> Option<Info> {
     retrieve_release_info(&DISTRIBUTIONS, "/")
 }
 
+/// Values of `/etc/os-release` stored in [`Info`] besides the type and version.
+///
+/// Read whichever source identified the distribution, so `ID_LIKE` is
+/// also known for distributions that `DISTRIBUTIONS` does not list.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct OsReleaseFields {
+    pub(crate) id_like: Option<Vec<String>>,
+    pub(crate) build_id: Option<String>,
+    pub(crate) variant: Option<String>,
+}
+
+impl OsReleaseFields {
+    /// Reads `etc/os-release` below `root`; a missing file gives no values.
+    pub(crate) fn read(root: &str) -> Self {
+        let path = Path::new(root).join("etc/os-release");
+        match std::fs::read_to_string(&path) {
+            Ok(content) => Self::parse(&content),
+            Err(error) => {
+                trace!("Unable to read {:?}: {:?}", path, error);
+                Self::default()
+            }
+        }
+    }
+
+    /// Extracts `ID_LIKE`, `BUILD_ID` and `VARIANT_ID` from os-release content.
+    pub(crate) fn parse(content: &str) -> Self {
+        Self {
+            id_like: os_release_value(content, "ID_LIKE")
+                .map(|ids| ids.split_whitespace().map(str::to_owned).collect()),
+            build_id: os_release_value(content, "BUILD_ID"),
+            variant: os_release_value(content, "VARIANT_ID"),
+        }
+    }
+
+    /// Stores the values in `info`.
+    pub(crate) fn apply(self, info: &mut Info) {
+        info.id_like = self.id_like;
+        info.build_id = self.build_id;
+        info.variant = self.variant;
+    }
+}
+
 /// Iterate over the given list of `ReleaseInfo` and check if the `path`
 /// exists. If it does, read the file and call the `system` and `version`
 /// closures to retrieve the system type and version. If they are defined,
@@ -246,6 +289,57 @@ mod system_file_release_test {
         );
     }
 
+    /// Root of the `etc/os-release` fixture of `distribution`
+    fn fixture(distribution: &str) -> String {
+        format!(
+            "{}/src/linux/tests/{}",
+            env!("CARGO_MANIFEST_DIR"),
+            distribution
+        )
+    }
+
+    #[test]
+    fn os_release_fields_ubuntu() {
+        let fields = OsReleaseFields::read(&fixture("Ubuntu"));
+        assert_eq!(fields.id_like, Some(vec!["debian".to_string()]));
+        assert_eq!(fields.build_id, None);
+        assert_eq!(fields.variant, None);
+    }
+
+    #[test]
+    fn os_release_fields_fedora() {
+        let fields = OsReleaseFields::read(&fixture("Fedora_35"));
+        assert_eq!(fields.id_like, None);
+        assert_eq!(fields.variant.as_deref(), Some("workstation"));
+    }
+
+    #[test]
+    fn os_release_fields_opensuse_tumbleweed() {
+        let fields = OsReleaseFields::read(&fixture("openSUSE_Tumbleweed"));
+        assert_eq!(
+            fields.id_like,
+            Some(vec!["opensuse".to_string(), "suse".to_string()])
+        );
+        assert_eq!(fields.build_id, None);
+    }
+
+    #[test]
+    fn os_release_fields_minimal_custom_distro() {
+        let fields = OsReleaseFields::read(&fixture("Custom_minimal"));
+        assert_eq!(
+            fields,
+            OsReleaseFields {
+                id_like: Some(vec!["rhel".to_string(), "fedora".to_string()]),
+                build_id: Some("2024-05-01 \"nightly\"".to_string()),
+                variant: Some("edge".to_string()),
+            }
+        );
+        assert_eq!(
+            OsReleaseFields::read(&fixture("does_not_exist")),
+            OsReleaseFields::default()
+        );
+    }
+
     #[test]
     fn retrieve_release_info_handles_missing_file() {
         let temp_dir = tempfile::tempdir().unwrap();
@@ -0,0 +1,119 @@
+use log::trace;
+
+/// Returns the number of logical CPUs available to the process.
+///
+/// `std::thread::available_parallelism` is asked first, as it honours CPU
+/// affinity masks and cgroup quotas. If it fails, the `processor` entries of
+/// `/proc/cpuinfo` are counted on Linux and `sysctl hw.logicalcpu` is
+/// queried on macOS.
+///
+/// # Returns
+///
+/// * `Option<usize>` - The number of logical CPUs, or `None` if it cannot be determined.
+pub fn get() -> Option<usize> {
+    match std::thread::available_parallelism() {
+        Ok(count) => Some(count.get()),
+        Err(e) => {
+            trace!("available_parallelism() failed: {:?}", e);
+            fallback()
+        }
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn fallback() -> Option<usize> {
+    match std::fs::read_to_string("/proc/cpuinfo") {
+        Ok(cpuinfo) => parse_cpuinfo(&cpuinfo),
+        Err(e) => {
+            trace!("Failed to read /proc/cpuinfo: {:?}", e);
+            None
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn fallback() -> Option<usize> {
+    use std::process::Command;
+
+    let output = Command::new("sysctl")
+        .arg("-n")
+        .arg("hw.logicalcpu")
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        trace!("'sysctl -n hw.logicalcpu' failed: {:?}", output.status);
+        return None;
+    }
+    String::from_utf8_lossy(&output.stdout)
+        .trim()
+        .parse()
+        .ok()
+        .filter(|&count| count > 0)
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android", target_os = "macos")))]
+fn fallback() -> Option<usize> {
+    None
+}
+
+/// Counts the `processor` entries in `/proc/cpuinfo` content.
+///
+/// Returns `None` if there are none, e.g. on kernels that only report a
+/// single summary block.
+#[cfg_attr(not(any(target_os = "linux", target_os = "android")), allow(dead_code))]
+pub(crate) fn parse_cpuinfo(cpuinfo: &str) -> Option<usize> {
+    let count = cpuinfo
+        .lines()
+        .filter(|line| {
+            line.split_once(':')
+                .is_some_and(|(key, _)| key.trim() == "processor")
+        })
+        .count();
+    if count > 0 {
+        Some(count)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn host_has_at_least_one_cpu() {
+        assert!(get().is_some_and(|count| count >= 1));
+    }
+
+    #[test]
+    fn parse_counts_processor_entries() {
+        let cpuinfo = "processor\t: 0\n\
+                       vendor_id\t: GenuineIntel\n\
+                       model name\t: Intel(R) Core(TM) i5-7200U CPU @ 2.50GHz\n\
+                       \n\
+                       processor\t: 1\n\
+                       vendor_id\t: GenuineIntel\n\
+                       \n\
+                       processor\t: 2\n\
+                       vendor_id\t: GenuineIntel\n\
+                       \n\
+                       processor\t: 3\n\
+                       vendor_id\t: GenuineIntel\n";
+        assert_eq!(parse_cpuinfo(cpuinfo), Some(4));
+    }
+
+    #[test]
+    fn parse_ignores_similar_keys() {
+        let cpuinfo = "Processor\t: ARMv7 Processor rev 4 (v7l)\n\
+                       processor\t: 0\n\
+                       model name\t: processor\n";
+        assert_eq!(parse_cpuinfo(cpuinfo), Some(1));
+    }
+
+    #[test]
+    fn parse_without_processors_is_none() {
+        assert_eq!(parse_cpuinfo("model name\t: Unknown\n"), None);
+        assert_eq!(parse_cpuinfo(""), None);
+    }
+}
@@ -3,15 +3,22 @@ use std::process::Command;
 
 use log::{trace, warn};
 
-use crate::{kernel_version, system_info::Info, system_os::Type, SystemMatcher, SystemVersion};
+use crate::{
+    hostname, kernel_version, system_info::Info, system_os::Type, uptime, SystemMatcher,
+    SystemVersion,
+};
 
 pub fn current_platform() -> Info {
     trace!("macos::current_platform() is called");
 
+    let version = version();
     let info = Info {
         system_type: Type::Macos,
-        version: version(),
+        codename: codename(&version).map(String::from),
+        version,
         kernel_version: kernel_version::get(),
+        hostname: hostname::get(),
+        uptime: uptime::get(),
         ..Default::default()
     };
     trace!("Returning {:?}", info);
@@ -25,6 +32,63 @@ fn version() -> SystemVersion {
     }
 }
 
+/// Marketing names by release, as `(major, minor, name)`.
+///
+/// The minor number only distinguishes releases before macOS 11, where each
+/// `10.x` had its own name; later releases are matched on the major number.
+const CODENAMES: &[(u64, u64, &str)] = &[
+    (10, 0, "Cheetah"),
+    (10, 1, "Puma"),
+    (10, 2, "Jaguar"),
+    (10, 3, "Panther"),
+    (10, 4, "Tiger"),
+    (10, 5, "Leopard"),
+    (10, 6, "Snow Leopard"),
+    (10, 7, "Lion"),
+    (10, 8, "Mountain Lion"),
+    (10, 9, "Mavericks"),
+    (10, 10, "Yosemite"),
+    (10, 11, "El Capitan"),
+    (10, 12, "Sierra"),
+    (10, 13, "High Sierra"),
+    (10, 14, "Mojave"),
+    (10, 15, "Catalina"),
+    // Big Sur reports itself as 10.16 to binaries built with older SDKs
+    (10, 16, "Big Sur"),
+    (11, 0, "Big Sur"),
+    (12, 0, "Monterey"),
+    (13, 0, "Ventura"),
+    (14, 0, "Sonoma"),
+    (15, 0, "Sequoia"),
+    (26, 0, "Tahoe"),
+];
+
+/// Returns the marketing name of a macOS version, e.g. "Ventura" for 13.x.
+///
+/// `sw_vers` omits the patch number of `.0` releases ("14.5"), which
+/// `SystemVersion` keeps as a custom string, so those are parsed here too.
+fn codename(version: &SystemVersion) -> Option<&'static str> {
+    let (major, minor) = match *version {
+        SystemVersion::Semantic(major, minor, _) | SystemVersion::PreRelease(major, minor, ..) => {
+            (major, minor)
+        }
+        SystemVersion::Custom(ref version) => {
+            let mut parts = version.trim().split('.');
+            let major = parts.next()?.parse().ok()?;
+            let minor = parts.next().map_or(Some(0), |minor| minor.parse().ok())?;
+            (major, minor)
+        }
+        _ => return None,
+    };
+    let minor = if major == 10 { minor } else { 0 };
+    CODENAMES
+        .iter()
+        .find(|&&(release_major, release_minor, _)| {
+            release_major == major && release_minor == minor
+        })
+        .map(|&(_, _, name)| name)
+}
+
 fn product_version() -> Option<String> {
     match Command::new("sw_vers").output() {
         Ok(value) => {
@@ -39,11 +103,29 @@ fn product_version() -> Option<String> {
     }
 }
 
+/// Returns the product version from `sw_vers` output, with `-beta`
+/// appended for beta builds.
+///
+/// A beta reports the version it will be released as ("14.0"), and only its
+/// build number tells it apart by ending in a lowercase letter
+/// ("23A5257q"). Rapid Security Responses end in a letter too, but report
+/// it as `ProductVersionExtra`.
 fn parce(sw_vers_output: &str) -> Option<String> {
-    SystemMatcher::PrefixedVersion {
+    let version = SystemMatcher::PrefixedVersion {
         prefix: "ProductVersion:",
     }
+    .find(sw_vers_output)?;
+    let beta = SystemMatcher::PrefixedWord {
+        prefix: "BuildVersion:",
+    }
     .find(sw_vers_output)
+    .is_some_and(|build| build.ends_with(|c: char| c.is_ascii_lowercase()))
+        && !sw_vers_output.contains("ProductVersionExtra:");
+    Some(if beta {
+        format!("{}-beta", version)
+    } else {
+        version
+    })
 }
 
 #[cfg(test)]
@@ -72,7 +154,23 @@ mod macos_tests {
     #[test]
     fn parce_version_macos() {
         let parce_version_macos_output = parce(sw_vers_output());
-        assert!(parce_version_macos_output.is_some());
+        assert_eq!(parce_version_macos_output.as_deref(), Some("10.15.7"));
+    }
+
+    #[test]
+    fn parce_two_and_three_component_versions() {
+        let big_sur = "ProductName:	macOS\n\
+                       ProductVersion:	11.0\n\
+                       BuildVersion:	20A2411";
+        assert_eq!(parce(big_sur).as_deref(), Some("11.0"));
+        let big_sur_update = "ProductName:	macOS\n\
+                              ProductVersion:	11.0.1\n\
+                              BuildVersion:	20B29";
+        assert_eq!(parce(big_sur_update).as_deref(), Some("11.0.1"));
+        assert_eq!(
+            SystemVersion::from_string(parce(big_sur_update).unwrap()),
+            SystemVersion::semantic(11, 0, 1)
+        );
     }
 
     fn sw_vers_output() -> &'static str {
@@ -84,19 +182,33 @@ mod macos_tests {
     #[test]
     fn parce_beta_version_macos() {
         let parce_output_macos = parce(sw_vers_output_beta());
-        assert!(parce_output_macos.is_none());
+        assert_eq!(parce_output_macos.as_deref(), Some("14.0-beta"));
+        let version = SystemVersion::from_string(parce_output_macos.unwrap());
+        assert_eq!(version, SystemVersion::semantic_pre(14, 0, 0, "beta"));
+        assert!(version < SystemVersion::from_string("14.0"));
+        assert_eq!(codename(&version), Some("Sonoma"));
     }
 
+    /// `sw_vers` of the first macOS Sonoma developer beta
     fn sw_vers_output_beta() -> &'static str {
-        "ProductName:	Mac OS X\n\
-         ProductVersion:	11.0.1\n\
-         BuildVersion:	20B29"
+        "ProductName:\t\tmacOS\n\
+         ProductVersion:\t\t14.0\n\
+         BuildVersion:\t\t23A5257q\n"
+    }
+
+    #[test]
+    fn parce_rapid_security_response_is_not_beta() {
+        let output = "ProductName:\t\tmacOS\n\
+                      ProductVersion:\t\t13.3.1\n\
+                      ProductVersionExtra:\t(a)\n\
+                      BuildVersion:\t\t22E772610a\n";
+        assert_eq!(parce(output).as_deref(), Some("13.3.1"));
     }
 
     #[test]
     fn parce_double_digit_patch_version() {
         let parce_output_macos = parce(sw_vers_output_double_digit_patch_version());
-        assert!(parce_output_macos.is_some());
+        assert_eq!(parce_output_macos.as_deref(), Some("10.15.21"));
     }
 
     fn sw_vers_output_double_digit_patch_version() -> &'static str {
@@ -104,4 +216,62 @@ mod macos_tests {
         ProductVersion:	10.15.21\n\
         BuildVersion:	ABCD123"
     }
+
+    #[test]
+    fn codename_ventura() {
+        let version = SystemVersion::from_string("13.6.1");
+        assert_eq!(codename(&version), Some("Ventura"));
+    }
+
+    #[test]
+    fn codename_catalina() {
+        let version = SystemVersion::from_string("10.15.7");
+        assert_eq!(codename(&version), Some("Catalina"));
+    }
+
+    #[test]
+    fn codename_without_patch_number() {
+        assert_eq!(
+            codename(&SystemVersion::from_string("14.5")),
+            Some("Sonoma")
+        );
+        assert_eq!(codename(&SystemVersion::from_string("15")), Some("Sequoia"));
+    }
+
+    #[test]
+    fn codename_every_release() {
+        for &(major, minor, name) in CODENAMES {
+            let version = SystemVersion::from_string(format!("{}.{}.1", major, minor));
+            assert_eq!(codename(&version), Some(name), "{}.{}", major, minor);
+        }
+        let modern = [
+            ("10.15.7", "Catalina"),
+            ("11.7.10", "Big Sur"),
+            ("12.7.6", "Monterey"),
+            ("13.7.8", "Ventura"),
+            ("14.7.1", "Sonoma"),
+            ("15.6.1", "Sequoia"),
+        ];
+        for (version, name) in modern {
+            assert_eq!(
+                codename(&SystemVersion::from_string(version)),
+                Some(name),
+                "{}",
+                version
+            );
+        }
+    }
+
+    #[test]
+    fn current_platform_fills_codename() {
+        let info = current_platform();
+        assert_eq!(info.codename(), codename(info.version()));
+    }
+
+    #[test]
+    fn codename_unknown_release() {
+        assert_eq!(codename(&SystemVersion::from_string("10.17.0")), None);
+        assert_eq!(codename(&SystemVersion::from_string("99.1.0")), None);
+        assert_eq!(codename(&SystemVersion::Unknown), None);
+    }
 }
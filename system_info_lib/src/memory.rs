@@ -0,0 +1,755 @@
+//src/memory.rs
+// spell-checker:ignore smbios, dimm, lpddr, sodimm, rsmb, dmi
+
+#![cfg_attr(windows, allow(unsafe_code))]
+
+use log::trace;
+
+/// An installed memory module (a DIMM, or soldered memory).
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MemoryModule {
+    /// Slot the module is installed in (`DIMM_A1`, `ChannelA-DIMM0`, ...).
+    pub locator: Option<String>,
+    /// Memory technology (`DDR4`, `DDR5`, `LPDDR5`, ...).
+    pub memory_type: Option<String>,
+    /// Module size in bytes.
+    pub size_bytes: Option<u64>,
+    /// Maximum rated speed in MT/s.
+    pub speed_mts: Option<u32>,
+    /// Speed the module is configured to run at, in MT/s.
+    pub configured_speed_mts: Option<u32>,
+    /// Module manufacturer, as reported by the firmware.
+    pub manufacturer: Option<String>,
+}
+
+/// Installed system memory.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MemoryInfo {
+    /// The installed modules; empty slots are not listed.
+    pub modules: Vec<MemoryModule>,
+}
+
+impl MemoryInfo {
+    /// Returns the combined size of all modules with a known size, in bytes.
+    pub fn total_bytes(&self) -> Option<u64> {
+        self.modules
+            .iter()
+            .filter_map(|module| module.size_bytes)
+            .fold(None, |total, size| {
+                Some(total.unwrap_or(0u64).saturating_add(size))
+            })
+    }
+
+    /// Returns the speed the memory runs at, in MT/s.
+    ///
+    /// This is the slowest configured speed of all modules, falling back to
+    /// the rated speed for modules that do not report a configured one.
+    pub fn configured_speed_mts(&self) -> Option<u32> {
+        self.modules
+            .iter()
+            .filter_map(|module| module.configured_speed_mts.or(module.speed_mts))
+            .min()
+    }
+
+    /// Returns the memory technology of the first module that reports one.
+    pub fn memory_type(&self) -> Option<&str> {
+        self.modules
+            .iter()
+            .find_map(|module| module.memory_type.as_deref())
+    }
+}
+
+/// Returns the installed memory modules of the running system.
+///
+/// Read from the SMBIOS memory device (type 17) structures: through
+/// `/sys/firmware/dmi/entries` on Linux, which is usually only readable by
+/// root, and through the raw firmware table on Windows. On macOS the output
+/// of `system_profiler SPMemoryDataType` is used; on Apple Silicon, whose
+/// memory is part of the chip package, type and speed come from a table of
+/// known chips.
+///
+/// # Returns
+///
+/// * `Option<MemoryInfo>` - The modules, or `None` if none could be read.
+pub fn get() -> Option<MemoryInfo> {
+    let modules = imp::modules();
+    if modules.is_empty() {
+        trace!("No memory module information available");
+        None
+    } else {
+        Some(MemoryInfo { modules })
+    }
+}
+
+/// SMBIOS structure type of a memory device.
+const SMBIOS_MEMORY_DEVICE: u8 = 17;
+
+/// SMBIOS structure type marking the end of the table.
+const SMBIOS_END_OF_TABLE: u8 = 127;
+
+/// Length of the formatted area of a SMBIOS 2.1 memory device, the oldest supported.
+const MEMORY_DEVICE_MIN_LENGTH: usize = 0x15;
+
+/// Reads a little-endian `u16` at `offset`, if it lies within `data`.
+fn word(data: &[u8], offset: usize) -> Option<u16> {
+    data.get(offset..offset + 2)
+        .map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]]))
+}
+
+/// Reads a little-endian `u32` at `offset`, if it lies within `data`.
+fn dword(data: &[u8], offset: usize) -> Option<u32> {
+    data.get(offset..offset + 4)
+        .map(|bytes| u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+/// Returns string `index` (1-based) of a SMBIOS string set.
+///
+/// Placeholders such as `Not Specified` are treated as absent.
+fn smbios_string(strings: &[u8], index: u8) -> Option<String> {
+    if index == 0 {
+        return None;
+    }
+    let value = strings
+        .split(|&byte| byte == 0)
+        .take_while(|value| !value.is_empty())
+        .nth(usize::from(index) - 1)?;
+    let value = String::from_utf8_lossy(value).trim().to_owned();
+    match value.as_str() {
+        "" | "Not Specified" | "Unknown" | "Undefined" | "NO DIMM" => None,
+        _ => Some(value),
+    }
+}
+
+/// Returns the name of a SMBIOS memory type.
+fn smbios_memory_type(value: u8) -> Option<&'static str> {
+    let name = match value {
+        0x0F => "SDRAM",
+        0x11 => "RDRAM",
+        0x12 => "DDR",
+        0x13 => "DDR2",
+        0x14 => "DDR2 FB-DIMM",
+        0x18 => "DDR3",
+        0x1A => "DDR4",
+        0x1B => "LPDDR",
+        0x1C => "LPDDR2",
+        0x1D => "LPDDR3",
+        0x1E => "LPDDR4",
+        0x20 => "HBM",
+        0x21 => "HBM2",
+        0x22 => "DDR5",
+        0x23 => "LPDDR5",
+        0x24 => "HBM3",
+        _ => return None,
+    };
+    Some(name)
+}
+
+/// Reads a speed word, following `0xFFFF` to the 32-bit extended field.
+fn smbios_speed(formatted: &[u8], offset: usize, extended_offset: usize) -> Option<u32> {
+    match word(formatted, offset)? {
+        0 => None,
+        0xFFFF => dword(formatted, extended_offset).filter(|&speed| speed != 0),
+        speed => Some(u32::from(speed)),
+    }
+}
+
+/// Parses one SMBIOS memory device (type 17) structure, including its strings.
+///
+/// Returns `None` for other structure types, truncated structures and
+/// empty slots.
+#[cfg_attr(
+    not(any(target_os = "linux", target_os = "android", windows)),
+    allow(dead_code)
+)]
+pub(crate) fn parse_memory_device(structure: &[u8]) -> Option<MemoryModule> {
+    if structure.first() != Some(&SMBIOS_MEMORY_DEVICE) {
+        return None;
+    }
+    let length = usize::from(*structure.get(1)?);
+    if length < MEMORY_DEVICE_MIN_LENGTH || length > structure.len() {
+        trace!("Skipping truncated SMBIOS memory device");
+        return None;
+    }
+    let (formatted, strings) = structure.split_at(length);
+
+    let size_bytes = match word(formatted, 0x0C)? {
+        0 => return None,
+        0xFFFF => None,
+        // Sizes of 32 GiB and more are stored in the extended size, in MiB
+        0x7FFF => dword(formatted, 0x1C).map(|mib| u64::from(mib & 0x7FFF_FFFF) << 20),
+        size if size & 0x8000 != 0 => Some(u64::from(size & 0x7FFF) << 10),
+        size => Some(u64::from(size) << 20),
+    };
+    let locator =
+        smbios_string(strings, formatted[0x10]).or_else(|| smbios_string(strings, formatted[0x11]));
+    let manufacturer = formatted
+        .get(0x17)
+        .and_then(|&index| smbios_string(strings, index));
+    Some(MemoryModule {
+        locator,
+        memory_type: smbios_memory_type(formatted[0x12]).map(str::to_owned),
+        size_bytes,
+        speed_mts: smbios_speed(formatted, 0x15, 0x54),
+        configured_speed_mts: smbios_speed(formatted, 0x20, 0x58),
+        manufacturer,
+    })
+}
+
+/// Splits a SMBIOS table into structures, each with its string set.
+///
+/// Stops at the end-of-table structure or at the first malformed structure.
+#[cfg_attr(not(windows), allow(dead_code))]
+pub(crate) fn split_smbios_table(table: &[u8]) -> Vec<&[u8]> {
+    let mut structures = Vec::new();
+    let mut offset = 0;
+    while let Some(header) = table.get(offset..offset + 4) {
+        let length = usize::from(header[1]);
+        if length < 4 {
+            break;
+        }
+        // The string set ends with two consecutive NUL bytes
+        let end = match table
+            .get(offset + length..)
+            .and_then(|rest| rest.windows(2).position(|pair| pair == [0, 0]))
+        {
+            Some(position) => offset + length + position + 2,
+            None => break,
+        };
+        structures.push(&table[offset..end]);
+        if header[0] == SMBIOS_END_OF_TABLE {
+            break;
+        }
+        offset = end;
+    }
+    structures
+}
+
+/// Parses the memory devices of a `RawSMBIOSData` buffer as returned by
+/// `GetSystemFirmwareTable('RSMB')`.
+#[cfg_attr(not(windows), allow(dead_code))]
+pub(crate) fn parse_raw_smbios_data(data: &[u8]) -> Vec<MemoryModule> {
+    // BYTE Used20CallingMethod, SMBIOSMajorVersion, SMBIOSMinorVersion,
+    // DmiRevision; DWORD Length; BYTE SMBIOSTableData[]
+    let length = match dword(data, 4) {
+        Some(length) => length as usize,
+        None => return Vec::new(),
+    };
+    let table = &data[8..data.len().min(8usize.saturating_add(length))];
+    split_smbios_table(table)
+        .into_iter()
+        .filter_map(parse_memory_device)
+        .collect()
+}
+
+/// Reads the memory devices from a directory laid out like `/sys/firmware/dmi/entries`.
+#[cfg_attr(not(any(target_os = "linux", target_os = "android")), allow(dead_code))]
+pub(crate) fn read_dmi_entries(entries: &std::path::Path) -> Vec<MemoryModule> {
+    let mut devices: Vec<(u32, std::path::PathBuf)> = match std::fs::read_dir(entries) {
+        Ok(dir) => dir
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let name = entry.file_name();
+                let index = name.to_str()?.strip_prefix("17-")?.parse().ok()?;
+                Some((index, entry.path().join("raw")))
+            })
+            .collect(),
+        Err(error) => {
+            trace!("Cannot read {}: {}", entries.display(), error);
+            return Vec::new();
+        }
+    };
+    devices.sort();
+    devices
+        .into_iter()
+        .filter_map(|(_, raw)| match std::fs::read(&raw) {
+            Ok(structure) => parse_memory_device(&structure),
+            Err(error) => {
+                trace!("Cannot read {}: {}", raw.display(), error);
+                None
+            }
+        })
+        .collect()
+}
+
+/// Memory of Apple Silicon chips, as `(chip, memory type, speed in MT/s)`.
+///
+/// Variants are listed before the base chip of the same generation.
+const APPLE_CHIP_MEMORY: [(&str, &str, u32); 9] = [
+    ("M1 Pro", "LPDDR5", 6400),
+    ("M1 Max", "LPDDR5", 6400),
+    ("M1 Ultra", "LPDDR5", 6400),
+    ("M1", "LPDDR4X", 4266),
+    ("M2", "LPDDR5", 6400),
+    ("M3", "LPDDR5", 6400),
+    ("M4 Pro", "LPDDR5X", 8533),
+    ("M4 Max", "LPDDR5X", 8533),
+    ("M4", "LPDDR5X", 7500),
+];
+
+/// Looks up the memory type and speed of an Apple Silicon chip such as `Apple M2 Pro`.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+pub(crate) fn apple_chip_memory(chip: &str) -> Option<(&'static str, u32)> {
+    let model = chip.trim();
+    let model = model.strip_prefix("Apple ").unwrap_or(model);
+    APPLE_CHIP_MEMORY
+        .iter()
+        .find(|(name, _, _)| {
+            model
+                .strip_prefix(name)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with(' '))
+        })
+        .map(|&(_, memory_type, speed)| (memory_type, speed))
+}
+
+/// Parses a `system_profiler` size such as `16 GB`; `empty` slots yield `None`.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn parse_profiler_size(size: &str) -> Option<u64> {
+    let mut parts = size.split_whitespace();
+    let value: u64 = parts.next()?.parse().ok()?;
+    let shift = match parts.next()? {
+        "KB" => 10,
+        "MB" => 20,
+        "GB" => 30,
+        "TB" => 40,
+        _ => return None,
+    };
+    Some(value << shift)
+}
+
+/// Builds the modules from `system_profiler -json SPMemoryDataType` output
+/// and the chip name from `sysctl -n machdep.cpu.brand_string`.
+///
+/// Intel Macs list one item per slot. Apple Silicon reports a single entry
+/// without speed, which is completed from the chip table; if the output is
+/// missing, a module without size is reported for known chips.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+pub(crate) fn from_macos_output(profiler: Option<&str>, chip: Option<&str>) -> Vec<MemoryModule> {
+    use serde_json::Value;
+
+    fn text(value: &Value, key: &str) -> Option<String> {
+        value
+            .get(key)
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_owned)
+    }
+
+    let entries = profiler
+        .and_then(|output| serde_json::from_str::<Value>(output).ok())
+        .and_then(|value| value.get("SPMemoryDataType")?.as_array().cloned())
+        .unwrap_or_default();
+    let chip_memory = chip.and_then(apple_chip_memory);
+
+    let mut modules = Vec::new();
+    for entry in &entries {
+        match entry.get("_items").and_then(Value::as_array) {
+            Some(items) => {
+                for item in items {
+                    let size_bytes = match text(item, "dimm_size") {
+                        Some(size) => match parse_profiler_size(&size) {
+                            Some(size) => Some(size),
+                            None => continue,
+                        },
+                        None => None,
+                    };
+                    let speed = text(item, "dimm_speed").and_then(|speed| {
+                        speed.split_whitespace().next().and_then(|v| v.parse().ok())
+                    });
+                    modules.push(MemoryModule {
+                        locator: text(item, "_name"),
+                        memory_type: text(item, "dimm_type"),
+                        size_bytes,
+                        speed_mts: speed,
+                        configured_speed_mts: speed,
+                        manufacturer: text(item, "dimm_manufacturer"),
+                    });
+                }
+            }
+            None => {
+                let (memory_type, speed) = match chip_memory {
+                    Some((memory_type, speed)) => (Some(memory_type.to_owned()), Some(speed)),
+                    None => (text(entry, "dimm_type"), None),
+                };
+                modules.push(MemoryModule {
+                    locator: None,
+                    memory_type,
+                    size_bytes: text(entry, "SPMemoryDataType")
+                        .and_then(|size| parse_profiler_size(&size)),
+                    speed_mts: speed,
+                    configured_speed_mts: speed,
+                    manufacturer: text(entry, "dimm_manufacturer"),
+                });
+            }
+        }
+    }
+    if modules.is_empty() {
+        if let Some((memory_type, speed)) = chip_memory {
+            modules.push(MemoryModule {
+                locator: None,
+                memory_type: Some(memory_type.to_owned()),
+                size_bytes: None,
+                speed_mts: Some(speed),
+                configured_speed_mts: Some(speed),
+                manufacturer: None,
+            });
+        }
+    }
+    modules
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+mod imp {
+    use super::MemoryModule;
+    use std::path::Path;
+
+    pub fn modules() -> Vec<MemoryModule> {
+        super::read_dmi_entries(Path::new("/sys/firmware/dmi/entries"))
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod imp {
+    use super::MemoryModule;
+    use log::trace;
+    use std::process::Command;
+
+    pub fn modules() -> Vec<MemoryModule> {
+        let profiler = run("system_profiler", &["-json", "SPMemoryDataType"]);
+        let chip = run("sysctl", &["-n", "machdep.cpu.brand_string"]);
+        super::from_macos_output(profiler.as_deref(), chip.as_deref())
+    }
+
+    fn run(program: &str, args: &[&str]) -> Option<String> {
+        match Command::new(program).args(args).output() {
+            Ok(output) if output.status.success() => {
+                Some(String::from_utf8_lossy(&output.stdout).into_owned())
+            }
+            Ok(output) => {
+                trace!("{} exited with {}", program, output.status);
+                None
+            }
+            Err(error) => {
+                trace!("Cannot run {}: {}", program, error);
+                None
+            }
+        }
+    }
+}
+
+#[cfg(windows)]
+mod imp {
+    use super::MemoryModule;
+    use log::trace;
+    use std::ptr;
+    use windows_sys::Win32::System::SystemInformation::GetSystemFirmwareTable;
+
+    /// Provider signature of the raw SMBIOS table
+    const RSMB: u32 = u32::from_be_bytes(*b"RSMB");
+
+    pub fn modules() -> Vec<MemoryModule> {
+        let size = unsafe { GetSystemFirmwareTable(RSMB, 0, ptr::null_mut(), 0) };
+        if size == 0 {
+            trace!(
+                "GetSystemFirmwareTable failed: {:?}",
+                std::io::Error::last_os_error()
+            );
+            return Vec::new();
+        }
+        let mut buffer = vec![0u8; size as usize];
+        let written = unsafe { GetSystemFirmwareTable(RSMB, 0, buffer.as_mut_ptr().cast(), size) };
+        if written == 0 || written > size {
+            trace!("SMBIOS table changed size or could not be read");
+            return Vec::new();
+        }
+        buffer.truncate(written as usize);
+        super::parse_raw_smbios_data(&buffer)
+    }
+}
+
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    windows
+)))]
+mod imp {
+    use super::MemoryModule;
+
+    pub fn modules() -> Vec<MemoryModule> {
+        Vec::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::fs;
+
+    /// Builds a SMBIOS 3.3 memory device (length 0x5C) followed by `strings`.
+    fn memory_device(
+        size: u16,
+        memory_type: u8,
+        speed: u16,
+        configured_speed: u16,
+        strings: &[&str],
+    ) -> Vec<u8> {
+        let mut formatted = vec![0u8; 0x5C];
+        formatted[0] = SMBIOS_MEMORY_DEVICE;
+        formatted[1] = 0x5C;
+        formatted[0x0C..0x0E].copy_from_slice(&size.to_le_bytes());
+        formatted[0x0E] = 0x09; // DIMM
+        formatted[0x10] = 1; // device locator
+        formatted[0x11] = 2; // bank locator
+        formatted[0x12] = memory_type;
+        formatted[0x15..0x17].copy_from_slice(&speed.to_le_bytes());
+        formatted[0x17] = 3; // manufacturer
+        formatted[0x20..0x22].copy_from_slice(&configured_speed.to_le_bytes());
+        with_strings(formatted, strings)
+    }
+
+    fn with_strings(mut structure: Vec<u8>, strings: &[&str]) -> Vec<u8> {
+        for string in strings {
+            structure.extend_from_slice(string.as_bytes());
+            structure.push(0);
+        }
+        if strings.is_empty() {
+            structure.push(0);
+        }
+        structure.push(0);
+        structure
+    }
+
+    fn ddr4_dimm() -> Vec<u8> {
+        memory_device(16384, 0x1A, 3200, 2933, &["DIMM_A1", "BANK 0", "Samsung"])
+    }
+
+    fn module(
+        locator: &str,
+        memory_type: &str,
+        size_gib: u64,
+        speed: u32,
+        configured: u32,
+    ) -> MemoryModule {
+        MemoryModule {
+            locator: Some(locator.to_owned()),
+            memory_type: Some(memory_type.to_owned()),
+            size_bytes: Some(size_gib << 30),
+            speed_mts: Some(speed),
+            configured_speed_mts: Some(configured),
+            manufacturer: None,
+        }
+    }
+
+    #[test]
+    fn memory_device_parsing() {
+        assert_eq!(
+            parse_memory_device(&ddr4_dimm()),
+            Some(MemoryModule {
+                manufacturer: Some("Samsung".to_owned()),
+                ..module("DIMM_A1", "DDR4", 16, 3200, 2933)
+            })
+        );
+
+        // Extended size and speeds, placeholder strings, bank locator fallback
+        let mut ddr5 = memory_device(
+            0x7FFF,
+            0x22,
+            0xFFFF,
+            0xFFFF,
+            &["Not Specified", "P0 CHANNEL A", "Unknown"],
+        );
+        ddr5[0x1C..0x20].copy_from_slice(&(64u32 * 1024).to_le_bytes());
+        ddr5[0x54..0x58].copy_from_slice(&8000u32.to_le_bytes());
+        ddr5[0x58..0x5C].copy_from_slice(&6400u32.to_le_bytes());
+        assert_eq!(
+            parse_memory_device(&ddr5),
+            Some(module("P0 CHANNEL A", "DDR5", 64, 8000, 6400))
+        );
+
+        // Size in KiB, unknown speed and an SMBIOS 2.3 structure without
+        // configured speed
+        let mut old = memory_device(0x8000 | 512, 0x18, 0, 0, &[]);
+        old.drain(0x1C..0x5C);
+        old[1] = 0x1C;
+        old[0x10] = 0;
+        old[0x11] = 0;
+        old[0x17] = 0;
+        assert_eq!(
+            parse_memory_device(&old),
+            Some(MemoryModule {
+                locator: None,
+                memory_type: Some("DDR3".to_owned()),
+                size_bytes: Some(512 << 10),
+                speed_mts: None,
+                configured_speed_mts: None,
+                manufacturer: None,
+            })
+        );
+    }
+
+    #[test]
+    fn memory_device_bounds() {
+        // Empty slot
+        assert_eq!(
+            parse_memory_device(&memory_device(0, 0x02, 0, 0, &["DIMM_B1"])),
+            None
+        );
+        // Other structure type
+        let mut bios = ddr4_dimm();
+        bios[0] = 0;
+        assert_eq!(parse_memory_device(&bios), None);
+        // Declared length beyond the data, or below the minimum
+        let dimm = ddr4_dimm();
+        for len in [0, 1, 2, 0x0C, 0x14, 0x30] {
+            assert_eq!(parse_memory_device(&dimm[..len]), None, "{}", len);
+        }
+        let mut short = dimm.clone();
+        short[1] = 0x10;
+        assert_eq!(parse_memory_device(&short), None);
+        // String indexes past the string set
+        let mut missing = memory_device(8192, 0x1A, 2666, 2666, &["DIMM_A1"]);
+        missing[0x10] = 5;
+        missing[0x11] = 9;
+        let parsed = parse_memory_device(&missing).unwrap();
+        assert_eq!(parsed.locator, None);
+        assert_eq!(parsed.manufacturer, None);
+        assert_eq!(parsed.size_bytes, Some(8 << 30));
+    }
+
+    #[test]
+    fn raw_smbios_table() {
+        let bios = with_strings(
+            vec![0, 0x18, 0, 0].into_iter().chain([0; 20]).collect(),
+            &["Vendor", "1.0"],
+        );
+        let array = with_strings(
+            vec![16, 0x0F, 1, 0].into_iter().chain([0; 11]).collect(),
+            &[],
+        );
+        let empty = memory_device(0, 0x02, 0, 0, &["DIMM_A2"]);
+        let end = with_strings(vec![SMBIOS_END_OF_TABLE, 4, 0xFF, 0xFF], &[]);
+        let second = memory_device(16384, 0x1A, 3200, 3200, &["DIMM_B1"]);
+
+        let mut table = Vec::new();
+        for structure in [
+            &bios,
+            &array,
+            &ddr4_dimm(),
+            &empty,
+            &second,
+            &end,
+            &ddr4_dimm(),
+        ] {
+            table.extend_from_slice(structure);
+        }
+        assert_eq!(split_smbios_table(&table).len(), 6);
+
+        let mut data = vec![0, 3, 3, 0];
+        data.extend_from_slice(&(table.len() as u32).to_le_bytes());
+        data.extend_from_slice(&table);
+        let modules = parse_raw_smbios_data(&data);
+        assert_eq!(modules.len(), 2);
+        assert_eq!(modules[1].locator.as_deref(), Some("DIMM_B1"));
+
+        let info = MemoryInfo { modules };
+        assert_eq!(info.total_bytes(), Some(32 << 30));
+        assert_eq!(info.configured_speed_mts(), Some(2933));
+        assert_eq!(info.memory_type(), Some("DDR4"));
+
+        // Truncated buffers never panic
+        for len in 0..data.len() {
+            let _ = parse_raw_smbios_data(&data[..len]);
+        }
+        let mut oversized = data.clone();
+        oversized[4..8].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert_eq!(parse_raw_smbios_data(&oversized).len(), 2);
+    }
+
+    #[test]
+    fn dmi_entries_fixture() {
+        let root = tempfile::tempdir().unwrap();
+        let entries = root.path();
+        for (name, raw) in [
+            ("17-1", memory_device(16384, 0x1A, 3200, 3200, &["DIMM_B1"])),
+            ("17-0", ddr4_dimm()),
+            ("17-2", memory_device(0, 0x02, 0, 0, &["DIMM_C1"])),
+            ("16-0", vec![16, 0x0F, 0, 0, 0, 0]),
+        ] {
+            fs::create_dir(entries.join(name)).unwrap();
+            fs::write(entries.join(name).join("raw"), raw).unwrap();
+        }
+        // Entries without a readable `raw` file are skipped
+        fs::create_dir(entries.join("17-3")).unwrap();
+
+        let modules = read_dmi_entries(entries);
+        let locators: Vec<_> = modules.iter().map(|m| m.locator.as_deref()).collect();
+        assert_eq!(locators, vec![Some("DIMM_A1"), Some("DIMM_B1")]);
+        assert_eq!(read_dmi_entries(&entries.join("missing")), Vec::new());
+    }
+
+    #[test]
+    fn apple_chip_table() {
+        assert_eq!(apple_chip_memory("Apple M1"), Some(("LPDDR4X", 4266)));
+        assert_eq!(apple_chip_memory("Apple M1 Max"), Some(("LPDDR5", 6400)));
+        assert_eq!(apple_chip_memory("Apple M2 Ultra"), Some(("LPDDR5", 6400)));
+        assert_eq!(apple_chip_memory("Apple M4 Pro\n"), Some(("LPDDR5X", 8533)));
+        assert_eq!(apple_chip_memory("Apple M4"), Some(("LPDDR5X", 7500)));
+        assert_eq!(apple_chip_memory("Apple M10"), None);
+        assert_eq!(
+            apple_chip_memory("Intel(R) Core(TM) i9-9880H CPU @ 2.30GHz"),
+            None
+        );
+    }
+
+    #[test]
+    fn macos_output_parsing() {
+        let intel = r#"{"SPMemoryDataType": [{
+            "_name": "Memory",
+            "_items": [
+                {"_name": "BANK 0/ChannelA-DIMM0", "dimm_manufacturer": "0x80AD",
+                 "dimm_size": "8 GB", "dimm_speed": "2667 MHz", "dimm_status": "ok",
+                 "dimm_type": "DDR4"},
+                {"_name": "BANK 1/ChannelB-DIMM0", "dimm_size": "empty",
+                 "dimm_speed": "empty", "dimm_status": "empty", "dimm_type": "empty"}
+            ],
+            "is_memory_upgradeable": "Yes"
+        }]}"#;
+        let modules = from_macos_output(Some(intel), Some("Intel(R) Core(TM) i7"));
+        assert_eq!(
+            modules,
+            vec![MemoryModule {
+                manufacturer: Some("0x80AD".to_owned()),
+                ..module("BANK 0/ChannelA-DIMM0", "DDR4", 8, 2667, 2667)
+            }]
+        );
+
+        let apple = r#"{"SPMemoryDataType": [{
+            "SPMemoryDataType": "16 GB", "dimm_manufacturer": "Hynix", "dimm_type": "LPDDR4"
+        }]}"#;
+        let modules = from_macos_output(Some(apple), Some("Apple M1"));
+        assert_eq!(
+            modules,
+            vec![MemoryModule {
+                locator: None,
+                memory_type: Some("LPDDR4X".to_owned()),
+                size_bytes: Some(16 << 30),
+                speed_mts: Some(4266),
+                configured_speed_mts: Some(4266),
+                manufacturer: Some("Hynix".to_owned()),
+            }]
+        );
+
+        // Unknown chip keeps the reported type; no output falls back to the table
+        let modules = from_macos_output(Some(apple), Some("Apple M9"));
+        assert_eq!(modules[0].memory_type.as_deref(), Some("LPDDR4"));
+        assert_eq!(modules[0].speed_mts, None);
+        let modules = from_macos_output(Some("not json"), Some("Apple M3 Pro"));
+        assert_eq!(modules[0].memory_type.as_deref(), Some("LPDDR5"));
+        assert_eq!(modules[0].size_bytes, None);
+        assert_eq!(from_macos_output(None, None), Vec::new());
+    }
+}
@@ -0,0 +1,742 @@
+//src/network.rs
+// spell-checker:ignore operstate, ifconfig, networksetup, phy80211, baset, ieee80211, sockaddr
+
+#![cfg_attr(windows, allow(unsafe_code))]
+
+use log::trace;
+
+/// Summary of a network interface.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InterfaceInfo {
+    /// Interface name (`eth0`, `en0`, `Ethernet`, ...).
+    pub name: String,
+    /// Hardware address.
+    ///
+    /// Without the `identifiers` feature the address is replaced by a stable
+    /// hash (`hash:` followed by 16 hex digits), so interfaces can still be
+    /// told apart without exposing the MAC.
+    pub mac_address: Option<String>,
+    /// Negotiated link speed in Mbit/s.
+    pub link_speed_mbps: Option<u64>,
+    /// Whether this is a wireless interface.
+    pub wireless: bool,
+    /// Whether the interface is up.
+    pub up: bool,
+    /// Whether the interface has an IPv4 address.
+    pub ipv4: bool,
+    /// Whether the interface has an IPv6 address.
+    pub ipv6: bool,
+    /// Whether the default route goes through this interface.
+    pub primary: bool,
+}
+
+/// Returns the network interfaces of the running system.
+///
+/// Loopback and virtual interfaces without hardware address are skipped.
+/// The primary interface, if any, comes first; the others are ordered by name.
+///
+/// # Returns
+///
+/// * `Option<Vec<InterfaceInfo>>` - The interfaces, or `None` if none were found.
+pub fn get() -> Option<Vec<InterfaceInfo>> {
+    let mut interfaces = imp::interfaces();
+    interfaces.sort_by(|a, b| b.primary.cmp(&a.primary).then_with(|| a.name.cmp(&b.name)));
+    if interfaces.is_empty() {
+        trace!("No network interfaces found");
+        None
+    } else {
+        Some(interfaces)
+    }
+}
+
+/// Formats raw hardware address bytes as `aa:bb:cc:dd:ee:ff`.
+///
+/// Returns `None` for empty or all-zero addresses.
+#[cfg_attr(not(windows), allow(dead_code))]
+pub(crate) fn format_mac(bytes: &[u8]) -> Option<String> {
+    if bytes.iter().all(|&b| b == 0) {
+        return None;
+    }
+    let hex: Vec<String> = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+    Some(hex.join(":"))
+}
+
+/// Returns the MAC as reported, or its stable hash without the `identifiers` feature.
+///
+/// Returns `None` for the all-zero address used by interfaces without hardware.
+pub(crate) fn present_mac(mac: &str) -> Option<String> {
+    let mac = mac.trim().to_ascii_lowercase();
+    if mac.is_empty() || mac.chars().all(|c| c == '0' || c == ':') {
+        return None;
+    }
+    if cfg!(feature = "identifiers") {
+        Some(mac)
+    } else {
+        Some(redact_mac(&mac))
+    }
+}
+
+/// Replaces a MAC with a hash that is stable across runs and Rust versions.
+pub(crate) fn redact_mac(mac: &str) -> String {
+    // 64-bit FNV-1a
+    let hash = mac
+        .to_ascii_lowercase()
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        });
+    format!("hash:{:016x}", hash)
+}
+
+/// Returns the interface of the default IPv4 route from `/proc/net/route` content.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+pub(crate) fn parse_default_route(route: &str) -> Option<String> {
+    const RTF_UP: u32 = 0x1;
+    route.lines().skip(1).find_map(|line| {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() < 8 {
+            return None;
+        }
+        let flags = u32::from_str_radix(fields[3], 16).ok()?;
+        if fields[1] == "00000000" && fields[7] == "00000000" && flags & RTF_UP != 0 {
+            Some(fields[0].to_owned())
+        } else {
+            None
+        }
+    })
+}
+
+/// Returns the interfaces with IPv4 routes from `/proc/net/route` content.
+///
+/// The kernel adds a prefix route for every configured IPv4 address, so an
+/// interface with an address has at least one route.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+pub(crate) fn parse_ipv4_interfaces(route: &str) -> Vec<String> {
+    let mut interfaces: Vec<String> = route
+        .lines()
+        .skip(1)
+        .filter_map(|line| line.split_whitespace().next())
+        .map(str::to_owned)
+        .collect();
+    interfaces.sort();
+    interfaces.dedup();
+    interfaces
+}
+
+/// Returns the interfaces with IPv6 addresses from `/proc/net/if_inet6` content.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+pub(crate) fn parse_ipv6_interfaces(if_inet6: &str) -> Vec<String> {
+    let mut interfaces: Vec<String> = if_inet6
+        .lines()
+        .filter_map(|line| line.split_whitespace().nth(5))
+        .map(str::to_owned)
+        .collect();
+    interfaces.sort();
+    interfaces.dedup();
+    interfaces
+}
+
+/// Parses the `speed` sysfs attribute; `-1` and errors mean unknown.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+pub(crate) fn parse_sysfs_speed(speed: &str) -> Option<u64> {
+    speed.trim().parse::<u64>().ok().filter(|&speed| speed > 0)
+}
+
+/// Reads every interface below a `/sys/class/net`-like directory.
+///
+/// `route` and `if_inet6` are the contents of `/proc/net/route` and
+/// `/proc/net/if_inet6`.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+pub(crate) fn read_sysfs(
+    root: &std::path::Path,
+    route: &str,
+    if_inet6: &str,
+) -> Vec<InterfaceInfo> {
+    use std::fs;
+
+    /// `ARPHRD_LOOPBACK` from `<linux/if_arp.h>`
+    const ARPHRD_LOOPBACK: &str = "772";
+
+    let read = |path: std::path::PathBuf| fs::read_to_string(path).ok();
+    let primary = parse_default_route(route);
+    let ipv4 = parse_ipv4_interfaces(route);
+    let ipv6 = parse_ipv6_interfaces(if_inet6);
+    let entries = match fs::read_dir(root) {
+        Ok(entries) => entries,
+        Err(error) => {
+            trace!("Cannot read {}: {}", root.display(), error);
+            return Vec::new();
+        }
+    };
+    entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let path = entry.path();
+            if read(path.join("type")).is_some_and(|kind| kind.trim() == ARPHRD_LOOPBACK) {
+                return None;
+            }
+            let mac_address = read(path.join("address")).and_then(|mac| present_mac(&mac));
+            mac_address.as_ref()?;
+            Some(InterfaceInfo {
+                link_speed_mbps: read(path.join("speed")).and_then(|s| parse_sysfs_speed(&s)),
+                wireless: path.join("wireless").exists() || path.join("phy80211").exists(),
+                up: read(path.join("operstate")).is_some_and(|state| state.trim() == "up"),
+                ipv4: ipv4.contains(&name),
+                ipv6: ipv6.contains(&name),
+                primary: primary.as_deref() == Some(name.as_str()),
+                mac_address,
+                name,
+            })
+        })
+        .collect()
+}
+
+/// Hardware ports from `networksetup -listallhardwareports`, as `(port, device)`.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+pub(crate) fn parse_hardware_ports(output: &str) -> Vec<(String, String)> {
+    let mut ports = Vec::new();
+    let mut port: Option<&str> = None;
+    for line in output.lines() {
+        if let Some(name) = line.strip_prefix("Hardware Port:") {
+            port = Some(name.trim());
+        } else if let Some(device) = line.strip_prefix("Device:") {
+            if let Some(port) = port.take() {
+                ports.push((port.to_owned(), device.trim().to_owned()));
+            }
+        }
+    }
+    ports
+}
+
+/// Interface details parsed from `ifconfig` output.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+pub(crate) struct IfconfigEntry {
+    pub name: String,
+    pub mac: Option<String>,
+    pub link_speed_mbps: Option<u64>,
+    pub active: bool,
+    pub ipv4: bool,
+    pub ipv6: bool,
+}
+
+/// Parses the output of `ifconfig` on macOS and the BSDs.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+pub(crate) fn parse_ifconfig(output: &str) -> Vec<IfconfigEntry> {
+    let mut entries: Vec<IfconfigEntry> = Vec::new();
+    for line in output.lines() {
+        if !line.starts_with(char::is_whitespace) {
+            if let Some((name, _)) = line.split_once(": ") {
+                entries.push(IfconfigEntry {
+                    name: name.to_owned(),
+                    ..IfconfigEntry::default()
+                });
+            }
+            continue;
+        }
+        let entry = match entries.last_mut() {
+            Some(entry) => entry,
+            None => continue,
+        };
+        let mut fields = line.split_whitespace();
+        match fields.next() {
+            Some("ether") => entry.mac = fields.next().map(str::to_owned),
+            Some("inet") => entry.ipv4 = true,
+            Some("inet6") => entry.ipv6 = true,
+            Some("status:") => entry.active = fields.next() == Some("active"),
+            Some("media:") => entry.link_speed_mbps = parse_media_speed(line),
+            _ => {}
+        }
+    }
+    entries
+}
+
+/// Extracts the speed from an `ifconfig` media line such as
+/// `media: autoselect (1000baseT <full-duplex>)`.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn parse_media_speed(line: &str) -> Option<u64> {
+    let media = line.split('(').nth(1)?;
+    let lower = media.to_ascii_lowercase();
+    let index = lower.find("base")?;
+    let speed = &lower[..index];
+    if let Some(gigabit) = speed.strip_suffix('g') {
+        gigabit.parse::<u64>().ok().map(|g| g * 1000)
+    } else {
+        speed.parse().ok()
+    }
+}
+
+/// Returns the interface from `route -n get default` output.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+pub(crate) fn parse_route_get_interface(output: &str) -> Option<String> {
+    output
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("interface:"))
+        .map(|name| name.trim().to_owned())
+}
+
+/// Combines `networksetup`, `ifconfig` and `route` output into interfaces.
+///
+/// Only devices listed as hardware ports are reported.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+pub(crate) fn from_macos_output(
+    hardware_ports: &str,
+    ifconfig: &str,
+    default_route: &str,
+) -> Vec<InterfaceInfo> {
+    let entries = parse_ifconfig(ifconfig);
+    let primary = parse_route_get_interface(default_route);
+    parse_hardware_ports(hardware_ports)
+        .into_iter()
+        .filter_map(|(port, device)| {
+            let entry = entries.iter().find(|entry| entry.name == device)?;
+            Some(InterfaceInfo {
+                mac_address: entry.mac.as_deref().and_then(present_mac),
+                link_speed_mbps: entry.link_speed_mbps,
+                wireless: port.contains("Wi-Fi") || port.contains("AirPort"),
+                up: entry.active,
+                ipv4: entry.ipv4,
+                ipv6: entry.ipv6,
+                primary: primary.as_deref() == Some(device.as_str()),
+                name: device,
+            })
+        })
+        .collect()
+}
+
+/// Adapter details read from `GetAdaptersAddresses`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(not(windows), allow(dead_code))]
+pub(crate) struct AdapterRecord {
+    pub name: String,
+    pub mac: Vec<u8>,
+    pub if_type: u32,
+    pub up: bool,
+    /// Transmit speed in bit/s; `u64::MAX` if unknown
+    pub transmit_link_speed: u64,
+    pub has_gateway: bool,
+    pub ipv4: bool,
+    pub ipv6: bool,
+}
+
+/// Converts an adapter record, skipping loopback adapters.
+#[cfg_attr(not(windows), allow(dead_code))]
+pub(crate) fn from_adapter(adapter: AdapterRecord) -> Option<InterfaceInfo> {
+    const IF_TYPE_SOFTWARE_LOOPBACK: u32 = 24;
+    const IF_TYPE_IEEE80211: u32 = 71;
+
+    if adapter.if_type == IF_TYPE_SOFTWARE_LOOPBACK {
+        return None;
+    }
+    let link_speed_mbps = match adapter.transmit_link_speed {
+        0 | u64::MAX => None,
+        bits => Some(bits / 1_000_000),
+    };
+    Some(InterfaceInfo {
+        mac_address: format_mac(&adapter.mac).and_then(|mac| present_mac(&mac)),
+        link_speed_mbps,
+        wireless: adapter.if_type == IF_TYPE_IEEE80211,
+        up: adapter.up,
+        ipv4: adapter.ipv4,
+        ipv6: adapter.ipv6,
+        primary: adapter.up && adapter.has_gateway,
+        name: adapter.name,
+    })
+}
+
+#[cfg(target_os = "linux")]
+mod imp {
+    use super::InterfaceInfo;
+    use std::{fs, path::Path};
+
+    pub fn interfaces() -> Vec<InterfaceInfo> {
+        let route = fs::read_to_string("/proc/net/route").unwrap_or_default();
+        let if_inet6 = fs::read_to_string("/proc/net/if_inet6").unwrap_or_default();
+        super::read_sysfs(Path::new("/sys/class/net"), &route, &if_inet6)
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod imp {
+    use super::InterfaceInfo;
+    use log::trace;
+    use std::process::Command;
+
+    pub fn interfaces() -> Vec<InterfaceInfo> {
+        let hardware_ports = match run("networksetup", &["-listallhardwareports"]) {
+            Some(output) => output,
+            None => return Vec::new(),
+        };
+        let ifconfig = run("ifconfig", &[]).unwrap_or_default();
+        let default_route = run("route", &["-n", "get", "default"]).unwrap_or_default();
+        super::from_macos_output(&hardware_ports, &ifconfig, &default_route)
+    }
+
+    fn run(program: &str, args: &[&str]) -> Option<String> {
+        match Command::new(program).args(args).output() {
+            Ok(output) if output.status.success() => {
+                Some(String::from_utf8_lossy(&output.stdout).into_owned())
+            }
+            Ok(output) => {
+                trace!("{} exited with {}", program, output.status);
+                None
+            }
+            Err(error) => {
+                trace!("Cannot run {}: {}", program, error);
+                None
+            }
+        }
+    }
+}
+
+#[cfg(windows)]
+mod imp {
+    use super::{AdapterRecord, InterfaceInfo};
+    use log::trace;
+    use std::ptr;
+    use windows_sys::Win32::{
+        NetworkManagement::{
+            IpHelper::{
+                GetAdaptersAddresses, GAA_FLAG_INCLUDE_GATEWAYS, GAA_FLAG_SKIP_ANYCAST,
+                GAA_FLAG_SKIP_DNS_SERVER, GAA_FLAG_SKIP_MULTICAST, IP_ADAPTER_ADDRESSES_LH,
+            },
+            Ndis::IfOperStatusUp,
+        },
+        Networking::WinSock::{AF_INET, AF_INET6, AF_UNSPEC},
+    };
+
+    const NO_ERROR: u32 = 0;
+    const ERROR_BUFFER_OVERFLOW: u32 = 111;
+
+    pub fn interfaces() -> Vec<InterfaceInfo> {
+        let flags = GAA_FLAG_INCLUDE_GATEWAYS
+            | GAA_FLAG_SKIP_ANYCAST
+            | GAA_FLAG_SKIP_MULTICAST
+            | GAA_FLAG_SKIP_DNS_SERVER;
+        let mut size: u32 = 16 * 1024;
+        // `u64` elements keep the buffer aligned for the adapter structures
+        let mut buffer: Vec<u64> = Vec::new();
+        for _ in 0..3 {
+            buffer.resize((size as usize + 7) / 8, 0);
+            let result = unsafe {
+                GetAdaptersAddresses(
+                    u32::from(AF_UNSPEC),
+                    flags,
+                    ptr::null(),
+                    buffer.as_mut_ptr().cast(),
+                    &mut size,
+                )
+            };
+            match result {
+                NO_ERROR => return collect(buffer.as_ptr().cast()),
+                ERROR_BUFFER_OVERFLOW => continue,
+                code => {
+                    trace!("GetAdaptersAddresses failed with {}", code);
+                    return Vec::new();
+                }
+            }
+        }
+        Vec::new()
+    }
+
+    fn collect(mut adapter: *const IP_ADAPTER_ADDRESSES_LH) -> Vec<InterfaceInfo> {
+        let mut interfaces = Vec::new();
+        while let Some(current) = unsafe { adapter.as_ref() } {
+            let mac_len =
+                (current.PhysicalAddressLength as usize).min(current.PhysicalAddress.len());
+            let mut record = AdapterRecord {
+                name: wide_to_string(current.FriendlyName),
+                mac: current.PhysicalAddress[..mac_len].to_vec(),
+                if_type: current.IfType,
+                up: current.OperStatus == IfOperStatusUp,
+                transmit_link_speed: current.TransmitLinkSpeed,
+                has_gateway: !current.FirstGatewayAddress.is_null(),
+                ipv4: false,
+                ipv6: false,
+            };
+            let mut unicast = current.FirstUnicastAddress;
+            while let Some(address) = unsafe { unicast.as_ref() } {
+                if let Some(sockaddr) = unsafe { address.Address.lpSockaddr.as_ref() } {
+                    match sockaddr.sa_family {
+                        AF_INET => record.ipv4 = true,
+                        AF_INET6 => record.ipv6 = true,
+                        _ => {}
+                    }
+                }
+                unicast = address.Next;
+            }
+            interfaces.extend(super::from_adapter(record));
+            adapter = current.Next;
+        }
+        interfaces
+    }
+
+    fn wide_to_string(wide: *const u16) -> String {
+        if wide.is_null() {
+            return String::new();
+        }
+        let mut len = 0;
+        while unsafe { *wide.add(len) } != 0 {
+            len += 1;
+        }
+        String::from_utf16_lossy(unsafe { std::slice::from_raw_parts(wide, len) })
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+mod imp {
+    use super::InterfaceInfo;
+
+    pub fn interfaces() -> Vec<InterfaceInfo> {
+        Vec::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::fs;
+
+    const PROC_NET_ROUTE: &str = "\
+Iface\tDestination\tGateway \tFlags\tRefCnt\tUse\tMetric\tMask\t\tMTU\tWindow\tIRTT
+wlan0\t00000000\t0101A8C0\t0003\t0\t0\t600\t00000000\t0\t0\t0
+wlan0\t0001A8C0\t00000000\t0001\t0\t0\t600\t00FFFFFF\t0\t0\t0
+docker0\t000011AC\t00000000\t0001\t0\t0\t0\t0000FFFF\t0\t0\t0
+";
+
+    const PROC_NET_IF_INET6: &str = "\
+00000000000000000000000000000001 01 80 10 80       lo
+fe800000000000000a1b2c3d4e5f6071 03 40 20 80    wlan0
+fe800000000000000b1c2d3e4f506172 02 40 20 80     eth0
+";
+
+    const NETWORKSETUP: &str = "
+Hardware Port: Ethernet
+Device: en1
+Ethernet Address: 3c:22:fb:00:00:01
+
+Hardware Port: Wi-Fi
+Device: en0
+Ethernet Address: 3c:22:fb:00:00:02
+
+VLAN Configurations
+===================
+";
+
+    const IFCONFIG: &str = "\
+lo0: flags=8049<UP,LOOPBACK,RUNNING,MULTICAST> mtu 16384
+\tinet 127.0.0.1 netmask 0xff000000
+\tinet6 ::1 prefixlen 128
+en0: flags=8863<UP,BROADCAST,SMART,RUNNING,SIMPLEX,MULTICAST> mtu 1500
+\tether 3c:22:fb:00:00:02
+\tinet6 fe80::1c3a:9f2b:6d1e:8a4c%en0 prefixlen 64 secured scopeid 0xb
+\tinet 192.168.1.23 netmask 0xffffff00 broadcast 192.168.1.255
+\tmedia: autoselect
+\tstatus: active
+en1: flags=8863<UP,BROADCAST,SMART,RUNNING,SIMPLEX,MULTICAST> mtu 1500
+\tether 3c:22:fb:00:00:01
+\tmedia: autoselect (10Gbase-T <full-duplex>)
+\tstatus: inactive
+";
+
+    const ROUTE_GET_DEFAULT: &str = "   route to: default
+destination: default
+       mask: default
+    gateway: 192.168.1.1
+  interface: en0
+      flags: <UP,GATEWAY,DONE,STATIC,PRCLONING,GLOBAL>
+";
+
+    fn expected_mac(mac: &str) -> Option<String> {
+        if cfg!(feature = "identifiers") {
+            Some(mac.to_owned())
+        } else {
+            Some(redact_mac(mac))
+        }
+    }
+
+    #[test]
+    fn mac_is_redacted_with_stable_hash() {
+        assert_eq!(
+            redact_mac("3c:22:fb:00:00:02"),
+            redact_mac("3C:22:FB:00:00:02")
+        );
+        assert_ne!(
+            redact_mac("3c:22:fb:00:00:02"),
+            redact_mac("3c:22:fb:00:00:01")
+        );
+        assert_eq!(redact_mac("").len(), "hash:".len() + 16);
+        assert_eq!(present_mac("00:00:00:00:00:00\n"), None);
+        assert_eq!(
+            present_mac("3C:22:FB:00:00:02\n"),
+            expected_mac("3c:22:fb:00:00:02")
+        );
+        assert_eq!(
+            format_mac(&[0x3c, 0x22, 0xfb, 0, 0, 2]),
+            Some("3c:22:fb:00:00:02".to_owned())
+        );
+        assert_eq!(format_mac(&[]), None);
+    }
+
+    #[test]
+    fn linux_proc_net_parsing() {
+        assert_eq!(
+            parse_default_route(PROC_NET_ROUTE),
+            Some("wlan0".to_owned())
+        );
+        assert_eq!(parse_default_route("Iface\tDestination\n"), None);
+        assert_eq!(
+            parse_ipv4_interfaces(PROC_NET_ROUTE),
+            vec!["docker0", "wlan0"]
+        );
+        assert_eq!(
+            parse_ipv6_interfaces(PROC_NET_IF_INET6),
+            vec!["eth0", "lo", "wlan0"]
+        );
+        assert_eq!(parse_sysfs_speed("1000\n"), Some(1000));
+        assert_eq!(parse_sysfs_speed("-1\n"), None);
+    }
+
+    #[test]
+    fn linux_sysfs_fixture() {
+        let root = tempfile::tempdir().unwrap();
+        let write = |interface: &str, file: &str, content: &str| {
+            let dir = root.path().join(interface);
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(dir.join(file), content).unwrap();
+        };
+        write("lo", "type", "772\n");
+        write("lo", "address", "00:00:00:00:00:00\n");
+        write("eth0", "type", "1\n");
+        write("eth0", "address", "52:54:00:12:34:56\n");
+        write("eth0", "operstate", "up\n");
+        write("eth0", "speed", "1000\n");
+        write("wlan0", "type", "1\n");
+        write("wlan0", "address", "a0:b1:c2:d3:e4:f5\n");
+        write("wlan0", "operstate", "up\n");
+        write("wlan0", "speed", "-1\n");
+        fs::create_dir_all(root.path().join("wlan0").join("wireless")).unwrap();
+        write("tun0", "type", "65534\n");
+        write("tun0", "operstate", "unknown\n");
+
+        let mut interfaces = read_sysfs(root.path(), PROC_NET_ROUTE, PROC_NET_IF_INET6);
+        interfaces.sort_by(|a, b| a.name.cmp(&b.name));
+        assert_eq!(
+            interfaces,
+            vec![
+                InterfaceInfo {
+                    name: "eth0".to_owned(),
+                    mac_address: expected_mac("52:54:00:12:34:56"),
+                    link_speed_mbps: Some(1000),
+                    wireless: false,
+                    up: true,
+                    ipv4: false,
+                    ipv6: true,
+                    primary: false,
+                },
+                InterfaceInfo {
+                    name: "wlan0".to_owned(),
+                    mac_address: expected_mac("a0:b1:c2:d3:e4:f5"),
+                    link_speed_mbps: None,
+                    wireless: true,
+                    up: true,
+                    ipv4: true,
+                    ipv6: true,
+                    primary: true,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn macos_output_parsing() {
+        assert_eq!(
+            parse_hardware_ports(NETWORKSETUP),
+            vec![
+                ("Ethernet".to_owned(), "en1".to_owned()),
+                ("Wi-Fi".to_owned(), "en0".to_owned()),
+            ]
+        );
+        assert_eq!(
+            parse_route_get_interface(ROUTE_GET_DEFAULT),
+            Some("en0".to_owned())
+        );
+        assert_eq!(parse_ifconfig(IFCONFIG).len(), 3);
+
+        assert_eq!(
+            from_macos_output(NETWORKSETUP, IFCONFIG, ROUTE_GET_DEFAULT),
+            vec![
+                InterfaceInfo {
+                    name: "en1".to_owned(),
+                    mac_address: expected_mac("3c:22:fb:00:00:01"),
+                    link_speed_mbps: Some(10_000),
+                    wireless: false,
+                    up: false,
+                    ipv4: false,
+                    ipv6: false,
+                    primary: false,
+                },
+                InterfaceInfo {
+                    name: "en0".to_owned(),
+                    mac_address: expected_mac("3c:22:fb:00:00:02"),
+                    link_speed_mbps: None,
+                    wireless: true,
+                    up: true,
+                    ipv4: true,
+                    ipv6: true,
+                    primary: true,
+                },
+            ]
+        );
+        assert_eq!(
+            parse_media_speed("\tmedia: autoselect (1000baseT <full-duplex>)"),
+            Some(1000)
+        );
+    }
+
+    #[test]
+    fn windows_adapter_conversion() {
+        let ethernet = AdapterRecord {
+            name: "Ethernet".to_owned(),
+            mac: vec![0x00, 0x15, 0x5d, 0x01, 0x02, 0x03],
+            if_type: 6,
+            up: true,
+            transmit_link_speed: 2_500_000_000,
+            has_gateway: true,
+            ipv4: true,
+            ipv6: true,
+        };
+        assert_eq!(
+            from_adapter(ethernet.clone()),
+            Some(InterfaceInfo {
+                name: "Ethernet".to_owned(),
+                mac_address: expected_mac("00:15:5d:01:02:03"),
+                link_speed_mbps: Some(2500),
+                wireless: false,
+                up: true,
+                ipv4: true,
+                ipv6: true,
+                primary: true,
+            })
+        );
+
+        let wifi = from_adapter(AdapterRecord {
+            name: "Wi-Fi".to_owned(),
+            if_type: 71,
+            transmit_link_speed: u64::MAX,
+            ..ethernet.clone()
+        })
+        .unwrap();
+        assert!(wifi.wireless);
+        assert_eq!(wifi.link_speed_mbps, None);
+
+        let loopback = AdapterRecord {
+            if_type: 24,
+            ..ethernet
+        };
+        assert_eq!(from_adapter(loopback), None);
+    }
+}
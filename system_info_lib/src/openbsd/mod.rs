@@ -2,7 +2,8 @@
 use log::{error, trace};
 
 use crate::{
-    architecture, bit_depth, system_info::Info, system_os::Type, system_uname::uname, SystemVersion,
+    architecture, bit_depth, hostname, kernel_version, system_info::Info, system_os::Type,
+    system_uname::uname, uptime, SystemVersion,
 };
 
 pub fn current_platform() -> Info {
@@ -16,6 +17,9 @@ pub fn current_platform() -> Info {
         system_type: Type::OpenBSD,
         version,
         bit_depth: bit_depth::get(),
+        hostname: hostname::get(),
+        uptime: uptime::get(),
+        kernel_version: kernel_version::get(),
         architecture: architecture::get(),
         ..Default::default()
     };
@@ -0,0 +1,72 @@
+//src/os_release.rs
+//! Parser for `/etc/os-release`, see os-release(5).
+//!
+//! Shared by Linux distribution detection and the server edition heuristic,
+//! which also reads the file on the BSDs.
+
+/// Returns the value of `key` in os-release `content`.
+///
+/// Follows os-release(5): the value may be wrapped in double or single
+/// quotes, and backslash escapes are resolved outside single quotes. Empty
+/// values and comments give `None`.
+pub(crate) fn os_release_value(content: &str, key: &str) -> Option<String> {
+    let raw = content.lines().find_map(|line| {
+        let (name, value) = line.trim().split_once('=')?;
+        if name == key {
+            Some(value)
+        } else {
+            None
+        }
+    })?;
+    let value = unquote(raw.trim());
+    if value.is_empty() {
+        None
+    } else {
+        Some(value)
+    }
+}
+
+/// Removes shell-style quoting from an os-release value.
+fn unquote(raw: &str) -> String {
+    let mut value = String::with_capacity(raw.len());
+    let mut quote = None;
+    let mut chars = raw.chars();
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (None, '"') | (None, '\'') => quote = Some(c),
+            (Some(open), _) if c == open => quote = None,
+            (Some('\''), _) => value.push(c),
+            (_, '\\') => match chars.next() {
+                // Inside double quotes only these characters are escaped
+                Some(next) if quote.is_none() || "\"\\$`".contains(next) => value.push(next),
+                Some(next) => {
+                    value.push(c);
+                    value.push(next);
+                }
+                None => value.push(c),
+            },
+            _ => value.push(c),
+        }
+    }
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn os_release_value_quoting() {
+        let content = "# ID_LIKE=comment\nID_LIKE=\"rhel fedora\"\nA='it''s'\nB=\"a\\$b\\x\"\nC=one\\ two\nD=\"\"";
+        assert_eq!(
+            os_release_value(content, "ID_LIKE").as_deref(),
+            Some("rhel fedora")
+        );
+        assert_eq!(os_release_value(content, "A").as_deref(), Some("its"));
+        assert_eq!(os_release_value(content, "B").as_deref(), Some("a$b\\x"));
+        assert_eq!(os_release_value(content, "C").as_deref(), Some("one two"));
+        assert_eq!(os_release_value(content, "D"), None);
+        assert_eq!(os_release_value(content, "MISSING"), None);
+    }
+}
@@ -0,0 +1,379 @@
+//src/redaction.rs
+
+use sha2::{Digest, Sha256};
+
+/// Serialized fields that identify the machine.
+///
+/// A [`Redaction`] policy applies to these fields only. MAC addresses are
+/// also hashed when collected unless the `identifiers` feature is enabled;
+/// a policy applies on top of that.
+pub const IDENTIFYING_FIELDS: &[&str] = &["hostname", "mac_address"];
+
+/// Serialized fields of `Info` and the types it includes, reviewed as safe
+/// to export.
+#[cfg_attr(not(all(test, feature = "serde")), allow(dead_code))]
+pub(crate) const NON_IDENTIFYING_FIELDS: &[&str] = &[
+    // Info
+    "system_type",
+    "version",
+    "edition",
+    "codename",
+    "bit_depth",
+    "architecture",
+    "kernel_version",
+    "patch_level",
+    "uptime",
+    "wsl_version",
+    "container_runtime",
+    "id_like",
+    "build_id",
+    "variant",
+    "display_version",
+    "update_build_revision",
+    "workstation",
+    "storage",
+    "network_interfaces",
+    "memory",
+    "ui_theme",
+    // InterfaceInfo
+    "name",
+    "link_speed_mbps",
+    "wireless",
+    "up",
+    "ipv4",
+    "ipv6",
+    "primary",
+    // MountInfo
+    "mount_point",
+    "filesystem",
+    "total_bytes",
+    "available_bytes",
+    // MemoryInfo and MemoryModule
+    "modules",
+    "locator",
+    "memory_type",
+    "size_bytes",
+    "speed_mts",
+    "configured_speed_mts",
+    "manufacturer",
+];
+
+/// How identifying fields are exported.
+///
+/// # Examples
+///
+/// ```
+/// use system_info_lib::Redaction;
+///
+/// let policy = Redaction::hash_identifiers("fleet-salt");
+/// let hashed = policy.apply("aa:bb:cc:dd:ee:ff").unwrap();
+/// assert!(hashed.starts_with("sha256:"));
+/// assert_eq!(Redaction::StripIdentifiers.apply("aa:bb:cc:dd:ee:ff"), None);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub enum Redaction {
+    /// Export identifying fields unchanged.
+    #[default]
+    None,
+    /// Replace each identifying value with a salted hash.
+    ///
+    /// The value becomes `sha256:` followed by the first 16 hex digits of
+    /// the SHA-256 of the salt followed by the value, which is the same on
+    /// every run and machine for the same salt.
+    HashIdentifiers {
+        /// Prepended to each value before hashing.
+        salt: String,
+    },
+    /// Remove identifying values.
+    StripIdentifiers,
+}
+
+impl Redaction {
+    /// Creates a [`Redaction::HashIdentifiers`] policy with `salt`.
+    pub fn hash_identifiers<S: Into<String>>(salt: S) -> Self {
+        Redaction::HashIdentifiers { salt: salt.into() }
+    }
+
+    /// Returns `value` as exported under this policy.
+    pub fn apply(&self, value: &str) -> Option<String> {
+        match *self {
+            Redaction::None => Some(value.to_string()),
+            Redaction::HashIdentifiers { ref salt } => {
+                let digest = Sha256::new()
+                    .chain_update(salt)
+                    .chain_update(value)
+                    .finalize();
+                let hex: Vec<String> = digest[..8]
+                    .iter()
+                    .map(|byte| format!("{:02x}", byte))
+                    .collect();
+                Some(format!("sha256:{}", hex.concat()))
+            }
+            Redaction::StripIdentifiers => None,
+        }
+    }
+
+    /// Applies the policy to an optional identifying field in place.
+    pub fn apply_to(&self, field: &mut Option<String>) {
+        if *self != Redaction::None {
+            *field = field.as_deref().and_then(|value| self.apply(value));
+        }
+    }
+}
+
+/// Types whose identifying fields can be redacted.
+pub trait Redact {
+    /// Applies `policy` to every field in [`IDENTIFYING_FIELDS`].
+    fn redact(&mut self, policy: &Redaction);
+}
+
+impl<T: Redact> Redact for Vec<T> {
+    fn redact(&mut self, policy: &Redaction) {
+        for value in self {
+            value.redact(policy);
+        }
+    }
+}
+
+#[cfg(feature = "network")]
+impl Redact for crate::InterfaceInfo {
+    fn redact(&mut self, policy: &Redaction) {
+        policy.apply_to(&mut self.mac_address);
+    }
+}
+
+/// Serializes a value with its identifying fields redacted.
+///
+/// Works for [`Info`](crate::Info), including the network interfaces it
+/// serializes with the `network` feature, and for any [`Redact`] type.
+///
+/// # Examples
+///
+/// ```
+/// use system_info_lib::{Info, Redacted, Redaction};
+///
+/// let info = Info::builder().build();
+/// let policy = Redaction::hash_identifiers("fleet-salt");
+/// let json = serde_json::to_string(&Redacted::new(&info, &policy)).unwrap();
+/// assert!(!json.contains("hash:"));
+/// ```
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, Copy)]
+pub struct Redacted<'a, T> {
+    pub(crate) value: &'a T,
+    pub(crate) policy: &'a Redaction,
+}
+
+#[cfg(feature = "serde")]
+impl<'a, T> Redacted<'a, T> {
+    /// Wraps `value` to be serialized under `policy`.
+    pub fn new(value: &'a T, policy: &'a Redaction) -> Self {
+        Redacted { value, policy }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'a, T: Redact + Clone + serde::Serialize> serde::Serialize for Redacted<'a, T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut value = self.value.clone();
+        value.redact(self.policy);
+        value.serialize(serializer)
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+    use crate::{BitDepth, Info, SystemVersion, Type};
+    use pretty_assertions::assert_eq;
+    use serde_json::Value;
+
+    fn full_info() -> Info {
+        let builder = Info::builder()
+            .system_type(Type::Ubuntu)
+            .version(SystemVersion::semantic(24, 4, 1))
+            .edition("Ubuntu 24.04.1 LTS")
+            .codename("noble")
+            .bit_depth(BitDepth::X64)
+            .architecture("x86_64")
+            .kernel_version("6.8.0-45-generic")
+            .patch_level(1)
+            .hostname("build-42.example.com");
+        #[cfg(feature = "storage")]
+        let builder = builder.storage(vec![crate::MountInfo {
+            mount_point: "/".to_string(),
+            filesystem: "ext4".to_string(),
+            total_bytes: 512_000_000_000,
+            available_bytes: 128_000_000_000,
+        }]);
+        #[cfg(feature = "network")]
+        let builder = builder.network_interfaces(vec![crate::InterfaceInfo {
+            name: "eth0".to_string(),
+            mac_address: Some("aa:bb:cc:dd:ee:ff".to_string()),
+            link_speed_mbps: Some(1000),
+            wireless: false,
+            up: true,
+            ipv4: true,
+            ipv6: true,
+            primary: true,
+        }]);
+        #[cfg(feature = "memory")]
+        let builder = builder.memory_modules(crate::MemoryInfo {
+            modules: vec![crate::MemoryModule {
+                locator: Some("DIMM_A1".to_string()),
+                memory_type: Some("DDR5".to_string()),
+                size_bytes: Some(17_179_869_184),
+                speed_mts: Some(5600),
+                configured_speed_mts: Some(5200),
+                manufacturer: Some("Samsung".to_string()),
+            }],
+        });
+        builder.build()
+    }
+
+    fn serialize(policy: &Redaction) -> Value {
+        serde_json::to_value(Redacted::new(&full_info(), policy)).unwrap()
+    }
+
+    #[test]
+    fn every_field_is_classified() {
+        let info = serde_json::to_value(full_info()).unwrap();
+        for (feature, section) in [
+            (cfg!(feature = "storage"), "storage"),
+            (cfg!(feature = "network"), "network_interfaces"),
+            (cfg!(feature = "memory"), "memory"),
+        ] {
+            assert_eq!(info.get(section).is_some_and(|v| !v.is_null()), feature);
+        }
+        let mut fields: Vec<String> = info.as_object().unwrap().keys().cloned().collect();
+        for nested in ["storage", "network_interfaces", "memory"] {
+            match info.get(nested) {
+                Some(Value::Array(values)) => {
+                    for value in values {
+                        fields.extend(value.as_object().unwrap().keys().cloned());
+                    }
+                }
+                Some(Value::Object(value)) => {
+                    fields.extend(value.keys().cloned());
+                    if let Some(Value::Array(modules)) = value.get("modules") {
+                        for module in modules {
+                            fields.extend(module.as_object().unwrap().keys().cloned());
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        for field in fields {
+            assert!(
+                IDENTIFYING_FIELDS.contains(&field.as_str())
+                    ^ NON_IDENTIFYING_FIELDS.contains(&field.as_str()),
+                "classify `{}` in IDENTIFYING_FIELDS or NON_IDENTIFYING_FIELDS",
+                field
+            );
+        }
+    }
+
+    /// Only the hostname and the network interfaces serialized with the
+    /// `network` feature can differ.
+    #[test]
+    fn info_policies() {
+        let plain = serde_json::to_value(full_info()).unwrap();
+        let without_interfaces = |mut value: Value| {
+            let fields = value.as_object_mut().unwrap();
+            fields.remove("hostname");
+            fields.remove("network_interfaces");
+            value
+        };
+        assert_eq!(serialize(&Redaction::None), plain);
+        for policy in [
+            Redaction::hash_identifiers("fleet-salt"),
+            Redaction::StripIdentifiers,
+        ] {
+            let redacted = serialize(&policy);
+            assert_eq!(
+                without_interfaces(redacted.clone()),
+                without_interfaces(plain.clone())
+            );
+            match policy {
+                Redaction::StripIdentifiers => assert_eq!(redacted["hostname"], Value::Null),
+                _ => assert_eq!(
+                    redacted["hostname"].as_str(),
+                    policy.apply("build-42.example.com").as_deref()
+                ),
+            }
+            if let Some(Value::Array(interfaces)) = redacted.get("network_interfaces") {
+                for interface in interfaces {
+                    let mac = &interface["mac_address"];
+                    match policy {
+                        Redaction::StripIdentifiers => assert_eq!(mac, &Value::Null),
+                        _ => assert_eq!(mac.as_str(), policy.apply("aa:bb:cc:dd:ee:ff").as_deref()),
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn hashing_is_deterministic() {
+        let policy = Redaction::hash_identifiers("fleet-salt");
+        // SHA-256 of "fleet-salt" followed by the value, truncated to 64 bits
+        assert_eq!(
+            policy.apply("aa:bb:cc:dd:ee:ff").as_deref(),
+            Some("sha256:96bb3d42187057fb")
+        );
+        assert_eq!(
+            policy.apply("hash:0123456789abcdef").as_deref(),
+            Some("sha256:d0337f79320b3974")
+        );
+        assert_ne!(
+            Redaction::hash_identifiers("other-salt").apply("aa:bb:cc:dd:ee:ff"),
+            policy.apply("aa:bb:cc:dd:ee:ff")
+        );
+    }
+
+    #[cfg(feature = "network")]
+    #[test]
+    fn interface_policies() {
+        use serde_json::json;
+
+        let interface = crate::InterfaceInfo {
+            name: "eth0".to_string(),
+            mac_address: Some("aa:bb:cc:dd:ee:ff".to_string()),
+            link_speed_mbps: Some(1000),
+            wireless: false,
+            up: true,
+            ipv4: true,
+            ipv6: true,
+            primary: true,
+        };
+        let serialize = |policy: &Redaction| {
+            serde_json::to_value(Redacted::new(&vec![interface.clone()], policy)).unwrap()
+        };
+        assert_eq!(
+            serialize(&Redaction::None),
+            json!([{
+                "name": "eth0",
+                "mac_address": "aa:bb:cc:dd:ee:ff",
+                "link_speed_mbps": 1000,
+                "wireless": false,
+                "up": true,
+                "ipv4": true,
+                "ipv6": true,
+                "primary": true,
+            }])
+        );
+        assert_eq!(
+            serialize(&Redaction::hash_identifiers("fleet-salt"))[0]["mac_address"],
+            json!("sha256:96bb3d42187057fb")
+        );
+        assert_eq!(
+            serialize(&Redaction::StripIdentifiers)[0]["mac_address"],
+            Value::Null
+        );
+        assert_eq!(
+            serialize(&Redaction::StripIdentifiers)[0]["name"],
+            json!("eth0")
+        );
+    }
+}
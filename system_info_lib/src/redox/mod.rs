@@ -3,7 +3,7 @@ use std::{fs::File, io::Read};
 
 use log::{error, trace};
 
-use crate::{bit_depth, system_info::Info, system_os::Type, BitDepth, SystemVersion};
+use crate::{bit_depth, system_info::Info, system_os::Type, uptime, BitDepth, SystemVersion};
 
 const UNAME_FILE: &str = "sys:uname";
 
@@ -17,6 +17,7 @@ pub fn current_platform() -> Info {
         system_type: Type::Redox,
         version,
         bit_depth: BitDepth::Unknown,
+        uptime: uptime::get(),
         ..Default::default()
     };
     trace!("Returning {:?}", info);
@@ -0,0 +1,151 @@
+//src/server_edition.rs
+use crate::system_os::Type;
+
+/// Desktop session directories installed by display managers.
+#[cfg(all(unix, not(target_os = "macos")))]
+const SESSION_DIRS: [&str; 2] = ["/usr/share/xsessions", "/usr/share/wayland-sessions"];
+
+/// Environment variables set inside a graphical session.
+#[cfg(all(unix, not(target_os = "macos")))]
+const DESKTOP_VARS: [&str; 4] = [
+    "XDG_CURRENT_DESKTOP",
+    "DESKTOP_SESSION",
+    "WAYLAND_DISPLAY",
+    "DISPLAY",
+];
+
+/// Returns whether the running system is a server edition.
+///
+/// The edition string is checked first (`Windows Server 2022 Datacenter`),
+/// then `VARIANT_ID` in `/etc/os-release`, and finally whether a desktop
+/// environment is running or installed.
+pub fn get(system_type: Type, edition: Option<&str>) -> Option<bool> {
+    classify(
+        system_type,
+        edition,
+        os_release_variant().as_deref(),
+        has_desktop(),
+    )
+}
+
+/// Combines the heuristics, in order of reliability.
+pub(crate) fn classify(
+    system_type: Type,
+    edition: Option<&str>,
+    variant_id: Option<&str>,
+    has_desktop: Option<bool>,
+) -> Option<bool> {
+    if let Some(edition) = edition {
+        if edition.to_ascii_lowercase().contains("server") {
+            return Some(true);
+        }
+        if system_type == Type::Windows {
+            return Some(false);
+        }
+    }
+    match system_type {
+        Type::Windows | Type::Unknown => return None,
+        Type::Macos | Type::Android | Type::ChromeOS => return Some(false),
+        _ => {}
+    }
+    if let Some(variant_id) = variant_id {
+        match variant_id {
+            "server" | "server-core" | "coreos" => return Some(true),
+            "workstation" | "desktop" | "kde" | "silverblue" | "kinoite" => return Some(false),
+            _ => {}
+        }
+    }
+    has_desktop.map(|desktop| !desktop)
+}
+
+/// Extracts `VARIANT_ID` from `/etc/os-release` content, lowercased.
+#[cfg(any(all(unix, not(target_os = "macos")), test))]
+pub(crate) fn parse_variant_id(os_release: &str) -> Option<String> {
+    crate::os_release::os_release_value(os_release, "VARIANT_ID")
+        .map(|value| value.to_ascii_lowercase())
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+fn os_release_variant() -> Option<String> {
+    let content = std::fs::read_to_string("/etc/os-release").ok()?;
+    parse_variant_id(&content)
+}
+
+#[cfg(not(all(unix, not(target_os = "macos"))))]
+fn os_release_variant() -> Option<String> {
+    None
+}
+
+/// Returns `true` if a graphical session is running or a desktop session is installed.
+#[cfg(all(unix, not(target_os = "macos")))]
+fn has_desktop() -> Option<bool> {
+    let running = DESKTOP_VARS
+        .iter()
+        .any(|var| std::env::var_os(var).is_some_and(|value| !value.is_empty()));
+    let installed = SESSION_DIRS
+        .iter()
+        .any(|dir| std::fs::read_dir(dir).is_ok_and(|mut entries| entries.next().is_some()));
+    Some(running || installed)
+}
+
+#[cfg(not(all(unix, not(target_os = "macos"))))]
+fn has_desktop() -> Option<bool> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn windows_server_edition() {
+        assert_eq!(
+            classify(
+                Type::Windows,
+                Some("Windows Server 2022 Datacenter"),
+                None,
+                None
+            ),
+            Some(true)
+        );
+        assert_eq!(
+            classify(Type::Windows, Some("Windows 11 Professional"), None, None),
+            Some(false)
+        );
+        assert_eq!(classify(Type::Windows, None, None, None), None);
+    }
+
+    #[test]
+    fn linux_server_variant() {
+        let os_release =
+            "NAME=\"Fedora Linux\"\nVERSION_ID=40\nVARIANT=\"Server Edition\"\nVARIANT_ID=server\n";
+        let variant = parse_variant_id(os_release);
+        assert_eq!(variant.as_deref(), Some("server"));
+        assert_eq!(
+            classify(Type::Fedora, None, variant.as_deref(), Some(true)),
+            Some(true)
+        );
+        assert_eq!(
+            classify(Type::Fedora, None, Some("workstation"), Some(false)),
+            Some(false)
+        );
+        assert_eq!(parse_variant_id("NAME=Debian\nVARIANT_ID=\"\"\n"), None);
+        assert_eq!(
+            parse_variant_id("VARIANT_ID='CoreOS'\n").as_deref(),
+            Some("coreos")
+        );
+    }
+
+    #[test]
+    fn falls_back_to_desktop_presence() {
+        assert_eq!(classify(Type::Debian, None, None, Some(false)), Some(true));
+        assert_eq!(
+            classify(Type::Ubuntu, None, Some("cloud"), Some(true)),
+            Some(false)
+        );
+        assert_eq!(classify(Type::Debian, None, None, None), None);
+        assert_eq!(classify(Type::Unknown, None, None, Some(false)), None);
+        assert_eq!(classify(Type::Macos, None, None, None), Some(false));
+    }
+}
@@ -0,0 +1,335 @@
+//src/storage.rs
+// spell-checker:ignore statvfs, statfs, fstype, fstypename, bavail, frsize, tmpfs, devtmpfs
+// spell-checker:ignore cgroup, debugfs, tracefs, pstore, securityfs, mqueue, hugetlbfs
+
+#![allow(unsafe_code)]
+
+use log::trace;
+
+/// Space usage of a mounted filesystem.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MountInfo {
+    /// Where the filesystem is mounted (`/`, `/home`, `C:\`, ...).
+    pub mount_point: String,
+    /// Filesystem type (`ext4`, `apfs`, `NTFS`, ...).
+    pub filesystem: String,
+    /// Total size in bytes.
+    pub total_bytes: u64,
+    /// Bytes available to unprivileged users.
+    pub available_bytes: u64,
+}
+
+impl MountInfo {
+    /// Returns the number of bytes not available to unprivileged users.
+    pub fn used_bytes(&self) -> u64 {
+        self.total_bytes.saturating_sub(self.available_bytes)
+    }
+
+    /// Returns the share of the filesystem in use, in percent.
+    ///
+    /// Returns `0.0` for filesystems reporting a size of zero.
+    pub fn used_percent(&self) -> f64 {
+        if self.total_bytes == 0 {
+            0.0
+        } else {
+            self.used_bytes() as f64 * 100.0 / self.total_bytes as f64
+        }
+    }
+}
+
+/// Mount points reported on Unix-like systems, if they are separate filesystems.
+#[cfg(unix)]
+const MOUNT_POINTS: [&str; 3] = ["/", "/home", "/var"];
+
+/// Filesystem types that do not represent disk storage.
+const PSEUDO_FILESYSTEMS: [&str; 22] = [
+    "autofs",
+    "binfmt_misc",
+    "bpf",
+    "cgroup",
+    "cgroup2",
+    "configfs",
+    "debugfs",
+    "devfs",
+    "devpts",
+    "devtmpfs",
+    "fdescfs",
+    "fusectl",
+    "hugetlbfs",
+    "mqueue",
+    "nullfs",
+    "proc",
+    "procfs",
+    "pstore",
+    "securityfs",
+    "sysfs",
+    "tmpfs",
+    "tracefs",
+];
+
+/// Returns `true` if `filesystem` is a virtual filesystem that holds no disk storage.
+pub(crate) fn is_pseudo_filesystem(filesystem: &str) -> bool {
+    let filesystem = filesystem.to_ascii_lowercase();
+    PSEUDO_FILESYSTEMS.contains(&filesystem.as_str())
+}
+
+/// Returns the mounted filesystems of the running system.
+///
+/// On Unix-like systems `/`, `/home` and `/var` are checked; the latter two
+/// are only reported if they are separate mounts. On Windows every fixed
+/// drive is reported. Pseudo filesystems are skipped.
+///
+/// # Returns
+///
+/// * `Option<Vec<MountInfo>>` - The mounts, or `None` if none could be queried.
+pub fn get() -> Option<Vec<MountInfo>> {
+    let mounts: Vec<MountInfo> = imp::mounts()
+        .into_iter()
+        .filter(|mount| !is_pseudo_filesystem(&mount.filesystem))
+        .collect();
+    if mounts.is_empty() {
+        trace!("No storage information available");
+        None
+    } else {
+        Some(mounts)
+    }
+}
+
+/// Returns the filesystem type of `mount_point` from `/proc/mounts` content.
+///
+/// The last matching entry wins, as later mounts hide earlier ones.
+#[cfg_attr(not(any(target_os = "linux", target_os = "android")), allow(dead_code))]
+pub(crate) fn parse_mount_type(mounts: &str, mount_point: &str) -> Option<String> {
+    mounts.lines().rev().find_map(|line| {
+        let mut fields = line.split_whitespace();
+        let _device = fields.next()?;
+        let target = fields.next()?;
+        let filesystem = fields.next()?;
+        if target == mount_point {
+            Some(filesystem.to_owned())
+        } else {
+            None
+        }
+    })
+}
+
+#[cfg(unix)]
+mod imp {
+    use super::{MountInfo, MOUNT_POINTS};
+    use log::trace;
+    use std::ffi::CString;
+    use std::mem::MaybeUninit;
+    use std::os::unix::fs::MetadataExt;
+    use std::path::Path;
+
+    pub fn mounts() -> Vec<MountInfo> {
+        MOUNT_POINTS
+            .iter()
+            .filter(|mount_point| is_mount_point(Path::new(mount_point)))
+            .filter_map(|mount_point| query(mount_point))
+            .collect()
+    }
+
+    /// A directory is a mount point if it lives on another device than its parent.
+    fn is_mount_point(path: &Path) -> bool {
+        let parent = match path.parent() {
+            Some(parent) => parent,
+            None => return true,
+        };
+        match (std::fs::metadata(path), std::fs::metadata(parent)) {
+            (Ok(dir), Ok(parent)) => dir.dev() != parent.dev(),
+            _ => false,
+        }
+    }
+
+    // The `statvfs` field types differ between platforms
+    #[allow(clippy::unnecessary_cast)]
+    fn query(mount_point: &str) -> Option<MountInfo> {
+        let path = CString::new(mount_point).ok()?;
+        let mut stat = MaybeUninit::<libc::statvfs>::uninit();
+        if unsafe { libc::statvfs(path.as_ptr(), stat.as_mut_ptr()) } != 0 {
+            trace!(
+                "statvfs({}) failed: {:?}",
+                mount_point,
+                std::io::Error::last_os_error()
+            );
+            return None;
+        }
+        let stat = unsafe { stat.assume_init() };
+        let fragment_size = stat.f_frsize as u64;
+        Some(MountInfo {
+            mount_point: mount_point.to_owned(),
+            filesystem: filesystem_type(mount_point).unwrap_or_else(|| "unknown".to_owned()),
+            total_bytes: (stat.f_blocks as u64).saturating_mul(fragment_size),
+            available_bytes: (stat.f_bavail as u64).saturating_mul(fragment_size),
+        })
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    fn filesystem_type(mount_point: &str) -> Option<String> {
+        let mounts = std::fs::read_to_string("/proc/mounts").ok()?;
+        super::parse_mount_type(&mounts, mount_point)
+    }
+
+    #[cfg(any(
+        target_os = "macos",
+        target_os = "freebsd",
+        target_os = "openbsd",
+        target_os = "dragonfly"
+    ))]
+    fn filesystem_type(mount_point: &str) -> Option<String> {
+        use std::ffi::CStr;
+
+        let path = CString::new(mount_point).ok()?;
+        let mut stat = MaybeUninit::<libc::statfs>::uninit();
+        if unsafe { libc::statfs(path.as_ptr(), stat.as_mut_ptr()) } != 0 {
+            return None;
+        }
+        let stat = unsafe { stat.assume_init() };
+        let name = unsafe { CStr::from_ptr(stat.f_fstypename.as_ptr()) };
+        Some(name.to_string_lossy().into_owned())
+    }
+
+    #[cfg(not(any(
+        target_os = "linux",
+        target_os = "android",
+        target_os = "macos",
+        target_os = "freebsd",
+        target_os = "openbsd",
+        target_os = "dragonfly"
+    )))]
+    fn filesystem_type(_mount_point: &str) -> Option<String> {
+        None
+    }
+}
+
+#[cfg(windows)]
+mod imp {
+    use super::MountInfo;
+    use log::trace;
+    use std::{ffi::OsStr, os::windows::ffi::OsStrExt, ptr};
+    use windows_sys::Win32::Storage::FileSystem::{
+        GetDiskFreeSpaceExW, GetDriveTypeW, GetLogicalDrives, GetVolumeInformationW,
+    };
+
+    const DRIVE_FIXED: u32 = 3;
+
+    pub fn mounts() -> Vec<MountInfo> {
+        let drives = unsafe { GetLogicalDrives() };
+        (0..26u8)
+            .filter(|index| drives & (1 << index) != 0)
+            .map(|index| format!("{}:\\", (b'A' + index) as char))
+            .filter_map(|root| query(&root))
+            .collect()
+    }
+
+    fn query(root: &str) -> Option<MountInfo> {
+        let wide: Vec<u16> = OsStr::new(root).encode_wide().chain(Some(0)).collect();
+        if unsafe { GetDriveTypeW(wide.as_ptr()) } != DRIVE_FIXED {
+            return None;
+        }
+        let mut available = 0u64;
+        let mut total = 0u64;
+        if unsafe {
+            GetDiskFreeSpaceExW(wide.as_ptr(), &mut available, &mut total, ptr::null_mut())
+        } == 0
+        {
+            trace!(
+                "GetDiskFreeSpaceExW({}) failed: {:?}",
+                root,
+                std::io::Error::last_os_error()
+            );
+            return None;
+        }
+        let mut filesystem = [0u16; 32];
+        let filesystem = if unsafe {
+            GetVolumeInformationW(
+                wide.as_ptr(),
+                ptr::null_mut(),
+                0,
+                ptr::null_mut(),
+                ptr::null_mut(),
+                ptr::null_mut(),
+                filesystem.as_mut_ptr(),
+                filesystem.len() as u32,
+            )
+        } != 0
+        {
+            let len = filesystem.iter().position(|&c| c == 0).unwrap_or(0);
+            String::from_utf16_lossy(&filesystem[..len])
+        } else {
+            "unknown".to_owned()
+        };
+        Some(MountInfo {
+            mount_point: root.to_owned(),
+            filesystem,
+            total_bytes: total,
+            available_bytes: available,
+        })
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+mod imp {
+    use super::MountInfo;
+
+    pub fn mounts() -> Vec<MountInfo> {
+        Vec::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn mount(total_bytes: u64, available_bytes: u64) -> MountInfo {
+        MountInfo {
+            mount_point: "/".to_owned(),
+            filesystem: "ext4".to_owned(),
+            total_bytes,
+            available_bytes,
+        }
+    }
+
+    #[test]
+    fn pseudo_filesystems_are_filtered() {
+        for filesystem in ["proc", "sysfs", "tmpfs", "devtmpfs", "cgroup2", "TRACEFS"] {
+            assert!(is_pseudo_filesystem(filesystem), "{}", filesystem);
+        }
+        for filesystem in ["ext4", "xfs", "btrfs", "apfs", "zfs", "overlay", "NTFS"] {
+            assert!(!is_pseudo_filesystem(filesystem), "{}", filesystem);
+        }
+    }
+
+    #[test]
+    fn used_percent() {
+        assert_eq!(mount(1000, 250).used_bytes(), 750);
+        assert!((mount(1000, 250).used_percent() - 75.0).abs() < f64::EPSILON);
+        assert!((mount(1000, 1000).used_percent()).abs() < f64::EPSILON);
+        assert!((mount(0, 0).used_percent()).abs() < f64::EPSILON);
+        // Never underflows, even for inconsistent sizes
+        assert_eq!(mount(100, 200).used_bytes(), 0);
+    }
+
+    #[test]
+    fn parse_mount_type_uses_last_entry() {
+        let mounts = "\
+sysfs /sys sysfs rw,nosuid 0 0
+/dev/sda1 / ext4 rw,relatime 0 0
+tmpfs /home tmpfs rw 0 0
+/dev/sda2 /home xfs rw 0 0
+";
+        assert_eq!(parse_mount_type(mounts, "/"), Some("ext4".to_owned()));
+        assert_eq!(parse_mount_type(mounts, "/home"), Some("xfs".to_owned()));
+        assert_eq!(parse_mount_type(mounts, "/var"), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn root_is_reported() {
+        let mounts = get().expect("root filesystem should be queryable");
+        assert!(mounts.iter().any(|mount| mount.mount_point == "/"));
+    }
+}
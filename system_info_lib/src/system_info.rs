@@ -1,8 +1,14 @@
 //src/system_info.rs
 use crate::bit_depth::BitDepth;
+use crate::container_runtime::ContainerRuntime;
+use crate::kernel_version::KernelVersion;
 use crate::system_os::Type;
 use crate::SystemVersion;
 use std::fmt::Display;
+use std::time::Duration;
+
+/// Windows 11 kept the 10.0 version number; its first release was build 22000.
+pub(crate) const WINDOWS_11_FIRST_BUILD: u64 = 22000;
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -30,7 +36,56 @@ pub struct Info {
     pub(crate) architecture: Option<String>,
 
     /// The kernel version of the operating system, if known.
-    pub(crate) kernel_version: Option<String>,
+    pub(crate) kernel_version: Option<KernelVersion>,
+
+    /// The patch level of the operating system release, if known.
+    pub(crate) patch_level: Option<u32>,
+
+    /// The hostname of the machine, if known.
+    pub(crate) hostname: Option<String>,
+
+    /// How long the system had been running when it was detected, if known.
+    pub(crate) uptime: Option<Duration>,
+
+    /// The WSL version (1 or 2) when running under Windows Subsystem for Linux.
+    pub(crate) wsl_version: Option<u8>,
+
+    /// The container runtime when running inside a container.
+    pub(crate) container_runtime: Option<ContainerRuntime>,
+
+    /// The distributions this one derives from, from `ID_LIKE` in `/etc/os-release`.
+    pub(crate) id_like: Option<Vec<String>>,
+
+    /// The image build identifier, from `BUILD_ID` in `/etc/os-release`.
+    pub(crate) build_id: Option<String>,
+
+    /// The variant identifier, from `VARIANT_ID` in `/etc/os-release`.
+    pub(crate) variant: Option<String>,
+
+    /// The release's display version on Windows, e.g. `23H2`.
+    pub(crate) display_version: Option<String>,
+
+    /// The update build revision on Windows, e.g. `3007` in `10.0.22631.3007`.
+    pub(crate) update_build_revision: Option<u32>,
+
+    /// Whether Windows reports a workstation rather than a server product type.
+    pub(crate) workstation: Option<bool>,
+
+    /// The mounted filesystems, read when the system was detected.
+    #[cfg(feature = "storage")]
+    pub(crate) storage: Option<Vec<crate::MountInfo>>,
+
+    /// The network interfaces, read when the system was detected.
+    #[cfg(feature = "network")]
+    pub(crate) network_interfaces: Option<Vec<crate::InterfaceInfo>>,
+
+    /// The installed memory modules, read when the system was detected.
+    #[cfg(feature = "memory")]
+    pub(crate) memory: Option<crate::MemoryInfo>,
+
+    /// The desktop theme, read when the system was detected.
+    #[cfg(feature = "desktop")]
+    pub(crate) ui_theme: Option<crate::UiTheme>,
 }
 
 impl Info {
@@ -46,6 +101,18 @@ impl Info {
     ///     - `bit_depth`: `BitDepth::Unknown`
     ///     - `architecture`: `None`
     ///     - `kernel_version`: `None`
+    ///     - `patch_level`: `None`
+    ///     - `hostname`: `None`
+    ///     - `uptime`: `None`
+    ///     - `wsl_version`: `None`
+    ///     - `container_runtime`: `None`
+    ///     - `id_like`: `None`
+    ///     - `build_id`: `None`
+    ///     - `variant`: `None`
+    ///     - `display_version`: `None`
+    ///     - `update_build_revision`: `None`
+    ///     - `workstation`: `None`
+    ///     - `storage`, `network_interfaces`, `memory` and `ui_theme`: `None`
     pub fn unknown() -> Self {
         Self {
             system_type: Type::Unknown,
@@ -55,6 +122,25 @@ impl Info {
             bit_depth: BitDepth::Unknown,
             architecture: None,
             kernel_version: None,
+            patch_level: None,
+            hostname: None,
+            uptime: None,
+            wsl_version: None,
+            container_runtime: None,
+            id_like: None,
+            build_id: None,
+            variant: None,
+            display_version: None,
+            update_build_revision: None,
+            workstation: None,
+            #[cfg(feature = "storage")]
+            storage: None,
+            #[cfg(feature = "network")]
+            network_interfaces: None,
+            #[cfg(feature = "memory")]
+            memory: None,
+            #[cfg(feature = "desktop")]
+            ui_theme: None,
         }
     }
 
@@ -149,7 +235,517 @@ impl Info {
     /// }
     /// ```
     pub fn kernel_version(&self) -> Option<&str> {
-        self.kernel_version.as_ref().map(String::as_ref)
+        self.kernel_version.as_ref().map(KernelVersion::release)
+    }
+
+    /// Returns the kernel version with its parsed version numbers.
+    ///
+    /// Unlike [`kernel_version()`](Self::kernel_version), the result can be
+    /// compared against other kernel versions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use system_info_lib::{Info, KernelVersion};
+    ///
+    /// let info = Info::builder().kernel_version("6.5.0-14-generic").build();
+    /// assert!(info.kernel().unwrap() >= &KernelVersion::parse("6.1"));
+    /// ```
+    pub fn kernel(&self) -> Option<&KernelVersion> {
+        self.kernel_version.as_ref()
+    }
+
+    /// Returns the patch level of the OS release.
+    ///
+    /// On FreeBSD this is the `-pN` suffix of `uname -r` (`4` for
+    /// `13.2-RELEASE-p4`), which also stays part of [`version()`](Info::version).
+    ///
+    /// # Returns
+    ///
+    /// * `Option<u32>` - The patch level, if known.
+    pub fn patch_level(&self) -> Option<u32> {
+        self.patch_level
+    }
+
+    /// Returns the hostname of the machine.
+    ///
+    /// Read with `gethostname(2)` on Unix-like systems and with
+    /// `GetComputerNameExW` (`ComputerNameDnsHostname`) on Windows. Android
+    /// reports the `ro.product.device` property of `/system/build.prop`,
+    /// falling back to `uname -n`.
+    ///
+    /// The hostname identifies the machine, so [`Redacted`](crate::Redacted)
+    /// hashes or strips it like a MAC address.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<String>` - The hostname, if known.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use system_info_lib::get;
+    ///
+    /// if let Some(hostname) = get().hostname() {
+    ///     println!("Running on {}", hostname);
+    /// }
+    /// ```
+    pub fn hostname(&self) -> Option<String> {
+        self.hostname.clone()
+    }
+
+    /// Returns how long the system has been running.
+    ///
+    /// Parsed from `/proc/uptime` on Linux, computed from the `kern.boottime`
+    /// sysctl on macOS and the BSDs and from the `boot_time` kstat on
+    /// illumos, and read with `GetTickCount64` on Windows.
+    ///
+    /// The uptime is measured when the `Info` is detected, so with the
+    /// `cache` feature it stays at the value of the first [`get()`](crate::get)
+    /// until [`refresh()`](crate::refresh) is called. It takes part in
+    /// comparisons, so two detections of the same system differ in it.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<Duration>` - The uptime, if known.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use system_info_lib::get;
+    ///
+    /// if let Some(uptime) = get().uptime() {
+    ///     println!("Up for {} hours", uptime.as_secs() / 3600);
+    /// }
+    /// ```
+    pub fn uptime(&self) -> Option<Duration> {
+        self.uptime
+    }
+
+    /// Returns `true` when running under Windows Subsystem for Linux.
+    ///
+    /// Always `false` on other platforms than Linux. See
+    /// [`wsl_version()`](Self::wsl_version) for how WSL is detected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use system_info_lib::get;
+    ///
+    /// if get().is_wsl() {
+    ///     println!("Files under /mnt/c live on the Windows host");
+    /// }
+    /// ```
+    pub fn is_wsl(&self) -> bool {
+        self.wsl_version.is_some()
+    }
+
+    /// Returns the Windows Subsystem for Linux version, 1 or 2.
+    ///
+    /// WSL is detected from "microsoft" in `/proc/version` or the
+    /// `WSLInterop` entry in `/proc/sys/fs/binfmt_misc`. WSL 2 runs a real
+    /// Microsoft-built kernel whose `/proc/sys/kernel/osrelease` contains
+    /// `microsoft-standard` (`-microsoft-standard-WSL2` since 5.10), while
+    /// WSL 1 reports an emulated release such as `4.4.0-19041-Microsoft`.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<u8>` - The WSL version, `None` outside WSL and on other
+    ///   platforms than Linux.
+    pub fn wsl_version(&self) -> Option<u8> {
+        self.wsl_version
+    }
+
+    /// Returns `true` when running inside a container.
+    ///
+    /// Always `false` on other platforms than Linux. See
+    /// [`container_runtime()`](Self::container_runtime) for how containers
+    /// are detected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use system_info_lib::get;
+    ///
+    /// if get().is_container() {
+    ///     println!("Hardware information describes the host, not the container");
+    /// }
+    /// ```
+    pub fn is_container(&self) -> bool {
+        self.container_runtime.is_some()
+    }
+
+    /// Returns the container runtime when running inside a container.
+    ///
+    /// Checked in this order: `kubepods` in `/proc/1/cgroup` (Kubernetes),
+    /// the `container` variable of the environment or `/proc/self/environ`
+    /// (`lxc`, `podman`, `docker`), then `/.dockerenv` and `docker` or `lxc`
+    /// in `/proc/1/cgroup`. An unrecognized `container` value gives
+    /// [`ContainerRuntime::Unknown`].
+    ///
+    /// # Returns
+    ///
+    /// * `Option<ContainerRuntime>` - `None` outside a container and on other
+    ///   platforms than Linux.
+    pub fn container_runtime(&self) -> Option<ContainerRuntime> {
+        self.container_runtime
+    }
+
+    /// Returns the distributions this one is derived from or compatible with.
+    ///
+    /// Read from `ID_LIKE` in `/etc/os-release`, closest relative first, so
+    /// an unrecognized distribution can still be identified as Debian-like.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&[String]>` - The `ID` values of the related distributions,
+    ///   e.g. `["rhel", "fedora"]`. `None` if unknown or on other platforms
+    ///   than Linux.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use system_info_lib::get;
+    ///
+    /// let info = get();
+    /// let debian_like = info
+    ///     .id_like()
+    ///     .is_some_and(|ids| ids.iter().any(|id| id == "debian"));
+    /// println!("Debian-like: {}", debian_like);
+    /// ```
+    pub fn id_like(&self) -> Option<&[String]> {
+        self.id_like.as_deref()
+    }
+
+    /// Returns the build identifier of the OS image.
+    ///
+    /// Read from `BUILD_ID` in `/etc/os-release`; rolling releases and
+    /// image-based systems set it, e.g. `20230816`.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&str>` - The build identifier, if known.
+    pub fn build_id(&self) -> Option<&str> {
+        self.build_id.as_deref()
+    }
+
+    /// Returns the variant of the OS.
+    ///
+    /// Read from `VARIANT_ID` in `/etc/os-release`, e.g. `workstation` or
+    /// `server` on Fedora.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&str>` - The variant identifier, if known.
+    pub fn variant(&self) -> Option<&str> {
+        self.variant.as_deref()
+    }
+
+    /// Returns the display version of the OS release.
+    ///
+    /// Read from `DisplayVersion` in the Windows registry, e.g. `23H2`.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&str>` - The display version, if known.
+    pub fn display_version(&self) -> Option<&str> {
+        self.display_version.as_deref()
+    }
+
+    /// Returns the update build revision of the OS release.
+    ///
+    /// Read from `UBR` in the Windows registry. It is the fourth component of
+    /// the full Windows version, e.g. `3007` in `10.0.22631.3007`; the version
+    /// itself stays `10.0.22631`.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<u32>` - The update build revision, if known.
+    pub fn update_build_revision(&self) -> Option<u32> {
+        self.update_build_revision
+    }
+
+    /// Returns whether Windows is a workstation (client) release.
+    ///
+    /// Read from the product type reported by `RtlGetVersion`:
+    /// `VER_NT_WORKSTATION` is a workstation, a domain controller or server
+    /// is not.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<bool>` - `None` on other systems or if unknown.
+    pub fn is_workstation(&self) -> Option<bool> {
+        self.workstation
+    }
+
+    /// Returns `true` on Windows 11.
+    ///
+    /// Windows 11 kept the 10.0 version number of Windows 10, so it is told
+    /// apart by its build, 22000 or later. Windows Server 2025 shares those
+    /// builds, so the product type must also be a workstation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use system_info_lib::{Info, SystemVersion, Type};
+    ///
+    /// let info = Info::builder()
+    ///     .system_type(Type::Windows)
+    ///     .version(SystemVersion::semantic(10, 0, 26100))
+    ///     .workstation(false)
+    ///     .build();
+    /// assert!(!info.is_windows_11());
+    /// ```
+    pub fn is_windows_11(&self) -> bool {
+        self.system_type == Type::Windows
+            && self.workstation == Some(true)
+            && matches!(
+                self.version,
+                SystemVersion::Semantic(10, 0, build) if build >= WINDOWS_11_FIRST_BUILD
+            )
+    }
+
+    /// Returns the feature flags of the current CPU (e.g. `avx2`, `avx512`, `neon`, `sve`).
+    ///
+    /// Unlike the other accessors this is not stored in `Info`; the flags are
+    /// read from the running system on each call (`/proc/cpuinfo` on Linux,
+    /// `sysctl machdep.cpu.features` on macOS). An empty vector is returned
+    /// if the flags cannot be determined.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use system_info_lib::get;
+    ///
+    /// let info = get();
+    /// if info.cpu_features().iter().any(|f| f == "avx2") {
+    ///     println!("AVX2 is supported");
+    /// }
+    /// ```
+    pub fn cpu_features(&self) -> Vec<String> {
+        crate::cpu_features::get()
+    }
+
+    /// Returns the number of logical CPUs available to the process.
+    ///
+    /// Like [`cpu_features()`](Info::cpu_features), this is read from the
+    /// running system on each call. `std::thread::available_parallelism` is
+    /// used first, falling back to counting the `processor` entries of
+    /// `/proc/cpuinfo` on Linux and `sysctl hw.logicalcpu` on macOS.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<usize>` - The number of logical CPUs, if it can be determined.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use system_info_lib::get;
+    ///
+    /// if let Some(count) = get().logical_cpu_count() {
+    ///     println!("{} logical CPUs", count);
+    /// }
+    /// ```
+    pub fn logical_cpu_count(&self) -> Option<usize> {
+        crate::logical_cpus::get()
+    }
+
+    /// Returns `true` if the process runs inside a FreeBSD jail.
+    ///
+    /// Like [`cpu_features()`](Info::cpu_features), this is read from the
+    /// running system on each call, using the `security.jail.jailed` sysctl.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<bool>` - `None` on other systems or if the sysctl cannot be read.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use system_info_lib::get;
+    ///
+    /// if get().is_jailed() == Some(true) {
+    ///     println!("Running inside a jail");
+    /// }
+    /// ```
+    pub fn is_jailed(&self) -> Option<bool> {
+        #[cfg(target_os = "freebsd")]
+        {
+            crate::imp::is_jailed()
+        }
+        #[cfg(not(target_os = "freebsd"))]
+        {
+            None
+        }
+    }
+
+    /// Returns `true` for server editions and `false` for desktop systems.
+    ///
+    /// This is a heuristic, evaluated on each call:
+    ///
+    /// 1. An edition containing "Server" (e.g. `Windows Server 2022 Datacenter`)
+    ///    is a server; any other known Windows edition is a desktop.
+    /// 2. On Linux, `VARIANT_ID` in `/etc/os-release` (`server`, `workstation`, ...).
+    /// 3. On other Unix-like systems, the absence of both a running graphical
+    ///    session and installed desktop sessions indicates a server.
+    ///
+    /// macOS, Android and ChromeOS are always desktops.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<bool>` - `None` if none of the heuristics apply.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use system_info_lib::get;
+    ///
+    /// if get().is_server_edition() == Some(true) {
+    ///     println!("Running on a server");
+    /// }
+    /// ```
+    pub fn is_server_edition(&self) -> Option<bool> {
+        crate::server_edition::get(self.system_type, self.edition())
+    }
+
+    /// Returns the init system managing services.
+    ///
+    /// Evaluated on each call on Linux, from `/run/systemd/system`, the
+    /// `/run/openrc` and `/run/runit` runtime directories, the `/sbin/init`
+    /// symlink target and finally the installed `/sbin/openrc`, `/etc/runit`
+    /// or `/etc/inittab`.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<InitSystem>` - `None` on other systems or if no check matches.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use system_info_lib::{get, InitSystem};
+    ///
+    /// if get().init_system() == Some(InitSystem::Systemd) {
+    ///     println!("Managing services with systemctl");
+    /// }
+    /// ```
+    pub fn init_system(&self) -> Option<crate::InitSystem> {
+        crate::init_system::get()
+    }
+
+    /// Returns the size and free space of the mounted filesystems.
+    ///
+    /// Read once when [`get()`](crate::get) detects the system, so the free
+    /// space is as of detection; call [`refresh()`](crate::refresh) with the
+    /// `cache` feature for current values. On Unix-like systems `/`, `/home`
+    /// and `/var` are reported (the latter two only if they are separate
+    /// mounts), on Windows every fixed drive. Pseudo filesystems such as
+    /// `proc` or `tmpfs` are skipped.
+    ///
+    /// Storage is not part of the `Display` output, but is included when
+    /// `Info` is serialized with the `serde` feature.
+    ///
+    /// Requires the `storage` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use system_info_lib::get;
+    ///
+    /// let info = get();
+    /// for mount in info.storage().unwrap_or_default() {
+    ///     println!("{}: {:.1}% used", mount.mount_point, mount.used_percent());
+    /// }
+    /// ```
+    #[cfg(feature = "storage")]
+    pub fn storage(&self) -> Option<&[crate::MountInfo]> {
+        self.storage.as_deref()
+    }
+
+    /// Returns a summary of the network interfaces.
+    ///
+    /// Like [`storage()`](Info::storage), this is read when the system is
+    /// detected: from `/sys/class/net` on Linux, from
+    /// `networksetup` and `ifconfig` on macOS and from
+    /// `GetAdaptersAddresses` on Windows. The interface carrying the default
+    /// route is marked as primary and listed first.
+    ///
+    /// MAC addresses are only reported with the `identifiers` feature;
+    /// otherwise each is replaced by a stable hash.
+    ///
+    /// Requires the `network` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use system_info_lib::get;
+    ///
+    /// let info = get();
+    /// if let Some(primary) = info.network_interfaces().unwrap_or_default().first() {
+    ///     println!("{} (wireless: {})", primary.name, primary.wireless);
+    /// }
+    /// ```
+    #[cfg(feature = "network")]
+    pub fn network_interfaces(&self) -> Option<&[crate::InterfaceInfo]> {
+        self.network_interfaces.as_deref()
+    }
+
+    /// Returns the installed memory modules with their type and speed.
+    ///
+    /// Like [`storage()`](Info::storage), this is read when the system is
+    /// detected: from the SMBIOS memory device entries in
+    /// `/sys/firmware/dmi/entries` on Linux (usually readable only by root)
+    /// and from the firmware SMBIOS table on Windows. On macOS
+    /// `system_profiler` is used; Apple Silicon reports no module details,
+    /// so type and speed come from a table of known chips.
+    ///
+    /// Requires the `memory` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use system_info_lib::get;
+    ///
+    /// if let Some(memory) = get().memory_modules() {
+    ///     println!(
+    ///         "{} at {:?} MT/s in {} module(s)",
+    ///         memory.memory_type().unwrap_or("Unknown"),
+    ///         memory.configured_speed_mts(),
+    ///         memory.modules.len()
+    ///     );
+    /// }
+    /// ```
+    #[cfg(feature = "memory")]
+    pub fn memory_modules(&self) -> Option<&crate::MemoryInfo> {
+        self.memory.as_ref()
+    }
+
+    /// Returns whether the desktop uses a light, dark or high contrast theme.
+    ///
+    /// Like [`storage()`](Info::storage), this is read when the system is
+    /// detected: from the `AppsUseLightTheme` registry value and
+    /// the high contrast setting on Windows, from
+    /// `defaults read -g AppleInterfaceStyle` on macOS, and on Linux from the
+    /// `org.freedesktop.appearance` settings of the XDG desktop portal, with
+    /// `gsettings` as a fallback when D-Bus is unavailable.
+    ///
+    /// Requires the `desktop` feature.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<UiTheme>` - The theme, or `None` on headless systems.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use system_info_lib::{get, UiTheme};
+    ///
+    /// if get().ui_theme() == Some(UiTheme::Dark) {
+    ///     println!("Using the dark overlay");
+    /// }
+    /// ```
+    #[cfg(feature = "desktop")]
+    pub fn ui_theme(&self) -> Option<crate::UiTheme> {
+        self.ui_theme
     }
 
     /// Creates a new [`InfoBuilder`] for constructing an `Info` instance.
@@ -172,6 +768,46 @@ impl Info {
     pub fn builder() -> InfoBuilder {
         InfoBuilder::new()
     }
+
+    /// Serializes the `Info` to a JSON string.
+    ///
+    /// Fields that are not known serialize as `null`. Requires the `serde`
+    /// feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns the `serde_json` error if serialization fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use system_info_lib::Info;
+    ///
+    /// let json = Info::unknown().to_json().unwrap();
+    /// assert!(json.contains(r#""version":"unknown""#));
+    /// assert!(json.contains(r#""edition":null"#));
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+}
+
+/// Serializes `Info` like its own `Serialize` impl, with the hostname and
+/// the network interfaces redacted.
+#[cfg(feature = "serde")]
+impl serde::Serialize for crate::redaction::Redacted<'_, Info> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut info = self.value.clone();
+        self.policy.apply_to(&mut info.hostname);
+        #[cfg(feature = "network")]
+        if let Some(interfaces) = info.network_interfaces.as_mut() {
+            use crate::redaction::Redact;
+
+            interfaces.redact(self.policy);
+        }
+        info.serialize(serializer)
+    }
 }
 
 impl Default for Info {
@@ -187,6 +823,18 @@ impl Default for Info {
     ///     - `bit_depth`: `BitDepth::Unknown`
     ///     - `architecture`: `None`
     ///     - `kernel_version`: `None`
+    ///     - `patch_level`: `None`
+    ///     - `hostname`: `None`
+    ///     - `uptime`: `None`
+    ///     - `wsl_version`: `None`
+    ///     - `container_runtime`: `None`
+    ///     - `id_like`: `None`
+    ///     - `build_id`: `None`
+    ///     - `variant`: `None`
+    ///     - `display_version`: `None`
+    ///     - `update_build_revision`: `None`
+    ///     - `workstation`: `None`
+    ///     - `storage`, `network_interfaces`, `memory` and `ui_theme`: `None`
     fn default() -> Self {
         Self::unknown()
     }
@@ -197,7 +845,8 @@ impl Display for Info {
     ///
     /// The format is as follows:
     ///
-    /// `<system_type> [<edition>] (<codename>) <version>, <bit_depth>, <architecture>`
+    /// `<system_type> [<edition>] (<codename>) <version>, <bit_depth>, <architecture>,
+    /// kernel <kernel_version>, variant <variant>, build <build_id>, like <id_like>`
     ///
     /// Where:
     ///
@@ -207,6 +856,9 @@ impl Display for Info {
     /// - `<version>` is the version of the operating system
     /// - `<bit_depth>` is the bit depth of the operating system, if known
     /// - `<architecture>` is the architecture of the operating system, if known
+    /// - `<kernel_version>` is the kernel release, if known
+    /// - `<variant>`, `<build_id>` and the space-separated `<id_like>` come
+    ///   from `/etc/os-release` and are only shown if known
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.system_type)?;
         if let Some(edition) = &self.edition {
@@ -222,6 +874,18 @@ impl Display for Info {
         if let Some(architecture) = &self.architecture {
             write!(f, ", {}", architecture)?;
         }
+        if let Some(kernel_version) = &self.kernel_version {
+            write!(f, ", kernel {}", kernel_version)?;
+        }
+        if let Some(variant) = &self.variant {
+            write!(f, ", variant {}", variant)?;
+        }
+        if let Some(build_id) = &self.build_id {
+            write!(f, ", build {}", build_id)?;
+        }
+        if let Some(id_like) = &self.id_like {
+            write!(f, ", like {}", id_like.join(" "))?;
+        }
         Ok(())
     }
 }
@@ -257,6 +921,25 @@ pub struct InfoBuilder {
     bit_depth: Option<BitDepth>,
     architecture: Option<String>,
     kernel_version: Option<String>,
+    patch_level: Option<u32>,
+    hostname: Option<String>,
+    uptime: Option<Duration>,
+    wsl_version: Option<u8>,
+    container_runtime: Option<ContainerRuntime>,
+    id_like: Option<Vec<String>>,
+    build_id: Option<String>,
+    variant: Option<String>,
+    display_version: Option<String>,
+    update_build_revision: Option<u32>,
+    workstation: Option<bool>,
+    #[cfg(feature = "storage")]
+    storage: Option<Vec<crate::MountInfo>>,
+    #[cfg(feature = "network")]
+    network_interfaces: Option<Vec<crate::InterfaceInfo>>,
+    #[cfg(feature = "memory")]
+    memory: Option<crate::MemoryInfo>,
+    #[cfg(feature = "desktop")]
+    ui_theme: Option<crate::UiTheme>,
 }
 
 impl InfoBuilder {
@@ -379,6 +1062,232 @@ impl InfoBuilder {
         self
     }
 
+    /// Sets the patch level of the OS release.
+    ///
+    /// # Arguments
+    ///
+    /// * `patch_level` - The patch level, e.g. `4` for FreeBSD `13.2-RELEASE-p4`.
+    ///
+    /// # Returns
+    ///
+    /// The builder instance for method chaining.
+    pub fn patch_level(mut self, patch_level: u32) -> Self {
+        self.patch_level = Some(patch_level);
+        self
+    }
+
+    /// Sets the hostname.
+    ///
+    /// # Arguments
+    ///
+    /// * `hostname` - The hostname of the machine.
+    ///
+    /// # Returns
+    ///
+    /// The builder instance for method chaining.
+    pub fn hostname(mut self, hostname: impl Into<String>) -> Self {
+        self.hostname = Some(hostname.into());
+        self
+    }
+
+    /// Sets the uptime.
+    ///
+    /// # Arguments
+    ///
+    /// * `uptime` - How long the system has been running.
+    ///
+    /// # Returns
+    ///
+    /// The builder instance for method chaining.
+    pub fn uptime(mut self, uptime: Duration) -> Self {
+        self.uptime = Some(uptime);
+        self
+    }
+
+    /// Sets the Windows Subsystem for Linux version.
+    ///
+    /// # Arguments
+    ///
+    /// * `wsl_version` - The WSL version, 1 or 2.
+    ///
+    /// # Returns
+    ///
+    /// The builder instance for method chaining.
+    pub fn wsl_version(mut self, wsl_version: u8) -> Self {
+        self.wsl_version = Some(wsl_version);
+        self
+    }
+
+    /// Sets the container runtime.
+    ///
+    /// # Arguments
+    ///
+    /// * `container_runtime` - The runtime of the container the system runs in.
+    ///
+    /// # Returns
+    ///
+    /// The builder instance for method chaining.
+    pub fn container_runtime(mut self, container_runtime: ContainerRuntime) -> Self {
+        self.container_runtime = Some(container_runtime);
+        self
+    }
+
+    /// Sets the distributions the system is derived from.
+    ///
+    /// # Arguments
+    ///
+    /// * `id_like` - The `ID_LIKE` values, closest relative first.
+    ///
+    /// # Returns
+    ///
+    /// The builder instance for method chaining.
+    pub fn id_like<I, S>(mut self, id_like: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.id_like = Some(id_like.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Sets the build identifier.
+    ///
+    /// # Arguments
+    ///
+    /// * `build_id` - The `BUILD_ID` of the OS image.
+    ///
+    /// # Returns
+    ///
+    /// The builder instance for method chaining.
+    pub fn build_id(mut self, build_id: impl Into<String>) -> Self {
+        self.build_id = Some(build_id.into());
+        self
+    }
+
+    /// Sets the variant.
+    ///
+    /// # Arguments
+    ///
+    /// * `variant` - The `VARIANT_ID` of the operating system.
+    ///
+    /// # Returns
+    ///
+    /// The builder instance for method chaining.
+    pub fn variant(mut self, variant: impl Into<String>) -> Self {
+        self.variant = Some(variant.into());
+        self
+    }
+
+    /// Sets the display version.
+    ///
+    /// # Arguments
+    ///
+    /// * `display_version` - The Windows display version, e.g. `23H2`.
+    ///
+    /// # Returns
+    ///
+    /// The builder instance for method chaining.
+    pub fn display_version(mut self, display_version: impl Into<String>) -> Self {
+        self.display_version = Some(display_version.into());
+        self
+    }
+
+    /// Sets the update build revision.
+    ///
+    /// # Arguments
+    ///
+    /// * `update_build_revision` - The Windows update build revision, e.g. `3007`.
+    ///
+    /// # Returns
+    ///
+    /// The builder instance for method chaining.
+    pub fn update_build_revision(mut self, update_build_revision: u32) -> Self {
+        self.update_build_revision = Some(update_build_revision);
+        self
+    }
+
+    /// Sets whether Windows is a workstation release.
+    ///
+    /// # Arguments
+    ///
+    /// * `workstation` - `false` for a Windows Server product type.
+    ///
+    /// # Returns
+    ///
+    /// The builder instance for method chaining.
+    pub fn workstation(mut self, workstation: bool) -> Self {
+        self.workstation = Some(workstation);
+        self
+    }
+
+    /// Sets the mounted filesystems.
+    ///
+    /// Requires the `storage` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `storage` - The size and free space of each mounted filesystem.
+    ///
+    /// # Returns
+    ///
+    /// The builder instance for method chaining.
+    #[cfg(feature = "storage")]
+    pub fn storage(mut self, storage: Vec<crate::MountInfo>) -> Self {
+        self.storage = Some(storage);
+        self
+    }
+
+    /// Sets the network interfaces.
+    ///
+    /// Requires the `network` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `network_interfaces` - The interfaces, the primary one first.
+    ///
+    /// # Returns
+    ///
+    /// The builder instance for method chaining.
+    #[cfg(feature = "network")]
+    pub fn network_interfaces(mut self, network_interfaces: Vec<crate::InterfaceInfo>) -> Self {
+        self.network_interfaces = Some(network_interfaces);
+        self
+    }
+
+    /// Sets the installed memory modules.
+    ///
+    /// Requires the `memory` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `memory` - The installed memory.
+    ///
+    /// # Returns
+    ///
+    /// The builder instance for method chaining.
+    #[cfg(feature = "memory")]
+    pub fn memory_modules(mut self, memory: crate::MemoryInfo) -> Self {
+        self.memory = Some(memory);
+        self
+    }
+
+    /// Sets the desktop theme.
+    ///
+    /// Requires the `desktop` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `ui_theme` - The colour theme of the desktop.
+    ///
+    /// # Returns
+    ///
+    /// The builder instance for method chaining.
+    #[cfg(feature = "desktop")]
+    pub fn ui_theme(mut self, ui_theme: crate::UiTheme) -> Self {
+        self.ui_theme = Some(ui_theme);
+        self
+    }
+
     /// Builds the [`Info`] instance.
     ///
     /// All unset fields will default to their unknown values:
@@ -389,6 +1298,18 @@ impl InfoBuilder {
     /// - `bit_depth`: `BitDepth::Unknown`
     /// - `architecture`: `None`
     /// - `kernel_version`: `None`
+    /// - `patch_level`: `None`
+    /// - `hostname`: `None`
+    /// - `uptime`: `None`
+    /// - `wsl_version`: `None`
+    /// - `container_runtime`: `None`
+    /// - `id_like`: `None`
+    /// - `build_id`: `None`
+    /// - `variant`: `None`
+    /// - `display_version`: `None`
+    /// - `update_build_revision`: `None`
+    /// - `workstation`: `None`
+    /// - `storage`, `network_interfaces`, `memory` and `ui_theme`: `None`
     ///
     /// # Returns
     ///
@@ -401,7 +1322,26 @@ impl InfoBuilder {
             codename: self.codename,
             bit_depth: self.bit_depth.unwrap_or(BitDepth::Unknown),
             architecture: self.architecture,
-            kernel_version: self.kernel_version,
+            kernel_version: self.kernel_version.map(KernelVersion::parse),
+            patch_level: self.patch_level,
+            hostname: self.hostname,
+            uptime: self.uptime,
+            wsl_version: self.wsl_version,
+            container_runtime: self.container_runtime,
+            id_like: self.id_like,
+            build_id: self.build_id,
+            variant: self.variant,
+            display_version: self.display_version,
+            update_build_revision: self.update_build_revision,
+            workstation: self.workstation,
+            #[cfg(feature = "storage")]
+            storage: self.storage,
+            #[cfg(feature = "network")]
+            network_interfaces: self.network_interfaces,
+            #[cfg(feature = "memory")]
+            memory: self.memory,
+            #[cfg(feature = "desktop")]
+            ui_theme: self.ui_theme,
         }
     }
 
@@ -534,6 +1474,139 @@ mod tests {
         assert_eq!(info.kernel_version(), Some("5.15.0-76-generic"));
     }
 
+    #[test]
+    fn test_builder_with_patch_level() {
+        let info = Info::builder()
+            .system_type(Type::FreeBSD)
+            .version(SystemVersion::custom("13.2-RELEASE"))
+            .patch_level(4)
+            .build();
+
+        assert_eq!(info.patch_level(), Some(4));
+        assert_eq!(Info::unknown().patch_level(), None);
+    }
+
+    #[test]
+    fn test_builder_with_uptime() {
+        let info = Info::builder()
+            .system_type(Type::Linux)
+            .uptime(Duration::from_secs(3600))
+            .build();
+
+        assert_eq!(info.uptime(), Some(Duration::from_secs(3600)));
+        assert_eq!(Info::unknown().uptime(), None);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_to_json() {
+        let info = Info::builder()
+            .system_type(Type::Fedora)
+            .version(SystemVersion::Semantic(40, 0, 0))
+            .build();
+        let json: serde_json::Value = serde_json::from_str(&info.to_json().unwrap()).unwrap();
+
+        assert_eq!(json["system_type"], "Fedora");
+        assert_eq!(json["edition"], serde_json::Value::Null);
+        assert_eq!(json["hostname"], serde_json::Value::Null);
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&Info::unknown().to_json().unwrap()).unwrap()
+                ["version"],
+            "unknown"
+        );
+    }
+
+    #[test]
+    fn test_builder_with_wsl_version() {
+        let info = Info::builder()
+            .system_type(Type::Ubuntu)
+            .wsl_version(2)
+            .build();
+
+        assert!(info.is_wsl());
+        assert_eq!(info.wsl_version(), Some(2));
+        assert!(!Info::unknown().is_wsl());
+        assert_eq!(Info::unknown().wsl_version(), None);
+    }
+
+    #[test]
+    fn test_builder_with_container_runtime() {
+        let info = Info::builder()
+            .system_type(Type::Alpine)
+            .container_runtime(ContainerRuntime::Podman)
+            .build();
+
+        assert!(info.is_container());
+        assert_eq!(info.container_runtime(), Some(ContainerRuntime::Podman));
+        assert!(!Info::unknown().is_container());
+        assert_eq!(Info::unknown().container_runtime(), None);
+    }
+
+    #[test]
+    fn test_builder_with_os_release_fields() {
+        let info = Info::builder()
+            .system_type(Type::Fedora)
+            .version(SystemVersion::Semantic(39, 0, 0))
+            .id_like(["rhel", "fedora"])
+            .build_id("20231105")
+            .variant("workstation")
+            .build();
+
+        assert_eq!(
+            info.id_like(),
+            Some(&["rhel".to_string(), "fedora".to_string()][..])
+        );
+        assert_eq!(info.build_id(), Some("20231105"));
+        assert_eq!(info.variant(), Some("workstation"));
+        assert_eq!(
+            info.to_string(),
+            "Fedora 39.0.0, variant workstation, build 20231105, like rhel fedora"
+        );
+        assert_eq!(Info::unknown().id_like(), None);
+        assert_eq!(Info::unknown().build_id(), None);
+        assert_eq!(Info::unknown().variant(), None);
+    }
+
+    #[test]
+    fn test_builder_with_display_version() {
+        let info = Info::builder()
+            .system_type(Type::Windows)
+            .version(SystemVersion::semantic(10, 0, 22631))
+            .edition("Windows 11 Professional")
+            .display_version("23H2")
+            .update_build_revision(3007)
+            .build();
+
+        assert_eq!(info.display_version(), Some("23H2"));
+        assert_eq!(info.update_build_revision(), Some(3007));
+        assert_eq!(Info::unknown().display_version(), None);
+        assert_eq!(Info::unknown().update_build_revision(), None);
+    }
+
+    #[test]
+    fn test_is_windows_11() {
+        let windows = |build, workstation| {
+            Info::builder()
+                .system_type(Type::Windows)
+                .version(SystemVersion::semantic(10, 0, build))
+                .workstation(workstation)
+                .build()
+        };
+        assert!(windows(22000, true).is_windows_11());
+        assert!(windows(26100, true).is_windows_11());
+        assert!(!windows(19045, true).is_windows_11());
+        // Windows Server 2025
+        assert!(!windows(26100, false).is_windows_11());
+        assert_eq!(windows(26100, false).is_workstation(), Some(false));
+
+        let unknown_product_type = Info::builder()
+            .system_type(Type::Windows)
+            .version(SystemVersion::semantic(10, 0, 22631))
+            .build();
+        assert!(!unknown_product_type.is_windows_11());
+        assert!(!Info::unknown().is_windows_11());
+    }
+
     #[test]
     fn test_builder_kernel_version_string_conversions() {
         let kernel_string = String::from("6.1.0-13-amd64");
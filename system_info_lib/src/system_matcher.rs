@@ -206,6 +206,42 @@ mod system_matcher_tests {
         }
     }
 
+    #[test]
+    fn prefixed_version_sw_vers() {
+        let data = [
+            (
+                "ProductVersion:\t11.0\nBuildVersion:\t20A2411",
+                Some("11.0"),
+            ),
+            (
+                "ProductVersion:\t11.0.1\nBuildVersion:\t20B29",
+                Some("11.0.1"),
+            ),
+            (
+                "ProductVersion:\t10.15.7\nBuildVersion:\t19H15",
+                Some("10.15.7"),
+            ),
+            (
+                "ProductVersion:\t10.15.21\nBuildVersion:\tABCD123",
+                Some("10.15.21"),
+            ),
+            (
+                "ProductVersion:\t14.0.0-beta\nBuildVersion:\t23A5257q",
+                Some("14.0.0-beta"),
+            ),
+            ("ProductName:\tmacOS\nBuildVersion:\t23A5257q", None),
+        ];
+
+        let matcher = SystemMatcher::PrefixedVersion {
+            prefix: "ProductVersion:",
+        };
+
+        for (input, expected) in &data {
+            let result = matcher.find(input);
+            assert_eq!(result.as_deref(), *expected);
+        }
+    }
+
     #[test]
     fn key_value() {
         let data = [
@@ -45,6 +45,8 @@ pub enum Type {
     Fedora,
     /// Unix-like operating system with open-source code
     FreeBSD,
+    /// Capability-based operating system from Google
+    Fuchsia,
     /// Gaming Linux distribution based on Arch
     Garuda,
     /// Linux distribution with a high degree of customization
@@ -157,6 +159,7 @@ impl Display for Type {
             Type::EndeavourOS => write!(f, "EndeavourOS"),
             Type::Fedora => write!(f, "Fedora"),
             Type::FreeBSD => write!(f, "FreeBSD"),
+            Type::Fuchsia => write!(f, "Fuchsia"),
             Type::Garuda => write!(f, "Garuda Linux"),
             Type::Gentoo => write!(f, "Gentoo Linux"),
             Type::HardenedBSD => write!(f, "HardenedBSD"),
@@ -257,17 +260,20 @@ mod system_os_tests {
     /// edition, codename, a bit of depth, and architecture.
     #[test]
     fn test_info_display() {
-        let info = Info {
-            system_type: Type::Linux,
-            version: SystemVersion::Semantic(1, 1, 1),
-            edition: Some("Pro".to_string()),
-            codename: Some("Focal".to_string()),
-            bit_depth: BitDepth::X64,
-            architecture: Some("x86_64".to_string()),
-            kernel_version: Some("5.15.0".to_string()),
-        };
+        let info = Info::builder()
+            .system_type(Type::Linux)
+            .version(SystemVersion::Semantic(1, 1, 1))
+            .edition("Pro")
+            .codename("Focal")
+            .bit_depth(BitDepth::X64)
+            .architecture("x86_64")
+            .kernel_version("5.15.0")
+            .build();
         let display = format!("{}", info);
-        assert_eq!(display, "Linux Pro (Focal) 1.1.1, 64-bit, x86_64");
+        assert_eq!(
+            display,
+            "Linux Pro (Focal) 1.1.1, 64-bit, x86_64, kernel 5.15.0"
+        );
     }
 
     #[test]
@@ -296,6 +302,7 @@ mod system_os_tests {
             (Type::EndeavourOS, "EndeavourOS"),
             (Type::Fedora, "Fedora"),
             (Type::FreeBSD, "FreeBSD"),
+            (Type::Fuchsia, "Fuchsia"),
             (Type::Garuda, "Garuda Linux"),
             (Type::Gentoo, "Gentoo Linux"),
             (Type::HardenedBSD, "HardenedBSD"),
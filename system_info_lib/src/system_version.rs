@@ -1,11 +1,12 @@
 //src/system_info.rs
+use std::cmp::Ordering;
 use std::fmt::Display;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// Operating system version.
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 /// Represents a rolling release version of the system.
 ///
@@ -22,6 +23,9 @@ use serde::{Deserialize, Serialize};
 /// ```    
 pub enum SystemVersion {
     /// Represents an unknown version of the system.
+    ///
+    /// Serialized as the string `"unknown"`.
+    #[cfg_attr(feature = "serde", serde(rename = "unknown", alias = "Unknown"))]
     Unknown,
     /// Represents a semantic version of the system with major, minor, and patch numbers.
     Semantic(u64, u64, u64),
@@ -29,6 +33,11 @@ pub enum SystemVersion {
     Rolling(Option<String>),
     /// Represents a custom version of the system as a string.
     Custom(String),
+    /// Represents a pre-release of a semantic version, with major, minor, and
+    /// patch numbers and a tag such as `beta` or `rc.1`.
+    ///
+    /// A pre-release orders before the release it precedes.
+    PreRelease(u64, u64, u64, String),
 }
 
 impl SystemVersion {
@@ -37,20 +46,42 @@ impl SystemVersion {
     /// # Arguments
     ///
     /// * `s` - A string that represents the version. It can be a semantic version
-    ///   (e.g., "1.2.3"), a custom version string, or an empty string.
+    ///   (e.g., "1.2.3" or "13.0.0-beta"), a custom version string, or an empty string.
     ///
     /// # Returns
     ///
     /// * `SystemVersion::Unknown` if the string is empty.
     /// * `SystemVersion::Semantic` if the string is a valid semantic version.
+    /// * `SystemVersion::PreRelease` if it is a valid semantic version followed
+    ///   by `-` and a pre-release tag. The patch number may be omitted, as in
+    ///   `13.0-beta`, and is then 0.
     /// * `SystemVersion::Custom` for any other non-empty string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use system_info_lib::SystemVersion;
+    ///
+    /// let beta = SystemVersion::from_string("13.0.0-beta");
+    /// assert!(beta.is_prerelease());
+    /// assert!(beta < SystemVersion::from_string("13.0.0"));
+    /// ```
     pub fn from_string<S: Into<String> + AsRef<str>>(s: S) -> Self {
         if s.as_ref().is_empty() {
-            Self::Unknown
-        } else if let Some((major, minor, patch)) = parse_version(s.as_ref()) {
-            Self::Semantic(major, minor, patch)
-        } else {
-            Self::Custom(s.into())
+            return Self::Unknown;
+        }
+        let (version, pre_release) = split_pre_release(s.as_ref());
+        match (parse_version(version), pre_release) {
+            (Some((major, minor, patch)), None) => Self::Semantic(major, minor, patch),
+            (Some((major, minor, patch)), Some(pre_release)) => {
+                Self::PreRelease(major, minor, patch, pre_release.to_owned())
+            }
+            // Pre-releases are often numbered like `13.0-beta`
+            (None, Some(pre_release)) => match parse_major_minor(version) {
+                Some((major, minor)) => Self::PreRelease(major, minor, 0, pre_release.to_owned()),
+                None => Self::Custom(s.into()),
+            },
+            (None, None) => Self::Custom(s.into()),
         }
     }
 
@@ -85,6 +116,82 @@ impl SystemVersion {
         Self::Semantic(major, minor, patch)
     }
 
+    /// Creates a pre-release of a semantic version.
+    ///
+    /// # Arguments
+    ///
+    /// * `major` - The major version number
+    /// * `minor` - The minor version number
+    /// * `patch` - The patch version number
+    /// * `pre_release` - The pre-release tag, e.g. `beta` or `rc.1`
+    ///
+    /// # Returns
+    ///
+    /// A `SystemVersion::PreRelease` instance with the specified version numbers and tag.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use system_info_lib::SystemVersion;
+    ///
+    /// let version = SystemVersion::semantic_pre(13, 0, 0, "beta");
+    /// assert_eq!(version.to_string(), "13.0.0-beta");
+    /// assert!(version < SystemVersion::semantic(13, 0, 0));
+    /// ```
+    pub fn semantic_pre(
+        major: u64,
+        minor: u64,
+        patch: u64,
+        pre_release: impl Into<String>,
+    ) -> Self {
+        Self::PreRelease(major, minor, patch, pre_release.into())
+    }
+
+    /// Returns the pre-release tag of a pre-release version, e.g. `"beta"`.
+    pub fn pre_release(&self) -> Option<&str> {
+        match *self {
+            Self::PreRelease(_, _, _, ref pre_release) => Some(pre_release),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` for a semantic version with a pre-release tag.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use system_info_lib::SystemVersion;
+    ///
+    /// assert!(SystemVersion::from_string("6.9.0-rc2").is_prerelease());
+    /// assert!(!SystemVersion::from_string("6.9.0").is_prerelease());
+    /// ```
+    pub fn is_prerelease(&self) -> bool {
+        self.pre_release().is_some()
+    }
+
+    /// Returns `true` if the version is `major.minor` or newer.
+    ///
+    /// The version is compared with `major.minor.0` by its [ordering](Ord),
+    /// so a pre-release of `major.minor.0` is not at least that version.
+    /// Custom versions that start with numbers, such as Ubuntu's `22.04`,
+    /// are compared by those numbers. Unknown, rolling and other custom
+    /// versions are never at least any version.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use system_info_lib::SystemVersion;
+    ///
+    /// assert!(SystemVersion::from_string("10.15.7").at_least(10, 15));
+    /// assert!(!SystemVersion::from_string("10.14.6").at_least(10, 15));
+    /// assert!(!SystemVersion::from_string("14.0.0-beta").at_least(14, 0));
+    /// assert!(SystemVersion::from_string("22.04").at_least(20, 4));
+    /// assert!(!SystemVersion::Unknown.at_least(0, 0));
+    /// ```
+    pub fn at_least(&self, major: u64, minor: u64) -> bool {
+        self.numbered_parts().is_some() && *self >= Self::Semantic(major, minor, 0)
+    }
+
     /// Creates a rolling release version with an optional codename.
     ///
     /// Rolling releases are distributions that continuously update rather than
@@ -159,6 +266,108 @@ impl Default for SystemVersion {
     }
 }
 
+impl PartialOrd for SystemVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for SystemVersion {
+    /// Orders versions by their numbers, across variants.
+    ///
+    /// Semantic and pre-release versions, and custom versions that start
+    /// with a number such as Ubuntu's `22.04`, compare by major, minor and
+    /// patch number, a missing number counting as 0. A pre-release orders
+    /// before the release with the same numbers, and two pre-release tags
+    /// compare by their dot-separated identifiers, numeric ones by value.
+    /// With equal numbers a semantic version orders before a custom one.
+    ///
+    /// Unknown versions order first, then numbered versions, rolling
+    /// releases and other custom versions, which compare runs of digits by
+    /// value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use system_info_lib::SystemVersion;
+    ///
+    /// let version = SystemVersion::from_string("22.04");
+    /// assert!(version >= SystemVersion::from_string("22.04"));
+    /// assert!(version > SystemVersion::from_string("9.10"));
+    /// assert!(SystemVersion::from_string("24.04.1") > version);
+    /// assert!(SystemVersion::Unknown < SystemVersion::semantic(0, 0, 1));
+    /// ```
+    fn cmp(&self, other: &Self) -> Ordering {
+        if let (
+            Some((major, minor, patch, pre_release)),
+            Some((other_major, other_minor, other_patch, other_pre_release)),
+        ) = (self.numbered_parts(), other.numbered_parts())
+        {
+            return (major, minor, patch)
+                .cmp(&(other_major, other_minor, other_patch))
+                .then_with(|| match (pre_release, other_pre_release) {
+                    (None, None) => Ordering::Equal,
+                    (None, Some(_)) => Ordering::Greater,
+                    (Some(_), None) => Ordering::Less,
+                    (Some(a), Some(b)) => compare_pre_release(a, b).then_with(|| a.cmp(b)),
+                })
+                .then_with(|| match (self, other) {
+                    (SystemVersion::Custom(a), SystemVersion::Custom(b)) => {
+                        compare_numeric_runs(a, b).then_with(|| a.cmp(b))
+                    }
+                    _ => self.rank().cmp(&other.rank()),
+                });
+        }
+        self.class()
+            .cmp(&other.class())
+            .then_with(|| match (self, other) {
+                (SystemVersion::Rolling(a), SystemVersion::Rolling(b)) => a.cmp(b),
+                (SystemVersion::Custom(a), SystemVersion::Custom(b)) => {
+                    compare_numeric_runs(a, b).then_with(|| a.cmp(b))
+                }
+                _ => Ordering::Equal,
+            })
+    }
+}
+
+impl SystemVersion {
+    /// Position of the variant in declaration order; a pre-release ranks
+    /// with the semantic versions
+    fn rank(&self) -> u8 {
+        match *self {
+            SystemVersion::Unknown => 0,
+            SystemVersion::Semantic(..) | SystemVersion::PreRelease(..) => 1,
+            SystemVersion::Rolling(_) => 2,
+            SystemVersion::Custom(_) => 3,
+        }
+    }
+
+    /// Like [`rank()`](Self::rank), but numbered custom versions rank with
+    /// the semantic versions
+    fn class(&self) -> u8 {
+        if self.numbered_parts().is_some() {
+            1
+        } else {
+            self.rank()
+        }
+    }
+
+    /// The numbers and pre-release tag of a semantic, pre-release or
+    /// numbered custom version
+    fn numbered_parts(&self) -> Option<(u64, u64, u64, Option<&str>)> {
+        match *self {
+            SystemVersion::Semantic(major, minor, patch) => Some((major, minor, patch, None)),
+            SystemVersion::PreRelease(major, minor, patch, ref pre_release) => {
+                Some((major, minor, patch, Some(pre_release)))
+            }
+            SystemVersion::Custom(ref version) => {
+                leading_numbers(version).map(|(major, minor, patch)| (major, minor, patch, None))
+            }
+            _ => None,
+        }
+    }
+}
+
 impl Display for SystemVersion {
     /// Formats the version as a string.
     ///
@@ -166,6 +375,7 @@ impl Display for SystemVersion {
     ///
     /// * `SystemVersion::Unknown` is formatted as `"Unknown"`.
     /// * `SystemVersion::Semantic(1, 2, 3)` is formatted as `"1.2.3"`.
+    /// * `SystemVersion::PreRelease(1, 2, 3, "beta".to_string())` is formatted as `"1.2.3-beta"`.
     /// * `SystemVersion::Rolling(Some("focal".to_string()))` is formatted as `"Rolling (focal)"`.
     /// * `SystemVersion::Rolling(None)` is formatted as `"Rolling"`.
     /// * `SystemVersion::Custom("custom_version".to_string())` is formatted as `"custom_version"`.
@@ -176,6 +386,9 @@ impl Display for SystemVersion {
             SystemVersion::Semantic(major, minor, patch) => {
                 write!(f, "{}.{}.{}", major, minor, patch)
             }
+            SystemVersion::PreRelease(major, minor, patch, ref pre_release) => {
+                write!(f, "{}.{}.{}-{}", major, minor, patch, pre_release)
+            }
             SystemVersion::Rolling(ref codename) => {
                 if let Some(codename) = codename {
                     write!(f, "Rolling ({})", codename)
@@ -200,7 +413,8 @@ impl Display for SystemVersion {
 /// * `"1.2.3"` is parsed as `(1, 2, 3)`.
 /// * `"1.2"` is not parsed at all and returns `None`.
 /// * `"1.2.3.4"` is not parsed at all and returns `None`.
-/// * `"1.2.3-alpha"` is not parsed at all and returns `None`.
+/// * `"1.2.3-alpha"` is not parsed at all and returns `None`; see
+///   [`split_pre_release`] for the pre-release tag.
 ///
 /// # Return
 ///
@@ -220,6 +434,137 @@ fn parse_version(s: &str) -> Option<(u64, u64, u64)> {
     Some((major, minor, patch))
 }
 
+/// Parses a two-part `major.minor` version.
+fn parse_major_minor(s: &str) -> Option<(u64, u64)> {
+    let (major, minor) = s.trim().split_once('.')?;
+    Some((major.parse().ok()?, minor.parse().ok()?))
+}
+
+/// Parses the leading dot-separated numbers of a custom version.
+///
+/// Up to three numbers are read and missing ones are 0, so `"22.04"` gives
+/// `(22, 4, 0)` and `"2023.12-LTS"` gives `(2023, 12, 0)`. Returns `None`
+/// if the version does not start with a digit.
+fn leading_numbers(s: &str) -> Option<(u64, u64, u64)> {
+    let mut numbers = [0u64; 3];
+    let mut rest = s.trim();
+    for (index, number) in numbers.iter_mut().enumerate() {
+        let end = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        if end == 0 {
+            if index == 0 {
+                return None;
+            }
+            break;
+        }
+        *number = rest[..end].parse().ok()?;
+        match rest[end..].strip_prefix('.') {
+            Some(next) if next.starts_with(|c: char| c.is_ascii_digit()) => rest = next,
+            _ => break,
+        }
+    }
+    Some((numbers[0], numbers[1], numbers[2]))
+}
+
+/// Splits a version at the first `-` into the version and its pre-release tag.
+///
+/// The tag must be dot-separated identifiers of ASCII letters, digits and
+/// hyphens, as in SemVer; otherwise the whole string is returned as the
+/// version. `"13.0.0-beta"` gives `("13.0.0", Some("beta"))`.
+fn split_pre_release(s: &str) -> (&str, Option<&str>) {
+    let s = s.trim();
+    match s.split_once('-') {
+        Some((version, pre_release))
+            if pre_release.split('.').all(|identifier| {
+                !identifier.is_empty()
+                    && identifier
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '-')
+            }) =>
+        {
+            (version, Some(pre_release))
+        }
+        _ => (s, None),
+    }
+}
+
+/// Compares two pre-release tags by SemVer precedence.
+///
+/// Identifiers are compared in order: numeric ones by value and before
+/// alphanumeric ones, which compare as ASCII. A tag that is a prefix of the
+/// other orders first, so `beta` < `beta.2` < `rc`.
+fn compare_pre_release(a: &str, b: &str) -> Ordering {
+    let mut a = a.split('.');
+    let mut b = b.split('.');
+    loop {
+        match (a.next(), b.next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(a), Some(b)) => {
+                let ordering = match (a.parse::<u64>(), b.parse::<u64>()) {
+                    (Ok(a), Ok(b)) => a.cmp(&b),
+                    (Ok(_), Err(_)) => Ordering::Less,
+                    (Err(_), Ok(_)) => Ordering::Greater,
+                    (Err(_), Err(_)) => a.cmp(b),
+                };
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+        }
+    }
+}
+
+/// Compares two strings with runs of ASCII digits compared by value.
+///
+/// The remaining text compares as ASCII, so `"9.10"` < `"22.04"` and
+/// `"NT 6.1"` < `"NT 10.0"`. Leading zeros are ignored: `"22.04"` and
+/// `"22.4"` compare equal.
+fn compare_numeric_runs(a: &str, b: &str) -> Ordering {
+    let mut a = a.as_bytes();
+    let mut b = b.as_bytes();
+    loop {
+        match (a.first(), b.first()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) if x.is_ascii_digit() && y.is_ascii_digit() => {
+                let (x, rest_a) = split_digits(a);
+                let (y, rest_b) = split_digits(b);
+                let ordering = x.len().cmp(&y.len()).then_with(|| x.cmp(y));
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+                a = rest_a;
+                b = rest_b;
+            }
+            (Some(x), Some(y)) => {
+                if x != y {
+                    return x.cmp(y);
+                }
+                a = &a[1..];
+                b = &b[1..];
+            }
+        }
+    }
+}
+
+/// Splits the leading digits off `s`, without leading zeros
+fn split_digits(s: &[u8]) -> (&[u8], &[u8]) {
+    let end = s
+        .iter()
+        .position(|c| !c.is_ascii_digit())
+        .unwrap_or(s.len());
+    let (digits, rest) = s.split_at(end);
+    let start = digits
+        .iter()
+        .position(|&c| c != b'0')
+        .unwrap_or(digits.len());
+    (&digits[start..], rest)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -266,6 +611,20 @@ mod tests {
         assert_eq!(version.to_string(), "Unknown");
     }
 
+    /// Tests that `SystemVersion::Unknown` is serialized as `"unknown"`.
+    ///
+    /// The former `"Unknown"` spelling is still accepted when deserializing.
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serialize_unknown() {
+        let json = serde_json::to_string(&SystemVersion::Unknown).unwrap();
+        assert_eq!(json, r#""unknown""#);
+        for json in [r#""unknown""#, r#""Unknown""#] {
+            let version: SystemVersion = serde_json::from_str(json).unwrap();
+            assert_eq!(version, SystemVersion::Unknown);
+        }
+    }
+
     /// Tests that a semantic version is formatted as `"X.Y.Z"`.
     ///
     /// This test case ensures that `Display` for `SystemVersion` returns the
@@ -331,6 +690,194 @@ mod tests {
         assert_eq!(parsed, None);
     }
 
+    /// Tests that a beta version is parsed with its pre-release tag.
+    ///
+    /// This test case ensures that `SystemVersion::from_string` keeps the
+    /// version numbers of a pre-release instead of returning a
+    /// `SystemVersion::Custom`, and that it round-trips through `Display`.
+    #[test]
+    fn test_from_string_prerelease() {
+        let version = SystemVersion::from_string("13.0.0-beta");
+        assert_eq!(
+            version,
+            SystemVersion::PreRelease(13, 0, 0, "beta".to_string())
+        );
+        assert!(version.is_prerelease());
+        assert_eq!(version.pre_release(), Some("beta"));
+        assert_eq!(version.to_string(), "13.0.0-beta");
+        assert!(!SystemVersion::from_string("13.0.0").is_prerelease());
+        assert_eq!(
+            SystemVersion::from_string("13.0.0-"),
+            SystemVersion::Custom("13.0.0-".to_string())
+        );
+        let two_part = SystemVersion::from_string("13.0-beta");
+        assert_eq!(
+            two_part,
+            SystemVersion::PreRelease(13, 0, 0, "beta".to_string())
+        );
+        assert!(two_part < SystemVersion::from_string("13.0"));
+        assert!(two_part < SystemVersion::from_string("13.0.0"));
+        assert_eq!(
+            SystemVersion::from_string("13-beta"),
+            SystemVersion::Custom("13-beta".to_string())
+        );
+        assert_eq!(
+            SystemVersion::from_string("13.0.x-beta"),
+            SystemVersion::Custom("13.0.x-beta".to_string())
+        );
+    }
+
+    /// Tests that pre-releases order before their final release.
+    ///
+    /// This test case ensures that `13.0.0-beta` < `13.0.0-beta.2` <
+    /// `13.0.0-rc.1` < `13.0.0` < `13.0.1-beta`.
+    #[test]
+    fn test_prerelease_ordering() {
+        let versions: Vec<SystemVersion> = [
+            "13.0.0-beta",
+            "13.0.0-beta.2",
+            "13.0.0-beta.10",
+            "13.0.0-rc.1",
+            "13.0.0",
+            "13.0.1-beta",
+        ]
+        .iter()
+        .map(|version| SystemVersion::from_string(*version))
+        .collect();
+        for pair in versions.windows(2) {
+            assert!(pair[0] < pair[1], "{} < {}", pair[0], pair[1]);
+        }
+        assert!(SystemVersion::Unknown < SystemVersion::from_string("1.0.0-alpha"));
+        assert!(SystemVersion::from_string("99.0.0") < SystemVersion::rolling(None::<String>));
+    }
+
+    /// Tests that custom versions compare their numbers by value.
+    ///
+    /// This test case ensures that two-part versions, which parse as
+    /// `SystemVersion::Custom`, order numerically and that equal strings
+    /// still compare equal.
+    #[test]
+    fn test_custom_ordering() {
+        let ubuntu = SystemVersion::from_string("22.04");
+        assert!(ubuntu >= SystemVersion::from_string("22.04"));
+        assert!(ubuntu > SystemVersion::from_string("9.10"));
+        assert!(ubuntu < SystemVersion::from_string("24.04"));
+        assert!(SystemVersion::custom("NT 6.1") < SystemVersion::custom("NT 10.0"));
+        assert_ne!(
+            SystemVersion::custom("22.4").cmp(&SystemVersion::custom("22.04")),
+            std::cmp::Ordering::Equal
+        );
+        assert_eq!(
+            compare_numeric_runs("22.04", "22.4"),
+            std::cmp::Ordering::Equal
+        );
+        assert!(SystemVersion::Unknown < ubuntu);
+    }
+
+    /// Tests that numbered custom versions compare with semantic ones by
+    /// their numbers.
+    ///
+    /// This test case ensures that `24.04.1` >= `22.04` and that a custom
+    /// version no longer orders above every semantic version.
+    #[test]
+    fn test_semantic_custom_ordering() {
+        let noble = SystemVersion::from_string("24.04.1");
+        let jammy = SystemVersion::from_string("22.04");
+        assert_eq!(noble, SystemVersion::Semantic(24, 4, 1));
+        assert_eq!(jammy, SystemVersion::Custom("22.04".to_string()));
+        assert!(noble >= jammy);
+        assert!(jammy < noble);
+        assert!(SystemVersion::semantic(22, 4, 0) < jammy);
+        assert!(SystemVersion::semantic(22, 4, 1) > jammy);
+        assert!(SystemVersion::custom("11") > SystemVersion::semantic(10, 15, 7));
+        assert!(SystemVersion::custom("2023.12-LTS") > SystemVersion::semantic(2023, 11, 9));
+        assert!(SystemVersion::semantic_pre(22, 4, 0, "beta") < jammy);
+        assert!(SystemVersion::custom("NT 10.0") > SystemVersion::semantic(99, 0, 0));
+        assert!(SystemVersion::custom(" x") > jammy);
+        assert!(SystemVersion::rolling(None::<String>) > jammy);
+
+        let mut versions = vec![
+            SystemVersion::custom("NT 10.0"),
+            noble.clone(),
+            SystemVersion::rolling(None::<String>),
+            jammy.clone(),
+            SystemVersion::from_string("20.04.6"),
+            SystemVersion::Unknown,
+        ];
+        versions.sort();
+        assert_eq!(
+            versions,
+            vec![
+                SystemVersion::Unknown,
+                SystemVersion::Semantic(20, 4, 6),
+                jammy,
+                noble,
+                SystemVersion::rolling(None::<String>),
+                SystemVersion::custom("NT 10.0"),
+            ]
+        );
+    }
+
+    /// Tests that patch numbers compare by value, not as text.
+    ///
+    /// This test case ensures that `10.15.21` > `10.15.7` and that two-digit
+    /// minor and major numbers order the same way.
+    #[test]
+    fn test_double_digit_ordering() {
+        let versions: Vec<SystemVersion> = ["9.9.9", "10.2.0", "10.15.7", "10.15.21", "11.0.0"]
+            .iter()
+            .map(|version| SystemVersion::from_string(*version))
+            .collect();
+        for pair in versions.windows(2) {
+            assert!(pair[0] < pair[1], "{} < {}", pair[0], pair[1]);
+        }
+        assert_eq!(
+            versions.iter().max(),
+            Some(&SystemVersion::semantic(11, 0, 0))
+        );
+    }
+
+    /// Tests that `SystemVersion::Unknown` orders before every other version.
+    #[test]
+    fn test_unknown_ordering() {
+        let mut versions = [
+            SystemVersion::custom("NT 10.0"),
+            SystemVersion::semantic(0, 0, 0),
+            SystemVersion::Unknown,
+            SystemVersion::rolling(None::<String>),
+        ];
+        versions.sort();
+        assert_eq!(versions[0], SystemVersion::Unknown);
+        assert_eq!(
+            SystemVersion::Unknown.cmp(&SystemVersion::Unknown),
+            std::cmp::Ordering::Equal
+        );
+        assert!(SystemVersion::Unknown < SystemVersion::custom(""));
+    }
+
+    /// Tests `at_least()` for semantic, pre-release, custom, unknown and
+    /// rolling versions.
+    #[test]
+    fn test_at_least() {
+        let catalina = SystemVersion::from_string("10.15.21");
+        assert!(catalina.at_least(10, 15));
+        assert!(catalina.at_least(10, 2));
+        assert!(catalina.at_least(9, 99));
+        assert!(!catalina.at_least(10, 16));
+        assert!(!catalina.at_least(11, 0));
+        assert!(!SystemVersion::from_string("14.0.0-beta").at_least(14, 0));
+        assert!(SystemVersion::from_string("14.1.0-beta").at_least(14, 0));
+        assert!(SystemVersion::from_string("14.0.1-beta").at_least(14, 0));
+
+        assert!(SystemVersion::from_string("22.04").at_least(22, 4));
+        assert!(!SystemVersion::from_string("22.04").at_least(22, 10));
+        assert!(SystemVersion::custom("11").at_least(11, 0));
+        assert!(SystemVersion::custom("2023.12-LTS").at_least(2023, 12));
+        assert!(!SystemVersion::custom("NT 10.0").at_least(0, 0));
+        assert!(!SystemVersion::Unknown.at_least(0, 0));
+        assert!(!SystemVersion::rolling(Some("2024.01")).at_least(0, 0));
+    }
+
     /// Tests the `semantic()` constructor.
     ///
     /// This test ensures that `SystemVersion::semantic()` creates a valid
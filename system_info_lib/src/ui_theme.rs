@@ -0,0 +1,353 @@
+//src/ui_theme.rs
+// spell-checker:ignore gsettings, gdbus, highcontrast, hkey, dword, winini
+
+#![cfg_attr(windows, allow(unsafe_code))]
+
+use std::fmt::{self, Display, Formatter};
+
+/// The colour theme of the desktop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum UiTheme {
+    /// Dark text on a light background.
+    Light,
+    /// Light text on a dark background.
+    Dark,
+    /// A high contrast accessibility theme, which takes precedence over
+    /// the light/dark preference.
+    HighContrast,
+}
+
+impl Display for UiTheme {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match *self {
+            UiTheme::Light => write!(f, "light"),
+            UiTheme::Dark => write!(f, "dark"),
+            UiTheme::HighContrast => write!(f, "high contrast"),
+        }
+    }
+}
+
+/// Returns the colour theme of the desktop the process runs in.
+///
+/// On Windows the `AppsUseLightTheme` registry value and the
+/// `SPI_GETHIGHCONTRAST` system parameter are read. On macOS
+/// `defaults read -g AppleInterfaceStyle` is queried, together with the
+/// "Increase contrast" accessibility setting. On Linux the
+/// `org.freedesktop.appearance` settings are read from the XDG desktop
+/// portal if a D-Bus session bus is available, falling back to `gsettings`.
+///
+/// # Returns
+///
+/// * `Option<UiTheme>` - The theme, or `None` without a graphical session.
+pub fn get() -> Option<UiTheme> {
+    imp::theme()
+}
+
+/// Maps the Windows theme settings.
+///
+/// `apps_use_light_theme` is the `AppsUseLightTheme` registry value, which
+/// is missing before Windows 10 1809; those versions only have a light theme.
+#[cfg_attr(not(windows), allow(dead_code))]
+fn from_windows(apps_use_light_theme: Option<u32>, high_contrast: bool) -> UiTheme {
+    if high_contrast {
+        UiTheme::HighContrast
+    } else if apps_use_light_theme == Some(0) {
+        UiTheme::Dark
+    } else {
+        UiTheme::Light
+    }
+}
+
+/// Maps the output of `defaults read -g AppleInterfaceStyle` and
+/// `defaults read com.apple.universalaccess increaseContrast`.
+///
+/// Both keys are absent unless the setting was enabled, in which case
+/// `defaults` fails and the output is `None`.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn from_macos(interface_style: Option<&str>, increase_contrast: Option<&str>) -> UiTheme {
+    if increase_contrast.map(str::trim) == Some("1") {
+        UiTheme::HighContrast
+    } else if interface_style.map(str::trim) == Some("Dark") {
+        UiTheme::Dark
+    } else {
+        UiTheme::Light
+    }
+}
+
+/// Extracts the value of a `gdbus call` reply to
+/// `org.freedesktop.portal.Settings.Read`, e.g. `(<<uint32 1>>,)`.
+#[cfg_attr(not(any(target_os = "linux", target_os = "android")), allow(dead_code))]
+fn parse_portal_reply(reply: &str) -> Option<u32> {
+    let (_, value) = reply.split_once("uint32")?;
+    let digits: String = value
+        .trim_start()
+        .chars()
+        .take_while(char::is_ascii_digit)
+        .collect();
+    digits.parse().ok()
+}
+
+/// Maps the `color-scheme` and `contrast` keys of the
+/// `org.freedesktop.appearance` portal namespace.
+///
+/// `color-scheme` is 0 for no preference, which applications render as a
+/// light theme, 1 for dark and 2 for light; `contrast` is 1 for high contrast.
+#[cfg_attr(not(any(target_os = "linux", target_os = "android")), allow(dead_code))]
+fn from_portal(color_scheme: Option<u32>, contrast: Option<u32>) -> Option<UiTheme> {
+    if contrast == Some(1) {
+        return Some(UiTheme::HighContrast);
+    }
+    match color_scheme? {
+        1 => Some(UiTheme::Dark),
+        _ => Some(UiTheme::Light),
+    }
+}
+
+/// Maps the output of `gsettings get org.gnome.desktop.interface color-scheme`
+/// (e.g. `'prefer-dark'`) and of
+/// `gsettings get org.gnome.desktop.a11y.interface high-contrast`.
+#[cfg_attr(not(any(target_os = "linux", target_os = "android")), allow(dead_code))]
+fn from_gsettings(color_scheme: Option<&str>, high_contrast: Option<&str>) -> Option<UiTheme> {
+    if high_contrast.map(str::trim) == Some("true") {
+        return Some(UiTheme::HighContrast);
+    }
+    match color_scheme?.trim().trim_matches('\'') {
+        "prefer-dark" => Some(UiTheme::Dark),
+        _ => Some(UiTheme::Light),
+    }
+}
+
+/// Runs `program`, returning its output on success.
+#[cfg(any(target_os = "linux", target_os = "android", target_os = "macos"))]
+fn run(program: &str, args: &[&str]) -> Option<String> {
+    use log::trace;
+    use std::process::Command;
+
+    match Command::new(program).args(args).output() {
+        Ok(output) if output.status.success() => {
+            Some(String::from_utf8_lossy(&output.stdout).into_owned())
+        }
+        Ok(output) => {
+            trace!("{} exited with {}", program, output.status);
+            None
+        }
+        Err(error) => {
+            trace!("Cannot run {}: {}", program, error);
+            None
+        }
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+mod imp {
+    use super::{run, UiTheme};
+    use std::env;
+
+    pub fn theme() -> Option<UiTheme> {
+        if env::var_os("DISPLAY").is_none() && env::var_os("WAYLAND_DISPLAY").is_none() {
+            return None;
+        }
+        portal().or_else(gsettings)
+    }
+
+    fn portal() -> Option<UiTheme> {
+        env::var_os("DBUS_SESSION_BUS_ADDRESS")?;
+        let read = |key: &str| {
+            run(
+                "gdbus",
+                &[
+                    "call",
+                    "--session",
+                    "--dest",
+                    "org.freedesktop.portal.Desktop",
+                    "--object-path",
+                    "/org/freedesktop/portal/desktop",
+                    "--method",
+                    "org.freedesktop.portal.Settings.Read",
+                    "org.freedesktop.appearance",
+                    key,
+                ],
+            )
+            .and_then(|reply| super::parse_portal_reply(&reply))
+        };
+        super::from_portal(read("color-scheme"), read("contrast"))
+    }
+
+    fn gsettings() -> Option<UiTheme> {
+        let color_scheme = run(
+            "gsettings",
+            &["get", "org.gnome.desktop.interface", "color-scheme"],
+        );
+        let high_contrast = run(
+            "gsettings",
+            &["get", "org.gnome.desktop.a11y.interface", "high-contrast"],
+        );
+        super::from_gsettings(color_scheme.as_deref(), high_contrast.as_deref())
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod imp {
+    use super::{run, UiTheme};
+
+    pub fn theme() -> Option<UiTheme> {
+        let interface_style = run("defaults", &["read", "-g", "AppleInterfaceStyle"]);
+        let increase_contrast = run(
+            "defaults",
+            &["read", "com.apple.universalaccess", "increaseContrast"],
+        );
+        Some(super::from_macos(
+            interface_style.as_deref(),
+            increase_contrast.as_deref(),
+        ))
+    }
+}
+
+#[cfg(windows)]
+mod imp {
+    use super::UiTheme;
+    use log::trace;
+    use std::ffi::OsStr;
+    use std::os::windows::ffi::OsStrExt;
+    use std::{mem, ptr};
+    use windows_sys::Win32::Foundation::ERROR_SUCCESS;
+    use windows_sys::Win32::System::Registry::{RegGetValueW, HKEY_CURRENT_USER, RRF_RT_REG_DWORD};
+    use windows_sys::Win32::UI::Accessibility::{HCF_HIGHCONTRASTON, HIGHCONTRASTW};
+    use windows_sys::Win32::UI::WindowsAndMessaging::{SystemParametersInfoW, SPI_GETHIGHCONTRAST};
+
+    pub fn theme() -> Option<UiTheme> {
+        Some(super::from_windows(apps_use_light_theme(), high_contrast()))
+    }
+
+    fn apps_use_light_theme() -> Option<u32> {
+        let sub_key = to_wide("Software\\Microsoft\\Windows\\CurrentVersion\\Themes\\Personalize");
+        let name = to_wide("AppsUseLightTheme");
+        let mut value: u32 = 0;
+        let mut size = mem::size_of::<u32>() as u32;
+        // SAFETY: sub_key and name are valid null-terminated wide strings,
+        // value is a writable DWORD and size holds its size in bytes.
+        let status = unsafe {
+            RegGetValueW(
+                HKEY_CURRENT_USER,
+                sub_key.as_ptr(),
+                name.as_ptr(),
+                RRF_RT_REG_DWORD,
+                ptr::null_mut(),
+                (&mut value as *mut u32).cast(),
+                &mut size,
+            )
+        };
+        if status == ERROR_SUCCESS {
+            Some(value)
+        } else {
+            trace!("RegGetValueW(AppsUseLightTheme) failed: {}", status);
+            None
+        }
+    }
+
+    fn high_contrast() -> bool {
+        let mut settings = HIGHCONTRASTW {
+            cbSize: mem::size_of::<HIGHCONTRASTW>() as u32,
+            dwFlags: 0,
+            lpszDefaultScheme: ptr::null_mut(),
+        };
+        // SAFETY: settings is a HIGHCONTRASTW with cbSize set, as required
+        // by SPI_GETHIGHCONTRAST.
+        let ok = unsafe {
+            SystemParametersInfoW(
+                SPI_GETHIGHCONTRAST,
+                settings.cbSize,
+                (&mut settings as *mut HIGHCONTRASTW).cast(),
+                0,
+            )
+        };
+        if ok == 0 {
+            trace!(
+                "SystemParametersInfoW(SPI_GETHIGHCONTRAST) failed: {:?}",
+                std::io::Error::last_os_error()
+            );
+            return false;
+        }
+        settings.dwFlags & HCF_HIGHCONTRASTON != 0
+    }
+
+    fn to_wide(value: &str) -> Vec<u16> {
+        OsStr::new(value).encode_wide().chain(Some(0)).collect()
+    }
+}
+
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    windows
+)))]
+mod imp {
+    use super::UiTheme;
+
+    pub fn theme() -> Option<UiTheme> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn windows_registry_and_high_contrast() {
+        assert_eq!(from_windows(Some(0), false), UiTheme::Dark);
+        assert_eq!(from_windows(Some(1), false), UiTheme::Light);
+        assert_eq!(from_windows(None, false), UiTheme::Light);
+        assert_eq!(from_windows(Some(0), true), UiTheme::HighContrast);
+    }
+
+    #[test]
+    fn macos_defaults_output() {
+        assert_eq!(from_macos(Some("Dark\n"), None), UiTheme::Dark);
+        // "The domain/default pair of (kCFPreferencesAnyApplication,
+        // AppleInterfaceStyle) does not exist" makes `defaults` fail
+        assert_eq!(from_macos(None, None), UiTheme::Light);
+        assert_eq!(
+            from_macos(Some("Dark\n"), Some("1\n")),
+            UiTheme::HighContrast
+        );
+        assert_eq!(from_macos(None, Some("0\n")), UiTheme::Light);
+    }
+
+    #[test]
+    fn portal_reply() {
+        assert_eq!(parse_portal_reply("(<<uint32 1>>,)\n"), Some(1));
+        assert_eq!(parse_portal_reply("(<uint32 2>,)\n"), Some(2));
+        assert_eq!(parse_portal_reply("(<<'prefer-dark'>>,)\n"), None);
+        assert_eq!(parse_portal_reply(""), None);
+    }
+
+    #[test]
+    fn portal_appearance_settings() {
+        assert_eq!(from_portal(Some(1), Some(0)), Some(UiTheme::Dark));
+        assert_eq!(from_portal(Some(2), None), Some(UiTheme::Light));
+        assert_eq!(from_portal(Some(0), None), Some(UiTheme::Light));
+        assert_eq!(from_portal(Some(1), Some(1)), Some(UiTheme::HighContrast));
+        assert_eq!(from_portal(None, None), None);
+    }
+
+    #[test]
+    fn gsettings_output() {
+        assert_eq!(
+            from_gsettings(Some("'prefer-dark'\n"), Some("false\n")),
+            Some(UiTheme::Dark)
+        );
+        assert_eq!(
+            from_gsettings(Some("'default'\n"), None),
+            Some(UiTheme::Light)
+        );
+        assert_eq!(
+            from_gsettings(Some("'default'\n"), Some("true\n")),
+            Some(UiTheme::HighContrast)
+        );
+        assert_eq!(from_gsettings(None, None), None);
+    }
+}
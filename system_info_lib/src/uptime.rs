@@ -0,0 +1,236 @@
+//src/uptime.rs
+// spell-checker:ignore boottime, timeval, kstat
+
+// `sysctl`, `clock_gettime` and `GetTickCount64` are foreign functions
+#![cfg_attr(
+    any(
+        target_os = "macos",
+        target_os = "freebsd",
+        target_os = "openbsd",
+        target_os = "netbsd",
+        target_os = "dragonfly",
+        target_os = "redox",
+        windows
+    ),
+    allow(unsafe_code)
+)]
+
+use std::time::Duration;
+
+/// Returns how long the system has been running.
+///
+/// Linux and Android parse `/proc/uptime`. macOS and the BSDs subtract the
+/// `kern.boottime` sysctl from the current time, illumos does the same with
+/// the `unix:0:system_misc:boot_time` kstat. Windows calls `GetTickCount64`
+/// and Redox reads the monotonic clock, which starts at boot.
+///
+/// # Returns
+///
+/// * `Option<Duration>` - The uptime, if it can be determined.
+pub fn get() -> Option<Duration> {
+    imp::uptime()
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+mod imp {
+    use log::error;
+    use std::time::Duration;
+
+    pub fn uptime() -> Option<Duration> {
+        match std::fs::read_to_string("/proc/uptime") {
+            Ok(content) => super::parse_proc_uptime(&content),
+            Err(e) => {
+                error!("Failed to read /proc/uptime: {:?}", e);
+                None
+            }
+        }
+    }
+}
+
+#[cfg(any(
+    target_os = "macos",
+    target_os = "freebsd",
+    target_os = "openbsd",
+    target_os = "netbsd",
+    target_os = "dragonfly"
+))]
+mod imp {
+    use log::error;
+    use std::{
+        mem::{self, MaybeUninit},
+        ptr,
+        time::{Duration, SystemTime},
+    };
+
+    pub fn uptime() -> Option<Duration> {
+        let mut mib = [libc::CTL_KERN, libc::KERN_BOOTTIME];
+        let mut boot_time = MaybeUninit::<libc::timeval>::uninit();
+        let mut size = mem::size_of::<libc::timeval>();
+        if unsafe {
+            libc::sysctl(
+                mib.as_mut_ptr(),
+                mib.len() as _,
+                boot_time.as_mut_ptr().cast(),
+                &mut size,
+                ptr::null_mut(),
+                0,
+            )
+        } != 0
+        {
+            error!(
+                "sysctl(kern.boottime) failed: {:?}",
+                std::io::Error::last_os_error()
+            );
+            return None;
+        }
+        let boot_time = unsafe { boot_time.assume_init() };
+        let boot_time = Duration::new(
+            u64::try_from(boot_time.tv_sec).ok()?,
+            u32::try_from(boot_time.tv_usec).ok()? * 1000,
+        );
+        super::since_boot(boot_time, SystemTime::now())
+    }
+}
+
+#[cfg(target_os = "illumos")]
+mod imp {
+    use log::error;
+    use std::{
+        process::Command,
+        time::{Duration, SystemTime},
+    };
+
+    pub fn uptime() -> Option<Duration> {
+        let output = Command::new("kstat")
+            .args(["-p", "unix:0:system_misc:boot_time"])
+            .output()
+            .map_err(|e| error!("Failed to invoke 'kstat': {:?}", e))
+            .ok()?;
+        if !output.status.success() {
+            error!("'kstat' invocation failed: {:?}", output);
+            return None;
+        }
+        // `unix:0:system_misc:boot_time<TAB>1700000000`
+        let seconds = String::from_utf8_lossy(&output.stdout)
+            .split_whitespace()
+            .nth(1)?
+            .parse()
+            .ok()?;
+        super::since_boot(Duration::from_secs(seconds), SystemTime::now())
+    }
+}
+
+#[cfg(target_os = "redox")]
+mod imp {
+    use log::error;
+    use std::{mem::MaybeUninit, time::Duration};
+
+    pub fn uptime() -> Option<Duration> {
+        let mut now = MaybeUninit::<libc::timespec>::uninit();
+        if unsafe { libc::clock_gettime(libc::CLOCK_MONOTONIC, now.as_mut_ptr()) } != 0 {
+            error!(
+                "clock_gettime(CLOCK_MONOTONIC) failed: {:?}",
+                std::io::Error::last_os_error()
+            );
+            return None;
+        }
+        let now = unsafe { now.assume_init() };
+        Some(Duration::new(
+            u64::try_from(now.tv_sec).ok()?,
+            u32::try_from(now.tv_nsec).ok()?,
+        ))
+    }
+}
+
+#[cfg(windows)]
+mod imp {
+    use std::time::Duration;
+    use windows_sys::Win32::System::SystemInformation::GetTickCount64;
+
+    pub fn uptime() -> Option<Duration> {
+        Some(Duration::from_millis(unsafe { GetTickCount64() }))
+    }
+}
+
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "freebsd",
+    target_os = "openbsd",
+    target_os = "netbsd",
+    target_os = "dragonfly",
+    target_os = "illumos",
+    target_os = "redox",
+    windows
+)))]
+mod imp {
+    use std::time::Duration;
+
+    pub fn uptime() -> Option<Duration> {
+        None
+    }
+}
+
+/// Parses the first field of `/proc/uptime`, the seconds since boot.
+#[cfg(any(target_os = "linux", target_os = "android", test))]
+fn parse_proc_uptime(content: &str) -> Option<Duration> {
+    let seconds: f64 = content.split_whitespace().next()?.parse().ok()?;
+    if seconds.is_finite() && seconds >= 0.0 {
+        Some(Duration::from_secs_f64(seconds))
+    } else {
+        None
+    }
+}
+
+/// Returns the time elapsed between `boot_time` (since the Unix epoch) and `now`.
+///
+/// `None` if the clock is set before the boot time.
+#[cfg(any(
+    target_os = "macos",
+    target_os = "freebsd",
+    target_os = "openbsd",
+    target_os = "netbsd",
+    target_os = "dragonfly",
+    target_os = "illumos",
+    test
+))]
+fn since_boot(boot_time: Duration, now: std::time::SystemTime) -> Option<Duration> {
+    let now = now.duration_since(std::time::UNIX_EPOCH).ok()?;
+    now.checked_sub(boot_time)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::time::UNIX_EPOCH;
+
+    #[test]
+    #[cfg(any(unix, windows))]
+    fn uptime_positive() {
+        let uptime = get().expect("uptime failed");
+        assert!(uptime > Duration::from_secs(0));
+    }
+
+    #[test]
+    fn proc_uptime() {
+        assert_eq!(
+            parse_proc_uptime("350735.47 234388.90\n"),
+            Some(Duration::from_millis(350_735_470))
+        );
+        assert_eq!(parse_proc_uptime(""), None);
+        assert_eq!(parse_proc_uptime("-1.0 0.0"), None);
+        assert_eq!(parse_proc_uptime("uptime"), None);
+    }
+
+    #[test]
+    fn boot_time() {
+        let now = UNIX_EPOCH + Duration::from_secs(1_700_000_600);
+        assert_eq!(
+            since_boot(Duration::from_secs(1_700_000_000), now),
+            Some(Duration::from_secs(600))
+        );
+        assert_eq!(since_boot(Duration::from_secs(1_800_000_000), now), None);
+    }
+}
@@ -3,12 +3,14 @@ mod winapi;
 
 use log::trace;
 
-use crate::{kernel_version, Info};
+use crate::{hostname, kernel_version, uptime, Info};
 
 pub fn current_platform() -> Info {
     trace!("windows::current_platform is called");
     let mut info = winapi::get();
     info.kernel_version = kernel_version::get();
+    info.hostname = hostname::get();
+    info.uptime = uptime::get();
     trace!("Returning {:?}", info);
     info
 }
@@ -26,4 +28,17 @@ mod windows_tests {
         assert_eq!(Type::Windows, version.system_type());
         assert!(version.edition().is_some());
     }
+
+    #[test]
+    fn windows_11_edition() {
+        let info = current_platform();
+        assert!(info.is_workstation().is_some());
+        let edition = info.edition().expect("edition() failed");
+        assert_eq!(
+            info.is_windows_11(),
+            edition.starts_with("Windows 11"),
+            "{}",
+            edition
+        );
+    }
 }
@@ -2,7 +2,7 @@
 // spell-checker:ignore dword, minwindef, ntdef, ntdll, ntstatus, osversioninfoex, osversioninfoexa
 // spell-checker:ignore osversioninfoexw, serverr, sysinfoapi, winnt, winuser, pbool, libloaderapi
 // spell-checker:ignore lpcstr, processthreadsapi, farproc, lstatus, wchar, lpbyte, hkey, winerror
-// spell-checker:ignore osstr, winreg
+// spell-checker:ignore osstr, winreg, ubr
 
 #![allow(unsafe_code)]
 
@@ -17,7 +17,10 @@ use windows_sys::Win32::{
     Foundation::{ERROR_SUCCESS, FARPROC, NTSTATUS, STATUS_SUCCESS},
     System::{
         LibraryLoader::{GetModuleHandleA, GetProcAddress},
-        Registry::{RegOpenKeyExW, RegQueryValueExW, HKEY_LOCAL_MACHINE, KEY_READ, REG_SZ},
+        Registry::{
+            RegCloseKey, RegOpenKeyExW, RegQueryValueExW, HKEY, HKEY_LOCAL_MACHINE, KEY_READ,
+            REG_DWORD, REG_SZ,
+        },
         SystemInformation::{
             GetNativeSystemInfo, GetSystemInfo, PROCESSOR_ARCHITECTURE_AMD64,
             PROCESSOR_ARCHITECTURE_ARM, PROCESSOR_ARCHITECTURE_IA64, PROCESSOR_ARCHITECTURE_INTEL,
@@ -28,7 +31,11 @@ use windows_sys::Win32::{
     UI::WindowsAndMessaging::{GetSystemMetrics, SM_SERVERR2},
 };
 
-use crate::{system_info::Info, system_os::Type, SystemVersion};
+use crate::{
+    system_info::{Info, WINDOWS_11_FIRST_BUILD},
+    system_os::Type,
+    SystemVersion,
+};
 
 #[cfg(target_arch = "x86")]
 #[allow(clippy::upper_case_acronyms)]
@@ -38,32 +45,57 @@ type OSVERSIONINFOEX = windows_sys::Win32::System::SystemInformation::OSVERSIONI
 #[allow(clippy::upper_case_acronyms)]
 type OSVERSIONINFOEX = windows_sys::Win32::System::SystemInformation::OSVERSIONINFOEXW;
 
+/// Registry key describing the installed Windows release.
+const CURRENT_VERSION_KEY: &str = "SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion";
+
 pub fn get() -> Info {
-    let (version, edition) = version();
+    let (version, edition, display_version, update_build_revision) = version();
     let native_system_info = native_system_info();
 
     Info {
         system_type: Type::Windows,
         version,
         edition,
+        display_version,
+        update_build_revision,
+        workstation: version_info().map(|info| is_workstation(&info)),
         bit_depth: bitness(),
         architecture: architecture(native_system_info),
         ..Default::default()
     }
 }
 
-fn version() -> (SystemVersion, Option<String>) {
-    match version_info() {
-        None => (SystemVersion::Unknown, None),
-        Some(v) => (
-            SystemVersion::Semantic(
-                v.dwMajorVersion as u64,
-                v.dwMinorVersion as u64,
-                v.dwBuildNumber as u64,
-            ),
-            product_name(&v).or_else(|| edition(&v)),
-        ),
+// Returns the version, edition, display version (e.g. `23H2`) and update build revision.
+//
+// `CurrentBuildNumber` from the registry takes precedence over the build reported by
+// RtlGetVersion. `UBR` is reported separately so the version stays semantic.
+fn version() -> (SystemVersion, Option<String>, Option<String>, Option<u32>) {
+    let mut info = match version_info() {
+        None => return (SystemVersion::Unknown, None, None, None),
+        Some(v) => v,
+    };
+    let key = RegistryKey::open_local_machine(CURRENT_VERSION_KEY);
+    let key = key.as_ref();
+    if let Some(build) = key
+        .and_then(|k| k.string("CurrentBuildNumber"))
+        .and_then(|b| b.parse().ok())
+    {
+        info.dwBuildNumber = build;
     }
+    let ubr = key.and_then(|k| k.dword("UBR"));
+    let display_version = key.and_then(|k| k.string("DisplayVersion"));
+
+    (
+        SystemVersion::Semantic(
+            info.dwMajorVersion as u64,
+            info.dwMinorVersion as u64,
+            info.dwBuildNumber as u64,
+        ),
+        key.and_then(|k| product_name(k, &info))
+            .or_else(|| edition(&info)),
+        display_version,
+        ubr,
+    )
 }
 
 // According to https://learn.microsoft.com/en-us/windows/win32/api/sysinfoapi/ns-sysinfoapi-system_info
@@ -158,83 +190,148 @@ fn version_info() -> Option<OSVERSIONINFOEX> {
     }
 }
 
-fn product_name(info: &OSVERSIONINFOEX) -> Option<String> {
-    let sub_key = to_wide("SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion");
-    let mut key = Default::default();
-    // SAFETY: sub_key.as_ptr() is a valid null-terminated wide string,
-    // key is a valid mutable pointer to receive the opened key handle.
-    if unsafe { RegOpenKeyExW(HKEY_LOCAL_MACHINE, sub_key.as_ptr(), 0, KEY_READ, &mut key) }
-        != ERROR_SUCCESS
-        || key == 0
-    {
-        log::error!("RegOpenKeyExW(HKEY_LOCAL_MACHINE, ...) failed");
-        return None;
-    }
-
-    let is_win_11 = info.dwMajorVersion == 10 && info.dwBuildNumber >= 22000;
-
-    // Get size of the data.
-    let name = to_wide(if is_win_11 {
+fn product_name(key: &RegistryKey, info: &OSVERSIONINFOEX) -> Option<String> {
+    let is_win_11 = is_windows_11(info);
+    let value = key.string(if is_win_11 {
         "EditionID"
     } else {
         "ProductName"
-    });
-    let mut data_type = 0;
-    let mut data_size = 0;
-    // SAFETY: key is a valid registry key handle (checked above),
-    // name.as_ptr() is a valid null-terminated wide string.
-    // First call queries the size without reading data.
-    if unsafe {
-        RegQueryValueExW(
-            key,
-            name.as_ptr(),
-            ptr::null_mut(),
-            &mut data_type,
-            ptr::null_mut(),
-            &mut data_size,
-        )
-    } != ERROR_SUCCESS
-        || data_type != REG_SZ
-        || data_size == 0
-        || data_size % 2 != 0
-    {
-        log::error!("RegQueryValueExW failed");
-        return None;
+    })?;
+    Some(product_name_for_build(&value, is_win_11))
+}
+
+// Windows 11 still reports "Windows 10" in `ProductName`, so its name is built from `EditionID`.
+fn product_name_for_build(value: &str, is_win_11: bool) -> String {
+    if is_win_11 {
+        format!("Windows 11 {}", value)
+    } else {
+        value.to_string()
     }
+}
 
-    // Get the data.
-    let mut data = vec![0u16; data_size as usize / 2];
-    // SAFETY: key is valid, name.as_ptr() is valid, data buffer is properly sized
-    // based on the size returned by the first RegQueryValueExW call.
-    if unsafe {
-        RegQueryValueExW(
-            key,
-            name.as_ptr(),
-            ptr::null_mut(),
-            ptr::null_mut(),
-            data.as_mut_ptr().cast(),
-            &mut data_size,
-        )
-    } != ERROR_SUCCESS
-        || data_size as usize != data.len() * 2
-    {
-        return None;
+// Windows Server 2025 shares the Windows 11 builds, so the product type decides.
+fn is_windows_11(info: &OSVERSIONINFOEX) -> bool {
+    is_workstation(info) && is_windows_11_build(info.dwMajorVersion, info.dwBuildNumber)
+}
+
+fn is_windows_11_build(major: u32, build: u32) -> bool {
+    major == 10 && u64::from(build) >= WINDOWS_11_FIRST_BUILD
+}
+
+fn is_workstation(info: &OSVERSIONINFOEX) -> bool {
+    u32::from(info.wProductType) == VER_NT_WORKSTATION
+}
+
+/// An open registry key, closed on drop.
+struct RegistryKey(HKEY);
+
+impl RegistryKey {
+    fn open_local_machine(sub_key: &str) -> Option<Self> {
+        let sub_key = to_wide(sub_key);
+        let mut key = Default::default();
+        // SAFETY: sub_key.as_ptr() is a valid null-terminated wide string,
+        // key is a valid mutable pointer to receive the opened key handle.
+        if unsafe { RegOpenKeyExW(HKEY_LOCAL_MACHINE, sub_key.as_ptr(), 0, KEY_READ, &mut key) }
+            != ERROR_SUCCESS
+            || key == 0
+        {
+            log::error!("RegOpenKeyExW(HKEY_LOCAL_MACHINE, ...) failed");
+            return None;
+        }
+        Some(Self(key))
     }
 
-    // If the data has the REG_SZ, REG_MULTI_SZ or REG_EXPAND_SZ type, the string may not have been
-    // stored with the proper terminating null characters.
-    if let Some(0) = data.last() {
-        data.pop();
+    // Reads a REG_SZ value, `None` if it is missing or has another type.
+    fn string(&self, name: &str) -> Option<String> {
+        // Get size of the data.
+        let name = to_wide(name);
+        let mut data_type = 0;
+        let mut data_size = 0;
+        // SAFETY: self.0 is a valid registry key handle (checked when opened),
+        // name.as_ptr() is a valid null-terminated wide string.
+        // First call queries the size without reading data.
+        if unsafe {
+            RegQueryValueExW(
+                self.0,
+                name.as_ptr(),
+                ptr::null_mut(),
+                &mut data_type,
+                ptr::null_mut(),
+                &mut data_size,
+            )
+        } != ERROR_SUCCESS
+            || data_type != REG_SZ
+            || data_size == 0
+            || data_size % 2 != 0
+        {
+            // Missing values are expected, e.g. `DisplayVersion` before Windows 10 20H2.
+            log::debug!("RegQueryValueExW failed");
+            return None;
+        }
+
+        // Get the data.
+        let mut data = vec![0u16; data_size as usize / 2];
+        // SAFETY: self.0 is valid, name.as_ptr() is valid, data buffer is properly sized
+        // based on the size returned by the first RegQueryValueExW call.
+        if unsafe {
+            RegQueryValueExW(
+                self.0,
+                name.as_ptr(),
+                ptr::null_mut(),
+                ptr::null_mut(),
+                data.as_mut_ptr().cast(),
+                &mut data_size,
+            )
+        } != ERROR_SUCCESS
+            || data_size as usize != data.len() * 2
+        {
+            return None;
+        }
+
+        // If the data has the REG_SZ, REG_MULTI_SZ or REG_EXPAND_SZ type, the string may not
+        // have been stored with the proper terminating null characters.
+        if let Some(0) = data.last() {
+            data.pop();
+        }
+
+        Some(
+            OsString::from_wide(data.as_slice())
+                .to_string_lossy()
+                .into_owned(),
+        )
     }
 
-    let value = OsString::from_wide(data.as_slice())
-        .to_string_lossy()
-        .into_owned();
+    // Reads a REG_DWORD value.
+    fn dword(&self, name: &str) -> Option<u32> {
+        let name = to_wide(name);
+        let mut data_type = 0;
+        let mut data = 0u32;
+        let mut data_size = mem::size_of::<u32>() as u32;
+        // SAFETY: self.0 is valid, name.as_ptr() is a valid null-terminated wide string and
+        // data is a 4-byte buffer whose size is passed in data_size.
+        if unsafe {
+            RegQueryValueExW(
+                self.0,
+                name.as_ptr(),
+                ptr::null_mut(),
+                &mut data_type,
+                (&mut data as *mut u32).cast(),
+                &mut data_size,
+            )
+        } != ERROR_SUCCESS
+            || data_type != REG_DWORD
+            || data_size as usize != mem::size_of::<u32>()
+        {
+            return None;
+        }
+        Some(data)
+    }
+}
 
-    if is_win_11 {
-        Some(format!("Windows 11 {}", value))
-    } else {
-        Some(value)
+impl Drop for RegistryKey {
+    fn drop(&mut self) {
+        // SAFETY: self.0 was opened by RegOpenKeyExW and is closed exactly once.
+        unsafe { RegCloseKey(self.0) };
     }
 }
 
@@ -252,7 +349,7 @@ fn edition(version_info: &OSVERSIONINFOEX) -> Option<String> {
     ) {
         // Windows 10.
         (10, 0, VER_NT_WORKSTATION) => {
-            if version_info.dwBuildNumber >= 22000 {
+            if is_windows_11(version_info) {
                 Some("Windows 11")
             } else {
                 Some("Windows 10")
@@ -379,6 +476,60 @@ mod tests {
             let edition = edition(&info).unwrap();
             assert_eq!(edition, expected_edition);
         }
+
+        info.dwMajorVersion = 10;
+        info.dwMinorVersion = 0;
+        info.wProductType = VER_NT_WORKSTATION as u8;
+        info.dwBuildNumber = 19045;
+        assert_eq!(edition(&info).unwrap(), "Windows 10");
+        info.dwBuildNumber = 22631;
+        assert_eq!(edition(&info).unwrap(), "Windows 11");
+        assert!(is_windows_11(&info));
+
+        // Windows Server 2025
+        info.dwBuildNumber = 26100;
+        info.wProductType = 3;
+        assert!(!is_windows_11(&info));
+    }
+
+    #[test]
+    fn windows_11_build_mapping() {
+        let test_data = [
+            (10, 10240, false),
+            (10, 19045, false),
+            (10, 21999, false),
+            (10, 22000, true),
+            (10, 22631, true),
+            (10, 26100, true),
+            (6, 22000, false),
+        ];
+
+        for &(major, build, expected) in &test_data {
+            assert_eq!(is_windows_11_build(major, build), expected, "{}", build);
+        }
+    }
+
+    #[test]
+    fn product_name_mapping() {
+        assert_eq!(
+            product_name_for_build("Professional", true),
+            "Windows 11 Professional"
+        );
+        assert_eq!(
+            product_name_for_build("Windows 10 Pro", false),
+            "Windows 10 Pro"
+        );
+    }
+
+    #[test]
+    fn registry_current_version() {
+        let key = RegistryKey::open_local_machine(CURRENT_VERSION_KEY).expect("open failed");
+        let build = key
+            .string("CurrentBuildNumber")
+            .expect("CurrentBuildNumber");
+        assert!(build.parse::<u32>().is_ok(), "{}", build);
+        assert_eq!(key.dword("CurrentBuildNumber"), None);
+        assert_eq!(key.string("NoSuchValue"), None);
     }
 
     #[test]
@@ -442,7 +593,8 @@ mod tests {
     #[test]
     fn get_product_name() {
         let version = version_info().expect("version_info() failed");
-        let edition = product_name(&version).expect("edition() failed");
+        let key = RegistryKey::open_local_machine(CURRENT_VERSION_KEY).expect("open failed");
+        let edition = product_name(&key, &version).expect("edition() failed");
         assert!(!edition.is_empty());
     }
 
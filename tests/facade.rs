@@ -0,0 +1,50 @@
+//! Tests that every symbol re-exported by the facade resolves and works.
+//!
+//! A re-export that no longer matches `system_info_lib` or `gpu_info` fails
+//! to compile here.
+
+use sysinfo_utils::system::{
+    self, BitDepth, Info, InfoBuilder, InfoExt, SystemVersion, SystemVersionExt, Type,
+};
+
+#[test]
+fn test_system_reexports() {
+    let info: Info = system::get();
+    assert_ne!(info.system_type(), Type::Unknown);
+
+    let built: Info = InfoBuilder::new()
+        .system_type(Type::Ubuntu)
+        .version(SystemVersion::semantic(24, 4, 0))
+        .bit_depth(BitDepth::X64)
+        .build();
+    assert!(InfoExt::is_linux(&built));
+    assert!(built.is_64bit());
+    assert_eq!(SystemVersionExt::major(built.version()), Some(24));
+    assert_eq!(Info::builder().build(), InfoBuilder::new().build());
+}
+
+#[test]
+fn test_crate_reexports() {
+    assert_eq!(
+        sysinfo_utils::system_info_lib::get().system_type(),
+        system::get().system_type()
+    );
+}
+
+#[cfg(feature = "full")]
+#[test]
+fn test_gpu_reexports() {
+    use sysinfo_utils::gpu::{self, GpuInfo, GpuManager};
+
+    let gpu: GpuInfo = gpu::get();
+    assert!(!gpu.format_name_gpu().is_empty());
+    #[cfg(any(target_os = "linux", target_os = "macos", target_os = "windows"))]
+    {
+        let gpus: Vec<GpuInfo> = gpu::get_all();
+        assert_eq!(gpus.len(), sysinfo_utils::gpu_info::get_all().len());
+    }
+    let manager = GpuManager::from_iter(vec![GpuInfo::builder().name("RTX 4090").build()]);
+    assert_eq!(manager.gpu_count(), 1);
+
+    let _: sysinfo_utils::system_cli::ColorChoice = Default::default();
+}